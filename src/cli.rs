@@ -1,7 +1,20 @@
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, FromArgMatches, Parser, Subcommand};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-#[derive(Parser, Debug)]
+use crate::config::ProjectConfig;
+use crate::error::DbtLineageError;
+use crate::graph::filter::{resolve_node_types, TestFilter};
+use crate::graph::impact::ImpactSeverity;
+use crate::graph::sort::NodeSort;
+use crate::graph::types::NodeType;
+use crate::parser::columns::IdentifierCase;
+use crate::parser::discovery::{parse_seed_file_types, DEFAULT_SEED_FILE_TYPES};
+use crate::render::json::JsonShape;
+use crate::render::{EdgeStyle, LabelDetail};
+
+#[derive(Parser, Debug, Clone)]
 #[command(name = "dbt-lineage", about = "Visualize dbt model lineage")]
 pub struct Cli {
     #[command(subcommand)]
@@ -26,36 +39,434 @@ pub struct Cli {
     #[arg(short = 'i', long)]
     pub interactive: bool,
 
-    /// Output format: ascii (default), dot, json, mermaid, svg, html
+    /// Output format: ascii (default), dot, json, mermaid, svg, html, cytoscape, csv, tree
     #[arg(short = 'o', long, default_value = "ascii")]
     pub output: OutputFormat,
 
-    /// Include test nodes
+    /// File prefix for `-o csv`: writes <PREFIX>_nodes.csv and <PREFIX>_edges.csv
+    #[arg(long, value_name = "PREFIX")]
+    pub output_file: Option<PathBuf>,
+
+    /// Also keep these node types: seed, snapshot, test, exposure (comma-separated;
+    /// models, sources and phantoms are kept by default)
+    #[arg(long, value_name = "TYPES")]
+    pub include: Option<String>,
+
+    /// Drop these node types: model, source, seed, snapshot, test, exposure, phantom
+    /// (comma-separated); wins over --include
+    #[arg(long, value_name = "TYPES")]
+    pub exclude: Option<String>,
+
+    /// Include test nodes (deprecated: use --include test)
     #[arg(long)]
     pub include_tests: bool,
 
-    /// Include seed nodes
+    /// When tests are included, keep only singular (hand-written SQL) or generic
+    /// (schema YAML) tests
+    #[arg(long, value_enum, default_value = "all")]
+    pub tests: TestFilter,
+
+    /// Include seed nodes (deprecated: use --include seed)
     #[arg(long)]
     pub include_seeds: bool,
 
-    /// Include snapshot nodes
+    /// Include snapshot nodes (deprecated: use --include snapshot)
     #[arg(long)]
     pub include_snapshots: bool,
 
-    /// Include exposure nodes
+    /// Include exposure nodes (deprecated: use --include exposure)
     #[arg(long)]
     pub include_exposures: bool,
 
-    /// Selector expression: tag:X, path:Y, or model name (comma-separated)
+    /// Selector expression: tag:X, path:Y, config.materialized:Z, schema:S, or model name;
+    /// commas intersect, spaces union (`tag:a,tag:b orders`). `@FILE` or `-` (stdin) reads one
+    /// selector per line
     #[arg(short = 's', long)]
     pub select: Option<String>,
 
-    /// Use manifest.json instead of parsing SQL (path to manifest file or directory containing target/manifest.json)
+    /// Use a named selector from the project's selectors.yml instead of --select
+    #[arg(long, value_name = "NAME", conflicts_with = "select")]
+    pub selector: Option<String>,
+
+    /// Only keep nodes with these materializations, e.g. incremental,table (comma-separated)
+    #[arg(long, value_name = "TYPES")]
+    pub materialized: Option<String>,
+
+    /// Only keep these edge types: ref, source, test, exposure (comma-separated)
+    #[arg(long, value_name = "TYPES")]
+    pub edge_types: Option<String>,
+
+    /// Hide these edge types: ref, source, test, exposure (comma-separated)
+    #[arg(long, value_name = "TYPES")]
+    pub hide_edge_types: Option<String>,
+
+    /// Use manifest.json instead of parsing SQL (path to manifest file or directory containing
+    /// target/manifest.json); repeat to merge several projects' manifests
+    #[arg(long, value_name = "PATH")]
+    pub manifest: Vec<PathBuf>,
+
+    /// Also read dbt's compiled SQL (target/compiled) for columns and macro-generated dependencies
+    #[arg(long, conflicts_with = "manifest")]
+    pub use_compiled: bool,
+
+    /// Also parse files matching this glob, relative to the project directory, as
+    /// models (e.g. 'transform/**/*.sql'); repeatable
+    #[arg(long, value_name = "GLOB", conflicts_with = "manifest")]
+    pub model_glob: Vec<String>,
+
+    /// Also parse files matching this glob as snapshots; repeatable
+    #[arg(long, value_name = "GLOB", conflicts_with = "manifest")]
+    pub snapshot_glob: Vec<String>,
+
+    /// Also parse files matching this glob as singular tests; repeatable
+    #[arg(long, value_name = "GLOB", conflicts_with = "manifest")]
+    pub test_glob: Vec<String>,
+
+    /// File extensions of seeds under the seed paths (comma-separated, e.g.
+    /// csv,json,parquet) [default: csv]
+    #[arg(long, value_name = "TYPES", conflicts_with = "manifest")]
+    pub seed_file_types: Option<String>,
+
+    /// Load column names and types from catalog.json (file, or directory containing
+    /// target/catalog.json); detected automatically next to --manifest
     #[arg(long)]
-    pub manifest: Option<PathBuf>,
+    pub catalog: Option<PathBuf>,
+
+    /// Outline nodes by their last run status from target/run_results.json (dot, svg, html)
+    #[arg(long)]
+    pub with_status: bool,
+
+    /// Read run status from this run_results.json instead of target/run_results.json
+    /// (TUI and --with-status), e.g. another target's artifacts
+    #[arg(long, value_name = "PATH")]
+    pub run_results: Option<PathBuf>,
+
+    /// YAML file mapping node and edge types to hex colors for dot, svg, mermaid
+    /// and html output
+    #[arg(long, value_name = "PATH")]
+    pub palette: Option<PathBuf>,
+
+    /// Append detail to node labels in diagrams and the TUI: columns (`(12 cols)`),
+    /// materialization or none
+    #[arg(long, value_enum, default_value = "none")]
+    pub label_detail: LabelDetail,
+
+    /// Cut node names longer than N characters with `…` in ascii, tree, dot,
+    /// mermaid and svg output and in TUI node boxes; tooltips and the TUI detail
+    /// panel keep the full name
+    #[arg(long, value_name = "N")]
+    pub max_label_len: Option<usize>,
+
+    /// Wrap layers holding more than N nodes into several side-by-side columns in
+    /// ascii, svg and html output and in the TUI, so a source feeding hundreds of
+    /// models stays readable
+    #[arg(long, value_name = "N")]
+    pub max_layer_width: Option<usize>,
+
+    /// Replace node names with stable hashed ones (e.g. `model_3fa9c1`) and drop
+    /// descriptions, file paths, columns and tags, to share a graph's shape
+    #[arg(long, conflicts_with_all = ["interactive", "dump_manifest"])]
+    pub anonymize: bool,
+
+    /// With --anonymize, write a JSON map from the new names back to the unique_ids
+    #[arg(long, value_name = "PATH", requires = "anonymize")]
+    pub anonymize_map: Option<PathBuf>,
+
+    /// How svg and html edges are drawn: straight, ortho (right angles) or curved.
+    /// Edges that skip layers are routed between the nodes in those layers
+    #[arg(long, value_enum, default_value = "curved")]
+    pub edge_style: EdgeStyle,
+
+    /// Layout of -o json: flat (nodes and edges lists) or tree (nested
+    /// `{ node, children }` objects walking out from MODEL, honoring -u/-d)
+    #[arg(long, value_enum, default_value = "flat")]
+    pub json_shape: JsonShape,
+
+    /// Order nodes in the TUI node list (within groups) and in exports: name, type,
+    /// downstream or status (default: graph order)
+    #[arg(long, value_enum)]
+    pub sort: Option<NodeSort>,
+
+    /// Only keep nodes whose files were changed by commits since this date (any date
+    /// `git log --since` accepts, e.g. 2024-05-01 or "2 weeks ago")
+    #[arg(long, value_name = "DATE")]
+    pub changed_since: Option<String>,
+
+    /// With --changed-since, also keep nodes up to N hops up- or downstream of a changed node
+    #[arg(
+        long,
+        value_name = "N",
+        default_value = "0",
+        requires = "changed_since"
+    )]
+    pub changed_neighbors: usize,
+
+    /// Drop nodes left without any edge after filtering (the --model focus and
+    /// nodes selected by name are kept)
+    #[arg(long)]
+    pub trim_orphans: bool,
+
+    /// Replace all nodes in this directory (or configured group) with one meta-node (repeatable)
+    #[arg(long, value_name = "DIR")]
+    pub collapse_group: Vec<String>,
+
+    /// Write the unfiltered graph as a minimal manifest.json to FILE (`-` for stdout) and exit
+    #[arg(long, value_name = "FILE")]
+    pub dump_manifest: Option<PathBuf>,
+
+    /// Project name used to expand unique_ids in --dump-manifest (default: name in dbt_project.yml)
+    #[arg(long, value_name = "NAME", requires = "dump_manifest")]
+    pub project_name: Option<String>,
+
+    /// Print a timing breakdown of each pipeline stage to stderr
+    #[arg(long)]
+    pub profile: bool,
+
+    /// Kill dbt runs started from the TUI after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub run_timeout: Option<u64>,
+
+    /// Casing of unquoted SQL column identifiers (quoted identifiers are always kept as written)
+    #[arg(long, value_enum, default_value = "preserve")]
+    pub identifier_case: IdentifierCase,
+
+    /// Read defaults from this file instead of <project-dir>/.dbt-lineage.yml
+    #[arg(long, value_name = "PATH", conflicts_with = "no_config")]
+    pub config: Option<PathBuf>,
+
+    /// Ignore .dbt-lineage.yml
+    #[arg(long)]
+    pub no_config: bool,
 }
 
-#[derive(Debug, Clone, clap::ValueEnum)]
+impl Cli {
+    /// Build the CLI from parsed arguments, filling in options not given on the
+    /// command line from `.dbt-lineage.yml` (or `--config`). Subcommands don't read it.
+    pub fn from_matches_with_config(matches: &ArgMatches) -> anyhow::Result<Self> {
+        let mut cli = Self::from_arg_matches(matches)?;
+        if cli.command.is_some() {
+            return Ok(cli);
+        }
+        if let Some(config) = cli.load_config(&cli.project_dir)? {
+            cli.apply_config(config, matches);
+        }
+        Ok(cli)
+    }
+
+    /// Node types to keep, from `--include`/`--exclude` and the older
+    /// `--include-tests` style flags, which are aliases for `--include`
+    pub fn node_types(&self) -> anyhow::Result<HashSet<NodeType>> {
+        let aliases = [
+            (self.include_tests, NodeType::Test),
+            (self.include_seeds, NodeType::Seed),
+            (self.include_snapshots, NodeType::Snapshot),
+            (self.include_exposures, NodeType::Exposure),
+        ];
+        resolve_node_types(
+            self.include.as_deref(),
+            self.exclude.as_deref(),
+            aliases
+                .into_iter()
+                .filter(|&(on, _)| on)
+                .map(|(_, node_type)| node_type),
+        )
+    }
+
+    /// Seed file extensions from `--seed-file-types`, `csv` when not given
+    pub fn seed_file_types(&self) -> anyhow::Result<Vec<String>> {
+        let Some(input) = self.seed_file_types.as_deref() else {
+            return Ok(DEFAULT_SEED_FILE_TYPES.map(String::from).to_vec());
+        };
+        let types = parse_seed_file_types(input);
+        if types.is_empty() {
+            return Err(DbtLineageError::InvalidArgument(
+                "--seed-file-types needs at least one extension, e.g. csv,json".into(),
+            )
+            .into());
+        }
+        Ok(types)
+    }
+
+    /// The `--config` file, or `.dbt-lineage.yml` in `project_dir`; none with `--no-config`
+    pub fn load_config(&self, project_dir: &Path) -> anyhow::Result<Option<ProjectConfig>> {
+        if self.no_config {
+            return Ok(None);
+        }
+        match &self.config {
+            Some(path) => ProjectConfig::load(path).map(Some),
+            None => ProjectConfig::load_from_project(project_dir),
+        }
+    }
+
+    /// Overwrite each option that was not given on the command line with the
+    /// config value, if it has one
+    fn apply_config(&mut self, config: ProjectConfig, matches: &ArgMatches) {
+        fn layer<T>(field: &mut T, value: Option<T>, id: &str, matches: &ArgMatches) {
+            if let Some(value) = value {
+                if matches.value_source(id) != Some(ValueSource::CommandLine) {
+                    *field = value;
+                }
+            }
+        }
+        fn layer_opt<T>(field: &mut Option<T>, value: Option<T>, id: &str, m: &ArgMatches) {
+            layer(field, value.map(Some), id, m);
+        }
+
+        layer(&mut self.output, config.output, "output", matches);
+        // --select and --selector are alternatives, so either one on the
+        // command line shuts out both config keys
+        let selection_on_command_line = ["select", "selector"]
+            .iter()
+            .any(|id| matches.value_source(id) == Some(ValueSource::CommandLine));
+        if !selection_on_command_line {
+            layer_opt(&mut self.select, config.select, "select", matches);
+            layer_opt(&mut self.selector, config.selector, "selector", matches);
+        }
+        layer_opt(
+            &mut self.materialized,
+            config.materialized,
+            "materialized",
+            matches,
+        );
+        layer_opt(&mut self.include, config.include, "include", matches);
+        layer_opt(&mut self.exclude, config.exclude, "exclude", matches);
+        layer(
+            &mut self.include_tests,
+            config.include_tests,
+            "include_tests",
+            matches,
+        );
+        layer(
+            &mut self.include_seeds,
+            config.include_seeds,
+            "include_seeds",
+            matches,
+        );
+        layer(
+            &mut self.include_snapshots,
+            config.include_snapshots,
+            "include_snapshots",
+            matches,
+        );
+        layer(
+            &mut self.include_exposures,
+            config.include_exposures,
+            "include_exposures",
+            matches,
+        );
+        layer_opt(
+            &mut self.edge_types,
+            config.edge_types,
+            "edge_types",
+            matches,
+        );
+        layer_opt(
+            &mut self.hide_edge_types,
+            config.hide_edge_types,
+            "hide_edge_types",
+            matches,
+        );
+        layer(&mut self.manifest, config.manifest, "manifest", matches);
+        layer(
+            &mut self.model_glob,
+            config.model_glob,
+            "model_glob",
+            matches,
+        );
+        layer(
+            &mut self.snapshot_glob,
+            config.snapshot_glob,
+            "snapshot_glob",
+            matches,
+        );
+        layer(&mut self.test_glob, config.test_glob, "test_glob", matches);
+        layer_opt(
+            &mut self.seed_file_types,
+            config.seed_file_types,
+            "seed_file_types",
+            matches,
+        );
+        layer(
+            &mut self.use_compiled,
+            config.use_compiled,
+            "use_compiled",
+            matches,
+        );
+        layer_opt(&mut self.catalog, config.catalog, "catalog", matches);
+        layer(
+            &mut self.identifier_case,
+            config.identifier_case,
+            "identifier_case",
+            matches,
+        );
+        layer(
+            &mut self.with_status,
+            config.with_status,
+            "with_status",
+            matches,
+        );
+        layer(
+            &mut self.trim_orphans,
+            config.trim_orphans,
+            "trim_orphans",
+            matches,
+        );
+        layer(
+            &mut self.collapse_group,
+            config.collapse_group,
+            "collapse_group",
+            matches,
+        );
+        layer(&mut self.tests, config.tests, "tests", matches);
+        layer(
+            &mut self.label_detail,
+            config.label_detail,
+            "label_detail",
+            matches,
+        );
+        layer_opt(
+            &mut self.max_label_len,
+            config.max_label_len,
+            "max_label_len",
+            matches,
+        );
+        layer_opt(
+            &mut self.max_layer_width,
+            config.max_layer_width,
+            "max_layer_width",
+            matches,
+        );
+        layer(
+            &mut self.edge_style,
+            config.edge_style,
+            "edge_style",
+            matches,
+        );
+        layer(
+            &mut self.json_shape,
+            config.json_shape,
+            "json_shape",
+            matches,
+        );
+        layer_opt(&mut self.palette, config.palette, "palette", matches);
+        layer_opt(
+            &mut self.run_results,
+            config.run_results,
+            "run_results",
+            matches,
+        );
+        layer_opt(&mut self.sort, config.sort, "sort", matches);
+        layer_opt(
+            &mut self.run_timeout,
+            config.run_timeout,
+            "run_timeout",
+            matches,
+        );
+    }
+}
+
+#[derive(Debug, Clone, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Ascii,
     Dot,
@@ -63,26 +474,43 @@ pub enum OutputFormat {
     Mermaid,
     Svg,
     Html,
+    Cytoscape,
+    Csv,
+    /// Indented tree of the focus model's ancestors and/or descendants
+    Tree,
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone)]
 pub enum Command {
     /// Compute downstream impact analysis for a model
     Impact {
-        /// Model name to analyze impact for
+        /// Model name to analyze impact for (prefix with = to require an exact match)
         model: String,
 
         /// Path to dbt project directory
         #[arg(short = 'p', long = "project-dir", default_value = ".")]
         project_dir: PathBuf,
 
-        /// Output format: text (default) or json
+        /// Output format: text (default), json, or markdown for PR comments
         #[arg(short = 'o', long, default_value = "text")]
         output: ImpactOutputFormat,
 
-        /// Use manifest.json instead of parsing SQL
+        /// URL prefix for file links in `-o markdown`, e.g.
+        /// https://github.com/org/repo/blob/main/
+        #[arg(long, value_name = "URL")]
+        link_prefix: Option<String>,
+
+        /// Walk upstream instead: list everything the model depends on (root-cause analysis)
         #[arg(long)]
-        manifest: Option<PathBuf>,
+        upstream: bool,
+
+        /// Use manifest.json instead of parsing SQL (repeatable)
+        #[arg(long, value_name = "PATH")]
+        manifest: Vec<PathBuf>,
+
+        /// Exit with code 1 when the overall severity is at least SEVERITY
+        #[arg(long, value_enum, value_name = "SEVERITY", conflicts_with = "upstream")]
+        fail_on: Option<ImpactSeverity>,
     },
 
     /// Compare lineage between git refs
@@ -99,9 +527,211 @@ pub enum Command {
         #[arg(short = 'p', long = "project-dir", default_value = ".")]
         project_dir: PathBuf,
 
-        /// Output format: text (default) or json
+        /// Output format: text (default), json, markdown for PR comments, or a dot,
+        /// svg or mermaid diagram of the changed nodes
         #[arg(short = 'o', long, default_value = "text")]
         output: DiffOutputFormat,
+
+        /// URL prefix for file links in `-o markdown`, e.g.
+        /// https://github.com/org/repo/blob/main/
+        #[arg(long, value_name = "URL")]
+        link_prefix: Option<String>,
+
+        /// Only re-parse files reported by `git diff --name-status` (faster, but misses
+        /// structural changes in untouched files)
+        #[arg(long, alias = "only-changed-files")]
+        fast: bool,
+
+        /// Only show these statuses: a (added), m (modified), r (removed), u (unchanged)
+        #[arg(long, value_name = "STATUSES", default_value = crate::graph::diff::DEFAULT_DIFF_FILTER)]
+        diff_filter: String,
+
+        /// Also list unchanged nodes (same as adding `u` to --diff-filter)
+        #[arg(long)]
+        include_unchanged: bool,
+
+        /// Summarize added/removed/modified counts per folder, node type or tag
+        #[arg(long, value_name = "KEY")]
+        group_by: Option<crate::graph::diff::DiffGroupBy>,
+
+        /// Show how many nodes sit downstream of each added or modified node in
+        /// the head graph, with the impact severity
+        #[arg(long, conflicts_with = "fast")]
+        with_impact: bool,
+
+        /// Also compare column-level lineage: columns whose source columns were
+        /// added, removed or changed confidence, grouped by model
+        #[arg(long, conflicts_with = "fast")]
+        columns: bool,
+
+        /// Exit with code 1 when the diff shows any added, removed or modified
+        /// node or edge
+        #[arg(long)]
+        exit_code: bool,
+    },
+
+    /// Show the path(s) connecting two nodes
+    Path {
+        /// Upstream node to start from (model name, source as schema.table, or unique_id)
+        from: String,
+
+        /// Downstream node to reach
+        to: String,
+
+        /// Path to dbt project directory
+        #[arg(short = 'p', long = "project-dir", default_value = ".")]
+        project_dir: PathBuf,
+
+        /// Output format: ascii (default), dot, json, mermaid, svg, html
+        #[arg(short = 'o', long, default_value = "ascii")]
+        output: OutputFormat,
+
+        /// Show every simple path instead of only the shortest one
+        #[arg(long)]
+        all: bool,
+
+        /// Maximum number of nodes per path when using --all
+        #[arg(long, default_value = "10")]
+        max_length: usize,
+
+        /// File prefix for `-o csv`: writes <PREFIX>_nodes.csv and <PREFIX>_edges.csv
+        #[arg(long, value_name = "PREFIX")]
+        output_file: Option<PathBuf>,
+
+        /// Use manifest.json instead of parsing SQL (repeatable)
+        #[arg(long, value_name = "PATH")]
+        manifest: Vec<PathBuf>,
+    },
+
+    /// Print lineage health statistics (node counts, phantom refs, untested models, depth)
+    Stats {
+        /// Path to dbt project directory
+        #[arg(short = 'p', long = "project-dir", default_value = ".")]
+        project_dir: PathBuf,
+
+        /// Output format: text (default), json, or prometheus
+        #[arg(short = 'o', long, alias = "format", default_value = "text")]
+        output: StatsOutputFormat,
+
+        /// Use manifest.json instead of parsing SQL (repeatable)
+        #[arg(long, value_name = "PATH")]
+        manifest: Vec<PathBuf>,
+    },
+
+    /// Print one node per line (unique_id by default) for scripting and shell completion
+    List {
+        /// Path to dbt project directory
+        #[arg(short = 'p', long = "project-dir", default_value = ".")]
+        project_dir: PathBuf,
+
+        /// Only list these node types: model, source, seed, snapshot, test, exposure,
+        /// phantom (comma-separated; default: all)
+        #[arg(long = "type", value_name = "TYPES")]
+        node_types: Option<String>,
+
+        /// Selector expression: tag:X, path:Y, config.materialized:Z, schema:S, or model
+        /// name; commas intersect, spaces union (`tag:a,tag:b orders`). `@FILE` or `-`
+        /// (stdin) reads one selector per line
+        #[arg(short = 's', long)]
+        select: Option<String>,
+
+        /// Print labels (model names) instead of unique_ids
+        #[arg(long)]
+        labels: bool,
+
+        /// Order the list by name, type, downstream or status (default: graph order)
+        #[arg(long, value_enum)]
+        sort: Option<NodeSort>,
+
+        /// run_results.json to read for --sort status [default: `run-results` from
+        /// .dbt-lineage.yml, else target/run_results.json]
+        #[arg(long, value_name = "PATH")]
+        run_results: Option<PathBuf>,
+
+        /// Use manifest.json instead of parsing SQL (repeatable)
+        #[arg(long, value_name = "PATH")]
+        manifest: Vec<PathBuf>,
+    },
+
+    /// Write a Markdown page per model with its metadata, columns and neighbors
+    Docs {
+        /// Directory to write the pages to (created if missing)
+        #[arg(long, value_name = "DIR")]
+        output_dir: PathBuf,
+
+        /// Path to dbt project directory
+        #[arg(short = 'p', long = "project-dir", default_value = ".")]
+        project_dir: PathBuf,
+
+        /// Only document models matching this selector expression: tag:X, path:Y,
+        /// config.materialized:Z, schema:S, or model name. `@FILE` or `-` (stdin) reads one
+        /// selector per line
+        #[arg(short = 's', long)]
+        select: Option<String>,
+
+        /// Use manifest.json instead of parsing SQL (repeatable)
+        #[arg(long, value_name = "PATH")]
+        manifest: Vec<PathBuf>,
+    },
+
+    /// Fail when a changed model affects too many downstream nodes (CI guardrail)
+    Check {
+        /// Maximum number of downstream nodes a changed model may affect
+        #[arg(long, default_value = "20")]
+        blast_radius: usize,
+
+        /// Base git ref to diff against
+        #[arg(long, default_value = "main")]
+        base_ref: String,
+
+        /// Head git ref to compare (defaults to working tree)
+        #[arg(long)]
+        head: Option<String>,
+
+        /// Path to dbt project directory
+        #[arg(short = 'p', long = "project-dir", default_value = ".")]
+        project_dir: PathBuf,
+
+        /// Output format: text (default) or json
+        #[arg(short = 'o', long, default_value = "text")]
+        output: CheckOutputFormat,
+
+        /// Check layering instead of blast radius: comma-separated layer tags,
+        /// earliest first (e.g. staging,intermediate,marts)
+        #[arg(long, value_name = "LAYERS")]
+        layer_order: Option<String>,
+
+        /// Check documentation instead of blast radius: fail when a model has
+        /// no description
+        #[arg(long, conflicts_with = "layer_order")]
+        require_descriptions: bool,
+
+        /// Models to check with --require-descriptions (e.g. "tag:marts" or
+        /// "path:models/marts"; "@file" reads selectors from a file)
+        #[arg(short = 's', long, requires = "require_descriptions")]
+        select: Option<String>,
+
+        /// Check operations instead of blast radius: fail when a node's last
+        /// successful run is older than its `meta.sla_hours`
+        #[arg(long, conflicts_with_all = ["layer_order", "require_descriptions"])]
+        sla: bool,
+
+        /// run_results.json to read for --sla [default: target/run_results.json]
+        #[arg(long, value_name = "PATH", requires = "sla")]
+        run_results: Option<PathBuf>,
+
+        /// Check for drift instead of blast radius: fail when the working tree's
+        /// lineage differs from the JSON baseline at PATH
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["layer_order", "require_descriptions", "sla"]
+        )]
+        baseline: Option<PathBuf>,
+
+        /// Write the current lineage to the --baseline file instead of comparing
+        #[arg(long, requires = "baseline")]
+        write_baseline: bool,
     },
 }
 
@@ -109,12 +739,45 @@ pub enum Command {
 pub enum ImpactOutputFormat {
     Text,
     Json,
+    /// GitHub-flavored Markdown for PR comments
+    Markdown,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
 pub enum DiffOutputFormat {
     Text,
     Json,
+    /// GitHub-flavored Markdown for PR comments
+    Markdown,
+    /// Changed nodes plus one hop of context, colored by change status
+    Dot,
+    Svg,
+    Mermaid,
+}
+
+impl DiffOutputFormat {
+    /// Diagram format used to draw the changed subgraph, or `None` for a report
+    pub fn diagram(&self) -> Option<OutputFormat> {
+        match self {
+            DiffOutputFormat::Text | DiffOutputFormat::Json | DiffOutputFormat::Markdown => None,
+            DiffOutputFormat::Dot => Some(OutputFormat::Dot),
+            DiffOutputFormat::Svg => Some(OutputFormat::Svg),
+            DiffOutputFormat::Mermaid => Some(OutputFormat::Mermaid),
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum CheckOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum StatsOutputFormat {
+    Text,
+    Json,
+    Prometheus,
 }
 
 #[cfg(test)]
@@ -135,10 +798,155 @@ mod tests {
         assert!(!cli.include_snapshots);
         assert!(!cli.include_exposures);
         assert!(cli.select.is_none());
-        assert!(cli.manifest.is_none());
+        assert!(cli.manifest.is_empty());
+        assert!(!cli.profile);
+        assert_eq!(cli.identifier_case, IdentifierCase::Preserve);
         assert!(matches!(cli.output, OutputFormat::Ascii));
     }
 
+    #[test]
+    fn test_edge_type_flags() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "--edge-types",
+            "ref,source",
+            "--hide-edge-types",
+            "test",
+        ])
+        .unwrap();
+        assert_eq!(cli.edge_types.as_deref(), Some("ref,source"));
+        assert_eq!(cli.hide_edge_types.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn test_node_type_flags() {
+        let types = |args: &[&str]| {
+            let mut argv = vec!["dbt-lineage"];
+            argv.extend_from_slice(args);
+            let mut types: Vec<_> = Cli::try_parse_from(argv)
+                .unwrap()
+                .node_types()
+                .unwrap()
+                .into_iter()
+                .map(|t| t.label())
+                .collect();
+            types.sort();
+            types
+        };
+        assert_eq!(types(&[]), ["model", "phantom", "source"]);
+        assert_eq!(
+            types(&["--include", "seed,test", "--exclude", "phantom"]),
+            ["model", "seed", "source", "test"]
+        );
+        // The older flags are aliases for --include
+        assert_eq!(
+            types(&["--include-exposures", "--include-seeds"]),
+            types(&["--include", "exposure,seed"])
+        );
+        assert_eq!(
+            types(&["--include-tests", "--exclude", "test"]),
+            ["model", "phantom", "source"]
+        );
+        let bad = Cli::try_parse_from(["dbt-lineage", "--exclude", "widget"]).unwrap();
+        assert!(bad.node_types().is_err());
+    }
+
+    #[test]
+    fn test_selector_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "--selector", "nightly"]).unwrap();
+        assert_eq!(cli.selector.as_deref(), Some("nightly"));
+        assert!(
+            Cli::try_parse_from(["dbt-lineage", "--selector", "nightly", "-s", "orders"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_seed_file_types_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert_eq!(cli.seed_file_types().unwrap(), vec!["csv"]);
+        let cli =
+            Cli::try_parse_from(["dbt-lineage", "--seed-file-types", "csv, .JSON,csv"]).unwrap();
+        assert_eq!(cli.seed_file_types().unwrap(), vec!["csv", "json"]);
+        let cli = Cli::try_parse_from(["dbt-lineage", "--seed-file-types", " , "]).unwrap();
+        assert!(cli.seed_file_types().is_err());
+    }
+
+    #[test]
+    fn test_identifier_case_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "--identifier-case", "lower"]).unwrap();
+        assert_eq!(cli.identifier_case, IdentifierCase::Lower);
+        assert!(Cli::try_parse_from(["dbt-lineage", "--identifier-case", "upper"]).is_err());
+    }
+
+    #[test]
+    fn test_label_detail_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert_eq!(cli.label_detail, LabelDetail::None);
+        let cli = Cli::try_parse_from(["dbt-lineage", "--label-detail", "columns"]).unwrap();
+        assert_eq!(cli.label_detail, LabelDetail::Columns);
+        assert!(Cli::try_parse_from(["dbt-lineage", "--label-detail", "tags"]).is_err());
+    }
+
+    #[test]
+    fn test_json_shape_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert_eq!(cli.json_shape, JsonShape::Flat);
+        let cli = Cli::try_parse_from(["dbt-lineage", "--json-shape", "tree"]).unwrap();
+        assert_eq!(cli.json_shape, JsonShape::Tree);
+        assert!(Cli::try_parse_from(["dbt-lineage", "--json-shape", "nested"]).is_err());
+    }
+
+    #[test]
+    fn test_edge_style_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert_eq!(cli.edge_style, EdgeStyle::Curved);
+        let cli = Cli::try_parse_from(["dbt-lineage", "--edge-style", "ortho"]).unwrap();
+        assert_eq!(cli.edge_style, EdgeStyle::Ortho);
+        assert!(Cli::try_parse_from(["dbt-lineage", "--edge-style", "spline"]).is_err());
+    }
+
+    #[test]
+    fn test_exit_code_flags() {
+        let cli =
+            Cli::try_parse_from(["dbt-lineage", "diff", "--base", "main", "--exit-code"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Diff {
+                exit_code: true,
+                ..
+            })
+        ));
+        let cli =
+            Cli::try_parse_from(["dbt-lineage", "impact", "orders", "--fail-on", "high"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Impact {
+                fail_on: Some(ImpactSeverity::High),
+                ..
+            })
+        ));
+        assert!(
+            Cli::try_parse_from(["dbt-lineage", "impact", "orders", "--fail-on", "severe"])
+                .is_err()
+        );
+        // Severity only rates downstream impact
+        assert!(Cli::try_parse_from([
+            "dbt-lineage",
+            "impact",
+            "orders",
+            "--upstream",
+            "--fail-on",
+            "high"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_profile_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "--profile"]).unwrap();
+        assert!(cli.profile);
+    }
+
     #[test]
     fn test_all_flags() {
         let cli = Cli::try_parse_from([
@@ -193,13 +1001,153 @@ mod tests {
     fn test_manifest_flag() {
         let cli =
             Cli::try_parse_from(["dbt-lineage", "--manifest", "/path/to/manifest.json"]).unwrap();
-        assert_eq!(cli.manifest, Some(PathBuf::from("/path/to/manifest.json")));
+        assert_eq!(cli.manifest, vec![PathBuf::from("/path/to/manifest.json")]);
+    }
+
+    #[test]
+    fn test_catalog_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "--catalog", "target/catalog.json"]).unwrap();
+        assert_eq!(cli.catalog, Some(PathBuf::from("target/catalog.json")));
+
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert!(cli.catalog.is_none());
+    }
+
+    #[test]
+    fn test_with_status_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "-o", "svg", "--with-status"]).unwrap();
+        assert!(cli.with_status);
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert!(!cli.with_status);
+    }
+
+    #[test]
+    fn test_use_compiled_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "--use-compiled"]).unwrap();
+        assert!(cli.use_compiled);
+        assert!(
+            Cli::try_parse_from(["dbt-lineage", "--use-compiled", "--manifest", "m.json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_tests_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert_eq!(cli.tests, TestFilter::All);
+        let cli = Cli::try_parse_from(["dbt-lineage", "--tests", "singular"]).unwrap();
+        assert_eq!(cli.tests, TestFilter::Singular);
+        assert!(Cli::try_parse_from(["dbt-lineage", "--tests", "schema"]).is_err());
+    }
+
+    #[test]
+    fn test_sort_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "--sort", "downstream"]).unwrap();
+        assert_eq!(cli.sort, Some(NodeSort::Downstream));
+        assert!(Cli::try_parse_from(["dbt-lineage", "--sort", "size"]).is_err());
+
+        let cli = Cli::try_parse_from(["dbt-lineage", "list", "--sort", "status"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::List {
+                sort: Some(NodeSort::Status),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_docs_subcommand() {
+        let cli =
+            Cli::try_parse_from(["dbt-lineage", "docs", "--output-dir", "docs/models"]).unwrap();
+        match cli.command {
+            Some(Command::Docs {
+                output_dir, select, ..
+            }) => {
+                assert_eq!(output_dir, PathBuf::from("docs/models"));
+                assert!(select.is_none());
+            }
+            other => panic!("expected docs, got {:?}", other),
+        }
+        // Where to write is required
+        assert!(Cli::try_parse_from(["dbt-lineage", "docs"]).is_err());
+    }
+
+    #[test]
+    fn test_collapse_group_repeatable() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "--collapse-group",
+            "models/staging",
+            "--collapse-group",
+            "models/intermediate",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.collapse_group,
+            vec!["models/staging", "models/intermediate"]
+        );
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert!(cli.collapse_group.is_empty());
+    }
+
+    #[test]
+    fn test_discovery_globs() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "--model-glob",
+            "transform/**/*.sql",
+            "--model-glob",
+            "legacy/*.sql",
+            "--snapshot-glob",
+            "history/*.sql",
+        ])
+        .unwrap();
+        assert_eq!(cli.model_glob, vec!["transform/**/*.sql", "legacy/*.sql"]);
+        assert_eq!(cli.snapshot_glob, vec!["history/*.sql"]);
+        assert!(cli.test_glob.is_empty());
+        // Globs feed SQL parsing, which --manifest skips
+        assert!(Cli::try_parse_from([
+            "dbt-lineage",
+            "--manifest",
+            "target",
+            "--test-glob",
+            "checks/*.sql"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_run_timeout_flag() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "-i", "--run-timeout", "600"]).unwrap();
+        assert_eq!(cli.run_timeout, Some(600));
+
+        let cli = Cli::try_parse_from(["dbt-lineage"]).unwrap();
+        assert!(cli.run_timeout.is_none());
+    }
+
+    #[test]
+    fn test_manifest_flag_repeatable() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "--manifest",
+            "core/target/manifest.json",
+            "--manifest",
+            "finance/target/manifest.json",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.manifest,
+            vec![
+                PathBuf::from("core/target/manifest.json"),
+                PathBuf::from("finance/target/manifest.json")
+            ]
+        );
     }
 
     #[test]
     fn test_manifest_flag_directory() {
         let cli = Cli::try_parse_from(["dbt-lineage", "--manifest", "/path/to/project"]).unwrap();
-        assert_eq!(cli.manifest, Some(PathBuf::from("/path/to/project")));
+        assert_eq!(cli.manifest, vec![PathBuf::from("/path/to/project")]);
     }
 
     #[test]
@@ -222,6 +1170,17 @@ mod tests {
         let cli = Cli::try_parse_from(["dbt-lineage", "-o", "html"]).unwrap();
         assert!(matches!(cli.output, OutputFormat::Html));
 
+        let cli = Cli::try_parse_from(["dbt-lineage", "-o", "cytoscape"]).unwrap();
+        assert!(matches!(cli.output, OutputFormat::Cytoscape));
+
+        let cli = Cli::try_parse_from(["dbt-lineage", "-o", "csv", "--output-file", "out/lineage"])
+            .unwrap();
+        assert!(matches!(cli.output, OutputFormat::Csv));
+        assert_eq!(cli.output_file, Some(PathBuf::from("out/lineage")));
+
+        let cli = Cli::try_parse_from(["dbt-lineage", "orders", "-o", "tree"]).unwrap();
+        assert!(matches!(cli.output, OutputFormat::Tree));
+
         // Invalid format
         let result = Cli::try_parse_from(["dbt-lineage", "-o", "yaml"]);
         assert!(result.is_err());
@@ -256,6 +1215,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_markdown_link_prefix() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "impact",
+            "orders",
+            "-o",
+            "markdown",
+            "--link-prefix",
+            "https://x/blob/main/",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Impact {
+                ref output,
+                ref link_prefix,
+                ..
+            }) => {
+                assert!(matches!(output, ImpactOutputFormat::Markdown));
+                assert_eq!(link_prefix.as_deref(), Some("https://x/blob/main/"));
+            }
+            _ => panic!("Expected Impact subcommand"),
+        }
+        let cli = Cli::try_parse_from(["dbt-lineage", "diff", "--base", "main", "-o", "markdown"])
+            .unwrap();
+        match cli.command {
+            Some(Command::Diff { ref output, .. }) => {
+                assert!(matches!(output, DiffOutputFormat::Markdown));
+                assert!(output.diagram().is_none());
+            }
+            _ => panic!("Expected Diff subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_impact_subcommand_upstream() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "impact", "orders"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Impact {
+                upstream: false,
+                ..
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["dbt-lineage", "impact", "orders", "--upstream"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Impact { upstream: true, .. })
+        ));
+    }
+
     #[test]
     fn test_diff_subcommand() {
         let cli = Cli::try_parse_from(["dbt-lineage", "diff", "--base", "main"]).unwrap();
@@ -270,6 +1281,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_diff_subcommand_filter() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "diff", "--base", "main"]).unwrap();
+        match cli.command {
+            Some(Command::Diff {
+                ref diff_filter,
+                include_unchanged,
+                ..
+            }) => {
+                assert_eq!(diff_filter, "a,m,r");
+                assert!(!include_unchanged);
+            }
+            _ => panic!("Expected Diff subcommand"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "diff",
+            "--base",
+            "main",
+            "--diff-filter",
+            "a,r",
+            "--include-unchanged",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Diff {
+                ref diff_filter,
+                include_unchanged,
+                ..
+            }) => {
+                assert_eq!(diff_filter, "a,r");
+                assert!(include_unchanged);
+            }
+            _ => panic!("Expected Diff subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_diff_subcommand_fast() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "diff", "--base", "main"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Diff { fast: false, .. })
+        ));
+
+        for flag in ["--fast", "--only-changed-files"] {
+            let cli = Cli::try_parse_from(["dbt-lineage", "diff", "--base", "main", flag]).unwrap();
+            assert!(matches!(
+                cli.command,
+                Some(Command::Diff { fast: true, .. })
+            ));
+        }
+    }
+
     #[test]
     fn test_diff_subcommand_with_head() {
         let cli =
@@ -285,4 +1351,287 @@ mod tests {
             _ => panic!("Expected Diff subcommand"),
         }
     }
+
+    #[test]
+    fn test_path_subcommand() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "path", "raw.orders", "dashboard", "--all"])
+            .unwrap();
+        match cli.command {
+            Some(Command::Path {
+                ref from,
+                ref to,
+                all,
+                max_length,
+                ..
+            }) => {
+                assert_eq!(from, "raw.orders");
+                assert_eq!(to, "dashboard");
+                assert!(all);
+                assert_eq!(max_length, 10);
+            }
+            _ => panic!("Expected Path subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_stats_subcommand() {
+        let cli = Cli::try_parse_from(["dbt-lineage", "stats", "--format", "prometheus"]).unwrap();
+        match cli.command {
+            Some(Command::Stats { ref output, .. }) => {
+                assert!(matches!(output, StatsOutputFormat::Prometheus));
+            }
+            _ => panic!("Expected Stats subcommand"),
+        }
+
+        let cli = Cli::try_parse_from(["dbt-lineage", "stats"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Stats {
+                output: StatsOutputFormat::Text,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_list_subcommand() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "list",
+            "--type",
+            "model,source",
+            "-s",
+            "tag:nightly",
+            "--labels",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::List {
+                node_types,
+                select,
+                labels,
+                ..
+            }) => {
+                assert_eq!(node_types.as_deref(), Some("model,source"));
+                assert_eq!(select.as_deref(), Some("tag:nightly"));
+                assert!(labels);
+            }
+            _ => panic!("Expected List subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_check_subcommand() {
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "check",
+            "--blast-radius",
+            "5",
+            "--base-ref",
+            "develop",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Check {
+                blast_radius,
+                ref base_ref,
+                ref head,
+                ..
+            }) => {
+                assert_eq!(blast_radius, 5);
+                assert_eq!(base_ref, "develop");
+                assert!(head.is_none());
+            }
+            _ => panic!("Expected Check subcommand"),
+        }
+
+        let cli = Cli::try_parse_from(["dbt-lineage", "check"]).unwrap();
+        match cli.command {
+            Some(Command::Check {
+                blast_radius,
+                ref base_ref,
+                ..
+            }) => {
+                assert_eq!(blast_radius, 20);
+                assert_eq!(base_ref, "main");
+            }
+            _ => panic!("Expected Check subcommand"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "check",
+            "--require-descriptions",
+            "-s",
+            "tag:marts",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Check {
+                require_descriptions,
+                ref select,
+                ..
+            }) => {
+                assert!(require_descriptions);
+                assert_eq!(select.as_deref(), Some("tag:marts"));
+            }
+            _ => panic!("Expected Check subcommand"),
+        }
+        assert!(Cli::try_parse_from(["dbt-lineage", "check", "-s", "orders"]).is_err());
+        assert!(Cli::try_parse_from([
+            "dbt-lineage",
+            "check",
+            "--require-descriptions",
+            "--layer-order",
+            "staging,marts"
+        ])
+        .is_err());
+
+        let cli = Cli::try_parse_from([
+            "dbt-lineage",
+            "check",
+            "--baseline",
+            "lineage.json",
+            "--write-baseline",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Check {
+                ref baseline,
+                write_baseline,
+                ..
+            }) => {
+                assert_eq!(baseline.as_deref(), Some(Path::new("lineage.json")));
+                assert!(write_baseline);
+            }
+            _ => panic!("Expected Check subcommand"),
+        }
+        assert!(Cli::try_parse_from(["dbt-lineage", "check", "--write-baseline"]).is_err());
+        assert!(Cli::try_parse_from([
+            "dbt-lineage",
+            "check",
+            "--baseline",
+            "lineage.json",
+            "--sla"
+        ])
+        .is_err());
+    }
+
+    fn parse_with_config(args: &[&str]) -> anyhow::Result<Cli> {
+        use clap::CommandFactory;
+        let matches = Cli::command().try_get_matches_from(args)?;
+        Cli::from_matches_with_config(&matches)
+    }
+
+    fn write_config(dir: &std::path::Path) {
+        std::fs::write(
+            dir.join(crate::config::CONFIG_FILE),
+            "output: dot\nselect: tag:nightly\ninclude-tests: true\nrun-timeout: 60\nmax-label-len: 40\nmax-layer-width: 50\ncollapse-group: models/staging\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_config_file_fills_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path());
+        let project = dir.path().to_str().unwrap();
+
+        let cli = parse_with_config(&["dbt-lineage", "-p", project]).unwrap();
+        assert!(matches!(cli.output, OutputFormat::Dot));
+        assert_eq!(cli.select.as_deref(), Some("tag:nightly"));
+        assert!(cli.include_tests);
+        assert!(!cli.include_seeds);
+        assert_eq!(cli.run_timeout, Some(60));
+        assert_eq!(cli.max_label_len, Some(40));
+        assert_eq!(cli.max_layer_width, Some(50));
+        assert_eq!(cli.collapse_group, vec!["models/staging"]);
+    }
+
+    #[test]
+    fn test_command_line_overrides_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path());
+        let project = dir.path().to_str().unwrap();
+
+        let cli = parse_with_config(&[
+            "dbt-lineage",
+            "-p",
+            project,
+            "-o",
+            "json",
+            "-s",
+            "path:models",
+            "--collapse-group",
+            "models/marts",
+        ])
+        .unwrap();
+        assert!(matches!(cli.output, OutputFormat::Json));
+        assert_eq!(cli.select.as_deref(), Some("path:models"));
+        assert_eq!(cli.collapse_group, vec!["models/marts"]);
+        assert!(cli.include_tests);
+    }
+
+    #[test]
+    fn test_command_line_selection_overrides_config_selector() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(crate::config::CONFIG_FILE),
+            "selector: marts\n",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        let cli = parse_with_config(&["dbt-lineage", "-p", project]).unwrap();
+        assert_eq!(cli.selector.as_deref(), Some("marts"));
+
+        let cli = parse_with_config(&["dbt-lineage", "-p", project, "-s", "stg_orders"]).unwrap();
+        assert_eq!(cli.select.as_deref(), Some("stg_orders"));
+        assert!(cli.selector.is_none());
+
+        // And the other way round: a config `select` yields to --selector
+        write_config(dir.path());
+        let cli =
+            parse_with_config(&["dbt-lineage", "-p", project, "--selector", "nightly"]).unwrap();
+        assert_eq!(cli.selector.as_deref(), Some("nightly"));
+        assert!(cli.select.is_none());
+    }
+
+    #[test]
+    fn test_no_config_and_explicit_config() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path());
+        let project = dir.path().to_str().unwrap();
+
+        let cli = parse_with_config(&["dbt-lineage", "-p", project, "--no-config"]).unwrap();
+        assert!(matches!(cli.output, OutputFormat::Ascii));
+        assert!(cli.select.is_none());
+
+        let other = tempfile::tempdir().unwrap();
+        let path = dir.path().join(crate::config::CONFIG_FILE);
+        let cli = parse_with_config(&[
+            "dbt-lineage",
+            "-p",
+            other.path().to_str().unwrap(),
+            "--config",
+            path.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert!(matches!(cli.output, OutputFormat::Dot));
+
+        assert!(parse_with_config(&["dbt-lineage", "--config", "x.yml", "--no-config"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_config_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(crate::config::CONFIG_FILE),
+            "bogus-key: 1\n",
+        )
+        .unwrap();
+        let err =
+            parse_with_config(&["dbt-lineage", "-p", dir.path().to_str().unwrap()]).unwrap_err();
+        assert!(err.to_string().contains(crate::config::CONFIG_FILE));
+    }
 }
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::cli::OutputFormat;
+use crate::error::DbtLineageError;
+use crate::graph::filter::TestFilter;
+use crate::graph::sort::NodeSort;
+use crate::parser::columns::IdentifierCase;
+use crate::render::json::JsonShape;
+use crate::render::{EdgeStyle, LabelDetail};
+
+/// Name of the optional defaults file in the project root
+pub const CONFIG_FILE: &str = ".dbt-lineage.yml";
+
+/// Defaults for the main command, loaded from `.dbt-lineage.yml`.
+/// Keys mirror the long CLI flag names; flags given on the command line win.
+///
+/// ```yaml
+/// output: svg
+/// select: tag:finance
+/// include-seeds: true
+/// hide-edge-types: test
+/// ```
+///
+/// `rebuild-weights` is the one key read by a subcommand: `impact` uses it for
+/// the rebuild-cost estimate.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub output: Option<OutputFormat>,
+    pub select: Option<String>,
+    pub selector: Option<String>,
+    pub materialized: Option<String>,
+    pub include: Option<String>,
+    pub exclude: Option<String>,
+    pub include_tests: Option<bool>,
+    pub tests: Option<TestFilter>,
+    pub include_seeds: Option<bool>,
+    pub include_snapshots: Option<bool>,
+    pub include_exposures: Option<bool>,
+    pub edge_types: Option<String>,
+    pub hide_edge_types: Option<String>,
+    /// One path or a list, relative to the config file's directory
+    #[serde(deserialize_with = "one_or_many")]
+    pub manifest: Option<Vec<PathBuf>>,
+    /// Relative to the config file's directory
+    pub catalog: Option<PathBuf>,
+    pub use_compiled: Option<bool>,
+    pub model_glob: Option<Vec<String>>,
+    pub snapshot_glob: Option<Vec<String>>,
+    pub test_glob: Option<Vec<String>>,
+    pub seed_file_types: Option<String>,
+    pub identifier_case: Option<IdentifierCase>,
+    pub with_status: Option<bool>,
+    /// Relative to the config file's directory
+    pub run_results: Option<PathBuf>,
+    /// One directory or group, or a list
+    #[serde(deserialize_with = "one_or_many")]
+    pub collapse_group: Option<Vec<String>>,
+    pub trim_orphans: Option<bool>,
+    pub run_timeout: Option<u64>,
+    pub sort: Option<NodeSort>,
+    pub label_detail: Option<LabelDetail>,
+    pub max_label_len: Option<usize>,
+    pub max_layer_width: Option<usize>,
+    pub edge_style: Option<EdgeStyle>,
+    pub json_shape: Option<JsonShape>,
+    /// Relative to the config file's directory
+    pub palette: Option<PathBuf>,
+    /// Materialization to rebuild weight, merged over the defaults
+    pub rebuild_weights: Option<BTreeMap<String, u64>>,
+}
+
+impl ProjectConfig {
+    /// Parse a config from YAML
+    pub fn parse(content: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(content)
+    }
+
+    /// Load a config file, resolving relative paths against its directory
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| DbtLineageError::FileReadError {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+        let mut config = Self::parse(&content).map_err(|e| DbtLineageError::YamlParseError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        for p in config
+            .manifest
+            .iter_mut()
+            .flatten()
+            .chain(config.catalog.as_mut())
+            .chain(config.palette.as_mut())
+            .chain(config.run_results.as_mut())
+        {
+            if p.is_relative() {
+                *p = base.join(&*p);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load `.dbt-lineage.yml` from the project root, if present
+    pub fn load_from_project(project_dir: &Path) -> Result<Option<Self>> {
+        let path = project_dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::load(&path).map(Some)
+    }
+}
+
+/// Accept either `key: value` or `key: [value, ...]`
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => Some(vec![value]),
+        OneOrMany::Many(values) => Some(values),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kebab_case_keys() {
+        let config = ProjectConfig::parse(
+            "output: svg\nselect: tag:finance\ninclude-seeds: true\nidentifier-case: lower\nrun-timeout: 600\n",
+        )
+        .unwrap();
+        assert!(matches!(config.output, Some(OutputFormat::Svg)));
+        assert_eq!(config.select.as_deref(), Some("tag:finance"));
+        assert_eq!(config.include_seeds, Some(true));
+        assert_eq!(config.include_tests, None);
+        assert_eq!(config.identifier_case, Some(IdentifierCase::Lower));
+        assert_eq!(config.run_timeout, Some(600));
+    }
+
+    #[test]
+    fn test_parse_rebuild_weights() {
+        let config =
+            ProjectConfig::parse("rebuild-weights:\n  table: 8\n  incremental: 4\n").unwrap();
+        assert_eq!(
+            config.rebuild_weights,
+            Some(BTreeMap::from([
+                ("incremental".to_string(), 4),
+                ("table".to_string(), 8)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let err = ProjectConfig::parse("outptu: svg\n").unwrap_err();
+        assert!(err.to_string().contains("unknown field `outptu`"));
+    }
+
+    #[test]
+    fn test_load_resolves_paths_relative_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(CONFIG_FILE),
+            "manifest: target/manifest.json\ncatalog: /abs/catalog.json\npalette: brand.yml\nrun-results: prod/run_results.json\n",
+        )
+        .unwrap();
+        let config = ProjectConfig::load_from_project(dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            config.manifest,
+            Some(vec![dir.path().join("target/manifest.json")])
+        );
+        assert_eq!(config.catalog, Some(PathBuf::from("/abs/catalog.json")));
+        assert_eq!(config.palette, Some(dir.path().join("brand.yml")));
+        assert_eq!(
+            config.run_results,
+            Some(dir.path().join("prod/run_results.json"))
+        );
+    }
+
+    #[test]
+    fn test_manifest_list() {
+        let config = ProjectConfig::parse(
+            "manifest: [core/manifest.json, finance/manifest.json]
+",
+        )
+        .unwrap();
+        assert_eq!(
+            config.manifest,
+            Some(vec![
+                PathBuf::from("core/manifest.json"),
+                PathBuf::from("finance/manifest.json")
+            ])
+        );
+        assert_eq!(
+            ProjectConfig::parse(
+                "output: svg
+"
+            )
+            .unwrap()
+            .manifest,
+            None
+        );
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ProjectConfig::load_from_project(dir.path())
+            .unwrap()
+            .is_none());
+        assert!(ProjectConfig::load(&dir.path().join("nope.yml")).is_err());
+    }
+}
@@ -13,12 +13,21 @@ pub enum DbtLineageError {
         source: std::io::Error,
     },
 
+    #[error("failed to write file {path}: {source}")]
+    FileWriteError {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
     #[error("failed to parse YAML in {path}: {source}")]
     YamlParseError {
         path: PathBuf,
         source: serde_yaml::Error,
     },
 
+    #[error("failed to parse SQL in {path}: {detail}")]
+    SqlParseError { path: PathBuf, detail: String },
+
     #[error("model not found: {0}")]
     ModelNotFound(String),
 
@@ -37,6 +46,32 @@ pub enum DbtLineageError {
         path: PathBuf,
         source: serde_json::Error,
     },
+
+    #[error("no models found under {paths}; check --project-dir / model-paths in dbt_project.yml")]
+    NoModelsFound { paths: String },
+
+    #[error("no nodes found in manifest {0}; run `dbt compile` or `dbt parse` to populate it")]
+    EmptyManifest(PathBuf),
+
+    #[error("no lineage nodes found at {base} or {head}; check --project-dir / model-paths in dbt_project.yml")]
+    EmptyDiff { base: String, head: String },
+
+    /// A flag, argument or config value the tool can't act on
+    #[error("{0}")]
+    InvalidArgument(String),
+}
+
+impl DbtLineageError {
+    /// Whether the error comes from how the tool was invoked rather than from
+    /// the project, so it exits with [`crate::exit::ExitStatus::Usage`]
+    pub fn is_usage(&self) -> bool {
+        matches!(
+            self,
+            DbtLineageError::InvalidArgument(_)
+                | DbtLineageError::ModelNotFound(_)
+                | DbtLineageError::ProjectNotFound(_)
+        )
+    }
 }
 
 #[cfg(test)]
@@ -59,6 +94,15 @@ mod tests {
         };
         assert!(err.to_string().contains("/bar.sql"));
 
+        let err = DbtLineageError::SqlParseError {
+            path: PathBuf::from("models/orders.sql"),
+            detail: "unclosed `{{` on line 3".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "failed to parse SQL in models/orders.sql: unclosed `{{` on line 3"
+        );
+
         let err = DbtLineageError::ModelNotFound("orders".into());
         assert_eq!(err.to_string(), "model not found: orders");
 
@@ -72,5 +116,40 @@ mod tests {
         };
         assert!(err.to_string().contains("duplicate model name"));
         assert!(err.to_string().contains("orders"));
+
+        let err = DbtLineageError::NoModelsFound {
+            paths: "proj/models".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "no models found under proj/models; check --project-dir / model-paths in dbt_project.yml"
+        );
+
+        let err = DbtLineageError::EmptyManifest(PathBuf::from("target/manifest.json"));
+        assert!(err
+            .to_string()
+            .contains("no nodes found in manifest target/manifest.json"));
+
+        let err = DbtLineageError::EmptyDiff {
+            base: "main".into(),
+            head: "HEAD".into(),
+        };
+        assert!(err
+            .to_string()
+            .contains("no lineage nodes found at main or HEAD"));
+
+        let err = DbtLineageError::InvalidArgument("unknown edge type 'x'".into());
+        assert_eq!(err.to_string(), "unknown edge type 'x'");
+    }
+
+    #[test]
+    fn test_is_usage() {
+        assert!(DbtLineageError::InvalidArgument("x".into()).is_usage());
+        assert!(DbtLineageError::ModelNotFound("orders".into()).is_usage());
+        assert!(!DbtLineageError::CycleDetected.is_usage());
+        assert!(!DbtLineageError::NoModelsFound {
+            paths: "models".into()
+        }
+        .is_usage());
     }
 }
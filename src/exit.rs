@@ -0,0 +1,90 @@
+use crate::error::DbtLineageError;
+
+/// Process exit codes shared by every command, so CI pipelines can tell
+/// "found something" apart from "could not run"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Ran to completion with nothing to report
+    Ok = 0,
+    /// Ran to completion and found what the caller asked to fail on: `check`
+    /// violations, `diff --exit-code` changes, `impact --fail-on` severity, or
+    /// no path for `path`
+    Findings = 1,
+    /// Bad flags, arguments or config: unknown option or node, invalid filter
+    /// value, missing manifest or catalog, unknown git ref
+    Usage = 2,
+    /// Anything else: unreadable project, git or IO failures, broken artifacts
+    Error = 3,
+}
+
+impl ExitStatus {
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Exit status for a command that failed with `err`: [`ExitStatus::Usage`]
+    /// when anything in its chain is a usage error, else [`ExitStatus::Error`]
+    pub fn for_error(err: &anyhow::Error) -> Self {
+        let is_usage = err.chain().any(|cause| {
+            cause
+                .downcast_ref::<DbtLineageError>()
+                .is_some_and(DbtLineageError::is_usage)
+                || cause.is::<clap::Error>()
+        });
+        if is_usage {
+            ExitStatus::Usage
+        } else {
+            ExitStatus::Error
+        }
+    }
+
+    /// [`ExitStatus::Findings`] when `found`, else [`ExitStatus::Ok`]
+    pub fn findings_if(found: bool) -> Self {
+        if found {
+            ExitStatus::Findings
+        } else {
+            ExitStatus::Ok
+        }
+    }
+}
+
+impl From<ExitStatus> for std::process::ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        std::process::ExitCode::from(status.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_codes() {
+        assert_eq!(ExitStatus::Ok.code(), 0);
+        assert_eq!(ExitStatus::Findings.code(), 1);
+        assert_eq!(ExitStatus::Usage.code(), 2);
+        assert_eq!(ExitStatus::Error.code(), 3);
+        assert_eq!(ExitStatus::findings_if(true), ExitStatus::Findings);
+        assert_eq!(ExitStatus::findings_if(false), ExitStatus::Ok);
+    }
+
+    #[test]
+    fn test_for_error() {
+        let err: anyhow::Error = DbtLineageError::InvalidArgument("bad flag".into()).into();
+        assert_eq!(ExitStatus::for_error(&err), ExitStatus::Usage);
+
+        // Context added on top keeps the usage classification
+        let err = Err::<(), _>(DbtLineageError::ModelNotFound("orders".into()))
+            .context("while filtering")
+            .unwrap_err();
+        assert_eq!(ExitStatus::for_error(&err), ExitStatus::Usage);
+
+        let err: anyhow::Error = DbtLineageError::CycleDetected.into();
+        assert_eq!(ExitStatus::for_error(&err), ExitStatus::Error);
+        assert_eq!(
+            ExitStatus::for_error(&anyhow::anyhow!("git log failed")),
+            ExitStatus::Error
+        );
+    }
+}
@@ -3,6 +3,8 @@ use std::process::Command;
 
 use anyhow::{Context, Result};
 
+use crate::error::DbtLineageError;
+
 /// Check if a path is inside a git repository
 pub fn is_git_repo(path: &Path) -> bool {
     Command::new("git")
@@ -22,7 +24,9 @@ pub fn validate_ref(path: &Path, git_ref: &str) -> Result<String> {
         .context("Failed to run git rev-parse")?;
 
     if !output.status.success() {
-        anyhow::bail!("Invalid git ref: {}", git_ref);
+        return Err(
+            DbtLineageError::InvalidArgument(format!("Invalid git ref: {}", git_ref)).into(),
+        );
     }
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -79,6 +83,134 @@ pub fn git_ls_tree(path: &Path, git_ref: &str, pattern: &str) -> Result<Vec<Stri
     Ok(files)
 }
 
+/// How a file changed between two refs, from `git diff --name-status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+/// A single entry of `git diff --name-status` output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub kind: ChangeKind,
+    /// Path at the head side (for deletions, the deleted path)
+    pub path: String,
+    /// Previous path for renames
+    pub old_path: Option<String>,
+}
+
+impl ChangedFile {
+    /// Path of the file at the base side
+    pub fn base_path(&self) -> &str {
+        self.old_path.as_deref().unwrap_or(&self.path)
+    }
+}
+
+/// Parse `git diff --name-status` output. Copies are treated as additions and
+/// type changes as modifications; unknown status letters are skipped.
+pub fn parse_name_status(output: &str) -> Vec<ChangedFile> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            let status = parts.next()?.chars().next()?;
+            let first = parts.next()?.to_string();
+            let second = parts.next().map(|s| s.to_string());
+            match (status, second) {
+                ('R', Some(new)) => Some(ChangedFile {
+                    kind: ChangeKind::Renamed,
+                    path: new,
+                    old_path: Some(first),
+                }),
+                ('C', Some(new)) => Some(ChangedFile {
+                    kind: ChangeKind::Added,
+                    path: new,
+                    old_path: None,
+                }),
+                (status, _) => {
+                    let kind = match status {
+                        'A' => ChangeKind::Added,
+                        'M' | 'T' => ChangeKind::Modified,
+                        'D' => ChangeKind::Deleted,
+                        _ => return None,
+                    };
+                    Some(ChangedFile {
+                        kind,
+                        path: first,
+                        old_path: None,
+                    })
+                }
+            }
+        })
+        .collect()
+}
+
+/// List files changed between `base` and `head` (or the working tree when `head` is
+/// `None`), with paths relative to `path`. Only changes under `path` are reported.
+pub fn git_diff_name_status(
+    path: &Path,
+    base: &str,
+    head: Option<&str>,
+) -> Result<Vec<ChangedFile>> {
+    let mut args = vec!["diff", "--name-status", "--relative", "-M", base];
+    if let Some(head) = head {
+        args.push(head);
+    }
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(path)
+        .output()
+        .context("Failed to run git diff --name-status")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff --name-status failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_name_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Files touched by commits since `since` (any date `git log --since` accepts, e.g.
+/// `2024-05-01` or `2 weeks ago`), relative to `path`. Only files under `path`
+/// are reported, each once, sorted.
+pub fn files_changed_since(path: &Path, since: &str) -> Result<Vec<String>> {
+    let since_arg = format!("--since={}", since);
+    let output = Command::new("git")
+        .args([
+            "log",
+            &since_arg,
+            "--name-only",
+            "--pretty=format:",
+            "--relative",
+        ])
+        .current_dir(path)
+        .output()
+        .context("Failed to run git log")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log --since {} failed: {}",
+            since,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +310,96 @@ mod tests {
         let files = result.unwrap();
         assert!(files.contains(&"README.md".to_string()));
     }
+
+    #[test]
+    fn test_parse_name_status() {
+        let out = "M\tmodels/a.sql\nA\tmodels/b.sql\nD\tmodels/c.sql\nR087\tmodels/old.sql\tmodels/new.sql\nX\tweird\n";
+        let files = parse_name_status(out);
+        assert_eq!(files.len(), 4);
+        assert_eq!(files[0].kind, ChangeKind::Modified);
+        assert_eq!(files[1].kind, ChangeKind::Added);
+        assert_eq!(files[2].kind, ChangeKind::Deleted);
+        assert_eq!(files[3].kind, ChangeKind::Renamed);
+        assert_eq!(files[3].path, "models/new.sql");
+        assert_eq!(files[3].base_path(), "models/old.sql");
+        assert_eq!(files[0].base_path(), "models/a.sql");
+    }
+
+    #[test]
+    fn test_git_diff_name_status() {
+        let (_tmp, path) = setup_temp_git_repo();
+        std::fs::write(path.join("README.md"), "# changed\n").unwrap();
+        std::fs::write(path.join("new.sql"), "select 1").unwrap();
+
+        // Working tree: only tracked files show up
+        let files = git_diff_name_status(&path, "HEAD", None).unwrap();
+        assert_eq!(
+            files,
+            vec![ChangedFile {
+                kind: ChangeKind::Modified,
+                path: "README.md".to_string(),
+                old_path: None,
+            }]
+        );
+
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "second"])
+            .current_dir(&path)
+            .output()
+            .unwrap();
+        let files = git_diff_name_status(&path, "HEAD~1", Some("HEAD")).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files
+            .iter()
+            .any(|f| f.path == "new.sql" && f.kind == ChangeKind::Added));
+    }
+
+    #[test]
+    fn test_git_diff_name_status_invalid_ref() {
+        let (_tmp, path) = setup_temp_git_repo();
+        assert!(git_diff_name_status(&path, "nonexistent_branch_xyz", None).is_err());
+    }
+
+    #[test]
+    fn test_files_changed_since() {
+        let (_tmp, path) = setup_temp_git_repo();
+        std::fs::create_dir_all(path.join("models")).unwrap();
+        let commit = |file: &str, date: Option<&str>| {
+            std::fs::write(path.join(file), "select 1").unwrap();
+            Command::new("git")
+                .args(["add", "."])
+                .current_dir(&path)
+                .output()
+                .unwrap();
+            let mut cmd = Command::new("git");
+            cmd.args(["commit", "-m", file]).current_dir(&path);
+            if let Some(date) = date {
+                cmd.env("GIT_AUTHOR_DATE", date)
+                    .env("GIT_COMMITTER_DATE", date);
+            }
+            cmd.output().unwrap();
+        };
+        commit("models/old.sql", Some("2001-01-01T00:00:00"));
+        commit("models/new.sql", None);
+        commit("models/new.sql.bak", None);
+
+        let files = files_changed_since(&path, "2010-01-01").unwrap();
+        assert!(files.contains(&"models/new.sql".to_string()));
+        assert!(!files.contains(&"models/old.sql".to_string()));
+
+        // Paths are relative to the directory asked about
+        let files = files_changed_since(&path.join("models"), "1 week ago").unwrap();
+        assert_eq!(files, vec!["new.sql", "new.sql.bak"]);
+    }
+
+    #[test]
+    fn test_files_changed_since_outside_repo() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(files_changed_since(tmp.path(), "2010-01-01").is_err());
+    }
 }
@@ -0,0 +1,178 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+use petgraph::stable_graph::NodeIndex;
+
+use crate::error::DbtLineageError;
+use crate::graph::types::*;
+
+/// Hex digits of the hash used in an anonymized name, unless two nodes collide
+const HASH_DIGITS: usize = 6;
+
+/// A graph with every name replaced, and how to get the names back
+#[derive(Debug, Clone)]
+pub struct Anonymized {
+    /// Same nodes (under the same indices), edges and node types
+    pub graph: LineageGraph,
+    /// Original unique_id to its replacement
+    pub ids: BTreeMap<String, String>,
+    /// Replacement label to the original unique_id, for `--anonymize-map`
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Anonymized {
+    /// Write `labels` as a JSON object (`{"model_3fa9c1": "model.orders", ...}`)
+    pub fn write_map(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.labels)?;
+        std::fs::write(path, json + "\n").map_err(|e| DbtLineageError::FileWriteError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Ok(())
+    }
+}
+
+/// 64-bit FNV-1a, spelled out so names stay the same across builds and platforms
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Replace labels and unique_ids with `<type>_<hash>` names derived from the
+/// unique_id, e.g. `model_3fa9c1` (unique_id `model.3fa9c1`), so the shape of
+/// the graph can be shared without its names.
+///
+/// Everything else that could identify a node is dropped: descriptions, file
+/// paths, columns, tags, versions and projects, plus the SQL snippets on edges.
+/// Node types and materializations are kept. A name only gets longer when two
+/// nodes would otherwise share it.
+pub fn anonymize(graph: &LineageGraph) -> Anonymized {
+    let mut order: Vec<NodeIndex> = graph.node_indices().collect();
+    order.sort_by(|&a, &b| graph[a].unique_id.cmp(&graph[b].unique_id));
+
+    let mut anonymized = graph.clone();
+    let mut ids = BTreeMap::new();
+    let mut labels = BTreeMap::new();
+    let mut used = HashSet::new();
+    for idx in order {
+        let original = &graph[idx];
+        let kind = original.node_type.label();
+        let hash = format!("{:016x}", fnv1a(&original.unique_id));
+        let digits = (HASH_DIGITS..=hash.len())
+            .find(|&n| !used.contains(&(kind, hash[..n].to_string())))
+            .unwrap_or(hash.len());
+        let short = hash[..digits].to_string();
+        used.insert((kind, short.clone()));
+
+        let label = format!("{}_{}", kind, short);
+        let unique_id = format!("{}.{}", kind, short);
+        ids.insert(original.unique_id.clone(), unique_id.clone());
+        labels.insert(label.clone(), original.unique_id.clone());
+
+        anonymized[idx] = NodeData {
+            unique_id,
+            label,
+            node_type: original.node_type,
+            materialization: original.materialization.clone(),
+            test_kind: original.test_kind,
+            ..Default::default()
+        };
+    }
+    for edge in anonymized.edge_weights_mut() {
+        edge.snippet = None;
+    }
+
+    Anonymized {
+        graph: anonymized,
+        ids,
+        labels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn make_node(unique_id: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type,
+            file_path: Some(PathBuf::from(format!("models/{}.sql", unique_id))),
+            description: Some("Revenue by customer".into()),
+            materialization: Some("table".into()),
+            tags: vec!["finance".into()],
+            columns: vec!["customer_id".into()],
+            version: Some("2".into()),
+            project: Some("jaffle".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_anonymize_keeps_shape_and_drops_names() {
+        let mut g = LineageGraph::new();
+        let src = g.add_node(make_node("source.raw.orders", NodeType::Source));
+        let model = g.add_node(make_node("model.orders", NodeType::Model));
+        g.add_edge(
+            src,
+            model,
+            EdgeData {
+                edge_type: EdgeType::Source,
+                snippet: Some(EdgeSnippet {
+                    line: 3,
+                    text: "from {{ source('raw', 'orders') }}".into(),
+                }),
+            },
+        );
+
+        let result = anonymize(&g);
+        let out = &result.graph;
+        assert_eq!(out.node_count(), 2);
+        assert_eq!(out.edge_count(), 1);
+
+        let node = &out[model];
+        assert_eq!(node.node_type, NodeType::Model);
+        assert!(node.label.starts_with("model_"));
+        assert_eq!(node.label.len(), "model_".len() + HASH_DIGITS);
+        assert_eq!(node.unique_id, node.label.replacen('_', ".", 1));
+        assert_eq!(node.materialization.as_deref(), Some("table"));
+        assert!(node.file_path.is_none() && node.description.is_none());
+        assert!(node.tags.is_empty() && node.columns.is_empty());
+        assert!(node.version.is_none() && node.project.is_none());
+        assert!(out[src].label.starts_with("source_"));
+        assert!(out.edge_weights().all(|e| e.snippet.is_none()));
+
+        assert_eq!(result.ids["model.orders"], node.unique_id);
+        assert_eq!(result.labels[&node.label], "model.orders");
+
+        // Stable across runs
+        assert_eq!(anonymize(&g).graph[model].label, node.label);
+    }
+
+    #[test]
+    fn test_write_map() {
+        let mut g = LineageGraph::new();
+        g.add_node(make_node("model.orders", NodeType::Model));
+        let result = anonymize(&g);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("map.json");
+        result.write_map(&path).unwrap();
+
+        let map: BTreeMap<String, String> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(map, result.labels);
+        assert!(result
+            .write_map(&dir.path().join("missing/map.json"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_fnv1a_is_fixed() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -1,12 +1,20 @@
 use anyhow::Result;
 use petgraph::stable_graph::NodeIndex;
-use std::collections::HashMap;
-use std::path::Path;
-
-use crate::parser::columns::extract_select_columns;
-use crate::parser::discovery::DiscoveredFiles;
-use crate::parser::sql::{extract_config, extract_refs, extract_sources};
-use crate::parser::yaml_schema::{parse_schema_file, ExposureDefinition};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::error::DbtLineageError;
+use crate::parser::columns::{extract_select_columns_with_case, IdentifierCase};
+use crate::parser::discovery::{classify_files, DiscoveredFiles};
+use crate::parser::project::{DbtProject, FolderConfigs, ResolvedPaths};
+use crate::parser::sql::{
+    defines_generic_test, extract_compiled_relations, extract_config, extract_refs_with_lines,
+    extract_sources_with_lines, find_parse_problems,
+};
+use crate::parser::yaml_schema::{column_descriptions, parse_schema_file, ExposureDefinition};
+use crate::profile::Profiler;
+
+use super::suggest::{closest_names, did_you_mean};
 
 use super::types::*;
 
@@ -14,6 +22,16 @@ use super::types::*;
 struct GraphBuilder {
     graph: LineageGraph,
     node_map: HashMap<String, NodeIndex>,
+    /// Model SQL path → unique_id, so duplicate-named models stay addressable
+    model_ids: HashMap<PathBuf, String>,
+    /// Versioned model name → version an unpinned `ref()` resolves to
+    latest_versions: HashMap<String, String>,
+    /// Phantom node → project-relative files whose ref()/source() created it
+    phantom_referrers: HashMap<NodeIndex, Vec<PathBuf>>,
+    /// Phantom ref → existing names it is probably a typo of
+    phantom_suggestions: HashMap<NodeIndex, Vec<String>>,
+    /// Files that could not be read or parsed cleanly
+    warnings: Vec<DbtLineageError>,
 }
 
 impl GraphBuilder {
@@ -21,6 +39,53 @@ impl GraphBuilder {
         Self {
             graph: LineageGraph::new(),
             node_map: HashMap::new(),
+            model_ids: HashMap::new(),
+            latest_versions: HashMap::new(),
+            phantom_referrers: HashMap::new(),
+            phantom_suggestions: HashMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Record that `referrer` points at `idx`, if `idx` is a phantom
+    fn note_phantom_referrer(&mut self, idx: NodeIndex, referrer: &Path) {
+        if self.graph[idx].node_type != NodeType::Phantom {
+            return;
+        }
+        let referrers = self.phantom_referrers.entry(idx).or_default();
+        if !referrers.iter().any(|r| r == referrer) {
+            referrers.push(referrer.to_path_buf());
+        }
+    }
+
+    /// Describe every phantom with the files that referenced it and return the
+    /// graph with the warnings collected along the way
+    fn finish(mut self) -> BuildOutput {
+        for (idx, referrers) in self.phantom_referrers {
+            let kind = if self.graph[idx].unique_id.starts_with("source.") {
+                "Undeclared source()"
+            } else {
+                "Unresolved ref()"
+            };
+            let files: Vec<String> = referrers
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            let mut description = format!(
+                "{} referenced by {} file{}: {}",
+                kind,
+                files.len(),
+                if files.len() == 1 { "" } else { "s" },
+                files.join(", ")
+            );
+            if let Some(suggestions) = self.phantom_suggestions.get(&idx) {
+                description.push_str(&format!(". Did you mean: {}?", suggestions.join(", ")));
+            }
+            self.graph[idx].description = Some(description);
+        }
+        BuildOutput {
+            graph: self.graph,
+            warnings: self.warnings,
         }
     }
 
@@ -32,39 +97,98 @@ impl GraphBuilder {
         idx
     }
 
-    /// Get or create a phantom ref node, returning its index
-    fn get_or_create_phantom_ref(&mut self, ref_name: &str, sql_path: &Path) -> NodeIndex {
-        let dep_id = resolve_ref(ref_name, &self.node_map);
+    /// Resolve a (possibly version-pinned) ref to a node unique_id.
+    ///
+    /// A pinned ref maps to `model.<name>.v<version>`; an unpinned ref to a
+    /// versioned model maps to its latest version when that node exists.
+    fn resolve_model_ref(&self, name: &str, version: Option<&str>) -> String {
+        if let Some(v) = version {
+            return versioned_model_id(name, v);
+        }
+        if let Some(latest) = self.latest_versions.get(name) {
+            let latest_id = versioned_model_id(name, latest);
+            if self.node_map.contains_key(&latest_id) {
+                return latest_id;
+            }
+        }
+        resolve_ref(name, &self.node_map)
+    }
+
+    /// Get or create a phantom ref node, returning its index. `referrer` is the
+    /// project-relative path of `sql_path`, recorded on the phantom.
+    fn get_or_create_phantom_ref(
+        &mut self,
+        ref_name: &str,
+        version: Option<&str>,
+        sql_path: &Path,
+        referrer: &Path,
+    ) -> NodeIndex {
+        let dep_id = self.resolve_model_ref(ref_name, version);
         if let Some(&idx) = self.node_map.get(&dep_id) {
+            self.note_phantom_referrer(idx, referrer);
             return idx;
         }
-        eprintln!(
-            "Warning: unresolved ref '{}' in {}",
-            ref_name,
-            sql_path.display()
-        );
-        let phantom_id = format!("model.{}", ref_name);
-        self.add_node(NodeData {
-            unique_id: phantom_id,
+        let suggestions = self.ref_suggestions(ref_name);
+        let hint = did_you_mean(&suggestions)
+            .map(|h| format!("; {}", h))
+            .unwrap_or_default();
+        match version {
+            Some(v) => eprintln!(
+                "Warning: unresolved ref '{}' (v{}) in {}{}",
+                ref_name,
+                v,
+                sql_path.display(),
+                hint
+            ),
+            None => eprintln!(
+                "Warning: unresolved ref '{}' in {}{}",
+                ref_name,
+                sql_path.display(),
+                hint
+            ),
+        }
+        let idx = self.add_node(NodeData {
+            unique_id: dep_id,
             label: ref_name.to_string(),
             node_type: NodeType::Phantom,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
-        })
+            version: version.map(str::to_string),
+            ..Default::default()
+        });
+        self.note_phantom_referrer(idx, referrer);
+        if !suggestions.is_empty() {
+            self.phantom_suggestions.insert(idx, suggestions);
+        }
+        idx
+    }
+
+    /// Names of existing models, seeds and snapshots that `ref_name` is likely a typo of
+    fn ref_suggestions(&self, ref_name: &str) -> Vec<String> {
+        let candidates = self
+            .node_map
+            .values()
+            .map(|&idx| &self.graph[idx])
+            .filter(|node| {
+                matches!(
+                    node.node_type,
+                    NodeType::Model | NodeType::Seed | NodeType::Snapshot
+                )
+            })
+            .map(|node| node.label.as_str());
+        closest_names(ref_name, candidates)
     }
 
-    /// Get or create a phantom source node, returning its index
+    /// Get or create a phantom source node, returning its index. `referrer` is
+    /// the project-relative path of `sql_path`, recorded on the phantom.
     fn get_or_create_phantom_source(
         &mut self,
         source_name: &str,
         table_name: &str,
         sql_path: &Path,
+        referrer: &Path,
     ) -> NodeIndex {
         let source_id = format!("source.{}.{}", source_name, table_name);
         if let Some(&idx) = self.node_map.get(&source_id) {
+            self.note_phantom_referrer(idx, referrer);
             return idx;
         }
         eprintln!(
@@ -74,30 +198,81 @@ impl GraphBuilder {
             sql_path.display()
         );
         let label = format!("{}.{}", source_name, table_name);
-        self.add_node(NodeData {
+        let idx = self.add_node(NodeData {
             unique_id: source_id,
             label,
             node_type: NodeType::Phantom,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
-        })
+            ..Default::default()
+        });
+        self.note_phantom_referrer(idx, referrer);
+        idx
+    }
+}
+
+/// Tests under a `generic/` directory, or defining a `{% test %}` block, are
+/// generic test definitions; every other test file is a singular test
+fn sql_test_kind(relative_path: &Path, content: &str) -> TestKind {
+    let in_generic_dir = relative_path
+        .parent()
+        .is_some_and(|dir| dir.components().any(|c| c.as_os_str() == "generic"));
+    if in_generic_dir || defines_generic_test(content) {
+        TestKind::Generic
+    } else {
+        TestKind::Singular
     }
 }
 
 /// Read a file with a descriptive error
-fn read_file(path: &Path) -> Result<String> {
-    std::fs::read_to_string(path).map_err(|e| {
-        crate::error::DbtLineageError::FileReadError {
-            path: path.to_path_buf(),
-            source: e,
-        }
-        .into()
+fn read_file(path: &Path) -> Result<String, DbtLineageError> {
+    std::fs::read_to_string(path).map_err(|e| DbtLineageError::FileReadError {
+        path: path.to_path_buf(),
+        source: e,
     })
 }
 
+/// File contents keyed by the paths listed in `DiscoveredFiles` (and, with a
+/// compiled directory, their compiled counterparts)
+type FileContents = HashMap<PathBuf, String>;
+
+/// Read every YAML and SQL file the build needs from disk, plus
+/// `dbt_project.yml` and the compiled SQL that exists for them. Files that
+/// can't be read are left out and returned as warnings.
+fn load_contents(
+    project_dir: &Path,
+    files: &DiscoveredFiles,
+    options: &BuildOptions,
+) -> (FileContents, Vec<DbtLineageError>) {
+    let mut contents = FileContents::new();
+    let mut warnings = Vec::new();
+    // Only its folder-level model config is used; a missing or unreadable file
+    // just means there is none
+    let project_file = project_dir.join("dbt_project.yml");
+    if let Ok(content) = std::fs::read_to_string(&project_file) {
+        contents.insert(project_file, content);
+    }
+    for path in files
+        .yaml_files
+        .iter()
+        .chain(&files.model_sql_files)
+        .chain(&files.snapshot_sql_files)
+        .chain(&files.test_sql_files)
+    {
+        match read_file(path) {
+            Ok(content) => contents.insert(path.clone(), content),
+            Err(e) => {
+                warnings.push(e);
+                continue;
+            }
+        };
+        if let Some(compiled) = compiled_sql_path(path, project_dir, options) {
+            if let Ok(content) = std::fs::read_to_string(&compiled) {
+                contents.insert(compiled, content);
+            }
+        }
+    }
+    (contents, warnings)
+}
+
 /// Extract the file stem as a string, defaulting to "unknown"
 fn file_stem_str(path: &Path) -> String {
     path.file_stem()
@@ -111,6 +286,7 @@ fn add_source_nodes(
     gb: &mut GraphBuilder,
     schema: &crate::parser::yaml_schema::SchemaFile,
     yaml_path: &Path,
+    project_dir: &Path,
 ) {
     for source_def in &schema.sources {
         for table in &source_def.tables {
@@ -120,14 +296,19 @@ fn add_source_nodes(
                 unique_id,
                 label,
                 node_type: NodeType::Source,
-                file_path: Some(yaml_path.to_path_buf()),
+                file_path: Some(project_relative_path(yaml_path, project_dir)),
                 description: table
                     .description
                     .clone()
                     .or_else(|| source_def.description.clone()),
-                materialization: None,
-                tags: vec![],
-                columns: vec![],
+                column_descriptions: column_descriptions(&table.columns),
+                schema: Some(
+                    source_def
+                        .schema
+                        .clone()
+                        .unwrap_or_else(|| source_def.name.clone()),
+                ),
+                ..Default::default()
             });
         }
     }
@@ -138,73 +319,190 @@ fn add_source_nodes(
 struct YamlModelMeta {
     description: Option<String>,
     materialization: Option<String>,
+    schema: Option<String>,
     tags: Vec<String>,
+    column_descriptions: BTreeMap<String, String>,
+    /// `meta`, with `config.meta` keys overriding model-level ones
+    dbt_meta: BTreeMap<String, serde_json::Value>,
+    /// Declared versions as `(version, defined_in)` pairs
+    versions: Vec<(String, Option<String>)>,
 }
 
 /// Parse YAML schema files: create source nodes, collect model metadata and exposures
 fn process_yaml_files(
     gb: &mut GraphBuilder,
     files: &DiscoveredFiles,
-) -> Result<(HashMap<String, YamlModelMeta>, Vec<ExposureDefinition>)> {
+    contents: &FileContents,
+    project_dir: &Path,
+) -> (HashMap<String, YamlModelMeta>, Vec<ExposureDefinition>) {
     let mut model_meta: HashMap<String, YamlModelMeta> = HashMap::new();
     let mut exposures: Vec<ExposureDefinition> = Vec::new();
 
     for yaml_path in &files.yaml_files {
-        let content = read_file(yaml_path)?;
-        let schema = match parse_schema_file(&content) {
+        let Some(content) = contents.get(yaml_path) else {
+            continue;
+        };
+        let schema = match parse_schema_file(content) {
             Ok(s) => s,
             Err(_) => continue,
         };
 
-        add_source_nodes(gb, &schema, yaml_path);
+        add_source_nodes(gb, &schema, yaml_path, project_dir);
 
         for model_def in &schema.models {
             let mut meta = YamlModelMeta {
                 description: model_def.description.clone(),
+                column_descriptions: column_descriptions(&model_def.columns),
+                dbt_meta: model_def.meta.clone(),
                 ..Default::default()
             };
             // Merge tags from model-level and config-level
             let mut tags = model_def.tags.clone();
             if let Some(cfg) = &model_def.config {
                 meta.materialization = cfg.materialized.clone();
+                meta.schema = cfg.schema.clone();
                 tags.extend(cfg.tags.clone());
+                meta.dbt_meta.extend(cfg.meta.clone());
             }
             tags.sort();
             tags.dedup();
             meta.tags = tags;
+            meta.versions = model_def
+                .versions
+                .iter()
+                .zip(model_def.version_ids())
+                .map(|(v, id)| (id, v.defined_in.clone()))
+                .collect();
+            if let Some(latest) = model_def.resolved_latest_version() {
+                gb.latest_versions.insert(model_def.name.clone(), latest);
+            }
             model_meta.insert(model_def.name.clone(), meta);
         }
 
-        exposures.extend(schema.exposures.into_iter());
+        exposures.extend(schema.exposures);
+    }
+
+    (model_meta, exposures)
+}
+
+/// Unique id of a specific model version, e.g. `model.dim_customers.v2`
+fn versioned_model_id(name: &str, version: &str) -> String {
+    format!("model.{}.v{}", name, version)
+}
+
+/// Map SQL file stems to the `(model name, version)` they implement.
+///
+/// A version's file is its `defined_in` value, defaulting to `<name>_v<version>`.
+fn versioned_file_stems(
+    model_meta: &HashMap<String, YamlModelMeta>,
+) -> HashMap<String, (String, String)> {
+    let mut stems = HashMap::new();
+    for (name, meta) in model_meta {
+        for (version, defined_in) in &meta.versions {
+            let stem = defined_in
+                .clone()
+                .unwrap_or_else(|| format!("{}_v{}", name, version));
+            stems.insert(stem, (name.clone(), version.clone()));
+        }
+    }
+    stems
+}
+
+/// Pick the canonical file for each model name.
+///
+/// When several SQL files share a name, the one with the shallowest path wins
+/// (ties broken lexicographically), so a bare `ref('name')` resolves the same
+/// way regardless of filesystem walk order.
+fn canonical_model_paths(paths: &[PathBuf]) -> HashMap<String, &PathBuf> {
+    let mut canonical: HashMap<String, &PathBuf> = HashMap::new();
+    for path in paths {
+        let name = file_stem_str(path);
+        let key = |p: &PathBuf| (p.components().count(), p.clone());
+        match canonical.get(&name) {
+            Some(existing) if key(existing) <= key(path) => {}
+            _ => {
+                canonical.insert(name, path);
+            }
+        }
     }
+    canonical
+}
 
-    Ok((model_meta, exposures))
+/// Build a unique_id suffix from a model's directory, e.g. `models/finance` → `models_finance`
+fn path_suffix(relative_path: &Path) -> String {
+    relative_path
+        .parent()
+        .map(|dir| {
+            dir.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("_")
+        })
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "root".to_string())
 }
 
-/// Create nodes for model SQL files (with duplicate detection)
+/// Create nodes for model SQL files (with duplicate detection).
+///
+/// Duplicate-named models are all kept: the canonical one (see
+/// `canonical_model_paths`) gets `model.<name>`, the others get
+/// `model.<name>__<dir>` where `<dir>` is derived from their relative path.
+/// Directories that flatten to the same `<dir>` (`a_b/` and `a/b/`) get a
+/// numbered suffix (`__a_b_2`) so every id stays unique.
+/// A bare `ref('name')` always resolves to the canonical model.
+///
+/// Files implementing a version of a versioned model become
+/// `model.<name>.v<version>` nodes labelled with the base model name.
 fn process_model_files(
     gb: &mut GraphBuilder,
     files: &DiscoveredFiles,
+    contents: &FileContents,
     project_dir: &Path,
     model_meta: &HashMap<String, YamlModelMeta>,
+    folder_configs: &FolderConfigs,
+    options: &BuildOptions,
 ) {
-    let mut model_name_paths: HashMap<String, std::path::PathBuf> = HashMap::new();
+    let canonical = canonical_model_paths(&files.model_sql_files);
+    let versioned_stems = versioned_file_stems(model_meta);
+    let mut taken_ids: HashSet<String> = canonical
+        .keys()
+        .map(|name| format!("model.{}", name))
+        .collect();
 
     for sql_path in &files.model_sql_files {
-        let model_name = file_stem_str(sql_path);
-
-        if let Some(existing_path) = model_name_paths.get(&model_name) {
-            eprintln!(
-                "Warning: duplicate model name '{}' in {} and {}",
-                model_name,
-                existing_path.display(),
-                sql_path.display()
-            );
-        }
-        model_name_paths.insert(model_name.clone(), sql_path.clone());
+        let file_stem = file_stem_str(sql_path);
+        let relative_path = project_relative_path(sql_path, project_dir);
+        let (model_name, version) = match versioned_stems.get(&file_stem) {
+            Some((name, v)) => (name.clone(), Some(v.clone())),
+            None => (file_stem.clone(), None),
+        };
+
+        let unique_id = match (&version, canonical.get(&file_stem)) {
+            (Some(v), _) => versioned_model_id(&model_name, v),
+            (None, Some(&primary)) if primary != sql_path => {
+                eprintln!(
+                    "Warning: duplicate model name '{}' in {} and {}; ref('{}') resolves to {}",
+                    model_name,
+                    primary.display(),
+                    sql_path.display(),
+                    model_name,
+                    primary.display()
+                );
+                let base = format!("model.{}__{}", model_name, path_suffix(&relative_path));
+                let mut unique_id = base.clone();
+                let mut n = 1;
+                while taken_ids.contains(&unique_id) {
+                    n += 1;
+                    unique_id = format!("{}_{}", base, n);
+                }
+                taken_ids.insert(unique_id.clone());
+                unique_id
+            }
+            (None, _) => format!("model.{}", model_name),
+        };
+        gb.model_ids.insert(sql_path.clone(), unique_id.clone());
 
-        // Read SQL content once for config extraction and column extraction
-        let sql_content = std::fs::read_to_string(sql_path).ok();
+        let sql_content = contents.get(sql_path);
 
         // Extract config from SQL
         let sql_config = sql_content
@@ -213,29 +511,37 @@ fn process_model_files(
             .unwrap_or_default();
 
         let yaml_meta = model_meta.get(&model_name);
+        let folder_config = folder_configs.resolve(&relative_path);
 
-        // SQL config takes precedence over YAML config; merge tags
+        // SQL config takes precedence over YAML config, which takes precedence
+        // over dbt_project.yml (for materialization and schema alike); tags
+        // from all three are merged
         let materialization = sql_config
             .materialized
-            .or_else(|| yaml_meta.and_then(|m| m.materialization.clone()));
+            .or_else(|| yaml_meta.and_then(|m| m.materialization.clone()))
+            .or(folder_config.materialized);
+        let schema = sql_config
+            .schema
+            .or_else(|| yaml_meta.and_then(|m| m.schema.clone()))
+            .or(folder_config.schema);
 
         let mut tags = sql_config.tags;
         if let Some(meta) = yaml_meta {
             tags.extend(meta.tags.clone());
         }
+        tags.extend(folder_config.tags);
         tags.sort();
         tags.dedup();
 
-        let unique_id = format!("model.{}", model_name);
-        let relative_path = sql_path
-            .strip_prefix(project_dir)
-            .unwrap_or(sql_path)
-            .to_path_buf();
+        let mut dbt_meta = yaml_meta.map(|m| m.dbt_meta.clone()).unwrap_or_default();
+        dbt_meta.extend(sql_config.meta);
 
-        // Extract columns from SELECT clause
-        let columns = sql_content
-            .as_ref()
-            .map(|content| extract_select_columns(content))
+        // Extract columns from SELECT clause, preferring the compiled SQL
+        let compiled_content =
+            compiled_sql_path(sql_path, project_dir, options).and_then(|path| contents.get(&path));
+        let columns = compiled_content
+            .or(sql_content)
+            .map(|content| extract_select_columns_with_case(content, options.identifier_case))
             .unwrap_or_default();
 
         gb.add_node(NodeData {
@@ -247,14 +553,23 @@ fn process_model_files(
             materialization,
             tags,
             columns,
+            version,
+            column_descriptions: yaml_meta
+                .map(|m| m.column_descriptions.clone())
+                .unwrap_or_default(),
+            meta: dbt_meta,
+            schema,
+            ..Default::default()
         });
     }
 }
 
-/// Create nodes for simple file-based resources (seeds, snapshots)
+/// Create nodes for simple file-based resources (seeds, snapshots). A
+/// snapshot's strategy settings come from the `config()` in its file.
 fn process_simple_nodes(
     gb: &mut GraphBuilder,
     paths: &[std::path::PathBuf],
+    contents: &FileContents,
     project_dir: &Path,
     prefix: &str,
     node_type: NodeType,
@@ -262,27 +577,124 @@ fn process_simple_nodes(
     for path in paths {
         let name = file_stem_str(path);
         let unique_id = format!("{}.{}", prefix, name);
-        let relative_path = path.strip_prefix(project_dir).unwrap_or(path).to_path_buf();
+        let relative_path = project_relative_path(path, project_dir);
+        let snapshot = contents
+            .get(path)
+            .filter(|_| node_type == NodeType::Snapshot)
+            .and_then(|content| extract_config(content).snapshot.non_empty());
 
         gb.add_node(NodeData {
             unique_id,
             label: name,
             node_type,
             file_path: Some(relative_path),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            snapshot,
+            ..Default::default()
         });
     }
 }
 
-/// Parse SQL files for ref()/source() calls and add edges
+/// Where the compiled counterpart of a project SQL file would be, if `options`
+/// has a compiled directory
+fn compiled_sql_path(
+    sql_path: &Path,
+    project_dir: &Path,
+    options: &BuildOptions,
+) -> Option<PathBuf> {
+    Some(
+        options
+            .compiled_dir
+            .as_ref()?
+            .join(project_relative_path(sql_path, project_dir)),
+    )
+}
+
+/// Lookup from relation names in compiled SQL to graph nodes. Keys are
+/// lowercased; a name shared by several nodes maps to `None`, since the
+/// relation can't be attributed.
+struct RelationIndex {
+    /// Models, seeds and snapshots by name
+    by_name: HashMap<String, Option<NodeIndex>>,
+    /// Sources by `<source>.<table>`
+    sources: HashMap<String, Option<NodeIndex>>,
+    /// Sources by table name alone
+    source_tables: HashMap<String, Option<NodeIndex>>,
+}
+
+impl RelationIndex {
+    fn new(graph: &LineageGraph) -> Self {
+        fn insert(map: &mut HashMap<String, Option<NodeIndex>>, key: &str, idx: NodeIndex) {
+            map.entry(key.to_lowercase())
+                .and_modify(|existing| *existing = None)
+                .or_insert(Some(idx));
+        }
+
+        let mut index = Self {
+            by_name: HashMap::new(),
+            sources: HashMap::new(),
+            source_tables: HashMap::new(),
+        };
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            match node.node_type {
+                NodeType::Model | NodeType::Seed | NodeType::Snapshot => {
+                    insert(&mut index.by_name, &node.label, idx)
+                }
+                NodeType::Source => {
+                    insert(&mut index.sources, &node.label, idx);
+                    let table = node.label.rsplit('.').next().unwrap_or(&node.label);
+                    insert(&mut index.source_tables, table, idx);
+                }
+                _ => {}
+            }
+        }
+        index
+    }
+
+    /// Resolve a relation's parts: a `<schema>.<table>` matching a source name
+    /// wins, then a model/seed/snapshot name, then a source table name
+    fn resolve(&self, parts: &[String]) -> Option<NodeIndex> {
+        let table = parts.last()?.to_lowercase();
+        let qualified = match parts {
+            [.., schema, _] => Some(format!("{}.{}", schema.to_lowercase(), table)),
+            _ => None,
+        };
+        let lookup =
+            |map: &HashMap<String, Option<NodeIndex>>, key: &str| map.get(key).copied().flatten();
+        qualified
+            .and_then(|q| lookup(&self.sources, &q))
+            .or_else(|| lookup(&self.by_name, &table))
+            .or_else(|| lookup(&self.source_tables, &table))
+    }
+}
+
+/// The trimmed source line `line` (1-based) of `content`, for explaining an edge
+fn edge_snippet(content: &str, line: usize) -> EdgeSnippet {
+    EdgeSnippet {
+        line,
+        text: content
+            .lines()
+            .nth(line - 1)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Parse SQL files for ref()/source() calls and add edges. With a compiled
+/// directory, relations read by the compiled SQL that match a known node add
+/// the edges ref()/source() parsing missed (e.g. refs generated by macros).
 fn process_sql_edges(
     gb: &mut GraphBuilder,
     files: &DiscoveredFiles,
+    contents: &FileContents,
     project_dir: &Path,
-) -> Result<()> {
+    options: &BuildOptions,
+) {
+    let relations = options
+        .compiled_dir
+        .as_ref()
+        .map(|_| RelationIndex::new(&gb.graph));
     let all_sql_files: Vec<(&std::path::PathBuf, &str)> = files
         .model_sql_files
         .iter()
@@ -292,25 +704,32 @@ fn process_sql_edges(
         .collect();
 
     for (sql_path, file_type) in &all_sql_files {
-        let content = read_file(sql_path)?;
+        let Some(content) = contents.get(*sql_path) else {
+            continue;
+        };
+        for detail in find_parse_problems(content) {
+            gb.warnings.push(DbtLineageError::SqlParseError {
+                path: project_relative_path(sql_path, project_dir),
+                detail,
+            });
+        }
         let node_name = file_stem_str(sql_path);
-        let node_unique_id = format!("{}.{}", file_type, node_name);
+        let node_unique_id = match gb.model_ids.get(*sql_path) {
+            Some(id) => id.clone(),
+            None => format!("{}.{}", file_type, node_name),
+        };
 
         // Create test nodes on the fly
         if *file_type == "test" {
-            let relative_path = sql_path
-                .strip_prefix(project_dir)
-                .unwrap_or(sql_path)
-                .to_path_buf();
+            let relative_path = project_relative_path(sql_path, project_dir);
+            let test_kind = sql_test_kind(&relative_path, content);
             gb.add_node(NodeData {
                 unique_id: node_unique_id.clone(),
                 label: node_name,
                 node_type: NodeType::Test,
                 file_path: Some(relative_path),
-                description: None,
-                materialization: None,
-                tags: vec![],
-                columns: vec![],
+                test_kind: Some(test_kind),
+                ..Default::default()
             });
         }
 
@@ -319,34 +738,74 @@ fn process_sql_edges(
             None => continue,
         };
 
-        for ref_call in extract_refs(&content) {
-            let dep_idx = gb.get_or_create_phantom_ref(&ref_call.name, sql_path);
-            gb.graph.add_edge(
+        let referrer = project_relative_path(sql_path, project_dir);
+        for (ref_call, line) in extract_refs_with_lines(content) {
+            let dep_idx = gb.get_or_create_phantom_ref(
+                &ref_call.name,
+                ref_call.version.as_deref(),
+                sql_path,
+                &referrer,
+            );
+            add_unique_edge(
+                &mut gb.graph,
                 dep_idx,
                 current_idx,
                 EdgeData {
                     edge_type: EdgeType::Ref,
+                    snippet: Some(edge_snippet(content, line)),
                 },
             );
         }
 
-        for source_call in extract_sources(&content) {
+        for (source_call, line) in extract_sources_with_lines(content) {
             let source_idx = gb.get_or_create_phantom_source(
                 &source_call.source_name,
                 &source_call.table_name,
                 sql_path,
+                &referrer,
             );
-            gb.graph.add_edge(
+            add_unique_edge(
+                &mut gb.graph,
                 source_idx,
                 current_idx,
                 EdgeData {
                     edge_type: EdgeType::Source,
+                    snippet: Some(edge_snippet(content, line)),
                 },
             );
         }
-    }
 
-    Ok(())
+        let Some(relations) = &relations else {
+            continue;
+        };
+        let Some(compiled) =
+            compiled_sql_path(sql_path, project_dir, options).and_then(|path| contents.get(&path))
+        else {
+            continue;
+        };
+        for relation in extract_compiled_relations(compiled) {
+            let Some(dep_idx) = relations.resolve(&relation) else {
+                continue;
+            };
+            if dep_idx == current_idx || gb.graph.contains_edge(dep_idx, current_idx) {
+                continue;
+            }
+            let edge_type = if gb.graph[dep_idx].node_type == NodeType::Source {
+                EdgeType::Source
+            } else {
+                EdgeType::Ref
+            };
+            add_unique_edge(
+                &mut gb.graph,
+                dep_idx,
+                current_idx,
+                EdgeData {
+                    edge_type,
+                    snippet: None,
+                },
+            );
+        }
+    }
 }
 
 /// Create exposure nodes and edges to their dependencies
@@ -357,54 +816,175 @@ fn process_exposures(gb: &mut GraphBuilder, exposures: &[ExposureDefinition]) {
             unique_id,
             label: exposure.name.clone(),
             node_type: NodeType::Exposure,
-            file_path: None,
             description: exposure.description.clone(),
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         for dep in &exposure.depends_on {
-            if let Some(model_name) = parse_exposure_ref(dep) {
-                let dep_id = resolve_ref(&model_name, &gb.node_map);
-                if let Some(&dep_idx) = gb.node_map.get(&dep_id) {
-                    gb.graph.add_edge(
-                        dep_idx,
-                        idx,
-                        EdgeData {
-                            edge_type: EdgeType::Exposure,
-                        },
-                    );
+            let dep_id = match parse_exposure_ref(dep) {
+                Some(ExposureDependency::Ref(model_name)) => {
+                    gb.resolve_model_ref(&model_name, None)
                 }
+                Some(ExposureDependency::Source {
+                    source_name,
+                    table_name,
+                }) => format!("source.{}.{}", source_name, table_name),
+                None => continue,
+            };
+            if let Some(&dep_idx) = gb.node_map.get(&dep_id) {
+                add_unique_edge(
+                    &mut gb.graph,
+                    dep_idx,
+                    idx,
+                    EdgeData {
+                        edge_type: EdgeType::Exposure,
+                        snippet: None,
+                    },
+                );
             }
         }
     }
 }
 
+/// Options controlling how SQL files are interpreted while building the graph
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    /// Casing applied to unquoted column identifiers
+    pub identifier_case: IdentifierCase,
+    /// dbt's compiled SQL directory (`target/compiled/<project>`). When set, a
+    /// file's compiled counterpart is used for column extraction and to find
+    /// relations that macros hide from ref()/source() parsing.
+    pub compiled_dir: Option<PathBuf>,
+}
+
+/// A built graph and the problems found in individual files along the way.
+///
+/// An unreadable file ([`DbtLineageError::FileReadError`]) is skipped, and SQL
+/// with unclosed Jinja tags, unparseable ref()/source() arguments or unbalanced
+/// parentheses ([`DbtLineageError::SqlParseError`]) is parsed as far as
+/// possible, so one bad file never aborts the build.
+pub struct BuildOutput {
+    pub graph: LineageGraph,
+    pub warnings: Vec<DbtLineageError>,
+}
+
 /// Build the lineage graph from discovered files
 pub fn build_graph(project_dir: &Path, files: &DiscoveredFiles) -> Result<LineageGraph> {
+    build_graph_profiled(
+        project_dir,
+        files,
+        &BuildOptions::default(),
+        &mut Profiler::disabled(),
+    )
+}
+
+/// Build the lineage graph with `options`, recording per-stage timings in
+/// `profiler`. File-level problems are printed to stderr as warnings.
+pub fn build_graph_profiled(
+    project_dir: &Path,
+    files: &DiscoveredFiles,
+    options: &BuildOptions,
+    profiler: &mut Profiler,
+) -> Result<LineageGraph> {
+    let output = build_graph_with_warnings(project_dir, files, options, profiler);
+    for warning in &output.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(output.graph)
+}
+
+/// Build the lineage graph like [`build_graph_profiled`], returning file-level
+/// problems instead of printing them
+pub fn build_graph_with_warnings(
+    project_dir: &Path,
+    files: &DiscoveredFiles,
+    options: &BuildOptions,
+    profiler: &mut Profiler,
+) -> BuildOutput {
+    let (contents, read_warnings) =
+        profiler.time("read files", || load_contents(project_dir, files, options));
+    let mut output = build_graph_with_contents(project_dir, files, &contents, options, profiler);
+    output.warnings.splice(0..0, read_warnings);
+    output
+}
+
+/// Build the lineage graph from already-loaded file contents, without touching
+/// the filesystem.
+///
+/// Paths are project-relative and sorted by dbt's default layout: `models/`
+/// (SQL and YAML), `seeds/` (CSV and YAML), `snapshots/` and `tests/`. A
+/// `dbt_project.yml` supplies folder-level model config; other files outside
+/// those directories are ignored.
+pub fn build_graph_from_contents(files: &[(PathBuf, String)]) -> Result<LineageGraph> {
+    let layout = ResolvedPaths {
+        model_paths: vec![PathBuf::from("models")],
+        seed_paths: vec![PathBuf::from("seeds")],
+        snapshot_paths: vec![PathBuf::from("snapshots")],
+        test_paths: vec![PathBuf::from("tests")],
+    };
+    let discovered = classify_files(&layout, files.iter().map(|(path, _)| path.as_path()));
+    let contents: FileContents = files.iter().cloned().collect();
+    Ok(build_graph_with_contents(
+        Path::new(""),
+        &discovered,
+        &contents,
+        &BuildOptions::default(),
+        &mut Profiler::disabled(),
+    )
+    .graph)
+}
+
+/// Shared graph construction over preloaded `contents`
+fn build_graph_with_contents(
+    project_dir: &Path,
+    files: &DiscoveredFiles,
+    contents: &FileContents,
+    options: &BuildOptions,
+    profiler: &mut Profiler,
+) -> BuildOutput {
     let mut gb = GraphBuilder::new();
 
-    let (model_meta, exposures) = process_yaml_files(&mut gb, files)?;
-    process_model_files(&mut gb, files, project_dir, &model_meta);
-    process_simple_nodes(
-        &mut gb,
-        &files.seed_files,
-        project_dir,
-        "seed",
-        NodeType::Seed,
-    );
-    process_simple_nodes(
-        &mut gb,
-        &files.snapshot_sql_files,
-        project_dir,
-        "snapshot",
-        NodeType::Snapshot,
-    );
-    process_sql_edges(&mut gb, files, project_dir)?;
-    process_exposures(&mut gb, &exposures);
+    let folder_configs = contents
+        .get(&project_dir.join("dbt_project.yml"))
+        .and_then(|content| serde_yaml::from_str::<DbtProject>(content).ok())
+        .map(|project| project.folder_configs())
+        .unwrap_or_default();
+    let (model_meta, exposures) = profiler.time("yaml parse", || {
+        process_yaml_files(&mut gb, files, contents, project_dir)
+    });
+    profiler.time("model nodes", || {
+        process_model_files(
+            &mut gb,
+            files,
+            contents,
+            project_dir,
+            &model_meta,
+            &folder_configs,
+            options,
+        );
+        process_simple_nodes(
+            &mut gb,
+            &files.seed_files,
+            contents,
+            project_dir,
+            "seed",
+            NodeType::Seed,
+        );
+        process_simple_nodes(
+            &mut gb,
+            &files.snapshot_sql_files,
+            contents,
+            project_dir,
+            "snapshot",
+            NodeType::Snapshot,
+        );
+    });
+    profiler.time("sql parse/edges", || {
+        process_sql_edges(&mut gb, files, contents, project_dir, options)
+    });
+    profiler.time("exposures", || process_exposures(&mut gb, &exposures));
 
-    Ok(gb.graph)
+    gb.finish()
 }
 
 /// Try to resolve a ref name to a node unique_id
@@ -429,17 +1009,32 @@ fn resolve_ref(name: &str, node_map: &HashMap<String, NodeIndex>) -> String {
     model_id
 }
 
+/// A dependency listed under an exposure's `depends_on`
+#[derive(Debug, PartialEq, Eq)]
+enum ExposureDependency {
+    Ref(String),
+    Source {
+        source_name: String,
+        table_name: String,
+    },
+}
+
 /// Parse a ref('name') or source('src', 'table') string from exposure depends_on
-fn parse_exposure_ref(dep: &str) -> Option<String> {
+fn parse_exposure_ref(dep: &str) -> Option<ExposureDependency> {
+    let unquote = |s: &str| s.trim().trim_matches('\'').trim_matches('"').to_string();
     let dep = dep.trim();
-    if dep.starts_with("ref(") {
+    if let Some(inner) = dep.strip_prefix("ref(") {
         // Extract name from ref('name')
-        let inner = dep.trim_start_matches("ref(").trim_end_matches(')');
-        let name = inner.trim().trim_matches('\'').trim_matches('"');
-        Some(name.to_string())
-    } else if dep.starts_with("source(") {
-        // For sources in exposures, we won't create edges here for simplicity
-        None
+        Some(ExposureDependency::Ref(unquote(
+            inner.trim_end_matches(')'),
+        )))
+    } else if let Some(inner) = dep.strip_prefix("source(") {
+        // Extract both arguments from source('src', 'table')
+        let (source_name, table_name) = inner.trim_end_matches(')').split_once(',')?;
+        Some(ExposureDependency::Source {
+            source_name: unquote(source_name),
+            table_name: unquote(table_name),
+        })
     } else {
         None
     }
@@ -460,11 +1055,7 @@ mod tests {
             unique_id: "model.orders".to_string(),
             label: "orders".to_string(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         node_map.insert("model.orders".to_string(), idx);
 
@@ -479,11 +1070,7 @@ mod tests {
             unique_id: "seed.countries".to_string(),
             label: "countries".to_string(),
             node_type: NodeType::Seed,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         node_map.insert("seed.countries".to_string(), idx);
 
@@ -498,11 +1085,7 @@ mod tests {
             unique_id: "snapshot.snap_orders".to_string(),
             label: "snap_orders".to_string(),
             node_type: NodeType::Snapshot,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         node_map.insert("snapshot.snap_orders".to_string(), idx);
 
@@ -522,13 +1105,20 @@ mod tests {
     fn test_parse_exposure_ref() {
         assert_eq!(
             parse_exposure_ref("ref('orders')"),
-            Some("orders".to_string())
+            Some(ExposureDependency::Ref("orders".to_string()))
         );
         assert_eq!(
             parse_exposure_ref("ref(\"orders\")"),
-            Some("orders".to_string())
+            Some(ExposureDependency::Ref("orders".to_string()))
+        );
+        assert_eq!(
+            parse_exposure_ref("source('raw', \"orders\")"),
+            Some(ExposureDependency::Source {
+                source_name: "raw".to_string(),
+                table_name: "orders".to_string(),
+            })
         );
-        assert_eq!(parse_exposure_ref("source('raw', 'orders')"), None);
+        assert_eq!(parse_exposure_ref("source('raw')"), None);
         assert_eq!(parse_exposure_ref("something_else"), None);
     }
 
@@ -629,17 +1219,44 @@ models:
         let snap_dir = project_dir.join("snapshots");
         fs::create_dir_all(&snap_dir).unwrap();
         fs::write(snap_dir.join("snap_orders.sql"), "SELECT 1").unwrap();
+        fs::write(
+            snap_dir.join("snap_customers.sql"),
+            "{% snapshot snap_customers %}\n\
+             {{ config(target_schema='snapshots', strategy='check', \
+             unique_key=['id', 'region'], check_cols='all') }}\n\
+             select * from {{ source('raw', 'customers') }}\n\
+             {% endsnapshot %}",
+        )
+        .unwrap();
 
         let files = DiscoveredFiles {
-            snapshot_sql_files: vec![project_dir.join("snapshots/snap_orders.sql")],
+            snapshot_sql_files: vec![
+                project_dir.join("snapshots/snap_orders.sql"),
+                project_dir.join("snapshots/snap_customers.sql"),
+            ],
             ..Default::default()
         };
 
         let graph = build_graph(&project_dir, &files).unwrap();
-        assert_eq!(graph.node_count(), 1);
-        let node = &graph[graph.node_indices().next().unwrap()];
-        assert_eq!(node.node_type, NodeType::Snapshot);
-        assert_eq!(node.label, "snap_orders");
+        let node = |label: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].label == label)
+                .unwrap();
+            graph[idx].clone()
+        };
+        let orders = node("snap_orders");
+        assert_eq!(orders.node_type, NodeType::Snapshot);
+        assert_eq!(orders.snapshot, None);
+        assert_eq!(
+            node("snap_customers").snapshot,
+            Some(SnapshotConfig {
+                strategy: Some("check".into()),
+                unique_key: vec!["id".into(), "region".into()],
+                updated_at: None,
+                check_cols: vec!["all".into()],
+            })
+        );
     }
 
     #[test]
@@ -670,6 +1287,35 @@ models:
         assert_eq!(graph.node_count(), 2);
         // ref edge: stg_orders → assert_positive
         assert_eq!(graph.edge_count(), 1);
+        let test = graph
+            .node_indices()
+            .find(|&i| graph[i].node_type == NodeType::Test)
+            .unwrap();
+        assert_eq!(graph[test].test_kind, Some(TestKind::Singular));
+    }
+
+    #[test]
+    fn test_sql_test_kind() {
+        assert_eq!(
+            sql_test_kind(Path::new("tests/assert_positive.sql"), "SELECT 1"),
+            TestKind::Singular
+        );
+        assert_eq!(
+            sql_test_kind(Path::new("tests/generic/not_negative.sql"), "SELECT 1"),
+            TestKind::Generic
+        );
+        assert_eq!(
+            sql_test_kind(
+                Path::new("tests/is_even.sql"),
+                "{% test is_even(model, column_name) %} SELECT 1 {% endtest %}"
+            ),
+            TestKind::Generic
+        );
+        // Only directories count, not the file name
+        assert_eq!(
+            sql_test_kind(Path::new("tests/generic.sql"), "SELECT 1"),
+            TestKind::Singular
+        );
     }
 
     #[test]
@@ -708,6 +1354,32 @@ exposures:
         assert_eq!(graph.edge_count(), 1);
     }
 
+    #[test]
+    fn test_build_graph_exposure_depending_on_source() {
+        let schema = r#"
+version: 2
+sources:
+  - name: raw
+    tables:
+      - name: orders
+exposures:
+  - name: ops_dashboard
+    depends_on:
+      - source('raw', 'orders')
+      - source('raw', 'missing')
+"#;
+        let files = vec![(PathBuf::from("models/schema.yml"), schema.to_string())];
+        let graph = build_graph_from_contents(&files).unwrap();
+
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+        let edge = graph.edge_indices().next().unwrap();
+        let (source, target) = graph.edge_endpoints(edge).unwrap();
+        assert_eq!(graph[source].unique_id, "source.raw.orders");
+        assert_eq!(graph[target].unique_id, "exposure.ops_dashboard");
+        assert_eq!(graph[edge].edge_type, EdgeType::Exposure);
+    }
+
     #[test]
     fn test_build_graph_phantom_node_for_unresolved_ref() {
         let (_tmp, project_dir) = setup_temp_project();
@@ -735,6 +1407,93 @@ exposures:
         assert_eq!(graph[phantom].label, "nonexistent_model");
     }
 
+    #[test]
+    fn test_build_graph_phantom_lists_referencing_files() {
+        let (_tmp, project_dir) = setup_temp_project();
+
+        let models_dir = project_dir.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+        for name in ["orders", "payments"] {
+            fs::write(
+                models_dir.join(format!("{}.sql", name)),
+                "SELECT * FROM {{ ref('stg_ordres') }} JOIN {{ ref('stg_ordres') }}",
+            )
+            .unwrap();
+        }
+        fs::write(
+            models_dir.join("customers.sql"),
+            "SELECT * FROM {{ source('crm', 'accounts') }}",
+        )
+        .unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                project_dir.join("models/orders.sql"),
+                project_dir.join("models/payments.sql"),
+                project_dir.join("models/customers.sql"),
+            ],
+            ..Default::default()
+        };
+
+        let graph = build_graph(&project_dir, &files).unwrap();
+        let description = |unique_id: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == unique_id)
+                .unwrap();
+            graph[idx].description.clone().unwrap()
+        };
+        assert_eq!(
+            description("model.stg_ordres"),
+            "Unresolved ref() referenced by 2 files: models/orders.sql, models/payments.sql"
+        );
+        assert_eq!(
+            description("source.crm.accounts"),
+            "Undeclared source() referenced by 1 file: models/customers.sql"
+        );
+        // Real models are not annotated
+        assert!(graph
+            .node_indices()
+            .filter(|&i| graph[i].node_type == NodeType::Model)
+            .all(|i| graph[i].description.is_none()));
+    }
+
+    #[test]
+    fn test_build_graph_phantom_suggests_close_model_names() {
+        let (_tmp, project_dir) = setup_temp_project();
+
+        let models_dir = project_dir.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+        fs::write(models_dir.join("stg_orders.sql"), "SELECT 1 AS id").unwrap();
+        fs::write(
+            models_dir.join("orders.sql"),
+            "SELECT * FROM {{ ref('stg_ordres') }} JOIN {{ ref('totally_unknown') }}",
+        )
+        .unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                project_dir.join("models/stg_orders.sql"),
+                project_dir.join("models/orders.sql"),
+            ],
+            ..Default::default()
+        };
+
+        let graph = build_graph(&project_dir, &files).unwrap();
+        let description = |unique_id: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == unique_id)
+                .unwrap();
+            graph[idx].description.clone().unwrap()
+        };
+        assert_eq!(
+            description("model.stg_ordres"),
+            "Unresolved ref() referenced by 1 file: models/orders.sql. Did you mean: stg_orders?"
+        );
+        assert!(!description("model.totally_unknown").contains("Did you mean"));
+    }
+
     #[test]
     fn test_build_graph_phantom_node_for_unresolved_source() {
         let (_tmp, project_dir) = setup_temp_project();
@@ -781,12 +1540,171 @@ exposures:
     }
 
     #[test]
-    fn test_build_graph_edge_types() {
-        use petgraph::visit::IntoEdgeReferences;
-
-        let (_tmp, project_dir) = setup_temp_project();
-
-        let files = DiscoveredFiles {
+    fn test_build_graph_column_descriptions() {
+        let schema = r#"
+version: 2
+sources:
+  - name: raw
+    tables:
+      - name: orders
+        columns:
+          - name: id
+            description: Source key
+models:
+  - name: stg_orders
+    columns:
+      - name: order_id
+        description: Order key
+      - name: status
+        description: ""
+"#;
+        let files = vec![
+            (PathBuf::from("models/schema.yml"), schema.to_string()),
+            (
+                PathBuf::from("models/stg_orders.sql"),
+                "SELECT id AS order_id, status FROM {{ source('raw', 'orders') }}".to_string(),
+            ),
+        ];
+        let graph = build_graph_from_contents(&files).unwrap();
+        let node = |id: &str| {
+            &graph[graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap()]
+        };
+
+        let stg = &node("model.stg_orders").column_descriptions;
+        assert_eq!(stg.len(), 1);
+        assert_eq!(stg["order_id"], "Order key");
+        assert_eq!(
+            node("source.raw.orders").column_descriptions["id"],
+            "Source key"
+        );
+    }
+
+    #[test]
+    fn test_build_graph_schema() {
+        let project = r#"
+name: shop
+models:
+  shop:
+    marts:
+      +schema: marts
+"#;
+        let schema_yml = r#"
+version: 2
+sources:
+  - name: raw
+    tables:
+      - name: orders
+  - name: stripe
+    schema: stripe_v2
+    tables:
+      - name: payments
+models:
+  - name: revenue
+    config:
+      schema: finance
+"#;
+        let files = vec![
+            (PathBuf::from("dbt_project.yml"), project.to_string()),
+            (PathBuf::from("models/schema.yml"), schema_yml.to_string()),
+            (
+                PathBuf::from("models/staging/stg_orders.sql"),
+                "SELECT 1".to_string(),
+            ),
+            (
+                PathBuf::from("models/marts/orders.sql"),
+                "SELECT 1".to_string(),
+            ),
+            (
+                PathBuf::from("models/marts/revenue.sql"),
+                "SELECT 1".to_string(),
+            ),
+            (
+                PathBuf::from("models/marts/refunds.sql"),
+                "{{ config(schema='finance_sql') }} SELECT 1".to_string(),
+            ),
+        ];
+        let graph = build_graph_from_contents(&files).unwrap();
+        let schema = |id: &str| {
+            graph[graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap()]
+            .schema
+            .clone()
+        };
+
+        assert_eq!(schema("model.stg_orders"), None);
+        assert_eq!(schema("model.orders").as_deref(), Some("marts"));
+        assert_eq!(schema("model.revenue").as_deref(), Some("finance"));
+        assert_eq!(schema("model.refunds").as_deref(), Some("finance_sql"));
+        // A source's schema defaults to its name
+        assert_eq!(schema("source.raw.orders").as_deref(), Some("raw"));
+        assert_eq!(
+            schema("source.stripe.payments").as_deref(),
+            Some("stripe_v2")
+        );
+    }
+
+    #[test]
+    fn test_build_graph_folder_config_from_project_file() {
+        let project = r#"
+name: shop
+models:
+  shop:
+    +materialized: view
+    +tags: core
+    marts:
+      +materialized: table
+      +tags: ["marts"]
+      finance:
+        +tags: finance
+  some_package:
+    +materialized: ephemeral
+"#;
+        let files = vec![
+            (PathBuf::from("dbt_project.yml"), project.to_string()),
+            (
+                PathBuf::from("models/staging/stg_orders.sql"),
+                "SELECT 1".to_string(),
+            ),
+            (
+                PathBuf::from("models/marts/finance/revenue.sql"),
+                "{{ config(tags=['daily']) }} SELECT 1".to_string(),
+            ),
+            (
+                PathBuf::from("models/marts/orders.sql"),
+                "{{ config(materialized='incremental') }} SELECT 1".to_string(),
+            ),
+        ];
+        let graph = build_graph_from_contents(&files).unwrap();
+        let node = |id: &str| {
+            &graph[graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap()]
+        };
+
+        let stg = node("model.stg_orders");
+        assert_eq!(stg.materialization.as_deref(), Some("view"));
+        assert_eq!(stg.tags, ["core"]);
+        let revenue = node("model.revenue");
+        assert_eq!(revenue.materialization.as_deref(), Some("table"));
+        assert_eq!(revenue.tags, ["core", "daily", "finance", "marts"]);
+        // The model's own config() wins over the folder default
+        let orders = node("model.orders");
+        assert_eq!(orders.materialization.as_deref(), Some("incremental"));
+    }
+
+    #[test]
+    fn test_build_graph_edge_types() {
+        use petgraph::visit::IntoEdgeReferences;
+
+        let (_tmp, project_dir) = setup_temp_project();
+
+        let files = DiscoveredFiles {
             model_sql_files: vec![
                 project_dir.join("models/stg_orders.sql"),
                 project_dir.join("models/orders.sql"),
@@ -804,6 +1722,251 @@ exposures:
         assert!(edge_types.contains(&EdgeType::Ref));
     }
 
+    #[test]
+    fn test_repeated_refs_collapse_to_one_edge() {
+        let files = vec![
+            (
+                PathBuf::from("models/sources.yml"),
+                "version: 2\nsources:\n  - name: raw\n    tables:\n      - name: orders\n"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("models/stg_orders.sql"),
+                "SELECT * FROM {{ source('raw', 'orders') }} UNION ALL SELECT * FROM {{ source('raw', 'orders') }}"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("models/orders.sql"),
+                "WITH base AS (SELECT * FROM {{ ref('stg_orders') }})\n\
+                 SELECT * FROM base JOIN {{ ref('stg_orders') }} USING (id)"
+                    .to_string(),
+            ),
+        ];
+
+        let graph = build_graph_from_contents(&files).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_sql_edges_carry_snippet_of_first_call() {
+        let files = vec![
+            (PathBuf::from("models/stg_orders.sql"), "SELECT 1".to_string()),
+            (
+                PathBuf::from("models/orders.sql"),
+                "WITH base AS (\n  SELECT * FROM {{ ref('stg_orders') }}\n)\nSELECT * FROM {{ ref('stg_orders') }}"
+                    .to_string(),
+            ),
+        ];
+
+        let graph = build_graph_from_contents(&files).unwrap();
+        let edge = graph.edge_indices().next().unwrap();
+        assert_eq!(
+            graph[edge].snippet,
+            Some(EdgeSnippet {
+                line: 2,
+                text: "SELECT * FROM {{ ref('stg_orders') }}".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_graph_from_contents() {
+        let files = vec![
+            (
+                PathBuf::from("models/schema.yml"),
+                "version: 2\nsources:\n  - name: raw\n    tables:\n      - name: orders\n"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("models/staging/stg_orders.sql"),
+                "SELECT id FROM {{ source('raw', 'orders') }}".to_string(),
+            ),
+            (
+                PathBuf::from("models/orders.sql"),
+                "SELECT * FROM {{ ref('stg_orders') }} JOIN {{ ref('countries') }}".to_string(),
+            ),
+            (PathBuf::from("seeds/countries.csv"), "id\n1".to_string()),
+            (
+                PathBuf::from("tests/assert_orders.sql"),
+                "SELECT * FROM {{ ref('orders') }}".to_string(),
+            ),
+            (PathBuf::from("README.md"), "# ignored".to_string()),
+        ];
+
+        let graph = build_graph_from_contents(&files).unwrap();
+        assert_eq!(graph.node_count(), 5);
+        assert_eq!(graph.edge_count(), 4);
+
+        let stg = graph
+            .node_indices()
+            .find(|&i| graph[i].unique_id == "model.stg_orders")
+            .unwrap();
+        assert_eq!(graph[stg].columns, vec!["id"]);
+        assert_eq!(
+            graph[stg].file_path.as_deref(),
+            Some(Path::new("models/staging/stg_orders.sql"))
+        );
+        assert!(graph
+            .node_indices()
+            .all(|i| graph[i].node_type != NodeType::Phantom));
+    }
+
+    #[test]
+    fn test_build_graph_from_contents_matches_disk() {
+        let (_tmp, project_dir) = setup_temp_project();
+        let paths = [
+            "models/stg_orders.sql",
+            "models/orders.sql",
+            "models/schema.yml",
+        ];
+        let files = DiscoveredFiles {
+            model_sql_files: vec![project_dir.join(paths[0]), project_dir.join(paths[1])],
+            yaml_files: vec![project_dir.join(paths[2])],
+            ..Default::default()
+        };
+        let from_disk = build_graph(&project_dir, &files).unwrap();
+
+        let contents: Vec<_> = paths
+            .iter()
+            .map(|p| {
+                (
+                    PathBuf::from(p),
+                    fs::read_to_string(project_dir.join(p)).unwrap(),
+                )
+            })
+            .collect();
+        let from_contents = build_graph_from_contents(&contents).unwrap();
+
+        let summary = |g: &LineageGraph| {
+            let mut nodes: Vec<_> = g
+                .node_indices()
+                .map(|i| (g[i].unique_id.clone(), g[i].file_path.clone()))
+                .collect();
+            nodes.sort();
+            (nodes, g.edge_count())
+        };
+        assert_eq!(summary(&from_disk), summary(&from_contents));
+    }
+
+    #[test]
+    fn test_build_graph_profiled_records_stages() {
+        let (_tmp, project_dir) = setup_temp_project();
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                project_dir.join("models/stg_orders.sql"),
+                project_dir.join("models/orders.sql"),
+            ],
+            yaml_files: vec![project_dir.join("models/schema.yml")],
+            ..Default::default()
+        };
+
+        let mut profiler = Profiler::new(true);
+        let graph = build_graph_profiled(
+            &project_dir,
+            &files,
+            &BuildOptions::default(),
+            &mut profiler,
+        )
+        .unwrap();
+        assert_eq!(graph.node_count(), 3);
+        let names: Vec<_> = profiler.stages().iter().map(|s| s.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "read files",
+                "yaml parse",
+                "model nodes",
+                "sql parse/edges",
+                "exposures"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_graph_identifier_case_lower() {
+        let tmp = tempfile::tempdir().unwrap();
+        let models_dir = tmp.path().join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+        fs::write(
+            models_dir.join("orders.sql"),
+            r#"SELECT Customer_ID, "Order_ID" FROM raw"#,
+        )
+        .unwrap();
+        let files = DiscoveredFiles {
+            model_sql_files: vec![models_dir.join("orders.sql")],
+            ..Default::default()
+        };
+
+        let options = BuildOptions {
+            identifier_case: IdentifierCase::Lower,
+            ..Default::default()
+        };
+        let graph =
+            build_graph_profiled(tmp.path(), &files, &options, &mut Profiler::disabled()).unwrap();
+        let idx = graph.node_indices().next().unwrap();
+        assert_eq!(graph[idx].columns, vec!["customer_id", "Order_ID"]);
+
+        let graph = build_graph(tmp.path(), &files).unwrap();
+        let idx = graph.node_indices().next().unwrap();
+        assert_eq!(graph[idx].columns, vec!["Customer_ID", "Order_ID"]);
+    }
+
+    #[test]
+    fn test_build_graph_with_warnings_keeps_going() {
+        let (_tmp, project_dir) = setup_temp_project();
+        let models_dir = project_dir.join("models");
+        fs::write(
+            models_dir.join("broken.sql"),
+            "SELECT *\nFROM {{ ref('orders') }}\nWHERE id IN (1, 2\n",
+        )
+        .unwrap();
+        fs::write(models_dir.join("binary.sql"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                models_dir.join("stg_orders.sql"),
+                models_dir.join("orders.sql"),
+                models_dir.join("broken.sql"),
+                models_dir.join("binary.sql"),
+            ],
+            yaml_files: vec![models_dir.join("schema.yml")],
+            ..Default::default()
+        };
+        let output = build_graph_with_warnings(
+            &project_dir,
+            &files,
+            &BuildOptions::default(),
+            &mut Profiler::disabled(),
+        );
+
+        assert_eq!(output.warnings.len(), 2);
+        assert!(matches!(
+            &output.warnings[0],
+            DbtLineageError::FileReadError { path, .. } if path.ends_with("binary.sql")
+        ));
+        match &output.warnings[1] {
+            DbtLineageError::SqlParseError { path, detail } => {
+                assert_eq!(path, &PathBuf::from("models/broken.sql"));
+                assert_eq!(detail, "unclosed `(` on line 3");
+            }
+            other => panic!("unexpected warning: {other}"),
+        }
+
+        // The broken file's ref is still picked up
+        let broken = output
+            .graph
+            .node_indices()
+            .find(|&i| output.graph[i].unique_id == "model.broken")
+            .unwrap();
+        let orders = output
+            .graph
+            .node_indices()
+            .find(|&i| output.graph[i].unique_id == "model.orders")
+            .unwrap();
+        assert!(output.graph.find_edge(orders, broken).is_some());
+    }
+
     #[test]
     fn test_build_graph_empty_files() {
         let tmp = tempfile::tempdir().unwrap();
@@ -858,9 +2021,50 @@ models:
         assert!(graph[stg].tags.contains(&"daily".to_string()));
     }
 
+    #[test]
+    fn test_build_graph_meta_merge() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project_dir = tmp.path().to_path_buf();
+        let models_dir = project_dir.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+
+        fs::write(
+            models_dir.join("orders.sql"),
+            "{{ config(meta={'sla_hours': 6}) }}\nSELECT 1",
+        )
+        .unwrap();
+        fs::write(
+            models_dir.join("schema.yml"),
+            r#"
+version: 2
+models:
+  - name: orders
+    meta:
+      owner: finance
+      sla_hours: 48
+    config:
+      meta:
+        tier: gold
+"#,
+        )
+        .unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![project_dir.join("models/orders.sql")],
+            yaml_files: vec![project_dir.join("models/schema.yml")],
+            ..Default::default()
+        };
+        let graph = build_graph(&project_dir, &files).unwrap();
+        let orders = graph.node_indices().next().unwrap();
+        let meta = &graph[orders].meta;
+        // SQL config wins over YAML, which merges model-level and config meta
+        assert_eq!(meta["sla_hours"], 6);
+        assert_eq!(meta["owner"], "finance");
+        assert_eq!(meta["tier"], "gold");
+    }
+
     #[test]
     fn test_build_graph_duplicate_model_name() {
-        // Covers line 197: duplicate model name warning
         let tmp = tempfile::tempdir().unwrap();
         let project_dir = tmp.path().to_path_buf();
 
@@ -887,5 +2091,256 @@ models:
             .filter(|&i| graph[i].label == "orders")
             .collect();
         assert_eq!(order_nodes.len(), 2);
+
+        let mut ids: Vec<&str> = order_nodes
+            .iter()
+            .map(|&i| graph[i].unique_id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["model.orders", "model.orders__models_subdir"]);
+    }
+
+    #[test]
+    fn test_build_graph_versioned_models() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project_dir = tmp.path().to_path_buf();
+        let models_dir = project_dir.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+
+        fs::write(
+            models_dir.join("schema.yml"),
+            r#"
+models:
+  - name: dim_customers
+    latest_version: 1
+    versions:
+      - v: 1
+        defined_in: dim_customers
+      - v: 2
+"#,
+        )
+        .unwrap();
+        fs::write(models_dir.join("dim_customers.sql"), "SELECT 1 AS id").unwrap();
+        fs::write(models_dir.join("dim_customers_v2.sql"), "SELECT 2 AS id").unwrap();
+        fs::write(
+            models_dir.join("orders.sql"),
+            "SELECT * FROM {{ ref('dim_customers') }}",
+        )
+        .unwrap();
+        fs::write(
+            models_dir.join("orders_next.sql"),
+            "SELECT * FROM {{ ref('dim_customers', v=2) }}\nJOIN {{ ref('dim_customers', v=3) }}",
+        )
+        .unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                models_dir.join("dim_customers.sql"),
+                models_dir.join("dim_customers_v2.sql"),
+                models_dir.join("orders.sql"),
+                models_dir.join("orders_next.sql"),
+            ],
+            yaml_files: vec![models_dir.join("schema.yml")],
+            ..Default::default()
+        };
+        let graph = build_graph(&project_dir, &files).unwrap();
+
+        let find = |id: &str| {
+            graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap_or_else(|| panic!("missing node {}", id))
+        };
+        let v1 = find("model.dim_customers.v1");
+        let v2 = find("model.dim_customers.v2");
+        assert_eq!(graph[v1].label, "dim_customers");
+        assert_eq!(graph[v2].label, "dim_customers");
+        assert_eq!(graph[v1].version.as_deref(), Some("1"));
+        assert_eq!(graph[v2].display_name(), "dim_customers (v2)");
+
+        // Unpinned ref follows latest_version; pinned refs hit the exact version
+        assert!(graph.contains_edge(v1, find("model.orders")));
+        assert!(graph.contains_edge(v2, find("model.orders_next")));
+
+        // Missing versions become versioned phantoms
+        let v3 = find("model.dim_customers.v3");
+        assert_eq!(graph[v3].node_type, NodeType::Phantom);
+        assert_eq!(graph[v3].version.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_build_graph_duplicate_ref_resolves_to_shallowest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project_dir = tmp.path().to_path_buf();
+
+        let models_dir = project_dir.join("models");
+        let subdir = models_dir.join("subdir");
+        fs::create_dir_all(&subdir).unwrap();
+
+        fs::write(subdir.join("orders.sql"), "SELECT 2").unwrap();
+        fs::write(models_dir.join("orders.sql"), "SELECT 1").unwrap();
+        fs::write(
+            models_dir.join("report.sql"),
+            "SELECT * FROM {{ ref('orders') }}",
+        )
+        .unwrap();
+
+        // Deeper file listed first: resolution must not depend on walk order
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                project_dir.join("models/subdir/orders.sql"),
+                project_dir.join("models/orders.sql"),
+                project_dir.join("models/report.sql"),
+            ],
+            ..Default::default()
+        };
+
+        let graph = build_graph(&project_dir, &files).unwrap();
+        let report = graph
+            .node_indices()
+            .find(|&i| graph[i].label == "report")
+            .unwrap();
+        let parents: Vec<_> = graph
+            .neighbors_directed(report, petgraph::Direction::Incoming)
+            .collect();
+        assert_eq!(parents.len(), 1);
+        assert_eq!(graph[parents[0]].unique_id, "model.orders");
+        assert_eq!(
+            graph[parents[0]].file_path,
+            Some(PathBuf::from("models/orders.sql"))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_names_with_colliding_path_suffixes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project_dir = tmp.path().to_path_buf();
+        let models_dir = project_dir.join("models");
+        fs::create_dir_all(models_dir.join("a_b")).unwrap();
+        fs::create_dir_all(models_dir.join("a/b")).unwrap();
+        fs::write(models_dir.join("orders.sql"), "SELECT 1").unwrap();
+        fs::write(models_dir.join("a_b/orders.sql"), "SELECT 2").unwrap();
+        fs::write(models_dir.join("a/b/orders.sql"), "SELECT 3").unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                project_dir.join("models/orders.sql"),
+                project_dir.join("models/a/b/orders.sql"),
+                project_dir.join("models/a_b/orders.sql"),
+            ],
+            ..Default::default()
+        };
+        let graph = build_graph(&project_dir, &files).unwrap();
+        let mut ids: Vec<(String, PathBuf)> = graph
+            .node_indices()
+            .map(|i| {
+                (
+                    graph[i].unique_id.clone(),
+                    graph[i].file_path.clone().unwrap(),
+                )
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            vec![
+                ("model.orders".into(), PathBuf::from("models/orders.sql")),
+                (
+                    "model.orders__models_a_b".into(),
+                    PathBuf::from("models/a/b/orders.sql")
+                ),
+                (
+                    "model.orders__models_a_b_2".into(),
+                    PathBuf::from("models/a_b/orders.sql")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_suffix() {
+        assert_eq!(
+            path_suffix(Path::new("models/finance/orders.sql")),
+            "models_finance"
+        );
+        assert_eq!(path_suffix(Path::new("orders.sql")), "root");
+    }
+
+    #[test]
+    fn test_build_graph_with_compiled_sql() {
+        let (_tmp, project_dir) = setup_temp_project();
+        let models_dir = project_dir.join("models");
+        // The ref to stg_orders is hidden in a macro
+        fs::write(
+            models_dir.join("revenue.sql"),
+            "SELECT {{ cents_to_dollars('amount') }} AS amount FROM {{ staged('orders') }}",
+        )
+        .unwrap();
+
+        let compiled_dir = project_dir.join("target/compiled/my_project");
+        fs::create_dir_all(compiled_dir.join("models")).unwrap();
+        fs::write(
+            compiled_dir.join("models/revenue.sql"),
+            "-- FROM analytics.stg_payments\n\
+             select amount / 100 as amount_usd\n\
+             from \"db\".\"analytics\".\"stg_orders\" o\n\
+             join raw_db.raw.orders r on true",
+        )
+        .unwrap();
+
+        let files = DiscoveredFiles {
+            model_sql_files: vec![
+                project_dir.join("models/stg_orders.sql"),
+                project_dir.join("models/orders.sql"),
+                project_dir.join("models/revenue.sql"),
+            ],
+            yaml_files: vec![project_dir.join("models/schema.yml")],
+            ..Default::default()
+        };
+        let find = |graph: &LineageGraph, id: &str| {
+            graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap()
+        };
+
+        // Raw SQL only: no dependencies and the macro hides the column alias
+        let graph = build_graph(&project_dir, &files).unwrap();
+        let revenue = find(&graph, "model.revenue");
+        assert_eq!(
+            graph
+                .neighbors_directed(revenue, petgraph::Direction::Incoming)
+                .count(),
+            0
+        );
+
+        let options = BuildOptions {
+            compiled_dir: Some(compiled_dir),
+            ..Default::default()
+        };
+        let graph = build_graph_profiled(&project_dir, &files, &options, &mut Profiler::disabled())
+            .unwrap();
+        let revenue = find(&graph, "model.revenue");
+        let stg = find(&graph, "model.stg_orders");
+        let source = find(&graph, "source.raw.orders");
+        assert_eq!(graph[revenue].columns, vec!["amount_usd"]);
+        let edge = graph.find_edge(stg, revenue).unwrap();
+        assert_eq!(graph[edge].edge_type, EdgeType::Ref);
+        let edge = graph.find_edge(source, revenue).unwrap();
+        assert_eq!(graph[edge].edge_type, EdgeType::Source);
+        // Commented-out relations are ignored, and no phantoms are created
+        assert_eq!(
+            graph
+                .neighbors_directed(revenue, petgraph::Direction::Incoming)
+                .count(),
+            2
+        );
+        assert!(graph
+            .node_indices()
+            .all(|i| graph[i].node_type != NodeType::Phantom));
+
+        // orders has a raw ref to stg_orders; no compiled file, no duplicate edge
+        let orders = find(&graph, "model.orders");
+        assert_eq!(graph.edges_connecting(stg, orders).count(), 1);
     }
 }
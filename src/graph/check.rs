@@ -0,0 +1,472 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use serde::Serialize;
+
+use crate::error::DbtLineageError;
+use crate::graph::diff::{edge_type_str, DiffStatus, LineageDiff};
+use crate::graph::filter::{list_nodes, Selector};
+use crate::graph::impact::compute_impact;
+use crate::graph::types::*;
+
+/// A changed model and the size of its downstream closure
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedModelRadius {
+    pub unique_id: String,
+    pub label: String,
+    pub status: DiffStatus,
+    /// Number of nodes downstream of the model (transitively)
+    pub downstream: usize,
+}
+
+/// Result of checking a diff against a blast-radius budget
+#[derive(Debug, Clone, Serialize)]
+pub struct BlastRadiusReport {
+    pub base_ref: String,
+    pub head_ref: String,
+    pub threshold: usize,
+    /// Every changed model, largest blast radius first (ties by unique_id)
+    pub checked: Vec<ChangedModelRadius>,
+    /// Changed models whose downstream closure exceeds `threshold`
+    pub violations: Vec<ChangedModelRadius>,
+}
+
+impl BlastRadiusReport {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Check every model added, modified or removed in `diff` against `threshold`.
+///
+/// Added and modified models are measured in `head_graph`; removed models are
+/// measured in `base_graph`, where their dependents still point at them.
+pub fn check_blast_radius(
+    diff: &LineageDiff,
+    base_graph: &LineageGraph,
+    head_graph: &LineageGraph,
+    threshold: usize,
+) -> BlastRadiusReport {
+    let mut checked: Vec<ChangedModelRadius> = diff
+        .nodes
+        .iter()
+        .filter(|n| n.status != DiffStatus::Unchanged)
+        .filter(|n| n.node_type == NodeType::Model.label())
+        .filter_map(|n| {
+            let graph = if n.status == DiffStatus::Removed {
+                base_graph
+            } else {
+                head_graph
+            };
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == n.unique_id)?;
+            Some(ChangedModelRadius {
+                unique_id: n.unique_id.clone(),
+                label: n.label.clone(),
+                status: n.status,
+                downstream: compute_impact(graph, idx).impacted_nodes.len(),
+            })
+        })
+        .collect();
+
+    checked.sort_by(|a, b| {
+        b.downstream
+            .cmp(&a.downstream)
+            .then_with(|| a.unique_id.cmp(&b.unique_id))
+    });
+    let violations = checked
+        .iter()
+        .filter(|c| c.downstream > threshold)
+        .cloned()
+        .collect();
+
+    BlastRadiusReport {
+        base_ref: diff.base_ref.clone(),
+        head_ref: diff.head_ref.clone(),
+        threshold,
+        checked,
+        violations,
+    }
+}
+
+/// An edge running from a later architectural layer back into an earlier one
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerViolation {
+    pub source: String,
+    pub source_layer: String,
+    pub target: String,
+    pub target_layer: String,
+    pub edge_type: &'static str,
+}
+
+/// Result of checking the graph's edges against a layer order
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerReport {
+    /// Layer tags, earliest first
+    pub layers: Vec<String>,
+    /// Number of nodes tagged with one of the layers
+    pub layered_nodes: usize,
+    /// Backward edges, sorted by source then target
+    pub violations: Vec<LayerViolation>,
+}
+
+impl LayerReport {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Parse a comma-separated layer order such as `staging,intermediate,marts`
+pub fn parse_layer_order(input: &str) -> Result<Vec<String>> {
+    let mut layers: Vec<String> = Vec::new();
+    for layer in input.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if layers.iter().any(|l| l == layer) {
+            return Err(DbtLineageError::InvalidArgument(format!(
+                "layer '{}' appears more than once in --layer-order",
+                layer
+            ))
+            .into());
+        }
+        layers.push(layer.to_string());
+    }
+    if layers.is_empty() {
+        return Err(DbtLineageError::InvalidArgument(
+            "--layer-order needs at least one layer tag".into(),
+        )
+        .into());
+    }
+    Ok(layers)
+}
+
+/// Check that no edge goes from a later layer to an earlier one.
+///
+/// A node's layer is the earliest entry of `layers` among its tags; nodes
+/// without a layer tag are not checked. Edges within a layer or towards a later
+/// layer are allowed.
+pub fn check_layer_order(graph: &LineageGraph, layers: &[String]) -> LayerReport {
+    let layer_of = |node: &NodeData| layers.iter().position(|l| node.tags.contains(l));
+
+    let layered_nodes = graph
+        .node_indices()
+        .filter(|&i| layer_of(&graph[i]).is_some())
+        .count();
+
+    let mut violations: Vec<LayerViolation> = graph
+        .edge_references()
+        .filter_map(|edge| {
+            let source = &graph[edge.source()];
+            let target = &graph[edge.target()];
+            let source_layer = layer_of(source)?;
+            let target_layer = layer_of(target)?;
+            (source_layer > target_layer).then(|| LayerViolation {
+                source: source.unique_id.clone(),
+                source_layer: layers[source_layer].clone(),
+                target: target.unique_id.clone(),
+                target_layer: layers[target_layer].clone(),
+                edge_type: edge_type_str(edge.weight().edge_type),
+            })
+        })
+        .collect();
+    violations.sort_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+
+    LayerReport {
+        layers: layers.to_vec(),
+        layered_nodes,
+        violations,
+    }
+}
+
+/// A model without a description
+#[derive(Debug, Clone, Serialize)]
+pub struct UndocumentedModel {
+    pub unique_id: String,
+    pub label: String,
+    pub file_path: Option<String>,
+}
+
+/// Result of checking models for descriptions
+#[derive(Debug, Clone, Serialize)]
+pub struct DescriptionReport {
+    /// Models matched by the selectors
+    pub checked: usize,
+    pub documented: usize,
+    /// Percentage of checked models with a description; 100 when none were checked
+    pub coverage: f64,
+    /// Sorted by unique_id
+    pub missing: Vec<UndocumentedModel>,
+}
+
+impl DescriptionReport {
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Check that every model matched by `selectors` (every model when empty) has
+/// a description. Blank descriptions count as missing.
+pub fn check_descriptions(graph: &LineageGraph, selectors: &[Vec<Selector>]) -> DescriptionReport {
+    let models = list_nodes(graph, Some(&HashSet::from([NodeType::Model])), selectors);
+    let missing: Vec<UndocumentedModel> = models
+        .iter()
+        .map(|&idx| &graph[idx])
+        .filter(|node| {
+            node.description
+                .as_deref()
+                .is_none_or(|d| d.trim().is_empty())
+        })
+        .map(|node| UndocumentedModel {
+            unique_id: node.unique_id.clone(),
+            label: node.label.clone(),
+            file_path: node.file_path.as_ref().map(|p| p.display().to_string()),
+        })
+        .collect();
+
+    let checked = models.len();
+    let documented = checked - missing.len();
+    let coverage = if checked == 0 {
+        100.0
+    } else {
+        documented as f64 * 100.0 / checked as f64
+    };
+    DescriptionReport {
+        checked,
+        documented,
+        coverage,
+        missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::diff::compute_diff;
+    use crate::graph::filter::parse_selectors;
+
+    fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    /// `hub` feeds `fanout` children; `leaf` feeds nothing
+    fn make_graph(fanout: usize, hub_materialization: Option<&str>) -> LineageGraph {
+        let mut g = LineageGraph::new();
+        let mut hub = make_node("model.hub", "hub", NodeType::Model);
+        hub.materialization = hub_materialization.map(String::from);
+        let hub = g.add_node(hub);
+        g.add_node(make_node("model.leaf", "leaf", NodeType::Model));
+        for i in 0..fanout {
+            let child = g.add_node(make_node(
+                &format!("model.child_{}", i),
+                &format!("child_{}", i),
+                NodeType::Model,
+            ));
+            g.add_edge(
+                hub,
+                child,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        g
+    }
+
+    #[test]
+    fn test_modified_hub_over_budget_fails() {
+        let base = make_graph(5, Some("view"));
+        let head = make_graph(5, Some("table"));
+        let diff = compute_diff(&base, &head, "main", "HEAD");
+
+        let report = check_blast_radius(&diff, &base, &head, 3);
+        assert!(!report.passed());
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].label, "hub");
+        assert_eq!(report.violations[0].downstream, 5);
+        assert_eq!(report.violations[0].status, DiffStatus::Modified);
+    }
+
+    #[test]
+    fn test_within_budget_passes() {
+        let base = make_graph(5, Some("view"));
+        let head = make_graph(5, Some("table"));
+        let diff = compute_diff(&base, &head, "main", "HEAD");
+
+        let report = check_blast_radius(&diff, &base, &head, 5);
+        assert!(report.passed());
+        assert_eq!(report.checked.len(), 1);
+    }
+
+    #[test]
+    fn test_unchanged_models_are_ignored() {
+        let base = make_graph(5, None);
+        let head = make_graph(5, None);
+        let diff = compute_diff(&base, &head, "main", "HEAD");
+
+        let report = check_blast_radius(&diff, &base, &head, 0);
+        assert!(report.passed());
+        assert!(report.checked.is_empty());
+    }
+
+    #[test]
+    fn test_removed_model_measured_in_base() {
+        let base = make_graph(4, None);
+        let mut head = make_graph(4, None);
+        let hub = head
+            .node_indices()
+            .find(|&i| head[i].unique_id == "model.hub")
+            .unwrap();
+        head.remove_node(hub);
+        let diff = compute_diff(&base, &head, "main", "HEAD");
+
+        let report = check_blast_radius(&diff, &base, &head, 2);
+        let hub = report
+            .violations
+            .iter()
+            .find(|v| v.unique_id == "model.hub")
+            .unwrap();
+        assert_eq!(hub.status, DiffStatus::Removed);
+        assert_eq!(hub.downstream, 4);
+    }
+
+    fn tagged(unique_id: &str, tags: &[&str]) -> NodeData {
+        let mut node = make_node(
+            unique_id,
+            unique_id.rsplit('.').next().unwrap(),
+            NodeType::Model,
+        );
+        node.tags = tags.iter().map(|t| t.to_string()).collect();
+        node
+    }
+
+    fn layers() -> Vec<String> {
+        parse_layer_order("staging,intermediate,marts").unwrap()
+    }
+
+    #[test]
+    fn test_parse_layer_order() {
+        assert_eq!(layers(), vec!["staging", "intermediate", "marts"]);
+        assert_eq!(parse_layer_order(" a , b ,").unwrap(), vec!["a", "b"]);
+        assert!(parse_layer_order("").is_err());
+        assert!(parse_layer_order("a,b,a").is_err());
+    }
+
+    #[test]
+    fn test_backward_edge_is_reported() {
+        let mut g = LineageGraph::new();
+        let stg = g.add_node(tagged("model.stg_orders", &["staging"]));
+        let int = g.add_node(tagged("model.int_orders", &["intermediate"]));
+        let mart = g.add_node(tagged("model.fct_orders", &["marts", "finance"]));
+        for (a, b) in [(stg, int), (int, mart), (mart, int), (stg, mart)] {
+            g.add_edge(
+                a,
+                b,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        let report = check_layer_order(&g, &layers());
+        assert!(!report.passed());
+        assert_eq!(report.layered_nodes, 3);
+        assert_eq!(report.violations.len(), 1);
+        let v = &report.violations[0];
+        assert_eq!(v.source, "model.fct_orders");
+        assert_eq!(v.source_layer, "marts");
+        assert_eq!(v.target, "model.int_orders");
+        assert_eq!(v.target_layer, "intermediate");
+        assert_eq!(v.edge_type, "ref");
+    }
+
+    #[test]
+    fn test_untagged_nodes_and_same_layer_edges_pass() {
+        let mut g = LineageGraph::new();
+        let a = g.add_node(tagged("model.int_a", &["intermediate"]));
+        let b = g.add_node(tagged("model.int_b", &["intermediate"]));
+        let mart = g.add_node(tagged("model.fct", &["marts"]));
+        let loose = g.add_node(tagged("model.loose", &[]));
+        for (x, y) in [(a, b), (mart, loose), (loose, a)] {
+            g.add_edge(
+                x,
+                y,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        let report = check_layer_order(&g, &layers());
+        assert!(report.passed());
+        assert_eq!(report.layered_nodes, 3);
+    }
+
+    #[test]
+    fn test_earliest_layer_tag_wins() {
+        let mut g = LineageGraph::new();
+        let both = g.add_node(tagged("model.both", &["marts", "staging"]));
+        let int = g.add_node(tagged("model.int", &["intermediate"]));
+        g.add_edge(
+            both,
+            int,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+        assert!(check_layer_order(&g, &layers()).passed());
+    }
+
+    #[test]
+    fn test_check_descriptions() {
+        let mut g = LineageGraph::new();
+        let mut orders = make_node("model.orders", "orders", NodeType::Model);
+        orders.description = Some("One row per order".into());
+        orders.tags = vec!["marts".into()];
+        g.add_node(orders);
+        let mut customers = make_node("model.customers", "customers", NodeType::Model);
+        customers.description = Some("  ".into());
+        customers.tags = vec!["marts".into()];
+        customers.file_path = Some("models/marts/customers.sql".into());
+        g.add_node(customers);
+        g.add_node(make_node("model.stg_orders", "stg_orders", NodeType::Model));
+        // Only models are checked
+        g.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+
+        let report = check_descriptions(&g, &[]);
+        assert!(!report.passed());
+        assert_eq!((report.checked, report.documented), (3, 1));
+        assert!((report.coverage - 100.0 / 3.0).abs() < 1e-9);
+        let missing: Vec<&str> = report.missing.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(missing, vec!["customers", "stg_orders"]);
+        assert_eq!(
+            report.missing[0].file_path.as_deref(),
+            Some("models/marts/customers.sql")
+        );
+
+        // Scoped to the marts
+        let report = check_descriptions(&g, &parse_selectors("tag:marts"));
+        assert_eq!((report.checked, report.documented), (2, 1));
+        assert_eq!(report.coverage, 50.0);
+
+        let report = check_descriptions(&g, &parse_selectors("orders"));
+        assert!(report.passed());
+        assert_eq!(report.coverage, 100.0);
+
+        let report = check_descriptions(&g, &parse_selectors("tag:missing"));
+        assert!(report.passed());
+        assert_eq!((report.checked, report.coverage), (0, 100.0));
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::graph::groups::{directory_group_key, GroupConfig};
+use crate::graph::types::*;
+
+/// Whether a node's group key falls under one of the requested groups: an exact
+/// match, or a subdirectory of it (`models/staging` covers `models/staging/stripe`)
+fn matching_group<'a>(key: &str, groups: &'a [String]) -> Option<&'a str> {
+    groups.iter().map(|g| g.trim_end_matches('/')).find(|g| {
+        key == *g
+            || key
+                .strip_prefix(*g)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Replace every node whose group key (configured group, else directory) matches
+/// one of `groups` with a single meta-node per group.
+///
+/// Edges into and out of a collapsed group are rerouted to its meta-node, edges
+/// inside it are dropped, and parallel edges of the same type are merged. The
+/// meta-node is labelled `<group> (<n> nodes)`; groups that match no node are
+/// ignored.
+pub fn collapse_groups(
+    graph: &LineageGraph,
+    groups: &[String],
+    config: Option<&GroupConfig>,
+) -> LineageGraph {
+    let mut group_of: HashMap<NodeIndex, &str> = HashMap::new();
+    let mut members: HashMap<&str, Vec<NodeIndex>> = HashMap::new();
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        let key = match config {
+            Some(config) => config.group_key(node),
+            None => directory_group_key(node),
+        };
+        if let Some(group) = matching_group(&key, groups) {
+            group_of.insert(idx, group);
+            members.entry(group).or_default().push(idx);
+        }
+    }
+
+    let mut collapsed = LineageGraph::new();
+    let mut mapping: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut meta_nodes: HashMap<&str, NodeIndex> = HashMap::new();
+
+    // Keep node order stable: a meta-node takes the place of its first member
+    for idx in graph.node_indices() {
+        let new_idx = match group_of.get(&idx) {
+            Some(&group) => *meta_nodes
+                .entry(group)
+                .or_insert_with(|| collapsed.add_node(meta_node(graph, group, &members[group]))),
+            None => collapsed.add_node(graph[idx].clone()),
+        };
+        mapping.insert(idx, new_idx);
+    }
+
+    let mut seen: HashSet<(NodeIndex, NodeIndex, EdgeType)> = HashSet::new();
+    for edge in graph.edge_references() {
+        let source = mapping[&edge.source()];
+        let target = mapping[&edge.target()];
+        let edge_type = edge.weight().edge_type;
+        if source != target && seen.insert((source, target, edge_type)) {
+            collapsed.add_edge(
+                source,
+                target,
+                EdgeData {
+                    edge_type,
+                    snippet: None,
+                },
+            );
+        }
+    }
+
+    collapsed
+}
+
+/// Synthetic node standing in for a collapsed group. It takes the members' node
+/// type when they all share one, else it is shown as a model.
+fn meta_node(graph: &LineageGraph, group: &str, members: &[NodeIndex]) -> NodeData {
+    let first_type = graph[members[0]].node_type;
+    let node_type = if members.iter().all(|&i| graph[i].node_type == first_type) {
+        first_type
+    } else {
+        NodeType::Model
+    };
+    let noun = if members.len() == 1 { "node" } else { "nodes" };
+    NodeData {
+        unique_id: format!("group.{}", group),
+        label: format!("{} ({} {})", group, members.len(), noun),
+        node_type,
+        description: Some(format!("Collapsed group of {} {}", members.len(), noun)),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn make_node(unique_id: &str, node_type: NodeType, file_path: &str) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type,
+            file_path: Some(PathBuf::from(file_path)),
+            ..Default::default()
+        }
+    }
+
+    fn edge(g: &mut LineageGraph, a: NodeIndex, b: NodeIndex) {
+        g.add_edge(
+            a,
+            b,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+    }
+
+    /// raw -> stg_a -> stg_b -> orders, raw -> stg_c -> orders
+    fn make_graph() -> LineageGraph {
+        let mut g = LineageGraph::new();
+        let raw = g.add_node(make_node(
+            "source.raw.orders",
+            NodeType::Source,
+            "models/sources.yml",
+        ));
+        let a = g.add_node(make_node(
+            "model.stg_a",
+            NodeType::Model,
+            "models/staging/stg_a.sql",
+        ));
+        let b = g.add_node(make_node(
+            "model.stg_b",
+            NodeType::Model,
+            "models/staging/stg_b.sql",
+        ));
+        let c = g.add_node(make_node(
+            "model.stg_c",
+            NodeType::Model,
+            "models/staging/stripe/stg_c.sql",
+        ));
+        let orders = g.add_node(make_node(
+            "model.orders",
+            NodeType::Model,
+            "models/marts/orders.sql",
+        ));
+        edge(&mut g, raw, a);
+        edge(&mut g, a, b);
+        edge(&mut g, b, orders);
+        edge(&mut g, raw, c);
+        edge(&mut g, c, orders);
+        g
+    }
+
+    fn find(g: &LineageGraph, unique_id: &str) -> NodeIndex {
+        g.node_indices()
+            .find(|&i| g[i].unique_id == unique_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_collapse_directory_reroutes_edges() {
+        let g = make_graph();
+        let collapsed = collapse_groups(&g, &["models/staging/".to_string()], None);
+
+        assert_eq!(collapsed.node_count(), 3);
+        let meta = find(&collapsed, "group.models/staging");
+        assert_eq!(collapsed[meta].label, "models/staging (3 nodes)");
+        assert_eq!(collapsed[meta].node_type, NodeType::Model);
+
+        // raw -> meta and meta -> orders, each merged into a single edge
+        assert_eq!(collapsed.edge_count(), 2);
+        let raw = find(&collapsed, "source.raw.orders");
+        let orders = find(&collapsed, "model.orders");
+        assert!(collapsed.find_edge(raw, meta).is_some());
+        assert!(collapsed.find_edge(meta, orders).is_some());
+    }
+
+    #[test]
+    fn test_collapse_only_exact_directory_or_subdirectories() {
+        let g = make_graph();
+        let collapsed = collapse_groups(&g, &["models/staging/stripe".to_string()], None);
+        assert_eq!(collapsed.node_count(), 5);
+        let meta = find(&collapsed, "group.models/staging/stripe");
+        assert_eq!(collapsed[meta].label, "models/staging/stripe (1 node)");
+
+        // `models/stag` is not a directory prefix of `models/staging`
+        let collapsed = collapse_groups(&g, &["models/stag".to_string()], None);
+        assert_eq!(collapsed.node_count(), 5);
+        assert_eq!(collapsed.edge_count(), 5);
+    }
+
+    #[test]
+    fn test_collapse_multiple_groups() {
+        let g = make_graph();
+        let collapsed = collapse_groups(
+            &g,
+            &["models/staging".to_string(), "models/marts".to_string()],
+            None,
+        );
+        assert_eq!(collapsed.node_count(), 3);
+        let staging = find(&collapsed, "group.models/staging");
+        let marts = find(&collapsed, "group.models/marts");
+        assert!(collapsed.find_edge(staging, marts).is_some());
+    }
+
+    #[test]
+    fn test_collapse_configured_group() {
+        let g = make_graph();
+        let config =
+            GroupConfig::parse("groups:\n  - name: Staging\n    models: ['stg_*']\n").unwrap();
+        let collapsed = collapse_groups(&g, &["Staging".to_string()], Some(&config));
+        let meta = find(&collapsed, "group.Staging");
+        assert_eq!(collapsed[meta].label, "Staging (3 nodes)");
+        assert_eq!(collapsed.node_count(), 3);
+    }
+}
@@ -1,14 +1,19 @@
-use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use petgraph::stable_graph::NodeIndex;
 use serde::Serialize;
 
+use crate::error::DbtLineageError;
 use crate::git;
+use crate::graph::groups::directory_group_key;
+use crate::graph::impact::{compute_impact, ImpactSeverity};
 use crate::graph::types::*;
+use crate::parser::column_lineage::{ColumnConfidence, ColumnLineage};
 
 /// Status of a node or edge in the diff
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DiffStatus {
     Added,
@@ -37,6 +42,29 @@ pub struct DiffNode {
     pub status: DiffStatus,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub changes: Vec<String>,
+    /// Directory group key of the node (see [`directory_group_key`])
+    #[serde(skip)]
+    pub folder: String,
+    #[serde(skip)]
+    pub tags: Vec<String>,
+    /// Project-relative file, for links in `diff -o markdown`
+    #[serde(skip)]
+    pub file_path: Option<PathBuf>,
+    /// Downstream reach in the head graph, for added and modified nodes with
+    /// `diff --with-impact`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impact: Option<DiffImpact>,
+}
+
+/// How much of the head graph sits downstream of a changed node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffImpact {
+    /// Every node downstream, tests included
+    pub downstream: usize,
+    pub affected_models: usize,
+    pub affected_exposures: usize,
+    /// Highest severity among the downstream nodes; `None` when there are none
+    pub severity: Option<ImpactSeverity>,
 }
 
 /// An edge in the diff with its status
@@ -48,6 +76,43 @@ pub struct DiffEdge {
     pub status: DiffStatus,
 }
 
+/// An output column's lineage that changed between the two refs
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnDiff {
+    /// unique_id of the model the column belongs to
+    pub node: String,
+    pub column: String,
+    /// Added when the column has no lineage at the base ref, removed when it
+    /// has none at the head ref, modified otherwise
+    pub status: DiffStatus,
+    pub edges: Vec<ColumnEdgeChange>,
+    /// Head columns still selecting a removed column, which will likely break
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub selected_by: Vec<ColumnRef>,
+}
+
+/// A column-level edge into a [`ColumnDiff`]'s column that was added, removed
+/// or changed confidence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnEdgeChange {
+    pub source_node: String,
+    /// Empty for a derived column with no identifiable source column
+    pub source_column: String,
+    pub status: DiffStatus,
+    /// Confidence at the head ref (at the base ref for removed edges)
+    pub confidence: ColumnConfidence,
+    /// Confidence at the base ref, for modified edges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_confidence: Option<ColumnConfidence>,
+}
+
+/// A column of a node
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ColumnRef {
+    pub node: String,
+    pub column: String,
+}
+
 /// Summary counts for a diff
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct DiffSummary {
@@ -58,14 +123,258 @@ pub struct DiffSummary {
     pub edges_removed: usize,
 }
 
+/// How `diff --group-by` aggregates changed nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffGroupBy {
+    /// Parent directory of the node's file
+    Folder,
+    /// Node type (model, source, seed, ...)
+    Type,
+    /// Each of the node's tags; untagged nodes form their own group
+    Tag,
+}
+
+impl DiffGroupBy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffGroupBy::Folder => "folder",
+            DiffGroupBy::Type => "type",
+            DiffGroupBy::Tag => "tag",
+        }
+    }
+
+    /// Groups a diff node belongs to. A node with several tags counts towards
+    /// each of them.
+    fn keys(&self, node: &DiffNode) -> Vec<String> {
+        match self {
+            DiffGroupBy::Folder => vec![node.folder.clone()],
+            DiffGroupBy::Type => vec![node.node_type.clone()],
+            DiffGroupBy::Tag if node.tags.is_empty() => vec!["(untagged)".to_string()],
+            DiffGroupBy::Tag => node.tags.clone(),
+        }
+    }
+}
+
+/// Per-group change counts for `diff --group-by`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffGroup {
+    pub group: String,
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+}
+
 /// Full lineage diff between two refs
 #[derive(Debug, Clone, Serialize)]
 pub struct LineageDiff {
     pub base_ref: String,
     pub head_ref: String,
     pub summary: DiffSummary,
+    /// What `groups` aggregates by, when the diff was grouped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<DiffGroupBy>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<DiffGroup>,
     pub nodes: Vec<DiffNode>,
     pub edges: Vec<DiffEdge>,
+    /// Column-level lineage changes, grouped by model and column, with
+    /// `diff --columns`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<ColumnDiff>>,
+}
+
+impl LineageDiff {
+    /// Keep only the nodes and edges whose status is in `statuses`. The summary
+    /// still counts every change.
+    pub fn retain_statuses(&mut self, statuses: &HashSet<DiffStatus>) {
+        self.nodes.retain(|n| statuses.contains(&n.status));
+        self.edges.retain(|e| statuses.contains(&e.status));
+        if let Some(columns) = &mut self.columns {
+            columns.retain(|c| statuses.contains(&c.status));
+        }
+    }
+
+    /// Whether any node or edge left in the diff was added, removed or modified
+    pub fn has_changes(&self) -> bool {
+        self.nodes.iter().any(|n| n.status != DiffStatus::Unchanged)
+            || self.edges.iter().any(|e| e.status != DiffStatus::Unchanged)
+            || self.columns.as_ref().is_some_and(|c| !c.is_empty())
+    }
+
+    /// Attach the downstream reach in `head` to every added and modified node,
+    /// using the same walk as `impact`
+    pub fn annotate_impact(&mut self, head: &LineageGraph) {
+        let head_nodes: HashMap<&str, NodeIndex> = head
+            .node_indices()
+            .map(|idx| (head[idx].unique_id.as_str(), idx))
+            .collect();
+        for node in &mut self.nodes {
+            if !matches!(node.status, DiffStatus::Added | DiffStatus::Modified) {
+                continue;
+            }
+            let Some(&idx) = head_nodes.get(node.unique_id.as_str()) else {
+                continue;
+            };
+            let report = compute_impact(head, idx);
+            let downstream = report.impacted_nodes.len();
+            node.impact = Some(DiffImpact {
+                downstream,
+                affected_models: report.affected_models,
+                affected_exposures: report.affected_exposures,
+                severity: report.overall_severity.filter(|_| downstream > 0),
+            });
+        }
+    }
+
+    /// Count the added, removed and modified nodes left in the diff per group,
+    /// sorted by group name. Groups with only unchanged nodes are left out.
+    pub fn group_by(&mut self, by: DiffGroupBy) {
+        let mut groups: BTreeMap<String, DiffGroup> = BTreeMap::new();
+        for node in &self.nodes {
+            if node.status == DiffStatus::Unchanged {
+                continue;
+            }
+            for key in by.keys(node) {
+                let group = groups.entry(key.clone()).or_insert_with(|| DiffGroup {
+                    group: key,
+                    ..Default::default()
+                });
+                match node.status {
+                    DiffStatus::Added => group.added += 1,
+                    DiffStatus::Removed => group.removed += 1,
+                    DiffStatus::Modified => group.modified += 1,
+                    DiffStatus::Unchanged => {}
+                }
+            }
+        }
+        self.group_by = Some(by);
+        self.groups = groups.into_values().collect();
+    }
+}
+
+/// (target node, target column) → (source node, source column) → confidence.
+/// When the same edge is found twice, the first confidence wins.
+type ColumnEdgeMap = BTreeMap<(String, String), BTreeMap<(String, String), ColumnConfidence>>;
+
+fn column_edge_map(lineage: &ColumnLineage) -> ColumnEdgeMap {
+    let mut map = ColumnEdgeMap::new();
+    for edge in &lineage.edges {
+        map.entry((edge.target_node.clone(), edge.target_column.clone()))
+            .or_default()
+            .entry((edge.source_node.clone(), edge.source_column.clone()))
+            .or_insert(edge.confidence);
+    }
+    map
+}
+
+/// Compare column-level lineage at two refs: every output column with an
+/// added or removed edge, or an edge whose confidence changed, sorted by model
+/// and column. A removed column that head columns still select lists them in
+/// `selected_by`.
+pub fn compute_column_diff(base: &ColumnLineage, head: &ColumnLineage) -> Vec<ColumnDiff> {
+    let base_map = column_edge_map(base);
+    let head_map = column_edge_map(head);
+    let empty = BTreeMap::new();
+
+    let targets: BTreeSet<&(String, String)> = base_map.keys().chain(head_map.keys()).collect();
+    let mut diffs = Vec::new();
+    for target in targets {
+        let base_edges = base_map.get(target).unwrap_or(&empty);
+        let head_edges = head_map.get(target).unwrap_or(&empty);
+
+        let mut edges = Vec::new();
+        for ((source_node, source_column), &confidence) in head_edges {
+            let base_confidence = base_edges.get(&(source_node.clone(), source_column.clone()));
+            let (status, base_confidence) = match base_confidence {
+                None => (DiffStatus::Added, None),
+                Some(&c) if c != confidence => (DiffStatus::Modified, Some(c)),
+                Some(_) => continue,
+            };
+            edges.push(ColumnEdgeChange {
+                source_node: source_node.clone(),
+                source_column: source_column.clone(),
+                status,
+                confidence,
+                base_confidence,
+            });
+        }
+        for ((source_node, source_column), &confidence) in base_edges {
+            if head_edges.contains_key(&(source_node.clone(), source_column.clone())) {
+                continue;
+            }
+            edges.push(ColumnEdgeChange {
+                source_node: source_node.clone(),
+                source_column: source_column.clone(),
+                status: DiffStatus::Removed,
+                confidence,
+                base_confidence: None,
+            });
+        }
+        if edges.is_empty() {
+            continue;
+        }
+        edges.sort_by(|a, b| {
+            (&a.source_node, &a.source_column).cmp(&(&b.source_node, &b.source_column))
+        });
+
+        let status = if base_edges.is_empty() {
+            DiffStatus::Added
+        } else if head_edges.is_empty() {
+            DiffStatus::Removed
+        } else {
+            DiffStatus::Modified
+        };
+        let selected_by = if status == DiffStatus::Removed {
+            let mut readers: Vec<ColumnRef> = head
+                .edges
+                .iter()
+                .filter(|e| e.source_node == target.0 && e.source_column == target.1)
+                .map(|e| ColumnRef {
+                    node: e.target_node.clone(),
+                    column: e.target_column.clone(),
+                })
+                .collect();
+            readers.sort();
+            readers.dedup();
+            readers
+        } else {
+            vec![]
+        };
+
+        diffs.push(ColumnDiff {
+            node: target.0.clone(),
+            column: target.1.clone(),
+            status,
+            edges,
+            selected_by,
+        });
+    }
+    diffs
+}
+
+/// Statuses shown by `diff` unless `--diff-filter` says otherwise
+pub const DEFAULT_DIFF_FILTER: &str = "a,m,r";
+
+/// Parse a git-style comma-separated status filter: `a`/`added`, `m`/`modified`,
+/// `r`/`removed`, `u`/`unchanged`
+pub fn parse_diff_filter(input: &str) -> Result<HashSet<DiffStatus>> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.to_lowercase().as_str() {
+            "a" | "added" => Ok(DiffStatus::Added),
+            "m" | "modified" => Ok(DiffStatus::Modified),
+            "r" | "removed" => Ok(DiffStatus::Removed),
+            "u" | "unchanged" => Ok(DiffStatus::Unchanged),
+            other => Err(DbtLineageError::InvalidArgument(format!(
+                "unknown diff status '{}' (expected a, m, r, u)",
+                other
+            ))
+            .into()),
+        })
+        .collect()
 }
 
 /// An edge tuple for set comparison
@@ -76,7 +385,7 @@ struct EdgeTuple {
     edge_type: String,
 }
 
-fn edge_type_str(et: EdgeType) -> &'static str {
+pub(crate) fn edge_type_str(et: EdgeType) -> &'static str {
     match et {
         EdgeType::Ref => "ref",
         EdgeType::Source => "source",
@@ -161,6 +470,10 @@ pub fn compute_diff(
             node_type: node.node_type.label().to_string(),
             status: DiffStatus::Added,
             changes: vec![],
+            folder: directory_group_key(node),
+            tags: node.tags.clone(),
+            file_path: node.file_path.clone(),
+            impact: None,
         });
         summary.nodes_added += 1;
     }
@@ -174,6 +487,10 @@ pub fn compute_diff(
             node_type: node.node_type.label().to_string(),
             status: DiffStatus::Removed,
             changes: vec![],
+            folder: directory_group_key(node),
+            tags: node.tags.clone(),
+            file_path: node.file_path.clone(),
+            impact: None,
         });
         summary.nodes_removed += 1;
     }
@@ -195,6 +512,10 @@ pub fn compute_diff(
             node_type: head_node.node_type.label().to_string(),
             status,
             changes,
+            folder: directory_group_key(head_node),
+            tags: head_node.tags.clone(),
+            file_path: head_node.file_path.clone(),
+            impact: None,
         });
     }
 
@@ -236,11 +557,134 @@ pub fn compute_diff(
         base_ref: base_ref.to_string(),
         head_ref: head_ref.to_string(),
         summary,
+        group_by: None,
+        groups: vec![],
         nodes: diff_nodes,
         edges: diff_edges,
+        columns: None,
+    }
+}
+
+/// Change status of the nodes and edges of a [`diff_subgraph`], for drawing it
+#[derive(Debug, Clone, Default)]
+pub struct DiffHighlight {
+    /// By unique_id; nodes not listed are unchanged context
+    pub nodes: HashMap<String, DiffStatus>,
+    /// By (source, target) unique_id; edges not listed are unchanged
+    pub edges: HashMap<(String, String), DiffStatus>,
+}
+
+impl DiffHighlight {
+    pub fn node_status(&self, unique_id: &str) -> DiffStatus {
+        self.nodes
+            .get(unique_id)
+            .copied()
+            .unwrap_or(DiffStatus::Unchanged)
+    }
+
+    pub fn edge_status(&self, source: &str, target: &str) -> DiffStatus {
+        self.edges
+            .get(&(source.to_string(), target.to_string()))
+            .copied()
+            .unwrap_or(DiffStatus::Unchanged)
     }
 }
 
+/// The part of the lineage a diff touches: every added, removed or modified
+/// node in `diff`, the ends of its added and removed edges, and their direct
+/// neighbours in the head graph as context. Removed nodes and edges come from
+/// the base graph, so they can be drawn as ghosts.
+pub fn diff_subgraph(
+    base: &LineageGraph,
+    head: &LineageGraph,
+    diff: &LineageDiff,
+) -> (LineageGraph, DiffHighlight) {
+    use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+    let mut highlight = DiffHighlight::default();
+    for node in diff
+        .nodes
+        .iter()
+        .filter(|n| n.status != DiffStatus::Unchanged)
+    {
+        highlight.nodes.insert(node.unique_id.clone(), node.status);
+    }
+    for edge in diff
+        .edges
+        .iter()
+        .filter(|e| e.status != DiffStatus::Unchanged)
+    {
+        highlight
+            .edges
+            .insert((edge.source.clone(), edge.target.clone()), edge.status);
+    }
+
+    let index = |graph: &LineageGraph| -> HashMap<String, NodeIndex> {
+        graph
+            .node_indices()
+            .map(|idx| (graph[idx].unique_id.clone(), idx))
+            .collect()
+    };
+    let base_index = index(base);
+    let head_index = index(head);
+
+    let focus: BTreeSet<&str> = highlight
+        .nodes
+        .keys()
+        .map(String::as_str)
+        .chain(
+            highlight
+                .edges
+                .keys()
+                .flat_map(|(s, t)| [s.as_str(), t.as_str()]),
+        )
+        .collect();
+    let mut keep = focus.clone();
+    for &id in &focus {
+        // A removed node's neighbours are looked up in the base graph, but only
+        // those still in the head graph are context
+        let (graph, idx) = match (head_index.get(id), base_index.get(id)) {
+            (Some(&idx), _) => (head, idx),
+            (None, Some(&idx)) => (base, idx),
+            (None, None) => continue,
+        };
+        for neighbor in graph.neighbors_undirected(idx) {
+            let neighbor_id = graph[neighbor].unique_id.as_str();
+            if head_index.contains_key(neighbor_id) {
+                keep.insert(neighbor_id);
+            }
+        }
+    }
+
+    let mut subgraph = LineageGraph::new();
+    let mut added: HashMap<&str, NodeIndex> = HashMap::new();
+    for &id in &keep {
+        let data = match (head_index.get(id), base_index.get(id)) {
+            (Some(&idx), _) => head[idx].clone(),
+            (None, Some(&idx)) => base[idx].clone(),
+            (None, None) => continue,
+        };
+        added.insert(id, subgraph.add_node(data));
+    }
+
+    let mut add_edges = |graph: &LineageGraph, removed_only: bool| {
+        for edge in graph.edge_references() {
+            let source = graph[edge.source()].unique_id.as_str();
+            let target = graph[edge.target()].unique_id.as_str();
+            if removed_only && highlight.edge_status(source, target) != DiffStatus::Removed {
+                continue;
+            }
+            if let (Some(&s), Some(&t)) = (added.get(source), added.get(target)) {
+                subgraph.add_edge(s, t, edge.weight().clone());
+            }
+        }
+    };
+    add_edges(head, false);
+    add_edges(base, true);
+
+    (subgraph, highlight)
+}
+
 /// Build a graph from a git ref by reading manifest.json at that ref.
 /// Falls back to reading SQL/YAML files if no manifest is available.
 pub fn build_graph_from_ref(project_dir: &Path, git_ref: &str) -> Result<LineageGraph> {
@@ -249,6 +693,7 @@ pub fn build_graph_from_ref(project_dir: &Path, git_ref: &str) -> Result<Lineage
         let manifest: crate::parser::manifest::Manifest =
             serde_json::from_str(&manifest_content)
                 .context("Failed to parse manifest.json from git ref")?;
+        manifest.warn_skipped(&format!("manifest.json at {}", git_ref));
         return crate::parser::manifest::build_graph_from_parsed_manifest(&manifest);
     }
 
@@ -280,24 +725,28 @@ fn parse_sources_from_git(
 ) {
     for yaml_path in yaml_files {
         if let Ok(content) = git::git_show(project_dir, git_ref, yaml_path) {
-            if let Ok(schema) = crate::parser::yaml_schema::parse_schema_file(&content) {
-                for source_def in &schema.sources {
-                    for table in &source_def.tables {
-                        let unique_id = format!("source.{}.{}", source_def.name, table.name);
-                        let label = format!("{}.{}", source_def.name, table.name);
-                        graph.add_node(NodeData {
-                            unique_id,
-                            label,
-                            node_type: NodeType::Source,
-                            file_path: Some(yaml_path.into()),
-                            description: table.description.clone(),
-                            materialization: None,
-                            tags: vec![],
-                            columns: vec![],
-                        });
-                    }
-                }
-            }
+            add_source_nodes_from_yaml(graph, yaml_path, &content);
+        }
+    }
+}
+
+/// Add a source node for every source table defined in a YAML file's content
+fn add_source_nodes_from_yaml(graph: &mut LineageGraph, yaml_path: &str, content: &str) {
+    let Ok(schema) = crate::parser::yaml_schema::parse_schema_file(content) else {
+        return;
+    };
+    for source_def in &schema.sources {
+        for table in &source_def.tables {
+            let unique_id = format!("source.{}.{}", source_def.name, table.name);
+            let label = format!("{}.{}", source_def.name, table.name);
+            graph.add_node(NodeData {
+                unique_id,
+                label,
+                node_type: NodeType::Source,
+                file_path: Some(project_relative_path(Path::new(yaml_path), Path::new(""))),
+                description: table.description.clone(),
+                ..Default::default()
+            });
         }
     }
 }
@@ -311,27 +760,154 @@ fn parse_models_from_git(
 ) {
     for sql_path in sql_files {
         if let Ok(content) = git::git_show(project_dir, git_ref, sql_path) {
-            let model_name = std::path::Path::new(sql_path)
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown")
-                .to_string();
-            let unique_id = format!("model.{}", model_name);
-            let config = crate::parser::sql::extract_config(&content);
-            let columns = crate::parser::columns::extract_select_columns(&content);
+            add_model_node_from_sql(graph, sql_path, &content);
+        }
+    }
+}
 
-            graph.add_node(NodeData {
-                unique_id,
-                label: model_name,
-                node_type: NodeType::Model,
-                file_path: Some(sql_path.into()),
-                description: None,
-                materialization: config.materialized,
-                tags: config.tags,
-                columns,
-            });
+/// Unique id of the model defined by a SQL file
+fn model_id_for_path(sql_path: &str) -> (String, String) {
+    let model_name = Path::new(sql_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+    (format!("model.{}", model_name), model_name)
+}
+
+/// Add a model node built from a SQL file's content
+fn add_model_node_from_sql(graph: &mut LineageGraph, sql_path: &str, content: &str) {
+    let (unique_id, model_name) = model_id_for_path(sql_path);
+    let config = crate::parser::sql::extract_config(content);
+    let columns = crate::parser::columns::extract_select_columns(content);
+
+    graph.add_node(NodeData {
+        unique_id,
+        label: model_name,
+        node_type: NodeType::Model,
+        // ls-tree runs in the project dir, so its paths are already project-relative
+        file_path: Some(project_relative_path(Path::new(sql_path), Path::new(""))),
+        materialization: config.materialized,
+        tags: config.tags,
+        columns,
+        ..Default::default()
+    });
+}
+
+/// Dependency edges declared by a model's SQL (`ref()` and `source()` calls)
+fn sql_dependency_edges(sql_path: &str, content: &str) -> HashSet<EdgeTuple> {
+    let (unique_id, _) = model_id_for_path(sql_path);
+    let refs = crate::parser::sql::extract_refs(content)
+        .into_iter()
+        .map(|r| (format!("model.{}", r.name), EdgeType::Ref));
+    let sources = crate::parser::sql::extract_sources(content)
+        .into_iter()
+        .map(|s| {
+            (
+                format!("source.{}.{}", s.source_name, s.table_name),
+                EdgeType::Source,
+            )
+        });
+    refs.chain(sources)
+        .map(|(source, edge_type)| EdgeTuple {
+            source,
+            target: unique_id.clone(),
+            edge_type: edge_type_str(edge_type).to_string(),
+        })
+        .collect()
+}
+
+fn is_sql_file(path: &str) -> bool {
+    path.ends_with(".sql")
+}
+
+fn is_yaml_file(path: &str) -> bool {
+    path.ends_with(".yml") || path.ends_with(".yaml")
+}
+
+/// Fast, file-scoped diff for `diff --fast`.
+///
+/// Only the SQL and YAML files listed in `changed` (from `git diff --name-status`) are
+/// parsed, at both sides, instead of rebuilding the whole project twice. Files outside
+/// `model_paths` (project-relative, e.g. `models`) are ignored, so macros, tests,
+/// analyses and `dbt_project.yml` don't show up as models. Node changes
+/// come from those files alone, and edge changes are limited to the `ref()`/`source()`
+/// calls they declare, so structural effects on untouched files are not reported.
+/// `load_base` and `load_head` return a file's content at each side, if it exists.
+pub fn compute_changed_files_diff(
+    changed: &[git::ChangedFile],
+    model_paths: &[PathBuf],
+    base_ref: &str,
+    head_ref: &str,
+    load_base: impl Fn(&str) -> Option<String>,
+    load_head: impl Fn(&str) -> Option<String>,
+) -> LineageDiff {
+    let (base_graph, base_edges) = build_changed_files_side(changed, model_paths, true, load_base);
+    let (head_graph, head_edges) = build_changed_files_side(changed, model_paths, false, load_head);
+
+    let mut diff = compute_diff(&base_graph, &head_graph, base_ref, head_ref);
+
+    // Nodes are diffed from the changed files alone; edges come from their declared
+    // dependencies, since the other endpoint is usually not in either subgraph.
+    let mut diff_edges = Vec::new();
+    for edge in head_edges.difference(&base_edges) {
+        diff_edges.push(DiffEdge {
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+            edge_type: edge.edge_type.clone(),
+            status: DiffStatus::Added,
+        });
+    }
+    for edge in base_edges.difference(&head_edges) {
+        diff_edges.push(DiffEdge {
+            source: edge.source.clone(),
+            target: edge.target.clone(),
+            edge_type: edge.edge_type.clone(),
+            status: DiffStatus::Removed,
+        });
+    }
+    diff_edges.sort_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+
+    diff.summary.edges_added = head_edges.difference(&base_edges).count();
+    diff.summary.edges_removed = base_edges.difference(&head_edges).count();
+    diff.edges = diff_edges;
+    diff
+}
+
+/// Parse the changed files present at one side of a fast diff
+fn build_changed_files_side(
+    changed: &[git::ChangedFile],
+    model_paths: &[PathBuf],
+    is_base: bool,
+    load: impl Fn(&str) -> Option<String>,
+) -> (LineageGraph, HashSet<EdgeTuple>) {
+    let mut graph = LineageGraph::new();
+    let mut edges = HashSet::new();
+
+    for file in changed {
+        let path = match (is_base, file.kind) {
+            (true, git::ChangeKind::Added) | (false, git::ChangeKind::Deleted) => continue,
+            (true, _) => file.base_path(),
+            (false, _) => file.path.as_str(),
+        };
+        if !model_paths
+            .iter()
+            .any(|dir| Path::new(path).starts_with(dir))
+        {
+            continue;
+        }
+        let Some(content) = load(path) else {
+            continue;
+        };
+        if is_sql_file(path) {
+            add_model_node_from_sql(&mut graph, path, &content);
+            edges.extend(sql_dependency_edges(path, &content));
+        } else if is_yaml_file(path) {
+            add_source_nodes_from_yaml(&mut graph, path, &content);
         }
     }
+
+    (graph, edges)
 }
 
 #[cfg(test)]
@@ -348,14 +924,188 @@ mod tests {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
             materialization: materialization.map(|s| s.to_string()),
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn test_parse_diff_filter() {
+        let statuses = parse_diff_filter(DEFAULT_DIFF_FILTER).unwrap();
+        assert_eq!(
+            statuses,
+            HashSet::from([DiffStatus::Added, DiffStatus::Modified, DiffStatus::Removed])
+        );
+        let statuses = parse_diff_filter("A, unchanged").unwrap();
+        assert_eq!(
+            statuses,
+            HashSet::from([DiffStatus::Added, DiffStatus::Unchanged])
+        );
+        let err = parse_diff_filter("a,x").unwrap_err();
+        assert!(err.to_string().contains("unknown diff status 'x'"));
+    }
+
+    #[test]
+    fn test_retain_statuses_keeps_summary() {
+        let mut base = LineageGraph::new();
+        let kept = base.add_node(make_node("model.kept", "kept", NodeType::Model, None));
+        let old = base.add_node(make_node("model.old", "old", NodeType::Model, None));
+        base.add_edge(
+            kept,
+            old,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+        let mut head = LineageGraph::new();
+        head.add_node(make_node("model.kept", "kept", NodeType::Model, None));
+        head.add_node(make_node("model.new", "new", NodeType::Model, None));
+
+        let mut diff = compute_diff(&base, &head, "main", "HEAD");
+        assert_eq!(diff.nodes.len(), 3);
+        diff.retain_statuses(&HashSet::from([DiffStatus::Added, DiffStatus::Unchanged]));
+
+        let labels: Vec<_> = diff.nodes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(labels, vec!["new", "kept"]);
+        assert!(diff.edges.is_empty());
+        assert_eq!(diff.summary.nodes_removed, 1);
+        assert_eq!(diff.summary.edges_removed, 1);
+
+        assert!(diff.has_changes());
+        // Only the unchanged node left: nothing for --exit-code to report
+        diff.retain_statuses(&HashSet::from([DiffStatus::Unchanged]));
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn test_annotate_impact() {
+        let ref_edge = || EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        };
+        let mut base = LineageGraph::new();
+        base.add_node(make_node("model.stg", "stg", NodeType::Model, Some("view")));
+        base.add_node(make_node("model.gone", "gone", NodeType::Model, None));
+        let mut head = LineageGraph::new();
+        let stg = head.add_node(make_node(
+            "model.stg",
+            "stg",
+            NodeType::Model,
+            Some("table"),
+        ));
+        let mart = head.add_node(make_node("model.mart", "mart", NodeType::Model, None));
+        let dash = head.add_node(make_node("exposure.dash", "dash", NodeType::Exposure, None));
+        head.add_edge(stg, mart, ref_edge());
+        head.add_edge(mart, dash, ref_edge());
+
+        let mut diff = compute_diff(&base, &head, "main", "HEAD");
+        diff.annotate_impact(&head);
+        let impact = |id: &str| {
+            diff.nodes
+                .iter()
+                .find(|n| n.unique_id == id)
+                .unwrap()
+                .impact
+                .clone()
+        };
+        assert_eq!(
+            impact("model.stg"),
+            Some(DiffImpact {
+                downstream: 2,
+                affected_models: 1,
+                affected_exposures: 1,
+                severity: Some(ImpactSeverity::Critical),
+            })
+        );
+        assert_eq!(impact("exposure.dash").unwrap().severity, None);
+        // Removed nodes have no head graph to measure
+        assert_eq!(impact("model.gone"), None);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let node = |id: &str, path: Option<&str>, node_type: NodeType, tags: &[&str]| {
+            let mut node = make_node(id, id.rsplit('.').next().unwrap(), node_type, None);
+            node.file_path = path.map(std::path::PathBuf::from);
+            node.tags = tags.iter().map(|t| t.to_string()).collect();
+            node
+        };
+        let mut base = LineageGraph::new();
+        base.add_node(node(
+            "model.stg_a",
+            Some("models/staging/stg_a.sql"),
+            NodeType::Model,
+            &["nightly"],
+        ));
+        base.add_node(node(
+            "model.kept",
+            Some("models/kept.sql"),
+            NodeType::Model,
+            &[],
+        ));
+        base.add_node(node("seed.old", Some("seeds/old.csv"), NodeType::Seed, &[]));
+        let mut head = LineageGraph::new();
+        head.add_node(node(
+            "model.stg_a",
+            Some("models/staging/stg_a.sql"),
+            NodeType::Model,
+            &["nightly", "pii"],
+        ));
+        head.add_node(node(
+            "model.kept",
+            Some("models/kept.sql"),
+            NodeType::Model,
+            &[],
+        ));
+        head.add_node(node(
+            "model.stg_b",
+            Some("models/staging/stg_b.sql"),
+            NodeType::Model,
+            &["pii"],
+        ));
+        head.add_node(node("source.raw.orders", None, NodeType::Source, &[]));
+
+        let mut diff = compute_diff(&base, &head, "main", "HEAD");
+        diff.group_by(DiffGroupBy::Folder);
+        let group = |group: &str, added, removed, modified| DiffGroup {
+            group: group.to_string(),
+            added,
+            removed,
+            modified,
+        };
+        assert_eq!(diff.group_by, Some(DiffGroupBy::Folder));
+        assert_eq!(
+            diff.groups,
+            vec![
+                group("(other)", 1, 0, 0),
+                group("models/staging", 1, 0, 1),
+                group("seeds", 0, 1, 0),
+            ]
+        );
+
+        diff.group_by(DiffGroupBy::Type);
+        assert_eq!(
+            diff.groups,
+            vec![
+                group("model", 1, 0, 1),
+                group("seed", 0, 1, 0),
+                group("source", 1, 0, 0)
+            ]
+        );
+
+        // A node counts towards each of its tags
+        diff.group_by(DiffGroupBy::Tag);
+        assert_eq!(
+            diff.groups,
+            vec![
+                group("(untagged)", 1, 1, 0),
+                group("nightly", 0, 0, 1),
+                group("pii", 1, 0, 1),
+            ]
+        );
+    }
+
     #[test]
     fn test_compute_diff_no_changes() {
         let mut base = LineageGraph::new();
@@ -366,6 +1116,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -377,6 +1128,7 @@ mod tests {
             b2,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -458,6 +1210,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -476,6 +1229,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -494,21 +1248,15 @@ mod tests {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
             tags: vec!["nightly".into()],
-            columns: vec![],
+            ..Default::default()
         };
         let head = NodeData {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
             tags: vec!["nightly".into(), "daily".into()],
-            columns: vec![],
+            ..Default::default()
         };
         let changes = detect_node_changes(&base, &head);
         assert_eq!(changes.len(), 1);
@@ -521,21 +1269,15 @@ mod tests {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
             description: Some("old".into()),
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let head = NodeData {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
             description: Some("new".into()),
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let changes = detect_node_changes(&base, &head);
         assert_eq!(changes.len(), 1);
@@ -614,21 +1356,15 @@ mod tests {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["col1".into(), "col2".into()],
+            ..Default::default()
         };
         let head = NodeData {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["col1".into(), "col2".into(), "col3".into()],
+            ..Default::default()
         };
         let changes = detect_node_changes(&base, &head);
         assert_eq!(changes.len(), 1);
@@ -873,6 +1609,7 @@ sources:
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -895,4 +1632,333 @@ sources:
         assert!(map.contains_key("model.a"));
         assert!(map.contains_key("model.b"));
     }
+
+    #[test]
+    fn test_compute_changed_files_diff() {
+        use crate::git::{ChangeKind, ChangedFile};
+
+        let base: HashMap<&str, &str> = HashMap::from([
+            (
+                "models/orders.sql",
+                "{{ config(materialized='view') }} SELECT * FROM {{ ref('stg_orders') }}",
+            ),
+            ("models/legacy.sql", "SELECT 1 AS x"),
+            ("models/old_name.sql", "SELECT 1 AS y"),
+        ]);
+        let head: HashMap<&str, &str> = HashMap::from([
+            (
+                "models/orders.sql",
+                "{{ config(materialized='table') }} SELECT * FROM {{ ref('stg_orders') }} JOIN {{ source('raw', 'payments') }}",
+            ),
+            ("models/customers.sql", "SELECT * FROM {{ ref('stg_customers') }}"),
+            ("models/staging/old_name.sql", "SELECT 1 AS y"),
+        ]);
+        let changed = vec![
+            ChangedFile {
+                kind: ChangeKind::Modified,
+                path: "models/orders.sql".into(),
+                old_path: None,
+            },
+            ChangedFile {
+                kind: ChangeKind::Added,
+                path: "models/customers.sql".into(),
+                old_path: None,
+            },
+            ChangedFile {
+                kind: ChangeKind::Deleted,
+                path: "models/legacy.sql".into(),
+                old_path: None,
+            },
+            ChangedFile {
+                kind: ChangeKind::Renamed,
+                path: "models/staging/old_name.sql".into(),
+                old_path: Some("models/old_name.sql".into()),
+            },
+            ChangedFile {
+                kind: ChangeKind::Modified,
+                path: "README.md".into(),
+                old_path: None,
+            },
+        ];
+
+        let diff = compute_changed_files_diff(
+            &changed,
+            &[PathBuf::from("models")],
+            "main",
+            "HEAD",
+            |p| base.get(p).map(|c| c.to_string()),
+            |p| head.get(p).map(|c| c.to_string()),
+        );
+
+        let status_of = |id: &str| {
+            diff.nodes
+                .iter()
+                .find(|n| n.unique_id == id)
+                .map(|n| n.status)
+        };
+        assert_eq!(status_of("model.orders"), Some(DiffStatus::Modified));
+        assert_eq!(status_of("model.customers"), Some(DiffStatus::Added));
+        assert_eq!(status_of("model.legacy"), Some(DiffStatus::Removed));
+        assert_eq!(status_of("model.old_name"), Some(DiffStatus::Unchanged));
+        // Untouched upstream models are not part of the fast diff
+        assert_eq!(status_of("model.stg_orders"), None);
+
+        assert_eq!(diff.summary.nodes_added, 1);
+        assert_eq!(diff.summary.nodes_removed, 1);
+        assert_eq!(diff.summary.nodes_modified, 1);
+        assert_eq!(diff.summary.edges_added, 2);
+        assert_eq!(diff.summary.edges_removed, 0);
+        assert!(diff.edges.iter().any(|e| e.source == "source.raw.payments"
+            && e.target == "model.orders"
+            && e.status == DiffStatus::Added));
+        assert!(diff
+            .edges
+            .iter()
+            .any(|e| e.source == "model.stg_customers" && e.target == "model.customers"));
+    }
+
+    #[test]
+    fn test_changed_files_diff_ignores_files_outside_model_paths() {
+        use crate::git::{ChangeKind, ChangedFile};
+
+        let head: HashMap<&str, &str> = HashMap::from([
+            (
+                "macros/cents.sql",
+                "{% macro cents(col) %}{{ col }} / 100{% endmacro %}",
+            ),
+            (
+                "tests/assert_positive.sql",
+                "SELECT * FROM {{ ref('orders') }} WHERE amount < 0",
+            ),
+            ("dbt_project.yml", "name: shop\n"),
+            (
+                "transform/orders.sql",
+                "SELECT * FROM {{ ref('stg_orders') }}",
+            ),
+        ]);
+        let changed: Vec<ChangedFile> = head
+            .keys()
+            .map(|path| ChangedFile {
+                kind: ChangeKind::Added,
+                path: path.to_string(),
+                old_path: None,
+            })
+            .collect();
+
+        let diff = compute_changed_files_diff(
+            &changed,
+            &[PathBuf::from("transform")],
+            "main",
+            "HEAD",
+            |_| None,
+            |p| head.get(p).map(|c| c.to_string()),
+        );
+
+        let ids: Vec<&str> = diff.nodes.iter().map(|n| n.unique_id.as_str()).collect();
+        assert_eq!(ids, vec!["model.orders"]);
+        assert_eq!(diff.summary.edges_added, 1);
+        assert_eq!(diff.edges[0].target, "model.orders");
+    }
+
+    fn column_edge(
+        source: (&str, &str),
+        target: (&str, &str),
+        confidence: ColumnConfidence,
+    ) -> crate::parser::column_lineage::ColumnEdge {
+        crate::parser::column_lineage::ColumnEdge {
+            source_node: source.0.into(),
+            source_column: source.1.into(),
+            target_node: target.0.into(),
+            target_column: target.1.into(),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_compute_column_diff() {
+        use ColumnConfidence::*;
+        let base = ColumnLineage {
+            edges: vec![
+                column_edge(("model.stg", "id"), ("model.orders", "id"), Direct),
+                column_edge(("model.stg", "amount"), ("model.orders", "amount"), Direct),
+                column_edge(
+                    ("model.stg", "customer_id"),
+                    ("model.orders", "customer_id"),
+                    Direct,
+                ),
+                column_edge(
+                    ("model.orders", "customer_id"),
+                    ("model.mart", "customer_id"),
+                    Direct,
+                ),
+            ],
+        };
+        let head = ColumnLineage {
+            edges: vec![
+                column_edge(("model.stg", "id"), ("model.orders", "id"), Direct),
+                column_edge(("model.stg", "amount"), ("model.orders", "amount"), Derived),
+                column_edge(("model.stg", "cents"), ("model.orders", "amount"), Derived),
+                column_edge(("model.stg", "status"), ("model.orders", "status"), Direct),
+                column_edge(
+                    ("model.orders", "customer_id"),
+                    ("model.mart", "customer_id"),
+                    Direct,
+                ),
+            ],
+        };
+
+        let diff = compute_column_diff(&base, &head);
+        let columns: Vec<(&str, &str, DiffStatus)> = diff
+            .iter()
+            .map(|c| (c.node.as_str(), c.column.as_str(), c.status))
+            .collect();
+        // Unchanged columns (orders.id, mart.customer_id) are left out
+        assert_eq!(
+            columns,
+            vec![
+                ("model.orders", "amount", DiffStatus::Modified),
+                ("model.orders", "customer_id", DiffStatus::Removed),
+                ("model.orders", "status", DiffStatus::Added),
+            ]
+        );
+
+        let amount = &diff[0];
+        assert_eq!(
+            amount.edges,
+            vec![
+                ColumnEdgeChange {
+                    source_node: "model.stg".into(),
+                    source_column: "amount".into(),
+                    status: DiffStatus::Modified,
+                    confidence: Derived,
+                    base_confidence: Some(Direct),
+                },
+                ColumnEdgeChange {
+                    source_node: "model.stg".into(),
+                    source_column: "cents".into(),
+                    status: DiffStatus::Added,
+                    confidence: Derived,
+                    base_confidence: None,
+                },
+            ]
+        );
+        assert!(amount.selected_by.is_empty());
+
+        // The removed column is still read downstream
+        let removed = &diff[1];
+        assert_eq!(removed.edges[0].status, DiffStatus::Removed);
+        assert_eq!(
+            removed.selected_by,
+            vec![ColumnRef {
+                node: "model.mart".into(),
+                column: "customer_id".into(),
+            }]
+        );
+
+        assert!(compute_column_diff(&base, &base).is_empty());
+    }
+
+    #[test]
+    fn test_column_diff_statuses() {
+        let mut diff = compute_diff(&LineageGraph::new(), &LineageGraph::new(), "main", "HEAD");
+        assert!(!diff.has_changes());
+        diff.columns = Some(compute_column_diff(
+            &ColumnLineage::default(),
+            &ColumnLineage {
+                edges: vec![column_edge(
+                    ("model.stg", "id"),
+                    ("model.orders", "id"),
+                    ColumnConfidence::Direct,
+                )],
+            },
+        ));
+        assert!(diff.has_changes());
+
+        diff.retain_statuses(&parse_diff_filter("r").unwrap());
+        assert_eq!(diff.columns.as_ref().map(Vec::len), Some(0));
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn test_diff_subgraph() {
+        let ref_edge = || EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        };
+        // raw -> stg -> orders -> report, plus legacy (removed) and far (untouched)
+        let mut base = LineageGraph::new();
+        let raw = base.add_node(make_node(
+            "source.raw.orders",
+            "raw",
+            NodeType::Source,
+            None,
+        ));
+        let stg = base.add_node(make_node("model.stg", "stg", NodeType::Model, Some("view")));
+        let orders = base.add_node(make_node("model.orders", "orders", NodeType::Model, None));
+        let report = base.add_node(make_node("model.report", "report", NodeType::Model, None));
+        let legacy = base.add_node(make_node("model.legacy", "legacy", NodeType::Model, None));
+        let far = base.add_node(make_node("model.far", "far", NodeType::Model, None));
+        base.add_edge(raw, stg, ref_edge());
+        base.add_edge(stg, orders, ref_edge());
+        base.add_edge(orders, report, ref_edge());
+        base.add_edge(legacy, report, ref_edge());
+        base.add_edge(far, raw, ref_edge());
+
+        let mut head = base.clone();
+        head.remove_node(legacy);
+        head[stg].materialization = Some("table".into());
+        let new = head.add_node(make_node("model.new", "new", NodeType::Model, None));
+        head.add_edge(orders, new, ref_edge());
+
+        let diff = compute_diff(&base, &head, "main", "HEAD");
+        let (subgraph, highlight) = diff_subgraph(&base, &head, &diff);
+
+        let mut ids: Vec<&str> = subgraph
+            .node_indices()
+            .map(|i| subgraph[i].unique_id.as_str())
+            .collect();
+        ids.sort();
+        // `far` is two hops from any change
+        assert_eq!(
+            ids,
+            vec![
+                "model.legacy",
+                "model.new",
+                "model.orders",
+                "model.report",
+                "model.stg",
+                "source.raw.orders"
+            ]
+        );
+        assert_eq!(highlight.node_status("model.stg"), DiffStatus::Modified);
+        assert_eq!(highlight.node_status("model.legacy"), DiffStatus::Removed);
+        assert_eq!(highlight.node_status("model.new"), DiffStatus::Added);
+        assert_eq!(highlight.node_status("model.orders"), DiffStatus::Unchanged);
+        assert_eq!(
+            highlight.edge_status("model.legacy", "model.report"),
+            DiffStatus::Removed
+        );
+        assert_eq!(
+            highlight.edge_status("model.orders", "model.new"),
+            DiffStatus::Added
+        );
+
+        // raw->stg, stg->orders, orders->report, orders->new, and the removed legacy->report
+        assert_eq!(subgraph.edge_count(), 5);
+        let legacy = subgraph
+            .node_indices()
+            .find(|&i| subgraph[i].unique_id == "model.legacy")
+            .unwrap();
+        assert_eq!(subgraph.neighbors(legacy).count(), 1);
+    }
+
+    #[test]
+    fn test_diff_subgraph_without_changes_is_empty() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.a", "a", NodeType::Model, None));
+        let diff = compute_diff(&graph, &graph, "main", "HEAD");
+        let (subgraph, highlight) = diff_subgraph(&graph, &graph, &diff);
+        assert_eq!(subgraph.node_count(), 0);
+        assert!(highlight.nodes.is_empty());
+    }
 }
@@ -2,7 +2,9 @@ use anyhow::Result;
 use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use petgraph::Direction;
+use serde::Deserialize;
 use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
 
 use crate::error::DbtLineageError;
 
@@ -10,10 +12,159 @@ use super::types::*;
 
 /// Configuration for which node types to include
 pub struct NodeTypeFilter {
-    pub include_tests: bool,
-    pub include_seeds: bool,
-    pub include_snapshots: bool,
-    pub include_exposures: bool,
+    /// Node types to keep; see [`resolve_node_types`]
+    pub node_types: HashSet<NodeType>,
+    /// Edge types to keep (`None` keeps all). Nodes left without any kept edge
+    /// are dropped unless they are the focus model or named by a selector.
+    pub edge_types: Option<HashSet<EdgeType>>,
+    /// Materializations to keep (`None` keeps all). Nodes without a known
+    /// materialization are dropped when this is set.
+    pub materializations: Option<HashSet<String>>,
+    /// Which kinds of test nodes to keep when tests are included
+    pub tests: TestFilter,
+}
+
+impl Default for NodeTypeFilter {
+    fn default() -> Self {
+        NodeTypeFilter {
+            node_types: DEFAULT_NODE_TYPES.into_iter().collect(),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::default(),
+        }
+    }
+}
+
+/// Test kinds selectable with `--tests`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestFilter {
+    /// Hand-written SQL tests only
+    Singular,
+    /// Tests generated from schema YAML only
+    Generic,
+    /// Both kinds
+    #[default]
+    All,
+}
+
+impl TestFilter {
+    /// Whether a test of `kind` passes; tests of unknown kind only pass `All`
+    fn keeps(self, kind: Option<TestKind>) -> bool {
+        match self {
+            TestFilter::All => true,
+            TestFilter::Singular => kind == Some(TestKind::Singular),
+            TestFilter::Generic => kind == Some(TestKind::Generic),
+        }
+    }
+}
+
+/// All edge types, in display order
+pub const ALL_EDGE_TYPES: [EdgeType; 4] = [
+    EdgeType::Ref,
+    EdgeType::Source,
+    EdgeType::Test,
+    EdgeType::Exposure,
+];
+
+/// All node types, in display order
+pub const ALL_NODE_TYPES: [NodeType; 7] = [
+    NodeType::Model,
+    NodeType::Source,
+    NodeType::Seed,
+    NodeType::Snapshot,
+    NodeType::Test,
+    NodeType::Exposure,
+    NodeType::Phantom,
+];
+
+/// Node types kept unless `--include` or `--exclude` says otherwise
+pub const DEFAULT_NODE_TYPES: [NodeType; 3] =
+    [NodeType::Model, NodeType::Source, NodeType::Phantom];
+
+/// Parse a comma-separated list of edge type names (`ref`, `source`, `test`, `exposure`)
+pub fn parse_edge_types(input: &str) -> Result<HashSet<EdgeType>> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.to_lowercase().as_str() {
+            "ref" => Ok(EdgeType::Ref),
+            "source" => Ok(EdgeType::Source),
+            "test" => Ok(EdgeType::Test),
+            "exposure" => Ok(EdgeType::Exposure),
+            other => Err(DbtLineageError::InvalidArgument(format!(
+                "unknown edge type '{}' (expected ref, source, test, exposure)",
+                other
+            ))
+            .into()),
+        })
+        .collect()
+}
+
+/// Parse a comma-separated list of node type names (`model`, `source`, `seed`,
+/// `snapshot`, `test`, `exposure`, `phantom`)
+pub fn parse_node_types(input: &str) -> Result<HashSet<NodeType>> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.to_lowercase().as_str() {
+            "model" => Ok(NodeType::Model),
+            "source" => Ok(NodeType::Source),
+            "seed" => Ok(NodeType::Seed),
+            "snapshot" => Ok(NodeType::Snapshot),
+            "test" => Ok(NodeType::Test),
+            "exposure" => Ok(NodeType::Exposure),
+            "phantom" => Ok(NodeType::Phantom),
+            other => Err(DbtLineageError::InvalidArgument(format!(
+                "unknown node type '{}' (expected model, source, seed, snapshot, test, exposure, phantom)",
+                other
+            ))
+            .into()),
+        })
+        .collect()
+}
+
+/// Combine `--edge-types` (include list) and `--hide-edge-types` (exclude list).
+/// Returns `None` when neither is given, meaning every edge type is kept.
+pub fn resolve_edge_types(
+    include: Option<&str>,
+    hide: Option<&str>,
+) -> Result<Option<HashSet<EdgeType>>> {
+    if include.is_none() && hide.is_none() {
+        return Ok(None);
+    }
+    let mut allowed = match include {
+        Some(list) => parse_edge_types(list)?,
+        None => ALL_EDGE_TYPES.into_iter().collect(),
+    };
+    if let Some(list) = hide {
+        for edge_type in parse_edge_types(list)? {
+            allowed.remove(&edge_type);
+        }
+    }
+    Ok(Some(allowed))
+}
+
+/// Node types to keep: the defaults (models, sources and phantoms) plus the
+/// `--include` list and `also` (the older `--include-tests` style flags), minus
+/// the `--exclude` list, which wins when a type is in both
+pub fn resolve_node_types(
+    include: Option<&str>,
+    exclude: Option<&str>,
+    also: impl IntoIterator<Item = NodeType>,
+) -> Result<HashSet<NodeType>> {
+    let mut kept: HashSet<NodeType> = DEFAULT_NODE_TYPES.into_iter().chain(also).collect();
+    if let Some(list) = include {
+        kept.extend(parse_node_types(list)?);
+    }
+    if let Some(list) = exclude {
+        for node_type in parse_node_types(list)? {
+            kept.remove(&node_type);
+        }
+    }
+    Ok(kept)
 }
 
 /// A parsed selector expression
@@ -23,36 +174,101 @@ pub enum Selector {
     Tag(String),
     /// Match nodes whose file_path starts with the given path prefix
     Path(String),
-    /// Match nodes whose label equals the given model name
+    /// Match nodes whose label equals the given model name (`orders` or the
+    /// anchored `=orders`)
     ModelName(String),
+    /// Match nodes with the given materialization (`config.materialized:X`)
+    Materialized(String),
+    /// Match nodes built in or read from the given schema (`schema:X` or
+    /// `config.schema:X`)
+    Schema(String),
 }
 
-/// Parse a comma-separated selector string into a list of `Selector` values.
+/// Parse a selector expression with dbt's semantics: space-separated terms are
+/// unioned, and the comma-separated selectors within a term are intersected.
+/// Each inner `Vec` is one term; a node is selected when it matches every
+/// selector of at least one term. Spaces around commas are ignored.
 ///
-/// Syntax:
+/// Syntax of a single selector:
 /// - `tag:nightly` -> `Selector::Tag("nightly")`
 /// - `path:models/staging` -> `Selector::Path("models/staging")`
+/// - `config.materialized:incremental` -> `Selector::Materialized("incremental")`
+/// - `schema:marts` or `config.schema:marts` -> `Selector::Schema("marts")`
 /// - `orders` -> `Selector::ModelName("orders")`
-pub fn parse_selectors(input: &str) -> Vec<Selector> {
-    input
+/// - `=orders` -> `Selector::ModelName("orders")`, the anchored spelling also
+///   accepted by `--model`, `impact` and `path`
+///
+/// `tag:nightly,tag:finance orders` selects nodes tagged both `nightly` and
+/// `finance`, plus `orders`.
+pub fn parse_selectors(input: &str) -> Vec<Vec<Selector>> {
+    let joined = input
         .split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(|s| {
-            if let Some(tag) = s.strip_prefix("tag:") {
-                Selector::Tag(tag.to_string())
-            } else if let Some(path) = s.strip_prefix("path:") {
-                Selector::Path(path.to_string())
-            } else {
-                Selector::ModelName(s.to_string())
-            }
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(",");
+    joined
+        .split_whitespace()
+        .map(|term| {
+            term.split(',')
+                .filter(|s| !s.is_empty())
+                .map(parse_selector)
+                .collect::<Vec<_>>()
         })
+        .filter(|term| !term.is_empty())
         .collect()
 }
 
-/// Check if a single node matches any of the given selectors (union / OR logic).
-fn node_matches_any_selector(node: &NodeData, selectors: &[Selector]) -> bool {
-    selectors.iter().any(|sel| match sel {
+/// Expand a `--select` argument that names a list of selectors: `@path` reads
+/// them from a file and `-` from stdin. Anything else is returned unchanged.
+pub fn expand_selector_arg(arg: &str) -> Result<String> {
+    let content = if arg == "-" {
+        std::io::read_to_string(std::io::stdin()).map_err(|e| {
+            DbtLineageError::InvalidArgument(format!("cannot read selectors from stdin: {}", e))
+        })?
+    } else if let Some(path) = arg.strip_prefix('@') {
+        std::fs::read_to_string(path).map_err(|e| {
+            DbtLineageError::InvalidArgument(format!("cannot read selector file '{}': {}", path, e))
+        })?
+    } else {
+        return Ok(arg.to_string());
+    };
+    Ok(selectors_from_lines(&content))
+}
+
+/// Join a selector list, one selector per line, into a single expression
+/// whose terms are unioned. Blank lines and `#` comments are skipped.
+pub fn selectors_from_lines(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse one selector (no commas or spaces)
+fn parse_selector(s: &str) -> Selector {
+    if let Some(tag) = s.strip_prefix("tag:") {
+        Selector::Tag(tag.to_string())
+    } else if let Some(path) = s.strip_prefix("path:") {
+        Selector::Path(path.to_string())
+    } else if let Some(materialized) = s.strip_prefix("config.materialized:") {
+        Selector::Materialized(materialized.to_string())
+    } else if let Some(schema) = s
+        .strip_prefix("schema:")
+        .or_else(|| s.strip_prefix("config.schema:"))
+    {
+        Selector::Schema(schema.to_string())
+    } else if let Some(name) = s.strip_prefix('=') {
+        Selector::ModelName(name.to_string())
+    } else {
+        Selector::ModelName(s.to_string())
+    }
+}
+
+/// Check if a single node matches one selector
+fn node_matches_selector(node: &NodeData, selector: &Selector) -> bool {
+    match selector {
         Selector::Tag(tag) => node.tags.contains(tag),
         Selector::Path(prefix) => node
             .file_path
@@ -60,17 +276,64 @@ fn node_matches_any_selector(node: &NodeData, selectors: &[Selector]) -> bool {
             .map(|fp| fp.to_string_lossy().starts_with(prefix.as_str()))
             .unwrap_or(false),
         Selector::ModelName(name) => node.label == *name,
-    })
+        Selector::Materialized(materialized) => {
+            node.materialization.as_deref() == Some(materialized.as_str())
+        }
+        Selector::Schema(schema) => node.schema.as_deref() == Some(schema.as_str()),
+    }
+}
+
+/// Check if a node matches every selector of at least one term (see [`parse_selectors`])
+fn node_matches_any_term(node: &NodeData, terms: &[Vec<Selector>]) -> bool {
+    terms
+        .iter()
+        .any(|term| term.iter().all(|sel| node_matches_selector(node, sel)))
 }
 
-/// Return the set of node indices that match any of the given selectors.
-pub fn apply_selectors(graph: &LineageGraph, selectors: &[Selector]) -> HashSet<NodeIndex> {
+/// Whether some selector in `terms` names the node's model, which keeps it from
+/// being pruned for lack of edges
+fn named_by_selector(node: &NodeData, terms: &[Vec<Selector>]) -> bool {
+    terms
+        .iter()
+        .flatten()
+        .any(|sel| matches!(sel, Selector::ModelName(name) if node.label == *name))
+}
+
+/// Parse a comma-separated list of materializations (`incremental,table`)
+pub fn parse_materializations(input: &str) -> HashSet<String> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Return the set of node indices selected by a parsed selector expression.
+pub fn apply_selectors(graph: &LineageGraph, selectors: &[Vec<Selector>]) -> HashSet<NodeIndex> {
     graph
         .node_indices()
-        .filter(|&idx| node_matches_any_selector(&graph[idx], selectors))
+        .filter(|&idx| node_matches_any_term(&graph[idx], selectors))
         .collect()
 }
 
+/// Nodes of the given types (all types when `None`) that match the selectors (all
+/// nodes when empty), sorted by unique_id. Unlike [`filter_graph`] this never
+/// copies the graph, so it stays cheap for scripting.
+pub fn list_nodes(
+    graph: &LineageGraph,
+    node_types: Option<&HashSet<NodeType>>,
+    selectors: &[Vec<Selector>],
+) -> Vec<NodeIndex> {
+    let mut nodes: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| node_types.is_none_or(|types| types.contains(&graph[idx].node_type)))
+        .filter(|&idx| selectors.is_empty() || node_matches_any_term(&graph[idx], selectors))
+        .collect();
+    nodes.sort_by(|&a, &b| graph[a].unique_id.cmp(&graph[b].unique_id));
+    nodes
+}
+
 /// Filter the graph based on focus model, distance, selectors, and node types
 pub fn filter_graph(
     graph: &LineageGraph,
@@ -78,7 +341,7 @@ pub fn filter_graph(
     upstream: Option<usize>,
     downstream: Option<usize>,
     type_filter: &NodeTypeFilter,
-    selectors: &[Selector],
+    selectors: &[Vec<Selector>],
 ) -> Result<LineageGraph> {
     // Check for cycles
     if petgraph::algo::is_cyclic_directed(graph) {
@@ -86,18 +349,16 @@ pub fn filter_graph(
     }
 
     let mut keep_nodes: HashSet<NodeIndex> = HashSet::new();
+    // Nodes that survive edge-type pruning even when left without edges
+    let mut pinned: HashSet<NodeIndex> = HashSet::new();
 
     if let Some(model_name) = focus_model {
         // Find the focus node
-        let focus_idx = graph
-            .node_indices()
-            .find(|&idx| {
-                let node = &graph[idx];
-                node.label == model_name || node.unique_id == format!("model.{}", model_name)
-            })
+        let focus_idx = find_focus_node(graph, model_name)
             .ok_or_else(|| DbtLineageError::ModelNotFound(model_name.to_string()))?;
 
         keep_nodes.insert(focus_idx);
+        pinned.insert(focus_idx);
 
         // BFS upstream (predecessors)
         bfs_collect(
@@ -136,9 +397,115 @@ pub fn filter_graph(
         }
     }
 
-    let keep_nodes = apply_type_filter(graph, keep_nodes, type_filter);
+    let mut keep_nodes = apply_type_filter(graph, keep_nodes, type_filter);
+    if let Some(materializations) = &type_filter.materializations {
+        keep_nodes.retain(|&idx| {
+            graph[idx]
+                .materialization
+                .as_ref()
+                .is_some_and(|m| materializations.contains(m))
+        });
+    }
+
+    if let Some(allowed) = &type_filter.edge_types {
+        pinned.extend(
+            graph
+                .node_indices()
+                .filter(|&idx| named_by_selector(&graph[idx], selectors)),
+        );
+        keep_nodes = drop_edgeless_nodes(graph, keep_nodes, allowed, &pinned);
+    }
+
+    Ok(build_subgraph(
+        graph,
+        &keep_nodes,
+        type_filter.edge_types.as_ref(),
+    ))
+}
+
+/// Nodes whose project-relative file is in `files`, plus every node within
+/// `neighbors` hops of one of them in either direction
+pub fn nodes_for_files(
+    graph: &LineageGraph,
+    files: &HashSet<PathBuf>,
+    neighbors: usize,
+) -> HashSet<NodeIndex> {
+    let matched: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| {
+            graph[idx]
+                .file_path
+                .as_ref()
+                .is_some_and(|path| files.contains(path))
+        })
+        .collect();
+
+    let mut nodes: HashSet<NodeIndex> = matched.iter().copied().collect();
+    if neighbors > 0 {
+        for &idx in &matched {
+            bfs_collect(graph, idx, Direction::Incoming, Some(neighbors), &mut nodes);
+            bfs_collect(graph, idx, Direction::Outgoing, Some(neighbors), &mut nodes);
+        }
+    }
+    nodes
+}
+
+/// The node `--model <model_name>` refers to
+pub fn find_focus_node(graph: &LineageGraph, model_name: &str) -> Option<NodeIndex> {
+    graph
+        .node_indices()
+        .find(|&idx| is_focus_node(&graph[idx], model_name))
+}
 
-    Ok(build_subgraph(graph, &keep_nodes))
+/// Whether `node` is the one `--model <model_name>` refers to. A leading `=`
+/// is accepted and ignored, since this match is already exact.
+fn is_focus_node(node: &NodeData, model_name: &str) -> bool {
+    let model_name = model_name.strip_prefix('=').unwrap_or(model_name);
+    node.label == model_name || node.unique_id == format!("model.{}", model_name)
+}
+
+/// Drop nodes left without any incident edge, such as the stragglers a type or
+/// selector filter leaves behind. The `--model` focus node and nodes selected
+/// by name are kept even when standalone, since they were asked for explicitly.
+pub fn trim_orphans(
+    graph: &LineageGraph,
+    focus_model: Option<&str>,
+    selectors: &[Vec<Selector>],
+) -> LineageGraph {
+    let keep: HashSet<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| {
+            let node = &graph[idx];
+            graph.neighbors_undirected(idx).next().is_some()
+                || focus_model.is_some_and(|name| is_focus_node(node, name))
+                || named_by_selector(node, selectors)
+        })
+        .collect();
+    induced_subgraph(graph, &keep)
+}
+
+/// Copy of the graph with only `nodes` and the edges between them
+pub fn induced_subgraph(graph: &LineageGraph, nodes: &HashSet<NodeIndex>) -> LineageGraph {
+    build_subgraph(graph, nodes, None)
+}
+
+/// Remove nodes that have no edge of an allowed type to another kept node
+fn drop_edgeless_nodes(
+    graph: &LineageGraph,
+    nodes: HashSet<NodeIndex>,
+    allowed: &HashSet<EdgeType>,
+    pinned: &HashSet<NodeIndex>,
+) -> HashSet<NodeIndex> {
+    let connected: HashSet<NodeIndex> = graph
+        .edge_references()
+        .filter(|e| allowed.contains(&e.weight().edge_type))
+        .filter(|e| nodes.contains(&e.source()) && nodes.contains(&e.target()))
+        .flat_map(|e| [e.source(), e.target()])
+        .collect();
+    nodes
+        .into_iter()
+        .filter(|idx| connected.contains(idx) || pinned.contains(idx))
+        .collect()
 }
 
 /// Filter a set of node indices by node type
@@ -151,19 +518,19 @@ fn apply_type_filter(
         .into_iter()
         .filter(|&idx| {
             let node = &graph[idx];
-            match node.node_type {
-                NodeType::Test => type_filter.include_tests,
-                NodeType::Seed => type_filter.include_seeds,
-                NodeType::Snapshot => type_filter.include_snapshots,
-                NodeType::Exposure => type_filter.include_exposures,
-                NodeType::Model | NodeType::Source | NodeType::Phantom => true,
-            }
+            type_filter.node_types.contains(&node.node_type)
+                && (node.node_type != NodeType::Test || type_filter.tests.keeps(node.test_kind))
         })
         .collect()
 }
 
-/// Build a new graph containing only the specified nodes and their interconnecting edges
-fn build_subgraph(graph: &LineageGraph, keep_nodes: &HashSet<NodeIndex>) -> LineageGraph {
+/// Build a new graph containing only the specified nodes and their interconnecting edges,
+/// optionally restricted to the given edge types
+fn build_subgraph(
+    graph: &LineageGraph,
+    keep_nodes: &HashSet<NodeIndex>,
+    edge_types: Option<&HashSet<EdgeType>>,
+) -> LineageGraph {
     let mut new_graph = LineageGraph::new();
     let mut index_map: std::collections::HashMap<NodeIndex, NodeIndex> =
         std::collections::HashMap::new();
@@ -175,6 +542,9 @@ fn build_subgraph(graph: &LineageGraph, keep_nodes: &HashSet<NodeIndex>) -> Line
     }
 
     for edge in graph.edge_references() {
+        if edge_types.is_some_and(|allowed| !allowed.contains(&edge.weight().edge_type)) {
+            continue;
+        }
         let source = edge.source();
         let target = edge.target();
         if let (Some(&new_source), Some(&new_target)) =
@@ -224,6 +594,11 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    /// The default node types plus `extra`
+    fn node_types_with(extra: &[NodeType]) -> HashSet<NodeType> {
+        resolve_node_types(None, None, extra.iter().copied()).unwrap()
+    }
+
     fn make_node(
         unique_id: &str,
         label: &str,
@@ -236,10 +611,8 @@ mod tests {
             label: label.into(),
             node_type,
             file_path,
-            description: None,
-            materialization: None,
             tags,
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -280,6 +653,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -287,6 +661,7 @@ mod tests {
             c,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -294,6 +669,7 @@ mod tests {
             d,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
         g
@@ -303,38 +679,248 @@ mod tests {
     fn test_filter_no_focus() {
         let g = make_test_graph();
         let filter = NodeTypeFilter {
-            include_tests: false,
-            include_seeds: false,
-            include_snapshots: false,
-            include_exposures: true,
+            node_types: node_types_with(&[NodeType::Exposure]),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         };
         let filtered = filter_graph(&g, None, None, None, &filter, &[]).unwrap();
         assert_eq!(filtered.node_count(), 4);
     }
 
+    #[test]
+    fn test_parse_edge_types() {
+        let types = parse_edge_types("ref, Source").unwrap();
+        assert_eq!(types.len(), 2);
+        assert!(types.contains(&EdgeType::Ref));
+        assert!(types.contains(&EdgeType::Source));
+        assert!(parse_edge_types("ref,bogus").is_err());
+    }
+
+    #[test]
+    fn test_parse_node_types() {
+        let types = parse_node_types("model, Source,phantom").unwrap();
+        assert_eq!(types.len(), 3);
+        assert!(types.contains(&NodeType::Model));
+        assert!(types.contains(&NodeType::Source));
+        assert!(types.contains(&NodeType::Phantom));
+        let err = parse_node_types("model,view").unwrap_err();
+        assert!(err.to_string().contains("unknown node type 'view'"));
+    }
+
+    #[test]
+    fn test_list_nodes() {
+        let g = make_test_graph();
+        let ids = |nodes: Vec<NodeIndex>| -> Vec<String> {
+            nodes.iter().map(|&i| g[i].unique_id.clone()).collect()
+        };
+
+        assert_eq!(
+            ids(list_nodes(&g, None, &[])),
+            vec![
+                "exposure.dashboard",
+                "model.orders",
+                "model.stg_orders",
+                "source.raw.orders"
+            ]
+        );
+
+        let models = parse_node_types("model").unwrap();
+        assert_eq!(
+            ids(list_nodes(&g, Some(&models), &[])),
+            vec!["model.orders", "model.stg_orders"]
+        );
+        assert_eq!(
+            ids(list_nodes(&g, Some(&models), &parse_selectors("orders"))),
+            vec!["model.orders"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_edge_types() {
+        assert!(resolve_edge_types(None, None).unwrap().is_none());
+
+        let hidden = resolve_edge_types(None, Some("test,exposure"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(hidden.len(), 2);
+        assert!(!hidden.contains(&EdgeType::Exposure));
+
+        let both = resolve_edge_types(Some("ref,exposure"), Some("exposure"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(both, [EdgeType::Ref].into_iter().collect());
+    }
+
+    #[test]
+    fn test_resolve_node_types() {
+        let set = |types: &[NodeType]| types.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(
+            resolve_node_types(None, None, []).unwrap(),
+            set(&DEFAULT_NODE_TYPES)
+        );
+        assert_eq!(
+            resolve_node_types(Some("seed, test"), Some("phantom"), [NodeType::Exposure]).unwrap(),
+            set(&[
+                NodeType::Model,
+                NodeType::Source,
+                NodeType::Seed,
+                NodeType::Test,
+                NodeType::Exposure
+            ])
+        );
+        // --exclude wins over both --include and the older boolean flags
+        let kept = resolve_node_types(Some("test"), Some("test,source"), [NodeType::Test]).unwrap();
+        assert_eq!(kept, set(&[NodeType::Model, NodeType::Phantom]));
+        assert!(resolve_node_types(Some("widget"), None, []).is_err());
+    }
+
+    #[test]
+    fn test_filter_excludes_phantoms() {
+        let mut g = make_test_graph();
+        let orders = g.node_indices().find(|&i| g[i].label == "orders").unwrap();
+        let phantom = g.add_node(make_node(
+            "phantom.missing",
+            "missing",
+            NodeType::Phantom,
+            None,
+            vec![],
+        ));
+        g.add_edge(
+            phantom,
+            orders,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+        let kept = |exclude: Option<&str>| {
+            let filter = NodeTypeFilter {
+                node_types: resolve_node_types(None, exclude, []).unwrap(),
+                ..Default::default()
+            };
+            let filtered = filter_graph(&g, None, None, None, &filter, &[]).unwrap();
+            filtered
+                .node_indices()
+                .any(|i| filtered[i].node_type == NodeType::Phantom)
+        };
+        assert!(kept(None));
+        assert!(!kept(Some("phantom")));
+    }
+
+    #[test]
+    fn test_filter_edge_types_drops_edges_and_orphans() {
+        let g = make_test_graph();
+        let filter = NodeTypeFilter {
+            node_types: node_types_with(&[NodeType::Exposure]),
+            edge_types: Some([EdgeType::Ref, EdgeType::Source].into_iter().collect()),
+            materializations: None,
+            ..Default::default()
+        };
+        let filtered = filter_graph(&g, None, None, None, &filter, &[]).unwrap();
+        // dashboard loses its only (exposure) edge and is dropped
+        assert_eq!(filtered.node_count(), 3);
+        assert_eq!(filtered.edge_count(), 2);
+        assert!(filtered
+            .node_indices()
+            .all(|i| filtered[i].label != "dashboard"));
+    }
+
+    #[test]
+    fn test_filter_edge_types_keeps_selected_node() {
+        let g = make_test_graph();
+        let filter = NodeTypeFilter {
+            node_types: node_types_with(&[NodeType::Exposure]),
+            edge_types: Some([EdgeType::Source].into_iter().collect()),
+            materializations: None,
+            ..Default::default()
+        };
+        // Focus on orders: its only kept neighbours are via ref/exposure edges,
+        // which are hidden, but the focus node itself stays
+        let filtered = filter_graph(&g, Some("orders"), None, None, &filter, &[]).unwrap();
+        let labels: HashSet<_> = filtered
+            .node_indices()
+            .map(|i| filtered[i].label.clone())
+            .collect();
+        assert!(labels.contains("orders"));
+        assert!(labels.contains("raw.orders"));
+        assert!(labels.contains("stg_orders"));
+        assert!(!labels.contains("dashboard"));
+        assert_eq!(filtered.edge_count(), 1);
+
+        // A model named by a selector survives too
+        let selectors = parse_selectors("dashboard");
+        let filtered = filter_graph(&g, None, None, None, &filter, &selectors).unwrap();
+        assert_eq!(filtered.node_count(), 1);
+    }
+
+    #[test]
+    fn test_trim_orphans() {
+        let mut g = make_test_graph();
+        g.add_node(make_node(
+            "model.loose",
+            "loose",
+            NodeType::Model,
+            None,
+            vec![],
+        ));
+        g.add_node(make_node(
+            "model.standalone",
+            "standalone",
+            NodeType::Model,
+            None,
+            vec![],
+        ));
+        let labels = |g: &LineageGraph| -> HashSet<String> {
+            g.node_indices().map(|i| g[i].label.clone()).collect()
+        };
+
+        let trimmed = trim_orphans(&g, None, &[]);
+        assert_eq!(trimmed.node_count(), 4);
+        assert_eq!(trimmed.edge_count(), 3);
+        assert!(!labels(&trimmed).contains("loose"));
+
+        // The focus model and nodes selected by name are kept
+        let trimmed = trim_orphans(&g, Some("loose"), &parse_selectors("standalone tag:x"));
+        assert_eq!(trimmed.node_count(), 6);
+
+        // Without incident edges every other node goes
+        let filter = NodeTypeFilter {
+            node_types: node_types_with(&[NodeType::Exposure]),
+            ..Default::default()
+        };
+        let selectors = parse_selectors("orders loose");
+        let filtered = filter_graph(&g, None, None, None, &filter, &selectors).unwrap();
+        let trimmed = trim_orphans(&filtered, None, &parse_selectors("loose"));
+        assert_eq!(labels(&trimmed), HashSet::from(["loose".to_string()]));
+    }
+
     #[test]
     fn test_filter_focus_upstream_1() {
         let g = make_test_graph();
         let filter = NodeTypeFilter {
-            include_tests: false,
-            include_seeds: false,
-            include_snapshots: false,
-            include_exposures: true,
+            node_types: node_types_with(&[NodeType::Exposure]),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         };
         // Focus on "orders" with 1 upstream, 0 downstream
         let filtered = filter_graph(&g, Some("orders"), Some(1), Some(0), &filter, &[]).unwrap();
         // Should have: orders + stg_orders (1 upstream)
         assert_eq!(filtered.node_count(), 2);
+
+        let anchored = filter_graph(&g, Some("=orders"), Some(1), Some(0), &filter, &[]).unwrap();
+        assert_eq!(anchored.node_count(), 2);
     }
 
     #[test]
     fn test_filter_excludes_exposures() {
         let g = make_test_graph();
         let filter = NodeTypeFilter {
-            include_tests: false,
-            include_seeds: false,
-            include_snapshots: false,
-            include_exposures: false,
+            node_types: node_types_with(&[]),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         };
         let filtered = filter_graph(&g, None, None, None, &filter, &[]).unwrap();
         // Exposure should be excluded
@@ -345,10 +931,10 @@ mod tests {
     fn test_filter_model_not_found() {
         let g = make_test_graph();
         let filter = NodeTypeFilter {
-            include_tests: false,
-            include_seeds: false,
-            include_snapshots: false,
-            include_exposures: true,
+            node_types: node_types_with(&[NodeType::Exposure]),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         };
         let result = filter_graph(&g, Some("nonexistent"), None, None, &filter, &[]);
         assert!(result.is_err());
@@ -359,19 +945,70 @@ mod tests {
     #[test]
     fn test_parse_selectors_tag() {
         let selectors = parse_selectors("tag:nightly");
-        assert_eq!(selectors, vec![Selector::Tag("nightly".into())]);
+        assert_eq!(selectors, vec![vec![Selector::Tag("nightly".into())]]);
     }
 
     #[test]
     fn test_parse_selectors_path() {
         let selectors = parse_selectors("path:models/staging");
-        assert_eq!(selectors, vec![Selector::Path("models/staging".into())]);
+        assert_eq!(
+            selectors,
+            vec![vec![Selector::Path("models/staging".into())]]
+        );
     }
 
     #[test]
     fn test_parse_selectors_model_name() {
         let selectors = parse_selectors("orders");
-        assert_eq!(selectors, vec![Selector::ModelName("orders".into())]);
+        assert_eq!(selectors, vec![vec![Selector::ModelName("orders".into())]]);
+    }
+
+    #[test]
+    fn test_parse_selectors_anchored_model_name() {
+        let selectors = parse_selectors("=orders tag:nightly,=stg_orders");
+        assert_eq!(
+            selectors,
+            vec![
+                vec![Selector::ModelName("orders".into())],
+                vec![
+                    Selector::Tag("nightly".into()),
+                    Selector::ModelName("stg_orders".into()),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selectors_from_lines() {
+        let expr = selectors_from_lines(
+            "# changed in this PR\norders\n\n  stg_orders  \ntag:finance,tag:pii # both\n",
+        );
+        assert_eq!(expr, "orders stg_orders tag:finance,tag:pii");
+        assert_eq!(
+            parse_selectors(&expr),
+            vec![
+                vec![Selector::ModelName("orders".into())],
+                vec![Selector::ModelName("stg_orders".into())],
+                vec![Selector::Tag("finance".into()), Selector::Tag("pii".into()),],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_selector_arg() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("selectors.txt");
+        std::fs::write(&path, "orders\ncustomers\n").unwrap();
+        let arg = format!("@{}", path.display());
+        assert_eq!(expand_selector_arg(&arg).unwrap(), "orders customers");
+        assert_eq!(
+            expand_selector_arg("tag:nightly orders").unwrap(),
+            "tag:nightly orders"
+        );
+
+        let missing = format!("@{}", dir.path().join("missing.txt").display());
+        let err = expand_selector_arg(&missing).unwrap_err();
+        assert!(err.to_string().contains("cannot read selector file"));
     }
 
     #[test]
@@ -379,10 +1016,25 @@ mod tests {
         let selectors = parse_selectors("tag:nightly,path:models/staging,orders");
         assert_eq!(
             selectors,
-            vec![
+            vec![vec![
                 Selector::Tag("nightly".into()),
                 Selector::Path("models/staging".into()),
                 Selector::ModelName("orders".into()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_parse_selectors_union_of_intersections() {
+        let selectors = parse_selectors("tag:nightly,path:models/staging  orders");
+        assert_eq!(
+            selectors,
+            vec![
+                vec![
+                    Selector::Tag("nightly".into()),
+                    Selector::Path("models/staging".into()),
+                ],
+                vec![Selector::ModelName("orders".into())],
             ]
         );
     }
@@ -392,11 +1044,11 @@ mod tests {
         let selectors = parse_selectors(" tag:nightly , path:models/staging , orders ");
         assert_eq!(
             selectors,
-            vec![
+            vec![vec![
                 Selector::Tag("nightly".into()),
                 Selector::Path("models/staging".into()),
                 Selector::ModelName("orders".into()),
-            ]
+            ]]
         );
     }
 
@@ -409,7 +1061,7 @@ mod tests {
     #[test]
     fn test_parse_selectors_trailing_comma() {
         let selectors = parse_selectors("orders,");
-        assert_eq!(selectors, vec![Selector::ModelName("orders".into())]);
+        assert_eq!(selectors, vec![vec![Selector::ModelName("orders".into())]]);
     }
 
     // -- Selector-based graph filtering tests ---------------------------------
@@ -454,6 +1106,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -461,6 +1114,7 @@ mod tests {
             c,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -468,6 +1122,7 @@ mod tests {
             d,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
         g
@@ -475,10 +1130,10 @@ mod tests {
 
     fn default_type_filter() -> NodeTypeFilter {
         NodeTypeFilter {
-            include_tests: true,
-            include_seeds: true,
-            include_snapshots: true,
-            include_exposures: true,
+            node_types: ALL_NODE_TYPES.into_iter().collect(),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         }
     }
 
@@ -496,6 +1151,140 @@ mod tests {
         assert!(labels.contains(&"stg_orders".to_string()));
     }
 
+    #[test]
+    fn test_filter_test_kinds() {
+        let mut g = make_tagged_graph();
+        let orders = g.node_indices().find(|&i| g[i].label == "orders").unwrap();
+        for (name, kind) in [
+            ("assert_positive", Some(TestKind::Singular)),
+            ("not_null_orders_id", Some(TestKind::Generic)),
+            ("unknown_kind", None),
+        ] {
+            let mut node = make_node(
+                &format!("test.{}", name),
+                name,
+                NodeType::Test,
+                None,
+                vec![],
+            );
+            node.test_kind = kind;
+            let test = g.add_node(node);
+            g.add_edge(
+                orders,
+                test,
+                EdgeData {
+                    edge_type: EdgeType::Test,
+                    snippet: None,
+                },
+            );
+        }
+        let tests_kept = |tests: TestFilter| {
+            let filter = NodeTypeFilter {
+                tests,
+                ..default_type_filter()
+            };
+            let filtered = filter_graph(&g, None, None, None, &filter, &[]).unwrap();
+            let mut tests: Vec<String> = filtered
+                .node_indices()
+                .filter(|&i| filtered[i].node_type == NodeType::Test)
+                .map(|i| filtered[i].label.clone())
+                .collect();
+            tests.sort();
+            tests
+        };
+
+        assert_eq!(
+            tests_kept(TestFilter::All),
+            vec!["assert_positive", "not_null_orders_id", "unknown_kind"]
+        );
+        assert_eq!(tests_kept(TestFilter::Singular), vec!["assert_positive"]);
+        assert_eq!(tests_kept(TestFilter::Generic), vec!["not_null_orders_id"]);
+    }
+
+    /// Tagged graph with stg_orders as a view and orders as incremental
+    fn make_materialized_graph() -> LineageGraph {
+        let mut g = make_tagged_graph();
+        for idx in g.node_indices().collect::<Vec<_>>() {
+            g[idx].materialization = match g[idx].label.as_str() {
+                "stg_orders" => Some("view".into()),
+                "orders" => Some("incremental".into()),
+                _ => None,
+            };
+        }
+        g
+    }
+
+    fn labels(graph: &LineageGraph) -> Vec<String> {
+        let mut labels: Vec<String> = graph
+            .node_indices()
+            .map(|i| graph[i].label.clone())
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn test_selector_by_materialization() {
+        let g = make_materialized_graph();
+        let selectors = parse_selectors("config.materialized:incremental");
+        assert_eq!(
+            selectors,
+            vec![vec![Selector::Materialized("incremental".into())]]
+        );
+        let filtered =
+            filter_graph(&g, None, None, None, &default_type_filter(), &selectors).unwrap();
+        assert_eq!(labels(&filtered), vec!["orders"]);
+    }
+
+    #[test]
+    fn test_selector_by_schema() {
+        let mut g = make_tagged_graph();
+        for idx in g.node_indices().collect::<Vec<_>>() {
+            g[idx].schema = match g[idx].label.as_str() {
+                "stg_orders" => Some("staging".into()),
+                "orders" => Some("marts".into()),
+                _ => None,
+            };
+        }
+        assert_eq!(
+            parse_selectors("schema:marts config.schema:staging"),
+            vec![
+                vec![Selector::Schema("marts".into())],
+                vec![Selector::Schema("staging".into())],
+            ]
+        );
+        let selectors = parse_selectors("schema:marts");
+        let filtered =
+            filter_graph(&g, None, None, None, &default_type_filter(), &selectors).unwrap();
+        assert_eq!(labels(&filtered), vec!["orders"]);
+        // Nodes without a schema never match
+        let selectors = parse_selectors("schema:raw");
+        let filtered =
+            filter_graph(&g, None, None, None, &default_type_filter(), &selectors).unwrap();
+        assert!(labels(&filtered).is_empty());
+    }
+
+    #[test]
+    fn test_materializations_filter_composes_with_focus() {
+        let g = make_materialized_graph();
+        let filter = NodeTypeFilter {
+            materializations: Some(parse_materializations("view, table")),
+            ..default_type_filter()
+        };
+        // Upstream of orders is stg_orders and raw.orders; the source has no
+        // materialization and orders itself is incremental
+        let filtered = filter_graph(&g, Some("orders"), None, Some(0), &filter, &[]).unwrap();
+        assert_eq!(labels(&filtered), vec!["stg_orders"]);
+
+        let filter = NodeTypeFilter {
+            materializations: Some(parse_materializations("incremental")),
+            ..default_type_filter()
+        };
+        let selectors = parse_selectors("tag:nightly tag:daily");
+        let filtered = filter_graph(&g, None, None, None, &filter, &selectors).unwrap();
+        assert_eq!(labels(&filtered), vec!["orders"]);
+    }
+
     #[test]
     fn test_selector_by_path() {
         let g = make_tagged_graph();
@@ -530,7 +1319,7 @@ mod tests {
     fn test_selector_union_multiple() {
         let g = make_tagged_graph();
         // tag:nightly matches stg_orders, model name "orders" matches orders
-        let selectors = parse_selectors("tag:nightly,orders");
+        let selectors = parse_selectors("tag:nightly orders");
         let filtered =
             filter_graph(&g, None, None, None, &default_type_filter(), &selectors).unwrap();
         assert_eq!(filtered.node_count(), 2);
@@ -542,6 +1331,26 @@ mod tests {
         assert!(labels.contains(&"orders".to_string()));
     }
 
+    #[test]
+    fn test_selector_intersection() {
+        let g = make_tagged_graph();
+        // raw.orders and stg_orders live under models/staging; only stg_orders is nightly
+        let selectors = parse_selectors("tag:nightly,path:models/staging");
+        let filtered =
+            filter_graph(&g, None, None, None, &default_type_filter(), &selectors).unwrap();
+        assert_eq!(labels(&filtered), vec!["stg_orders"]);
+
+        // No node carries both tags
+        let selectors = parse_selectors("tag:nightly,tag:daily");
+        assert!(apply_selectors(&g, &selectors).is_empty());
+
+        // Intersections are unioned with the other terms
+        let selectors = parse_selectors("tag:nightly,tag:daily orders");
+        let filtered =
+            filter_graph(&g, None, None, None, &default_type_filter(), &selectors).unwrap();
+        assert_eq!(labels(&filtered), vec!["orders"]);
+    }
+
     #[test]
     fn test_selector_no_matches() {
         let g = make_tagged_graph();
@@ -580,7 +1389,7 @@ mod tests {
     #[test]
     fn test_selector_empty_does_not_filter() {
         let g = make_tagged_graph();
-        let no_selectors: Vec<Selector> = vec![];
+        let no_selectors: Vec<Vec<Selector>> = vec![];
         let filtered =
             filter_graph(&g, None, None, None, &default_type_filter(), &no_selectors).unwrap();
         assert_eq!(filtered.node_count(), 4);
@@ -589,14 +1398,14 @@ mod tests {
     #[test]
     fn test_apply_selectors_directly() {
         let g = make_tagged_graph();
-        let selectors = parse_selectors("tag:daily,stg_orders");
+        let selectors = parse_selectors("tag:daily stg_orders");
         let matched = apply_selectors(&g, &selectors);
         // tag:daily matches orders, stg_orders matches stg_orders
         assert_eq!(matched.len(), 2);
     }
 
     #[test]
-    fn test_node_matches_any_selector_tag() {
+    fn test_node_matches_selector_tag() {
         let node = make_node(
             "model.x",
             "x",
@@ -604,22 +1413,19 @@ mod tests {
             Some(PathBuf::from("models/x.sql")),
             vec!["nightly".into(), "daily".into()],
         );
-        assert!(node_matches_any_selector(
+        assert!(node_matches_selector(
             &node,
-            &[Selector::Tag("nightly".into())]
+            &Selector::Tag("nightly".into())
         ));
-        assert!(node_matches_any_selector(
+        assert!(node_matches_selector(&node, &Selector::Tag("daily".into())));
+        assert!(!node_matches_selector(
             &node,
-            &[Selector::Tag("daily".into())]
-        ));
-        assert!(!node_matches_any_selector(
-            &node,
-            &[Selector::Tag("weekly".into())]
+            &Selector::Tag("weekly".into())
         ));
     }
 
     #[test]
-    fn test_node_matches_any_selector_path() {
+    fn test_node_matches_selector_path() {
         let node = make_node(
             "model.x",
             "x",
@@ -627,39 +1433,39 @@ mod tests {
             Some(PathBuf::from("models/staging/x.sql")),
             vec![],
         );
-        assert!(node_matches_any_selector(
+        assert!(node_matches_selector(
             &node,
-            &[Selector::Path("models/staging".into())]
+            &Selector::Path("models/staging".into())
         ));
-        assert!(node_matches_any_selector(
+        assert!(node_matches_selector(
             &node,
-            &[Selector::Path("models".into())]
+            &Selector::Path("models".into())
         ));
-        assert!(!node_matches_any_selector(
+        assert!(!node_matches_selector(
             &node,
-            &[Selector::Path("tests".into())]
+            &Selector::Path("tests".into())
         ));
     }
 
     #[test]
-    fn test_node_matches_any_selector_path_none() {
+    fn test_node_matches_selector_path_none() {
         let node = make_node("exposure.x", "x", NodeType::Exposure, None, vec![]);
-        assert!(!node_matches_any_selector(
+        assert!(!node_matches_selector(
             &node,
-            &[Selector::Path("models".into())]
+            &Selector::Path("models".into())
         ));
     }
 
     #[test]
-    fn test_node_matches_any_selector_model_name() {
+    fn test_node_matches_selector_model_name() {
         let node = make_node("model.orders", "orders", NodeType::Model, None, vec![]);
-        assert!(node_matches_any_selector(
+        assert!(node_matches_selector(
             &node,
-            &[Selector::ModelName("orders".into())]
+            &Selector::ModelName("orders".into())
         ));
-        assert!(!node_matches_any_selector(
+        assert!(!node_matches_selector(
             &node,
-            &[Selector::ModelName("customers".into())]
+            &Selector::ModelName("customers".into())
         ));
     }
 
@@ -699,6 +1505,7 @@ mod tests {
             test,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -706,6 +1513,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -713,15 +1521,16 @@ mod tests {
             snap,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
         // Exclude all optional types
         let filter = NodeTypeFilter {
-            include_tests: false,
-            include_seeds: false,
-            include_snapshots: false,
-            include_exposures: false,
+            node_types: node_types_with(&[]),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         };
         let filtered = filter_graph(&g, None, None, None, &filter, &[]).unwrap();
         assert_eq!(filtered.node_count(), 1); // Only the model remains
@@ -733,10 +1542,10 @@ mod tests {
 
         // Include tests only
         let filter2 = NodeTypeFilter {
-            include_tests: true,
-            include_seeds: false,
-            include_snapshots: false,
-            include_exposures: false,
+            node_types: node_types_with(&[NodeType::Test]),
+            edge_types: None,
+            materializations: None,
+            tests: TestFilter::All,
         };
         let filtered2 = filter_graph(&g, None, None, None, &filter2, &[]).unwrap();
         assert_eq!(filtered2.node_count(), 2); // model + test
@@ -753,6 +1562,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -760,10 +1570,39 @@ mod tests {
             a,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
         let result = filter_graph(&g, None, None, None, &default_type_filter(), &[]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_nodes_for_files_with_neighbors() {
+        let mut g = make_test_graph();
+        let stg = g
+            .node_indices()
+            .find(|&i| g[i].label == "stg_orders")
+            .unwrap();
+        g[stg].file_path = Some(PathBuf::from("models/staging/stg_orders.sql"));
+        let files: HashSet<PathBuf> = [PathBuf::from("models/staging/stg_orders.sql")].into();
+
+        let nodes = nodes_for_files(&g, &files, 0);
+        assert_eq!(nodes, HashSet::from([stg]));
+
+        // One hop each way: raw.orders and orders, but not the dashboard
+        let nodes = nodes_for_files(&g, &files, 1);
+        let labels: HashSet<&str> = nodes.iter().map(|&i| g[i].label.as_str()).collect();
+        assert_eq!(
+            labels,
+            HashSet::from(["raw.orders", "stg_orders", "orders"])
+        );
+
+        let sub = induced_subgraph(&g, &nodes);
+        assert_eq!(sub.node_count(), 3);
+        assert_eq!(sub.edge_count(), 2);
+
+        assert!(nodes_for_files(&g, &HashSet::new(), 2).is_empty());
+    }
 }
@@ -0,0 +1,239 @@
+use std::path::Path;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use petgraph::stable_graph::NodeIndex;
+use serde::Deserialize;
+
+use crate::graph::types::*;
+
+/// Name of the optional grouping config file in the project root
+pub const GROUPS_FILE: &str = ".dbt-lineage-groups.yml";
+
+/// Synthetic node groupings loaded from `.dbt-lineage-groups.yml`.
+///
+/// ```yaml
+/// groups:
+///   - name: Finance
+///     models: ["fct_revenue*", "stg_stripe__*"]
+///     tags: [finance]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GroupConfig {
+    #[serde(default)]
+    pub groups: Vec<GroupRule>,
+}
+
+/// One named group; a node belongs to it if its label matches any glob or it
+/// carries any of the tags
+#[derive(Debug, Clone, Deserialize)]
+pub struct GroupRule {
+    pub name: String,
+    /// Glob patterns (`*`, `?`) matched against the node label
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl GroupRule {
+    fn matches(&self, node: &NodeData) -> bool {
+        self.models.iter().any(|p| glob_match(p, &node.label))
+            || self.tags.iter().any(|t| node.tags.contains(t))
+    }
+}
+
+impl GroupConfig {
+    /// Parse a grouping config from YAML
+    pub fn parse(content: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(content)
+    }
+
+    /// Load `.dbt-lineage-groups.yml` from the project root, if present
+    pub fn load(project_dir: &Path) -> Result<Option<Self>> {
+        let path = project_dir.join(GROUPS_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            crate::error::DbtLineageError::FileReadError {
+                path: path.clone(),
+                source: e,
+            }
+        })?;
+        let config = Self::parse(&content)
+            .map_err(|e| crate::error::DbtLineageError::YamlParseError { path, source: e })?;
+        Ok(Some(config))
+    }
+
+    /// Name of the first configured group the node matches
+    pub fn group_for(&self, node: &NodeData) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|rule| rule.matches(node))
+            .map(|rule| rule.name.as_str())
+    }
+
+    /// Group key for a node: its configured group, else its directory
+    pub fn group_key(&self, node: &NodeData) -> String {
+        self.group_for(node)
+            .map(str::to_string)
+            .unwrap_or_else(|| directory_group_key(node))
+    }
+
+    /// Partition the graph's nodes into groups, in first-seen order
+    pub fn clusters(&self, graph: &LineageGraph) -> Vec<(String, Vec<NodeIndex>)> {
+        let mut clusters: IndexMap<String, Vec<NodeIndex>> = IndexMap::new();
+        for idx in graph.node_indices() {
+            clusters
+                .entry(self.group_key(&graph[idx]))
+                .or_default()
+                .push(idx);
+        }
+        clusters.into_iter().collect()
+    }
+}
+
+/// Directory-based group key: the parent directory of the node's
+/// (project-relative) file, or a placeholder for file-less nodes
+pub fn directory_group_key(node: &NodeData) -> String {
+    match &node.file_path {
+        Some(path) => path
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "(root)".to_string()),
+        None => match node.node_type {
+            NodeType::Exposure => "(exposures)".to_string(),
+            NodeType::Phantom => "(unresolved)".to_string(),
+            _ => "(other)".to_string(),
+        },
+    }
+}
+
+/// Match `text` against a glob supporting `*` (any run) and `?` (one character)
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    // Position of the last `*` in the pattern and the text index it resumed at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star, resume)) = backtrack {
+            pi = star + 1;
+            ti = resume + 1;
+            backtrack = Some((star, resume + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn make_node(label: &str, file_path: Option<&str>, tags: &[&str]) -> NodeData {
+        NodeData {
+            unique_id: format!("model.{}", label),
+            label: label.into(),
+            node_type: NodeType::Model,
+            file_path: file_path.map(PathBuf::from),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    const CONFIG: &str = r#"
+groups:
+  - name: Finance
+    models: ["fct_revenue*", "stg_stripe__*"]
+  - name: Marketing
+    tags: [marketing]
+"#;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("stg_*", "stg_orders"));
+        assert!(glob_match("*orders", "stg_orders"));
+        assert!(glob_match("stg_?rders", "stg_orders"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("stg_*", "fct_orders"));
+        assert!(!glob_match("orders", "orders_v2"));
+    }
+
+    #[test]
+    fn test_group_for_globs_and_tags() {
+        let config = GroupConfig::parse(CONFIG).unwrap();
+        let revenue = make_node("fct_revenue_daily", Some("models/marts/x.sql"), &[]);
+        let stripe = make_node("stg_stripe__charges", Some("models/staging/x.sql"), &[]);
+        let campaigns = make_node("campaigns", None, &["marketing"]);
+        let other = make_node("orders", Some("models/marts/orders.sql"), &[]);
+
+        assert_eq!(config.group_for(&revenue), Some("Finance"));
+        assert_eq!(config.group_for(&stripe), Some("Finance"));
+        assert_eq!(config.group_for(&campaigns), Some("Marketing"));
+        assert_eq!(config.group_for(&other), None);
+        assert_eq!(config.group_key(&other), "models/marts");
+    }
+
+    #[test]
+    fn test_first_matching_group_wins() {
+        let config = GroupConfig::parse(
+            "groups:\n  - name: A\n    models: ['*']\n  - name: B\n    models: ['orders']\n",
+        )
+        .unwrap();
+        assert_eq!(config.group_for(&make_node("orders", None, &[])), Some("A"));
+    }
+
+    #[test]
+    fn test_directory_group_key() {
+        assert_eq!(
+            directory_group_key(&make_node("a", Some("models/staging/a.sql"), &[])),
+            "models/staging"
+        );
+        assert_eq!(
+            directory_group_key(&make_node("a", Some("a.sql"), &[])),
+            "(root)"
+        );
+        assert_eq!(directory_group_key(&make_node("a", None, &[])), "(other)");
+    }
+
+    #[test]
+    fn test_clusters_preserve_first_seen_order() {
+        let config = GroupConfig::parse(CONFIG).unwrap();
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("orders", Some("models/marts/orders.sql"), &[]));
+        let b = graph.add_node(make_node("fct_revenue", Some("models/marts/f.sql"), &[]));
+        let c = graph.add_node(make_node("customers", Some("models/marts/c.sql"), &[]));
+
+        let clusters = config.clusters(&graph);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0], ("models/marts".to_string(), vec![a, c]));
+        assert_eq!(clusters[1], ("Finance".to_string(), vec![b]));
+    }
+
+    #[test]
+    fn test_load_missing_and_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(GroupConfig::load(dir.path()).unwrap().is_none());
+
+        std::fs::write(dir.path().join(GROUPS_FILE), CONFIG).unwrap();
+        let config = GroupConfig::load(dir.path()).unwrap().unwrap();
+        assert_eq!(config.groups.len(), 2);
+
+        std::fs::write(dir.path().join(GROUPS_FILE), "groups: [name: 1: 2").unwrap();
+        let err = GroupConfig::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains(GROUPS_FILE));
+    }
+}
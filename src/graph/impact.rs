@@ -1,14 +1,14 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::path::PathBuf;
 
 use petgraph::stable_graph::NodeIndex;
-use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use serde::Serialize;
 
 use super::types::*;
 
 /// Severity level of impact
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, clap::ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum ImpactSeverity {
     Low,
@@ -28,6 +28,77 @@ impl ImpactSeverity {
     }
 }
 
+/// Which way the impact analysis walks the graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImpactDirection {
+    /// Everything affected by a change to the model
+    #[default]
+    Downstream,
+    /// Everything the model depends on (root-cause analysis)
+    Upstream,
+}
+
+impl ImpactDirection {
+    fn petgraph(self) -> Direction {
+        match self {
+            ImpactDirection::Downstream => Direction::Outgoing,
+            ImpactDirection::Upstream => Direction::Incoming,
+        }
+    }
+}
+
+/// Default weights for [`RebuildWeights`]
+const DEFAULT_REBUILD_WEIGHTS: &[(&str, u64)] = &[
+    ("view", 1),
+    ("ephemeral", 0),
+    ("table", 5),
+    ("incremental", 3),
+    ("materialized_view", 3),
+    ("snapshot", 3),
+    ("seed", 1),
+];
+
+/// Relative cost of rebuilding a node, keyed by materialization, for the
+/// rebuild-cost estimate. Snapshots and seeds use the keys `snapshot` and
+/// `seed`; a model without a materialization is a view, as in dbt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebuildWeights {
+    weights: BTreeMap<String, u64>,
+}
+
+impl Default for RebuildWeights {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_REBUILD_WEIGHTS
+                .iter()
+                .map(|&(materialization, weight)| (materialization.to_string(), weight))
+                .collect(),
+        }
+    }
+}
+
+impl RebuildWeights {
+    /// The defaults with some weights replaced or added (e.g. from the config file)
+    pub fn with_overrides(overrides: BTreeMap<String, u64>) -> Self {
+        let mut weights = Self::default();
+        weights.weights.extend(overrides);
+        weights
+    }
+
+    /// Cost of rebuilding `node`. Only models, snapshots and seeds are built;
+    /// a materialization without a weight counts as 1.
+    pub fn weight(&self, node: &NodeData) -> u64 {
+        let key = match node.node_type {
+            NodeType::Model => node.materialization.as_deref().unwrap_or("view"),
+            NodeType::Snapshot => "snapshot",
+            NodeType::Seed => "seed",
+            _ => return 0,
+        };
+        self.weights.get(key).copied().unwrap_or(1)
+    }
+}
+
 /// A single impacted node with its severity
 #[derive(Debug, Clone, Serialize)]
 pub struct ImpactedNode {
@@ -36,18 +107,34 @@ pub struct ImpactedNode {
     pub node_type: String,
     pub severity: ImpactSeverity,
     pub distance: usize,
+    /// Whether the node is a direct child (or parent, upstream) of the source
+    /// model rather than only reachable through other nodes
+    pub direct: bool,
+    /// Project-relative file, for links in `-o markdown`
+    #[serde(skip)]
+    pub file_path: Option<PathBuf>,
 }
 
 /// Full impact analysis report
 #[derive(Debug, Clone, Serialize)]
 pub struct ImpactReport {
     pub source_model: String,
-    pub overall_severity: ImpactSeverity,
+    /// Node type of the node the analysis starts from (model, source, exposure, ...)
+    pub source_node_type: String,
+    pub direction: ImpactDirection,
+    /// Highest severity among the impacted nodes. Severity rates what a change
+    /// breaks downstream, so an upstream report has none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overall_severity: Option<ImpactSeverity>,
     pub affected_models: usize,
     pub affected_tests: usize,
     pub affected_exposures: usize,
+    pub affected_sources: usize,
+    pub affected_seeds: usize,
     pub longest_path_length: usize,
     pub longest_path: Vec<String>,
+    /// Sum of the rebuild weights of the impacted nodes
+    pub rebuild_cost: u64,
     pub impacted_nodes: Vec<ImpactedNode>,
 }
 
@@ -79,13 +166,23 @@ pub fn classify_severity(node: &NodeData) -> ImpactSeverity {
 
 /// Find the longest path from the source node going downstream using BFS/DFS
 pub fn find_longest_path(graph: &LineageGraph, start: NodeIndex) -> Vec<String> {
+    find_longest_path_directed(graph, start, ImpactDirection::Downstream)
+}
+
+/// Find the longest path from `start` in the given direction.
+///
+/// Upstream paths are returned root-first, so they always read in data-flow order.
+pub fn find_longest_path_directed(
+    graph: &LineageGraph,
+    start: NodeIndex,
+    direction: ImpactDirection,
+) -> Vec<String> {
     let mut best_path: Vec<NodeIndex> = vec![start];
     let mut stack: Vec<(NodeIndex, Vec<NodeIndex>)> = vec![(start, vec![start])];
 
     while let Some((current, path)) = stack.pop() {
         let neighbors: Vec<NodeIndex> = graph
-            .edges_directed(current, Direction::Outgoing)
-            .map(|e| e.target())
+            .neighbors_directed(current, direction.petgraph())
             .collect();
 
         if neighbors.is_empty() {
@@ -103,6 +200,10 @@ pub fn find_longest_path(graph: &LineageGraph, start: NodeIndex) -> Vec<String>
         }
     }
 
+    if direction == ImpactDirection::Upstream {
+        best_path.reverse();
+    }
+
     best_path
         .iter()
         .map(|&idx| graph[idx].label.clone())
@@ -111,10 +212,32 @@ pub fn find_longest_path(graph: &LineageGraph, start: NodeIndex) -> Vec<String>
 
 /// Compute the full impact report for a given model
 pub fn compute_impact(graph: &LineageGraph, source_idx: NodeIndex) -> ImpactReport {
+    compute_impact_directed(graph, source_idx, ImpactDirection::Downstream)
+}
+
+/// Compute an impact report walking `direction` from the given model.
+///
+/// Downstream lists everything affected by the model; upstream lists everything
+/// it depends on, nearest first, as a root-cause checklist.
+pub fn compute_impact_directed(
+    graph: &LineageGraph,
+    source_idx: NodeIndex,
+    direction: ImpactDirection,
+) -> ImpactReport {
+    compute_impact_weighted(graph, source_idx, direction, &RebuildWeights::default())
+}
+
+/// Compute an impact report whose rebuild cost sums `weights` over the impacted nodes
+pub fn compute_impact_weighted(
+    graph: &LineageGraph,
+    source_idx: NodeIndex,
+    direction: ImpactDirection,
+    weights: &RebuildWeights,
+) -> ImpactReport {
     let source_node = &graph[source_idx];
     let source_model = source_node.label.clone();
 
-    // BFS downstream to find all impacted nodes with distances
+    // BFS to find all impacted nodes with distances
     let mut visited: HashSet<NodeIndex> = HashSet::new();
     let mut queue: VecDeque<(NodeIndex, usize)> = VecDeque::new();
     visited.insert(source_idx);
@@ -124,19 +247,24 @@ pub fn compute_impact(graph: &LineageGraph, source_idx: NodeIndex) -> ImpactRepo
     let mut affected_models = 0usize;
     let mut affected_tests = 0usize;
     let mut affected_exposures = 0usize;
+    let mut affected_sources = 0usize;
+    let mut affected_seeds = 0usize;
+    let mut rebuild_cost = 0u64;
 
     while let Some((current, distance)) = queue.pop_front() {
-        for edge in graph.edges_directed(current, Direction::Outgoing) {
-            let neighbor = edge.target();
+        for neighbor in graph.neighbors_directed(current, direction.petgraph()) {
             if visited.insert(neighbor) {
                 let node = &graph[neighbor];
                 let severity = classify_severity(node);
                 let next_distance = distance + 1;
+                rebuild_cost += weights.weight(node);
 
                 match node.node_type {
                     NodeType::Model => affected_models += 1,
                     NodeType::Test => affected_tests += 1,
                     NodeType::Exposure => affected_exposures += 1,
+                    NodeType::Source => affected_sources += 1,
+                    NodeType::Seed => affected_seeds += 1,
                     _ => {}
                 }
 
@@ -146,6 +274,8 @@ pub fn compute_impact(graph: &LineageGraph, source_idx: NodeIndex) -> ImpactRepo
                     node_type: node.node_type.label().to_string(),
                     severity,
                     distance: next_distance,
+                    direct: next_distance == 1,
+                    file_path: node.file_path.clone(),
                 });
 
                 queue.push_back((neighbor, next_distance));
@@ -153,30 +283,47 @@ pub fn compute_impact(graph: &LineageGraph, source_idx: NodeIndex) -> ImpactRepo
         }
     }
 
-    // Sort by severity (descending), then distance
-    impacted_nodes.sort_by(|a, b| {
-        b.severity
-            .cmp(&a.severity)
-            .then(a.distance.cmp(&b.distance))
-    });
+    // Ties are broken by unique_id so the order doesn't depend on graph order
+    match direction {
+        // Sort by severity (descending), then distance
+        ImpactDirection::Downstream => impacted_nodes.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then(a.distance.cmp(&b.distance))
+                .then_with(|| a.unique_id.cmp(&b.unique_id))
+        }),
+        // Nearest dependencies first: they are the first place to look
+        ImpactDirection::Upstream => impacted_nodes.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.unique_id.cmp(&b.unique_id))
+        }),
+    }
 
-    let overall_severity = impacted_nodes
-        .iter()
-        .map(|n| n.severity)
-        .max()
-        .unwrap_or(ImpactSeverity::Low);
+    let overall_severity = (direction == ImpactDirection::Downstream).then(|| {
+        impacted_nodes
+            .iter()
+            .map(|n| n.severity)
+            .max()
+            .unwrap_or(ImpactSeverity::Low)
+    });
 
-    let longest_path = find_longest_path(graph, source_idx);
+    let longest_path = find_longest_path_directed(graph, source_idx, direction);
     let longest_path_length = longest_path.len().saturating_sub(1);
 
     ImpactReport {
         source_model,
+        source_node_type: source_node.node_type.label().to_string(),
+        direction,
         overall_severity,
         affected_models,
         affected_tests,
         affected_exposures,
+        affected_sources,
+        affected_seeds,
         longest_path_length,
         longest_path,
+        rebuild_cost,
         impacted_nodes,
     }
 }
@@ -198,10 +345,8 @@ mod tests {
             label: label.into(),
             node_type,
             file_path: file_path.map(PathBuf::from),
-            description: None,
             materialization: materialization.map(|s| s.to_string()),
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -248,6 +393,7 @@ mod tests {
             stg,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -255,6 +401,7 @@ mod tests {
             mart,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -262,6 +409,7 @@ mod tests {
             test,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -269,6 +417,7 @@ mod tests {
             exp,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
@@ -344,11 +493,42 @@ mod tests {
         assert_eq!(report.affected_models, 1); // orders
         assert_eq!(report.affected_tests, 1); // orders_positive
         assert_eq!(report.affected_exposures, 1); // dashboard
-        assert_eq!(report.overall_severity, ImpactSeverity::Critical);
+        assert_eq!(report.overall_severity, Some(ImpactSeverity::Critical));
         assert!(report.longest_path_length >= 2);
         assert_eq!(report.impacted_nodes.len(), 3);
     }
 
+    #[test]
+    fn test_compute_impact_marks_direct_dependents() {
+        let (mut g, stg) = make_test_graph();
+        let exp = g
+            .node_indices()
+            .find(|&i| g[i].unique_id == "exposure.dashboard")
+            .unwrap();
+        // dashboard now reads stg_orders directly as well as through orders
+        g.add_edge(
+            stg,
+            exp,
+            EdgeData {
+                edge_type: EdgeType::Exposure,
+                snippet: None,
+            },
+        );
+
+        let report = compute_impact(&g, stg);
+        let direct = |label: &str| {
+            report
+                .impacted_nodes
+                .iter()
+                .find(|n| n.label == label)
+                .unwrap()
+                .direct
+        };
+        assert!(direct("orders"));
+        assert!(direct("dashboard"));
+        assert!(!direct("orders_positive"));
+    }
+
     #[test]
     fn test_compute_impact_leaf_node() {
         let (g, _) = make_test_graph();
@@ -363,6 +543,37 @@ mod tests {
         assert_eq!(report.affected_tests, 0);
         assert_eq!(report.affected_exposures, 0);
         assert!(report.impacted_nodes.is_empty());
+        assert_eq!(report.source_node_type, "exposure");
+        assert_eq!(report.overall_severity, Some(ImpactSeverity::Low));
+        assert_eq!(report.longest_path, vec!["dashboard"]);
+        assert_eq!(report.longest_path_length, 0);
+    }
+
+    #[test]
+    fn test_compute_impact_from_source() {
+        let (g, _) = make_test_graph();
+        let src = g
+            .node_indices()
+            .find(|&i| g[i].unique_id == "source.raw.orders")
+            .unwrap();
+        let report = compute_impact(&g, src);
+
+        assert_eq!(report.source_model, "raw.orders");
+        assert_eq!(report.source_node_type, "source");
+        // Full reach: both models, the test and the exposure at the end
+        assert_eq!(report.affected_models, 2);
+        assert_eq!(report.affected_tests, 1);
+        assert_eq!(report.affected_exposures, 1);
+        assert_eq!(report.affected_sources, 0);
+        assert_eq!(report.impacted_nodes.len(), 4);
+        assert_eq!(report.impacted_nodes[0].label, "dashboard");
+        assert_eq!(report.impacted_nodes[0].distance, 3);
+        assert_eq!(report.overall_severity, Some(ImpactSeverity::Critical));
+        assert_eq!(report.longest_path_length, 3);
+
+        // Nothing is upstream of a source
+        let report = compute_impact_directed(&g, src, ImpactDirection::Upstream);
+        assert!(report.impacted_nodes.is_empty());
     }
 
     #[test]
@@ -409,4 +620,124 @@ mod tests {
         let snap = make_node("snapshot.snap", "snap", NodeType::Snapshot, None, None);
         assert_eq!(classify_severity(&snap), ImpactSeverity::Medium);
     }
+
+    #[test]
+    fn test_compute_impact_upstream() {
+        let (g, _) = make_test_graph();
+        let mart = g.node_indices().find(|&i| g[i].label == "orders").unwrap();
+        let report = compute_impact_directed(&g, mart, ImpactDirection::Upstream);
+
+        assert_eq!(report.direction, ImpactDirection::Upstream);
+        // Severity rates downstream breakage; it means nothing upstream
+        assert_eq!(report.overall_severity, None);
+        assert_eq!(report.affected_models, 1); // stg_orders
+        assert_eq!(report.affected_sources, 1); // raw.orders
+        assert_eq!(report.affected_tests, 0);
+        assert_eq!(report.affected_exposures, 0);
+
+        let deps: Vec<(&str, usize)> = report
+            .impacted_nodes
+            .iter()
+            .map(|n| (n.label.as_str(), n.distance))
+            .collect();
+        assert_eq!(deps, vec![("stg_orders", 1), ("raw.orders", 2)]);
+
+        // Root-first so it reads in data-flow order
+        assert_eq!(
+            report.longest_path,
+            vec!["raw.orders", "stg_orders", "orders"]
+        );
+        assert_eq!(report.longest_path_length, 2);
+    }
+
+    #[test]
+    fn test_impact_ties_ordered_by_unique_id() {
+        let mut g = LineageGraph::new();
+        let root = g.add_node(make_node("model.root", "root", NodeType::Model, None, None));
+        // Equal severity and distance, added in reverse unique_id order
+        for id in ["model.c", "model.a", "model.b"] {
+            let child = g.add_node(make_node(id, "child", NodeType::Model, None, None));
+            g.add_edge(
+                root,
+                child,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+            g.add_edge(
+                child,
+                root,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        for direction in [ImpactDirection::Downstream, ImpactDirection::Upstream] {
+            let report = compute_impact_directed(&g, root, direction);
+            let ids: Vec<&str> = report
+                .impacted_nodes
+                .iter()
+                .map(|n| n.unique_id.as_str())
+                .collect();
+            assert_eq!(ids, vec!["model.a", "model.b", "model.c"]);
+        }
+    }
+
+    #[test]
+    fn test_compute_impact_upstream_of_root() {
+        let (g, _) = make_test_graph();
+        let src = g
+            .node_indices()
+            .find(|&i| g[i].label == "raw.orders")
+            .unwrap();
+        let report = compute_impact_directed(&g, src, ImpactDirection::Upstream);
+        assert!(report.impacted_nodes.is_empty());
+        assert_eq!(report.longest_path, vec!["raw.orders"]);
+    }
+
+    #[test]
+    fn test_compute_impact_defaults_to_downstream() {
+        let (g, stg) = make_test_graph();
+        let report = compute_impact(&g, stg);
+        assert_eq!(report.direction, ImpactDirection::Downstream);
+        assert_eq!(report.affected_sources, 0);
+    }
+
+    #[test]
+    fn test_rebuild_cost() {
+        let (g, stg) = make_test_graph();
+        // orders (table) = 5; the test and the exposure aren't built
+        assert_eq!(compute_impact(&g, stg).rebuild_cost, 5);
+
+        let src = g
+            .node_indices()
+            .find(|&i| g[i].node_type == NodeType::Source)
+            .unwrap();
+        // stg_orders (view) + orders (table)
+        assert_eq!(compute_impact(&g, src).rebuild_cost, 6);
+
+        let weights = RebuildWeights::with_overrides(BTreeMap::from([
+            ("table".to_string(), 10),
+            ("view".to_string(), 0),
+        ]));
+        let report = compute_impact_weighted(&g, src, ImpactDirection::Downstream, &weights);
+        assert_eq!(report.rebuild_cost, 10);
+    }
+
+    #[test]
+    fn test_rebuild_weight_by_node() {
+        let weights = RebuildWeights::default();
+        let model = |m: Option<&str>| make_node("model.x", "x", NodeType::Model, m, None);
+        assert_eq!(weights.weight(&model(None)), 1);
+        assert_eq!(weights.weight(&model(Some("incremental"))), 3);
+        assert_eq!(weights.weight(&model(Some("ephemeral"))), 0);
+        assert_eq!(weights.weight(&model(Some("custom"))), 1);
+        let snapshot = make_node("snapshot.s", "s", NodeType::Snapshot, None, None);
+        assert_eq!(weights.weight(&snapshot), 3);
+        let test = make_node("test.t", "t", NodeType::Test, None, None);
+        assert_eq!(weights.weight(&test), 0);
+    }
 }
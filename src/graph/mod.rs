@@ -1,5 +1,14 @@
+pub mod anonymize;
 pub mod builder;
+pub mod check;
+pub mod collapse;
 pub mod diff;
 pub mod filter;
+pub mod groups;
 pub mod impact;
+pub mod paths;
+pub mod sla;
+pub mod sort;
+pub mod stats;
+pub mod suggest;
 pub mod types;
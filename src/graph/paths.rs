@@ -0,0 +1,313 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+
+use super::types::*;
+
+/// Find a node by label or unique_id (`orders`, `model.orders`, `raw.orders`).
+/// A name anchored with `=` (`=orders`) must equal the label or unique_id;
+/// otherwise a unique_id ending in `.<name>` is accepted as a fallback.
+pub fn find_node(graph: &LineageGraph, name: &str) -> Option<NodeIndex> {
+    let (name, anchored) = match name.strip_prefix('=') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    graph
+        .node_indices()
+        .find(|&idx| graph[idx].label == name || graph[idx].unique_id == name)
+        .or_else(|| {
+            if anchored {
+                return None;
+            }
+            graph
+                .node_indices()
+                .find(|&idx| graph[idx].unique_id.ends_with(&format!(".{}", name)))
+        })
+}
+
+/// Every node reachable from `node` against or along edge direction: its
+/// ancestors for `Incoming`, its descendants for `Outgoing`. `node` itself is
+/// not included.
+pub fn reachable(
+    graph: &LineageGraph,
+    node: NodeIndex,
+    direction: Direction,
+) -> HashSet<NodeIndex> {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::from([node]);
+    while let Some(current) = queue.pop_front() {
+        for next in graph.neighbors_directed(current, direction) {
+            if next != node && seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen
+}
+
+/// Shortest downstream path from `from` to `to` (inclusive), following edge direction.
+/// Returns `None` when `to` is not reachable from `from`.
+pub fn shortest_path(
+    graph: &LineageGraph,
+    from: NodeIndex,
+    to: NodeIndex,
+) -> Option<Vec<NodeIndex>> {
+    let mut parent: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut visited: HashSet<NodeIndex> = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(from);
+    queue.push_back(from);
+
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            let mut path = vec![to];
+            let mut node = to;
+            while let Some(&prev) = parent.get(&node) {
+                path.push(prev);
+                node = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for edge in graph.edges_directed(current, Direction::Outgoing) {
+            let next = edge.target();
+            if visited.insert(next) {
+                parent.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// The main line through `node`: the shortest path from its nearest upstream
+/// source to its nearest downstream exposure, in downstream order. Without a
+/// source (or exposure) in reach, the nearest root (or leaf) ends the line
+/// instead. Neighbors are visited by unique_id, so ties resolve the same way
+/// every time.
+pub fn main_line(graph: &LineageGraph, node: NodeIndex) -> Vec<NodeIndex> {
+    let mut line = path_to_nearest_end(graph, node, Direction::Incoming, NodeType::Source);
+    line.reverse();
+    line.extend(
+        path_to_nearest_end(graph, node, Direction::Outgoing, NodeType::Exposure)
+            .into_iter()
+            .skip(1),
+    );
+    line
+}
+
+/// Shortest path from `node` (inclusive) against or along edge direction to the
+/// nearest node of type `preferred`, or failing that to the nearest node with
+/// no further neighbors that way
+fn path_to_nearest_end(
+    graph: &LineageGraph,
+    node: NodeIndex,
+    direction: Direction,
+    preferred: NodeType,
+) -> Vec<NodeIndex> {
+    let mut parent: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut queue = VecDeque::from([node]);
+    let mut dead_end = None;
+    let mut end = None;
+    while let Some(current) = queue.pop_front() {
+        if current != node && graph[current].node_type == preferred {
+            end = Some(current);
+            break;
+        }
+        let mut next: Vec<NodeIndex> = graph.neighbors_directed(current, direction).collect();
+        if next.is_empty() {
+            dead_end.get_or_insert(current);
+        }
+        next.sort_by(|&a, &b| graph[a].unique_id.cmp(&graph[b].unique_id));
+        for n in next {
+            if n != node && !parent.contains_key(&n) {
+                parent.insert(n, current);
+                queue.push_back(n);
+            }
+        }
+    }
+
+    let mut current = end.or(dead_end).unwrap_or(node);
+    let mut path = vec![current];
+    while let Some(&prev) = parent.get(&current) {
+        path.push(prev);
+        current = prev;
+    }
+    path.reverse();
+    path
+}
+
+/// All simple downstream paths from `from` to `to` with at most `max_nodes` nodes each,
+/// shortest first
+pub fn all_simple_paths(
+    graph: &LineageGraph,
+    from: NodeIndex,
+    to: NodeIndex,
+    max_nodes: usize,
+) -> Vec<Vec<NodeIndex>> {
+    if from == to {
+        return vec![vec![from]];
+    }
+    let max_intermediate = max_nodes.saturating_sub(2);
+    let mut paths: Vec<Vec<NodeIndex>> =
+        petgraph::algo::all_simple_paths(graph, from, to, 0, Some(max_intermediate)).collect();
+    paths.sort_by_key(|p| p.len());
+    paths
+}
+
+/// Build a graph containing only the nodes and consecutive edges of the given paths
+pub fn paths_subgraph(graph: &LineageGraph, paths: &[Vec<NodeIndex>]) -> LineageGraph {
+    let mut sub = LineageGraph::new();
+    let mut index_map: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut added_edges: HashSet<(NodeIndex, NodeIndex)> = HashSet::new();
+
+    for path in paths {
+        for &idx in path {
+            index_map
+                .entry(idx)
+                .or_insert_with(|| sub.add_node(graph[idx].clone()));
+        }
+        for pair in path.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if !added_edges.insert((a, b)) {
+                continue;
+            }
+            if let Some(edge) = graph.find_edge(a, b) {
+                sub.add_edge(index_map[&a], index_map[&b], graph[edge].clone());
+            }
+        }
+    }
+
+    sub
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    fn edge() -> EdgeData {
+        EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        }
+    }
+
+    /// src -> stg -> orders -> dashboard, plus src -> orders shortcut
+    fn make_graph() -> (LineageGraph, [NodeIndex; 4]) {
+        let mut g = LineageGraph::new();
+        let src = g.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        let stg = g.add_node(make_node("model.stg_orders", "stg_orders", NodeType::Model));
+        let orders = g.add_node(make_node("model.orders", "orders", NodeType::Model));
+        let dash = g.add_node(make_node(
+            "exposure.dashboard",
+            "dashboard",
+            NodeType::Exposure,
+        ));
+        g.add_edge(src, stg, edge());
+        g.add_edge(stg, orders, edge());
+        g.add_edge(orders, dash, edge());
+        g.add_edge(src, orders, edge());
+        (g, [src, stg, orders, dash])
+    }
+
+    #[test]
+    fn test_find_node() {
+        let (mut g, [src, _, orders, _]) = make_graph();
+        assert_eq!(find_node(&g, "orders"), Some(orders));
+        assert_eq!(find_node(&g, "model.orders"), Some(orders));
+        assert_eq!(find_node(&g, "raw.orders"), Some(src));
+        assert_eq!(find_node(&g, "missing"), None);
+
+        // Anchored names skip the unique_id suffix fallback
+        assert_eq!(find_node(&g, "=orders"), Some(orders));
+        assert_eq!(find_node(&g, "=source.raw.orders"), Some(src));
+        let customers = g.add_node(make_node(
+            "model.jaffle.customers",
+            "customers_v2",
+            NodeType::Model,
+        ));
+        assert_eq!(find_node(&g, "jaffle.customers"), Some(customers));
+        assert_eq!(find_node(&g, "=jaffle.customers"), None);
+    }
+
+    #[test]
+    fn test_shortest_path_uses_shortcut() {
+        let (g, [src, _, orders, dash]) = make_graph();
+        assert_eq!(shortest_path(&g, src, dash), Some(vec![src, orders, dash]));
+    }
+
+    #[test]
+    fn test_shortest_path_none_against_direction() {
+        let (g, [src, _, _, dash]) = make_graph();
+        assert_eq!(shortest_path(&g, dash, src), None);
+    }
+
+    #[test]
+    fn test_shortest_path_same_node() {
+        let (g, [src, ..]) = make_graph();
+        assert_eq!(shortest_path(&g, src, src), Some(vec![src]));
+    }
+
+    #[test]
+    fn test_main_line() {
+        let (mut g, [src, stg, orders, dash]) = make_graph();
+        // The shortcut wins upstream of orders; stg's only way up is direct
+        assert_eq!(main_line(&g, orders), vec![src, orders, dash]);
+        assert_eq!(main_line(&g, stg), vec![src, stg, orders, dash]);
+        assert_eq!(main_line(&g, src), vec![src, orders, dash]);
+        assert_eq!(main_line(&g, dash), vec![src, orders, dash]);
+
+        // An exposure further away beats a nearer leaf that isn't one
+        let test = g.add_node(make_node("test.not_null", "not_null", NodeType::Test));
+        g.add_edge(stg, test, edge());
+        assert_eq!(main_line(&g, stg), vec![src, stg, orders, dash]);
+
+        // Without any exposure downstream, the nearest leaf ends the line
+        let seed = g.add_node(make_node("seed.countries", "countries", NodeType::Seed));
+        let lonely = g.add_node(make_node("model.lonely", "lonely", NodeType::Model));
+        g.add_edge(seed, lonely, edge());
+        assert_eq!(main_line(&g, lonely), vec![seed, lonely]);
+        assert_eq!(main_line(&g, test), vec![src, stg, test]);
+    }
+
+    #[test]
+    fn test_all_simple_paths() {
+        let (g, [src, stg, orders, dash]) = make_graph();
+        let paths = all_simple_paths(&g, src, dash, 10);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0], vec![src, orders, dash]);
+        assert_eq!(paths[1], vec![src, stg, orders, dash]);
+
+        // Bounded length excludes the longer path
+        assert_eq!(all_simple_paths(&g, src, dash, 3).len(), 1);
+    }
+
+    #[test]
+    fn test_paths_subgraph() {
+        let (g, [src, stg, orders, dash]) = make_graph();
+        let paths = vec![vec![src, orders, dash], vec![src, stg, orders, dash]];
+        let sub = paths_subgraph(&g, &paths);
+        assert_eq!(sub.node_count(), 4);
+        assert_eq!(sub.edge_count(), 4);
+
+        let sub = paths_subgraph(&g, &paths[..1]);
+        assert_eq!(sub.node_count(), 3);
+        assert_eq!(sub.edge_count(), 2);
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::graph::types::*;
+use crate::parser::artifacts::{RunStatus, RunStatusMap};
+
+/// `meta` key holding a node's SLA: the most hours allowed since its last
+/// successful run
+pub const SLA_META_KEY: &str = "sla_hours";
+
+/// SLA in hours from `meta.sla_hours`: a positive number, or a string holding one
+pub fn sla_hours(node: &NodeData) -> Option<f64> {
+    let value = node.meta.get(SLA_META_KEY)?;
+    let hours = match value {
+        serde_json::Value::Number(n) => n.as_f64()?,
+        serde_json::Value::String(s) => s.trim().parse().ok()?,
+        _ => return None,
+    };
+    (hours.is_finite() && hours > 0.0).then_some(hours)
+}
+
+/// A node's SLA measured against its last run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlaCheck {
+    pub sla_hours: f64,
+    /// Hours since the last successful run; `None` when the last recorded run
+    /// failed or was skipped
+    pub age_hours: Option<f64>,
+}
+
+impl SlaCheck {
+    /// Whether the last success is older than the SLA, or unknown
+    pub fn breached(&self) -> bool {
+        self.age_hours.is_none_or(|age| age > self.sla_hours)
+    }
+}
+
+/// Check a node's SLA at `now`. `None` when the node has no SLA or no run
+/// recorded in run_results, since there is nothing to measure.
+pub fn evaluate_sla(node: &NodeData, status: &RunStatus, now: DateTime<Utc>) -> Option<SlaCheck> {
+    let sla_hours = sla_hours(node)?;
+    let last_success = match status {
+        RunStatus::NeverRun => return None,
+        RunStatus::Success { completed_at } => Some(*completed_at),
+        RunStatus::Outdated { run_at, .. } => Some(*run_at),
+        RunStatus::Error { .. } | RunStatus::Skipped { .. } => None,
+    };
+    let age_hours = last_success
+        .map(|at| (now - at).num_seconds().max(0) as f64 / 3600.0)
+        .map(|hours| (hours * 10.0).round() / 10.0);
+    Some(SlaCheck {
+        sla_hours,
+        age_hours,
+    })
+}
+
+/// A node past its SLA
+#[derive(Debug, Clone, Serialize)]
+pub struct SlaBreach {
+    pub unique_id: String,
+    pub label: String,
+    pub sla_hours: f64,
+    /// Hours since the last successful run, rounded to a tenth; absent when
+    /// the last recorded run failed or was skipped
+    pub age_hours: Option<f64>,
+}
+
+/// Result of `check --sla`
+#[derive(Debug, Clone, Serialize)]
+pub struct SlaReport {
+    /// Nodes with an SLA and a recorded run
+    pub checked: usize,
+    /// Nodes with an SLA but no run in run_results
+    pub not_run: usize,
+    pub breaches: Vec<SlaBreach>,
+}
+
+impl SlaReport {
+    pub fn passed(&self) -> bool {
+        self.breaches.is_empty()
+    }
+}
+
+/// Find every node whose last successful run is older than its `meta.sla_hours`.
+/// Nodes without an SLA are ignored.
+pub fn check_sla(graph: &LineageGraph, run_status: &RunStatusMap, now: DateTime<Utc>) -> SlaReport {
+    let mut report = SlaReport {
+        checked: 0,
+        not_run: 0,
+        breaches: Vec::new(),
+    };
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        if sla_hours(node).is_none() {
+            continue;
+        }
+        let status = run_status
+            .get(&node.unique_id)
+            .unwrap_or(&RunStatus::NeverRun);
+        let Some(check) = evaluate_sla(node, status, now) else {
+            report.not_run += 1;
+            continue;
+        };
+        report.checked += 1;
+        if check.breached() {
+            report.breaches.push(SlaBreach {
+                unique_id: node.unique_id.clone(),
+                label: node.label.clone(),
+                sla_hours: check.sla_hours,
+                age_hours: check.age_hours,
+            });
+        }
+    }
+    report
+        .breaches
+        .sort_by(|a, b| a.unique_id.cmp(&b.unique_id));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn make_node(unique_id: &str, sla: Option<serde_json::Value>) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type: NodeType::Model,
+            meta: sla
+                .map(|v| [(SLA_META_KEY.to_string(), v)].into_iter().collect())
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sla_hours() {
+        let hours = |v: serde_json::Value| sla_hours(&make_node("model.a", Some(v)));
+        assert_eq!(hours(serde_json::json!(6)), Some(6.0));
+        assert_eq!(hours(serde_json::json!(1.5)), Some(1.5));
+        assert_eq!(hours(serde_json::json!(" 24 ")), Some(24.0));
+        assert_eq!(hours(serde_json::json!(0)), None);
+        assert_eq!(hours(serde_json::json!("daily")), None);
+        assert_eq!(hours(serde_json::json!(true)), None);
+        assert_eq!(sla_hours(&make_node("model.a", None)), None);
+    }
+
+    #[test]
+    fn test_evaluate_sla() {
+        let now = Utc::now();
+        let node = make_node("model.a", Some(serde_json::json!(6)));
+        let ran = |hours: i64| RunStatus::Success {
+            completed_at: now - Duration::hours(hours),
+        };
+
+        let check = evaluate_sla(&node, &ran(2), now).unwrap();
+        assert_eq!(check.age_hours, Some(2.0));
+        assert!(!check.breached());
+        assert!(evaluate_sla(&node, &ran(9), now).unwrap().breached());
+
+        let failed = RunStatus::Error {
+            completed_at: Some(now),
+            message: "boom".into(),
+        };
+        let check = evaluate_sla(&node, &failed, now).unwrap();
+        assert_eq!(check.age_hours, None);
+        assert!(check.breached());
+
+        assert!(evaluate_sla(&node, &RunStatus::NeverRun, now).is_none());
+        let no_sla = make_node("model.b", None);
+        assert!(evaluate_sla(&no_sla, &ran(100), now).is_none());
+    }
+
+    #[test]
+    fn test_check_sla() {
+        let now = Utc::now();
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.fresh", Some(serde_json::json!(6))));
+        graph.add_node(make_node("model.stale", Some(serde_json::json!(6))));
+        graph.add_node(make_node("model.never", Some(serde_json::json!(6))));
+        graph.add_node(make_node("model.no_sla", None));
+        let mut status = RunStatusMap::new();
+        status.insert(
+            "model.fresh".into(),
+            RunStatus::Success {
+                completed_at: now - Duration::hours(1),
+            },
+        );
+        status.insert(
+            "model.stale".into(),
+            RunStatus::Success {
+                completed_at: now - Duration::minutes(9 * 60 + 30),
+            },
+        );
+        status.insert(
+            "model.no_sla".into(),
+            RunStatus::Success {
+                completed_at: now - Duration::days(30),
+            },
+        );
+
+        let report = check_sla(&graph, &status, now);
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.not_run, 1);
+        assert!(!report.passed());
+        assert_eq!(report.breaches.len(), 1);
+        assert_eq!(report.breaches[0].unique_id, "model.stale");
+        assert_eq!(report.breaches[0].age_hours, Some(9.5));
+    }
+}
@@ -0,0 +1,228 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use serde::Deserialize;
+
+use crate::graph::impact::compute_impact;
+use crate::graph::types::*;
+use crate::parser::artifacts::{RunStatus, RunStatusMap};
+
+/// Node orderings selectable with `--sort`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeSort {
+    /// Alphabetically by name
+    Name,
+    /// By node type (sources first, exposures last), then name
+    Type,
+    /// Most downstream dependents first, then name
+    Downstream,
+    /// Failing nodes first (error, outdated, skipped, never run, success), then name
+    Status,
+}
+
+/// Position of a node type in `--sort type` order, following the flow of data
+fn type_rank(node_type: NodeType) -> u8 {
+    match node_type {
+        NodeType::Source => 0,
+        NodeType::Seed => 1,
+        NodeType::Snapshot => 2,
+        NodeType::Model => 3,
+        NodeType::Test => 4,
+        NodeType::Exposure => 5,
+        NodeType::Phantom => 6,
+    }
+}
+
+/// Position of a run status in `--sort status` order: the ones needing attention first
+fn status_rank(status: Option<&RunStatus>) -> u8 {
+    match status {
+        Some(RunStatus::Error { .. }) => 0,
+        Some(RunStatus::Outdated { .. }) => 1,
+        Some(RunStatus::Skipped { .. }) => 2,
+        Some(RunStatus::NeverRun) | None => 3,
+        Some(RunStatus::Success { .. }) => 4,
+    }
+}
+
+/// Sort `nodes` in place. Ties, and every node under `--sort name`, are ordered
+/// by label and then unique_id. `run_status` is only consulted for
+/// [`NodeSort::Status`]; nodes missing from it count as never run.
+pub fn sort_nodes(
+    graph: &LineageGraph,
+    nodes: &mut [NodeIndex],
+    sort: NodeSort,
+    run_status: Option<&RunStatusMap>,
+) {
+    let by_name = |a: &NodeIndex, b: &NodeIndex| -> Ordering {
+        (&graph[*a].label, &graph[*a].unique_id).cmp(&(&graph[*b].label, &graph[*b].unique_id))
+    };
+
+    match sort {
+        NodeSort::Name => nodes.sort_by(by_name),
+        NodeSort::Type => nodes.sort_by(|a, b| {
+            type_rank(graph[*a].node_type)
+                .cmp(&type_rank(graph[*b].node_type))
+                .then_with(|| by_name(a, b))
+        }),
+        NodeSort::Downstream => {
+            let downstream: HashMap<NodeIndex, usize> = nodes
+                .iter()
+                .map(|&idx| (idx, compute_impact(graph, idx).impacted_nodes.len()))
+                .collect();
+            nodes.sort_by(|a, b| {
+                downstream[b]
+                    .cmp(&downstream[a])
+                    .then_with(|| by_name(a, b))
+            });
+        }
+        NodeSort::Status => {
+            let rank = |idx: &NodeIndex| {
+                status_rank(run_status.and_then(|m| m.get(&graph[*idx].unique_id)))
+            };
+            nodes.sort_by(|a, b| rank(a).cmp(&rank(b)).then_with(|| by_name(a, b)));
+        }
+    }
+}
+
+/// Copy of `graph` with its nodes inserted in `sort` order, so that renderers
+/// walking `node_indices()` (JSON, CSV, ...) emit them in that order
+pub fn sorted_graph(
+    graph: &LineageGraph,
+    sort: NodeSort,
+    run_status: Option<&RunStatusMap>,
+) -> LineageGraph {
+    let mut order: Vec<NodeIndex> = graph.node_indices().collect();
+    sort_nodes(graph, &mut order, sort, run_status);
+
+    let mut sorted = LineageGraph::new();
+    let mapping: HashMap<NodeIndex, NodeIndex> = order
+        .into_iter()
+        .map(|idx| (idx, sorted.add_node(graph[idx].clone())))
+        .collect();
+    for edge in graph.edge_references() {
+        sorted.add_edge(
+            mapping[&edge.source()],
+            mapping[&edge.target()],
+            edge.weight().clone(),
+        );
+    }
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_node(unique_id: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    /// raw -> stg -> (orders, customers); orders -> dashboard
+    fn make_graph() -> LineageGraph {
+        let mut g = LineageGraph::new();
+        let orders = g.add_node(make_node("model.orders", NodeType::Model));
+        let dashboard = g.add_node(make_node("exposure.dashboard", NodeType::Exposure));
+        let stg = g.add_node(make_node("model.stg", NodeType::Model));
+        let raw = g.add_node(make_node("source.raw.raw", NodeType::Source));
+        let customers = g.add_node(make_node("model.customers", NodeType::Model));
+        for (a, b) in [
+            (raw, stg),
+            (stg, orders),
+            (stg, customers),
+            (orders, dashboard),
+        ] {
+            g.add_edge(
+                a,
+                b,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        g
+    }
+
+    fn sorted_ids(
+        graph: &LineageGraph,
+        sort: NodeSort,
+        run_status: Option<&RunStatusMap>,
+    ) -> Vec<String> {
+        let mut nodes: Vec<NodeIndex> = graph.node_indices().collect();
+        sort_nodes(graph, &mut nodes, sort, run_status);
+        nodes.into_iter().map(|i| graph[i].label.clone()).collect()
+    }
+
+    #[test]
+    fn test_sort_by_name_and_type() {
+        let g = make_graph();
+        assert_eq!(
+            sorted_ids(&g, NodeSort::Name, None),
+            vec!["customers", "dashboard", "orders", "raw", "stg"]
+        );
+        assert_eq!(
+            sorted_ids(&g, NodeSort::Type, None),
+            vec!["raw", "customers", "orders", "stg", "dashboard"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_downstream_count() {
+        let g = make_graph();
+        assert_eq!(
+            sorted_ids(&g, NodeSort::Downstream, None),
+            vec!["raw", "stg", "orders", "customers", "dashboard"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_status() {
+        let g = make_graph();
+        let mut status = RunStatusMap::new();
+        status.insert(
+            "model.stg".into(),
+            RunStatus::Success {
+                completed_at: chrono::Utc::now(),
+            },
+        );
+        status.insert(
+            "model.orders".into(),
+            RunStatus::Error {
+                completed_at: None,
+                message: "boom".into(),
+            },
+        );
+        assert_eq!(
+            sorted_ids(&g, NodeSort::Status, Some(&status)),
+            vec!["orders", "customers", "dashboard", "raw", "stg"]
+        );
+    }
+
+    #[test]
+    fn test_sorted_graph_keeps_edges() {
+        let g = make_graph();
+        let sorted = sorted_graph(&g, NodeSort::Name, None);
+        let labels: Vec<&str> = sorted
+            .node_indices()
+            .map(|i| sorted[i].label.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["customers", "dashboard", "orders", "raw", "stg"]
+        );
+        assert_eq!(sorted.edge_count(), g.edge_count());
+        let stg = sorted
+            .node_indices()
+            .find(|&i| sorted[i].label == "stg")
+            .unwrap();
+        assert_eq!(sorted.neighbors(stg).count(), 2);
+    }
+}
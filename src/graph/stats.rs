@@ -0,0 +1,392 @@
+use std::collections::{BTreeMap, HashMap};
+
+use petgraph::stable_graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::Serialize;
+
+use super::paths::reachable;
+use super::suggest::closest_names;
+use super::types::*;
+
+/// Most models listed as source integration points
+const MAX_SOURCE_INTEGRATIONS: usize = 10;
+
+/// Aggregate health counts for a lineage graph
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphStats {
+    pub total_nodes: usize,
+    pub total_edges: usize,
+    /// Node counts keyed by node type label (model, source, ...)
+    pub nodes_by_type: BTreeMap<String, usize>,
+    /// Unresolved ref()/source() targets
+    pub phantom_refs: usize,
+    /// Phantoms created by a `ref()` to a model that doesn't exist (often a typo)
+    pub unresolved_refs: usize,
+    /// Phantoms created by a `source()` that no YAML file declares
+    pub undeclared_sources: usize,
+    /// Every phantom with the files that reference it, by unique_id
+    pub phantoms: Vec<PhantomNode>,
+    /// Models with no downstream test node
+    pub untested_models: usize,
+    /// Longest dependency chain, in edges
+    pub max_depth: usize,
+    /// Most distinct sources any model ultimately derives from
+    pub max_upstream_sources: usize,
+    /// Models deriving from more than one source, most sources first
+    pub source_integrations: Vec<SourceIntegration>,
+}
+
+/// A model and the number of distinct sources it ultimately derives from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceIntegration {
+    pub unique_id: String,
+    pub label: String,
+    pub upstream_sources: usize,
+}
+
+/// What kind of call produced a phantom node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhantomKind {
+    Ref,
+    Source,
+}
+
+/// An unresolved node and where it is referenced from
+#[derive(Debug, Clone, Serialize)]
+pub struct PhantomNode {
+    pub unique_id: String,
+    pub label: String,
+    pub kind: PhantomKind,
+    /// Files of the nodes that reference the phantom, sorted
+    pub referenced_by: Vec<String>,
+    /// Existing models, seeds or snapshots an unresolved ref is likely a typo of
+    pub suggestions: Vec<String>,
+}
+
+/// Phantoms in the graph, sorted by unique_id
+pub fn phantom_nodes(graph: &LineageGraph) -> Vec<PhantomNode> {
+    let ref_targets: Vec<&str> = graph
+        .node_indices()
+        .map(|idx| &graph[idx])
+        .filter(|node| {
+            matches!(
+                node.node_type,
+                NodeType::Model | NodeType::Seed | NodeType::Snapshot
+            )
+        })
+        .map(|node| node.label.as_str())
+        .collect();
+
+    let mut phantoms: Vec<PhantomNode> = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].node_type == NodeType::Phantom)
+        .map(|idx| {
+            let node = &graph[idx];
+            let mut referenced_by: Vec<String> = graph
+                .neighbors_directed(idx, Direction::Outgoing)
+                .filter_map(|child| graph[child].file_path.as_ref())
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            referenced_by.sort();
+            referenced_by.dedup();
+            let kind = if node.unique_id.starts_with("source.") {
+                PhantomKind::Source
+            } else {
+                PhantomKind::Ref
+            };
+            let suggestions = match kind {
+                PhantomKind::Ref => closest_names(&node.label, ref_targets.iter().copied()),
+                PhantomKind::Source => vec![],
+            };
+            PhantomNode {
+                unique_id: node.unique_id.clone(),
+                label: node.label.clone(),
+                kind,
+                referenced_by,
+                suggestions,
+            }
+        })
+        .collect();
+    phantoms.sort_by(|a, b| a.unique_id.cmp(&b.unique_id));
+    phantoms
+}
+
+/// Number of distinct source nodes anywhere upstream of `node`
+pub fn upstream_source_count(graph: &LineageGraph, node: NodeIndex) -> usize {
+    reachable(graph, node, Direction::Incoming)
+        .into_iter()
+        .filter(|&idx| graph[idx].node_type == NodeType::Source)
+        .count()
+}
+
+/// Every model with its upstream source count, most sources first, then by unique_id
+fn model_source_counts(graph: &LineageGraph) -> Vec<SourceIntegration> {
+    let mut counts: Vec<SourceIntegration> = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].node_type == NodeType::Model)
+        .map(|idx| SourceIntegration {
+            unique_id: graph[idx].unique_id.clone(),
+            label: graph[idx].label.clone(),
+            upstream_sources: upstream_source_count(graph, idx),
+        })
+        .collect();
+    counts.sort_by(|a, b| {
+        b.upstream_sources
+            .cmp(&a.upstream_sources)
+            .then_with(|| a.unique_id.cmp(&b.unique_id))
+    });
+    counts
+}
+
+/// Compute summary statistics for a graph
+pub fn compute_stats(graph: &LineageGraph) -> GraphStats {
+    let mut nodes_by_type: BTreeMap<String, usize> = BTreeMap::new();
+    for idx in graph.node_indices() {
+        *nodes_by_type
+            .entry(graph[idx].node_type.label().to_string())
+            .or_insert(0) += 1;
+    }
+
+    let phantom_refs = nodes_by_type
+        .get(NodeType::Phantom.label())
+        .copied()
+        .unwrap_or(0);
+    let phantoms = phantom_nodes(graph);
+    let undeclared_sources = phantoms
+        .iter()
+        .filter(|p| p.kind == PhantomKind::Source)
+        .count();
+
+    let untested_models = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].node_type == NodeType::Model)
+        .filter(|&idx| {
+            !graph
+                .edges_directed(idx, Direction::Outgoing)
+                .any(|e| graph[e.target()].node_type == NodeType::Test)
+        })
+        .count();
+
+    let mut source_integrations = model_source_counts(graph);
+    let max_upstream_sources = source_integrations
+        .first()
+        .map_or(0, |top| top.upstream_sources);
+    source_integrations.retain(|i| i.upstream_sources > 1);
+    source_integrations.truncate(MAX_SOURCE_INTEGRATIONS);
+
+    GraphStats {
+        total_nodes: graph.node_count(),
+        total_edges: graph.edge_count(),
+        nodes_by_type,
+        phantom_refs,
+        unresolved_refs: phantom_refs - undeclared_sources,
+        undeclared_sources,
+        phantoms,
+        untested_models,
+        max_depth: max_depth(graph),
+        max_upstream_sources,
+        source_integrations,
+    }
+}
+
+/// Length (in edges) of the longest path in the DAG; 0 for empty or cyclic graphs
+fn max_depth(graph: &LineageGraph) -> usize {
+    let Ok(topo) = petgraph::algo::toposort(graph, None) else {
+        return 0;
+    };
+    let mut depth: HashMap<_, usize> = HashMap::new();
+    let mut best = 0;
+    for node in topo {
+        let d = graph
+            .edges_directed(node, Direction::Incoming)
+            .filter_map(|e| depth.get(&e.source()).map(|d| d + 1))
+            .max()
+            .unwrap_or(0);
+        best = best.max(d);
+        depth.insert(node, d);
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_node(unique_id: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    fn edge(edge_type: EdgeType) -> EdgeData {
+        EdgeData {
+            edge_type,
+            snippet: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let mut g = LineageGraph::new();
+        let src = g.add_node(make_node("source.raw.orders", NodeType::Source));
+        let stg = g.add_node(make_node("model.stg_orders", NodeType::Model));
+        let mart = g.add_node(make_node("model.orders", NodeType::Model));
+        let ghost = g.add_node(make_node("model.ghost", NodeType::Phantom));
+        let test = g.add_node(make_node("test.orders_positive", NodeType::Test));
+        g.add_edge(src, stg, edge(EdgeType::Source));
+        g.add_edge(stg, mart, edge(EdgeType::Ref));
+        g.add_edge(ghost, mart, edge(EdgeType::Ref));
+        g.add_edge(mart, test, edge(EdgeType::Test));
+
+        let stats = compute_stats(&g);
+        assert_eq!(stats.total_nodes, 5);
+        assert_eq!(stats.total_edges, 4);
+        assert_eq!(stats.nodes_by_type["model"], 2);
+        assert_eq!(stats.nodes_by_type["source"], 1);
+        assert_eq!(stats.phantom_refs, 1);
+        assert_eq!(stats.unresolved_refs, 1);
+        assert_eq!(stats.undeclared_sources, 0);
+        // stg_orders has no test; orders does
+        assert_eq!(stats.untested_models, 1);
+        assert_eq!(stats.max_depth, 3);
+        assert_eq!(stats.max_upstream_sources, 1);
+        assert!(stats.source_integrations.is_empty());
+    }
+
+    #[test]
+    fn test_upstream_sources() {
+        // raw.orders -> stg_orders -> orders <- stg_payments <- raw.payments,
+        // orders -> revenue <- raw.fx (also read directly by stg_payments)
+        let mut g = LineageGraph::new();
+        let orders_src = g.add_node(make_node("source.raw.orders", NodeType::Source));
+        let payments_src = g.add_node(make_node("source.raw.payments", NodeType::Source));
+        let fx = g.add_node(make_node("source.raw.fx", NodeType::Source));
+        let stg_orders = g.add_node(make_node("model.stg_orders", NodeType::Model));
+        let stg_payments = g.add_node(make_node("model.stg_payments", NodeType::Model));
+        let orders = g.add_node(make_node("model.orders", NodeType::Model));
+        let revenue = g.add_node(make_node("model.revenue", NodeType::Model));
+        g.add_edge(orders_src, stg_orders, edge(EdgeType::Source));
+        g.add_edge(payments_src, stg_payments, edge(EdgeType::Source));
+        g.add_edge(fx, stg_payments, edge(EdgeType::Source));
+        g.add_edge(stg_orders, orders, edge(EdgeType::Ref));
+        g.add_edge(stg_payments, orders, edge(EdgeType::Ref));
+        g.add_edge(orders, revenue, edge(EdgeType::Ref));
+        g.add_edge(fx, revenue, edge(EdgeType::Source));
+
+        assert_eq!(upstream_source_count(&g, orders_src), 0);
+        assert_eq!(upstream_source_count(&g, stg_orders), 1);
+        // raw.fx reaches revenue twice but counts once
+        assert_eq!(upstream_source_count(&g, revenue), 3);
+
+        let stats = compute_stats(&g);
+        assert_eq!(stats.max_upstream_sources, 3);
+        let integrations: Vec<(&str, usize)> = stats
+            .source_integrations
+            .iter()
+            .map(|i| (i.label.as_str(), i.upstream_sources))
+            .collect();
+        assert_eq!(
+            integrations,
+            vec![
+                ("model.orders", 3),
+                ("model.revenue", 3),
+                ("model.stg_payments", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_integration_ties_ordered_by_unique_id() {
+        // Same label in two packages, added in reverse unique_id order
+        let mut g = LineageGraph::new();
+        let a = g.add_node(make_node("source.raw.a", NodeType::Source));
+        let b = g.add_node(make_node("source.raw.b", NodeType::Source));
+        for id in ["model.finance.orders", "model.core.orders"] {
+            let mut node = make_node(id, NodeType::Model);
+            node.label = "orders".into();
+            let model = g.add_node(node);
+            g.add_edge(a, model, edge(EdgeType::Source));
+            g.add_edge(b, model, edge(EdgeType::Source));
+        }
+
+        let ids: Vec<String> = compute_stats(&g)
+            .source_integrations
+            .into_iter()
+            .map(|i| i.unique_id)
+            .collect();
+        assert_eq!(ids, vec!["model.core.orders", "model.finance.orders"]);
+    }
+
+    #[test]
+    fn test_phantom_nodes_split_by_kind_with_referrers() {
+        let mut g = LineageGraph::new();
+        let typo = g.add_node(make_node("model.stg_ordres", NodeType::Phantom));
+        let undeclared = g.add_node(make_node("source.crm.accounts", NodeType::Phantom));
+        for name in ["orders", "payments"] {
+            let mut model = make_node(&format!("model.{}", name), NodeType::Model);
+            model.file_path = Some(format!("models/{}.sql", name).into());
+            let model = g.add_node(model);
+            g.add_edge(typo, model, edge(EdgeType::Ref));
+            g.add_edge(undeclared, model, edge(EdgeType::Source));
+        }
+
+        let stats = compute_stats(&g);
+        assert_eq!(stats.phantom_refs, 2);
+        assert_eq!(stats.unresolved_refs, 1);
+        assert_eq!(stats.undeclared_sources, 1);
+
+        let phantoms = &stats.phantoms;
+        assert_eq!(phantoms[0].unique_id, "model.stg_ordres");
+        assert_eq!(phantoms[0].kind, PhantomKind::Ref);
+        assert_eq!(
+            phantoms[0].referenced_by,
+            vec!["models/orders.sql", "models/payments.sql"]
+        );
+        assert_eq!(phantoms[1].kind, PhantomKind::Source);
+    }
+
+    #[test]
+    fn test_phantom_ref_suggestions() {
+        let labelled = |unique_id: &str, label: &str, node_type| NodeData {
+            label: label.into(),
+            ..make_node(unique_id, node_type)
+        };
+        let mut g = LineageGraph::new();
+        let typo = g.add_node(labelled(
+            "model.stg_ordres",
+            "stg_ordres",
+            NodeType::Phantom,
+        ));
+        let undeclared = g.add_node(labelled(
+            "source.raw.orderz",
+            "raw.orderz",
+            NodeType::Phantom,
+        ));
+        let model = g.add_node(labelled("model.stg_orders", "stg_orders", NodeType::Model));
+        g.add_node(labelled(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        g.add_edge(typo, model, edge(EdgeType::Ref));
+        g.add_edge(undeclared, model, edge(EdgeType::Source));
+
+        let phantoms = phantom_nodes(&g);
+        assert_eq!(phantoms[0].unique_id, "model.stg_ordres");
+        assert_eq!(phantoms[0].suggestions, vec!["stg_orders"]);
+        assert!(phantoms[1].suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_compute_stats_empty() {
+        let stats = compute_stats(&LineageGraph::new());
+        assert_eq!(stats.total_nodes, 0);
+        assert_eq!(stats.max_depth, 0);
+        assert!(stats.nodes_by_type.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+/// Most "did you mean" candidates offered for one misspelled name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Edit distance (insertions, deletions, substitutions) between two strings
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Candidates close enough to `name` to be a likely typo of it, closest first.
+///
+/// A candidate qualifies when its case-insensitive edit distance is at most a
+/// third of `name`'s length (and at least 1). Ties are broken alphabetically and
+/// at most three names are returned.
+pub fn closest_names<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+    let mut scored: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|c| (levenshtein(&name, &c.to_lowercase()), c))
+        .filter(|&(d, _)| d <= max_distance)
+        .collect();
+    scored.sort_unstable();
+    scored.dedup();
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c.to_string())
+        .collect()
+}
+
+/// `did you mean: a, b?`, or `None` without suggestions
+pub fn did_you_mean(suggestions: &[String]) -> Option<String> {
+    (!suggestions.is_empty()).then(|| format!("did you mean: {}?", suggestions.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("stg_ordres", "stg_orders"), 2);
+        assert_eq!(levenshtein("stg_orders", "stg_orders"), 0);
+    }
+
+    #[test]
+    fn test_closest_names() {
+        let names = [
+            "stg_orders",
+            "stg_customers",
+            "orders",
+            "stg_order",
+            "fct_revenue",
+        ];
+        assert_eq!(
+            closest_names("stg_ordres", names),
+            vec!["stg_order", "stg_orders"]
+        );
+        assert_eq!(closest_names("STG_ORDERS", names)[0], "stg_orders");
+        assert!(closest_names("dim_products", names).is_empty());
+        // Short names still allow a single edit
+        assert_eq!(closest_names("ab", ["ac", "xy"]), vec!["ac"]);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(did_you_mean(&[]), None);
+        assert_eq!(
+            did_you_mean(&["a".to_string(), "b".to_string()]).as_deref(),
+            Some("did you mean: a, b?")
+        );
+    }
+}
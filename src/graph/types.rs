@@ -1,12 +1,14 @@
-use petgraph::stable_graph::StableDiGraph;
-use std::path::PathBuf;
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
 
 /// The lineage DAG type
 pub type LineageGraph = StableDiGraph<NodeData, EdgeData>;
 
 /// Types of nodes in the dbt lineage
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum NodeType {
+    #[default]
     Model,
     Source,
     Seed,
@@ -43,8 +45,27 @@ impl NodeType {
     }
 }
 
-/// Data associated with each node
-#[derive(Debug, Clone)]
+/// How a test node is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TestKind {
+    /// Hand-written SQL file under a test path
+    Singular,
+    /// Generated from a test declared in schema YAML (e.g. `not_null`)
+    Generic,
+}
+
+impl TestKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TestKind::Singular => "singular",
+            TestKind::Generic => "generic",
+        }
+    }
+}
+
+/// Data associated with each node. Only `unique_id`, `label` and `node_type`
+/// are always set; fill in the rest with `..Default::default()`.
+#[derive(Debug, Clone, Default)]
 pub struct NodeData {
     /// Unique identifier (e.g., "model.stg_orders" or "source.raw.orders")
     pub unique_id: String,
@@ -52,7 +73,10 @@ pub struct NodeData {
     pub label: String,
     /// Node type
     pub node_type: NodeType,
-    /// Path to the source file (if applicable)
+    /// Path to the source file (if applicable), relative to the project root
+    /// (e.g. `models/staging/stg_orders.sql`). Every builder normalizes through
+    /// [`project_relative_path`], so this is the same whether the graph came from
+    /// SQL files, a manifest, or a git ref.
     pub file_path: Option<PathBuf>,
     /// Description from YAML schema
     pub description: Option<String>,
@@ -62,6 +86,63 @@ pub struct NodeData {
     pub tags: Vec<String>,
     /// Column names exposed by this model (from SELECT clause)
     pub columns: Vec<String>,
+    /// Model version for dbt versioned models (e.g. `"2"`); such nodes get a
+    /// `.v<version>` unique_id suffix and share their base `label`
+    pub version: Option<String>,
+    /// Warehouse data type per column name, populated from `catalog.json`
+    pub column_types: BTreeMap<String, String>,
+    /// Documented description per column name, from YAML schema, the manifest
+    /// or catalog comments
+    pub column_descriptions: BTreeMap<String, String>,
+    /// Singular or generic, for test nodes
+    pub test_kind: Option<TestKind>,
+    /// dbt project (package) defining the node; only set when several
+    /// manifests are merged
+    pub project: Option<String>,
+    /// `meta` from the SQL `config()`, YAML schema or manifest (e.g. `sla_hours`)
+    pub meta: BTreeMap<String, serde_json::Value>,
+    /// Schema the node is built in or read from: a model's custom `schema`
+    /// config (e.g. `marts`), or a source's schema
+    pub schema: Option<String>,
+    /// Change detection settings, for snapshots whose config sets any
+    pub snapshot: Option<SnapshotConfig>,
+}
+
+/// How a snapshot detects changed rows, from its `config()` or the manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotConfig {
+    /// `timestamp` or `check`
+    pub strategy: Option<String>,
+    /// Key columns; dbt accepts one or a list
+    pub unique_key: Vec<String>,
+    /// Column compared by the `timestamp` strategy
+    pub updated_at: Option<String>,
+    /// Columns compared by the `check` strategy, or `["all"]`
+    pub check_cols: Vec<String>,
+}
+
+impl SnapshotConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `Some` unless nothing is set
+    pub fn non_empty(self) -> Option<Self> {
+        (!self.is_empty()).then_some(self)
+    }
+}
+
+/// A config value that may be one string or a list of them (`unique_key`,
+/// `check_cols`) as a list; anything else is empty
+pub fn string_list(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => vec![s.clone()],
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
 }
 
 impl NodeData {
@@ -69,15 +150,51 @@ impl NodeData {
     pub fn display_name(&self) -> String {
         let prefix = self.node_type.prefix();
         if prefix.is_empty() {
-            self.label.clone()
+            self.versioned_label()
         } else {
-            format!("{}{}", prefix, self.label)
+            format!("{}{}", prefix, self.versioned_label())
+        }
+    }
+
+    /// Label with the model version appended, e.g. `dim_customers (v2)`
+    pub fn versioned_label(&self) -> String {
+        match &self.version {
+            Some(v) => format!("{} (v{})", self.label, v),
+            None => self.label.clone(),
         }
     }
+
+    /// Whether both nodes have a known project and the projects differ
+    pub fn in_other_project(&self, other: &NodeData) -> bool {
+        matches!((&self.project, &other.project), (Some(a), Some(b)) if a != b)
+    }
+}
+
+/// Normalize a resource path to the project-relative form stored in [`NodeData::file_path`].
+///
+/// Absolute paths under `project_dir` have that prefix stripped (falling back to the
+/// canonicalized forms to see through symlinks); `.` components are dropped so
+/// `./models/a.sql` and `models/a.sql` compare equal. Paths outside the project are
+/// kept as they are.
+pub fn project_relative_path(path: &Path, project_dir: &Path) -> PathBuf {
+    let rel = match path.strip_prefix(project_dir) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) if path.is_absolute() => match (path.canonicalize(), project_dir.canonicalize()) {
+            (Ok(p), Ok(dir)) => p
+                .strip_prefix(&dir)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| path.to_path_buf()),
+            _ => path.to_path_buf(),
+        },
+        Err(_) => path.to_path_buf(),
+    };
+    rel.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
 }
 
 /// Edge types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(dead_code)]
 pub enum EdgeType {
     /// ref() dependency
@@ -90,16 +207,92 @@ pub enum EdgeType {
     Exposure,
 }
 
+/// The SQL line a `ref()`/`source()` edge was parsed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeSnippet {
+    /// 1-based line number in the dependent node's file
+    pub line: usize,
+    /// The line itself, trimmed
+    pub text: String,
+}
+
 /// Data associated with each edge
 #[derive(Debug, Clone)]
 pub struct EdgeData {
     pub edge_type: EdgeType,
+    /// Where the edge came from in SQL; None for manifest-built graphs and for
+    /// edges declared in YAML or inferred from compiled SQL
+    pub snippet: Option<EdgeSnippet>,
+}
+
+/// Add an edge unless one of the same type already connects the two nodes, so a
+/// model that refs the same upstream twice gets a single edge (keeping the first
+/// occurrence's snippet). Returns whether an edge was added.
+pub fn add_unique_edge(
+    graph: &mut LineageGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    data: EdgeData,
+) -> bool {
+    if graph
+        .edges_connecting(source, target)
+        .any(|e| e.weight().edge_type == data.edge_type)
+    {
+        return false;
+    }
+    graph.add_edge(source, target, data);
+    true
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_add_unique_edge_skips_same_type_only() {
+        let mut graph = LineageGraph::new();
+        let node = |id: &str| NodeData {
+            unique_id: id.into(),
+            label: id.into(),
+            node_type: NodeType::Model,
+            ..Default::default()
+        };
+        let edge = |edge_type| EdgeData {
+            edge_type,
+            snippet: None,
+        };
+        let a = graph.add_node(node("model.a"));
+        let b = graph.add_node(node("model.b"));
+
+        assert!(add_unique_edge(&mut graph, a, b, edge(EdgeType::Ref)));
+        assert!(!add_unique_edge(&mut graph, a, b, edge(EdgeType::Ref)));
+        assert!(add_unique_edge(&mut graph, a, b, edge(EdgeType::Test)));
+        assert!(add_unique_edge(&mut graph, b, a, edge(EdgeType::Ref)));
+        assert_eq!(graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn test_project_relative_path() {
+        let dir = Path::new("/proj");
+        assert_eq!(
+            project_relative_path(Path::new("/proj/models/a.sql"), dir),
+            PathBuf::from("models/a.sql")
+        );
+        assert_eq!(
+            project_relative_path(Path::new("./models/a.sql"), dir),
+            PathBuf::from("models/a.sql")
+        );
+        assert_eq!(
+            project_relative_path(Path::new("models/a.sql"), Path::new("")),
+            PathBuf::from("models/a.sql")
+        );
+        // Outside the project: left alone
+        assert_eq!(
+            project_relative_path(Path::new("/elsewhere/a.sql"), dir),
+            PathBuf::from("/elsewhere/a.sql")
+        );
+    }
+
     #[test]
     fn test_prefix_all_variants() {
         assert_eq!(NodeType::Model.prefix(), "");
@@ -128,26 +321,31 @@ mod tests {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         assert_eq!(node.display_name(), "orders");
     }
 
+    #[test]
+    fn test_display_name_versioned_model() {
+        let node = NodeData {
+            unique_id: "model.dim_customers.v2".into(),
+            label: "dim_customers".into(),
+            node_type: NodeType::Model,
+            version: Some("2".into()),
+            ..Default::default()
+        };
+        assert_eq!(node.versioned_label(), "dim_customers (v2)");
+        assert_eq!(node.display_name(), "dim_customers (v2)");
+    }
+
     #[test]
     fn test_display_name_source() {
         let node = NodeData {
             unique_id: "source.raw.orders".into(),
             label: "raw.orders".into(),
             node_type: NodeType::Source,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         assert_eq!(node.display_name(), "src:raw.orders");
     }
@@ -166,11 +364,7 @@ mod tests {
                 unique_id: "id".into(),
                 label: "x".into(),
                 node_type: nt,
-                file_path: None,
-                description: None,
-                materialization: None,
-                tags: vec![],
-                columns: vec![],
+                ..Default::default()
             };
             assert_eq!(node.display_name(), expected, "Failed for {:?}", nt);
         }
@@ -1,8 +1,11 @@
 pub mod cli;
+pub mod config;
 pub mod error;
+pub mod exit;
 pub mod git;
 pub mod graph;
 pub mod parser;
+pub mod profile;
 pub mod render;
 #[cfg(feature = "tui")]
 pub mod tui;
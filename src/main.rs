@@ -1,16 +1,40 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::CommandFactory;
+use petgraph::stable_graph::NodeIndex;
 
 use dbt_lineage::cli::{self, Cli, Command};
+use dbt_lineage::error::DbtLineageError;
+use dbt_lineage::exit::ExitStatus;
 use dbt_lineage::graph;
 use dbt_lineage::parser;
+use dbt_lineage::profile::Profiler;
 use dbt_lineage::render;
 
 #[cfg(not(tarpaulin_include))]
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn main() -> ExitCode {
+    match run() {
+        Ok(status) => status.into(),
+        Err(err) => {
+            eprintln!("Error: {:?}", err);
+            ExitStatus::for_error(&err).into()
+        }
+    }
+}
+
+/// Run the requested command. Failures are mapped to an exit code by `main`.
+#[cfg(not(tarpaulin_include))]
+fn run() -> Result<ExitStatus> {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_matches_with_config(&matches)
+        .map_err(|err| DbtLineageError::InvalidArgument(format!("{:#}", err)))?;
+    // Read `--select @file` / `--select -` once, so a TUI reload reuses the list
+    if let Some(select) = &cli.select {
+        cli.select = Some(graph::filter::expand_selector_arg(select)?);
+    }
 
     // Handle subcommands first
     if let Some(command) = &cli.command {
@@ -19,172 +43,1311 @@ fn main() -> Result<()> {
                 model,
                 project_dir,
                 output,
+                link_prefix,
+                upstream,
                 manifest,
-            } => run_impact_command(model, project_dir, output, manifest.as_ref()),
+                fail_on,
+            } => {
+                let weights = cli
+                    .load_config(project_dir)
+                    .map_err(|err| DbtLineageError::InvalidArgument(format!("{:#}", err)))?
+                    .and_then(|config| config.rebuild_weights)
+                    .map(graph::impact::RebuildWeights::with_overrides)
+                    .unwrap_or_default();
+                check_link_prefix(
+                    link_prefix.is_some(),
+                    matches!(output, cli::ImpactOutputFormat::Markdown),
+                )?;
+                run_impact_command(
+                    model,
+                    project_dir,
+                    output,
+                    link_prefix.as_deref(),
+                    *upstream,
+                    manifest,
+                    *fail_on,
+                    &weights,
+                )
+            }
             Command::Diff {
                 base,
                 head,
                 project_dir,
                 output,
-            } => run_diff_command(base, head.as_deref(), project_dir, output),
+                link_prefix,
+                fast,
+                diff_filter,
+                include_unchanged,
+                group_by,
+                with_impact,
+                columns,
+                exit_code,
+            } => {
+                check_link_prefix(
+                    link_prefix.is_some(),
+                    matches!(output, cli::DiffOutputFormat::Markdown),
+                )?;
+                let mut statuses = graph::diff::parse_diff_filter(diff_filter)?;
+                if *include_unchanged {
+                    statuses.insert(graph::diff::DiffStatus::Unchanged);
+                }
+                let diff = run_diff_command(
+                    base,
+                    head.as_deref(),
+                    project_dir,
+                    output,
+                    link_prefix.as_deref(),
+                    *fast,
+                    &statuses,
+                    *group_by,
+                    *with_impact,
+                    *columns,
+                )?;
+                Ok(ExitStatus::findings_if(*exit_code && diff.has_changes()))
+            }
+            Command::Path {
+                from,
+                to,
+                project_dir,
+                output,
+                all,
+                max_length,
+                output_file,
+                manifest,
+            } => run_path_command(
+                from,
+                to,
+                project_dir,
+                output,
+                *all,
+                *max_length,
+                output_file.as_deref(),
+                manifest,
+            ),
+            Command::Stats {
+                project_dir,
+                output,
+                manifest,
+            } => {
+                run_stats_command(project_dir, output, manifest)?;
+                Ok(ExitStatus::Ok)
+            }
+            Command::List {
+                project_dir,
+                node_types,
+                select,
+                labels,
+                sort,
+                run_results,
+                manifest,
+            } => {
+                // Like the main command, fall back to the config's `run-results`
+                let run_results = match run_results {
+                    Some(path) => Some(path.clone()),
+                    None if *sort == Some(graph::sort::NodeSort::Status) => cli
+                        .load_config(project_dir)
+                        .map_err(|err| DbtLineageError::InvalidArgument(format!("{:#}", err)))?
+                        .and_then(|config| config.run_results),
+                    None => None,
+                };
+                run_list_command(
+                    project_dir,
+                    node_types.as_deref(),
+                    select.as_deref(),
+                    *labels,
+                    *sort,
+                    run_results.as_deref(),
+                    manifest,
+                )?;
+                Ok(ExitStatus::Ok)
+            }
+            Command::Docs {
+                output_dir,
+                project_dir,
+                select,
+                manifest,
+            } => {
+                run_docs_command(project_dir, output_dir, select.as_deref(), manifest)?;
+                Ok(ExitStatus::Ok)
+            }
+            Command::Check {
+                project_dir,
+                output,
+                baseline: Some(baseline),
+                write_baseline,
+                ..
+            } => run_baseline_check_command(baseline, *write_baseline, project_dir, output),
+            Command::Check {
+                project_dir,
+                output,
+                sla: true,
+                run_results,
+                ..
+            } => run_sla_check_command(project_dir, run_results.as_deref(), output),
+            Command::Check {
+                project_dir,
+                output,
+                require_descriptions: true,
+                select,
+                ..
+            } => run_description_check_command(select.as_deref(), project_dir, output),
+            Command::Check {
+                project_dir,
+                output,
+                layer_order: Some(layer_order),
+                ..
+            } => run_layer_check_command(layer_order, project_dir, output),
+            Command::Check {
+                blast_radius,
+                base_ref,
+                head,
+                project_dir,
+                output,
+                layer_order: None,
+                require_descriptions: false,
+                select: _,
+                sla: false,
+                run_results: _,
+                baseline: None,
+                write_baseline: _,
+            } => run_check_command(
+                *blast_radius,
+                base_ref,
+                head.as_deref(),
+                project_dir,
+                output,
+            ),
         };
     }
 
-    let project_dir = cli.project_dir.canonicalize().unwrap_or(cli.project_dir);
+    let project_dir = cli
+        .project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| cli.project_dir.clone());
+    if matches!(cli.output, cli::OutputFormat::Tree) && cli.model.is_none() {
+        return Err(DbtLineageError::InvalidArgument(
+            "-o tree needs a model to start from, e.g. `dbt-lineage orders -o tree`".into(),
+        )
+        .into());
+    }
+    if cli.json_shape == render::json::JsonShape::Tree {
+        if !matches!(cli.output, cli::OutputFormat::Json) {
+            return Err(DbtLineageError::InvalidArgument(
+                "--json-shape tree only applies to -o json".into(),
+            )
+            .into());
+        }
+        if cli.model.is_none() {
+            return Err(DbtLineageError::InvalidArgument(
+                "--json-shape tree needs a model to start from, e.g. `dbt-lineage orders -o json --json-shape tree`".into(),
+            )
+            .into());
+        }
+    }
+    if cli.max_label_len == Some(0) {
+        return Err(
+            DbtLineageError::InvalidArgument("--max-label-len must be at least 1".into()).into(),
+        );
+    }
+    if cli.max_layer_width == Some(0) {
+        return Err(DbtLineageError::InvalidArgument(
+            "--max-layer-width must be at least 1".into(),
+        )
+        .into());
+    }
+    check_csv_output_file(&cli.output, cli.output_file.as_deref())?;
+    let mut profiler = Profiler::new(cli.profile);
+    // Load the palette before the slow parsing, so a bad color fails fast
+    let palette = cli
+        .palette
+        .as_deref()
+        .map(render::palette::Palette::load)
+        .transpose()
+        .map_err(|err| DbtLineageError::InvalidArgument(format!("{:#}", err)))?;
 
-    let dag = build_dag(&project_dir, cli.manifest.as_ref())?;
+    let dag = build_project_graph(&cli, &project_dir, &mut profiler)?;
 
-    // Parse selectors
-    let selectors = cli
-        .select
-        .as_deref()
-        .map(graph::filter::parse_selectors)
-        .unwrap_or_default();
+    if let Some(path) = &cli.dump_manifest {
+        let project_name = match &cli.project_name {
+            Some(name) => name.clone(),
+            None => parser::project::DbtProject::load(&project_dir)?.name,
+        };
+        if path.as_os_str() == "-" {
+            render::manifest::render_manifest(&dag, &project_name);
+        } else {
+            render::manifest::write_manifest(&dag, &project_name, path)?;
+            eprintln!("Wrote {}", path.display());
+        }
+        profiler.report();
+        return Ok(ExitStatus::Ok);
+    }
 
-    // Filter graph
-    let filtered = graph::filter::filter_graph(
-        &dag,
-        cli.model.as_deref(),
-        cli.upstream,
-        cli.downstream,
-        &graph::filter::NodeTypeFilter {
-            include_tests: cli.include_tests,
-            include_seeds: cli.include_seeds,
-            include_snapshots: cli.include_snapshots,
-            include_exposures: cli.include_exposures,
-        },
-        &selectors,
-    )?;
+    let groups = graph::groups::GroupConfig::load(&project_dir)?;
+    let filtered = filter_project_graph(&cli, &project_dir, dag, groups.as_ref(), &mut profiler)?;
+
+    // Column lineage and layout are computed lazily inside the TUI and the
+    // renderers, so measure them standalone to make their cost visible.
+    if profiler.is_enabled() {
+        profiler.time("column lineage", || {
+            parser::column_lineage::resolve_column_lineage(&filtered)
+        });
+        profiler.time("layout", || {
+            render::layout::sugiyama_layout_wrapped(&filtered, cli.max_layer_width)
+        });
+    }
 
     // Render
     #[cfg(feature = "tui")]
     if cli.interactive {
-        dbt_lineage::tui::run_tui(filtered, project_dir.clone())?;
-        return Ok(());
+        let run_timeout = cli.run_timeout.map(std::time::Duration::from_secs);
+        let graph_source = if cli.manifest.is_empty() {
+            dbt_lineage::tui::app::GraphSource::Sql
+        } else {
+            dbt_lineage::tui::app::GraphSource::Manifest
+        };
+        let sort = cli.sort;
+        let label_detail = cli.label_detail;
+        let max_label_len = cli.max_label_len;
+        let max_layer_width = cli.max_layer_width;
+        let run_results = cli.run_results.clone();
+        // `R` in the TUI runs the same pipeline again; `M` runs it on the other
+        // source, reading target/manifest.json when no --manifest was given
+        let reload_dir = project_dir.clone();
+        let reload: dbt_lineage::tui::app::GraphLoader = Box::new(move |source| {
+            let mut profiler = Profiler::disabled();
+            let mut cli = cli.clone();
+            match source {
+                dbt_lineage::tui::app::GraphSource::Sql => cli.manifest.clear(),
+                dbt_lineage::tui::app::GraphSource::Manifest if cli.manifest.is_empty() => {
+                    cli.manifest = vec![reload_dir.clone()];
+                    cli.use_compiled = false;
+                }
+                dbt_lineage::tui::app::GraphSource::Manifest => {}
+            }
+            let dag = build_project_graph(&cli, &reload_dir, &mut profiler)?;
+            let groups = graph::groups::GroupConfig::load(&reload_dir)?;
+            filter_project_graph(&cli, &reload_dir, dag, groups.as_ref(), &mut profiler)
+        });
+        dbt_lineage::tui::run_tui(
+            filtered,
+            project_dir.clone(),
+            groups.as_ref(),
+            run_timeout,
+            graph_source,
+            sort,
+            label_detail,
+            max_label_len,
+            max_layer_width,
+            Some(reload),
+            run_results,
+        )?;
+        profiler.report();
+        return Ok(ExitStatus::Ok);
     }
 
     #[cfg(not(feature = "tui"))]
     if cli.interactive {
-        anyhow::bail!("TUI feature not enabled. Rebuild with --features tui");
+        return Err(DbtLineageError::InvalidArgument(
+            "TUI feature not enabled. Rebuild with --features tui".into(),
+        )
+        .into());
     }
 
-    render_output(&cli.output, &filtered);
+    let run_status = if cli.with_status || cli.sort == Some(graph::sort::NodeSort::Status) {
+        Some(load_run_status(
+            &project_dir,
+            cli.run_results.as_deref(),
+            &filtered,
+        )?)
+    } else {
+        None
+    };
+    let filtered = match cli.sort {
+        Some(sort) => graph::sort::sorted_graph(&filtered, sort, run_status.as_ref()),
+        None => filtered,
+    };
+    // `--sort status` needs run status, but only `--with-status` draws it
+    let run_status = run_status.filter(|_| cli.with_status);
+    // `-u` alone draws only the ancestors and `-d` alone only the descendants
+    let tree_directions: &[render::ascii::TreeDirection] = match (cli.upstream, cli.downstream) {
+        (Some(_), None) => &[render::ascii::TreeDirection::Upstream],
+        (None, Some(_)) => &[render::ascii::TreeDirection::Downstream],
+        _ => &[
+            render::ascii::TreeDirection::Upstream,
+            render::ascii::TreeDirection::Downstream,
+        ],
+    };
+    let tree_root = cli
+        .model
+        .as_deref()
+        .and_then(|model| graph::filter::find_focus_node(&filtered, model))
+        .map(|root| (root, tree_directions));
 
-    Ok(())
+    // Names are replaced last, once nothing needs to look nodes up by them.
+    // Node indices stay the same, so the tree root still applies.
+    let (filtered, run_status, groups) = if cli.anonymize {
+        let anonymized = graph::anonymize::anonymize(&filtered);
+        if let Some(path) = &cli.anonymize_map {
+            anonymized.write_map(path)?;
+            eprintln!("Wrote {}", path.display());
+        }
+        let run_status = run_status.map(|statuses| {
+            statuses
+                .into_iter()
+                .filter_map(|(id, status)| Some((anonymized.ids.get(&id)?.clone(), status)))
+                .collect()
+        });
+        // Group names are the project's own words too
+        (anonymized.graph, run_status, None)
+    } else {
+        (filtered, run_status, groups)
+    };
+    // Only JSON carries inherited column descriptions, so skip the SQL pass
+    // otherwise (and when anonymizing, since they name columns)
+    let inherited_descriptions = if matches!(cli.output, cli::OutputFormat::Json) && !cli.anonymize
+    {
+        parser::column_lineage::resolve_column_lineage_in(&filtered, &project_dir)
+            .inherited_descriptions(&filtered)
+    } else {
+        Vec::new()
+    };
+    let options = render::RenderOptions {
+        groups: groups.as_ref(),
+        run_status: run_status.as_ref(),
+        inherited_descriptions: Some(&inherited_descriptions),
+        label_detail: cli.label_detail,
+        palette: palette.as_ref(),
+        edge_style: cli.edge_style,
+        max_label_len: cli.max_label_len,
+        max_layer_width: cli.max_layer_width,
+        diff: None,
+        json_shape: cli.json_shape,
+    };
+    profiler.time("render", || {
+        render_output(
+            &cli.output,
+            cli.output_file.as_deref(),
+            &filtered,
+            &options,
+            tree_root,
+        )
+    })?;
+    profiler.report();
+
+    Ok(ExitStatus::Ok)
 }
 
-/// Build the lineage DAG from either a manifest file or by parsing SQL files
+/// Build the full graph for the main command: parse SQL (or read `--manifest`),
+/// optionally with compiled SQL, then apply `--catalog`
 #[cfg(not(tarpaulin_include))]
-fn build_dag(project_dir: &Path, manifest: Option<&PathBuf>) -> Result<graph::types::LineageGraph> {
-    if let Some(manifest_arg) = manifest {
-        let manifest_path = resolve_manifest_path(manifest_arg)?;
-        parser::manifest::build_graph_from_manifest(&manifest_path)
+fn build_project_graph(
+    cli: &Cli,
+    project_dir: &Path,
+    profiler: &mut Profiler,
+) -> Result<graph::types::LineageGraph> {
+    let compiled_dir = if cli.use_compiled && cli.manifest.is_empty() {
+        let dir = parser::project::DbtProject::load(project_dir)?.compiled_dir(project_dir);
+        if !dir.is_dir() {
+            eprintln!(
+                "Warning: {} not found; run `dbt compile` first. Parsing raw SQL only.",
+                dir.display()
+            );
+        }
+        Some(dir)
     } else {
-        let project = parser::project::DbtProject::load(project_dir)?;
+        None
+    };
+    let build_options = graph::builder::BuildOptions {
+        identifier_case: cli.identifier_case,
+        compiled_dir,
+    };
+    let globs = parser::discovery::DiscoveryGlobs {
+        models: cli.model_glob.clone(),
+        snapshots: cli.snapshot_glob.clone(),
+        tests: cli.test_glob.clone(),
+        seed_file_types: cli.seed_file_types()?,
+    };
+    let mut dag = build_dag(project_dir, &cli.manifest, &build_options, &globs, profiler)?;
+
+    if let Some(catalog_path) = resolve_catalog_path(cli.catalog.as_ref(), cli.manifest.first())? {
+        let catalog = profiler.time("catalog load", || {
+            parser::catalog::load_catalog(&catalog_path)
+        })?;
+        parser::catalog::apply_catalog(&mut dag, &catalog);
+    }
+    Ok(dag)
+}
+
+/// Narrow the full graph down to what the main command shows: `--changed-since`,
+/// the focus model, selectors and type filters, `--trim-orphans` and
+/// `--collapse-group`
+#[cfg(not(tarpaulin_include))]
+fn filter_project_graph(
+    cli: &Cli,
+    project_dir: &Path,
+    mut dag: graph::types::LineageGraph,
+    groups: Option<&graph::groups::GroupConfig>,
+    profiler: &mut Profiler,
+) -> Result<graph::types::LineageGraph> {
+    if let Some(since) = &cli.changed_since {
+        dag = restrict_to_changed_files(project_dir, &dag, since, cli.changed_neighbors)?;
+    }
+
+    // Parse selectors, or look the named one up in selectors.yml
+    let selectors = match &cli.selector {
+        Some(name) => parser::selectors::load_named_selector(project_dir, name)?,
+        None => cli
+            .select
+            .as_deref()
+            .map(graph::filter::parse_selectors)
+            .unwrap_or_default(),
+    };
+
+    let node_types = cli.node_types()?;
+    let edge_types = graph::filter::resolve_edge_types(
+        cli.edge_types.as_deref(),
+        cli.hide_edge_types.as_deref(),
+    )?;
+
+    // Filter graph
+    let filtered = profiler.time("filter", || {
+        graph::filter::filter_graph(
+            &dag,
+            cli.model.as_deref(),
+            cli.upstream,
+            cli.downstream,
+            &graph::filter::NodeTypeFilter {
+                node_types,
+                edge_types,
+                materializations: cli
+                    .materialized
+                    .as_deref()
+                    .map(graph::filter::parse_materializations),
+                tests: cli.tests,
+            },
+            &selectors,
+        )
+    })?;
+
+    let filtered = if cli.trim_orphans {
+        graph::filter::trim_orphans(&filtered, cli.model.as_deref(), &selectors)
+    } else {
+        filtered
+    };
+
+    Ok(if cli.collapse_group.is_empty() {
+        filtered
+    } else {
+        graph::collapse::collapse_groups(&filtered, &cli.collapse_group, groups)
+    })
+}
+
+/// Build the lineage DAG from manifest files (merged when there are several) or
+/// by parsing SQL files.
+/// Fails with an actionable message when the result has no nodes.
+#[cfg(not(tarpaulin_include))]
+fn build_dag(
+    project_dir: &Path,
+    manifests: &[PathBuf],
+    options: &graph::builder::BuildOptions,
+    globs: &parser::discovery::DiscoveryGlobs,
+    profiler: &mut Profiler,
+) -> Result<graph::types::LineageGraph> {
+    if !manifests.is_empty() {
+        let manifest_paths = manifests
+            .iter()
+            .map(|arg| resolve_manifest_path(arg))
+            .collect::<Result<Vec<_>>>()?;
+        let dag = profiler.time("manifest load", || {
+            parser::manifest::build_graph_from_manifests(&manifest_paths)
+        })?;
+        if dag.node_count() == 0 {
+            let first = manifest_paths.into_iter().next().unwrap_or_default();
+            return Err(dbt_lineage::error::DbtLineageError::EmptyManifest(first).into());
+        }
+        Ok(dag)
+    } else {
+        let project = profiler.time("project load", || {
+            parser::project::DbtProject::load(project_dir)
+        })?;
         let paths = project.resolve_paths(project_dir);
-        let files = parser::discovery::discover_files(&paths)?;
-        graph::builder::build_graph(project_dir, &files)
+        let files = profiler.time("file discovery", || {
+            parser::discovery::discover_files_with_globs(&paths, project_dir, globs)
+        })?;
+        let dag = graph::builder::build_graph_profiled(project_dir, &files, options, profiler)?;
+        if dag.node_count() == 0 {
+            let paths = paths
+                .model_paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(dbt_lineage::error::DbtLineageError::NoModelsFound { paths }.into());
+        }
+        Ok(dag)
     }
 }
 
-/// Dispatch rendering based on output format
+/// Dispatch rendering based on output format. Formats that support them draw the
+/// configured group clusters and run status outlines. `-o tree` and
+/// `--json-shape tree` start from `tree_root` and walk the given directions.
 #[cfg(not(tarpaulin_include))]
-fn render_output(format: &cli::OutputFormat, graph: &graph::types::LineageGraph) {
+fn render_output(
+    format: &cli::OutputFormat,
+    output_file: Option<&Path>,
+    graph: &graph::types::LineageGraph,
+    options: &render::RenderOptions,
+    tree_root: Option<(NodeIndex, &[render::ascii::TreeDirection])>,
+) -> Result<()> {
     match format {
-        cli::OutputFormat::Ascii => render::ascii::render_ascii(graph),
-        cli::OutputFormat::Dot => render::dot::render_dot(graph),
-        cli::OutputFormat::Json => render::json::render_json(graph),
-        cli::OutputFormat::Mermaid => render::mermaid::render_mermaid(graph),
-        cli::OutputFormat::Svg => render::svg::render_svg(graph),
-        cli::OutputFormat::Html => render::html::render_html(graph),
+        cli::OutputFormat::Ascii => render::ascii::render_ascii_with_options(graph, options),
+        cli::OutputFormat::Dot => render::dot::render_dot_with_options(graph, options),
+        cli::OutputFormat::Json => match options.json_shape {
+            render::json::JsonShape::Flat => render::json::render_json_with_options(graph, options),
+            render::json::JsonShape::Tree => {
+                let (root, directions) = tree_root.ok_or_else(|| {
+                    DbtLineageError::InvalidArgument(
+                        "--json-shape tree needs a model that is still in the filtered graph"
+                            .into(),
+                    )
+                })?;
+                render::json::render_json_tree_with_options(graph, root, directions, options);
+            }
+        },
+        cli::OutputFormat::Mermaid => render::mermaid::render_mermaid_with_options(graph, options),
+        cli::OutputFormat::Svg => render::svg::render_svg_with_options(graph, options),
+        cli::OutputFormat::Html => render::html::render_html_with_options(graph, options),
+        cli::OutputFormat::Cytoscape => render::cytoscape::render_cytoscape(graph),
+        cli::OutputFormat::Csv => {
+            let prefix = output_file.ok_or_else(csv_needs_output_file)?;
+            let (nodes, edges) = render::csv::render_csv(graph, prefix)?;
+            eprintln!("Wrote {} and {}", nodes.display(), edges.display());
+        }
+        cli::OutputFormat::Tree => {
+            let (root, directions) = tree_root.ok_or_else(|| {
+                DbtLineageError::InvalidArgument(
+                    "-o tree needs a model that is still in the filtered graph".into(),
+                )
+            })?;
+            render::ascii::render_tree_with_options(graph, root, directions, options);
+        }
     }
+    Ok(())
+}
+
+/// Load the run status of every node in `graph` from `run_results` (default:
+/// `target/run_results.json`). A missing file is not an error: every node is
+/// then shown as never run.
+#[cfg(not(tarpaulin_include))]
+fn load_run_status(
+    project_dir: &Path,
+    run_results: Option<&Path>,
+    graph: &graph::types::LineageGraph,
+) -> Result<parser::artifacts::RunStatusMap> {
+    let path = run_results
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| parser::artifacts::default_run_results_path(project_dir));
+    match parser::artifacts::load_run_results_from(&path)? {
+        Some(results) => Ok(parser::artifacts::build_run_status_map(
+            &results,
+            graph,
+            project_dir,
+        )),
+        None => {
+            eprintln!(
+                "Warning: {} not found; all nodes are shown as never run",
+                path.display()
+            );
+            Ok(Default::default())
+        }
+    }
+}
+
+/// `-o csv` writes two files, so it needs a prefix to name them
+fn csv_needs_output_file() -> anyhow::Error {
+    DbtLineageError::InvalidArgument("-o csv needs --output-file <PREFIX>".into()).into()
+}
+
+/// Fail before parsing the project when `-o csv` has no `--output-file`
+fn check_csv_output_file(output: &cli::OutputFormat, output_file: Option<&Path>) -> Result<()> {
+    if matches!(output, cli::OutputFormat::Csv) && output_file.is_none() {
+        return Err(csv_needs_output_file());
+    }
+    Ok(())
+}
+
+/// `--link-prefix` only shapes Markdown links, so reject it elsewhere rather
+/// than ignore it
+fn check_link_prefix(given: bool, markdown: bool) -> Result<()> {
+    if given && !markdown {
+        return Err(DbtLineageError::InvalidArgument(
+            "--link-prefix only applies to -o markdown".into(),
+        )
+        .into());
+    }
+    Ok(())
 }
 
 /// Run the `impact` subcommand
 #[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
 fn run_impact_command(
     model: &str,
     project_dir: &Path,
     output: &cli::ImpactOutputFormat,
-    manifest: Option<&PathBuf>,
-) -> Result<()> {
+    link_prefix: Option<&str>,
+    upstream: bool,
+    manifest: &[PathBuf],
+    fail_on: Option<graph::impact::ImpactSeverity>,
+    weights: &graph::impact::RebuildWeights,
+) -> Result<ExitStatus> {
     let project_dir = project_dir
         .canonicalize()
         .unwrap_or_else(|_| project_dir.to_path_buf());
 
-    let dag = if let Some(manifest_arg) = manifest {
-        let manifest_path = resolve_manifest_path(manifest_arg)?;
-        parser::manifest::build_graph_from_manifest(&manifest_path)?
-    } else {
-        let project = parser::project::DbtProject::load(&project_dir)?;
-        let paths = project.resolve_paths(&project_dir);
-        let files = parser::discovery::discover_files(&paths)?;
-        graph::builder::build_graph(&project_dir, &files)?
-    };
+    let dag = build_dag(
+        &project_dir,
+        manifest,
+        &graph::builder::BuildOptions::default(),
+        &Default::default(),
+        &mut Profiler::disabled(),
+    )?;
 
-    // Find the source model node
-    let source_idx = dag
-        .node_indices()
-        .find(|&idx| {
-            let node = &dag[idx];
-            node.label == model || node.unique_id.ends_with(&format!(".{}", model))
-        })
-        .ok_or_else(|| anyhow::anyhow!("Model '{}' not found in the graph", model))?;
+    // Exact label or unique_id first, so `orders` is the model and not `raw.orders`
+    let source_idx = graph::paths::find_node(&dag, model).ok_or_else(|| {
+        DbtLineageError::InvalidArgument(format!("Model '{}' not found in the graph", model))
+    })?;
 
-    let report = graph::impact::compute_impact(&dag, source_idx);
+    let direction = if upstream {
+        graph::impact::ImpactDirection::Upstream
+    } else {
+        graph::impact::ImpactDirection::Downstream
+    };
+    let report = graph::impact::compute_impact_weighted(&dag, source_idx, direction, weights);
 
     match output {
         cli::ImpactOutputFormat::Text => render::impact::render_impact_text(&report),
         cli::ImpactOutputFormat::Json => render::impact::render_impact_json(&report),
+        cli::ImpactOutputFormat::Markdown => {
+            render::impact::render_impact_markdown(&report, link_prefix)
+        }
     }
 
-    Ok(())
+    let exceeded = fail_on.is_some_and(|threshold| {
+        !report.impacted_nodes.is_empty()
+            && report
+                .overall_severity
+                .is_some_and(|severity| severity >= threshold)
+    });
+    Ok(ExitStatus::findings_if(exceeded))
 }
 
-/// Run the `diff` subcommand
+/// Run the `diff` subcommand and return the diff it printed
 #[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
 fn run_diff_command(
     base: &str,
     head: Option<&str>,
     project_dir: &Path,
     output: &cli::DiffOutputFormat,
-) -> Result<()> {
+    link_prefix: Option<&str>,
+    fast: bool,
+    statuses: &HashSet<graph::diff::DiffStatus>,
+    group_by: Option<graph::diff::DiffGroupBy>,
+    with_impact: bool,
+    columns: bool,
+) -> Result<graph::diff::LineageDiff> {
     let project_dir = project_dir
         .canonicalize()
         .unwrap_or_else(|_| project_dir.to_path_buf());
 
     if !dbt_lineage::git::is_git_repo(&project_dir) {
-        anyhow::bail!("Not a git repository: {}", project_dir.display());
+        return Err(not_a_git_repo(&project_dir));
     }
 
     // Validate base ref
     dbt_lineage::git::validate_ref(&project_dir, base)?;
 
+    if fast {
+        if output.diagram().is_some() {
+            return Err(DbtLineageError::InvalidArgument(
+                "--fast cannot draw a diagram: it never builds the graphs (drop --fast or use -o text|json)".into(),
+            )
+            .into());
+        }
+        return run_fast_diff(
+            base,
+            head,
+            &project_dir,
+            output,
+            link_prefix,
+            statuses,
+            group_by,
+        );
+    }
+
     // Build base graph from git ref
     let base_graph = graph::diff::build_graph_from_ref(&project_dir, base)?;
 
     // Build head graph (from git ref or working tree)
-    let (head_graph, head_label) = if let Some(head_ref) = head {
-        dbt_lineage::git::validate_ref(&project_dir, head_ref)?;
-        let g = graph::diff::build_graph_from_ref(&project_dir, head_ref)?;
-        (g, head_ref.to_string())
+    let (head_graph, head_label) = build_head_graph(&project_dir, head)?;
+    ensure_diff_has_nodes(&base_graph, &head_graph, base, &head_label)?;
+
+    let mut diff = graph::diff::compute_diff(&base_graph, &head_graph, base, &head_label);
+    if with_impact {
+        diff.annotate_impact(&head_graph);
+    }
+    if columns {
+        // Each side's SQL is read at its own ref
+        let at_ref = |graph: &graph::types::LineageGraph, git_ref: &str| {
+            parser::column_lineage::resolve_column_lineage_with(graph, |path| {
+                dbt_lineage::git::git_show(&project_dir, git_ref, &path.to_string_lossy()).ok()
+            })
+        };
+        let base_lineage = at_ref(&base_graph, base);
+        let head_lineage = match head {
+            Some(head_ref) => at_ref(&head_graph, head_ref),
+            None => parser::column_lineage::resolve_column_lineage_in(&head_graph, &project_dir),
+        };
+        diff.columns = Some(graph::diff::compute_column_diff(
+            &base_lineage,
+            &head_lineage,
+        ));
+    }
+    finish_diff(
+        diff,
+        Some((&base_graph, &head_graph)),
+        output,
+        link_prefix,
+        statuses,
+        group_by,
+    )
+}
+
+/// `diff --fast`: only re-parse the files git reports as changed
+#[cfg(not(tarpaulin_include))]
+fn run_fast_diff(
+    base: &str,
+    head: Option<&str>,
+    project_dir: &Path,
+    output: &cli::DiffOutputFormat,
+    link_prefix: Option<&str>,
+    statuses: &HashSet<graph::diff::DiffStatus>,
+    group_by: Option<graph::diff::DiffGroupBy>,
+) -> Result<graph::diff::LineageDiff> {
+    if let Some(head_ref) = head {
+        dbt_lineage::git::validate_ref(project_dir, head_ref)?;
+    }
+    let changed = dbt_lineage::git::git_diff_name_status(project_dir, base, head)?;
+    let head_label = match head {
+        Some(head_ref) => head_ref.to_string(),
+        None => dbt_lineage::git::current_ref(project_dir).unwrap_or_else(|_| "HEAD".into()),
+    };
+
+    let model_paths: Vec<PathBuf> = parser::project::DbtProject::load(project_dir)?
+        .resolve_paths(project_dir)
+        .model_paths
+        .iter()
+        .map(|dir| graph::types::project_relative_path(dir, project_dir))
+        .collect();
+
+    let diff = graph::diff::compute_changed_files_diff(
+        &changed,
+        &model_paths,
+        base,
+        &head_label,
+        |path| dbt_lineage::git::git_show(project_dir, base, path).ok(),
+        |path| match head {
+            Some(head_ref) => dbt_lineage::git::git_show(project_dir, head_ref, path).ok(),
+            None => std::fs::read_to_string(project_dir.join(path)).ok(),
+        },
+    );
+    finish_diff(diff, None, output, link_prefix, statuses, group_by)
+}
+
+/// Filter and group a computed diff, then render it. Diagrams need the base
+/// and head graphs the diff was computed from.
+#[cfg(not(tarpaulin_include))]
+fn finish_diff(
+    mut diff: graph::diff::LineageDiff,
+    graphs: Option<(&graph::types::LineageGraph, &graph::types::LineageGraph)>,
+    output: &cli::DiffOutputFormat,
+    link_prefix: Option<&str>,
+    statuses: &HashSet<graph::diff::DiffStatus>,
+    group_by: Option<graph::diff::DiffGroupBy>,
+) -> Result<graph::diff::LineageDiff> {
+    diff.retain_statuses(statuses);
+    if let Some(by) = group_by {
+        diff.group_by(by);
+    }
+
+    match output.diagram() {
+        Some(format) => {
+            let (base_graph, head_graph) = graphs.ok_or_else(|| {
+                DbtLineageError::InvalidArgument(
+                    "drawing a diff needs the base and head graphs".into(),
+                )
+            })?;
+            let (subgraph, highlight) = graph::diff::diff_subgraph(base_graph, head_graph, &diff);
+            let options = render::RenderOptions {
+                diff: Some(&highlight),
+                ..Default::default()
+            };
+            render_output(&format, None, &subgraph, &options, None)?;
+        }
+        None => match output {
+            cli::DiffOutputFormat::Json => render::diff::render_diff_json(&diff),
+            cli::DiffOutputFormat::Markdown => {
+                render::diff::render_diff_markdown(&diff, link_prefix)
+            }
+            _ => render::diff::render_diff_text(&diff),
+        },
+    }
+
+    Ok(diff)
+}
+
+/// Run the `path` subcommand
+#[cfg(not(tarpaulin_include))]
+#[allow(clippy::too_many_arguments)]
+fn run_path_command(
+    from: &str,
+    to: &str,
+    project_dir: &Path,
+    output: &cli::OutputFormat,
+    all: bool,
+    max_length: usize,
+    output_file: Option<&Path>,
+    manifest: &[PathBuf],
+) -> Result<ExitStatus> {
+    check_csv_output_file(output, output_file)?;
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let dag = build_dag(
+        &project_dir,
+        manifest,
+        &graph::builder::BuildOptions::default(),
+        &Default::default(),
+        &mut Profiler::disabled(),
+    )?;
+
+    let not_found = |name: &str| {
+        DbtLineageError::InvalidArgument(format!("Node '{}' not found in the graph", name))
+    };
+    let from_idx = graph::paths::find_node(&dag, from).ok_or_else(|| not_found(from))?;
+    let to_idx = graph::paths::find_node(&dag, to).ok_or_else(|| not_found(to))?;
+
+    let paths = if all {
+        graph::paths::all_simple_paths(&dag, from_idx, to_idx, max_length)
     } else {
-        // Use current working tree
-        let g = build_working_tree_graph(&project_dir)?;
-        let label = dbt_lineage::git::current_ref(&project_dir).unwrap_or_else(|_| "HEAD".into());
-        (g, label)
+        graph::paths::shortest_path(&dag, from_idx, to_idx)
+            .into_iter()
+            .collect()
     };
 
+    // Finding no path is an answer, not a failure to run
+    if paths.is_empty() {
+        if graph::paths::shortest_path(&dag, to_idx, from_idx).is_some() {
+            eprintln!(
+                "No path from '{}' to '{}' (but '{}' is upstream of '{}'; try swapping them)",
+                from, to, to, from
+            );
+        } else {
+            eprintln!("No path from '{}' to '{}'", from, to);
+        }
+        return Ok(ExitStatus::Findings);
+    }
+
+    let subgraph = graph::paths::paths_subgraph(&dag, &paths);
+    let groups = graph::groups::GroupConfig::load(&project_dir)?;
+    let options = render::RenderOptions {
+        groups: groups.as_ref(),
+        ..Default::default()
+    };
+    // As a tree, the paths fan out downstream from `from`
+    let tree_root = graph::paths::find_node(&subgraph, from)
+        .map(|root| (root, &[render::ascii::TreeDirection::Downstream][..]));
+    render_output(output, output_file, &subgraph, &options, tree_root)?;
+
+    Ok(ExitStatus::Ok)
+}
+
+/// Run the `stats` subcommand
+#[cfg(not(tarpaulin_include))]
+fn run_stats_command(
+    project_dir: &Path,
+    output: &cli::StatsOutputFormat,
+    manifest: &[PathBuf],
+) -> Result<()> {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let dag = build_dag(
+        &project_dir,
+        manifest,
+        &graph::builder::BuildOptions::default(),
+        &Default::default(),
+        &mut Profiler::disabled(),
+    )?;
+    let stats = graph::stats::compute_stats(&dag);
+
+    match output {
+        cli::StatsOutputFormat::Text => render::stats::render_stats_text(&stats),
+        cli::StatsOutputFormat::Json => render::stats::render_stats_json(&stats),
+        cli::StatsOutputFormat::Prometheus => render::stats::render_stats_prometheus(&stats),
+    }
+
+    Ok(())
+}
+
+/// Run the `list` subcommand
+#[cfg(not(tarpaulin_include))]
+fn run_list_command(
+    project_dir: &Path,
+    node_types: Option<&str>,
+    select: Option<&str>,
+    labels: bool,
+    sort: Option<graph::sort::NodeSort>,
+    run_results: Option<&Path>,
+    manifest: &[PathBuf],
+) -> Result<()> {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let node_types = node_types
+        .map(graph::filter::parse_node_types)
+        .transpose()?;
+    let selectors = select
+        .map(graph::filter::expand_selector_arg)
+        .transpose()?
+        .as_deref()
+        .map(graph::filter::parse_selectors)
+        .unwrap_or_default();
+
+    let dag = build_dag(
+        &project_dir,
+        manifest,
+        &graph::builder::BuildOptions::default(),
+        &Default::default(),
+        &mut Profiler::disabled(),
+    )?;
+    let mut nodes = graph::filter::list_nodes(&dag, node_types.as_ref(), &selectors);
+    // Labels have always been listed alphabetically
+    let sort = sort.or(labels.then_some(graph::sort::NodeSort::Name));
+    if let Some(sort) = sort {
+        let run_status = match sort {
+            graph::sort::NodeSort::Status => {
+                Some(load_run_status(&project_dir, run_results, &dag)?)
+            }
+            _ => None,
+        };
+        graph::sort::sort_nodes(&dag, &mut nodes, sort, run_status.as_ref());
+    }
+    render::list::render_list(&dag, &nodes, labels);
+
+    Ok(())
+}
+
+/// Run the `docs` subcommand: write a Markdown page per (selected) model
+#[cfg(not(tarpaulin_include))]
+fn run_docs_command(
+    project_dir: &Path,
+    output_dir: &Path,
+    select: Option<&str>,
+    manifest: &[PathBuf],
+) -> Result<()> {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let selectors = select
+        .map(graph::filter::expand_selector_arg)
+        .transpose()?
+        .as_deref()
+        .map(graph::filter::parse_selectors)
+        .unwrap_or_default();
+
+    let dag = build_dag(
+        &project_dir,
+        manifest,
+        &graph::builder::BuildOptions::default(),
+        &Default::default(),
+        &mut Profiler::disabled(),
+    )?;
+    let models = HashSet::from([graph::types::NodeType::Model]);
+    let mut nodes = graph::filter::list_nodes(&dag, Some(&models), &selectors);
+    graph::sort::sort_nodes(&dag, &mut nodes, graph::sort::NodeSort::Name, None);
+    let written = render::docs::write_docs(&dag, &nodes, output_dir)?;
+    eprintln!("Wrote {} pages to {}", written.len(), output_dir.display());
+
+    Ok(())
+}
+
+/// Run the `check` subcommand: fail when a changed model's blast radius exceeds the budget
+#[cfg(not(tarpaulin_include))]
+fn run_check_command(
+    blast_radius: usize,
+    base: &str,
+    head: Option<&str>,
+    project_dir: &Path,
+    output: &cli::CheckOutputFormat,
+) -> Result<ExitStatus> {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    if !dbt_lineage::git::is_git_repo(&project_dir) {
+        return Err(not_a_git_repo(&project_dir));
+    }
+    dbt_lineage::git::validate_ref(&project_dir, base)?;
+
+    let base_graph = graph::diff::build_graph_from_ref(&project_dir, base)?;
+    let (head_graph, head_label) = build_head_graph(&project_dir, head)?;
+    ensure_diff_has_nodes(&base_graph, &head_graph, base, &head_label)?;
+
     let diff = graph::diff::compute_diff(&base_graph, &head_graph, base, &head_label);
+    let report = graph::check::check_blast_radius(&diff, &base_graph, &head_graph, blast_radius);
+
+    match output {
+        cli::CheckOutputFormat::Text => render::check::render_check_text(&report),
+        cli::CheckOutputFormat::Json => render::check::render_check_json(&report),
+    }
+
+    if !report.passed() {
+        eprintln!(
+            "{} changed model(s) exceed the blast radius budget of {}",
+            report.violations.len(),
+            blast_radius
+        );
+    }
+
+    Ok(ExitStatus::findings_if(!report.passed()))
+}
+
+/// Run `check --layer-order` against the working tree
+#[cfg(not(tarpaulin_include))]
+fn run_layer_check_command(
+    layer_order: &str,
+    project_dir: &Path,
+    output: &cli::CheckOutputFormat,
+) -> Result<ExitStatus> {
+    let layers = graph::check::parse_layer_order(layer_order)?;
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let graph = build_working_tree_graph(&project_dir)?;
+    let report = graph::check::check_layer_order(&graph, &layers);
+
+    match output {
+        cli::CheckOutputFormat::Text => render::check::render_layer_text(&report),
+        cli::CheckOutputFormat::Json => render::check::render_layer_json(&report),
+    }
+
+    if !report.passed() {
+        eprintln!(
+            "{} edge(s) go from a later layer to an earlier one",
+            report.violations.len()
+        );
+    }
+
+    Ok(ExitStatus::findings_if(!report.passed()))
+}
+
+/// Run `check --require-descriptions` against the working tree
+#[cfg(not(tarpaulin_include))]
+fn run_description_check_command(
+    select: Option<&str>,
+    project_dir: &Path,
+    output: &cli::CheckOutputFormat,
+) -> Result<ExitStatus> {
+    let selectors = select
+        .map(graph::filter::expand_selector_arg)
+        .transpose()?
+        .as_deref()
+        .map(graph::filter::parse_selectors)
+        .unwrap_or_default();
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let graph = build_working_tree_graph(&project_dir)?;
+    let report = graph::check::check_descriptions(&graph, &selectors);
 
     match output {
-        cli::DiffOutputFormat::Text => render::diff::render_diff_text(&diff),
-        cli::DiffOutputFormat::Json => render::diff::render_diff_json(&diff),
+        cli::CheckOutputFormat::Text => render::check::render_description_text(&report),
+        cli::CheckOutputFormat::Json => render::check::render_description_json(&report),
     }
 
+    if !report.passed() {
+        eprintln!("{} model(s) have no description", report.missing.len());
+    }
+
+    Ok(ExitStatus::findings_if(!report.passed()))
+}
+
+/// Run `check --sla`: fail when a node's last successful run is older than
+/// its `meta.sla_hours`
+#[cfg(not(tarpaulin_include))]
+fn run_sla_check_command(
+    project_dir: &Path,
+    run_results: Option<&Path>,
+    output: &cli::CheckOutputFormat,
+) -> Result<ExitStatus> {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+    let path = run_results
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| parser::artifacts::default_run_results_path(&project_dir));
+    let results = parser::artifacts::load_run_results_from(&path)?.ok_or_else(|| {
+        DbtLineageError::InvalidArgument(format!(
+            "--sla needs run results, but {} doesn't exist; run dbt first or pass --run-results",
+            path.display()
+        ))
+    })?;
+
+    let graph = build_working_tree_graph(&project_dir)?;
+    let run_status = parser::artifacts::build_run_status_map(&results, &graph, &project_dir);
+    let report = graph::sla::check_sla(&graph, &run_status, chrono::Utc::now());
+
+    match output {
+        cli::CheckOutputFormat::Text => render::check::render_sla_text(&report),
+        cli::CheckOutputFormat::Json => render::check::render_sla_json(&report),
+    }
+
+    if !report.passed() {
+        eprintln!("{} node(s) breached their SLA", report.breaches.len());
+    }
+
+    Ok(ExitStatus::findings_if(!report.passed()))
+}
+
+/// Run `check --baseline`: fail when the working tree's lineage differs from a
+/// committed JSON baseline, or rewrite the baseline with `--write-baseline`
+#[cfg(not(tarpaulin_include))]
+fn run_baseline_check_command(
+    baseline_path: &Path,
+    write_baseline: bool,
+    project_dir: &Path,
+    output: &cli::CheckOutputFormat,
+) -> Result<ExitStatus> {
+    let project_dir = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+    let current = build_working_tree_graph(&project_dir)?;
+
+    if write_baseline {
+        render::json::write_json_file(&current, baseline_path)?;
+        eprintln!(
+            "Wrote {} nodes and {} edges to {}",
+            current.node_count(),
+            current.edge_count(),
+            baseline_path.display()
+        );
+        return Ok(ExitStatus::Ok);
+    }
+
+    let baseline = parser::baseline::load_baseline(baseline_path)?;
+    let base_label = baseline_path.display().to_string();
+    ensure_diff_has_nodes(&baseline, &current, &base_label, "working tree")?;
+    let mut diff = graph::diff::compute_diff(&baseline, &current, &base_label, "working tree");
+    let changed = diff.has_changes();
+    diff.retain_statuses(&graph::diff::parse_diff_filter(
+        graph::diff::DEFAULT_DIFF_FILTER,
+    )?);
+
+    match output {
+        cli::CheckOutputFormat::Text => render::diff::render_diff_text(&diff),
+        cli::CheckOutputFormat::Json => render::diff::render_diff_json(&diff),
+    }
+
+    if changed {
+        eprintln!(
+            "Lineage differs from {}; rerun with --write-baseline if the change is intended",
+            baseline_path.display()
+        );
+    }
+
+    Ok(ExitStatus::findings_if(changed))
+}
+
+/// Error for `diff` and `check`, which compare git refs
+#[cfg(not(tarpaulin_include))]
+fn not_a_git_repo(project_dir: &Path) -> anyhow::Error {
+    DbtLineageError::InvalidArgument(format!("Not a git repository: {}", project_dir.display()))
+        .into()
+}
+
+/// Build the head side of a diff from a git ref, or the working tree when `head` is None.
+/// Returns the graph and the label to show for it.
+#[cfg(not(tarpaulin_include))]
+fn build_head_graph(
+    project_dir: &Path,
+    head: Option<&str>,
+) -> Result<(graph::types::LineageGraph, String)> {
+    if let Some(head_ref) = head {
+        dbt_lineage::git::validate_ref(project_dir, head_ref)?;
+        let g = graph::diff::build_graph_from_ref(project_dir, head_ref)?;
+        Ok((g, head_ref.to_string()))
+    } else {
+        // Use current working tree
+        let g = build_working_tree_graph(project_dir)?;
+        let label = dbt_lineage::git::current_ref(project_dir).unwrap_or_else(|_| "HEAD".into());
+        Ok((g, label))
+    }
+}
+
+/// Fail when neither side of a diff has any nodes, which means the project was not found
+/// rather than that nothing changed
+#[cfg(not(tarpaulin_include))]
+fn ensure_diff_has_nodes(
+    base_graph: &graph::types::LineageGraph,
+    head_graph: &graph::types::LineageGraph,
+    base: &str,
+    head: &str,
+) -> Result<()> {
+    if base_graph.node_count() == 0 && head_graph.node_count() == 0 {
+        return Err(dbt_lineage::error::DbtLineageError::EmptyDiff {
+            base: base.to_string(),
+            head: head.to_string(),
+        }
+        .into());
+    }
     Ok(())
 }
 
+/// Keep only nodes whose files were changed by commits since `since`, plus their
+/// neighbors up to `neighbors` hops away
+#[cfg(not(tarpaulin_include))]
+fn restrict_to_changed_files(
+    project_dir: &Path,
+    dag: &graph::types::LineageGraph,
+    since: &str,
+    neighbors: usize,
+) -> Result<graph::types::LineageGraph> {
+    if !dbt_lineage::git::is_git_repo(project_dir) {
+        return Err(DbtLineageError::InvalidArgument(format!(
+            "--changed-since needs a git repository, but {} is not inside one",
+            project_dir.display()
+        ))
+        .into());
+    }
+    let files: HashSet<PathBuf> = dbt_lineage::git::files_changed_since(project_dir, since)?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    let nodes = graph::filter::nodes_for_files(dag, &files, neighbors);
+    if nodes.is_empty() {
+        eprintln!("Warning: no model files changed since {}", since);
+    }
+    Ok(graph::filter::induced_subgraph(dag, &nodes))
+}
+
 /// Build a graph from the current working tree
 #[cfg(not(tarpaulin_include))]
 fn build_working_tree_graph(project_dir: &Path) -> Result<graph::types::LineageGraph> {
@@ -201,6 +1364,37 @@ fn build_working_tree_graph(project_dir: &Path) -> Result<graph::types::LineageG
     graph::builder::build_graph(project_dir, &files)
 }
 
+/// Resolve the catalog path from the --catalog argument (a file, or a directory
+/// containing `target/catalog.json`). Without --catalog, a `catalog.json` next to
+/// the manifest is picked up automatically.
+#[cfg(not(tarpaulin_include))]
+fn resolve_catalog_path(
+    catalog_arg: Option<&PathBuf>,
+    manifest_arg: Option<&PathBuf>,
+) -> Result<Option<PathBuf>> {
+    if let Some(catalog_arg) = catalog_arg {
+        let path = if catalog_arg.is_dir() {
+            catalog_arg.join("target").join("catalog.json")
+        } else {
+            catalog_arg.to_path_buf()
+        };
+        if !path.exists() {
+            return Err(DbtLineageError::InvalidArgument(format!(
+                "Catalog path does not exist: {}",
+                path.display()
+            ))
+            .into());
+        }
+        return Ok(Some(path));
+    }
+
+    let Some(manifest_arg) = manifest_arg else {
+        return Ok(None);
+    };
+    let sibling = resolve_manifest_path(manifest_arg)?.with_file_name("catalog.json");
+    Ok(sibling.exists().then_some(sibling))
+}
+
 /// Resolve the manifest path from the --manifest argument.
 /// If the path is a directory, look for `target/manifest.json` inside it.
 /// If it's a file, use it directly.
@@ -211,14 +1405,19 @@ fn resolve_manifest_path(manifest_arg: &Path) -> Result<PathBuf> {
         if candidate.exists() {
             Ok(candidate)
         } else {
-            anyhow::bail!(
+            Err(DbtLineageError::InvalidArgument(format!(
                 "No manifest.json found at {}. Expected target/manifest.json in the directory.",
                 candidate.display()
-            );
+            ))
+            .into())
         }
     } else if manifest_arg.exists() {
         Ok(manifest_arg.to_path_buf())
     } else {
-        anyhow::bail!("Manifest path does not exist: {}", manifest_arg.display());
+        Err(DbtLineageError::InvalidArgument(format!(
+            "Manifest path does not exist: {}",
+            manifest_arg.display()
+        ))
+        .into())
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -37,14 +37,24 @@ impl RunResult {
     }
 }
 
+/// Where dbt writes run results in a project: `target/run_results.json`
+pub fn default_run_results_path(project_dir: &Path) -> PathBuf {
+    project_dir.join("target").join("run_results.json")
+}
+
 /// Load `target/run_results.json` from the project directory.
 /// Returns `None` if the file doesn't exist.
 pub fn load_run_results(project_dir: &Path) -> Result<Option<RunResults>> {
-    let path = project_dir.join("target").join("run_results.json");
+    load_run_results_from(&default_run_results_path(project_dir))
+}
+
+/// Load a run_results.json from anywhere, e.g. another target's artifacts
+/// (`--run-results`). Returns `None` if the file doesn't exist.
+pub fn load_run_results_from(path: &Path) -> Result<Option<RunResults>> {
     if !path.exists() {
         return Ok(None);
     }
-    let content = fs::read_to_string(&path)?;
+    let content = fs::read_to_string(path)?;
     let results: RunResults = serde_json::from_str(&content)?;
     Ok(Some(results))
 }
@@ -288,20 +298,13 @@ mod tests {
             label: "stg_orders".into(),
             node_type: NodeType::Model,
             file_path: Some(std::path::PathBuf::from("models/stg_orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         graph.add_node(NodeData {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         graph
     }
@@ -470,11 +473,7 @@ mod tests {
             unique_id: "model.x".into(),
             label: "x".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let tmp = tempfile::tempdir().unwrap();
         let status = resolve_run_status(Some(&result), &node, tmp.path());
@@ -497,11 +496,7 @@ mod tests {
             unique_id: "test.x".into(),
             label: "x".into(),
             node_type: NodeType::Test,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let tmp = tempfile::tempdir().unwrap();
         let status = resolve_run_status(Some(&result), &node, tmp.path());
@@ -520,11 +515,7 @@ mod tests {
             unique_id: "test.x".into(),
             label: "x".into(),
             node_type: NodeType::Test,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let tmp = tempfile::tempdir().unwrap();
         let status = resolve_run_status(Some(&result), &node, tmp.path());
@@ -543,17 +534,33 @@ mod tests {
             unique_id: "model.x".into(),
             label: "x".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let tmp = tempfile::tempdir().unwrap();
         let status = resolve_run_status(Some(&result), &node, tmp.path());
         assert!(matches!(status, RunStatus::Skipped { .. }));
     }
 
+    #[test]
+    fn test_load_run_results_from_other_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("prod_run_results.json");
+        std::fs::write(
+            &path,
+            r#"{"results": [{"unique_id": "model.x.y", "status": "error", "message": "boom", "timing": []}]}"#,
+        )
+        .unwrap();
+        let rr = load_run_results_from(&path).unwrap().unwrap();
+        assert_eq!(rr.results[0].status, "error");
+        assert!(load_run_results_from(&tmp.path().join("missing.json"))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            default_run_results_path(Path::new("proj")),
+            Path::new("proj/target/run_results.json")
+        );
+    }
+
     #[test]
     fn test_load_run_results_existing_file() {
         let tmp = tempfile::tempdir().unwrap();
@@ -583,10 +590,7 @@ mod tests {
             label: "stg_orders".into(),
             node_type: NodeType::Model,
             file_path: Some(std::path::PathBuf::from("models/stg_orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
 
         // Use a timestamp in the past so the file modification is newer
@@ -609,10 +613,7 @@ mod tests {
             label: "stg_orders".into(),
             node_type: NodeType::Model,
             file_path: Some(std::path::PathBuf::from("models/stg_orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
 
         // Use a timestamp far in the future
@@ -635,11 +636,7 @@ mod tests {
             unique_id: "model.x".into(),
             label: "x".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let tmp = tempfile::tempdir().unwrap();
         let status = resolve_run_status(Some(&result), &node, tmp.path());
@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::error::DbtLineageError;
+use crate::graph::diff::edge_type_str;
+use crate::graph::filter::{ALL_EDGE_TYPES, ALL_NODE_TYPES};
+use crate::graph::types::*;
+use crate::render::json::JSON_FORMAT_VERSION;
+
+/// A lineage baseline: the flat `-o json` export written by
+/// `check --baseline PATH --write-baseline`
+#[derive(Debug, Deserialize)]
+struct BaselineFile {
+    format_version: Option<u32>,
+    #[serde(default)]
+    nodes: Vec<BaselineNode>,
+    #[serde(default)]
+    edges: Vec<BaselineEdge>,
+}
+
+/// The node fields the diff compares, plus those that identify the node.
+/// Derived fields such as `upstream_sources` are ignored.
+#[derive(Debug, Deserialize)]
+struct BaselineNode {
+    unique_id: String,
+    label: String,
+    node_type: String,
+    file_path: Option<PathBuf>,
+    description: Option<String>,
+    materialization: Option<String>,
+    schema: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    columns: Vec<String>,
+    version: Option<String>,
+    #[serde(default)]
+    column_types: BTreeMap<String, String>,
+    project: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BaselineEdge {
+    source: String,
+    target: String,
+    edge_type: String,
+}
+
+/// Read a baseline written by `check --write-baseline` back into a graph
+pub fn load_baseline(path: &Path) -> Result<LineageGraph> {
+    let content = std::fs::read_to_string(path).map_err(|e| DbtLineageError::FileReadError {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    parse_baseline(&content, path)
+}
+
+/// Parse baseline JSON; `path` is only used in error messages
+pub fn parse_baseline(content: &str, path: &Path) -> Result<LineageGraph> {
+    let baseline: BaselineFile =
+        serde_json::from_str(content).map_err(|e| DbtLineageError::ArtifactParseError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+    let invalid = |detail: String| -> anyhow::Error {
+        DbtLineageError::InvalidArgument(format!("baseline {}: {}", path.display(), detail)).into()
+    };
+    match baseline.format_version {
+        Some(JSON_FORMAT_VERSION) => {}
+        Some(version) => {
+            return Err(invalid(format!(
+                "format_version {} is not supported (expected {}); regenerate it with --write-baseline",
+                version, JSON_FORMAT_VERSION
+            )))
+        }
+        None => {
+            return Err(invalid(
+                "no format_version; write one with `dbt-lineage check --baseline <PATH> --write-baseline`"
+                    .into(),
+            ))
+        }
+    }
+
+    let mut graph = LineageGraph::new();
+    let mut indices = HashMap::new();
+    for node in baseline.nodes {
+        let node_type = ALL_NODE_TYPES
+            .into_iter()
+            .find(|nt| nt.label() == node.node_type)
+            .ok_or_else(|| {
+                invalid(format!(
+                    "unknown node type '{}' for {}",
+                    node.node_type, node.unique_id
+                ))
+            })?;
+        let unique_id = node.unique_id.clone();
+        let idx = graph.add_node(NodeData {
+            unique_id: node.unique_id,
+            label: node.label,
+            node_type,
+            file_path: node.file_path,
+            description: node.description,
+            materialization: node.materialization,
+            tags: node.tags,
+            columns: node.columns,
+            version: node.version,
+            column_types: node.column_types,
+            project: node.project,
+            schema: node.schema,
+            ..Default::default()
+        });
+        indices.insert(unique_id, idx);
+    }
+
+    for edge in baseline.edges {
+        let edge_type = ALL_EDGE_TYPES
+            .into_iter()
+            .find(|et| edge_type_str(*et) == edge.edge_type)
+            .ok_or_else(|| invalid(format!("unknown edge type '{}'", edge.edge_type)))?;
+        let endpoint = |id: &str| {
+            indices
+                .get(id)
+                .copied()
+                .ok_or_else(|| invalid(format!("edge refers to unknown node {}", id)))
+        };
+        let (source, target) = (endpoint(&edge.source)?, endpoint(&edge.target)?);
+        graph.add_edge(
+            source,
+            target,
+            EdgeData {
+                edge_type,
+                snippet: None,
+            },
+        );
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::diff::compute_diff;
+
+    fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_has_no_changes() {
+        let mut graph = LineageGraph::new();
+        let raw = graph.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        let orders = graph.add_node(NodeData {
+            file_path: Some(PathBuf::from("models/orders.sql")),
+            description: Some("Orders".into()),
+            materialization: Some("table".into()),
+            tags: vec!["marts".into()],
+            columns: vec!["order_id".into()],
+            ..make_node("model.orders", "orders", NodeType::Model)
+        });
+        graph.add_edge(
+            raw,
+            orders,
+            EdgeData {
+                edge_type: EdgeType::Source,
+                snippet: None,
+            },
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lineage.json");
+        crate::render::json::write_json_file(&graph, &path).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.node_count(), 2);
+        assert_eq!(baseline.edge_count(), 1);
+        assert!(!compute_diff(&baseline, &graph, "baseline", "head").has_changes());
+
+        let mut head = graph.clone();
+        head[orders].materialization = Some("view".into());
+        let diff = compute_diff(&baseline, &head, "baseline", "head");
+        assert!(diff.has_changes());
+        assert_eq!(diff.summary.nodes_modified, 1);
+    }
+
+    #[test]
+    fn test_rejects_unversioned_or_newer_files() {
+        let path = Path::new("lineage.json");
+        let err = parse_baseline(r#"{"nodes": [], "edges": []}"#, path).unwrap_err();
+        assert!(err.to_string().contains("no format_version"), "{}", err);
+        let err = parse_baseline(r#"{"format_version": 99, "nodes": []}"#, path).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("format_version 99 is not supported"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_rejects_dangling_edges_and_unknown_types() {
+        let path = Path::new("lineage.json");
+        let err = parse_baseline(
+            r#"{"format_version": 1, "nodes": [], "edges": [{"source": "model.a", "target": "model.b", "edge_type": "ref"}]}"#,
+            path,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown node model.a"), "{}", err);
+        let err = parse_baseline(
+            r#"{"format_version": 1, "nodes": [{"unique_id": "x.a", "label": "a", "node_type": "widget"}]}"#,
+            path,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown node type 'widget'"),
+            "{}",
+            err
+        );
+    }
+}
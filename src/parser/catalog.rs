@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::graph::types::LineageGraph;
+
+/// Parsed `catalog.json` (output of `dbt docs generate`)
+#[derive(Debug, Default, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    pub nodes: HashMap<String, CatalogTable>,
+    #[serde(default)]
+    pub sources: HashMap<String, CatalogTable>,
+}
+
+/// A relation in the catalog with the columns the warehouse reports for it
+#[derive(Debug, Default, Deserialize)]
+pub struct CatalogTable {
+    #[serde(default)]
+    pub columns: HashMap<String, CatalogColumn>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CatalogColumn {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: Option<String>,
+    /// 1-based ordinal position in the relation
+    #[serde(default)]
+    pub index: Option<u64>,
+    /// Column comment set in the warehouse
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl CatalogTable {
+    /// Columns in warehouse ordinal order
+    pub fn ordered_columns(&self) -> Vec<&CatalogColumn> {
+        let mut columns: Vec<&CatalogColumn> = self.columns.values().collect();
+        columns.sort_by(|a, b| {
+            a.index
+                .unwrap_or(u64::MAX)
+                .cmp(&b.index.unwrap_or(u64::MAX))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        columns
+    }
+}
+
+/// Load and parse a `catalog.json` file
+pub fn load_catalog(path: &Path) -> Result<Catalog> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        crate::error::DbtLineageError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        }
+    })?;
+    let catalog = serde_json::from_str(&content).map_err(|e| {
+        crate::error::DbtLineageError::ArtifactParseError {
+            path: path.to_path_buf(),
+            source: e,
+        }
+    })?;
+    Ok(catalog)
+}
+
+/// Map a catalog unique_id to this tool's graph unique_id by dropping the project:
+/// `model.proj.orders` → `model.orders`, `source.proj.raw.orders` → `source.raw.orders`,
+/// `model.proj.dim_customers.v2` → `model.dim_customers.v2`.
+fn graph_unique_id(catalog_id: &str) -> String {
+    match catalog_id.split('.').collect::<Vec<_>>().as_slice() {
+        [kind, _project, rest @ ..] if !rest.is_empty() => {
+            format!("{}.{}", kind, rest.join("."))
+        }
+        _ => catalog_id.to_string(),
+    }
+}
+
+/// Replace the columns of every graph node found in `catalog` with the warehouse
+/// columns and record their data types. Returns the number of nodes updated.
+///
+/// Nodes without a catalog entry keep their SQL-parsed columns. Warehouse
+/// comments fill in descriptions for columns the YAML leaves undocumented.
+pub fn apply_catalog(graph: &mut LineageGraph, catalog: &Catalog) -> usize {
+    let index: HashMap<String, _> = graph
+        .node_indices()
+        .map(|idx| (graph[idx].unique_id.clone(), idx))
+        .collect();
+
+    let mut updated = 0;
+    for (catalog_id, table) in catalog.nodes.iter().chain(catalog.sources.iter()) {
+        if table.columns.is_empty() {
+            continue;
+        }
+        let Some(&idx) = index.get(&graph_unique_id(catalog_id)) else {
+            continue;
+        };
+
+        let node = &mut graph[idx];
+        let columns = table.ordered_columns();
+        node.columns = columns.iter().map(|c| c.name.clone()).collect();
+        node.column_types = columns
+            .iter()
+            .filter_map(|c| c.data_type.clone().map(|t| (c.name.clone(), t)))
+            .collect();
+        for column in &columns {
+            let Some(comment) = column.comment.as_deref().map(str::trim) else {
+                continue;
+            };
+            if !comment.is_empty() {
+                node.column_descriptions
+                    .entry(column.name.clone())
+                    .or_insert_with(|| comment.to_string());
+            }
+        }
+        updated += 1;
+    }
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::*;
+
+    fn make_node(unique_id: &str, columns: &[&str]) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type: NodeType::Model,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    const CATALOG: &str = r#"{
+        "metadata": {"dbt_version": "1.7.0"},
+        "nodes": {
+            "model.proj.orders": {
+                "metadata": {"type": "BASE TABLE", "name": "orders"},
+                "columns": {
+                    "AMOUNT": {"type": "NUMBER", "index": 2, "name": "AMOUNT", "comment": null},
+                    "ORDER_ID": {"type": "INTEGER", "index": 1, "name": "ORDER_ID", "comment": null}
+                }
+            },
+            "model.proj.dim_customers.v2": {
+                "columns": {
+                    "ID": {"type": "INTEGER", "index": 1, "name": "ID"}
+                }
+            }
+        },
+        "sources": {
+            "source.proj.raw.customers": {
+                "columns": {
+                    "name": {"type": "TEXT", "index": 1, "name": "name"}
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_graph_unique_id() {
+        assert_eq!(graph_unique_id("model.proj.orders"), "model.orders");
+        assert_eq!(
+            graph_unique_id("source.proj.raw.orders"),
+            "source.raw.orders"
+        );
+        assert_eq!(
+            graph_unique_id("model.proj.dim_customers.v2"),
+            "model.dim_customers.v2"
+        );
+        assert_eq!(graph_unique_id("model.orders"), "model.orders");
+    }
+
+    #[test]
+    fn test_apply_catalog_overrides_columns_with_types() {
+        let catalog: Catalog = serde_json::from_str(CATALOG).unwrap();
+        let mut graph = LineageGraph::new();
+        let orders = graph.add_node(make_node("model.orders", &["*"]));
+        let dim = graph.add_node(make_node("model.dim_customers.v2", &[]));
+        let src = graph.add_node(make_node("source.raw.customers", &[]));
+        let untouched = graph.add_node(make_node("model.stg_orders", &["id"]));
+
+        assert_eq!(apply_catalog(&mut graph, &catalog), 3);
+
+        assert_eq!(graph[orders].columns, vec!["ORDER_ID", "AMOUNT"]);
+        assert_eq!(graph[orders].column_types["AMOUNT"], "NUMBER");
+        assert_eq!(graph[dim].columns, vec!["ID"]);
+        assert_eq!(graph[src].column_types["name"], "TEXT");
+        assert_eq!(graph[untouched].columns, vec!["id"]);
+        assert!(graph[untouched].column_types.is_empty());
+    }
+
+    #[test]
+    fn test_apply_catalog_comments_fill_missing_descriptions() {
+        let catalog: Catalog = serde_json::from_str(
+            r#"{"nodes": {"model.proj.orders": {"columns": {
+                "ID": {"type": "INTEGER", "index": 1, "name": "ID", "comment": "Warehouse id"},
+                "AMOUNT": {"type": "NUMBER", "index": 2, "name": "AMOUNT", "comment": "Warehouse amount"},
+                "NOTE": {"type": "TEXT", "index": 3, "name": "NOTE", "comment": " "}
+            }}}}"#,
+        )
+        .unwrap();
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.orders", &[]);
+        node.column_descriptions
+            .insert("AMOUNT".into(), "Documented amount".into());
+        let orders = graph.add_node(node);
+
+        apply_catalog(&mut graph, &catalog);
+        let descriptions = &graph[orders].column_descriptions;
+        assert_eq!(descriptions["ID"], "Warehouse id");
+        assert_eq!(descriptions["AMOUNT"], "Documented amount");
+        assert!(!descriptions.contains_key("NOTE"));
+    }
+
+    #[test]
+    fn test_load_catalog_missing_file() {
+        assert!(load_catalog(Path::new("/nonexistent/catalog.json")).is_err());
+    }
+
+    #[test]
+    fn test_load_catalog_invalid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("catalog.json");
+        std::fs::write(&path, "not json").unwrap();
+        let err = load_catalog(&path).unwrap_err();
+        assert!(err.to_string().contains("failed to parse artifact"));
+    }
+}
@@ -1,10 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 
 use regex::Regex;
 use serde::Serialize;
 use std::sync::LazyLock;
 
 use crate::graph::types::LineageGraph;
+use crate::parser::columns::{is_clause_keyword, StarModifiers};
+use crate::parser::sql::parse_source_args;
 
 /// Confidence level for a column-level edge
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -41,6 +44,18 @@ pub struct ColumnEdge {
     pub confidence: ColumnConfidence,
 }
 
+/// A description copied onto an undocumented column from the documented
+/// upstream column it passes through unchanged
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InheritedDescription {
+    pub node: String,
+    pub column: String,
+    pub description: String,
+    /// Documented column the description originates from
+    pub source_node: String,
+    pub source_column: String,
+}
+
 /// All column-level lineage information
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct ColumnLineage {
@@ -55,6 +70,137 @@ impl ColumnLineage {
             .filter(|e| e.target_node == target_node)
             .collect()
     }
+
+    /// Edges that feed `(node, column)` directly
+    pub fn upstream_of(&self, node: &str, column: &str) -> Vec<&ColumnEdge> {
+        self.edges
+            .iter()
+            .filter(|e| e.target_node == node && e.target_column == column)
+            .collect()
+    }
+
+    /// Every edge `(node, column)` is transitively built from, nearest first,
+    /// walking edge sources back until no upstream edge is recorded. Derived
+    /// edges with no source column end the walk. Each column is visited once,
+    /// so cyclic lineage terminates.
+    pub fn trace_upstream(&self, node: &str, column: &str) -> Vec<&ColumnEdge> {
+        let mut chain = Vec::new();
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut queue = VecDeque::from([(node.to_string(), column.to_string())]);
+        while let Some((n, c)) = queue.pop_front() {
+            if !visited.insert((n.clone(), c.clone())) {
+                continue;
+            }
+            for edge in self.upstream_of(&n, &c) {
+                chain.push(edge);
+                if !edge.source_column.is_empty() {
+                    queue.push_back((edge.source_node.clone(), edge.source_column.clone()));
+                }
+            }
+        }
+        chain
+    }
+
+    /// Descriptions for undocumented columns that select a documented upstream
+    /// column unchanged (`Direct` or `Aliased` edges), following chains of such
+    /// columns back to the documented one. Columns with their own description
+    /// are never overridden; when several upstream columns qualify, the first
+    /// edge wins. Column names are compared case-insensitively.
+    pub fn inherited_descriptions(&self, graph: &LineageGraph) -> Vec<InheritedDescription> {
+        let key = |node: &str, column: &str| (node.to_string(), column.to_lowercase());
+        // (node, column) → (description, origin node, origin column)
+        let mut known: HashMap<(String, String), (String, String, String)> = HashMap::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            for (column, description) in &node.column_descriptions {
+                known.insert(
+                    key(&node.unique_id, column),
+                    (description.clone(), node.unique_id.clone(), column.clone()),
+                );
+            }
+        }
+
+        let mut inherited = Vec::new();
+        loop {
+            let mut changed = false;
+            for edge in &self.edges {
+                if !matches!(
+                    edge.confidence,
+                    ColumnConfidence::Direct | ColumnConfidence::Aliased
+                ) {
+                    continue;
+                }
+                let target = key(&edge.target_node, &edge.target_column);
+                if known.contains_key(&target) {
+                    continue;
+                }
+                let Some(origin) = known
+                    .get(&key(&edge.source_node, &edge.source_column))
+                    .cloned()
+                else {
+                    continue;
+                };
+                inherited.push(InheritedDescription {
+                    node: edge.target_node.clone(),
+                    column: edge.target_column.clone(),
+                    description: origin.0.clone(),
+                    source_node: origin.1.clone(),
+                    source_column: origin.2.clone(),
+                });
+                known.insert(target, origin);
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        inherited
+    }
+
+    /// Collect every column edge reachable from `(node, column)`, walking
+    /// upstream through edge sources and downstream through edge targets.
+    /// Derived edges with no source column end the upstream walk. For the
+    /// upstream half alone, nearest first, see [`Self::trace_upstream`].
+    pub fn trace_column(&self, node: &str, column: &str) -> Vec<&ColumnEdge> {
+        let mut seen_edges: HashSet<usize> = HashSet::new();
+        let start = (node.to_string(), column.to_string());
+
+        // Upstream: follow edges whose target is the current column
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut stack = vec![start.clone()];
+        while let Some((n, c)) = stack.pop() {
+            if !visited.insert((n.clone(), c.clone())) {
+                continue;
+            }
+            for (i, e) in self.edges.iter().enumerate() {
+                if e.target_node == n
+                    && e.target_column == c
+                    && seen_edges.insert(i)
+                    && !e.source_column.is_empty()
+                {
+                    stack.push((e.source_node.clone(), e.source_column.clone()));
+                }
+            }
+        }
+
+        // Downstream: follow edges whose source is the current column
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut stack = vec![start];
+        while let Some((n, c)) = stack.pop() {
+            if !visited.insert((n.clone(), c.clone())) {
+                continue;
+            }
+            for (i, e) in self.edges.iter().enumerate() {
+                if e.source_node == n && e.source_column == c && seen_edges.insert(i) {
+                    stack.push((e.target_node.clone(), e.target_column.clone()));
+                }
+            }
+        }
+
+        let mut indices: Vec<usize> = seen_edges.into_iter().collect();
+        indices.sort_unstable();
+        indices.into_iter().map(|i| &self.edges[i]).collect()
+    }
 }
 
 /// A table reference extracted from FROM/JOIN clauses
@@ -77,6 +223,8 @@ pub struct SelectItem {
     pub source_column: Option<String>,
     /// Whether this is a `SELECT *`
     pub is_star: bool,
+    /// `EXCEPT` / `REPLACE` modifiers of a star item
+    pub star_modifiers: StarModifiers,
     /// Whether this is a derived expression
     pub is_derived: bool,
 }
@@ -84,11 +232,18 @@ pub struct SelectItem {
 /// Regex for FROM/JOIN table references with optional alias
 static TABLE_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"(?i)(?:FROM|JOIN)\s+\{\{\s*(?:ref\(\s*'([^']+)'\s*\)|source\(\s*'([^']+)'\s*,\s*'([^']+)'\s*\))\s*\}\}(?:\s+(?:AS\s+)?(\w+))?"
+        r"(?i)(?:FROM|JOIN)\s+\{\{-?\s*(?:ref\(\s*'([^']+)'\s*\)|source\s*\(([^()]*)\))\s*-?\}\}(?:\s+(?:AS\s+)?(\w+))?"
     )
     .unwrap()
 });
 
+/// Regex for a PIVOT or UNPIVOT operator
+static PIVOT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(?:UN)?PIVOT\s*\(").unwrap());
+
+/// Regex for identifiers inside an expression
+static IDENTIFIER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_]\w*").unwrap());
+
 /// Regex for simple column references: `alias.column` or `column`
 #[allow(dead_code)]
 static COLUMN_REF_RE: LazyLock<Regex> =
@@ -99,17 +254,23 @@ pub fn extract_table_refs(sql: &str) -> Vec<TableRef> {
     let mut refs = Vec::new();
 
     for cap in TABLE_REF_RE.captures_iter(sql) {
-        let alias = cap.get(4).map(|m| m.as_str().to_string());
+        // `FROM {{ ref('x') }} QUALIFY ...` has no alias; the next clause keyword
+        // is not one
+        let alias = cap
+            .get(3)
+            .map(|m| m.as_str())
+            .filter(|a| !is_clause_keyword(a))
+            .map(str::to_string);
 
         if let Some(ref_name) = cap.get(1) {
             refs.push(TableRef {
                 alias,
                 node_id: format!("model.{}", ref_name.as_str()),
             });
-        } else if let (Some(source_name), Some(table_name)) = (cap.get(2), cap.get(3)) {
+        } else if let Some(call) = cap.get(2).and_then(|args| parse_source_args(args.as_str())) {
             refs.push(TableRef {
                 alias,
-                node_id: format!("source.{}.{}", source_name.as_str(), table_name.as_str()),
+                node_id: format!("source.{}.{}", call.source_name, call.table_name),
             });
         }
     }
@@ -117,8 +278,26 @@ pub fn extract_table_refs(sql: &str) -> Vec<TableRef> {
     refs
 }
 
-/// Resolve column lineage for an entire graph
+/// Resolve column lineage for an entire graph, reading each node's
+/// project-relative SQL file from the current directory
 pub fn resolve_column_lineage(graph: &LineageGraph) -> ColumnLineage {
+    resolve_column_lineage_in(graph, Path::new(""))
+}
+
+/// Resolve column lineage for an entire graph, reading SQL files relative to
+/// `project_dir`
+pub fn resolve_column_lineage_in(graph: &LineageGraph, project_dir: &Path) -> ColumnLineage {
+    resolve_column_lineage_with(graph, |path| {
+        std::fs::read_to_string(project_dir.join(path)).ok()
+    })
+}
+
+/// Resolve column lineage for an entire graph, getting each node's SQL from
+/// `read_sql` (called with the node's file path), e.g. to read it at a git ref
+pub fn resolve_column_lineage_with(
+    graph: &LineageGraph,
+    read_sql: impl Fn(&Path) -> Option<String>,
+) -> ColumnLineage {
     let mut edges = Vec::new();
 
     // Build a map of unique_id -> columns for source resolution
@@ -133,28 +312,30 @@ pub fn resolve_column_lineage(graph: &LineageGraph) -> ColumnLineage {
     // For each model node with a file_path, try to resolve column lineage
     for idx in graph.node_indices() {
         let node = &graph[idx];
-        edges.extend(resolve_node_column_edges(node, &column_map));
+        edges.extend(resolve_node_column_edges(node, &read_sql, &column_map));
     }
 
     ColumnLineage { edges }
 }
 
-/// Resolve column edges for a single node by reading its SQL file
+/// Resolve column edges for a single node from its SQL file
 fn resolve_node_column_edges(
     node: &crate::graph::types::NodeData,
+    read_sql: &impl Fn(&Path) -> Option<String>,
     column_map: &HashMap<String, Vec<String>>,
 ) -> Vec<ColumnEdge> {
     let Some(file_path) = &node.file_path else {
         return vec![];
     };
 
-    let sql = match std::fs::read_to_string(file_path) {
-        Ok(s) => s,
-        Err(_) => return vec![],
+    let Some(sql) = read_sql(file_path) else {
+        return vec![];
     };
 
     let table_refs = extract_table_refs(&sql);
     let select_items = extract_select_items(&sql);
+    // PIVOT/UNPIVOT reshape the input, so `*` no longer passes its columns through
+    let reshaped = PIVOT_RE.is_match(&sql);
 
     // Build alias -> node_id map
     let alias_map: HashMap<String, String> = table_refs
@@ -171,6 +352,7 @@ fn resolve_node_column_edges(
 
     select_items
         .iter()
+        .filter(|item| !(reshaped && item.is_star))
         .flat_map(|item| {
             resolve_select_item(
                 item,
@@ -194,7 +376,7 @@ fn resolve_select_item(
     column_map: &HashMap<String, Vec<String>>,
 ) -> Vec<ColumnEdge> {
     if item.is_star {
-        return resolve_star_item(table_refs, target_id, column_map);
+        return resolve_star_item(table_refs, &item.star_modifiers, target_id, column_map);
     }
 
     if item.is_derived {
@@ -234,38 +416,92 @@ fn resolve_select_item(
     }
 }
 
-/// Resolve star expansion edges for all upstream sources
+/// Resolve star expansion edges for all upstream sources. Columns named in
+/// `EXCEPT` are left out; columns named in `REPLACE` are traced through their
+/// replacement expression instead.
 fn resolve_star_item(
     table_refs: &[TableRef],
+    modifiers: &StarModifiers,
     target_id: &str,
     column_map: &HashMap<String, Vec<String>>,
 ) -> Vec<ColumnEdge> {
     table_refs
         .iter()
         .flat_map(|tr| {
-            column_map
-                .get(&tr.node_id)
-                .into_iter()
-                .flatten()
-                .map(move |col| ColumnEdge {
-                    source_node: tr.node_id.clone(),
-                    source_column: col.clone(),
-                    target_node: target_id.to_string(),
-                    target_column: col.clone(),
-                    confidence: ColumnConfidence::Star,
+            let columns = column_map.get(&tr.node_id).map_or(&[][..], Vec::as_slice);
+            columns
+                .iter()
+                .filter(|col| !modifiers.excepts(col))
+                .flat_map(move |col| match modifiers.replacement(col) {
+                    Some(expr) => replaced_column_edges(&tr.node_id, columns, expr, col, target_id),
+                    None => vec![ColumnEdge {
+                        source_node: tr.node_id.clone(),
+                        source_column: col.clone(),
+                        target_node: target_id.to_string(),
+                        target_column: col.clone(),
+                        confidence: ColumnConfidence::Star,
+                    }],
                 })
         })
         .collect()
 }
 
+/// Edges for a star column rewritten by `REPLACE (expr AS column)`: an
+/// `Aliased` edge when `expr` is just another upstream column, else a
+/// `Derived` edge from each upstream column the expression mentions
+fn replaced_column_edges(
+    source_node: &str,
+    upstream_columns: &[String],
+    expr: &str,
+    column: &str,
+    target_id: &str,
+) -> Vec<ColumnEdge> {
+    let edge = |source_column: &str, confidence| ColumnEdge {
+        source_node: source_node.to_string(),
+        source_column: source_column.to_string(),
+        target_node: target_id.to_string(),
+        target_column: column.to_string(),
+        confidence,
+    };
+    let upstream = |word: &str| {
+        upstream_columns
+            .iter()
+            .find(|c| c.eq_ignore_ascii_case(word))
+    };
+
+    // `o.net_amount AS amount` and `net_amount AS amount` both select one column
+    let bare = expr.rsplit('.').next().unwrap_or(expr).trim();
+    if let Some(source) = upstream(bare) {
+        return vec![edge(source, ColumnConfidence::Aliased)];
+    }
+
+    let mut referenced: Vec<&String> = Vec::new();
+    for word in IDENTIFIER_RE.find_iter(expr) {
+        if let Some(source) = upstream(word.as_str()) {
+            if !referenced.contains(&source) {
+                referenced.push(source);
+            }
+        }
+    }
+    if referenced.is_empty() {
+        return vec![edge("", ColumnConfidence::Derived)];
+    }
+    referenced
+        .into_iter()
+        .map(|source| edge(source, ColumnConfidence::Derived))
+        .collect()
+}
+
 /// Extract SELECT items with source alias tracking from SQL
 pub fn extract_select_items(sql: &str) -> Vec<SelectItem> {
     // Reuse the column extraction logic but with richer output
     let columns = crate::parser::columns::extract_select_columns(sql);
+    let mut star_modifiers = crate::parser::columns::extract_star_modifiers(sql).into_iter();
     let mut items = Vec::new();
 
-    // Strip Jinja and find the SELECT body for detailed parsing
+    // Strip Jinja and any CREATE/INSERT wrapper for detailed parsing
     let cleaned = strip_jinja(sql);
+    let cleaned = crate::parser::columns::operative_query(&cleaned);
 
     for col in &columns {
         if col == "*" {
@@ -274,6 +510,7 @@ pub fn extract_select_items(sql: &str) -> Vec<SelectItem> {
                 source_alias: None,
                 source_column: None,
                 is_star: true,
+                star_modifiers: star_modifiers.next().unwrap_or_default(),
                 is_derived: false,
             });
             continue;
@@ -285,19 +522,21 @@ pub fn extract_select_items(sql: &str) -> Vec<SelectItem> {
                 source_alias: None,
                 source_column: None,
                 is_star: false,
+                star_modifiers: StarModifiers::default(),
                 is_derived: true,
             });
             continue;
         }
 
         // Try to find this column's source in the cleaned SQL
-        let (source_alias, source_column, is_derived) = find_column_source(&cleaned, col);
+        let (source_alias, source_column, is_derived) = find_column_source(cleaned, col);
 
         items.push(SelectItem {
             column_name: col.clone(),
             source_alias,
             source_column,
             is_star: false,
+            star_modifiers: StarModifiers::default(),
             is_derived,
         });
     }
@@ -448,6 +687,32 @@ mod tests {
         assert_eq!(items[0].column_name, "order_id");
     }
 
+    #[test]
+    fn test_extract_select_items_create_and_insert() {
+        // The schema-qualified target is not a source alias, and its column
+        // list is not a function call
+        let sql = "create table analytics.status (status text, amount int) as \
+                   (select status, amount from {{ ref('y') }} o)";
+        let items = extract_select_items(sql);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].source_alias, None);
+        assert_eq!(items[0].source_column.as_deref(), Some("status"));
+        assert!(!items[1].is_derived);
+        let refs = extract_table_refs(sql);
+        assert_eq!(refs[0].node_id, "model.y");
+        assert_eq!(refs[0].alias.as_deref(), Some("o"));
+
+        let sql = "INSERT INTO marts.amount (amount) \
+                   SELECT p.amount_cents amount FROM {{ source('raw', 'payments') }} p";
+        let items = extract_select_items(sql);
+        assert_eq!(items[0].column_name, "amount");
+        assert_eq!(items[0].source_alias.as_deref(), Some("p"));
+        assert_eq!(items[0].source_column.as_deref(), Some("amount_cents"));
+        let refs = extract_table_refs(sql);
+        assert_eq!(refs[0].node_id, "source.raw.payments");
+        assert_eq!(refs[0].alias.as_deref(), Some("p"));
+    }
+
     #[test]
     fn test_column_lineage_default() {
         let lineage = ColumnLineage::default();
@@ -483,6 +748,112 @@ mod tests {
         assert_eq!(edges.len(), 1);
     }
 
+    fn edge(src: &str, src_col: &str, tgt: &str, tgt_col: &str) -> ColumnEdge {
+        ColumnEdge {
+            source_node: src.to_string(),
+            source_column: src_col.to_string(),
+            target_node: tgt.to_string(),
+            target_column: tgt_col.to_string(),
+            confidence: ColumnConfidence::Direct,
+        }
+    }
+
+    #[test]
+    fn test_trace_column_transitive() {
+        let lineage = ColumnLineage {
+            edges: vec![
+                edge("source.raw.orders", "id", "model.stg", "order_id"),
+                edge("model.stg", "order_id", "model.mart", "order_id"),
+                edge("model.mart", "order_id", "model.report", "oid"),
+                edge("model.stg", "amount", "model.mart", "amount"),
+            ],
+        };
+
+        let chain = lineage.trace_column("model.mart", "order_id");
+        assert_eq!(chain.len(), 3);
+        assert!(chain.iter().all(|e| e.source_column != "amount"));
+
+        // Tracing from the root finds the whole downstream chain
+        let chain = lineage.trace_column("source.raw.orders", "id");
+        assert_eq!(chain.len(), 3);
+
+        assert!(lineage.trace_column("model.mart", "missing").is_empty());
+    }
+
+    #[test]
+    fn test_upstream_of() {
+        let lineage = ColumnLineage {
+            edges: vec![
+                edge("model.stg", "amount", "model.orders", "total_amount"),
+                edge("model.stg", "tax", "model.orders", "total_amount"),
+                edge("source.raw.orders", "amount", "model.stg", "amount"),
+                edge("model.orders", "total_amount", "model.report", "revenue"),
+            ],
+        };
+        let direct = lineage.upstream_of("model.orders", "total_amount");
+        let sources: Vec<&str> = direct.iter().map(|e| e.source_column.as_str()).collect();
+        assert_eq!(sources, vec!["amount", "tax"]);
+        assert!(lineage
+            .upstream_of("source.raw.orders", "amount")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_trace_upstream() {
+        let lineage = ColumnLineage {
+            edges: vec![
+                edge("model.orders", "total_amount", "model.report", "revenue"),
+                edge("source.raw.orders", "amount", "model.stg", "amount"),
+                edge("model.stg", "amount", "model.orders", "total_amount"),
+                edge("model.stg", "tax", "model.orders", "total_amount"),
+                edge("model.orders", "id", "model.report", "order_id"),
+            ],
+        };
+        let chain = lineage.trace_upstream("model.report", "revenue");
+        let hops: Vec<(&str, &str)> = chain
+            .iter()
+            .map(|e| (e.source_node.as_str(), e.source_column.as_str()))
+            .collect();
+        // Nearest first; downstream and unrelated columns are left out
+        assert_eq!(
+            hops,
+            vec![
+                ("model.orders", "total_amount"),
+                ("model.stg", "amount"),
+                ("model.stg", "tax"),
+                ("source.raw.orders", "amount"),
+            ]
+        );
+        assert!(lineage
+            .trace_upstream("source.raw.orders", "amount")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_trace_upstream_terminates_on_cycles() {
+        let lineage = ColumnLineage {
+            edges: vec![
+                edge("model.a", "x", "model.b", "x"),
+                edge("model.b", "x", "model.a", "x"),
+                edge("model.c", "", "model.a", "x"),
+            ],
+        };
+        let chain = lineage.trace_upstream("model.b", "x");
+        assert_eq!(chain.len(), 3);
+    }
+
+    #[test]
+    fn test_trace_column_stops_at_derived() {
+        let lineage = ColumnLineage {
+            edges: vec![
+                edge("model.a", "", "model.b", "total"),
+                edge("model.b", "total", "model.c", "total"),
+            ],
+        };
+        let chain = lineage.trace_column("model.c", "total");
+        assert_eq!(chain.len(), 2);
+    }
+
     #[test]
     fn test_strip_jinja() {
         let sql = "{{ config(materialized='table') }} SELECT * FROM {{ ref('orders') }}";
@@ -506,21 +877,15 @@ mod tests {
             unique_id: "model.stg_orders".into(),
             label: "stg_orders".into(),
             node_type: crate::graph::types::NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["order_id".into(), "customer_id".into(), "amount".into()],
+            ..Default::default()
         });
         graph.add_node(crate::graph::types::NodeData {
             unique_id: "model.stg_customers".into(),
             label: "stg_customers".into(),
             node_type: crate::graph::types::NodeType::Model,
             file_path: Some(sql_path.clone()),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         let lineage = resolve_column_lineage(&graph);
@@ -548,21 +913,15 @@ mod tests {
             unique_id: "model.stg_orders".into(),
             label: "stg_orders".into(),
             node_type: crate::graph::types::NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["order_id".into(), "status".into()],
+            ..Default::default()
         });
         graph.add_node(crate::graph::types::NodeData {
             unique_id: "model.mart".into(),
             label: "mart".into(),
             node_type: crate::graph::types::NodeType::Model,
             file_path: Some(sql_path),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         let lineage = resolve_column_lineage(&graph);
@@ -576,6 +935,138 @@ mod tests {
         assert!(star_edges.iter().any(|e| e.target_column == "status"));
     }
 
+    #[test]
+    fn test_extract_table_refs_qualified_and_keyword_sources() {
+        let sql = "SELECT o.id, c.name\n\
+                   FROM {{ source('prod', 'raw', 'orders') }} o\n\
+                   JOIN {{ source(source_name='raw', table_name='customers') }} AS c ON o.id = c.id";
+        let refs = extract_table_refs(sql);
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].node_id, "source.raw.orders");
+        assert_eq!(refs[0].alias.as_deref(), Some("o"));
+        assert_eq!(refs[1].node_id, "source.raw.customers");
+        assert_eq!(refs[1].alias.as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_extract_table_refs_ignores_clause_keywords_as_alias() {
+        let sql = "SELECT * FROM {{ ref('orders') }} QUALIFY rn = 1";
+        let refs = extract_table_refs(sql);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].node_id, "model.orders");
+        assert!(refs[0].alias.is_none());
+
+        let sql = "SELECT * FROM {{ ref('sales') }} PIVOT (SUM(amount) FOR m IN ('a'))";
+        assert!(extract_table_refs(sql)[0].alias.is_none());
+    }
+
+    fn lineage_for_sql(sql: &str, upstream_columns: &[&str]) -> ColumnLineage {
+        lineage_for_sql_over("stg_orders", sql, upstream_columns)
+    }
+
+    /// Column lineage of a `mart` model reading from the model `upstream`
+    fn lineage_for_sql_over(upstream: &str, sql: &str, upstream_columns: &[&str]) -> ColumnLineage {
+        let tmp = tempfile::tempdir().unwrap();
+        let sql_path = tmp.path().join("mart.sql");
+        std::fs::write(&sql_path, sql).unwrap();
+
+        let mut graph = LineageGraph::new();
+        graph.add_node(crate::graph::types::NodeData {
+            unique_id: format!("model.{}", upstream),
+            label: upstream.into(),
+            node_type: crate::graph::types::NodeType::Model,
+            columns: upstream_columns.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        });
+        graph.add_node(crate::graph::types::NodeData {
+            unique_id: "model.mart".into(),
+            label: "mart".into(),
+            node_type: crate::graph::types::NodeType::Model,
+            file_path: Some(sql_path),
+            ..Default::default()
+        });
+        resolve_column_lineage(&graph)
+    }
+
+    #[test]
+    fn test_star_except_omits_excepted_columns() {
+        let lineage = lineage_for_sql_over(
+            "x",
+            "SELECT * EXCEPT (secret) FROM {{ ref('x') }}",
+            &["id", "secret", "name"],
+        );
+        let targets: Vec<&str> = lineage
+            .edges
+            .iter()
+            .map(|e| e.target_column.as_str())
+            .collect();
+        assert_eq!(targets, vec!["id", "name"]);
+        assert!(lineage
+            .edges
+            .iter()
+            .all(|e| e.source_node == "model.x" && e.confidence == ColumnConfidence::Star));
+    }
+
+    #[test]
+    fn test_star_replace_redirects_replaced_columns() {
+        let lineage = lineage_for_sql(
+            "SELECT * EXCEPT (SSN) REPLACE (net_amount AS amount, ROUND(amount * fx, 2) AS fx, \
+             'n/a' AS status)\n\
+             FROM {{ ref('stg_orders') }}",
+            &["order_id", "amount", "net_amount", "fx", "status", "ssn"],
+        );
+        let edges: Vec<(&str, &str, ColumnConfidence)> = lineage
+            .edges
+            .iter()
+            .map(|e| {
+                (
+                    e.source_column.as_str(),
+                    e.target_column.as_str(),
+                    e.confidence,
+                )
+            })
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("order_id", "order_id", ColumnConfidence::Star),
+                ("net_amount", "amount", ColumnConfidence::Aliased),
+                ("net_amount", "net_amount", ColumnConfidence::Star),
+                ("amount", "fx", ColumnConfidence::Derived),
+                ("fx", "fx", ColumnConfidence::Derived),
+                ("", "status", ColumnConfidence::Derived),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_column_lineage_qualify() {
+        let lineage = lineage_for_sql(
+            "SELECT o.order_id, o.customer_id\n\
+             FROM {{ ref('stg_orders') }} o\n\
+             QUALIFY ROW_NUMBER() OVER (PARTITION BY o.customer_id ORDER BY o.ordered_at DESC) = 1",
+            &["order_id", "customer_id", "ordered_at"],
+        );
+        let targets: Vec<&str> = lineage
+            .edges
+            .iter()
+            .map(|e| e.target_column.as_str())
+            .collect();
+        assert_eq!(targets, vec!["order_id", "customer_id"]);
+        assert!(lineage.edges.iter().all(
+            |e| e.source_node == "model.stg_orders" && e.confidence == ColumnConfidence::Direct
+        ));
+    }
+
+    #[test]
+    fn test_resolve_column_lineage_pivot_star_not_expanded() {
+        let lineage = lineage_for_sql(
+            "SELECT * FROM {{ ref('stg_orders') }} PIVOT (SUM(amount) FOR status IN ('open', 'closed'))",
+            &["status", "amount"],
+        );
+        assert!(lineage.edges.is_empty());
+    }
+
     #[test]
     fn test_resolve_column_lineage_aliased_columns() {
         let tmp = tempfile::tempdir().unwrap();
@@ -591,21 +1082,15 @@ mod tests {
             unique_id: "model.stg_orders".into(),
             label: "stg_orders".into(),
             node_type: crate::graph::types::NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["order_id".into()],
+            ..Default::default()
         });
         graph.add_node(crate::graph::types::NodeData {
             unique_id: "model.model_a".into(),
             label: "model_a".into(),
             node_type: crate::graph::types::NodeType::Model,
             file_path: Some(sql_path),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         let lineage = resolve_column_lineage(&graph);
@@ -635,21 +1120,15 @@ mod tests {
             unique_id: "model.stg_orders".into(),
             label: "stg_orders".into(),
             node_type: crate::graph::types::NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["amount".into()],
+            ..Default::default()
         });
         graph.add_node(crate::graph::types::NodeData {
             unique_id: "model.model_b".into(),
             label: "model_b".into(),
             node_type: crate::graph::types::NodeType::Model,
             file_path: Some(sql_path),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         let lineage = resolve_column_lineage(&graph);
@@ -663,6 +1142,121 @@ mod tests {
         assert_eq!(derived[0].target_column, "total");
     }
 
+    fn column_edge(
+        source: (&str, &str),
+        target: (&str, &str),
+        confidence: ColumnConfidence,
+    ) -> ColumnEdge {
+        ColumnEdge {
+            source_node: source.0.to_string(),
+            source_column: source.1.to_string(),
+            target_node: target.0.to_string(),
+            target_column: target.1.to_string(),
+            confidence,
+        }
+    }
+
+    fn documented_node(unique_id: &str, docs: &[(&str, &str)]) -> crate::graph::types::NodeData {
+        crate::graph::types::NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type: crate::graph::types::NodeType::Model,
+            column_descriptions: docs
+                .iter()
+                .map(|(c, d)| (c.to_string(), d.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_inherited_descriptions_follow_direct_and_aliased_chains() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(documented_node(
+            "model.stg",
+            &[("order_id", "Order key"), ("amount", "Gross amount")],
+        ));
+        graph.add_node(documented_node("model.int", &[]));
+        graph.add_node(documented_node("model.mart", &[("id", "Own docs")]));
+
+        let lineage = ColumnLineage {
+            edges: vec![
+                // Listed downstream-first so the chain needs a second pass
+                column_edge(
+                    ("model.int", "order_key"),
+                    ("model.mart", "order_key"),
+                    ColumnConfidence::Direct,
+                ),
+                column_edge(
+                    ("model.stg", "ORDER_ID"),
+                    ("model.int", "order_key"),
+                    ColumnConfidence::Aliased,
+                ),
+                column_edge(
+                    ("model.stg", "order_id"),
+                    ("model.mart", "id"),
+                    ColumnConfidence::Direct,
+                ),
+                column_edge(
+                    ("model.stg", "amount"),
+                    ("model.mart", "total"),
+                    ColumnConfidence::Derived,
+                ),
+            ],
+        };
+
+        let inherited = lineage.inherited_descriptions(&graph);
+        assert_eq!(inherited.len(), 2);
+        let int = inherited.iter().find(|d| d.node == "model.int").unwrap();
+        assert_eq!(int.column, "order_key");
+        assert_eq!(int.description, "Order key");
+        assert_eq!(
+            (int.source_node.as_str(), int.source_column.as_str()),
+            ("model.stg", "order_id")
+        );
+        let mart = inherited.iter().find(|d| d.node == "model.mart").unwrap();
+        assert_eq!(mart.column, "order_key");
+        assert_eq!(mart.source_node, "model.stg");
+    }
+
+    #[test]
+    fn test_resolve_column_lineage_in_project_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("models")).unwrap();
+        std::fs::write(
+            tmp.path().join("models/mart.sql"),
+            "SELECT order_id FROM {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+
+        let mut graph = LineageGraph::new();
+        graph.add_node(documented_node("model.stg_orders", &[]));
+        let mut mart = documented_node("model.mart", &[]);
+        mart.file_path = Some("models/mart.sql".into());
+        graph.add_node(mart);
+
+        let lineage = resolve_column_lineage_in(&graph, tmp.path());
+        assert_eq!(lineage.edges.len(), 1);
+        assert_eq!(lineage.edges[0].source_node, "model.stg_orders");
+    }
+
+    #[test]
+    fn test_resolve_column_lineage_with_reader() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(documented_node("model.stg_orders", &[]));
+        let mut mart = documented_node("model.mart", &[]);
+        mart.file_path = Some("models/mart.sql".into());
+        graph.add_node(mart);
+
+        let lineage = resolve_column_lineage_with(&graph, |path| {
+            (path == Path::new("models/mart.sql"))
+                .then(|| "SELECT o.order_id AS id FROM {{ ref('stg_orders') }} o".to_string())
+        });
+        assert_eq!(lineage.edges.len(), 1);
+        assert_eq!(lineage.edges[0].target_column, "id");
+        assert_eq!(lineage.edges[0].source_column, "order_id");
+    }
+
     #[test]
     fn test_resolve_column_lineage_missing_file() {
         let mut graph = LineageGraph::new();
@@ -671,10 +1265,7 @@ mod tests {
             label: "missing".into(),
             node_type: crate::graph::types::NodeType::Model,
             file_path: Some("/nonexistent/path/model.sql".into()),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         let lineage = resolve_column_lineage(&graph);
@@ -736,31 +1327,22 @@ mod tests {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: crate::graph::types::NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["order_id".into(), "customer_id".into()],
+            ..Default::default()
         });
         graph.add_node(crate::graph::types::NodeData {
             unique_id: "model.customers".into(),
             label: "customers".into(),
             node_type: crate::graph::types::NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
             columns: vec!["id".into(), "name".into()],
+            ..Default::default()
         });
         graph.add_node(crate::graph::types::NodeData {
             unique_id: "model.joined".into(),
             label: "joined".into(),
             node_type: crate::graph::types::NodeType::Model,
             file_path: Some(sql_path),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
 
         let lineage = resolve_column_lineage(&graph);
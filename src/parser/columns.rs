@@ -8,10 +8,48 @@ static JINJA_TAG: LazyLock<Regex> =
 /// Regex to strip Jinja comments {# ... #}
 static JINJA_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{#[\s\S]*?#\}").unwrap());
 
+/// Keywords that end a SELECT list at the top level. Besides `FROM`, a SELECT may
+/// run straight into a later clause (e.g. `SELECT 1 AS x QUALIFY ...`), and the
+/// dialect-specific `QUALIFY`, `PIVOT`, `UNPIVOT` and `WINDOW` clauses count too.
+const CLAUSE_KEYWORDS: [&str; 13] = [
+    "from",
+    "where",
+    "group",
+    "having",
+    "qualify",
+    "window",
+    "order",
+    "limit",
+    "pivot",
+    "unpivot",
+    "union",
+    "intersect",
+    "minus",
+];
+
 /// Match the beginning of a SELECT clause (possibly with DISTINCT).
 static SELECT_START: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?is)\bSELECT\b\s+(?:DISTINCT\s+)?").unwrap());
 
+/// Match a statement that wraps its query: `CREATE ... AS <query>` or
+/// `INSERT ... <query>`, possibly after Jinja placeholders such as a config block
+static WRAPPING_STATEMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)^\s*(?:__jinja__\s*)*(CREATE|INSERT)\b").unwrap());
+
+/// How unquoted identifiers are cased when extracting column names.
+///
+/// Quoted identifiers (`"Order_ID"`, `` `Order_ID` ``) are case-sensitive in every
+/// warehouse, so they always keep their exact spelling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierCase {
+    /// Keep identifiers as written
+    #[default]
+    Preserve,
+    /// Lowercase unquoted identifiers
+    Lower,
+}
+
 /// Extract column names from the outermost SELECT clause of a SQL string.
 ///
 /// This is a best-effort regex-based extraction, not a full SQL parser.
@@ -20,49 +58,155 @@ static SELECT_START: LazyLock<Regex> =
 /// - `SELECT t.col1 AS alias1` -> `["alias1"]`
 /// - `SELECT col1 as alias1` -> `["alias1"]`
 /// - `SELECT *` -> `["*"]`
+/// - `SELECT * EXCEPT (a) REPLACE (b + 1 AS b)` -> `["*"]`; see
+///   [`extract_star_modifiers`] for the modifiers themselves
 /// - `SELECT DISTINCT col1, col2` -> `["col1", "col2"]`
 /// - Jinja tags are stripped before parsing
 /// - Subqueries in parentheses are skipped
 /// - Multiline SELECT clauses are handled
+/// - Quoted identifiers (`"a, weird"`, `` `col` ``) are kept as single tokens
+/// - The SELECT list ends at the first top-level `FROM` or clause keyword such as
+///   `WHERE`, `QUALIFY`, `WINDOW` or `PIVOT`
+/// - `CREATE TABLE x AS SELECT ...` and `INSERT INTO x (...) SELECT ...` are read
+///   from their query, skipping the target name and column list
 pub fn extract_select_columns(sql: &str) -> Vec<String> {
+    extract_select_columns_with_case(sql, IdentifierCase::Preserve)
+}
+
+/// Like [`extract_select_columns`], casing unquoted identifiers per `case`.
+pub fn extract_select_columns_with_case(sql: &str, case: IdentifierCase) -> Vec<String> {
+    select_list_items(sql)
+        .iter()
+        .filter_map(|item| classify_select_item(item.trim(), case))
+        .collect()
+}
+
+/// `EXCEPT` / `REPLACE` modifiers attached to a star select item, as in
+/// BigQuery's `SELECT * EXCEPT (ssn) REPLACE (UPPER(name) AS name)`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StarModifiers {
+    /// Columns dropped from the expansion
+    pub except: Vec<String>,
+    /// `(expression, column)` pairs whose expression replaces the column
+    pub replace: Vec<(String, String)>,
+}
+
+impl StarModifiers {
+    /// Whether `column` is dropped by `EXCEPT` (case-insensitive)
+    pub fn excepts(&self, column: &str) -> bool {
+        self.except.iter().any(|c| c.eq_ignore_ascii_case(column))
+    }
+
+    /// The expression `REPLACE` substitutes for `column`, if any (case-insensitive)
+    pub fn replacement(&self, column: &str) -> Option<&str> {
+        self.replace
+            .iter()
+            .find(|(_, c)| c.eq_ignore_ascii_case(column))
+            .map(|(expr, _)| expr.as_str())
+    }
+}
+
+/// Modifiers of every star item (`*`, `t.*`) in the outermost SELECT list, in
+/// order. A plain star yields empty modifiers.
+pub fn extract_star_modifiers(sql: &str) -> Vec<StarModifiers> {
+    select_list_items(sql)
+        .iter()
+        .filter_map(|item| parse_star_item(item.trim()))
+        .collect()
+}
+
+/// The raw items of the outermost SELECT list, split on top-level commas, with
+/// Jinja and line comments removed
+fn select_list_items(sql: &str) -> Vec<String> {
     // Strip Jinja comments and tags
     let cleaned = JINJA_COMMENT.replace_all(sql, "");
     let cleaned = JINJA_TAG.replace_all(&cleaned, "__jinja__");
+    let cleaned = strip_line_comments(&cleaned);
 
-    // Find the first SELECT keyword
-    let m = match SELECT_START.find(&cleaned) {
+    // Find the first SELECT keyword of the query itself
+    let cleaned = operative_query(&cleaned);
+    let m = match SELECT_START.find(cleaned) {
         Some(m) => m,
         None => return vec![],
     };
 
-    // Find the first top-level FROM after the SELECT (not inside parentheses)
+    // Find the first top-level FROM (or other clause) after the SELECT, not inside
+    // parentheses
     let after_select = &cleaned[m.end()..];
-    let select_body = match find_top_level_from(after_select) {
+    let select_body = match find_select_end(after_select) {
         Some(pos) => &after_select[..pos],
         None => return vec![],
     };
 
     // Split on commas, but not commas inside parentheses
-    let items = split_top_level_commas(select_body);
+    split_top_level_commas(select_body)
+}
 
-    items
-        .iter()
-        .filter_map(|item| classify_select_item(item.trim()))
-        .collect()
+/// The query a statement runs, for custom materializations whose SQL is
+/// `CREATE TABLE x AS SELECT ...` or `INSERT INTO x (a, b) SELECT ...` rather
+/// than a bare query: everything after the first top-level `AS` of a `CREATE`,
+/// or from the first top-level `SELECT` or `WITH` of an `INSERT`. Target names
+/// and column lists are skipped, so they are not mistaken for output columns.
+/// Other SQL is returned unchanged. Expects Jinja already replaced.
+pub(crate) fn operative_query(sql: &str) -> &str {
+    let Some(statement) = WRAPPING_STATEMENT.captures(sql) else {
+        return sql;
+    };
+    let is_create = statement[1].eq_ignore_ascii_case("create");
+    let start = statement.get(0).unwrap().end();
+    let bytes = sql.as_bytes();
+    let mut depth: i32 = 0;
+    let mut quote = None;
+    let mut i = start;
+    while i < bytes.len() {
+        if update_quote(&mut quote, bytes[i]) {
+            i += 1;
+            continue;
+        }
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' if depth > 0 => depth -= 1,
+            b if depth == 0 && b.is_ascii_alphabetic() && is_word_boundary(bytes[i - 1]) => {
+                let word_end = bytes[i..]
+                    .iter()
+                    .position(|&b| is_word_boundary(b))
+                    .map_or(bytes.len(), |p| i + p);
+                let word = &sql[i..word_end];
+                if is_create && word.eq_ignore_ascii_case("as") {
+                    return &sql[word_end..];
+                }
+                if !is_create
+                    && (word.eq_ignore_ascii_case("select") || word.eq_ignore_ascii_case("with"))
+                {
+                    return &sql[i..];
+                }
+                i = word_end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    sql
 }
 
 /// Classify a single SELECT item and return its column name, if any.
-fn classify_select_item(item: &str) -> Option<String> {
+fn classify_select_item(item: &str, case: IdentifierCase) -> Option<String> {
     if item.is_empty() {
         return None;
     }
 
     // Items starting with '(' are subqueries; check for alias after closing paren
     if item.starts_with('(') {
-        return extract_alias_after_paren(item);
+        return extract_alias_after_paren(item, case);
+    }
+
+    // `* EXCEPT (a, b)` and `* REPLACE (expr AS a)` are still a star
+    if parse_star_item(item).is_some() {
+        return Some("*".to_string());
     }
 
-    let col = extract_column_name(item);
+    let col = extract_column_name(item, case);
     if col.is_empty() {
         None
     } else {
@@ -75,40 +219,88 @@ fn is_word_boundary(b: u8) -> bool {
     !b.is_ascii_alphanumeric() && b != b'_'
 }
 
-/// Check if position `i` in string `s` starts a top-level `FROM` keyword with proper boundaries
-fn check_from_at(s: &str, bytes: &[u8], i: usize, len: usize) -> bool {
-    if i + 4 > len {
-        return false;
+/// Remove `--` line comments, which may contain unbalanced quotes. A `--`
+/// inside a string literal or quoted identifier (`'a--b'`) is kept.
+fn strip_line_comments(sql: &str) -> String {
+    let bytes = sql.as_bytes();
+    let mut out = String::with_capacity(sql.len());
+    let mut quote = None;
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !update_quote(&mut quote, bytes[i]) && bytes[i..].starts_with(b"--") {
+            out.push_str(&sql[start..i]);
+            i += bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .unwrap_or(bytes.len() - i);
+            start = i;
+            continue;
+        }
+        i += 1;
     }
-    let candidate = &s[i..i + 4];
-    if !candidate.eq_ignore_ascii_case("from") {
+    out.push_str(&sql[start..]);
+    out
+}
+
+/// Track whether a byte is inside a quoted identifier or string literal.
+/// Returns true if the byte should be treated as quoted content (including the quotes).
+fn update_quote(quote: &mut Option<u8>, b: u8) -> bool {
+    match *quote {
+        Some(q) => {
+            if b == q {
+                *quote = None;
+            }
+            true
+        }
+        None if matches!(b, b'"' | b'`' | b'\'') => {
+            *quote = Some(b);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Whether `word` is a clause keyword that can end a SELECT list (case-insensitive)
+pub(crate) fn is_clause_keyword(word: &str) -> bool {
+    CLAUSE_KEYWORDS
+        .iter()
+        .any(|kw| kw.eq_ignore_ascii_case(word))
+}
+
+/// Check if position `i` in string `s` starts a clause keyword with proper boundaries.
+/// A preceding `.` means a qualified column such as `t.order`, not a keyword.
+fn clause_keyword_at(s: &str, bytes: &[u8], i: usize) -> bool {
+    if i > 0 && (!is_word_boundary(bytes[i - 1]) || bytes[i - 1] == b'.') {
         return false;
     }
-    let before_ok = i == 0 || is_word_boundary(bytes[i - 1]);
-    let after_ok = i + 4 >= len || is_word_boundary(bytes[i + 4]);
-    before_ok && after_ok
+    let word_end = bytes[i..]
+        .iter()
+        .position(|&b| is_word_boundary(b))
+        .map_or(bytes.len(), |p| i + p);
+    is_clause_keyword(&s[i..word_end])
 }
 
-/// Find the position of the first top-level `FROM` keyword (not inside parentheses).
-/// Returns the byte offset of the start of `FROM` relative to the input string.
-fn find_top_level_from(s: &str) -> Option<usize> {
+/// Find the end of a SELECT list: the first top-level `FROM` or other clause keyword
+/// (not inside parentheses or quotes). Returns the byte offset of the keyword
+/// relative to the input string.
+fn find_select_end(s: &str) -> Option<usize> {
     let bytes = s.as_bytes();
     let len = bytes.len();
     let mut depth: i32 = 0;
+    let mut quote = None;
     let mut i = 0;
 
     while i < len {
+        if update_quote(&mut quote, bytes[i]) {
+            i += 1;
+            continue;
+        }
         match bytes[i] {
             b'(' => depth += 1,
-            b')' => {
-                if depth > 0 {
-                    depth -= 1;
-                }
-            }
-            b'f' | b'F' if depth == 0 => {
-                if check_from_at(s, bytes, i, len) {
-                    return Some(i);
-                }
+            b')' if depth > 0 => depth -= 1,
+            b if depth == 0 && b.is_ascii_alphabetic() && clause_keyword_at(s, bytes, i) => {
+                return Some(i)
             }
             _ => {}
         }
@@ -118,13 +310,23 @@ fn find_top_level_from(s: &str) -> Option<usize> {
     None
 }
 
-/// Split a string on commas that are not inside parentheses.
+/// Split a string on commas that are not inside parentheses or quotes.
 fn split_top_level_commas(s: &str) -> Vec<String> {
     let mut items = Vec::new();
     let mut current = String::new();
     let mut depth = 0;
+    let mut quote = None;
 
     for ch in s.chars() {
+        let quoted = if ch.is_ascii() {
+            update_quote(&mut quote, ch as u8)
+        } else {
+            quote.is_some()
+        };
+        if quoted {
+            current.push(ch);
+            continue;
+        }
         match ch {
             '(' => {
                 depth += 1;
@@ -151,8 +353,85 @@ fn split_top_level_commas(s: &str) -> Vec<String> {
     items
 }
 
+/// Parse a star select item (`*` or `t.*`) with any trailing `EXCEPT (...)` and
+/// `REPLACE (...)` modifiers. Returns `None` if the item is not a star.
+fn parse_star_item(item: &str) -> Option<StarModifiers> {
+    let head_end = item
+        .find(|c: char| c.is_ascii_whitespace() || c == '(')
+        .unwrap_or(item.len());
+    let head = &item[..head_end];
+    if head != "*" && !head.ends_with(".*") {
+        return None;
+    }
+
+    let mut modifiers = StarModifiers::default();
+    let mut rest = item[head_end..].trim_start();
+    while !rest.is_empty() {
+        let keyword_end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let keyword = &rest[..keyword_end];
+        let after = rest[keyword_end..].trim_start();
+        let close = after
+            .starts_with('(')
+            .then(|| matching_paren(after))
+            .flatten()?;
+        let entries = split_top_level_commas(&after[1..close]);
+        if keyword.eq_ignore_ascii_case("except") {
+            modifiers.except.extend(
+                entries
+                    .iter()
+                    .map(|e| clean_identifier(e, IdentifierCase::Preserve))
+                    .filter(|c| !c.is_empty()),
+            );
+        } else if keyword.eq_ignore_ascii_case("replace") {
+            modifiers
+                .replace
+                .extend(entries.iter().filter_map(|e| parse_replace_entry(e.trim())));
+        } else {
+            return None;
+        }
+        rest = after[close + 1..].trim_start();
+    }
+    Some(modifiers)
+}
+
+/// Split a `REPLACE` entry `expr AS column` into `(expr, column)`
+fn parse_replace_entry(entry: &str) -> Option<(String, String)> {
+    let alias = find_last_as_alias(entry)?;
+    let expr = entry[..entry.len() - alias.len()].trim_end();
+    // Drop the trailing `AS` keyword
+    let expr = expr[..expr.len() - 2].trim();
+    Some((
+        expr.to_string(),
+        clean_identifier(&alias, IdentifierCase::Preserve),
+    ))
+}
+
+/// Byte offset of the `)` closing the `(` at the start of `s`, skipping quotes
+fn matching_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, b) in s.bytes().enumerate() {
+        if update_quote(&mut quote, b) {
+            continue;
+        }
+        match b {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Extract the alias after a closing parenthesis, e.g., `(SELECT ...) AS alias`
-fn extract_alias_after_paren(item: &str) -> Option<String> {
+fn extract_alias_after_paren(item: &str, case: IdentifierCase) -> Option<String> {
     // Find the last closing paren
     let close = item.rfind(')')?;
     let after = item[close + 1..].trim();
@@ -171,7 +450,7 @@ fn extract_alias_after_paren(item: &str) -> Option<String> {
     if after.is_empty() {
         None
     } else {
-        Some(clean_identifier(after))
+        Some(clean_identifier(after, case))
     }
 }
 
@@ -181,24 +460,56 @@ fn extract_alias_after_paren(item: &str) -> Option<String> {
 /// 1. If `AS alias` is present, return the alias.
 /// 2. If `table.column`, return column.
 /// 3. Otherwise return the token itself (e.g., `*`, `col1`).
-fn extract_column_name(item: &str) -> String {
+fn extract_column_name(item: &str, case: IdentifierCase) -> String {
     let item = item.trim();
 
     // Check for AS alias (case-insensitive) - look for last " AS " or " as "
     // We search from the end to handle expressions like `CAST(x AS int) AS col`
     if let Some(alias) = find_last_as_alias(item) {
-        return clean_identifier(&alias);
+        return clean_identifier(&alias, case);
     }
 
     // No alias; take the last token (handles `table.col` and bare `col`)
-    let last_token = item.split_whitespace().last().unwrap_or(item);
+    let last_token = last_unquoted_token(item);
 
     // Handle table.column
-    if let Some(pos) = last_token.rfind('.') {
-        return clean_identifier(&last_token[pos + 1..]);
+    if let Some(pos) = last_unquoted_dot(last_token) {
+        return clean_identifier(&last_token[pos + 1..], case);
     }
 
-    clean_identifier(last_token)
+    clean_identifier(last_token, case)
+}
+
+/// The last whitespace-separated token, treating quoted runs as part of a token.
+fn last_unquoted_token(item: &str) -> &str {
+    let mut quote = None;
+    let mut start = 0;
+    for (i, b) in item.bytes().enumerate() {
+        if update_quote(&mut quote, b) {
+            continue;
+        }
+        if b.is_ascii_whitespace() {
+            start = i + 1;
+        }
+    }
+    let token = &item[start..];
+    if token.is_empty() {
+        item
+    } else {
+        token
+    }
+}
+
+/// Position of the last `.` outside quotes, e.g. the separator in `t."Order.ID"`.
+fn last_unquoted_dot(token: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut last = None;
+    for (i, b) in token.bytes().enumerate() {
+        if !update_quote(&mut quote, b) && b == b'.' {
+            last = Some(i);
+        }
+    }
+    last
 }
 
 /// Check if position `i` (a whitespace char) starts a top-level ` AS ` token.
@@ -216,22 +527,23 @@ fn is_as_keyword_at(item: &str, bytes: &[u8], i: usize, len: usize) -> Option<us
     }
 }
 
-/// Find the alias from the last ` AS ` keyword that is not inside parentheses.
+/// Find the alias from the last ` AS ` keyword that is not inside parentheses or quotes.
 fn find_last_as_alias(item: &str) -> Option<String> {
     let bytes = item.as_bytes();
     let len = bytes.len();
     let mut depth = 0;
+    let mut quote = None;
     let mut last_as_pos: Option<usize> = None;
 
     let mut i = 0;
     while i < len {
+        if update_quote(&mut quote, bytes[i]) {
+            i += 1;
+            continue;
+        }
         match bytes[i] {
             b'(' => depth += 1,
-            b')' => {
-                if depth > 0 {
-                    depth -= 1;
-                }
-            }
+            b')' if depth > 0 => depth -= 1,
             b' ' | b'\t' | b'\n' | b'\r' if depth == 0 => {
                 if let Some(pos) = is_as_keyword_at(item, bytes, i, len) {
                     last_as_pos = Some(pos);
@@ -246,11 +558,19 @@ fn find_last_as_alias(item: &str) -> Option<String> {
 }
 
 /// Clean an identifier: trim whitespace and remove surrounding backticks or quotes.
-fn clean_identifier(s: &str) -> String {
+/// Unquoted identifiers are cased per `case`; quoted ones keep their spelling.
+fn clean_identifier(s: &str, case: IdentifierCase) -> String {
     let s = s.trim();
-    let s = s.trim_matches('`');
-    let s = s.trim_matches('"');
-    s.to_string()
+    let quoted = s.len() >= 2
+        && ((s.starts_with('"') && s.ends_with('"')) || (s.starts_with('`') && s.ends_with('`')));
+    if quoted {
+        return s[1..s.len() - 1].to_string();
+    }
+    let s = s.trim_matches('`').trim_matches('"');
+    match case {
+        IdentifierCase::Preserve => s.to_string(),
+        IdentifierCase::Lower => s.to_lowercase(),
+    }
 }
 
 #[cfg(test)]
@@ -285,6 +605,39 @@ mod tests {
         assert_eq!(cols, vec!["*"]);
     }
 
+    #[test]
+    fn test_select_star_with_modifiers() {
+        let sql = "SELECT * EXCEPT (secret, dob), id FROM {{ ref('x') }}";
+        assert_eq!(extract_select_columns(sql), vec!["*", "id"]);
+
+        let sql = "SELECT t.* REPLACE (UPPER(name) AS name) FROM t";
+        assert_eq!(extract_select_columns(sql), vec!["*"]);
+    }
+
+    #[test]
+    fn test_extract_star_modifiers() {
+        let sql = "SELECT * except (secret, `dob`) REPLACE (ROUND(amount, 2) AS amount, \
+                   net AS \"Total\") FROM {{ ref('x') }}";
+        let modifiers = extract_star_modifiers(sql);
+        assert_eq!(modifiers.len(), 1);
+        assert_eq!(modifiers[0].except, vec!["secret", "dob"]);
+        assert_eq!(
+            modifiers[0].replace,
+            vec![
+                ("ROUND(amount, 2)".to_string(), "amount".to_string()),
+                ("net".to_string(), "Total".to_string()),
+            ]
+        );
+        assert!(modifiers[0].excepts("SECRET"));
+        assert_eq!(modifiers[0].replacement("total"), Some("net"));
+
+        assert_eq!(
+            extract_star_modifiers("SELECT id, * FROM t"),
+            vec![StarModifiers::default()]
+        );
+        assert!(extract_star_modifiers("SELECT id FROM t").is_empty());
+    }
+
     #[test]
     fn test_select_distinct() {
         let sql = "SELECT DISTINCT col1, col2 FROM my_table";
@@ -345,6 +698,52 @@ mod tests {
         assert!(!cols.is_empty());
     }
 
+    #[test]
+    fn test_create_table_as_select() {
+        let sql = r#"
+            {{ config(materialized='create_table_as') }}
+            CREATE OR REPLACE TABLE {{ this }} (order_id INT, status TEXT) AS (
+                SELECT o.order_id, o.status FROM {{ ref('stg_orders') }} o
+            )
+        "#;
+        assert_eq!(extract_select_columns(sql), vec!["order_id", "status"]);
+
+        // A quoted target name is not where the query starts
+        let sql = r#"create table "select orders" as select * from {{ ref('stg_orders') }}"#;
+        assert_eq!(extract_select_columns(sql), vec!["*"]);
+    }
+
+    #[test]
+    fn test_insert_into_select() {
+        let sql = r#"
+            INSERT INTO {{ this }} (order_id, selected_at)
+            SELECT order_id, current_timestamp AS selected_at
+            FROM {{ ref('stg_orders') }}
+        "#;
+        assert_eq!(extract_select_columns(sql), vec!["order_id", "selected_at"]);
+
+        let sql = r#"INSERT INTO "select from" (id, dt) SELECT id, dt FROM staging.orders"#;
+        assert_eq!(extract_select_columns(sql), vec!["id", "dt"]);
+    }
+
+    #[test]
+    fn test_operative_query() {
+        assert_eq!(operative_query("SELECT a FROM t"), "SELECT a FROM t");
+        assert_eq!(
+            operative_query("create view v (a, b) as select a, b from t"),
+            " select a, b from t"
+        );
+        assert_eq!(
+            operative_query("__jinja__\nINSERT INTO t (a) WITH c AS (SELECT 1) SELECT a FROM c"),
+            "WITH c AS (SELECT 1) SELECT a FROM c"
+        );
+        // A CREATE that never reaches its query is left alone
+        assert_eq!(
+            operative_query("CREATE TABLE t (a INT)"),
+            "CREATE TABLE t (a INT)"
+        );
+    }
+
     #[test]
     fn test_select_with_function() {
         let sql = "SELECT COUNT(*) AS total, SUM(amount) AS total_amount FROM orders";
@@ -427,28 +826,116 @@ mod tests {
     #[test]
     fn test_extract_alias_after_paren_no_alias() {
         // Subquery with no alias after the closing paren
-        let result = extract_alias_after_paren("(SELECT 1)");
+        let result = extract_alias_after_paren("(SELECT 1)", IdentifierCase::Preserve);
         assert!(result.is_none());
     }
 
     #[test]
     fn test_extract_alias_after_paren_bare_alias() {
         // Subquery with bare alias (no AS keyword)
-        let result = extract_alias_after_paren("(SELECT 1) my_alias");
+        let result = extract_alias_after_paren("(SELECT 1) my_alias", IdentifierCase::Preserve);
         assert_eq!(result, Some("my_alias".to_string()));
     }
 
     #[test]
     fn test_extract_alias_after_paren_as_alias() {
         // Subquery with AS alias
-        let result = extract_alias_after_paren("(SELECT 1) AS my_alias");
+        let result = extract_alias_after_paren("(SELECT 1) AS my_alias", IdentifierCase::Preserve);
         assert_eq!(result, Some("my_alias".to_string()));
     }
 
     #[test]
     fn test_extract_alias_after_paren_no_paren() {
         // No closing paren at all
-        let result = extract_alias_after_paren("SELECT 1");
+        let result = extract_alias_after_paren("SELECT 1", IdentifierCase::Preserve);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_quoted_identifier_with_comma() {
+        let sql = r#"SELECT "a, weird", b FROM t"#;
+        let cols = extract_select_columns(sql);
+        assert_eq!(cols, vec!["a, weird", "b"]);
+    }
+
+    #[test]
+    fn test_quoted_identifier_with_spaces_and_keywords() {
+        let sql = r#"SELECT "order from" , t."Order.ID", `my col`, x AS "Alias AS Name" FROM t"#;
+        let cols = extract_select_columns(sql);
+        assert_eq!(
+            cols,
+            vec!["order from", "Order.ID", "my col", "Alias AS Name"]
+        );
+    }
+
+    #[test]
+    fn test_string_literal_with_comma() {
+        let sql = "SELECT 'a, b' AS label, c FROM t";
+        let cols = extract_select_columns(sql);
+        assert_eq!(cols, vec!["label", "c"]);
+    }
+
+    #[test]
+    fn test_line_comment_with_apostrophe() {
+        let sql = "SELECT\n  a, -- don't split here\n  b\nFROM t";
+        let cols = extract_select_columns(sql);
+        assert_eq!(cols, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_double_dash_inside_string_literal() {
+        let sql = "SELECT 'a--b' AS x, \"c--d\" AS y, z -- trailing 'comment\nFROM t";
+        let cols = extract_select_columns(sql);
+        assert_eq!(cols, vec!["x", "y", "z"]);
+    }
+
+    #[test]
+    fn test_identifier_case_lower() {
+        let sql = r#"SELECT Customer_ID, "Order_ID", t.Status AS Order_Status FROM t"#;
+        let cols = extract_select_columns_with_case(sql, IdentifierCase::Lower);
+        assert_eq!(cols, vec!["customer_id", "Order_ID", "order_status"]);
+
+        let cols = extract_select_columns_with_case(sql, IdentifierCase::Preserve);
+        assert_eq!(cols, vec!["Customer_ID", "Order_ID", "Order_Status"]);
+    }
+
+    #[test]
+    fn test_qualify_row_number() {
+        let sql = r#"
+            select
+                order_id,
+                customer_id,
+                row_number() over (partition by customer_id order by ordered_at desc) as rn
+            from {{ ref('stg_orders') }}
+            qualify row_number() over (partition by customer_id order by ordered_at desc) = 1
+        "#;
+        let cols = extract_select_columns(sql);
+        assert_eq!(cols, vec!["order_id", "customer_id", "rn"]);
+    }
+
+    #[test]
+    fn test_select_list_ends_at_clause_without_from() {
+        let sql = "SELECT 1 AS id, 'a' AS name QUALIFY id = 1";
+        assert_eq!(extract_select_columns(sql), vec!["id", "name"]);
+
+        let sql = "SELECT a, b WINDOW w AS (PARTITION BY a)";
+        assert_eq!(extract_select_columns(sql), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_pivot_after_from() {
+        let sql =
+            "SELECT * FROM monthly_sales PIVOT (SUM(amount) FOR month IN ('JAN', 'FEB')) AS p";
+        assert_eq!(extract_select_columns(sql), vec!["*"]);
+    }
+
+    #[test]
+    fn test_clause_keywords_need_word_boundaries() {
+        // Columns that merely start with or are qualified by a keyword stay in the list
+        let sql = "SELECT order_id, t.order, windowed, qualify_flag FROM t";
+        assert_eq!(
+            extract_select_columns(sql),
+            vec!["order_id", "order", "windowed", "qualify_flag"]
+        );
+    }
 }
@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 use super::project::ResolvedPaths;
+use crate::graph::groups::glob_match;
 
 /// All discovered files in the dbt project, categorized by type
 #[derive(Debug, Default)]
@@ -14,8 +15,164 @@ pub struct DiscoveredFiles {
     pub yaml_files: Vec<PathBuf>,
 }
 
-/// Walk all configured paths and collect SQL/YAML files
+/// Extensions of seed files when none are configured
+pub const DEFAULT_SEED_FILE_TYPES: [&str; 1] = ["csv"];
+
+/// Extra files to pick up by glob (`--model-glob` and friends), on top of the
+/// configured paths. Patterns are relative to the project directory; `*` and `?`
+/// stay within one path segment and `**` spans any number of them.
+#[derive(Debug, Clone)]
+pub struct DiscoveryGlobs {
+    pub models: Vec<String>,
+    pub snapshots: Vec<String>,
+    pub tests: Vec<String>,
+    /// Extensions of the files under the seed paths that are seeds
+    /// (`--seed-file-types`), lowercase and without the dot
+    pub seed_file_types: Vec<String>,
+}
+
+impl Default for DiscoveryGlobs {
+    fn default() -> Self {
+        Self {
+            models: Vec::new(),
+            snapshots: Vec::new(),
+            tests: Vec::new(),
+            seed_file_types: DEFAULT_SEED_FILE_TYPES.map(String::from).to_vec(),
+        }
+    }
+}
+
+/// Parse a comma-separated list of seed file extensions (`csv,json,.parquet`),
+/// lowercased and without leading dots
+pub fn parse_seed_file_types(input: &str) -> Vec<String> {
+    let mut types: Vec<String> = Vec::new();
+    for extension in input.split(',') {
+        let extension = extension.trim().trim_start_matches('.').to_lowercase();
+        if !extension.is_empty() && !types.contains(&extension) {
+            types.push(extension);
+        }
+    }
+    types
+}
+
+/// [`discover_files`], plus the files matching `globs` under `project_dir`.
+/// A SQL file matched by a glob is classified by that glob's kind, even if it
+/// also sits in a configured directory of another kind; matched YAML files are
+/// read as schema files.
+pub fn discover_files_with_globs(
+    paths: &ResolvedPaths,
+    project_dir: &Path,
+    globs: &DiscoveryGlobs,
+) -> Result<DiscoveredFiles> {
+    let mut discovered = discover(paths, &globs.seed_file_types);
+    let kinds = [
+        (&globs.models, GlobKind::Model),
+        (&globs.snapshots, GlobKind::Snapshot),
+        (&globs.tests, GlobKind::Test),
+    ];
+    for (patterns, kind) in kinds {
+        for pattern in patterns {
+            for path in glob_files(project_dir, pattern) {
+                discovered.add_globbed(path, kind);
+            }
+        }
+    }
+    Ok(discovered)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum GlobKind {
+    Model,
+    Snapshot,
+    Test,
+}
+
+impl DiscoveredFiles {
+    /// File the path under `kind`, taking it out of any other SQL bucket
+    fn add_globbed(&mut self, path: PathBuf, kind: GlobKind) {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("sql") => {
+                for bucket in [
+                    &mut self.model_sql_files,
+                    &mut self.snapshot_sql_files,
+                    &mut self.test_sql_files,
+                ] {
+                    bucket.retain(|p| p != &path);
+                }
+                match kind {
+                    GlobKind::Model => self.model_sql_files.push(path),
+                    GlobKind::Snapshot => self.snapshot_sql_files.push(path),
+                    GlobKind::Test => self.test_sql_files.push(path),
+                }
+            }
+            Some("yml" | "yaml") if !self.yaml_files.contains(&path) => self.yaml_files.push(path),
+            _ => {}
+        }
+    }
+}
+
+/// Files under `project_dir` whose relative path matches `pattern`. Only the
+/// directory named by the pattern's literal prefix is walked.
+fn glob_files(project_dir: &Path, pattern: &str) -> Vec<PathBuf> {
+    let segments: Vec<&str> = pattern
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != ".")
+        .collect();
+    let literal = segments
+        .iter()
+        .take_while(|s| !s.contains(['*', '?']))
+        .count()
+        // The last segment names files, even when it has no wildcard
+        .min(segments.len().saturating_sub(1));
+    let root = segments[..literal]
+        .iter()
+        .fold(project_dir.to_path_buf(), |dir, s| dir.join(s));
+    if !root.is_dir() {
+        return Vec::new();
+    }
+
+    let mut files: Vec<PathBuf> = WalkDir::new(&root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let Ok(relative) = e.path().strip_prefix(&root) else {
+                return false;
+            };
+            let parts: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            path_glob_match(&segments[literal..], &parts)
+        })
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Match path segments against pattern segments, where `**` matches any number
+/// of whole segments and other segments are matched with [`glob_match`]
+fn path_glob_match(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| path_glob_match(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((first, path_rest)) => {
+                glob_match(segment, first) && path_glob_match(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Walk all configured paths and collect SQL/YAML files, and CSV seeds
 pub fn discover_files(paths: &ResolvedPaths) -> Result<DiscoveredFiles> {
+    Ok(discover(paths, &DEFAULT_SEED_FILE_TYPES.map(String::from)))
+}
+
+/// [`discover_files`] with seeds being the files with one of `seed_file_types`
+fn discover(paths: &ResolvedPaths, seed_file_types: &[String]) -> DiscoveredFiles {
     let mut discovered = DiscoveredFiles::default();
 
     // Models
@@ -28,10 +185,11 @@ pub fn discover_files(paths: &ResolvedPaths) -> Result<DiscoveredFiles> {
     // Seeds
     for dir in &paths.seed_paths {
         let (_, yaml) = walk_directory(dir);
-        // Seeds are CSV files typically, but we collect their YAML schema files
+        // Seeds are data files, but we collect their YAML schema files
         discovered.yaml_files.extend(yaml);
-        // Also look for .csv files
-        discovered.seed_files.extend(walk_csv_files(dir));
+        discovered
+            .seed_files
+            .extend(walk_seed_files(dir, seed_file_types));
     }
 
     // Snapshots
@@ -48,7 +206,45 @@ pub fn discover_files(paths: &ResolvedPaths) -> Result<DiscoveredFiles> {
         discovered.yaml_files.extend(yaml);
     }
 
-    Ok(discovered)
+    discovered
+}
+
+/// Sort already-known file paths into the same buckets `discover_files` fills,
+/// without touching the filesystem. Files outside the configured directories,
+/// or with other extensions, are dropped.
+pub fn classify_files<'a>(
+    paths: &ResolvedPaths,
+    files: impl IntoIterator<Item = &'a Path>,
+) -> DiscoveredFiles {
+    let under = |dirs: &[PathBuf], path: &Path| dirs.iter().any(|d| path.starts_with(d));
+    let mut discovered = DiscoveredFiles::default();
+
+    for path in files {
+        let extension = path.extension().and_then(|e| e.to_str());
+        let bucket = match extension {
+            Some("yml" | "yaml")
+                if under(&paths.model_paths, path)
+                    || under(&paths.seed_paths, path)
+                    || under(&paths.snapshot_paths, path)
+                    || under(&paths.test_paths, path) =>
+            {
+                &mut discovered.yaml_files
+            }
+            Some("sql") if under(&paths.model_paths, path) => &mut discovered.model_sql_files,
+            Some("sql") if under(&paths.snapshot_paths, path) => &mut discovered.snapshot_sql_files,
+            Some("sql") if under(&paths.test_paths, path) => &mut discovered.test_sql_files,
+            Some(extension)
+                if under(&paths.seed_paths, path)
+                    && DEFAULT_SEED_FILE_TYPES.contains(&extension) =>
+            {
+                &mut discovered.seed_files
+            }
+            _ => continue,
+        };
+        bucket.push(path.to_path_buf());
+    }
+
+    discovered
 }
 
 /// Walk a directory and return (sql_files, yaml_files)
@@ -76,8 +272,9 @@ fn walk_directory(dir: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
     (sql_files, yaml_files)
 }
 
-/// Walk a directory and return CSV files (for seeds)
-fn walk_csv_files(dir: &Path) -> Vec<PathBuf> {
+/// Walk a directory and return the seed files: those with one of `extensions`,
+/// compared case-insensitively
+fn walk_seed_files(dir: &Path, extensions: &[String]) -> Vec<PathBuf> {
     if !dir.exists() {
         return Vec::new();
     }
@@ -86,7 +283,12 @@ fn walk_csv_files(dir: &Path) -> Vec<PathBuf> {
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
-        .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("csv"))
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.iter().any(|s| s.eq_ignore_ascii_case(ext)))
+        })
         .map(|e| e.path().to_path_buf())
         .collect()
 }
@@ -96,6 +298,36 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn test_classify_files() {
+        let paths = ResolvedPaths {
+            model_paths: vec![PathBuf::from("models")],
+            seed_paths: vec![PathBuf::from("seeds")],
+            snapshot_paths: vec![PathBuf::from("snapshots")],
+            test_paths: vec![PathBuf::from("tests")],
+        };
+        let files = [
+            "models/staging/stg.sql",
+            "models/schema.yml",
+            "seeds/countries.csv",
+            "seeds/seeds.yml",
+            "snapshots/snap.sql",
+            "tests/assert.sql",
+            "macros/helper.sql",
+            "models/notes.md",
+        ];
+        let discovered = classify_files(&paths, files.iter().map(Path::new));
+
+        assert_eq!(
+            discovered.model_sql_files,
+            vec![PathBuf::from("models/staging/stg.sql")]
+        );
+        assert_eq!(discovered.yaml_files.len(), 2);
+        assert_eq!(discovered.seed_files.len(), 1);
+        assert_eq!(discovered.snapshot_sql_files.len(), 1);
+        assert_eq!(discovered.test_sql_files.len(), 1);
+    }
+
     #[test]
     fn test_walk_nonexistent_directory() {
         let (sql, yaml) = walk_directory(Path::new("/nonexistent/path"));
@@ -118,25 +350,46 @@ mod tests {
     }
 
     #[test]
-    fn test_walk_csv_files() {
+    fn test_walk_seed_files() {
         let tmp = tempfile::tempdir().unwrap();
         let seeds_dir = tmp.path().join("seeds");
         fs::create_dir_all(&seeds_dir).unwrap();
         fs::write(seeds_dir.join("countries.csv"), "id,name\n1,US").unwrap();
         fs::write(seeds_dir.join("schema.yml"), "version: 2").unwrap();
         fs::write(seeds_dir.join("notes.txt"), "notes").unwrap();
+        fs::write(seeds_dir.join("rates.JSON"), "[]").unwrap();
+        fs::write(seeds_dir.join("events.parquet"), "PAR1").unwrap();
 
-        let csv_files = walk_csv_files(&seeds_dir);
+        let csv_files = walk_seed_files(&seeds_dir, &["csv".into()]);
         assert_eq!(csv_files.len(), 1);
         assert!(csv_files[0].ends_with("countries.csv"));
+
+        let mut files = walk_seed_files(&seeds_dir, &parse_seed_file_types("json,parquet"));
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                seeds_dir.join("events.parquet"),
+                seeds_dir.join("rates.JSON")
+            ]
+        );
     }
 
     #[test]
-    fn test_walk_csv_files_nonexistent() {
-        let csv_files = walk_csv_files(Path::new("/nonexistent/path"));
+    fn test_walk_seed_files_nonexistent() {
+        let csv_files = walk_seed_files(Path::new("/nonexistent/path"), &["csv".into()]);
         assert!(csv_files.is_empty());
     }
 
+    #[test]
+    fn test_parse_seed_file_types() {
+        assert_eq!(
+            parse_seed_file_types("csv, .JSON,,parquet,csv"),
+            vec!["csv", "json", "parquet"]
+        );
+        assert!(parse_seed_file_types(" , ").is_empty());
+    }
+
     #[test]
     fn test_walk_directory_nested() {
         let tmp = tempfile::tempdir().unwrap();
@@ -209,4 +462,78 @@ mod tests {
         assert!(discovered.test_sql_files.is_empty());
         assert!(discovered.yaml_files.is_empty());
     }
+
+    #[test]
+    fn test_path_glob_match() {
+        let parts = |path: &str| path.split('/').map(String::from).collect::<Vec<_>>();
+        let pattern = ["transform", "**", "*.sql"];
+        assert!(path_glob_match(&pattern, &parts("transform/orders.sql")));
+        assert!(path_glob_match(
+            &pattern,
+            &parts("transform/a/b/orders.sql")
+        ));
+        assert!(!path_glob_match(&pattern, &parts("transform/a/orders.yml")));
+        assert!(!path_glob_match(&pattern, &parts("other/orders.sql")));
+        // A single `*` stays within one directory
+        assert!(!path_glob_match(&["*.sql"], &parts("a/orders.sql")));
+        assert!(path_glob_match(&["stg_?.sql"], &parts("stg_a.sql")));
+    }
+
+    #[test]
+    fn test_discover_files_with_globs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project_dir = tmp.path();
+        let write = |path: &str| {
+            let path = project_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "SELECT 1").unwrap();
+        };
+        write("models/orders.sql");
+        write("models/legacy/snap_orders.sql");
+        write("transform/core/deep/customers.sql");
+        write("transform/core/schema.yml");
+        write("transform/notes.md");
+        write("checks/assert_positive.sql");
+
+        let paths = ResolvedPaths {
+            model_paths: vec![project_dir.join("models")],
+            seed_paths: vec![],
+            snapshot_paths: vec![],
+            test_paths: vec![],
+        };
+        let globs = DiscoveryGlobs {
+            models: vec!["transform/**/*".into()],
+            snapshots: vec!["models/legacy/snap_*.sql".into()],
+            tests: vec!["./checks/*.sql".into(), "missing/**/*.sql".into()],
+            ..Default::default()
+        };
+        let discovered = discover_files_with_globs(&paths, project_dir, &globs).unwrap();
+
+        let mut models = discovered.model_sql_files.clone();
+        models.sort();
+        assert_eq!(
+            models,
+            vec![
+                project_dir.join("models/orders.sql"),
+                project_dir.join("transform/core/deep/customers.sql"),
+            ]
+        );
+        // Moved out of the models bucket by the snapshot glob
+        assert_eq!(
+            discovered.snapshot_sql_files,
+            vec![project_dir.join("models/legacy/snap_orders.sql")]
+        );
+        assert_eq!(
+            discovered.test_sql_files,
+            vec![project_dir.join("checks/assert_positive.sql")]
+        );
+        assert_eq!(
+            discovered.yaml_files,
+            vec![project_dir.join("transform/core/schema.yml")]
+        );
+
+        // Without globs this is plain discovery
+        let plain = discover_files_with_globs(&paths, project_dir, &DiscoveryGlobs::default());
+        assert_eq!(plain.unwrap().model_sql_files.len(), 2);
+    }
 }
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use petgraph::stable_graph::NodeIndex;
@@ -7,18 +7,75 @@ use serde::Deserialize;
 
 use crate::graph::types::*;
 
-/// Top-level manifest.json structure
+/// Top-level manifest.json structure.
+///
+/// Entries are deserialized one at a time, so a node, source or exposure with
+/// an unexpected shape is dropped and recorded in `skipped` instead of failing
+/// the whole manifest.
 #[derive(Debug, Deserialize)]
+#[serde(from = "RawManifest")]
 pub struct Manifest {
     /// Nodes keyed by unique_id (models, seeds, snapshots, tests, analyses)
-    #[serde(default)]
     pub nodes: HashMap<String, ManifestNode>,
     /// Sources keyed by unique_id
-    #[serde(default)]
     pub sources: HashMap<String, ManifestSource>,
     /// Exposures keyed by unique_id
-    #[serde(default)]
     pub exposures: HashMap<String, ManifestExposure>,
+    /// Entries that failed to deserialize, as `<unique_id>: <error>`
+    pub skipped: Vec<String>,
+}
+
+/// manifest.json with its entries still untyped
+#[derive(Deserialize)]
+struct RawManifest {
+    #[serde(default)]
+    nodes: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    sources: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    exposures: HashMap<String, serde_json::Value>,
+}
+
+impl From<RawManifest> for Manifest {
+    fn from(raw: RawManifest) -> Self {
+        let mut skipped = Vec::new();
+        let nodes = parse_entries(raw.nodes, &mut skipped);
+        let sources = parse_entries(raw.sources, &mut skipped);
+        let exposures = parse_entries(raw.exposures, &mut skipped);
+        skipped.sort();
+        Manifest {
+            nodes,
+            sources,
+            exposures,
+            skipped,
+        }
+    }
+}
+
+/// Deserialize each entry on its own, collecting the ones that fail in `skipped`
+fn parse_entries<T: serde::de::DeserializeOwned>(
+    entries: HashMap<String, serde_json::Value>,
+    skipped: &mut Vec<String>,
+) -> HashMap<String, T> {
+    entries
+        .into_iter()
+        .filter_map(|(key, value)| match serde_json::from_value(value) {
+            Ok(entry) => Some((key, entry)),
+            Err(err) => {
+                skipped.push(format!("{}: {}", key, err));
+                None
+            }
+        })
+        .collect()
+}
+
+impl Manifest {
+    /// Print a warning for each skipped entry; `origin` names the manifest
+    pub fn warn_skipped(&self, origin: &str) {
+        for entry in &self.skipped {
+            eprintln!("Warning: {}: skipped {}", origin, entry);
+        }
+    }
 }
 
 /// A node entry in the manifest (model, seed, snapshot, test, analysis)
@@ -33,6 +90,18 @@ pub struct ManifestNode {
     pub config: ManifestConfig,
     pub description: Option<String>,
     pub path: Option<String>,
+    /// Project-relative path; `path` is relative to the resource's own directory in real manifests
+    pub original_file_path: Option<String>,
+    /// Model version (number or string) for dbt versioned models
+    #[serde(default)]
+    pub version: Option<serde_json::Value>,
+    #[serde(default)]
+    pub columns: HashMap<String, ManifestColumn>,
+    /// Present on generic tests (`not_null`, `unique`, ...), absent on singular ones
+    #[serde(default)]
+    pub test_metadata: Option<serde_json::Value>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, serde_json::Value>,
 }
 
 /// A source entry in the manifest
@@ -45,6 +114,28 @@ pub struct ManifestSource {
     pub resource_type: String,
     pub description: Option<String>,
     pub path: Option<String>,
+    pub original_file_path: Option<String>,
+    #[serde(default)]
+    pub columns: HashMap<String, ManifestColumn>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub schema: Option<String>,
+}
+
+/// A documented column of a node or source
+#[derive(Debug, Deserialize)]
+pub struct ManifestColumn {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Non-empty column descriptions keyed by column name
+fn column_descriptions(columns: &HashMap<String, ManifestColumn>) -> BTreeMap<String, String> {
+    columns
+        .values()
+        .filter_map(|c| Some((c.name.clone(), non_empty_string(&c.description)?)))
+        .collect()
 }
 
 /// An exposure entry in the manifest
@@ -68,8 +159,37 @@ pub struct DependsOn {
 #[derive(Debug, Default, Deserialize)]
 pub struct ManifestConfig {
     pub materialized: Option<String>,
+    /// Custom schema; the node's resolved `schema` also carries the target's prefix
+    #[serde(default)]
+    pub schema: Option<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, serde_json::Value>,
+    /// Snapshot strategy, `timestamp` or `check`
+    #[serde(default)]
+    pub strategy: Option<String>,
+    /// A column name or a list of them
+    #[serde(default)]
+    pub unique_key: serde_json::Value,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// A list of column names, or `"all"`
+    #[serde(default)]
+    pub check_cols: serde_json::Value,
+}
+
+impl ManifestConfig {
+    /// Snapshot settings, `None` when none are set
+    fn snapshot_config(&self) -> Option<SnapshotConfig> {
+        SnapshotConfig {
+            strategy: self.strategy.clone(),
+            unique_key: string_list(&self.unique_key),
+            updated_at: self.updated_at.clone(),
+            check_cols: string_list(&self.check_cols),
+        }
+        .non_empty()
+    }
 }
 
 /// Map a manifest resource_type string to our NodeType enum
@@ -111,8 +231,118 @@ fn simplify_unique_id(unique_id: &str, resource_type: &str) -> String {
     }
 }
 
+/// Whether an id segment is a model version (`v2`)
+fn is_version_segment(segment: &str) -> bool {
+    segment
+        .strip_prefix('v')
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Ids a `depends_on` entry may be indexed under, most specific first: the id
+/// as written; a short id (`model.orders`) qualified with `package`, the
+/// dependent's package; and the short form of a qualified id, as nodes are keyed
+/// when ids are simplified. Versioned ids keep their version.
+fn dependency_id_forms(dep_id: &str, package: Option<&str>) -> Vec<String> {
+    let parts: Vec<&str> = dep_id.split('.').collect();
+    let resource_type = parts[0];
+    let is_short = match resource_type {
+        "source" => parts.len() == 3,
+        _ => parts.len() == 2 || (parts.len() == 3 && is_version_segment(parts[2])),
+    };
+
+    let mut forms = vec![dep_id.to_string()];
+    if let (true, Some(package)) = (is_short, package) {
+        forms.push(format!(
+            "{}.{}.{}",
+            resource_type,
+            package,
+            parts[1..].join(".")
+        ));
+    }
+    if !is_short {
+        let n = parts.len();
+        let simplified = if resource_type != "source" && n >= 4 && is_version_segment(parts[n - 1])
+        {
+            format!("{}.{}.{}", resource_type, parts[n - 2], parts[n - 1])
+        } else {
+            simplify_unique_id(dep_id, resource_type)
+        };
+        forms.push(simplified);
+    }
+    forms
+}
+
+/// Node a `depends_on` entry of a node from `package` refers to, trying each
+/// of its [`dependency_id_forms`]
+fn resolve_dependency(
+    node_map: &HashMap<String, NodeIndex>,
+    dep_id: &str,
+    package: Option<&str>,
+) -> Option<NodeIndex> {
+    dependency_id_forms(dep_id, package)
+        .iter()
+        .find_map(|id| node_map.get(id))
+        .copied()
+}
+
+/// Package segment of a manifest unique_id (`model.<package>.orders`)
+fn package_of(orig_id: &str) -> Option<&str> {
+    let mut parts = orig_id.split('.');
+    let package = parts.nth(1)?;
+    parts.next().map(|_| package)
+}
+
+/// How manifest unique_ids are carried into the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdStyle {
+    /// `model.my_project.orders` becomes `model.orders`, as when parsing SQL
+    Simplified,
+    /// Ids keep their package so that merged projects can't collide, and each
+    /// node records the project it comes from
+    Qualified,
+}
+
+impl IdStyle {
+    /// Graph unique_id for the manifest entry `orig_id`, whose short form is
+    /// built by `simplified`
+    fn unique_id(self, orig_id: &str, simplified: impl FnOnce() -> String) -> String {
+        match self {
+            IdStyle::Simplified => simplified(),
+            IdStyle::Qualified => orig_id.to_string(),
+        }
+    }
+
+    /// Package segment of `orig_id` (`model.<package>.orders`), when qualified
+    fn project(self, orig_id: &str) -> Option<String> {
+        match self {
+            IdStyle::Simplified => None,
+            IdStyle::Qualified => orig_id.split('.').nth(1).map(String::from),
+        }
+    }
+}
+
 /// Build a LineageGraph from a parsed manifest.json file.
 pub fn build_graph_from_manifest(manifest_path: &Path) -> Result<LineageGraph> {
+    build_graph_from_parsed_manifest(&read_manifest(manifest_path)?)
+}
+
+/// Build one LineageGraph from the manifests of several projects (dbt Mesh).
+///
+/// A single path behaves like [`build_graph_from_manifest`]. With more, see
+/// [`build_graph_from_parsed_manifests`].
+pub fn build_graph_from_manifests(manifest_paths: &[PathBuf]) -> Result<LineageGraph> {
+    if let [path] = manifest_paths {
+        return build_graph_from_manifest(path);
+    }
+    let manifests = manifest_paths
+        .iter()
+        .map(|path| read_manifest(path))
+        .collect::<Result<Vec<_>>>()?;
+    build_graph_from_parsed_manifests(&manifests)
+}
+
+/// Read and deserialize a manifest.json file
+fn read_manifest(manifest_path: &Path) -> Result<Manifest> {
     let content = std::fs::read_to_string(manifest_path).map_err(|e| {
         crate::error::DbtLineageError::FileReadError {
             path: manifest_path.to_path_buf(),
@@ -126,53 +356,88 @@ pub fn build_graph_from_manifest(manifest_path: &Path) -> Result<LineageGraph> {
             source: e,
         }
     })?;
-
-    build_graph_from_parsed_manifest(&manifest)
+    manifest.warn_skipped(&manifest_path.display().to_string());
+    Ok(manifest)
 }
 
 /// Build a LineageGraph from an already-parsed Manifest struct.
 /// This is separated for testability and reuse by the diff feature.
 pub fn build_graph_from_parsed_manifest(manifest: &Manifest) -> Result<LineageGraph> {
+    build_graph_with_ids(&[manifest], IdStyle::Simplified)
+}
+
+/// Merge several projects' manifests into one graph.
+///
+/// Unique_ids keep their package (`model.finance.revenue`) and each node
+/// records its project, so a `depends_on` entry naming a node from another
+/// manifest becomes an edge between projects. A node listed in more than one
+/// manifest is added once, from the first manifest that has it, with the
+/// dependencies from all of them.
+pub fn build_graph_from_parsed_manifests(manifests: &[Manifest]) -> Result<LineageGraph> {
+    let manifests: Vec<&Manifest> = manifests.iter().collect();
+    build_graph_with_ids(&manifests, IdStyle::Qualified)
+}
+
+fn build_graph_with_ids(manifests: &[&Manifest], ids: IdStyle) -> Result<LineageGraph> {
     let mut graph = LineageGraph::new();
     // Map from original manifest unique_id to graph NodeIndex
     let mut node_map: HashMap<String, NodeIndex> = HashMap::new();
 
-    // 1. Add source nodes
-    add_source_nodes(&mut graph, &mut node_map, &manifest.sources);
+    // Nodes of every manifest first, so that edges can resolve across them
+    for manifest in manifests {
+        // 1. Add source nodes
+        add_source_nodes(&mut graph, &mut node_map, &manifest.sources, ids);
 
-    // 2. Add regular nodes (models, seeds, snapshots, tests, analyses)
-    add_regular_nodes(&mut graph, &mut node_map, &manifest.nodes);
+        // 2. Add regular nodes (models, seeds, snapshots, tests, analyses)
+        add_regular_nodes(&mut graph, &mut node_map, &manifest.nodes, ids);
 
-    // 3. Add exposure nodes
-    add_exposure_nodes(&mut graph, &mut node_map, &manifest.exposures);
+        // 3. Add exposure nodes
+        add_exposure_nodes(&mut graph, &mut node_map, &manifest.exposures, ids);
+    }
 
-    // 4. Add edges from depends_on for regular nodes
-    add_node_edges(&mut graph, &node_map, &manifest.nodes);
+    for manifest in manifests {
+        // 4. Add edges from depends_on for regular nodes
+        add_node_edges(&mut graph, &node_map, &manifest.nodes);
 
-    // 5. Add edges from depends_on for exposures
-    add_exposure_edges(&mut graph, &node_map, &manifest.exposures);
+        // 5. Add edges from depends_on for exposures
+        add_exposure_edges(&mut graph, &node_map, &manifest.exposures);
+    }
 
     Ok(graph)
 }
 
+/// Project-relative file path for a manifest entry, preferring `original_file_path`
+fn manifest_file_path(original: &Option<String>, path: &Option<String>) -> Option<PathBuf> {
+    original
+        .as_ref()
+        .or(path.as_ref())
+        .map(|p| project_relative_path(Path::new(p), Path::new("")))
+}
+
 fn add_source_nodes(
     graph: &mut LineageGraph,
     node_map: &mut HashMap<String, NodeIndex>,
     sources: &HashMap<String, ManifestSource>,
+    ids: IdStyle,
 ) {
     for (orig_id, source) in sources {
-        let simple_id = simplify_unique_id(orig_id, "source");
+        if node_map.contains_key(orig_id) {
+            continue;
+        }
+        let simple_id = ids.unique_id(orig_id, || simplify_unique_id(orig_id, "source"));
         let label = format!("{}.{}", source.source_name, source.name);
 
         let idx = graph.add_node(NodeData {
             unique_id: simple_id.clone(),
             label,
             node_type: NodeType::Source,
-            file_path: source.path.as_ref().map(|p| p.into()),
+            file_path: manifest_file_path(&source.original_file_path, &source.path),
             description: non_empty_string(&source.description),
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            column_descriptions: column_descriptions(&source.columns),
+            project: ids.project(orig_id),
+            meta: source.meta.clone(),
+            schema: source.schema.clone(),
+            ..Default::default()
         });
         node_map.insert(orig_id.clone(), idx);
         // Also index by simplified id for edge resolution
@@ -184,20 +449,54 @@ fn add_regular_nodes(
     graph: &mut LineageGraph,
     node_map: &mut HashMap<String, NodeIndex>,
     nodes: &HashMap<String, ManifestNode>,
+    ids: IdStyle,
 ) {
     for (orig_id, node) in nodes {
+        if node_map.contains_key(orig_id) {
+            continue;
+        }
         let node_type = resource_type_to_node_type(&node.resource_type);
-        let simple_id = simplify_unique_id(orig_id, &node.resource_type);
+        let version = node.version.as_ref().and_then(json_scalar_to_string);
+        // Versioned ids ("model.proj.dim_customers.v2") keep the name and version
+        let simple_id = ids.unique_id(orig_id, || match &version {
+            Some(v) => format!(
+                "{}.{}.v{}",
+                orig_id.split('.').next().unwrap_or("model"),
+                node.name,
+                v
+            ),
+            None => simplify_unique_id(orig_id, &node.resource_type),
+        });
 
         let idx = graph.add_node(NodeData {
             unique_id: simple_id.clone(),
             label: node.name.clone(),
             node_type,
-            file_path: node.path.as_ref().map(|p| p.into()),
+            file_path: manifest_file_path(&node.original_file_path, &node.path),
             description: non_empty_string(&node.description),
             materialization: node.config.materialized.clone(),
             tags: node.config.tags.clone(),
-            columns: vec![],
+            version,
+            column_descriptions: column_descriptions(&node.columns),
+            test_kind: (node_type == NodeType::Test).then(|| {
+                if node.test_metadata.is_some() {
+                    TestKind::Generic
+                } else {
+                    TestKind::Singular
+                }
+            }),
+            project: ids.project(orig_id),
+            meta: node
+                .meta
+                .iter()
+                .chain(&node.config.meta)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            schema: node.config.schema.clone(),
+            snapshot: (node_type == NodeType::Snapshot)
+                .then(|| node.config.snapshot_config())
+                .flatten(),
+            ..Default::default()
         });
         node_map.insert(orig_id.clone(), idx);
         node_map.insert(simple_id, idx);
@@ -208,19 +507,20 @@ fn add_exposure_nodes(
     graph: &mut LineageGraph,
     node_map: &mut HashMap<String, NodeIndex>,
     exposures: &HashMap<String, ManifestExposure>,
+    ids: IdStyle,
 ) {
     for (orig_id, exposure) in exposures {
-        let simple_id = simplify_unique_id(orig_id, "exposure");
+        if node_map.contains_key(orig_id) {
+            continue;
+        }
+        let simple_id = ids.unique_id(orig_id, || simplify_unique_id(orig_id, "exposure"));
 
         let idx = graph.add_node(NodeData {
             unique_id: simple_id.clone(),
             label: exposure.name.clone(),
             node_type: NodeType::Exposure,
-            file_path: None,
             description: non_empty_string(&exposure.description),
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         node_map.insert(orig_id.clone(), idx);
         node_map.insert(simple_id, idx);
@@ -239,9 +539,17 @@ fn add_node_edges(
         };
 
         for dep_id in &node.depends_on.nodes {
-            if let Some(&dep_idx) = node_map.get(dep_id) {
+            if let Some(dep_idx) = resolve_dependency(node_map, dep_id, package_of(orig_id)) {
                 let edge_type = infer_edge_type(dep_id);
-                graph.add_edge(dep_idx, current_idx, EdgeData { edge_type });
+                add_unique_edge(
+                    graph,
+                    dep_idx,
+                    current_idx,
+                    EdgeData {
+                        edge_type,
+                        snippet: None,
+                    },
+                );
             }
         }
     }
@@ -259,12 +567,14 @@ fn add_exposure_edges(
         };
 
         for dep_id in &exposure.depends_on.nodes {
-            if let Some(&dep_idx) = node_map.get(dep_id) {
-                graph.add_edge(
+            if let Some(dep_idx) = resolve_dependency(node_map, dep_id, package_of(orig_id)) {
+                add_unique_edge(
+                    graph,
                     dep_idx,
                     current_idx,
                     EdgeData {
                         edge_type: EdgeType::Exposure,
+                        snippet: None,
                     },
                 );
             }
@@ -283,6 +593,15 @@ fn infer_edge_type(dep_unique_id: &str) -> EdgeType {
     }
 }
 
+/// Render a JSON scalar version (`2` or `"2"`) as a plain string
+fn json_scalar_to_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
 /// Return None for empty or whitespace-only strings
 fn non_empty_string(s: &Option<String>) -> Option<String> {
     s.as_ref().filter(|v| !v.trim().is_empty()).cloned()
@@ -337,6 +656,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dependency_id_forms() {
+        assert_eq!(
+            dependency_id_forms("model.proj.orders", Some("proj")),
+            vec!["model.proj.orders", "model.orders"]
+        );
+        assert_eq!(
+            dependency_id_forms("model.orders", Some("proj")),
+            vec!["model.orders", "model.proj.orders"]
+        );
+        assert_eq!(
+            dependency_id_forms("source.raw.orders", Some("proj")),
+            vec!["source.raw.orders", "source.proj.raw.orders"]
+        );
+        assert_eq!(
+            dependency_id_forms("source.proj.raw.orders", None),
+            vec!["source.proj.raw.orders", "source.raw.orders"]
+        );
+        assert_eq!(
+            dependency_id_forms("model.proj.dim_customers.v2", None),
+            vec!["model.proj.dim_customers.v2", "model.dim_customers.v2"]
+        );
+        assert_eq!(
+            dependency_id_forms("model.dim_customers.v2", Some("proj")),
+            vec!["model.dim_customers.v2", "model.proj.dim_customers.v2"]
+        );
+        assert_eq!(package_of("model.proj.orders"), Some("proj"));
+        assert_eq!(package_of("model.orders"), None);
+    }
+
     #[test]
     fn test_infer_edge_type() {
         assert_eq!(
@@ -377,9 +726,17 @@ mod tests {
                     config: ManifestConfig {
                         materialized: Some("view".to_string()),
                         tags: vec!["staging".to_string()],
+                        meta: Default::default(),
+                        schema: None,
+                        ..Default::default()
                     },
                     description: Some("Staged orders".to_string()),
                     path: Some("models/staging/stg_orders.sql".to_string()),
+                    original_file_path: None,
+                    version: None,
+                    columns: HashMap::new(),
+                    test_metadata: None,
+                    meta: Default::default(),
                 },
             )]),
             sources: HashMap::from([(
@@ -391,9 +748,14 @@ mod tests {
                     resource_type: "source".to_string(),
                     description: Some("Raw orders table".to_string()),
                     path: Some("models/staging/schema.yml".to_string()),
+                    original_file_path: None,
+                    columns: HashMap::new(),
+                    meta: Default::default(),
+                    schema: None,
                 },
             )]),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -434,6 +796,11 @@ mod tests {
                     config: ManifestConfig::default(),
                     description: None,
                     path: None,
+                    original_file_path: None,
+                    version: None,
+                    columns: HashMap::new(),
+                    test_metadata: None,
+                    meta: Default::default(),
                 },
             )]),
             sources: HashMap::new(),
@@ -448,6 +815,7 @@ mod tests {
                     description: Some("Weekly dashboard".to_string()),
                 },
             )]),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -479,6 +847,11 @@ mod tests {
                         config: ManifestConfig::default(),
                         description: None,
                         path: Some("seeds/countries.csv".to_string()),
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
                 (
@@ -491,14 +864,23 @@ mod tests {
                         config: ManifestConfig {
                             materialized: Some("snapshot".to_string()),
                             tags: vec![],
+                            meta: Default::default(),
+                            schema: None,
+                            ..Default::default()
                         },
                         description: None,
                         path: Some("snapshots/snap_orders.sql".to_string()),
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
             ]),
             sources: HashMap::new(),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -531,6 +913,11 @@ mod tests {
                         config: ManifestConfig::default(),
                         description: None,
                         path: None,
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
                 (
@@ -545,11 +932,17 @@ mod tests {
                         config: ManifestConfig::default(),
                         description: None,
                         path: Some("tests/assert_positive.sql".to_string()),
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
             ]),
             sources: HashMap::new(),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -563,12 +956,51 @@ mod tests {
         assert_eq!(graph[test_node].label, "assert_positive");
     }
 
+    #[test]
+    fn test_test_kind_from_test_metadata() {
+        let manifest: Manifest = serde_json::from_str(
+            r#"{
+                "nodes": {
+                    "test.proj.assert_positive": {
+                        "unique_id": "test.proj.assert_positive",
+                        "name": "assert_positive",
+                        "resource_type": "test"
+                    },
+                    "test.proj.not_null_orders_id.5fb22c2710": {
+                        "unique_id": "test.proj.not_null_orders_id.5fb22c2710",
+                        "name": "not_null_orders_id",
+                        "resource_type": "test",
+                        "test_metadata": {"name": "not_null", "kwargs": {"column_name": "id"}}
+                    },
+                    "model.proj.orders": {
+                        "unique_id": "model.proj.orders",
+                        "name": "orders",
+                        "resource_type": "model"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        let kind = |label: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].label == label)
+                .unwrap();
+            graph[idx].test_kind
+        };
+        assert_eq!(kind("assert_positive"), Some(TestKind::Singular));
+        assert_eq!(kind("not_null_orders_id"), Some(TestKind::Generic));
+        assert_eq!(kind("orders"), None);
+    }
+
     #[test]
     fn test_build_graph_empty_manifest() {
         let manifest = Manifest {
             nodes: HashMap::new(),
             sources: HashMap::new(),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -592,10 +1024,16 @@ mod tests {
                     config: ManifestConfig::default(),
                     description: None,
                     path: None,
+                    original_file_path: None,
+                    version: None,
+                    columns: HashMap::new(),
+                    test_metadata: None,
+                    meta: Default::default(),
                 },
             )]),
             sources: HashMap::new(),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -603,6 +1041,48 @@ mod tests {
         assert_eq!(graph.edge_count(), 0); // Edge to nonexistent node is skipped
     }
 
+    #[test]
+    fn test_build_graph_duplicate_dependency() {
+        let node = |id: &str, name: &str, deps: &[&str]| ManifestNode {
+            unique_id: id.to_string(),
+            name: name.to_string(),
+            resource_type: "model".to_string(),
+            depends_on: DependsOn {
+                nodes: deps.iter().map(|d| d.to_string()).collect(),
+            },
+            config: ManifestConfig::default(),
+            description: None,
+            path: None,
+            original_file_path: None,
+            version: None,
+            columns: HashMap::new(),
+            test_metadata: None,
+            meta: Default::default(),
+        };
+        let manifest = Manifest {
+            nodes: HashMap::from([
+                (
+                    "model.proj.stg".to_string(),
+                    node("model.proj.stg", "stg", &[]),
+                ),
+                (
+                    "model.proj.orders".to_string(),
+                    node(
+                        "model.proj.orders",
+                        "orders",
+                        &["model.proj.stg", "model.proj.stg"],
+                    ),
+                ),
+            ]),
+            sources: HashMap::new(),
+            exposures: HashMap::new(),
+            skipped: vec![],
+        };
+
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        assert_eq!(graph.edge_count(), 1);
+    }
+
     #[test]
     fn test_build_graph_optional_fields() {
         let manifest = Manifest {
@@ -616,13 +1096,22 @@ mod tests {
                     config: ManifestConfig {
                         materialized: None,
                         tags: vec![],
+                        meta: Default::default(),
+                        schema: None,
+                        ..Default::default()
                     },
                     description: None,
                     path: None,
+                    original_file_path: None,
+                    version: None,
+                    columns: HashMap::new(),
+                    test_metadata: None,
+                    meta: Default::default(),
                 },
             )]),
             sources: HashMap::new(),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -699,10 +1188,16 @@ mod tests {
                     config: ManifestConfig::default(),
                     description: None,
                     path: None,
+                    original_file_path: None,
+                    version: None,
+                    columns: HashMap::new(),
+                    test_metadata: None,
+                    meta: Default::default(),
                 },
             )]),
             sources: HashMap::new(),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -727,9 +1222,17 @@ mod tests {
                         config: ManifestConfig {
                             materialized: Some("view".to_string()),
                             tags: vec![],
+                            meta: Default::default(),
+                            schema: None,
+                            ..Default::default()
                         },
                         description: None,
                         path: None,
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
                 (
@@ -744,6 +1247,11 @@ mod tests {
                         config: ManifestConfig::default(),
                         description: None,
                         path: None,
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
                 (
@@ -761,9 +1269,17 @@ mod tests {
                         config: ManifestConfig {
                             materialized: Some("table".to_string()),
                             tags: vec!["marts".to_string()],
+                            meta: Default::default(),
+                            schema: None,
+                            ..Default::default()
                         },
                         description: Some("Order fact table".to_string()),
                         path: None,
+                        original_file_path: None,
+                        version: None,
+                        columns: HashMap::new(),
+                        test_metadata: None,
+                        meta: Default::default(),
                     },
                 ),
             ]),
@@ -777,6 +1293,10 @@ mod tests {
                         resource_type: "source".to_string(),
                         description: None,
                         path: None,
+                        original_file_path: None,
+                        columns: HashMap::new(),
+                        meta: Default::default(),
+                        schema: None,
                     },
                 ),
                 (
@@ -788,10 +1308,15 @@ mod tests {
                         resource_type: "source".to_string(),
                         description: None,
                         path: None,
+                        original_file_path: None,
+                        columns: HashMap::new(),
+                        meta: Default::default(),
+                        schema: None,
                     },
                 ),
             ]),
             exposures: HashMap::new(),
+            skipped: vec![],
         };
 
         let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
@@ -848,4 +1373,410 @@ mod tests {
         // Check edges exist
         assert!(graph.edge_count() > 0, "Should have edges");
     }
+
+    #[test]
+    fn test_file_path_prefers_original_file_path() {
+        let json = r#"{
+            "nodes": {
+                "model.proj.stg_orders": {
+                    "unique_id": "model.proj.stg_orders",
+                    "name": "stg_orders",
+                    "resource_type": "model",
+                    "path": "staging/stg_orders.sql",
+                    "original_file_path": "models/staging/stg_orders.sql"
+                },
+                "model.proj.orders": {
+                    "unique_id": "model.proj.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "path": "./models/orders.sql"
+                }
+            }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        let path_of = |label: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].label == label)
+                .unwrap();
+            graph[idx].file_path.clone().unwrap()
+        };
+        assert_eq!(
+            path_of("stg_orders"),
+            PathBuf::from("models/staging/stg_orders.sql")
+        );
+        assert_eq!(path_of("orders"), PathBuf::from("models/orders.sql"));
+    }
+
+    #[test]
+    fn test_meta_and_schema_from_node_config_and_source() {
+        let json = r#"{
+            "nodes": {
+                "model.proj.orders": {
+                    "unique_id": "model.proj.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "schema": "dbt_alice_marts",
+                    "meta": {"owner": "finance", "sla_hours": 48},
+                    "config": {"meta": {"sla_hours": 6}, "schema": "marts"}
+                }
+            },
+            "sources": {
+                "source.proj.raw.payments": {
+                    "unique_id": "source.proj.raw.payments",
+                    "name": "payments",
+                    "source_name": "raw",
+                    "schema": "raw_data",
+                    "meta": {"sla_hours": "12"}
+                }
+            }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        let meta_of = |label: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].label == label)
+                .unwrap();
+            graph[idx].meta.clone()
+        };
+        // config.meta wins over the node-level copy
+        assert_eq!(meta_of("orders")["sla_hours"], 6);
+        assert_eq!(meta_of("orders")["owner"], "finance");
+        assert_eq!(meta_of("raw.payments")["sla_hours"], "12");
+
+        // The custom schema config, not the target-prefixed resolved one
+        let schema_of = |label: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].label == label)
+                .unwrap();
+            graph[idx].schema.clone()
+        };
+        assert_eq!(schema_of("orders").as_deref(), Some("marts"));
+        assert_eq!(schema_of("raw.payments").as_deref(), Some("raw_data"));
+    }
+
+    #[test]
+    fn test_snapshot_config() {
+        let json = r#"{
+            "nodes": {
+                "snapshot.proj.snap_orders": {
+                    "unique_id": "snapshot.proj.snap_orders",
+                    "name": "snap_orders",
+                    "resource_type": "snapshot",
+                    "config": {
+                        "materialized": "snapshot",
+                        "strategy": "timestamp",
+                        "unique_key": "order_id",
+                        "updated_at": "updated_at",
+                        "check_cols": null
+                    }
+                },
+                "model.proj.orders": {
+                    "unique_id": "model.proj.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "config": {"unique_key": "order_id"}
+                }
+            },
+            "sources": {}
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        let snapshot_of = |label: &str| {
+            let idx = graph
+                .node_indices()
+                .find(|&i| graph[i].label == label)
+                .unwrap();
+            graph[idx].snapshot.clone()
+        };
+        assert_eq!(
+            snapshot_of("snap_orders"),
+            Some(SnapshotConfig {
+                strategy: Some("timestamp".into()),
+                unique_key: vec!["order_id".into()],
+                updated_at: Some("updated_at".into()),
+                check_cols: vec![],
+            })
+        );
+        // An incremental model's unique_key is not snapshot config
+        assert_eq!(snapshot_of("orders"), None);
+    }
+
+    #[test]
+    fn test_malformed_entries_are_skipped() {
+        let json = r#"{
+            "nodes": {
+                "model.proj.orders": {
+                    "unique_id": "model.proj.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "depends_on": {"nodes": ["source.proj.raw.orders"]}
+                },
+                "model.proj.odd": {
+                    "unique_id": "model.proj.odd",
+                    "name": 42,
+                    "resource_type": "model"
+                }
+            },
+            "sources": {
+                "source.proj.raw.orders": {
+                    "unique_id": "source.proj.raw.orders",
+                    "name": "orders",
+                    "source_name": "raw"
+                },
+                "source.proj.raw.broken": {"unique_id": "source.proj.raw.broken"}
+            },
+            "exposures": {
+                "exposure.proj.dash": {
+                    "unique_id": "exposure.proj.dash",
+                    "name": "dash",
+                    "depends_on": {"nodes": "model.proj.orders"}
+                }
+            }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.skipped.len(), 3);
+        assert!(manifest.skipped[0].starts_with("exposure.proj.dash: invalid type"));
+        assert!(manifest.skipped[1].starts_with("model.proj.odd: invalid type"));
+        assert!(manifest.skipped[2].starts_with("source.proj.raw.broken: missing field"));
+
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+
+        // The sections themselves still have to be objects
+        assert!(serde_json::from_str::<Manifest>(r#"{"nodes": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_versioned_model_nodes() {
+        let json = r#"{
+            "nodes": {
+                "model.proj.dim_customers.v1": {
+                    "unique_id": "model.proj.dim_customers.v1",
+                    "name": "dim_customers",
+                    "resource_type": "model",
+                    "version": 1,
+                    "latest_version": 2
+                },
+                "model.proj.dim_customers.v2": {
+                    "unique_id": "model.proj.dim_customers.v2",
+                    "name": "dim_customers",
+                    "resource_type": "model",
+                    "version": "2",
+                    "latest_version": 2
+                },
+                "model.proj.orders": {
+                    "unique_id": "model.proj.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "depends_on": {"nodes": ["model.proj.dim_customers.v2"]}
+                }
+            }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        let find = |id: &str| {
+            graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap()
+        };
+        let v1 = find("model.dim_customers.v1");
+        let v2 = find("model.dim_customers.v2");
+        assert_eq!(graph[v1].label, "dim_customers");
+        assert_eq!(graph[v1].version.as_deref(), Some("1"));
+        assert_eq!(graph[v2].version.as_deref(), Some("2"));
+        assert!(graph.contains_edge(v2, find("model.orders")));
+        assert!(graph[find("model.orders")].version.is_none());
+    }
+
+    #[test]
+    fn test_merge_manifests_resolves_cross_project_edges() {
+        let core: Manifest = serde_json::from_str(
+            r#"{
+            "nodes": {
+                "model.core.customers": {
+                    "unique_id": "model.core.customers",
+                    "name": "customers",
+                    "resource_type": "model",
+                    "depends_on": {"nodes": ["source.core.raw.customers"]}
+                },
+                "model.core.orders": {
+                    "unique_id": "model.core.orders",
+                    "name": "orders",
+                    "resource_type": "model"
+                }
+            },
+            "sources": {
+                "source.core.raw.customers": {
+                    "unique_id": "source.core.raw.customers",
+                    "name": "customers",
+                    "source_name": "raw"
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+        // The downstream project lists the public model it uses, and has its own `orders`
+        let finance: Manifest = serde_json::from_str(
+            r#"{
+            "nodes": {
+                "model.core.customers": {
+                    "unique_id": "model.core.customers",
+                    "name": "customers",
+                    "resource_type": "model"
+                },
+                "model.finance.orders": {
+                    "unique_id": "model.finance.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "depends_on": {"nodes": ["model.core.customers"]}
+                }
+            },
+            "exposures": {
+                "exposure.finance.revenue": {
+                    "unique_id": "exposure.finance.revenue",
+                    "name": "revenue",
+                    "depends_on": {"nodes": ["model.finance.orders", "model.core.orders"]}
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let graph = build_graph_from_parsed_manifests(&[core, finance]).unwrap();
+        let find = |id: &str| {
+            graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap_or_else(|| panic!("missing {}", id))
+        };
+
+        // Package-qualified ids keep both `orders` models apart; the shared model is added once
+        assert_eq!(graph.node_count(), 5);
+        let customers = find("model.core.customers");
+        let core_orders = find("model.core.orders");
+        let finance_orders = find("model.finance.orders");
+        let revenue = find("exposure.finance.revenue");
+        assert_eq!(graph[customers].project.as_deref(), Some("core"));
+        assert_eq!(graph[finance_orders].project.as_deref(), Some("finance"));
+        assert_eq!(
+            graph[find("source.core.raw.customers")].label,
+            "raw.customers"
+        );
+
+        assert!(graph.contains_edge(find("source.core.raw.customers"), customers));
+        assert!(graph.contains_edge(customers, finance_orders));
+        assert!(graph.contains_edge(core_orders, revenue));
+        assert!(graph[customers].in_other_project(&graph[finance_orders]));
+        assert!(!graph[finance_orders].in_other_project(&graph[revenue]));
+    }
+
+    #[test]
+    fn test_single_manifest_path_keeps_simplified_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.json");
+        fs::write(
+            &path,
+            r#"{"nodes": {"model.proj.orders": {"unique_id": "model.proj.orders", "name": "orders", "resource_type": "model"}}}"#,
+        )
+        .unwrap();
+        let graph = build_graph_from_manifests(&[path]).unwrap();
+        let node = &graph[graph.node_indices().next().unwrap()];
+        assert_eq!(node.unique_id, "model.orders");
+        assert_eq!(node.project, None);
+    }
+
+    #[test]
+    fn test_depends_on_resolves_after_simplification() {
+        // `depends_on` entries written under another package name, or in short
+        // form, still find their nodes
+        let json = r#"{
+            "nodes": {
+                "model.proj.stg_orders": {
+                    "unique_id": "model.proj.stg_orders",
+                    "name": "stg_orders",
+                    "resource_type": "model"
+                },
+                "model.proj.dim_customers.v2": {
+                    "unique_id": "model.proj.dim_customers.v2",
+                    "name": "dim_customers",
+                    "resource_type": "model",
+                    "version": 2
+                },
+                "model.proj.orders": {
+                    "unique_id": "model.proj.orders",
+                    "name": "orders",
+                    "resource_type": "model",
+                    "depends_on": {"nodes": [
+                        "model.old_proj.stg_orders",
+                        "model.old_proj.dim_customers.v2",
+                        "source.old_proj.raw.orders"
+                    ]}
+                }
+            },
+            "sources": {
+                "source.proj.raw.orders": {
+                    "unique_id": "source.proj.raw.orders",
+                    "name": "orders",
+                    "source_name": "raw"
+                }
+            },
+            "exposures": {
+                "exposure.proj.revenue": {
+                    "unique_id": "exposure.proj.revenue",
+                    "name": "revenue",
+                    "depends_on": {"nodes": ["model.orders"]}
+                }
+            }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        let graph = build_graph_from_parsed_manifest(&manifest).unwrap();
+        let find = |id: &str| {
+            graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap_or_else(|| panic!("missing {}", id))
+        };
+        let orders = find("model.orders");
+        assert!(graph.contains_edge(find("model.stg_orders"), orders));
+        assert!(graph.contains_edge(find("model.dim_customers.v2"), orders));
+        assert!(graph.contains_edge(find("source.raw.orders"), orders));
+        assert!(graph.contains_edge(orders, find("exposure.revenue")));
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_merged_manifests_resolve_short_ids_in_own_package() {
+        let manifest = |package: &str, deps: &str| -> Manifest {
+            serde_json::from_str(&format!(
+                r#"{{"nodes": {{
+                    "model.{p}.ledger": {{"unique_id": "model.{p}.ledger", "name": "ledger", "resource_type": "model"}},
+                    "model.{p}.report": {{"unique_id": "model.{p}.report", "name": "report", "resource_type": "model",
+                        "depends_on": {{"nodes": [{deps}]}}}}
+                }}}}"#,
+                p = package,
+                deps = deps
+            ))
+            .unwrap()
+        };
+        let graph = build_graph_from_parsed_manifests(&[
+            manifest("core", ""),
+            manifest("finance", r#""model.ledger""#),
+        ])
+        .unwrap();
+        let find = |id: &str| {
+            graph
+                .node_indices()
+                .find(|&i| graph[i].unique_id == id)
+                .unwrap()
+        };
+        // The short id means finance's own ledger, not core's
+        assert!(graph.contains_edge(find("model.finance.ledger"), find("model.finance.report")));
+        assert_eq!(graph.edge_count(), 1);
+    }
 }
@@ -1,9 +1,12 @@
 pub mod artifacts;
+pub mod baseline;
+pub mod catalog;
 pub mod column_lineage;
 pub mod columns;
 pub mod discovery;
 pub mod manifest;
 pub mod project;
+pub mod selectors;
 pub mod sql;
 #[allow(dead_code)]
 pub mod yaml_schema;
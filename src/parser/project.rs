@@ -20,6 +20,13 @@ pub struct DbtProject {
 
     #[serde(rename = "test-paths", default = "default_test_paths")]
     pub test_paths: Vec<String>,
+
+    #[serde(rename = "target-path", default = "default_target_path")]
+    pub target_path: String,
+
+    /// Folder-scoped model config, kept raw; see [`DbtProject::folder_configs`]
+    #[serde(default)]
+    pub models: serde_yaml::Value,
 }
 
 fn default_model_paths() -> Vec<String> {
@@ -38,6 +45,10 @@ fn default_test_paths() -> Vec<String> {
     vec!["tests".to_string()]
 }
 
+fn default_target_path() -> String {
+    "target".to_string()
+}
+
 impl DbtProject {
     pub fn load(project_dir: &Path) -> Result<Self> {
         let project_file = project_dir.join("dbt_project.yml");
@@ -57,6 +68,27 @@ impl DbtProject {
         Ok(project)
     }
 
+    /// Where `dbt compile` writes this project's compiled SQL
+    pub fn compiled_dir(&self, project_dir: &Path) -> PathBuf {
+        project_dir
+            .join(&self.target_path)
+            .join("compiled")
+            .join(&self.name)
+    }
+
+    /// The `+tags`, `+materialized` and `+schema` set for folders of this project under
+    /// `models:`. Blocks for installed packages are ignored.
+    pub fn folder_configs(&self) -> FolderConfigs {
+        let mut scopes = Vec::new();
+        if let Some(tree) = self.models.get(self.name.as_str()) {
+            collect_folder_configs(tree, &mut Vec::new(), &mut scopes);
+        }
+        FolderConfigs {
+            model_paths: self.model_paths.iter().map(PathBuf::from).collect(),
+            scopes,
+        }
+    }
+
     pub fn resolve_paths(&self, project_dir: &Path) -> ResolvedPaths {
         ResolvedPaths {
             model_paths: self
@@ -91,6 +123,112 @@ pub struct ResolvedPaths {
     pub test_paths: Vec<PathBuf>,
 }
 
+/// Config set in `dbt_project.yml` for one folder (or model) and everything below it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FolderConfig {
+    /// Folder names below a model path, possibly ending in a model name; empty
+    /// for the whole project
+    pub path: Vec<String>,
+    pub tags: Vec<String>,
+    pub materialized: Option<String>,
+    pub schema: Option<String>,
+}
+
+/// Every folder-level model config of a project, outermost first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FolderConfigs {
+    /// Model paths, relative to the project directory, that folder paths start at
+    model_paths: Vec<PathBuf>,
+    scopes: Vec<FolderConfig>,
+}
+
+impl FolderConfigs {
+    /// Effective config for the model at `relative_path` (relative to the
+    /// project directory). Tags add up from every enclosing folder, as in dbt,
+    /// and the innermost `+materialized` and `+schema` win. A model outside the model paths
+    /// only gets the project-wide config.
+    pub fn resolve(&self, relative_path: &Path) -> FolderConfig {
+        let within = self
+            .model_paths
+            .iter()
+            .find_map(|root| relative_path.strip_prefix(root).ok());
+        let path: Vec<String> = within
+            .map(|p| {
+                p.with_extension("")
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut effective = FolderConfig::default();
+        for scope in &self.scopes {
+            if !path.starts_with(&scope.path) {
+                continue;
+            }
+            effective.tags.extend(scope.tags.iter().cloned());
+            if scope.materialized.is_some() {
+                effective.materialized = scope.materialized.clone();
+            }
+            if scope.schema.is_some() {
+                effective.schema = scope.schema.clone();
+            }
+        }
+        effective.tags.sort();
+        effective.tags.dedup();
+        effective
+    }
+}
+
+/// Walk one level of the `models:` tree: `+`-prefixed keys (and the bare
+/// `tags`/`materialized`/`schema`) configure `path`, other mappings are subfolders
+fn collect_folder_configs(
+    tree: &serde_yaml::Value,
+    path: &mut Vec<String>,
+    scopes: &mut Vec<FolderConfig>,
+) {
+    let Some(mapping) = tree.as_mapping() else {
+        return;
+    };
+    let mut config = FolderConfig {
+        path: path.clone(),
+        ..Default::default()
+    };
+    let mut children = Vec::new();
+    for (key, value) in mapping {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        match key.trim_start_matches('+') {
+            "tags" => config.tags = string_list(value),
+            "materialized" => config.materialized = value.as_str().map(String::from),
+            "schema" => config.schema = value.as_str().map(String::from),
+            _ if key.starts_with('+') => {}
+            folder if value.is_mapping() => children.push((folder.to_string(), value)),
+            _ => {}
+        }
+    }
+    if !config.tags.is_empty() || config.materialized.is_some() || config.schema.is_some() {
+        scopes.push(config);
+    }
+    for (folder, value) in children {
+        path.push(folder);
+        collect_folder_configs(value, path, scopes);
+        path.pop();
+    }
+}
+
+/// A YAML string or list of strings
+fn string_list(value: &serde_yaml::Value) -> Vec<String> {
+    match value {
+        serde_yaml::Value::String(s) => vec![s.clone()],
+        serde_yaml::Value::Sequence(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +243,10 @@ mod tests {
         assert_eq!(project.seed_paths, vec!["seeds"]);
         assert_eq!(project.snapshot_paths, vec!["snapshots"]);
         assert_eq!(project.test_paths, vec!["tests"]);
+        assert_eq!(
+            project.compiled_dir(Path::new("/p")),
+            PathBuf::from("/p/target/compiled/my_project")
+        );
     }
 
     #[test]
@@ -147,6 +289,51 @@ seed-paths: ["data"]
         assert!(msg.contains("Failed to parse"), "Got: {}", msg);
     }
 
+    #[test]
+    fn test_folder_configs() {
+        let yaml = r#"
+name: shop
+model-paths: ["dbt/models"]
+models:
+  shop:
+    staging:
+      materialized: view
+      +tags: [staging, "nightly"]
+      stg_payments:
+        +materialized: table
+    marts:
+      +enabled: true
+"#;
+        let project: DbtProject = serde_yaml::from_str(yaml).unwrap();
+        let configs = project.folder_configs();
+
+        let stg = configs.resolve(Path::new("dbt/models/staging/stg_orders.sql"));
+        assert_eq!(stg.materialized.as_deref(), Some("view"));
+        assert_eq!(stg.tags, vec!["nightly", "staging"]);
+        // The innermost scope, here a single model, wins
+        let payments = configs.resolve(Path::new("dbt/models/staging/stg_payments.sql"));
+        assert_eq!(payments.materialized.as_deref(), Some("table"));
+        assert_eq!(payments.tags, vec!["nightly", "staging"]);
+        // Scopes without tags or materialization, and other folders, add nothing
+        assert_eq!(
+            configs.resolve(Path::new("dbt/models/marts/orders.sql")),
+            FolderConfig::default()
+        );
+        assert_eq!(
+            configs.resolve(Path::new("models/staging/stg_orders.sql")),
+            FolderConfig::default()
+        );
+        // No `models:` block at all
+        let bare: DbtProject = serde_yaml::from_str("name: shop\n").unwrap();
+        assert_eq!(
+            bare.folder_configs()
+                .resolve(Path::new("models/a.sql"))
+                .tags
+                .len(),
+            0
+        );
+    }
+
     #[test]
     fn test_resolve_paths() {
         let yaml = "name: my_project\n";
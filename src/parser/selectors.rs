@@ -0,0 +1,322 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::path::Path;
+
+use crate::error::DbtLineageError;
+use crate::graph::filter::{parse_selectors, Selector};
+
+/// A named selector from `selectors.yml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedSelector {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The dbt selector definition, kept raw; see [`resolve_selector`]
+    pub definition: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct SelectorsFile {
+    #[serde(default)]
+    selectors: Vec<NamedSelector>,
+}
+
+/// Read the named selectors from `selectors.yml` in `project_dir`
+pub fn load_selectors(project_dir: &Path) -> Result<Vec<NamedSelector>> {
+    let path = project_dir.join("selectors.yml");
+    let content = std::fs::read_to_string(&path).map_err(|e| DbtLineageError::FileReadError {
+        path: path.clone(),
+        source: e,
+    })?;
+    let file: SelectorsFile = serde_yaml::from_str(&content)
+        .map_err(|e| DbtLineageError::YamlParseError { path, source: e })?;
+    Ok(file.selectors)
+}
+
+/// Load `selectors.yml` from `project_dir` and resolve the selector `name`
+pub fn load_named_selector(project_dir: &Path, name: &str) -> Result<Vec<Vec<Selector>>> {
+    resolve_selector(&load_selectors(project_dir)?, name)
+}
+
+/// Translate the selector `name` into our selector terms: the outer `Vec` is
+/// unioned and each inner one intersected, as returned by [`parse_selectors`].
+///
+/// Supported definitions are a selector string (`"tag:nightly"`), a method and
+/// value (`{method: tag, value: nightly}` or `{tag: nightly}`), and `union` /
+/// `intersection` lists of those, nested freely. Methods are `tag`, `path`,
+/// `fqn` (a model name), `config.materialized`, `config.schema` and `selector`
+/// (another named selector). Graph operators, `exclude` and other methods are
+/// rejected rather than silently ignored.
+pub fn resolve_selector(selectors: &[NamedSelector], name: &str) -> Result<Vec<Vec<Selector>>> {
+    resolve_named(selectors, name, &mut Vec::new())
+}
+
+fn resolve_named(
+    selectors: &[NamedSelector],
+    name: &str,
+    stack: &mut Vec<String>,
+) -> Result<Vec<Vec<Selector>>> {
+    let Some(selector) = selectors.iter().find(|s| s.name == name) else {
+        let mut known: Vec<&str> = selectors.iter().map(|s| s.name.as_str()).collect();
+        known.sort_unstable();
+        return Err(invalid(format!(
+            "no selector named '{}' in selectors.yml (defined: {})",
+            name,
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        )));
+    };
+    if stack.iter().any(|s| s == name) {
+        return Err(invalid(format!(
+            "selector '{}' refers to itself through {}",
+            name,
+            stack.join(" -> ")
+        )));
+    }
+    stack.push(name.to_string());
+    let terms = translate(selectors, name, &selector.definition, stack)?;
+    stack.pop();
+    Ok(terms)
+}
+
+/// Translate one definition node into union-of-intersections form
+fn translate(
+    selectors: &[NamedSelector],
+    name: &str,
+    definition: &Value,
+    stack: &mut Vec<String>,
+) -> Result<Vec<Vec<Selector>>> {
+    let unsupported = |what: String| invalid(format!("selector '{}': {}", name, what));
+    match definition {
+        Value::String(expr) => Ok(parse_selectors(expr)),
+        Value::Mapping(map) => {
+            if let Some(items) = map.get("union") {
+                let mut terms = Vec::new();
+                for item in sequence(items, "union").map_err(unsupported)? {
+                    terms.extend(translate(selectors, name, item, stack)?);
+                }
+                return Ok(terms);
+            }
+            if let Some(items) = map.get("intersection") {
+                // (a ∪ b) ∩ c becomes (a ∩ c) ∪ (b ∩ c)
+                let mut terms: Vec<Vec<Selector>> = vec![Vec::new()];
+                for item in sequence(items, "intersection").map_err(unsupported)? {
+                    let item_terms = translate(selectors, name, item, stack)?;
+                    terms = terms
+                        .iter()
+                        .flat_map(|term| {
+                            item_terms.iter().map(move |other| {
+                                let mut joined = term.clone();
+                                joined.extend(other.iter().cloned());
+                                joined
+                            })
+                        })
+                        .collect();
+                }
+                return Ok(terms);
+            }
+            for key in [
+                "exclude",
+                "parents",
+                "children",
+                "parents_depth",
+                "children_depth",
+            ] {
+                if map.contains_key(key) {
+                    return Err(unsupported(format!("'{}' is not supported", key)));
+                }
+            }
+            let (method, value) = match (map.get("method"), map.get("value")) {
+                (Some(method), Some(value)) => (method, value),
+                // Key-value shorthand: `tag: nightly`
+                _ if map.len() == 1 => map.iter().next().unwrap(),
+                _ => return Err(unsupported("expected method and value".to_string())),
+            };
+            let (Some(method), Some(value)) = (method.as_str(), scalar(value)) else {
+                return Err(unsupported("method and value must be strings".to_string()));
+            };
+            let selector = match method {
+                "tag" => Selector::Tag(value),
+                "path" => Selector::Path(value),
+                "fqn" if !value.contains('.') => Selector::ModelName(value),
+                "config.materialized" => Selector::Materialized(value),
+                "config.schema" => Selector::Schema(value),
+                "selector" => return resolve_named(selectors, &value, stack),
+                "fqn" => {
+                    return Err(unsupported(format!(
+                        "fqn '{}' is not supported; use a model name or a path",
+                        value
+                    )))
+                }
+                other => {
+                    return Err(unsupported(format!(
+                        "method '{}' is not supported (expected tag, path, fqn, \
+                         config.materialized, config.schema or selector)",
+                        other
+                    )))
+                }
+            };
+            Ok(vec![vec![selector]])
+        }
+        _ => Err(unsupported(
+            "definition must be a string or a mapping".to_string(),
+        )),
+    }
+}
+
+fn sequence<'a>(value: &'a Value, key: &str) -> std::result::Result<&'a [Value], String> {
+    value
+        .as_sequence()
+        .map(Vec::as_slice)
+        .ok_or_else(|| format!("'{}' must be a list", key))
+}
+
+/// A string, number or boolean value as text (`value: 2024` is a fine tag)
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn invalid(message: String) -> anyhow::Error {
+    DbtLineageError::InvalidArgument(message).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Vec<NamedSelector> {
+        serde_yaml::from_str::<SelectorsFile>(yaml)
+            .unwrap()
+            .selectors
+    }
+
+    const SELECTORS: &str = r#"
+selectors:
+  - name: nightly
+    description: Everything that runs overnight
+    definition:
+      union:
+        - method: tag
+          value: nightly
+        - intersection:
+            - method: path
+              value: models/marts
+            - tag: finance
+  - name: marts_tables
+    definition:
+      intersection:
+        - union:
+            - method: config.materialized
+              value: table
+            - method: config.materialized
+              value: incremental
+        - method: path
+          value: models/marts
+  - name: orders_only
+    definition: "orders tag:core,config.schema:analytics"
+  - name: nested
+    definition:
+      union:
+        - method: selector
+          value: orders_only
+        - method: fqn
+          value: customers
+"#;
+
+    #[test]
+    fn test_union_and_intersection() {
+        let selectors = parse(SELECTORS);
+        assert_eq!(
+            selectors[0].description.as_deref(),
+            Some("Everything that runs overnight")
+        );
+        assert_eq!(
+            resolve_selector(&selectors, "nightly").unwrap(),
+            vec![
+                vec![Selector::Tag("nightly".into())],
+                vec![
+                    Selector::Path("models/marts".into()),
+                    Selector::Tag("finance".into())
+                ],
+            ]
+        );
+        // Intersection distributes over the union inside it
+        assert_eq!(
+            resolve_selector(&selectors, "marts_tables").unwrap(),
+            vec![
+                vec![
+                    Selector::Materialized("table".into()),
+                    Selector::Path("models/marts".into())
+                ],
+                vec![
+                    Selector::Materialized("incremental".into()),
+                    Selector::Path("models/marts".into())
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_definitions_and_references() {
+        let selectors = parse(SELECTORS);
+        assert_eq!(
+            resolve_selector(&selectors, "nested").unwrap(),
+            vec![
+                vec![Selector::ModelName("orders".into())],
+                vec![
+                    Selector::Tag("core".into()),
+                    Selector::Schema("analytics".into())
+                ],
+                vec![Selector::ModelName("customers".into())],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_definitions() {
+        let error = |yaml: &str, name: &str| {
+            resolve_selector(&parse(yaml), name)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(SELECTORS, "weekly"),
+            "no selector named 'weekly' in selectors.yml \
+             (defined: marts_tables, nested, nightly, orders_only)"
+        );
+        let yaml = r#"
+selectors:
+  - name: a
+    definition: {method: source_status, value: fresher}
+  - name: b
+    definition: {union: [{method: tag, value: x}, {exclude: [{method: tag, value: y}]}]}
+  - name: c
+    definition: {method: selector, value: d}
+  - name: d
+    definition: {method: selector, value: c}
+"#;
+        assert!(error(yaml, "a").contains("method 'source_status' is not supported"));
+        assert_eq!(error(yaml, "b"), "selector 'b': 'exclude' is not supported");
+        assert_eq!(
+            error(yaml, "c"),
+            "selector 'c' refers to itself through c -> d"
+        );
+    }
+
+    #[test]
+    fn test_load_named_selector() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load_named_selector(tmp.path(), "nightly").is_err());
+        std::fs::write(tmp.path().join("selectors.yml"), SELECTORS).unwrap();
+        assert_eq!(load_named_selector(tmp.path(), "nightly").unwrap().len(), 2);
+    }
+}
@@ -1,6 +1,9 @@
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
+use crate::graph::types::{string_list, SnapshotConfig};
+
 /// A reference to another dbt model via ref()
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RefCall {
@@ -8,6 +11,8 @@ pub struct RefCall {
     pub package: Option<String>,
     /// Model name
     pub name: String,
+    /// Pinned model version from `ref('name', v=2)` / `version=2`
+    pub version: Option<String>,
 }
 
 /// A reference to a dbt source via source()
@@ -21,7 +26,8 @@ pub struct SourceCall {
 
 static JINJA_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{#[\s\S]*?#\}").unwrap());
 
-// Matches ref('name'), ref("name"), ref('pkg', 'name'), ref("pkg", "name")
+// Matches ref('name'), ref("name"), ref('pkg', 'name'), ref("pkg", "name"),
+// optionally followed by a version kwarg: ref('name', v=2), ref('name', version='2')
 // Handles {{ ref(...) }} and {{- ref(...) -}} whitespace control
 static REF_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
@@ -35,6 +41,8 @@ static REF_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
             # Single-argument form: ref('name') or ref("name")
             ['"]([^'"]+)['"]
         )
+        # Optional version kwarg: v=2, version=2, v='2'
+        (?:\s*,\s*(?:v|version)\s*=\s*['"]?([A-Za-z0-9_.]+)['"]?)?
         \s*\)\s*
         -?\}\}
     "#,
@@ -42,43 +50,74 @@ static REF_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
     .unwrap()
 });
 
-// Matches source('src_name', 'table_name')
+// Matches source(...) with its argument list, e.g. source('src_name', 'table_name');
+// the arguments are interpreted by `parse_source_args`
 static SOURCE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
         r#"(?x)
         \{\{-?\s*
-        source\s*\(\s*
-        ['"]([^'"]+)['"]\s*,\s*['"]([^'"]+)['"]
-        \s*\)\s*
+        source\s*\(([^()]*)\)\s*
         -?\}\}
     "#,
     )
     .unwrap()
 });
 
-/// Strip Jinja comments from SQL content
+// One source() argument: a quoted string, optionally passed as `keyword=`
+static SOURCE_ARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*(?:(\w+)\s*=\s*)?['"]([^'"]+)['"]\s*$"#).unwrap());
+
+/// Strip Jinja comments from SQL content, keeping their line breaks so line
+/// numbers still match the original file
 fn strip_jinja_comments(sql: &str) -> String {
-    JINJA_COMMENT.replace_all(sql, "").to_string()
+    JINJA_COMMENT
+        .replace_all(sql, |caps: &regex::Captures| {
+            "\n".repeat(caps[0].matches('\n').count())
+        })
+        .to_string()
+}
+
+/// 1-based line number of byte offset `pos` in `text`
+fn line_of(text: &str, pos: usize) -> usize {
+    text[..pos].matches('\n').count() + 1
 }
 
 /// Extract all ref() calls from SQL content
 pub fn extract_refs(sql: &str) -> Vec<RefCall> {
+    extract_refs_with_lines(sql)
+        .into_iter()
+        .map(|(call, _)| call)
+        .collect()
+}
+
+/// Extract all ref() calls with the 1-based line each call starts on
+pub fn extract_refs_with_lines(sql: &str) -> Vec<(RefCall, usize)> {
     let cleaned = strip_jinja_comments(sql);
     let mut refs = Vec::new();
 
     for cap in REF_PATTERN.captures_iter(&cleaned) {
+        let line = line_of(&cleaned, cap.get(0).unwrap().start());
+        let version = cap.get(4).map(|v| v.as_str().to_string());
         if let (Some(pkg), Some(name)) = (cap.get(1), cap.get(2)) {
             // Two-argument form
-            refs.push(RefCall {
-                package: Some(pkg.as_str().to_string()),
-                name: name.as_str().to_string(),
-            });
+            refs.push((
+                RefCall {
+                    package: Some(pkg.as_str().to_string()),
+                    name: name.as_str().to_string(),
+                    version: version.clone(),
+                },
+                line,
+            ));
         } else if let Some(name) = cap.get(3) {
             // Single-argument form
-            refs.push(RefCall {
-                package: None,
-                name: name.as_str().to_string(),
-            });
+            refs.push((
+                RefCall {
+                    package: None,
+                    name: name.as_str().to_string(),
+                    version,
+                },
+                line,
+            ));
         }
     }
 
@@ -87,79 +126,448 @@ pub fn extract_refs(sql: &str) -> Vec<RefCall> {
 
 /// Extract all source() calls from SQL content
 pub fn extract_sources(sql: &str) -> Vec<SourceCall> {
+    extract_sources_with_lines(sql)
+        .into_iter()
+        .map(|(call, _)| call)
+        .collect()
+}
+
+/// Extract all source() calls with the 1-based line each call starts on
+pub fn extract_sources_with_lines(sql: &str) -> Vec<(SourceCall, usize)> {
     let cleaned = strip_jinja_comments(sql);
     let mut sources = Vec::new();
 
     for cap in SOURCE_PATTERN.captures_iter(&cleaned) {
-        sources.push(SourceCall {
-            source_name: cap[1].to_string(),
-            table_name: cap[2].to_string(),
-        });
+        if let Some(call) = parse_source_args(&cap[1]) {
+            sources.push((call, line_of(&cleaned, cap.get(0).unwrap().start())));
+        }
     }
 
     sources
 }
 
-/// Parsed config block from SQL
-#[derive(Debug, Clone, Default)]
-pub struct SqlConfig {
-    pub materialized: Option<String>,
-    pub tags: Vec<String>,
+/// Interpret the arguments of a `source(...)` call:
+/// - `'src', 'table'` — the standard form
+/// - `'db', 'src', 'table'` — a database-qualified form; the database is ignored
+/// - `source_name='src', table_name='table'` — keyword arguments, which may be
+///   mixed with positional ones
+///
+/// Other keyword arguments (e.g. `database=`) are ignored. Returns `None` when
+/// an argument is not a quoted string or the source or table is missing.
+pub(crate) fn parse_source_args(args: &str) -> Option<SourceCall> {
+    let mut positional = Vec::new();
+    let mut source_name = None;
+    let mut table_name = None;
+    for arg in args.split(',').filter(|a| !a.trim().is_empty()) {
+        let cap = SOURCE_ARG.captures(arg)?;
+        let value = cap[2].to_string();
+        match cap.get(1).map(|k| k.as_str()) {
+            None => positional.push(value),
+            Some("source_name") => source_name = Some(value),
+            Some("table_name") => table_name = Some(value),
+            Some(_) => {}
+        }
+    }
+
+    // The last two positional arguments are the source and the table
+    let mut positional = positional.into_iter().rev();
+    let table_name = table_name.or_else(|| positional.next())?;
+    let source_name = source_name.or_else(|| positional.next())?;
+    Some(SourceCall {
+        source_name,
+        table_name,
+    })
+}
+
+// Start of a ref()/source() call inside a Jinja expression, whether or not its
+// arguments can be parsed
+static CALL_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{-?\s*(ref|source)\s*\(").unwrap());
+
+// Jinja expressions and statements, SQL comments and string literals: the parts
+// of a file whose parentheses are not SQL grouping
+static NON_SQL_SPAN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{[\s\S]*?\}\}|\{%[\s\S]*?%\}|--[^\n]*|/\*[\s\S]*?\*/|'(?:[^']|'')*'").unwrap()
+});
+
+/// Problems that keep SQL content from being parsed reliably, each with the
+/// line it occurs on: unclosed Jinja tags, ref()/source() calls whose
+/// arguments are not plain strings, and unbalanced parentheses. Empty for
+/// well-formed files.
+pub fn find_parse_problems(sql: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    // Unclosed Jinja tags hide everything after them, so report the first one only
+    let mut pos = 0;
+    while let Some(offset) = sql[pos..].find('{') {
+        let start = pos + offset;
+        let closer = match sql[start + 1..].chars().next() {
+            Some('{') => "}}",
+            Some('%') => "%}",
+            Some('#') => "#}",
+            _ => {
+                pos = start + 1;
+                continue;
+            }
+        };
+        match sql[start + 2..].find(closer) {
+            Some(end) => pos = start + 2 + end + 2,
+            None => {
+                problems.push(format!(
+                    "unclosed `{}` on line {}",
+                    &sql[start..start + 2],
+                    line_of(sql, start)
+                ));
+                return problems;
+            }
+        }
+    }
+
+    let cleaned = strip_jinja_comments(sql);
+    let parsed: std::collections::HashSet<usize> = REF_PATTERN
+        .find_iter(&cleaned)
+        .map(|m| m.start())
+        .chain(
+            SOURCE_PATTERN
+                .captures_iter(&cleaned)
+                .filter(|cap| parse_source_args(&cap[1]).is_some())
+                .map(|cap| cap.get(0).unwrap().start()),
+        )
+        .collect();
+    for cap in CALL_START.captures_iter(&cleaned) {
+        let start = cap.get(0).unwrap().start();
+        if !parsed.contains(&start) {
+            problems.push(format!(
+                "{}() call on line {} has arguments other than quoted strings",
+                &cap[1],
+                line_of(&cleaned, start)
+            ));
+        }
+    }
+
+    let sql_only = NON_SQL_SPAN.replace_all(&cleaned, |caps: &regex::Captures| {
+        caps[0]
+            .chars()
+            .map(|c| if c == '\n' { '\n' } else { ' ' })
+            .collect::<String>()
+    });
+    let mut open_lines = Vec::new();
+    let mut line = 1;
+    for c in sql_only.chars() {
+        match c {
+            '\n' => line += 1,
+            '(' => open_lines.push(line),
+            ')' if open_lines.pop().is_none() => {
+                problems.push(format!("unmatched `)` on line {}", line));
+                return problems;
+            }
+            _ => {}
+        }
+    }
+    if let Some(line) = open_lines.first() {
+        problems.push(format!("unclosed `(` on line {}", line));
+    }
+
+    problems
 }
 
-// Matches {{ config(...) }} blocks — captures the inner arguments
-static CONFIG_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+// `{% test not_negative(model, column_name) %}`: the opening of a generic test definition
+static TEST_BLOCK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{%-?\s*test\s+\w+\s*\(").unwrap());
+
+/// Whether the file defines a generic test with a `{% test %}` block
+pub fn defines_generic_test(sql: &str) -> bool {
+    TEST_BLOCK.is_match(sql)
+}
+
+// Matches a schema-qualified relation after FROM/JOIN in compiled SQL, e.g.
+// `"analytics"."staging"."stg_orders"` or `staging.stg_orders`. Unqualified names
+// are skipped: in compiled SQL those are CTEs, not dbt relations.
+static COMPILED_RELATION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r#"(?x)
-        \{\{-?\s*
-        config\s*\(
-        ([\s\S]*?)
-        \)\s*
-        -?\}\}
+        r#"(?ix)
+        \b(?:from|join)\s+
+        (
+            (?:"[^"]+"|`[^`]+`|\[[^\]]+\]|[a-z_][\w$]*)
+            (?:\s*\.\s*(?:"[^"]+"|`[^`]+`|\[[^\]]+\]|[a-z_][\w$]*))+
+        )
     "#,
     )
     .unwrap()
 });
 
-// Matches materialized='value' or materialized="value"
-static MATERIALIZED_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r#"materialized\s*=\s*['"]([^'"]+)['"]"#).unwrap());
-
-// Matches tags=['a', 'b'] or tags=["a", "b"]
-static TAGS_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r#"tags\s*=\s*\[([^\]]*)\]"#).unwrap());
+// One part of a dotted relation name, quoted or bare
+static RELATION_PART: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"]+)"|`([^`]+)`|\[([^\]]+)\]|([A-Za-z_][\w$]*)"#).unwrap());
+
+static SQL_COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"--[^\n]*|/\*[\s\S]*?\*/").unwrap());
+
+/// Extract the schema-qualified relations read in compiled SQL (`FROM`/`JOIN`
+/// targets), each split into its unquoted parts, in order of appearance
+pub fn extract_compiled_relations(sql: &str) -> Vec<Vec<String>> {
+    let cleaned = SQL_COMMENT.replace_all(sql, "");
+    COMPILED_RELATION_PATTERN
+        .captures_iter(&cleaned)
+        .map(|cap| {
+            RELATION_PART
+                .captures_iter(&cap[1])
+                .filter_map(|part| (1..=4).find_map(|i| part.get(i)))
+                .map(|m| m.as_str().to_string())
+                .collect()
+        })
+        .collect()
+}
 
-// Matches individual tag values inside the tags list
-static TAG_VALUE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"['"]([^'"]+)['"]"#).unwrap());
+/// Parsed config block from SQL
+#[derive(Debug, Clone, Default)]
+pub struct SqlConfig {
+    pub materialized: Option<String>,
+    /// Custom schema (`schema='marts'`)
+    pub schema: Option<String>,
+    pub tags: Vec<String>,
+    /// `meta={...}`, with nested dicts and lists kept as JSON values
+    pub meta: BTreeMap<String, serde_json::Value>,
+    /// `strategy`, `unique_key`, `updated_at` and `check_cols`, set in snapshots
+    pub snapshot: SnapshotConfig,
+}
 
-/// Extract config() block settings from SQL content
+// Start of a {{ config(...) }} block; the arguments run to the matching `)`
+static CONFIG_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{-?\s*config\s*\(").unwrap());
+
+/// Extract config() block settings from SQL content.
+///
+/// Arguments may span lines and contain nested lists, dicts and calls, and
+/// strings with commas or brackets in them. `materialized`, `schema`, `tags`
+/// (a list or a single string), `meta` and the snapshot settings are read when
+/// their values are literals; every other argument, and values such as
+/// `var(...)`, are ignored.
 pub fn extract_config(sql: &str) -> SqlConfig {
     let cleaned = strip_jinja_comments(sql);
     let mut config = SqlConfig::default();
 
-    if let Some(cap) = CONFIG_PATTERN.captures(&cleaned) {
-        let inner = &cap[1];
-
-        if let Some(mat) = MATERIALIZED_PATTERN.captures(inner) {
-            config.materialized = Some(mat[1].to_string());
+    let Some(start) = CONFIG_START.find(&cleaned) else {
+        return config;
+    };
+    let Some(args) = split_call_args(&cleaned[start.end()..]) else {
+        return config;
+    };
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            continue;
+        };
+        let value = parse_literal(value)
+            .filter(|(_, rest)| rest.trim().is_empty())
+            .map(|(value, _)| value);
+        match (key.trim(), value) {
+            ("materialized", Some(serde_json::Value::String(m))) => config.materialized = Some(m),
+            ("schema", Some(serde_json::Value::String(s))) => config.schema = Some(s),
+            ("tags", Some(serde_json::Value::String(tag))) => config.tags = vec![tag],
+            ("tags", Some(serde_json::Value::Array(tags))) => {
+                config.tags = tags
+                    .into_iter()
+                    .filter_map(|t| match t {
+                        serde_json::Value::String(t) => Some(t),
+                        _ => None,
+                    })
+                    .collect();
+            }
+            ("meta", Some(serde_json::Value::Object(meta))) => {
+                config.meta = meta.into_iter().collect();
+            }
+            ("strategy", Some(serde_json::Value::String(s))) => config.snapshot.strategy = Some(s),
+            ("unique_key", Some(keys)) => config.snapshot.unique_key = string_list(&keys),
+            ("updated_at", Some(serde_json::Value::String(s))) => {
+                config.snapshot.updated_at = Some(s)
+            }
+            ("check_cols", Some(cols)) => config.snapshot.check_cols = string_list(&cols),
+            _ => {}
         }
+    }
 
-        if let Some(tags_cap) = TAGS_PATTERN.captures(inner) {
-            let tags_inner = &tags_cap[1];
-            config.tags = TAG_VALUE
-                .captures_iter(tags_inner)
-                .map(|c| c[1].to_string())
-                .collect();
+    config
+}
+
+/// Top-level arguments of a call whose `(` has just been consumed, split on
+/// the commas that are outside brackets and quoted strings. `None` when the
+/// call is never closed.
+fn split_call_args(s: &str) -> Option<Vec<&str>> {
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut arg_start = 0;
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')') if depth == 0 => {
+                args.push(&s[arg_start..i]);
+                args.retain(|a| !a.trim().is_empty());
+                return Some(args);
+            }
+            (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                args.push(&s[arg_start..i]);
+                arg_start = i + 1;
+            }
+            _ => {}
         }
     }
+    None
+}
 
-    config
+/// Parse a Jinja literal at the start of `s`: a quoted string, number,
+/// boolean, `none`, list, tuple or dict with string keys. Returns the value and
+/// the rest of `s`, or `None` for anything else (e.g. a `var()` call).
+fn parse_literal(s: &str) -> Option<(serde_json::Value, &str)> {
+    use serde_json::Value;
+
+    let s = s.trim_start();
+    let first = s.chars().next()?;
+    match first {
+        '\'' | '"' => {
+            let mut text = String::new();
+            let mut chars = s.char_indices().skip(1);
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        let (_, escaped) = chars.next()?;
+                        text.push(match escaped {
+                            'n' => '\n',
+                            't' => '\t',
+                            other => other,
+                        });
+                    }
+                    c if c == first => return Some((Value::String(text), &s[i + 1..])),
+                    c => text.push(c),
+                }
+            }
+            None
+        }
+        '[' | '(' => {
+            let close = if first == '[' { ']' } else { ')' };
+            let mut items = Vec::new();
+            let mut rest = &s[1..];
+            loop {
+                rest = rest.trim_start();
+                if let Some(after) = rest.strip_prefix(close) {
+                    return Some((Value::Array(items), after));
+                }
+                let (item, after) = parse_literal(rest)?;
+                items.push(item);
+                rest = after.trim_start();
+                if let Some(after) = rest.strip_prefix(',') {
+                    rest = after;
+                } else if !rest.starts_with(close) {
+                    return None;
+                }
+            }
+        }
+        '{' => {
+            let mut map = serde_json::Map::new();
+            let mut rest = &s[1..];
+            loop {
+                rest = rest.trim_start();
+                if let Some(after) = rest.strip_prefix('}') {
+                    return Some((Value::Object(map), after));
+                }
+                let (Value::String(key), after) = parse_literal(rest)? else {
+                    return None;
+                };
+                let after = after.trim_start().strip_prefix(':')?;
+                let (value, after) = parse_literal(after)?;
+                map.insert(key, value);
+                rest = after.trim_start();
+                if let Some(after) = rest.strip_prefix(',') {
+                    rest = after;
+                } else if !rest.starts_with('}') {
+                    return None;
+                }
+            }
+        }
+        _ => {
+            let end = s
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')))
+                .unwrap_or(s.len());
+            let (word, rest) = s.split_at(end);
+            let value = match word {
+                "True" | "true" => Value::Bool(true),
+                "False" | "false" => Value::Bool(false),
+                "None" | "none" => Value::Null,
+                _ => Value::Number(serde_json::from_str(word).ok()?),
+            };
+            Some((value, rest))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_defines_generic_test() {
+        assert!(defines_generic_test(
+            "{% test not_negative(model, column_name) %}\nSELECT * FROM {{ model }}\n{% endtest %}"
+        ));
+        assert!(defines_generic_test("{%- test is_even(model) -%}"));
+        assert!(!defines_generic_test(
+            "-- test that amounts are positive\nSELECT * FROM {{ ref('orders') }}"
+        ));
+    }
+
+    #[test]
+    fn test_find_parse_problems_well_formed() {
+        let sql = "-- count (open paren in a comment\n\
+                   SELECT count(*), ')' AS s\n\
+                   FROM {{ ref('orders') }}\n\
+                   JOIN {{ source('raw', 'customers') }} USING (id)\n\
+                   {% if is_incremental() %} WHERE x > (SELECT 1) {% endif %}";
+        assert!(find_parse_problems(sql).is_empty());
+    }
+
+    #[test]
+    fn test_find_parse_problems_unclosed_jinja() {
+        let sql = "SELECT *\nFROM {{ ref('orders') }}\nWHERE {{ var('x'\n";
+        assert_eq!(find_parse_problems(sql), vec!["unclosed `{{` on line 3"]);
+        assert_eq!(
+            find_parse_problems("{% if x %}\nSELECT 1\n{% endif"),
+            vec!["unclosed `{%` on line 3"]
+        );
+    }
+
+    #[test]
+    fn test_find_parse_problems_dynamic_ref() {
+        let sql =
+            "SELECT *\nFROM {{ ref(model_name) }}\nJOIN {{ source(src, 'orders') }} USING (id)";
+        assert_eq!(
+            find_parse_problems(sql),
+            vec![
+                "ref() call on line 2 has arguments other than quoted strings",
+                "source() call on line 3 has arguments other than quoted strings",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_parse_problems_unbalanced_parens() {
+        assert_eq!(
+            find_parse_problems("SELECT (a + b\nFROM t"),
+            vec!["unclosed `(` on line 1"]
+        );
+        assert_eq!(
+            find_parse_problems("SELECT a)\nFROM (t)"),
+            vec!["unmatched `)` on line 1"]
+        );
+    }
+
     #[test]
     fn test_single_ref() {
         let sql = "SELECT * FROM {{ ref('stg_orders') }}";
@@ -186,6 +594,32 @@ mod tests {
         assert_eq!(refs[0].name, "stg_orders");
     }
 
+    #[test]
+    fn test_versioned_ref() {
+        let sql = "SELECT * FROM {{ ref('dim_customers', v=2) }}";
+        let refs = extract_refs(sql);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].name, "dim_customers");
+        assert_eq!(refs[0].version.as_deref(), Some("2"));
+        assert!(refs[0].package.is_none());
+    }
+
+    #[test]
+    fn test_versioned_ref_version_kwarg_quoted() {
+        let sql = r#"SELECT * FROM {{ ref("pkg", "dim_customers", version="3") }}"#;
+        let refs = extract_refs(sql);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].package.as_deref(), Some("pkg"));
+        assert_eq!(refs[0].name, "dim_customers");
+        assert_eq!(refs[0].version.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_unversioned_ref_has_no_version() {
+        let refs = extract_refs("SELECT * FROM {{ ref('stg_orders') }}");
+        assert!(refs[0].version.is_none());
+    }
+
     #[test]
     fn test_whitespace_control() {
         let sql = "SELECT * FROM {{- ref('stg_orders') -}}";
@@ -226,6 +660,46 @@ mod tests {
         assert_eq!(sources[0].source_name, "raw");
     }
 
+    #[test]
+    fn test_source_database_qualified() {
+        let sql = "SELECT * FROM {{ source('analytics_db', 'raw', 'orders') }}";
+        assert_eq!(
+            extract_sources(sql),
+            vec![SourceCall {
+                source_name: "raw".into(),
+                table_name: "orders".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_source_keyword_args() {
+        let sql = r#"
+            SELECT * FROM {{ source(source_name="raw", table_name="orders") }}
+            JOIN {{ source('raw', table_name='customers') }} USING (id)
+            JOIN {{ source(table_name='payments', source_name='raw', database='prod',) }} USING (id)
+        "#;
+        let tables: Vec<(String, String)> = extract_sources(sql)
+            .into_iter()
+            .map(|s| (s.source_name, s.table_name))
+            .collect();
+        assert_eq!(
+            tables,
+            vec![
+                ("raw".to_string(), "orders".to_string()),
+                ("raw".to_string(), "customers".to_string()),
+                ("raw".to_string(), "payments".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_malformed_args_skipped() {
+        assert!(extract_sources("{{ source('raw') }}").is_empty());
+        assert!(extract_sources("{{ source(var('src'), 'orders') }}").is_empty());
+        assert!(parse_source_args("'raw', other").is_none());
+    }
+
     #[test]
     fn test_strip_jinja_comments() {
         let sql = r#"
@@ -257,6 +731,18 @@ mod tests {
         assert!(refs.is_empty());
     }
 
+    #[test]
+    fn test_call_lines_survive_jinja_comments() {
+        let sql = "{# header\n   comment #}\nSELECT *\nFROM {{ ref('a') }}\nJOIN {{ source('raw', 'b') }}";
+        let refs = extract_refs_with_lines(sql);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].0.name, "a");
+        assert_eq!(refs[0].1, 4);
+        let sources = extract_sources_with_lines(sql);
+        assert_eq!(sources[0].0.table_name, "b");
+        assert_eq!(sources[0].1, 5);
+    }
+
     #[test]
     fn test_extra_spaces() {
         let sql = "SELECT * FROM {{  ref(  'stg_orders'  )  }}";
@@ -297,6 +783,38 @@ mod tests {
         assert_eq!(config.tags, vec!["daily"]);
     }
 
+    #[test]
+    fn test_config_schema() {
+        let sql = "{{ config(materialized='table', schema='marts') }}\nSELECT 1";
+        assert_eq!(extract_config(sql).schema.as_deref(), Some("marts"));
+        // A computed schema isn't known until dbt renders it
+        let sql = "{{ config(schema=var('target_schema')) }}\nSELECT 1";
+        assert_eq!(extract_config(sql).schema, None);
+    }
+
+    #[test]
+    fn test_extract_config_snapshot() {
+        let sql = "{% snapshot snap_orders %}\n\
+                   {{ config(target_schema='snapshots', strategy='timestamp', \
+                   unique_key='id', updated_at='updated_at') }}\n\
+                   select * from {{ ref('orders') }}\n\
+                   {% endsnapshot %}";
+        let snapshot = extract_config(sql).snapshot;
+        assert_eq!(snapshot.strategy.as_deref(), Some("timestamp"));
+        assert_eq!(snapshot.unique_key, vec!["id"]);
+        assert_eq!(snapshot.updated_at.as_deref(), Some("updated_at"));
+        assert!(snapshot.check_cols.is_empty());
+
+        let sql = "{{ config(strategy='check', unique_key=['a', 'b'], check_cols=['status', 'amount']) }}";
+        let snapshot = extract_config(sql).snapshot;
+        assert_eq!(snapshot.unique_key, vec!["a", "b"]);
+        assert_eq!(snapshot.check_cols, vec!["status", "amount"]);
+
+        assert!(extract_config("{{ config(materialized='table') }}")
+            .snapshot
+            .is_empty());
+    }
+
     #[test]
     fn test_config_whitespace_control() {
         let sql = "{{- config(materialized='ephemeral') -}}\nSELECT 1";
@@ -318,6 +836,57 @@ mod tests {
         assert_eq!(config.tags, vec!["nightly", "warehouse"]);
     }
 
+    #[test]
+    fn test_config_nested_multiline_block() {
+        let sql = r#"{{
+            config(
+                materialized = "incremental",
+                unique_key = ['order_id', 'order_date'],
+                incremental_strategy = 'merge',
+                meta = {
+                    'owner': 'finance-team',
+                    'contact': {'slack': '#data, #finance', "oncall": True},
+                    'sla_hours': 6,
+                    'sources': ['erp', 'crm'],
+                },
+                post_hook = [
+                    "grant select on {{ this }} to role reporter",
+                    "analyze {{ this }} (tags=['not', 'these'], materialized='view')",
+                ],
+                partition_by = {'field': 'order_date', 'data_type': 'date'},
+                tags = ["finance", 'daily, late'],
+            )
+        }}
+        SELECT 1"#;
+        let config = extract_config(sql);
+        assert_eq!(config.materialized.as_deref(), Some("incremental"));
+        assert_eq!(config.tags, vec!["finance", "daily, late"]);
+        assert_eq!(config.meta["owner"], "finance-team");
+        assert_eq!(
+            config.meta["contact"],
+            serde_json::json!({"slack": "#data, #finance", "oncall": true})
+        );
+        assert_eq!(config.meta["sla_hours"], 6);
+        assert_eq!(config.meta["sources"], serde_json::json!(["erp", "crm"]));
+        assert_eq!(config.meta.len(), 4);
+    }
+
+    #[test]
+    fn test_config_single_tag_and_dynamic_values() {
+        let sql = r#"{{ config(materialized=var('mat', 'table'), tags='nightly', meta={'owner': env_var('OWNER')}) }}"#;
+        let config = extract_config(sql);
+        assert!(config.materialized.is_none());
+        assert_eq!(config.tags, vec!["nightly"]);
+        assert!(config.meta.is_empty());
+    }
+
+    #[test]
+    fn test_config_unclosed_is_ignored() {
+        let config = extract_config("{{ config(materialized='table', tags=['x'] }}\nSELECT 1");
+        assert!(config.materialized.is_none());
+        assert!(config.tags.is_empty());
+    }
+
     #[test]
     fn test_no_config() {
         let sql = "SELECT * FROM {{ ref('orders') }}";
@@ -335,4 +904,32 @@ mod tests {
         let config = extract_config(sql);
         assert!(config.materialized.is_none());
     }
+
+    #[test]
+    fn test_extract_compiled_relations() {
+        let sql = r#"
+with orders as (
+    select * from "analytics"."staging"."stg_orders"
+),
+payments as (select * from `proj`.`raw`.`payments`)
+select * from orders
+left join analytics.dim_customers c using (customer_id)
+join [db].[dbo].[Accounts] a on true
+-- join analytics.commented_out
+/* from analytics.also_commented */
+"#;
+        let relations = extract_compiled_relations(sql);
+        assert_eq!(relations.len(), 4);
+        assert_eq!(relations[0], vec!["analytics", "staging", "stg_orders"]);
+        assert_eq!(relations[1], vec!["proj", "raw", "payments"]);
+        assert_eq!(relations[2], vec!["analytics", "dim_customers"]);
+        assert_eq!(relations[3], vec!["db", "dbo", "Accounts"]);
+    }
+
+    #[test]
+    fn test_extract_compiled_relations_skips_unqualified_names() {
+        assert!(
+            extract_compiled_relations("select * from my_cte join other_cte on true").is_empty()
+        );
+    }
 }
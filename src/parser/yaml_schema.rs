@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
 /// Top-level schema YAML file (can contain sources, models, exposures)
@@ -18,6 +20,9 @@ pub struct SourceDefinition {
     pub name: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Warehouse schema of the tables; dbt defaults it to the source name
+    #[serde(default)]
+    pub schema: Option<String>,
     #[serde(default)]
     pub tables: Vec<SourceTable>,
 }
@@ -40,6 +45,17 @@ pub struct ColumnDefinition {
     pub tests: Vec<TestDefinition>,
 }
 
+/// Non-empty column descriptions keyed by column name
+pub fn column_descriptions(columns: &[ColumnDefinition]) -> BTreeMap<String, String> {
+    columns
+        .iter()
+        .filter_map(|c| {
+            let description = c.description.as_deref()?.trim();
+            (!description.is_empty()).then(|| (c.name.clone(), description.to_string()))
+        })
+        .collect()
+}
+
 /// Tests can be either a string or a map
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
@@ -59,6 +75,60 @@ pub struct ModelDefinition {
     pub config: Option<ModelConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, serde_json::Value>,
+    /// Versions declared for a dbt versioned model
+    #[serde(default)]
+    pub versions: Vec<ModelVersion>,
+    #[serde(default)]
+    pub latest_version: Option<serde_yaml::Value>,
+}
+
+impl ModelDefinition {
+    /// Declared version identifiers as strings (`v: 2` and `v: "2"` both yield `"2"`)
+    pub fn version_ids(&self) -> Vec<String> {
+        self.versions
+            .iter()
+            .filter_map(|v| yaml_scalar_to_string(&v.v))
+            .collect()
+    }
+
+    /// The version an unpinned `ref()` resolves to: `latest_version` if set,
+    /// otherwise the highest declared version
+    pub fn resolved_latest_version(&self) -> Option<String> {
+        if let Some(latest) = self.latest_version.as_ref().and_then(yaml_scalar_to_string) {
+            return Some(latest);
+        }
+        self.version_ids()
+            .into_iter()
+            .max_by(|a, b| compare_versions(a, b))
+    }
+}
+
+/// One entry of a model's `versions:` block
+#[derive(Debug, Deserialize, Clone)]
+pub struct ModelVersion {
+    pub v: serde_yaml::Value,
+    #[serde(default)]
+    pub defined_in: Option<String>,
+}
+
+/// Render a YAML scalar (number or string) as a plain string
+fn yaml_scalar_to_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Order version identifiers numerically when both parse as numbers,
+/// lexically otherwise
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal),
+        _ => a.cmp(b),
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -66,7 +136,11 @@ pub struct ModelConfig {
     #[serde(default)]
     pub materialized: Option<String>,
     #[serde(default)]
+    pub schema: Option<String>,
+    #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -133,6 +207,44 @@ models:
         assert_eq!(schema.models[0].columns.len(), 1);
     }
 
+    #[test]
+    fn test_parse_model_versions() {
+        let yaml = r#"
+models:
+  - name: dim_customers
+    latest_version: 1
+    versions:
+      - v: 1
+      - v: "2"
+        defined_in: dim_customers_next
+"#;
+        let schema = parse_schema_file(yaml).unwrap();
+        let model = &schema.models[0];
+        assert_eq!(model.version_ids(), vec!["1", "2"]);
+        assert_eq!(
+            model.versions[1].defined_in.as_deref(),
+            Some("dim_customers_next")
+        );
+        assert_eq!(model.resolved_latest_version().as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_latest_version_defaults_to_highest() {
+        let yaml = r#"
+models:
+  - name: dim_customers
+    versions:
+      - v: 2
+      - v: 10
+      - v: 3
+"#;
+        let schema = parse_schema_file(yaml).unwrap();
+        assert_eq!(
+            schema.models[0].resolved_latest_version().as_deref(),
+            Some("10")
+        );
+    }
+
     #[test]
     fn test_parse_exposures() {
         let yaml = r#"
@@ -0,0 +1,164 @@
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Timing of a single pipeline stage
+#[derive(Debug, Clone)]
+pub struct StageTiming {
+    pub name: &'static str,
+    pub duration: Duration,
+}
+
+/// Collects per-stage timings for `--profile`. When disabled, `time` just runs the closure.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    enabled: bool,
+    stages: Vec<StageTiming>,
+}
+
+impl Profiler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            stages: Vec::new(),
+        }
+    }
+
+    /// A profiler that records nothing
+    pub fn disabled() -> Self {
+        Self::new(false)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Run `f`, recording its wall-clock time under `name` if profiling is enabled
+    pub fn time<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let start = Instant::now();
+        let result = f();
+        self.record(name, start.elapsed());
+        result
+    }
+
+    /// Record a duration measured elsewhere
+    pub fn record(&mut self, name: &'static str, duration: Duration) {
+        if self.enabled {
+            self.stages.push(StageTiming { name, duration });
+        }
+    }
+
+    pub fn stages(&self) -> &[StageTiming] {
+        &self.stages
+    }
+
+    pub fn total(&self) -> Duration {
+        self.stages.iter().map(|s| s.duration).sum()
+    }
+
+    /// The stage that took the longest, if any were recorded
+    pub fn slowest(&self) -> Option<&StageTiming> {
+        self.stages.iter().max_by_key(|s| s.duration)
+    }
+
+    /// Print the timing breakdown to stderr (no-op when disabled)
+    pub fn report(&self) {
+        if self.enabled {
+            self.report_to_writer(&mut std::io::stderr());
+        }
+    }
+
+    pub fn report_to_writer<W: Write>(&self, w: &mut W) {
+        let total = self.total();
+        writeln!(w, "Profile:").unwrap();
+        for stage in &self.stages {
+            writeln!(
+                w,
+                "  {:<20} {:>10.2}ms {:>5.1}%",
+                stage.name,
+                millis(stage.duration),
+                percent(stage.duration, total)
+            )
+            .unwrap();
+        }
+        writeln!(w, "  {:<20} {:>10.2}ms", "total", millis(total)).unwrap();
+
+        // A single dominant stage usually means the graph is bigger than needed
+        if let Some(slowest) = self.slowest() {
+            if self.stages.len() > 1 && percent(slowest.duration, total) >= 50.0 {
+                writeln!(
+                    w,
+                    "Hint: '{}' dominates; narrow the graph with --select or a focus model",
+                    slowest.name
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn percent(part: Duration, total: Duration) -> f64 {
+    if total.is_zero() {
+        0.0
+    } else {
+        part.as_secs_f64() / total.as_secs_f64() * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_records_nothing() {
+        let mut p = Profiler::disabled();
+        let v = p.time("stage", || 42);
+        assert_eq!(v, 42);
+        p.record("other", Duration::from_millis(5));
+        assert!(p.stages().is_empty());
+        assert!(!p.is_enabled());
+    }
+
+    #[test]
+    fn test_enabled_records_stages_in_order() {
+        let mut p = Profiler::new(true);
+        p.time("first", || ());
+        p.record("second", Duration::from_millis(3));
+        let names: Vec<_> = p.stages().iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["first", "second"]);
+        assert!(p.total() >= Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_report_with_hint() {
+        let mut p = Profiler::new(true);
+        p.record("sql edges", Duration::from_millis(90));
+        p.record("render", Duration::from_millis(10));
+        let mut buf = Vec::new();
+        p.report_to_writer(&mut buf);
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.starts_with("Profile:"));
+        assert!(out.contains("sql edges"));
+        assert!(out.contains("90.00ms"));
+        assert!(out.contains("total"));
+        assert!(out.contains("Hint: 'sql edges' dominates"));
+    }
+
+    #[test]
+    fn test_report_without_hint_when_balanced() {
+        let mut p = Profiler::new(true);
+        p.record("a", Duration::from_millis(10));
+        p.record("b", Duration::from_millis(10));
+        p.record("c", Duration::from_millis(10));
+        let mut buf = Vec::new();
+        p.report_to_writer(&mut buf);
+        let out = String::from_utf8(buf).unwrap();
+        assert!(!out.contains("Hint"));
+    }
+}
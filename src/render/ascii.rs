@@ -1,23 +1,28 @@
 use std::io::Write;
 
 use colored::Colorize;
+use petgraph::stable_graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use petgraph::Direction;
+use std::collections::HashSet;
 
+use crate::graph::filter::ALL_NODE_TYPES;
 use crate::graph::types::*;
 
-use super::layout::{sugiyama_layout, LayoutResult};
+use super::layout::{sugiyama_layout_wrapped, LayoutResult};
+use super::RenderOptions;
 
 /// Warn if the graph layout is wider than the terminal
 #[cfg(not(tarpaulin_include))]
-fn warn_if_too_wide(graph: &LineageGraph) {
+fn warn_if_too_wide(graph: &LineageGraph, options: &RenderOptions) {
     if graph.node_count() == 0 {
         return;
     }
-    let layout = sugiyama_layout(graph);
+    let layout = sugiyama_layout_wrapped(graph, options.max_layer_width);
     if layout.num_layers == 0 {
         return;
     }
-    let col_widths = calculate_column_widths(graph, &layout);
+    let col_widths = calculate_column_widths(graph, &layout, options);
     let col_spacing = 4;
     let total_width: usize =
         col_widths.iter().sum::<usize>() + col_spacing * col_widths.len().saturating_sub(1);
@@ -34,10 +39,17 @@ fn warn_if_too_wide(graph: &LineageGraph) {
 /// Render the lineage graph as ASCII art to stdout
 #[cfg(not(tarpaulin_include))]
 pub fn render_ascii(graph: &LineageGraph) {
-    warn_if_too_wide(graph);
+    warn_if_too_wide(graph, &RenderOptions::default());
     render_ascii_to_writer(graph, &mut std::io::stdout().lock());
 }
 
+/// Render ASCII art to stdout with label details
+#[cfg(not(tarpaulin_include))]
+pub fn render_ascii_with_options(graph: &LineageGraph, options: &RenderOptions) {
+    warn_if_too_wide(graph, options);
+    write_ascii(graph, options, &mut std::io::stdout().lock());
+}
+
 /// Compute column x-offsets from column widths and spacing
 fn compute_col_offsets(col_widths: &[usize], spacing: usize) -> Vec<usize> {
     let mut offsets = vec![0usize; col_widths.len()];
@@ -54,6 +66,7 @@ fn render_row(
     row: usize,
     col_widths: &[usize],
     col_offsets: &[usize],
+    options: &RenderOptions,
 ) -> String {
     let mut line = String::new();
     let mut cursor = 0;
@@ -70,17 +83,17 @@ fn render_row(
 
         if row < layer.len() {
             let node = &graph[layer[row]];
-            let display = node.display_name();
-            let box_str = format!("[ {} ]", display);
+            let box_str = format!("[ {} ]", node_text(node, options));
+            let box_width = box_str.chars().count();
             let colored_box = colorize_node(&box_str, node.node_type);
 
-            let padding = col_width.saturating_sub(box_str.len()) / 2;
+            let padding = col_width.saturating_sub(box_width) / 2;
             for _ in 0..padding {
                 line.push(' ');
                 cursor += 1;
             }
             line.push_str(&colored_box);
-            cursor += box_str.len();
+            cursor += box_width;
 
             let remaining = col_start + col_width - cursor;
             for _ in 0..remaining {
@@ -109,21 +122,25 @@ fn format_edge_arrow(edge_type: EdgeType) -> &'static str {
 }
 
 fn render_ascii_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
+    write_ascii(graph, &RenderOptions::default(), w);
+}
+
+fn write_ascii<W: Write>(graph: &LineageGraph, options: &RenderOptions, w: &mut W) {
     if graph.node_count() == 0 {
         writeln!(w, "(empty graph — no nodes to display)").unwrap();
         return;
     }
 
-    let layout = sugiyama_layout(graph);
+    let layout = sugiyama_layout_wrapped(graph, options.max_layer_width);
     if layout.num_layers == 0 {
         return;
     }
 
-    let col_widths = calculate_column_widths(graph, &layout);
+    let col_widths = calculate_column_widths(graph, &layout, options);
     let col_offsets = compute_col_offsets(&col_widths, 4);
 
     for row in 0..layout.max_layer_width {
-        let line = render_row(graph, &layout, row, &col_widths, &col_offsets);
+        let line = render_row(graph, &layout, row, &col_widths, &col_offsets, options);
         writeln!(w, "{}", line.trim_end()).unwrap();
     }
 
@@ -143,11 +160,126 @@ fn render_ascii_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     }
 
     writeln!(w).unwrap();
-    print_legend_to_writer(w);
+    write_footer(
+        graph,
+        colored::control::SHOULD_COLORIZE.should_colorize(),
+        w,
+    );
+}
+
+/// Which way `-o tree` walks from the focus node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeDirection {
+    /// Ancestors: what the node is built from
+    Upstream,
+    /// Descendants: what is built from the node
+    Downstream,
+}
+
+impl TreeDirection {
+    fn label(self) -> &'static str {
+        match self {
+            TreeDirection::Upstream => "upstream",
+            TreeDirection::Downstream => "downstream",
+        }
+    }
+
+    pub(crate) fn edge_direction(self) -> Direction {
+        match self {
+            TreeDirection::Upstream => Direction::Incoming,
+            TreeDirection::Downstream => Direction::Outgoing,
+        }
+    }
+}
+
+/// Render the neighborhood of `root` as indented trees to stdout, one per direction
+#[cfg(not(tarpaulin_include))]
+pub fn render_tree_with_options(
+    graph: &LineageGraph,
+    root: NodeIndex,
+    directions: &[TreeDirection],
+    options: &RenderOptions,
+) {
+    write_tree(
+        graph,
+        root,
+        directions,
+        options,
+        &mut std::io::stdout().lock(),
+    );
+}
+
+/// Write one tree per direction, separated by a blank line. A node reached
+/// again through another branch (a diamond) is printed once more with
+/// `(see above)` and not expanded, so shared ancestors appear in full only once.
+pub fn write_tree<W: Write>(
+    graph: &LineageGraph,
+    root: NodeIndex,
+    directions: &[TreeDirection],
+    options: &RenderOptions,
+    w: &mut W,
+) {
+    for (i, &direction) in directions.iter().enumerate() {
+        if i > 0 {
+            writeln!(w).unwrap();
+        }
+        let node = &graph[root];
+        writeln!(
+            w,
+            "{} ({})",
+            colorize_node(&node_text(node, options), node.node_type),
+            direction.label()
+        )
+        .unwrap();
+        let mut seen = HashSet::from([root]);
+        write_subtree(graph, root, direction, options, "", &mut seen, w);
+    }
+}
+
+fn write_subtree<W: Write>(
+    graph: &LineageGraph,
+    parent: NodeIndex,
+    direction: TreeDirection,
+    options: &RenderOptions,
+    prefix: &str,
+    seen: &mut HashSet<NodeIndex>,
+    w: &mut W,
+) {
+    let mut children: Vec<NodeIndex> = graph
+        .neighbors_directed(parent, direction.edge_direction())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    children.sort_by_key(|&idx| graph[idx].display_name());
+
+    for (i, &child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let node = &graph[child];
+        let name = colorize_node(&node_text(node, options), node.node_type);
+        let branch = if last { "└── " } else { "├── " };
+        if !seen.insert(child) {
+            writeln!(w, "{}{}{} {}", prefix, branch, name, "(see above)".dimmed()).unwrap();
+            continue;
+        }
+        writeln!(w, "{}{}{}", prefix, branch, name).unwrap();
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        write_subtree(graph, child, direction, options, &child_prefix, seen, w);
+    }
+}
+
+/// A node's name, shortened to `--max-label-len` and followed by its label detail
+fn node_text(node: &NodeData, options: &RenderOptions) -> String {
+    options
+        .label_detail
+        .apply(node, options.shorten(node.display_name()))
 }
 
 /// Calculate the width needed for each column (layer)
-fn calculate_column_widths(graph: &LineageGraph, layout: &LayoutResult) -> Vec<usize> {
+fn calculate_column_widths(
+    graph: &LineageGraph,
+    layout: &LayoutResult,
+    options: &RenderOptions,
+) -> Vec<usize> {
     layout
         .layers
         .iter()
@@ -155,9 +287,8 @@ fn calculate_column_widths(graph: &LineageGraph, layout: &LayoutResult) -> Vec<u
             layer
                 .iter()
                 .map(|&idx| {
-                    let node = &graph[idx];
-                    // "[ display_name ]" = display_name.len() + 4
-                    node.display_name().len() + 4
+                    // "[ display_name ]" = display_name chars + 4
+                    node_text(&graph[idx], options).chars().count() + 4
                 })
                 .max()
                 .unwrap_or(0)
@@ -178,20 +309,51 @@ fn colorize_node(text: &str, node_type: NodeType) -> String {
     }
 }
 
-fn print_legend_to_writer<W: Write>(w: &mut W) {
+/// Legend for the node types in the graph, then a one-line count summary.
+/// Each type is shown in its color and with the prefix its names carry; without
+/// colors (`NO_COLOR`, or not a terminal) models are the names with no prefix,
+/// which the legend spells out.
+fn write_footer<W: Write>(graph: &LineageGraph, color: bool, w: &mut W) {
+    let counts: Vec<(NodeType, usize)> = ALL_NODE_TYPES
+        .iter()
+        .map(|&nt| {
+            let count = graph
+                .node_indices()
+                .filter(|&idx| graph[idx].node_type == nt)
+                .count();
+            (nt, count)
+        })
+        .filter(|&(_, count)| count > 0)
+        .collect();
+
+    let entries: Vec<String> = counts
+        .iter()
+        .map(|&(nt, _)| {
+            let entry = match (nt.prefix(), color) {
+                ("", true) => nt.label().to_string(),
+                ("", false) => format!("{} (no prefix)", nt.label()),
+                (prefix, _) => format!("{} ({})", nt.label(), prefix),
+            };
+            colorize_node(&entry, nt)
+        })
+        .collect();
     writeln!(w, "{}", "Legend:".bold()).unwrap();
-    writeln!(
-        w,
-        "  {} {} {} {} {} {} {}",
-        "model".blue().bold(),
-        "source".green(),
-        "seed".yellow(),
-        "snapshot".magenta(),
-        "test".cyan(),
-        "exposure".red(),
-        "phantom".dimmed(),
-    )
-    .unwrap();
+    writeln!(w, "  {}", entries.join("  ")).unwrap();
+
+    let mut summary: Vec<String> = counts
+        .iter()
+        .map(|&(nt, count)| plural(count, nt.label()))
+        .collect();
+    summary.push(plural(graph.edge_count(), "edge"));
+    writeln!(w, "{}", summary.join(", ")).unwrap();
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -236,17 +398,15 @@ unsafe fn libc_ioctl(fd: i32, request: u64, arg: *mut libc_winsize) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::layout::sugiyama_layout;
+    use crate::render::LabelDetail;
 
     fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
         NodeData {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -286,6 +446,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -300,11 +461,36 @@ mod tests {
     }
 
     #[test]
-    fn test_legend() {
-        let mut buf = Vec::new();
-        print_legend_to_writer(&mut buf);
-        let output = String::from_utf8(buf).unwrap();
-        assert!(output.contains("Legend:"));
+    fn test_footer() {
+        let mut graph = LineageGraph::new();
+        let raw = graph.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        let orders = graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        let revenue = graph.add_node(make_node("model.revenue", "revenue", NodeType::Model));
+        for (from, to) in [(raw, orders), (orders, revenue)] {
+            graph.add_edge(
+                from,
+                to,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        let footer = |color: bool| {
+            let mut buf = Vec::new();
+            write_footer(&graph, color, &mut buf);
+            String::from_utf8(buf).unwrap()
+        };
+        assert_eq!(
+            footer(false),
+            "Legend:\n  model (no prefix)  source (src:)\n2 models, 1 source, 2 edges\n"
+        );
+        assert!(footer(true).contains("  model  source (src:)\n"));
     }
 
     #[test]
@@ -339,11 +525,12 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
         let layout = sugiyama_layout(&graph);
-        let widths = calculate_column_widths(&graph, &layout);
+        let widths = calculate_column_widths(&graph, &layout, &RenderOptions::default());
         // Each column width should be at least label.len() + 4
         assert!(widths[0] >= 9); // "short" + 4
         assert!(widths[1] >= 18); // "very_long_name" + 4
@@ -359,6 +546,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -389,6 +577,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -396,6 +585,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -428,6 +618,7 @@ mod tests {
             a,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -435,6 +626,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -442,6 +634,7 @@ mod tests {
             t,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -449,6 +642,7 @@ mod tests {
             e,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
@@ -458,4 +652,112 @@ mod tests {
         assert!(output.contains("──test─>"));
         assert!(output.contains("──exp──>"));
     }
+
+    #[test]
+    fn test_label_detail() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.orders", "orders", NodeType::Model);
+        node.columns = vec!["id".into()];
+        graph.add_node(node);
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            label_detail: LabelDetail::Columns,
+            ..Default::default()
+        };
+        write_ascii(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("orders (1 col)"));
+    }
+
+    #[test]
+    fn test_max_label_len() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node(
+            "model.größenklassen",
+            "größenklassen",
+            NodeType::Model,
+        ));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        graph.add_edge(
+            a,
+            b,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+        let options = RenderOptions {
+            max_label_len: Some(5),
+            ..Default::default()
+        };
+
+        // Widths count characters, so multibyte names don't widen their column
+        let layout = sugiyama_layout(&graph);
+        assert_eq!(
+            calculate_column_widths(&graph, &layout, &options),
+            vec![9, 5]
+        );
+
+        let mut buf = Vec::new();
+        write_ascii(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("[ größ… ]    [ b ]"));
+        // The edge list keeps the full names
+        assert!(output.contains("größenklassen ──ref──> b"));
+
+        let mut buf = Vec::new();
+        write_tree(&graph, b, &[TreeDirection::Upstream], &options, &mut buf);
+        assert!(String::from_utf8(buf).unwrap().contains("└── größ…"));
+    }
+
+    #[test]
+    fn test_tree_marks_shared_ancestors() {
+        let mut graph = LineageGraph::new();
+        let raw = graph.add_node(make_node("source.raw.orders", "orders", NodeType::Source));
+        let stg_a = graph.add_node(make_node("model.stg_a", "stg_a", NodeType::Model));
+        let stg_b = graph.add_node(make_node("model.stg_b", "stg_b", NodeType::Model));
+        let orders = graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        for (from, to) in [(raw, stg_a), (raw, stg_b), (stg_a, orders), (stg_b, orders)] {
+            graph.add_edge(
+                from,
+                to,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        let mut buf = Vec::new();
+        write_tree(
+            &graph,
+            orders,
+            &[TreeDirection::Upstream, TreeDirection::Downstream],
+            &RenderOptions::default(),
+            &mut buf,
+        );
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "orders (upstream)\n\
+             ├── stg_a\n\
+             │   └── src:orders\n\
+             └── stg_b\n\
+             \x20   └── src:orders (see above)\n\
+             \n\
+             orders (downstream)\n"
+        );
+
+        let mut buf = Vec::new();
+        write_tree(
+            &graph,
+            raw,
+            &[TreeDirection::Downstream],
+            &RenderOptions::default(),
+            &mut buf,
+        );
+        let output = String::from_utf8(buf).unwrap();
+        assert_eq!(output.matches("orders").count(), 3);
+        assert!(output.contains("    └── orders (see above)"));
+    }
 }
@@ -0,0 +1,426 @@
+use std::io::Write;
+
+use colored::Colorize;
+
+use crate::graph::check::{BlastRadiusReport, DescriptionReport, LayerReport};
+use crate::graph::sla::SlaReport;
+
+/// Render a blast-radius check report as colored text to stdout
+pub fn render_check_text(report: &BlastRadiusReport) {
+    render_check_text_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_check_text_to_writer<W: Write>(report: &BlastRadiusReport, w: &mut W) {
+    writeln!(w).unwrap();
+    writeln!(
+        w,
+        "{}",
+        format!(
+            "Blast Radius Check: {} → {}",
+            report.base_ref, report.head_ref
+        )
+        .bold()
+    )
+    .unwrap();
+    writeln!(w, "{}", "=".repeat(50)).unwrap();
+    writeln!(
+        w,
+        "  Threshold:       {} downstream nodes",
+        report.threshold
+    )
+    .unwrap();
+    writeln!(w, "  Changed models:  {}", report.checked.len()).unwrap();
+    writeln!(w).unwrap();
+
+    if report.passed() {
+        writeln!(
+            w,
+            "{}",
+            "PASS: no changed model exceeds the blast radius budget".green()
+        )
+        .unwrap();
+        return;
+    }
+
+    writeln!(
+        w,
+        "{}",
+        format!(
+            "FAIL: {} changed model(s) exceed the blast radius budget:",
+            report.violations.len()
+        )
+        .red()
+        .bold()
+    )
+    .unwrap();
+    for v in &report.violations {
+        writeln!(
+            w,
+            "  {} ({}, {} downstream)",
+            v.label.red(),
+            v.status.label(),
+            v.downstream
+        )
+        .unwrap();
+    }
+}
+
+/// Render a blast-radius check report as JSON to stdout
+pub fn render_check_json(report: &BlastRadiusReport) {
+    render_check_json_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_check_json_to_writer<W: Write>(report: &BlastRadiusReport, w: &mut W) {
+    serde_json::to_writer_pretty(&mut *w, report).unwrap();
+    writeln!(w).unwrap();
+}
+
+/// Render a layer-order check report as colored text to stdout
+pub fn render_layer_text(report: &LayerReport) {
+    render_layer_text_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_layer_text_to_writer<W: Write>(report: &LayerReport, w: &mut W) {
+    writeln!(w).unwrap();
+    writeln!(
+        w,
+        "{}",
+        format!("Layer Check: {}", report.layers.join(" → ")).bold()
+    )
+    .unwrap();
+    writeln!(w, "{}", "=".repeat(50)).unwrap();
+    writeln!(w, "  Layered nodes:   {}", report.layered_nodes).unwrap();
+    writeln!(w).unwrap();
+
+    if report.passed() {
+        writeln!(
+            w,
+            "{}",
+            "PASS: no edge goes from a later layer to an earlier one".green()
+        )
+        .unwrap();
+        return;
+    }
+
+    writeln!(
+        w,
+        "{}",
+        format!(
+            "FAIL: {} edge(s) go from a later layer to an earlier one:",
+            report.violations.len()
+        )
+        .red()
+        .bold()
+    )
+    .unwrap();
+    for v in &report.violations {
+        writeln!(
+            w,
+            "  {} ({}) → {} ({}) [{}]",
+            v.source.red(),
+            v.source_layer,
+            v.target,
+            v.target_layer,
+            v.edge_type
+        )
+        .unwrap();
+    }
+}
+
+/// Render a layer-order check report as JSON to stdout
+pub fn render_layer_json(report: &LayerReport) {
+    render_layer_json_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_layer_json_to_writer<W: Write>(report: &LayerReport, w: &mut W) {
+    serde_json::to_writer_pretty(&mut *w, report).unwrap();
+    writeln!(w).unwrap();
+}
+
+/// Render a description check report as colored text to stdout
+pub fn render_description_text(report: &DescriptionReport) {
+    render_description_text_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_description_text_to_writer<W: Write>(report: &DescriptionReport, w: &mut W) {
+    writeln!(w).unwrap();
+    writeln!(w, "{}", "Description Check".bold()).unwrap();
+    writeln!(w, "{}", "=".repeat(50)).unwrap();
+    writeln!(w, "  Models checked:  {}", report.checked).unwrap();
+    writeln!(
+        w,
+        "  Coverage:        {:.1}% ({}/{} documented)",
+        report.coverage, report.documented, report.checked
+    )
+    .unwrap();
+    writeln!(w).unwrap();
+
+    if report.passed() {
+        writeln!(w, "{}", "PASS: every model has a description".green()).unwrap();
+        return;
+    }
+
+    writeln!(
+        w,
+        "{}",
+        format!(
+            "FAIL: {} model(s) have no description:",
+            report.missing.len()
+        )
+        .red()
+        .bold()
+    )
+    .unwrap();
+    for model in &report.missing {
+        match &model.file_path {
+            Some(path) => writeln!(w, "  {} ({})", model.label.red(), path).unwrap(),
+            None => writeln!(w, "  {}", model.label.red()).unwrap(),
+        }
+    }
+}
+
+/// Render a description check report as JSON to stdout
+pub fn render_description_json(report: &DescriptionReport) {
+    render_description_json_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_description_json_to_writer<W: Write>(report: &DescriptionReport, w: &mut W) {
+    serde_json::to_writer_pretty(&mut *w, report).unwrap();
+    writeln!(w).unwrap();
+}
+
+/// Render an SLA check report as colored text to stdout
+pub fn render_sla_text(report: &SlaReport) {
+    render_sla_text_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_sla_text_to_writer<W: Write>(report: &SlaReport, w: &mut W) {
+    writeln!(w).unwrap();
+    writeln!(w, "{}", "SLA Check".bold()).unwrap();
+    writeln!(w, "{}", "=".repeat(50)).unwrap();
+    writeln!(w, "  Nodes checked:   {}", report.checked).unwrap();
+    if report.not_run > 0 {
+        writeln!(w, "  Not run yet:     {}", report.not_run).unwrap();
+    }
+    writeln!(w).unwrap();
+
+    if report.passed() {
+        writeln!(w, "{}", "PASS: every node ran within its SLA".green()).unwrap();
+        return;
+    }
+
+    writeln!(
+        w,
+        "{}",
+        format!(
+            "FAIL: {} node(s) breached their SLA:",
+            report.breaches.len()
+        )
+        .red()
+        .bold()
+    )
+    .unwrap();
+    for breach in &report.breaches {
+        let age = match breach.age_hours {
+            Some(age) => format!("last success {}h ago", age),
+            None => "last run did not succeed".to_string(),
+        };
+        writeln!(
+            w,
+            "  {}  {} (SLA {}h)",
+            breach.label.red(),
+            age,
+            breach.sla_hours
+        )
+        .unwrap();
+    }
+}
+
+/// Render an SLA check report as JSON to stdout
+pub fn render_sla_json(report: &SlaReport) {
+    render_sla_json_to_writer(report, &mut std::io::stdout().lock());
+}
+
+pub fn render_sla_json_to_writer<W: Write>(report: &SlaReport, w: &mut W) {
+    serde_json::to_writer_pretty(&mut *w, report).unwrap();
+    writeln!(w).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::check::{ChangedModelRadius, LayerViolation, UndocumentedModel};
+    use crate::graph::diff::DiffStatus;
+
+    fn make_report(violating: bool) -> BlastRadiusReport {
+        let hub = ChangedModelRadius {
+            unique_id: "model.hub".into(),
+            label: "hub".into(),
+            status: DiffStatus::Modified,
+            downstream: 25,
+        };
+        BlastRadiusReport {
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            threshold: 20,
+            checked: vec![hub.clone()],
+            violations: if violating { vec![hub] } else { vec![] },
+        }
+    }
+
+    #[test]
+    fn test_text_lists_offenders() {
+        let mut buf = Vec::new();
+        render_check_text_to_writer(&make_report(true), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Blast Radius Check: main → feature"));
+        assert!(output.contains("Threshold:       20 downstream nodes"));
+        assert!(output.contains("FAIL: 1 changed model(s)"));
+        assert!(output.contains("hub"));
+        assert!(output.contains("(modified, 25 downstream)"));
+    }
+
+    #[test]
+    fn test_text_pass() {
+        let mut buf = Vec::new();
+        render_check_text_to_writer(&make_report(false), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("PASS"));
+        assert!(!output.contains("FAIL"));
+    }
+
+    #[test]
+    fn test_json_output() {
+        let mut buf = Vec::new();
+        render_check_json_to_writer(&make_report(true), &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["threshold"], 20);
+        assert_eq!(parsed["violations"][0]["unique_id"], "model.hub");
+        assert_eq!(parsed["violations"][0]["status"], "modified");
+        assert_eq!(parsed["violations"][0]["downstream"], 25);
+    }
+
+    fn make_layer_report(violating: bool) -> LayerReport {
+        let violation = LayerViolation {
+            source: "model.fct_orders".into(),
+            source_layer: "marts".into(),
+            target: "model.int_orders".into(),
+            target_layer: "intermediate".into(),
+            edge_type: "ref",
+        };
+        LayerReport {
+            layers: vec!["staging".into(), "intermediate".into(), "marts".into()],
+            layered_nodes: 3,
+            violations: if violating { vec![violation] } else { vec![] },
+        }
+    }
+
+    #[test]
+    fn test_layer_text_lists_backward_edges() {
+        let mut buf = Vec::new();
+        render_layer_text_to_writer(&make_layer_report(true), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Layer Check: staging → intermediate → marts"));
+        assert!(output.contains("FAIL: 1 edge(s)"));
+        assert!(output.contains("(marts) → model.int_orders (intermediate) [ref]"));
+
+        let mut buf = Vec::new();
+        render_layer_text_to_writer(&make_layer_report(false), &mut buf);
+        assert!(String::from_utf8(buf).unwrap().contains("PASS"));
+    }
+
+    #[test]
+    fn test_layer_json_output() {
+        let mut buf = Vec::new();
+        render_layer_json_to_writer(&make_layer_report(true), &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["layers"][2], "marts");
+        assert_eq!(parsed["violations"][0]["source"], "model.fct_orders");
+        assert_eq!(parsed["violations"][0]["target_layer"], "intermediate");
+    }
+
+    fn make_description_report(missing: bool) -> DescriptionReport {
+        DescriptionReport {
+            checked: 3,
+            documented: if missing { 2 } else { 3 },
+            coverage: if missing { 200.0 / 3.0 } else { 100.0 },
+            missing: if missing {
+                vec![UndocumentedModel {
+                    unique_id: "model.customers".into(),
+                    label: "customers".into(),
+                    file_path: Some("models/marts/customers.sql".into()),
+                }]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    #[test]
+    fn test_description_text_lists_missing() {
+        let mut buf = Vec::new();
+        render_description_text_to_writer(&make_description_report(true), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Coverage:        66.7% (2/3 documented)"));
+        assert!(output.contains("FAIL: 1 model(s) have no description"));
+        assert!(output.contains("(models/marts/customers.sql)"));
+
+        let mut buf = Vec::new();
+        render_description_text_to_writer(&make_description_report(false), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("100.0% (3/3 documented)"));
+        assert!(output.contains("PASS"));
+    }
+
+    #[test]
+    fn test_description_json_output() {
+        let mut buf = Vec::new();
+        render_description_json_to_writer(&make_description_report(true), &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["checked"], 3);
+        assert_eq!(parsed["documented"], 2);
+        assert_eq!(parsed["missing"][0]["unique_id"], "model.customers");
+    }
+
+    #[test]
+    fn test_sla_text_and_json() {
+        use crate::graph::sla::SlaBreach;
+
+        let mut report = SlaReport {
+            checked: 3,
+            not_run: 1,
+            breaches: vec![
+                SlaBreach {
+                    unique_id: "model.orders".into(),
+                    label: "orders".into(),
+                    sla_hours: 6.0,
+                    age_hours: Some(9.5),
+                },
+                SlaBreach {
+                    unique_id: "model.payments".into(),
+                    label: "payments".into(),
+                    sla_hours: 24.0,
+                    age_hours: None,
+                },
+            ],
+        };
+        let mut buf = Vec::new();
+        render_sla_text_to_writer(&report, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Not run yet:     1"));
+        assert!(output.contains("FAIL: 2 node(s) breached their SLA"));
+        assert!(output.contains("last success 9.5h ago (SLA 6h)"));
+        assert!(output.contains("last run did not succeed (SLA 24h)"));
+
+        let mut buf = Vec::new();
+        render_sla_json_to_writer(&report, &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["breaches"][0]["age_hours"], 9.5);
+        assert!(parsed["breaches"][1]["age_hours"].is_null());
+
+        report.breaches.clear();
+        let mut buf = Vec::new();
+        render_sla_text_to_writer(&report, &mut buf);
+        assert!(String::from_utf8(buf).unwrap().contains("PASS"));
+    }
+}
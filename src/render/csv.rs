@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use super::json::edge_type_label;
+use crate::graph::types::*;
+
+const NODE_HEADER: &str = "unique_id,label,node_type,materialization,tags,file_path,description";
+const EDGE_HEADER: &str = "source_id,target_id,edge_type";
+
+/// Write `<prefix>_nodes.csv` and `<prefix>_edges.csv` and return their paths
+pub fn render_csv(graph: &LineageGraph, prefix: &Path) -> Result<(PathBuf, PathBuf)> {
+    let nodes_path = prefixed_path(prefix, "_nodes.csv");
+    let edges_path = prefixed_path(prefix, "_edges.csv");
+    write_csv_file(&nodes_path, |w| write_nodes_csv(graph, w))?;
+    write_csv_file(&edges_path, |w| write_edges_csv(graph, w))?;
+    Ok((nodes_path, edges_path))
+}
+
+/// `out/lineage` + `_nodes.csv` → `out/lineage_nodes.csv`
+fn prefixed_path(prefix: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(prefix.as_os_str());
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+fn write_csv_file(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> std::io::Result<()>,
+) -> Result<()> {
+    let to_error = |e| crate::error::DbtLineageError::FileWriteError {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    let mut w = BufWriter::new(File::create(path).map_err(to_error)?);
+    write(&mut w).and_then(|_| w.flush()).map_err(to_error)?;
+    Ok(())
+}
+
+/// One row per node; tags are joined with `;`
+pub fn write_nodes_csv<W: Write>(graph: &LineageGraph, w: &mut W) -> std::io::Result<()> {
+    writeln!(w, "{}", NODE_HEADER)?;
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        let file_path = node
+            .file_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let fields = [
+            node.unique_id.as_str(),
+            node.label.as_str(),
+            node.node_type.label(),
+            node.materialization.as_deref().unwrap_or_default(),
+            &node.tags.join(";"),
+            &file_path,
+            node.description.as_deref().unwrap_or_default(),
+        ];
+        write_row(w, &fields)?;
+    }
+    Ok(())
+}
+
+/// One row per edge, by node unique_id
+pub fn write_edges_csv<W: Write>(graph: &LineageGraph, w: &mut W) -> std::io::Result<()> {
+    writeln!(w, "{}", EDGE_HEADER)?;
+    for edge in graph.edge_references() {
+        let edge_type = edge_type_label(edge.weight().edge_type);
+        let fields = [
+            graph[edge.source()].unique_id.as_str(),
+            graph[edge.target()].unique_id.as_str(),
+            edge_type.as_str(),
+        ];
+        write_row(w, &fields)?;
+    }
+    Ok(())
+}
+
+fn write_row<W: Write>(w: &mut W, fields: &[&str]) -> std::io::Result<()> {
+    let row: Vec<Cow<str>> = fields.iter().map(|f| csv_field(f)).collect();
+    writeln!(w, "{}", row.join(","))
+}
+
+/// Quote a field (RFC 4180) when it contains a comma, quote or line break
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_node(unique_id: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: unique_id.rsplit('.').next().unwrap().into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    fn make_graph() -> LineageGraph {
+        let mut graph = LineageGraph::new();
+        let src = graph.add_node(make_node("source.raw.orders", NodeType::Source));
+        let mut orders = make_node("model.orders", NodeType::Model);
+        orders.materialization = Some("table".into());
+        orders.tags = vec!["finance".into(), "daily".into()];
+        orders.file_path = Some(PathBuf::from("models/marts/orders.sql"));
+        orders.description = Some("Orders, one row per \"order\"\nafter cleanup".into());
+        let orders = graph.add_node(orders);
+        graph.add_edge(
+            src,
+            orders,
+            EdgeData {
+                edge_type: EdgeType::Source,
+                snippet: None,
+            },
+        );
+        graph
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn test_write_nodes_csv() {
+        let mut buf = Vec::new();
+        write_nodes_csv(&make_graph(), &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        let mut lines = output.splitn(3, '\n');
+        assert_eq!(lines.next().unwrap(), NODE_HEADER);
+        assert_eq!(lines.next().unwrap(), "source.raw.orders,orders,source,,,,");
+        assert_eq!(
+            lines.next().unwrap(),
+            "model.orders,orders,model,table,finance;daily,models/marts/orders.sql,\"Orders, one row per \"\"order\"\"\nafter cleanup\"\n"
+        );
+    }
+
+    #[test]
+    fn test_write_edges_csv() {
+        let mut buf = Vec::new();
+        write_edges_csv(&make_graph(), &mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            format!("{}\nsource.raw.orders,model.orders,source\n", EDGE_HEADER)
+        );
+    }
+
+    #[test]
+    fn test_render_csv_writes_prefixed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path().join("lineage");
+        let (nodes, edges) = render_csv(&make_graph(), &prefix).unwrap();
+
+        assert_eq!(nodes, dir.path().join("lineage_nodes.csv"));
+        assert_eq!(edges, dir.path().join("lineage_edges.csv"));
+        assert!(std::fs::read_to_string(&nodes)
+            .unwrap()
+            .starts_with(NODE_HEADER));
+        assert_eq!(std::fs::read_to_string(&edges).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn test_render_csv_unwritable_prefix() {
+        let err = render_csv(&make_graph(), Path::new("/nonexistent/dir/lineage")).unwrap_err();
+        assert!(err.to_string().contains("failed to write file"));
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use serde::Serialize;
+
+use super::json::edge_type_label;
+use crate::graph::types::*;
+
+/// Cytoscape.js elements JSON: `{ "elements": { "nodes": [...], "edges": [...] } }`
+#[derive(Serialize)]
+struct CytoscapeGraph {
+    elements: CytoscapeElements,
+}
+
+#[derive(Serialize)]
+struct CytoscapeElements {
+    nodes: Vec<CytoscapeElement<CytoscapeNode>>,
+    edges: Vec<CytoscapeElement<CytoscapeEdge>>,
+}
+
+/// Cytoscape wraps every element's fields in a `data` object
+#[derive(Serialize)]
+struct CytoscapeElement<T> {
+    data: T,
+}
+
+#[derive(Serialize)]
+struct CytoscapeNode {
+    /// The node's unique_id, so ids stay stable across exports
+    id: String,
+    label: String,
+    node_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    materialization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CytoscapeEdge {
+    /// `<source>-><target>`, suffixed with `#2`, `#3`, ... for parallel edges
+    id: String,
+    source: String,
+    target: String,
+    edge_type: String,
+}
+
+/// Render the lineage graph as Cytoscape.js elements JSON to stdout
+pub fn render_cytoscape(graph: &LineageGraph) {
+    render_cytoscape_to_writer(graph, &mut std::io::stdout().lock());
+}
+
+pub fn render_cytoscape_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
+    let nodes = graph
+        .node_indices()
+        .map(|idx| {
+            let node = &graph[idx];
+            CytoscapeElement {
+                data: CytoscapeNode {
+                    id: node.unique_id.clone(),
+                    label: node.label.clone(),
+                    node_type: node.node_type.label().to_string(),
+                    materialization: node.materialization.clone(),
+                    file_path: node.file_path.as_ref().map(|p| p.to_string_lossy().into()),
+                    description: node.description.clone(),
+                    tags: node.tags.clone(),
+                },
+            }
+        })
+        .collect();
+
+    // Cytoscape rejects duplicate element ids
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let edges = graph
+        .edge_references()
+        .map(|edge| {
+            let source = graph[edge.source()].unique_id.clone();
+            let target = graph[edge.target()].unique_id.clone();
+            let base_id = format!("{}->{}", source, target);
+            let count = seen.entry(base_id.clone()).or_insert(0);
+            *count += 1;
+            let id = if *count == 1 {
+                base_id
+            } else {
+                format!("{}#{}", base_id, count)
+            };
+            CytoscapeElement {
+                data: CytoscapeEdge {
+                    id,
+                    source,
+                    target,
+                    edge_type: edge_type_label(edge.weight().edge_type),
+                },
+            }
+        })
+        .collect();
+
+    let graph = CytoscapeGraph {
+        elements: CytoscapeElements { nodes, edges },
+    };
+    serde_json::to_writer_pretty(&mut *w, &graph).unwrap();
+    writeln!(w).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    fn render_to_value(graph: &LineageGraph) -> serde_json::Value {
+        let mut buf = Vec::new();
+        render_cytoscape_to_writer(graph, &mut buf);
+        serde_json::from_slice(&buf).unwrap()
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let parsed = render_to_value(&LineageGraph::new());
+        assert_eq!(parsed["elements"]["nodes"].as_array().unwrap().len(), 0);
+        assert_eq!(parsed["elements"]["edges"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_nodes_and_edges_use_data_objects() {
+        let mut graph = LineageGraph::new();
+        let src = graph.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        let mut stg = make_node("model.stg_orders", "stg_orders", NodeType::Model);
+        stg.materialization = Some("view".into());
+        stg.file_path = Some(PathBuf::from("models/staging/stg_orders.sql"));
+        let stg = graph.add_node(stg);
+        graph.add_edge(
+            src,
+            stg,
+            EdgeData {
+                edge_type: EdgeType::Source,
+                snippet: None,
+            },
+        );
+
+        let parsed = render_to_value(&graph);
+        let nodes = parsed["elements"]["nodes"].as_array().unwrap();
+        assert_eq!(nodes[0]["data"]["id"], "source.raw.orders");
+        assert_eq!(nodes[0]["data"]["node_type"], "source");
+        assert!(nodes[0]["data"].get("materialization").is_none());
+        assert_eq!(nodes[1]["data"]["id"], "model.stg_orders");
+        assert_eq!(nodes[1]["data"]["label"], "stg_orders");
+        assert_eq!(nodes[1]["data"]["materialization"], "view");
+        assert_eq!(
+            nodes[1]["data"]["file_path"],
+            "models/staging/stg_orders.sql"
+        );
+
+        let edge = &parsed["elements"]["edges"][0]["data"];
+        assert_eq!(edge["id"], "source.raw.orders->model.stg_orders");
+        assert_eq!(edge["source"], "source.raw.orders");
+        assert_eq!(edge["target"], "model.stg_orders");
+        assert_eq!(edge["edge_type"], "source");
+    }
+
+    #[test]
+    fn test_parallel_edges_get_unique_ids() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        for _ in 0..2 {
+            graph.add_edge(
+                a,
+                b,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        let parsed = render_to_value(&graph);
+        let edges = parsed["elements"]["edges"].as_array().unwrap();
+        assert_eq!(edges[0]["data"]["id"], "model.a->model.b");
+        assert_eq!(edges[1]["data"]["id"], "model.a->model.b#2");
+    }
+}
@@ -2,7 +2,36 @@ use std::io::Write;
 
 use colored::Colorize;
 
-use crate::graph::diff::{DiffStatus, LineageDiff};
+use crate::graph::diff::{ColumnDiff, DiffImpact, DiffStatus, LineageDiff};
+use crate::render::markdown::{
+    capitalize, close_details, escape_cell, node_cell, open_details, type_groups,
+};
+
+/// Statuses of a drawn diff (`diff -o dot|svg|mermaid`), in legend order
+pub const DIFF_LEGEND: [DiffStatus; 4] = [
+    DiffStatus::Added,
+    DiffStatus::Removed,
+    DiffStatus::Modified,
+    DiffStatus::Unchanged,
+];
+
+/// Fill color for a node or edge of a drawn diff
+pub fn diff_color(status: DiffStatus) -> &'static str {
+    match status {
+        DiffStatus::Added => "#2ECC71",
+        DiffStatus::Removed => "#E74C3C",
+        DiffStatus::Modified => "#F1C40F",
+        DiffStatus::Unchanged => "#ECF0F1",
+    }
+}
+
+/// Label color on a diff fill: white on red, black on the lighter fills
+pub fn diff_font_color(status: DiffStatus) -> &'static str {
+    match status {
+        DiffStatus::Removed => "white",
+        _ => "black",
+    }
+}
 
 /// Render diff report as colored text to stdout
 pub fn render_diff_text(diff: &LineageDiff) {
@@ -53,22 +82,33 @@ pub fn render_diff_text_to_writer<W: Write>(diff: &LineageDiff, w: &mut W) {
     .unwrap();
     writeln!(w).unwrap();
 
-    // Only show non-unchanged nodes
-    let changed_nodes: Vec<_> = diff
-        .nodes
-        .iter()
-        .filter(|n| n.status != DiffStatus::Unchanged)
-        .collect();
-
-    if !changed_nodes.is_empty() {
-        writeln!(w, "{}", "Changed Nodes:".bold()).unwrap();
-        for node in &changed_nodes {
-            let (symbol, color) = match node.status {
-                DiffStatus::Added => ("+", colored::Color::Green),
-                DiffStatus::Removed => ("-", colored::Color::Red),
-                DiffStatus::Modified => ("~", colored::Color::Yellow),
-                DiffStatus::Unchanged => (" ", colored::Color::White),
-            };
+    if let Some(by) = diff.group_by.filter(|_| !diff.groups.is_empty()) {
+        writeln!(w, "{}", format!("Changes by {}:", by.label()).bold()).unwrap();
+        let width = diff.groups.iter().map(|g| g.group.len()).max().unwrap_or(0);
+        for group in &diff.groups {
+            writeln!(
+                w,
+                "  {:<width$}  {} {} {}",
+                group.group,
+                format!("+{}", group.added).green(),
+                format!("-{}", group.removed).red(),
+                format!("~{}", group.modified).yellow(),
+            )
+            .unwrap();
+        }
+        writeln!(w).unwrap();
+    }
+
+    if !diff.nodes.is_empty() {
+        // Unchanged nodes are only present when the caller asked for them
+        let heading = if diff.nodes.iter().any(|n| n.status == DiffStatus::Unchanged) {
+            "Nodes:"
+        } else {
+            "Changed Nodes:"
+        };
+        writeln!(w, "{}", heading.bold()).unwrap();
+        for node in &diff.nodes {
+            let (symbol, color) = status_style(node.status);
             writeln!(
                 w,
                 "  {} {} ({}) [{}]",
@@ -78,6 +118,9 @@ pub fn render_diff_text_to_writer<W: Write>(diff: &LineageDiff, w: &mut W) {
                 node.status.label()
             )
             .unwrap();
+            if let Some(impact) = &node.impact {
+                writeln!(w, "      {}", describe_impact(impact).dimmed()).unwrap();
+            }
             for change in &node.changes {
                 writeln!(w, "      {}", change).unwrap();
             }
@@ -105,6 +148,97 @@ pub fn render_diff_text_to_writer<W: Write>(diff: &LineageDiff, w: &mut W) {
         }
         writeln!(w).unwrap();
     }
+
+    if let Some(columns) = &diff.columns {
+        render_column_changes(columns, w);
+    }
+}
+
+/// Symbol and color for a change status
+fn status_style(status: DiffStatus) -> (&'static str, colored::Color) {
+    match status {
+        DiffStatus::Added => ("+", colored::Color::Green),
+        DiffStatus::Removed => ("-", colored::Color::Red),
+        DiffStatus::Modified => ("~", colored::Color::Yellow),
+        DiffStatus::Unchanged => (" ", colored::Color::White),
+    }
+}
+
+/// `diff --columns`: changed columns under their model, each with the edges
+/// into it that changed
+fn render_column_changes<W: Write>(columns: &[ColumnDiff], w: &mut W) {
+    writeln!(w, "{}", "Column Changes:".bold()).unwrap();
+    if columns.is_empty() {
+        writeln!(w, "  none").unwrap();
+    }
+    let mut current_node = None;
+    for column in columns {
+        if current_node != Some(&column.node) {
+            writeln!(w, "  {}", column.node.bold()).unwrap();
+            current_node = Some(&column.node);
+        }
+        let (symbol, color) = status_style(column.status);
+        writeln!(
+            w,
+            "    {} {} [{}]",
+            symbol.color(color),
+            column.column.color(color),
+            column.status.label()
+        )
+        .unwrap();
+        for edge in &column.edges {
+            let (symbol, color) = status_style(edge.status);
+            let source = if edge.source_column.is_empty() {
+                edge.source_node.clone()
+            } else {
+                format!("{}.{}", edge.source_node, edge.source_column)
+            };
+            let confidence = match edge.base_confidence {
+                Some(base) => format!("{} → {}", base.label(), edge.confidence.label()),
+                None => edge.confidence.label().to_string(),
+            };
+            writeln!(
+                w,
+                "        {} {} ({})",
+                symbol.color(color),
+                source.color(color),
+                confidence.to_lowercase()
+            )
+            .unwrap();
+        }
+        if !column.selected_by.is_empty() {
+            let readers: Vec<String> = column
+                .selected_by
+                .iter()
+                .map(|c| format!("{}.{}", c.node, c.column))
+                .collect();
+            writeln!(
+                w,
+                "        {}",
+                format!("still selected by {}", readers.join(", ")).red()
+            )
+            .unwrap();
+        }
+    }
+    writeln!(w).unwrap();
+}
+
+/// `downstream: 5 nodes (3 models, 1 exposure), severity critical`
+fn describe_impact(impact: &DiffImpact) -> String {
+    let Some(severity) = impact.severity else {
+        return "downstream: none".to_string();
+    };
+    let plural = |n: usize, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+    let mut parts = vec![plural(impact.affected_models, "model")];
+    if impact.affected_exposures > 0 {
+        parts.push(plural(impact.affected_exposures, "exposure"));
+    }
+    format!(
+        "downstream: {} ({}), severity {}",
+        plural(impact.downstream, "node"),
+        parts.join(", "),
+        severity.label()
+    )
 }
 
 /// Render diff report as JSON to stdout
@@ -117,10 +251,157 @@ pub fn render_diff_json_to_writer<W: Write>(diff: &LineageDiff, w: &mut W) {
     writeln!(w).unwrap();
 }
 
+/// Render diff report as GitHub-flavored Markdown to stdout
+pub fn render_diff_markdown(diff: &LineageDiff, link_prefix: Option<&str>) {
+    render_diff_markdown_to_writer(diff, link_prefix, &mut std::io::stdout().lock());
+}
+
+/// Markdown for a PR comment: a table of counts, then the changed nodes in a
+/// collapsed `<details>` section per node type, followed by the changed edges
+/// and columns. Node names link to their files when `link_prefix` is given.
+pub fn render_diff_markdown_to_writer<W: Write>(
+    diff: &LineageDiff,
+    link_prefix: Option<&str>,
+    w: &mut W,
+) {
+    writeln!(
+        w,
+        "### Lineage diff: `{}` → `{}`\n",
+        escape_cell(&diff.base_ref),
+        escape_cell(&diff.head_ref)
+    )
+    .unwrap();
+    let summary = &diff.summary;
+    writeln!(
+        w,
+        "| | Added | Removed | Modified |\n\
+         | --- | ---: | ---: | ---: |\n\
+         | Nodes | {} | {} | {} |\n\
+         | Edges | {} | {} | |\n",
+        summary.nodes_added,
+        summary.nodes_removed,
+        summary.nodes_modified,
+        summary.edges_added,
+        summary.edges_removed
+    )
+    .unwrap();
+
+    if let Some(by) = diff.group_by.filter(|_| !diff.groups.is_empty()) {
+        writeln!(
+            w,
+            "| {} | Added | Removed | Modified |\n| --- | ---: | ---: | ---: |",
+            capitalize(by.label())
+        )
+        .unwrap();
+        for group in &diff.groups {
+            writeln!(
+                w,
+                "| {} | {} | {} | {} |",
+                escape_cell(&group.group),
+                group.added,
+                group.removed,
+                group.modified
+            )
+            .unwrap();
+        }
+        writeln!(w).unwrap();
+    }
+
+    if diff.nodes.is_empty() && diff.edges.is_empty() && diff.columns.is_none() {
+        writeln!(w, "_No lineage changes._").unwrap();
+        return;
+    }
+
+    let with_impact = diff.nodes.iter().any(|n| n.impact.is_some());
+    let groups = type_groups(|label| diff.nodes.iter().any(|node| node.node_type == label));
+    for (label, heading) in groups {
+        let nodes: Vec<_> = diff
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == label)
+            .collect();
+        open_details(w, &heading, nodes.len());
+        if with_impact {
+            writeln!(
+                w,
+                "| Node | Status | Changes | Downstream |\n| --- | --- | --- | --- |"
+            )
+            .unwrap();
+        } else {
+            writeln!(w, "| Node | Status | Changes |\n| --- | --- | --- |").unwrap();
+        }
+        for node in nodes {
+            let changes: Vec<String> = node.changes.iter().map(|c| escape_cell(c)).collect();
+            let mut row = format!(
+                "| {} | {} | {} |",
+                node_cell(&node.label, node.file_path.as_deref(), link_prefix),
+                node.status.label(),
+                changes.join("<br>")
+            );
+            if with_impact {
+                let impact = node.impact.as_ref().map(describe_impact);
+                row.push_str(&format!(" {} |", impact.unwrap_or_default()));
+            }
+            writeln!(w, "{}", row).unwrap();
+        }
+        close_details(w);
+    }
+
+    if !diff.edges.is_empty() {
+        open_details(w, "Edges", diff.edges.len());
+        writeln!(w, "| Edge | Type | Status |\n| --- | --- | --- |").unwrap();
+        for edge in &diff.edges {
+            writeln!(
+                w,
+                "| `{}` → `{}` | {} | {} |",
+                escape_cell(&edge.source),
+                escape_cell(&edge.target),
+                edge.edge_type,
+                edge.status.label()
+            )
+            .unwrap();
+        }
+        close_details(w);
+    }
+
+    if let Some(columns) = diff.columns.as_ref().filter(|c| !c.is_empty()) {
+        open_details(w, "Columns", columns.len());
+        writeln!(w, "| Column | Status | Sources |\n| --- | --- | --- |").unwrap();
+        for column in columns {
+            let sources: Vec<String> = column
+                .edges
+                .iter()
+                .map(|edge| {
+                    let (symbol, _) = status_style(edge.status);
+                    let source = if edge.source_column.is_empty() {
+                        edge.source_node.clone()
+                    } else {
+                        format!("{}.{}", edge.source_node, edge.source_column)
+                    };
+                    format!("{} `{}`", symbol, escape_cell(&source))
+                        .trim()
+                        .to_string()
+                })
+                .collect();
+            writeln!(
+                w,
+                "| `{}.{}` | {} | {} |",
+                escape_cell(&column.node),
+                escape_cell(&column.column),
+                column.status.label(),
+                sources.join("<br>")
+            )
+            .unwrap();
+        }
+        close_details(w);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::diff::{DiffEdge, DiffNode, DiffSummary, LineageDiff};
+    use crate::graph::diff::{DiffEdge, DiffGroupBy, DiffNode, DiffSummary, LineageDiff};
+    use crate::graph::impact::ImpactSeverity;
 
     fn make_diff() -> LineageDiff {
         LineageDiff {
@@ -133,6 +414,8 @@ mod tests {
                 edges_added: 1,
                 edges_removed: 0,
             },
+            group_by: None,
+            groups: vec![],
             nodes: vec![
                 DiffNode {
                     unique_id: "model.new_model".to_string(),
@@ -140,6 +423,10 @@ mod tests {
                     node_type: "model".to_string(),
                     status: DiffStatus::Added,
                     changes: vec![],
+                    folder: "models".to_string(),
+                    tags: vec![],
+                    file_path: None,
+                    impact: None,
                 },
                 DiffNode {
                     unique_id: "model.orders".to_string(),
@@ -147,6 +434,10 @@ mod tests {
                     node_type: "model".to_string(),
                     status: DiffStatus::Modified,
                     changes: vec!["materialization: view -> table".to_string()],
+                    folder: "models".to_string(),
+                    tags: vec![],
+                    file_path: None,
+                    impact: None,
                 },
                 DiffNode {
                     unique_id: "model.old_model".to_string(),
@@ -154,6 +445,10 @@ mod tests {
                     node_type: "model".to_string(),
                     status: DiffStatus::Removed,
                     changes: vec![],
+                    folder: "models".to_string(),
+                    tags: vec![],
+                    file_path: None,
+                    impact: None,
                 },
             ],
             edges: vec![DiffEdge {
@@ -162,6 +457,7 @@ mod tests {
                 edge_type: "ref".to_string(),
                 status: DiffStatus::Added,
             }],
+            columns: None,
         }
     }
 
@@ -181,6 +477,29 @@ mod tests {
         assert!(output.contains("Changed Edges:"));
     }
 
+    #[test]
+    fn test_render_diff_text_with_unchanged_nodes() {
+        let mut diff = make_diff();
+        diff.nodes.push(DiffNode {
+            unique_id: "model.customers".to_string(),
+            label: "customers".to_string(),
+            node_type: "model".to_string(),
+            status: DiffStatus::Unchanged,
+            changes: vec![],
+            folder: "models".to_string(),
+            tags: vec![],
+            file_path: None,
+            impact: None,
+        });
+        let mut buf = Vec::new();
+        render_diff_text_to_writer(&diff, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(!output.contains("Changed Nodes:"));
+        assert!(output.contains("Nodes:"));
+        assert!(output.contains("customers (model) [unchanged]"));
+    }
+
     #[test]
     fn test_render_diff_json() {
         let diff = make_diff();
@@ -195,14 +514,149 @@ mod tests {
         assert_eq!(parsed["nodes"].as_array().unwrap().len(), 3);
     }
 
+    #[test]
+    fn test_render_diff_grouped() {
+        let mut diff = make_diff();
+        diff.group_by(DiffGroupBy::Type);
+
+        let mut buf = Vec::new();
+        render_diff_text_to_writer(&diff, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Changes by type:"));
+        let row = output.lines().find(|l| l.starts_with("  model ")).unwrap();
+        assert!(row.contains("+1") && row.contains("-1") && row.contains("~1"));
+
+        let mut buf = Vec::new();
+        render_diff_json_to_writer(&diff, &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["group_by"], "type");
+        assert_eq!(
+            parsed["groups"],
+            serde_json::json!([{"group": "model", "added": 1, "removed": 1, "modified": 1}])
+        );
+
+        // Ungrouped output carries neither
+        let parsed: serde_json::Value = serde_json::to_value(make_diff()).expect("diff serializes");
+        assert!(parsed.get("group_by").is_none());
+        assert!(parsed.get("groups").is_none());
+    }
+
+    #[test]
+    fn test_render_diff_with_impact() {
+        let mut diff = make_diff();
+        diff.nodes[0].impact = Some(DiffImpact {
+            downstream: 4,
+            affected_models: 2,
+            affected_exposures: 1,
+            severity: Some(ImpactSeverity::Critical),
+        });
+        diff.nodes[1].impact = Some(DiffImpact {
+            downstream: 0,
+            affected_models: 0,
+            affected_exposures: 0,
+            severity: None,
+        });
+
+        let mut buf = Vec::new();
+        render_diff_text_to_writer(&diff, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains(
+            "new_model (model) [added]\n      downstream: 4 nodes (2 models, 1 exposure), severity critical\n"
+        ));
+        assert!(output.contains("      downstream: none\n"));
+
+        let parsed: serde_json::Value = serde_json::to_value(&diff).expect("diff serializes");
+        assert_eq!(
+            parsed["nodes"][0]["impact"],
+            serde_json::json!({
+                "downstream": 4,
+                "affected_models": 2,
+                "affected_exposures": 1,
+                "severity": "critical"
+            })
+        );
+        assert!(parsed["nodes"][2].get("impact").is_none());
+    }
+
+    #[test]
+    fn test_render_diff_text_with_columns() {
+        use crate::graph::diff::{ColumnEdgeChange, ColumnRef};
+        use crate::parser::column_lineage::ColumnConfidence;
+
+        let mut diff = make_diff();
+        let output = |diff: &LineageDiff| {
+            let mut buf = Vec::new();
+            render_diff_text_to_writer(diff, &mut buf);
+            String::from_utf8(buf).unwrap()
+        };
+        assert!(!output(&diff).contains("Column Changes:"));
+
+        diff.columns = Some(vec![]);
+        assert!(output(&diff).contains("Column Changes:\n  none"));
+
+        diff.columns = Some(vec![
+            ColumnDiff {
+                node: "model.orders".to_string(),
+                column: "amount".to_string(),
+                status: DiffStatus::Modified,
+                edges: vec![ColumnEdgeChange {
+                    source_node: "model.stg_orders".to_string(),
+                    source_column: "amount".to_string(),
+                    status: DiffStatus::Modified,
+                    confidence: ColumnConfidence::Derived,
+                    base_confidence: Some(ColumnConfidence::Direct),
+                }],
+                selected_by: vec![],
+            },
+            ColumnDiff {
+                node: "model.orders".to_string(),
+                column: "customer_id".to_string(),
+                status: DiffStatus::Removed,
+                edges: vec![ColumnEdgeChange {
+                    source_node: "model.stg_orders".to_string(),
+                    source_column: "customer_id".to_string(),
+                    status: DiffStatus::Removed,
+                    confidence: ColumnConfidence::Direct,
+                    base_confidence: None,
+                }],
+                selected_by: vec![ColumnRef {
+                    node: "model.customers".to_string(),
+                    column: "customer_id".to_string(),
+                }],
+            },
+        ]);
+        let text = output(&diff);
+        // Both columns sit under a single model heading
+        assert_eq!(text.matches("  model.orders\n").count(), 1);
+        assert!(text.contains("amount [modified]"));
+        assert!(text.contains("model.stg_orders.amount (direct → derived)"));
+        assert!(text.contains("customer_id [removed]"));
+        assert!(text.contains("still selected by model.customers.customer_id"));
+
+        let mut buf = Vec::new();
+        render_diff_json_to_writer(&diff, &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(
+            parsed["columns"][0]["edges"][0]["base_confidence"],
+            "direct"
+        );
+        assert_eq!(
+            parsed["columns"][1]["selected_by"][0]["node"],
+            "model.customers"
+        );
+    }
+
     #[test]
     fn test_render_diff_text_empty() {
         let diff = LineageDiff {
             base_ref: "main".to_string(),
             head_ref: "HEAD".to_string(),
             summary: DiffSummary::default(),
+            group_by: None,
+            groups: vec![],
             nodes: vec![],
             edges: vec![],
+            columns: None,
         };
         let mut buf = Vec::new();
         render_diff_text_to_writer(&diff, &mut buf);
@@ -223,6 +677,8 @@ mod tests {
                 edges_added: 1,
                 edges_removed: 1,
             },
+            group_by: None,
+            groups: vec![],
             nodes: vec![],
             edges: vec![
                 DiffEdge {
@@ -238,6 +694,7 @@ mod tests {
                     status: DiffStatus::Removed,
                 },
             ],
+            columns: None,
         };
         let mut buf = Vec::new();
         render_diff_text_to_writer(&diff, &mut buf);
@@ -262,6 +719,8 @@ mod tests {
                 edges_added: 0,
                 edges_removed: 0,
             },
+            group_by: None,
+            groups: vec![],
             nodes: vec![DiffNode {
                 unique_id: "model.orders".to_string(),
                 label: "orders".to_string(),
@@ -271,8 +730,13 @@ mod tests {
                     "materialization: view -> table".to_string(),
                     "columns: 3 -> 5".to_string(),
                 ],
+                folder: "models".to_string(),
+                tags: vec![],
+                file_path: None,
+                impact: None,
             }],
             edges: vec![],
+            columns: None,
         };
         let mut buf = Vec::new();
         render_diff_text_to_writer(&diff, &mut buf);
@@ -282,4 +746,40 @@ mod tests {
         assert!(output.contains("materialization: view -> table"));
         assert!(output.contains("columns: 3 -> 5"));
     }
+
+    #[test]
+    fn test_render_diff_markdown() {
+        let mut diff = make_diff();
+        diff.nodes[1].file_path = Some("models/orders.sql".into());
+        diff.nodes[1]
+            .changes
+            .push("description: a | b -> c".to_string());
+        let mut buf = Vec::new();
+        render_diff_markdown_to_writer(&diff, Some("https://x/blob/main/"), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.starts_with(
+            "### Lineage diff: `main` → `feature`\n\n\
+             | | Added | Removed | Modified |\n\
+             | --- | ---: | ---: | ---: |\n\
+             | Nodes | 1 | 1 | 1 |\n\
+             | Edges | 1 | 0 | |\n\n\
+             <details>\n<summary>Models (3)</summary>\n\n\
+             | Node | Status | Changes |\n"
+        ));
+        assert!(output.contains("| `new_model` | added |  |\n"));
+        assert!(output.contains(
+            "| [`orders`](https://x/blob/main/models/orders.sql) | modified | \
+             materialization: view -> table<br>description: a \\| b -> c |\n"
+        ));
+        assert!(output.contains("<summary>Edges (1)</summary>"));
+        assert!(output.contains("| `model.stg_orders` → `model.new_model` | ref | added |"));
+
+        diff.nodes.clear();
+        diff.edges.clear();
+        let mut buf = Vec::new();
+        render_diff_markdown_to_writer(&diff, None, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.ends_with("_No lineage changes._\n"));
+    }
 }
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use petgraph::stable_graph::NodeIndex;
+use petgraph::Direction;
+
+use crate::error::DbtLineageError;
+use crate::graph::types::*;
+
+/// Write one Markdown page per node into `dir` (created if needed) and return
+/// the paths written. Neighbors that have a page of their own are linked.
+pub fn write_docs(graph: &LineageGraph, nodes: &[NodeIndex], dir: &Path) -> Result<Vec<PathBuf>> {
+    let to_error = |path: &Path, e| DbtLineageError::FileWriteError {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    std::fs::create_dir_all(dir).map_err(|e| to_error(dir, e))?;
+    let pages: HashMap<NodeIndex, String> = nodes
+        .iter()
+        .map(|&idx| (idx, doc_file_name(&graph[idx])))
+        .collect();
+    let mut written = Vec::new();
+    for &idx in nodes {
+        let path = dir.join(&pages[&idx]);
+        std::fs::write(&path, node_doc(graph, idx, &pages)).map_err(|e| to_error(&path, e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Page name from the unique_id without its type, e.g. `model.orders` →
+/// `orders.md` and `model.dim_customers.v2` → `dim_customers.v2.md`, so
+/// versions and duplicate names get pages of their own
+pub fn doc_file_name(node: &NodeData) -> String {
+    let name = node
+        .unique_id
+        .split_once('.')
+        .map_or(node.unique_id.as_str(), |(_, rest)| rest);
+    let name: String = name
+        .chars()
+        .map(|c| if c == '/' || c == '\\' { '_' } else { c })
+        .collect();
+    format!("{}.md", name)
+}
+
+/// Markdown page for one node: its unique_id, description, materialization,
+/// tags, columns and direct upstream and downstream neighbors by label
+pub fn node_doc(
+    graph: &LineageGraph,
+    idx: NodeIndex,
+    pages: &HashMap<NodeIndex, String>,
+) -> String {
+    let node = &graph[idx];
+    let mut doc = String::new();
+    writeln!(doc, "# {}\n", node.versioned_label()).unwrap();
+    writeln!(doc, "`{}`\n", node.unique_id).unwrap();
+    if let Some(description) = node.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        writeln!(doc, "{}\n", description.trim()).unwrap();
+    }
+
+    let mut facts = Vec::new();
+    if let Some(materialization) = &node.materialization {
+        facts.push(format!("- **Materialization:** {}", materialization));
+    }
+    if !node.tags.is_empty() {
+        facts.push(format!("- **Tags:** {}", node.tags.join(", ")));
+    }
+    if let Some(path) = &node.file_path {
+        facts.push(format!("- **File:** `{}`", path.display()));
+    }
+    if !facts.is_empty() {
+        writeln!(doc, "{}\n", facts.join("\n")).unwrap();
+    }
+
+    writeln!(doc, "## Columns\n").unwrap();
+    if node.columns.is_empty() {
+        writeln!(doc, "_None known_\n").unwrap();
+    } else {
+        for column in &node.columns {
+            let mut line = format!("- `{}`", column);
+            if let Some(data_type) = node.column_types.get(column) {
+                write!(line, " ({})", data_type).unwrap();
+            }
+            if let Some(description) = node.column_descriptions.get(column) {
+                write!(line, ": {}", description.trim()).unwrap();
+            }
+            writeln!(doc, "{}", line).unwrap();
+        }
+        writeln!(doc).unwrap();
+    }
+
+    for (title, direction) in [
+        ("Upstream", Direction::Incoming),
+        ("Downstream", Direction::Outgoing),
+    ] {
+        writeln!(doc, "## {}\n", title).unwrap();
+        let mut neighbors: Vec<NodeIndex> = graph
+            .neighbors_directed(idx, direction)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        neighbors.sort_by_key(|&n| graph[n].display_name());
+        if neighbors.is_empty() {
+            writeln!(doc, "_None_\n").unwrap();
+            continue;
+        }
+        for neighbor in neighbors {
+            let other = &graph[neighbor];
+            let name = other.versioned_label();
+            let name = match pages.get(&neighbor) {
+                Some(page) => format!("[{}]({})", name, page),
+                None => name,
+            };
+            writeln!(doc, "- {} ({})", name, other.node_type.label()).unwrap();
+        }
+        writeln!(doc).unwrap();
+    }
+    // One trailing newline, not a blank line
+    doc.pop();
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    fn ref_edge() -> EdgeData {
+        EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        }
+    }
+
+    #[test]
+    fn test_doc_file_name() {
+        let name = |id: &str| doc_file_name(&make_node(id, "x", NodeType::Model));
+        assert_eq!(name("model.orders"), "orders.md");
+        assert_eq!(name("model.dim_customers.v2"), "dim_customers.v2.md");
+        assert_eq!(
+            name("model.orders__models_finance"),
+            "orders__models_finance.md"
+        );
+    }
+
+    #[test]
+    fn test_node_doc() {
+        let mut graph = LineageGraph::new();
+        let raw = graph.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        let mut orders = make_node("model.orders", "orders", NodeType::Model);
+        orders.description = Some("One row per order".into());
+        orders.materialization = Some("table".into());
+        orders.tags = vec!["core".into(), "daily".into()];
+        orders.file_path = Some(PathBuf::from("models/orders.sql"));
+        orders.columns = vec!["order_id".into(), "amount".into()];
+        orders
+            .column_types
+            .insert("order_id".into(), "integer".into());
+        orders
+            .column_descriptions
+            .insert("order_id".into(), "Order key".into());
+        let orders = graph.add_node(orders);
+        let revenue = graph.add_node(make_node("model.revenue", "revenue", NodeType::Model));
+        graph.add_edge(raw, orders, ref_edge());
+        graph.add_edge(orders, revenue, ref_edge());
+
+        let pages = HashMap::from([(revenue, "revenue.md".to_string())]);
+        assert_eq!(
+            node_doc(&graph, orders, &pages),
+            "# orders\n\n\
+             `model.orders`\n\n\
+             One row per order\n\n\
+             - **Materialization:** table\n\
+             - **Tags:** core, daily\n\
+             - **File:** `models/orders.sql`\n\n\
+             ## Columns\n\n\
+             - `order_id` (integer): Order key\n\
+             - `amount`\n\n\
+             ## Upstream\n\n\
+             - raw.orders (source)\n\n\
+             ## Downstream\n\n\
+             - [revenue](revenue.md) (model)\n"
+        );
+
+        let doc = node_doc(&graph, revenue, &pages);
+        assert!(doc.contains("## Columns\n\n_None known_\n"));
+        assert!(doc.ends_with("## Downstream\n\n_None_\n"));
+    }
+
+    #[test]
+    fn test_write_docs() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        graph.add_edge(a, b, ref_edge());
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("docs").join("models");
+        let written = write_docs(&graph, &[a, b], &dir).unwrap();
+        assert_eq!(written, vec![dir.join("a.md"), dir.join("b.md")]);
+        let page = std::fs::read_to_string(dir.join("b.md")).unwrap();
+        assert!(page.contains("- [a](a.md) (model)"));
+    }
+}
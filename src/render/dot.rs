@@ -2,14 +2,28 @@ use std::io::Write;
 
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
+use crate::graph::diff::DiffStatus;
 use crate::graph::types::*;
+use crate::render::diff::{diff_color, diff_font_color, DIFF_LEGEND};
+use crate::render::palette::Palette;
+use crate::render::status::{node_status, status_color, STATUS_LEGEND};
+use crate::render::RenderOptions;
 
 /// Render the lineage graph as Graphviz DOT format to stdout
 pub fn render_dot(graph: &LineageGraph) {
     render_dot_to_writer(graph, &mut std::io::stdout().lock());
 }
 
+/// Render DOT with optional group clusters and run status outlines
+pub fn render_dot_with_options(graph: &LineageGraph, options: &RenderOptions) {
+    write_dot(graph, options, &mut std::io::stdout().lock());
+}
+
 fn render_dot_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
+    write_dot(graph, &RenderOptions::default(), w);
+}
+
+fn write_dot<W: Write>(graph: &LineageGraph, options: &RenderOptions, w: &mut W) {
     writeln!(w, "digraph dbt_lineage {{").unwrap();
     writeln!(w, "  rankdir=LR;").unwrap();
     writeln!(
@@ -20,16 +34,31 @@ fn render_dot_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     writeln!(w).unwrap();
 
     // Render nodes
-    for idx in graph.node_indices() {
-        let node = &graph[idx];
-        let (color, fontcolor) = node_colors(node.node_type);
-        let label = node.display_name();
-        writeln!(
-            w,
-            "  \"{}\" [label=\"{}\", fillcolor=\"{}\", fontcolor=\"{}\"];",
-            node.unique_id, label, color, fontcolor
-        )
-        .unwrap();
+    match options.groups {
+        Some(groups) => {
+            for (i, (name, members)) in groups.clusters(graph).into_iter().enumerate() {
+                writeln!(w, "  subgraph \"cluster_{}\" {{", i).unwrap();
+                writeln!(w, "    label=\"{}\";", name.replace('"', "\\\"")).unwrap();
+                writeln!(w, "    style=dashed;").unwrap();
+                for idx in members {
+                    write!(w, "  ").unwrap();
+                    write_dot_node(&graph[idx], options, w);
+                }
+                writeln!(w, "  }}").unwrap();
+            }
+        }
+        None => {
+            for idx in graph.node_indices() {
+                write_dot_node(&graph[idx], options, w);
+            }
+        }
+    }
+
+    if options.run_status.is_some() {
+        write_dot_status_legend(w);
+    }
+    if options.diff.is_some() {
+        write_dot_diff_legend(w);
     }
 
     writeln!(w).unwrap();
@@ -44,9 +73,31 @@ fn render_dot_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
             EdgeType::Test => ", style=dotted",
             EdgeType::Exposure => ", style=bold",
         };
+        let change = options
+            .diff
+            .map(|diff| diff.edge_status(&source.unique_id, &target.unique_id));
+        let (style, color) = match change {
+            // Changed edges take the diff color; removed ones are dashed ghosts
+            Some(status @ DiffStatus::Added) => (
+                "",
+                format!(", color=\"{}\", penwidth=2", diff_color(status)),
+            ),
+            Some(status @ DiffStatus::Removed) => (
+                ", style=dashed",
+                format!(", color=\"{}\", penwidth=2", diff_color(status)),
+            ),
+            _ => (
+                style,
+                options
+                    .palette()
+                    .edge_color(edge.weight().edge_type)
+                    .map(|color| format!(", color=\"{}\"", color))
+                    .unwrap_or_default(),
+            ),
+        };
         writeln!(
             w,
-            "  \"{}\" -> \"{}\" [label=\"{}\"{style}];",
+            "  \"{}\" -> \"{}\" [label=\"{}\"{style}{color}];",
             source.unique_id,
             target.unique_id,
             edge.weight().edge_type_label(),
@@ -57,6 +108,82 @@ fn render_dot_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     writeln!(w, "}}").unwrap();
 }
 
+fn write_dot_node<W: Write>(node: &NodeData, options: &RenderOptions, w: &mut W) {
+    let change = options.diff.map(|diff| diff.node_status(&node.unique_id));
+    let (color, fontcolor) = match change {
+        Some(status) => (diff_color(status), diff_font_color(status)),
+        None => node_colors(options.palette(), node.node_type),
+    };
+    // Removed nodes are drawn as dashed ghosts
+    let ghost = if change == Some(DiffStatus::Removed) {
+        ", style=\"filled,dashed\""
+    } else {
+        ""
+    };
+    let outline = options
+        .run_status
+        .map(|map| {
+            format!(
+                ", color=\"{}\", penwidth=3",
+                status_color(node_status(map, &node.unique_id))
+            )
+        })
+        .unwrap_or_default();
+    // A shortened name keeps the full one as hover text
+    let name = options.shorten(node.display_name());
+    let tooltip = if name == node.display_name() {
+        String::new()
+    } else {
+        format!(", tooltip=\"{}\"", node.display_name())
+    };
+    writeln!(
+        w,
+        "  \"{}\" [label=\"{}\", fillcolor=\"{}\", fontcolor=\"{}\"{}{}{}];",
+        node.unique_id,
+        options.label_detail.apply(node, name),
+        color,
+        fontcolor,
+        ghost,
+        outline,
+        tooltip
+    )
+    .unwrap();
+}
+
+/// Legend cluster explaining the run status outline colors
+fn write_dot_status_legend<W: Write>(w: &mut W) {
+    writeln!(w, "  subgraph \"cluster_run_status_legend\" {{").unwrap();
+    writeln!(w, "    label=\"Run status\";").unwrap();
+    writeln!(w, "    style=dashed;").unwrap();
+    for (i, (label, color)) in STATUS_LEGEND.iter().enumerate() {
+        writeln!(
+            w,
+            "    \"__run_status_{}\" [label=\"{}\", fillcolor=\"white\", fontcolor=\"black\", color=\"{}\", penwidth=3];",
+            i, label, color
+        )
+        .unwrap();
+    }
+    writeln!(w, "  }}").unwrap();
+}
+
+/// Legend cluster explaining the change status fill colors of a drawn diff
+fn write_dot_diff_legend<W: Write>(w: &mut W) {
+    writeln!(w, "  subgraph \"cluster_diff_legend\" {{").unwrap();
+    writeln!(w, "    label=\"Changes\";").unwrap();
+    writeln!(w, "    style=dashed;").unwrap();
+    for status in DIFF_LEGEND {
+        writeln!(
+            w,
+            "    \"__diff_{0}\" [label=\"{0}\", fillcolor=\"{1}\", fontcolor=\"{2}\"];",
+            status.label(),
+            diff_color(status),
+            diff_font_color(status)
+        )
+        .unwrap();
+    }
+    writeln!(w, "  }}").unwrap();
+}
+
 impl EdgeData {
     fn edge_type_label(&self) -> &'static str {
         match self.edge_type {
@@ -68,32 +195,31 @@ impl EdgeData {
     }
 }
 
-fn node_colors(node_type: NodeType) -> (&'static str, &'static str) {
-    match node_type {
-        NodeType::Model => ("#4A90D9", "white"),
-        NodeType::Source => ("#27AE60", "white"),
-        NodeType::Seed => ("#F39C12", "white"),
-        NodeType::Snapshot => ("#8E44AD", "white"),
-        NodeType::Test => ("#1ABC9C", "white"),
-        NodeType::Exposure => ("#E74C3C", "white"),
-        NodeType::Phantom => ("#BDC3C7", "black"),
-    }
+/// Fill and label color for a node type, with dark labels on light fills
+fn node_colors(palette: &Palette, node_type: NodeType) -> (&str, &'static str) {
+    let fontcolor = if palette.has_light_fill(node_type) {
+        "black"
+    } else {
+        "white"
+    };
+    (palette.node_fill(node_type), fontcolor)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::groups::GroupConfig;
+    use crate::parser::artifacts::RunStatus;
+    use crate::parser::artifacts::RunStatusMap;
+    use crate::render::palette::DEFAULT_PALETTE;
+    use crate::render::LabelDetail;
 
     fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
         NodeData {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -135,6 +261,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -152,7 +279,10 @@ mod tests {
             (EdgeType::Exposure, "exposure"),
         ];
         for (et, expected) in types {
-            let ed = EdgeData { edge_type: et };
+            let ed = EdgeData {
+                edge_type: et,
+                snippet: None,
+            };
             assert_eq!(ed.edge_type_label(), expected);
         }
     }
@@ -169,7 +299,7 @@ mod tests {
             NodeType::Phantom,
         ];
         for nt in types {
-            let (color, fontcolor) = node_colors(nt);
+            let (color, fontcolor) = node_colors(&DEFAULT_PALETTE, nt);
             assert!(
                 color.starts_with('#'),
                 "Color for {:?} should start with #",
@@ -192,6 +322,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -199,6 +330,7 @@ mod tests {
             c,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -206,6 +338,7 @@ mod tests {
             d,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
@@ -253,6 +386,7 @@ mod tests {
             a,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -260,6 +394,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -267,6 +402,7 @@ mod tests {
             t,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -274,6 +410,7 @@ mod tests {
             e,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
@@ -286,4 +423,211 @@ mod tests {
         assert!(output.contains("style=dotted"));
         assert!(output.contains("style=bold"));
     }
+
+    #[test]
+    fn test_diff_colors_and_legend() {
+        use crate::graph::diff::DiffHighlight;
+
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        let c = graph.add_node(make_node("model.c", "c", NodeType::Model));
+        for target in [b, c] {
+            graph.add_edge(
+                a,
+                target,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        let mut highlight = DiffHighlight::default();
+        highlight.nodes.insert("model.b".into(), DiffStatus::Added);
+        highlight
+            .nodes
+            .insert("model.c".into(), DiffStatus::Removed);
+        highlight
+            .edges
+            .insert(("model.a".into(), "model.b".into()), DiffStatus::Added);
+        highlight
+            .edges
+            .insert(("model.a".into(), "model.c".into()), DiffStatus::Removed);
+        let options = RenderOptions {
+            diff: Some(&highlight),
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        write_dot(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output
+            .contains("\"model.a\" [label=\"a\", fillcolor=\"#ECF0F1\", fontcolor=\"black\"];"));
+        assert!(output
+            .contains("\"model.b\" [label=\"b\", fillcolor=\"#2ECC71\", fontcolor=\"black\"];"));
+        assert!(output.contains("\"model.c\" [label=\"c\", fillcolor=\"#E74C3C\", fontcolor=\"white\", style=\"filled,dashed\"];"));
+        assert!(output.contains(
+            "\"model.a\" -> \"model.b\" [label=\"ref\", color=\"#2ECC71\", penwidth=2];"
+        ));
+        assert!(output.contains("\"model.a\" -> \"model.c\" [label=\"ref\", style=dashed, color=\"#E74C3C\", penwidth=2];"));
+        assert!(output.contains("label=\"Changes\";"));
+        assert!(output.contains("\"__diff_removed\" [label=\"removed\""));
+    }
+
+    #[test]
+    fn test_run_status_outlines_and_legend() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        graph.add_node(make_node("model.broken", "broken", NodeType::Model));
+        graph.add_node(make_node("model.new", "new", NodeType::Model));
+        let mut status = RunStatusMap::new();
+        status.insert(
+            "model.orders".into(),
+            RunStatus::Success {
+                completed_at: chrono::Utc::now(),
+            },
+        );
+        status.insert(
+            "model.broken".into(),
+            RunStatus::Error {
+                completed_at: None,
+                message: "boom".into(),
+            },
+        );
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            run_status: Some(&status),
+            ..Default::default()
+        };
+        write_dot(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("\"model.orders\" [label=\"orders\", fillcolor=\"#4A90D9\", fontcolor=\"white\", color=\"#2ECC71\", penwidth=3];"));
+        assert!(output.contains("\"model.broken\" [label=\"broken\", fillcolor=\"#4A90D9\", fontcolor=\"white\", color=\"#E74C3C\", penwidth=3];"));
+        assert!(output.contains("\"model.new\" [label=\"new\", fillcolor=\"#4A90D9\", fontcolor=\"white\", color=\"#7F8C8D\", penwidth=3];"));
+        assert!(output.contains("label=\"Run status\";"));
+        assert!(output.contains("[label=\"skipped / never run\""));
+    }
+
+    #[test]
+    fn test_no_status_legend_by_default() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        let output = render_to_string(&graph);
+        assert!(!output.contains("penwidth"));
+        assert!(!output.contains("Run status"));
+    }
+
+    #[test]
+    fn test_grouped_clusters() {
+        let groups =
+            GroupConfig::parse("groups:\n  - name: Finance\n    models: ['fct_*']\n").unwrap();
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node(
+            "model.fct_revenue",
+            "fct_revenue",
+            NodeType::Model,
+        ));
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            groups: Some(&groups),
+            ..Default::default()
+        };
+        write_dot(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("subgraph \"cluster_0\" {"));
+        assert!(output.contains("label=\"Finance\";"));
+        assert!(output.contains("subgraph \"cluster_1\" {"));
+        assert!(output.contains("label=\"(other)\";"));
+        // Grouped nodes are declared inside their cluster
+        let finance = output.find("label=\"Finance\"").unwrap();
+        let other = output.find("label=\"(other)\"").unwrap();
+        let revenue = output.find("\"model.fct_revenue\" [").unwrap();
+        assert!(finance < revenue && revenue < other);
+    }
+
+    #[test]
+    fn test_ungrouped_has_no_clusters() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        assert!(!render_to_string(&graph).contains("subgraph"));
+    }
+
+    #[test]
+    fn test_max_label_len() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node(
+            "model.stg_stripe__payment_intents",
+            "stg_stripe__payment_intents",
+            NodeType::Model,
+        ));
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            max_label_len: Some(10),
+            ..Default::default()
+        };
+        write_dot(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains(
+            "[label=\"stg_strip…\", fillcolor=\"#4A90D9\", fontcolor=\"white\", tooltip=\"stg_stripe__payment_intents\"]"
+        ));
+        // Short names are left alone and get no tooltip
+        assert!(output.contains("[label=\"orders\", fillcolor=\"#4A90D9\", fontcolor=\"white\"]"));
+    }
+
+    #[test]
+    fn test_label_detail() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.orders", "orders", NodeType::Model);
+        node.materialization = Some("incremental".into());
+        graph.add_node(node);
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            label_detail: LabelDetail::Materialization,
+            ..Default::default()
+        };
+        write_dot(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("label=\"orders (incremental)\""));
+        // Plain output is unchanged
+        assert!(render_to_string(&graph).contains("label=\"orders\""));
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        let raw = graph.add_node(make_node("source.raw", "raw", NodeType::Source));
+        for (from, to, edge_type) in [(a, b, EdgeType::Ref), (raw, a, EdgeType::Source)] {
+            graph.add_edge(
+                from,
+                to,
+                EdgeData {
+                    edge_type,
+                    snippet: None,
+                },
+            );
+        }
+        let palette =
+            Palette::parse("nodes:\n  model: \"#F5F5DC\"\nedges:\n  ref: \"#888888\"\n").unwrap();
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            palette: Some(&palette),
+            ..Default::default()
+        };
+        write_dot(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        // A light fill gets a dark label; unset types keep the built-in colors
+        assert!(output.contains("[label=\"a\", fillcolor=\"#F5F5DC\", fontcolor=\"black\"]"));
+        assert!(output.contains("[label=\"src:raw\", fillcolor=\"#27AE60\", fontcolor=\"white\"]"));
+        assert!(output.contains("\"model.a\" -> \"model.b\" [label=\"ref\", color=\"#888888\"];"));
+        assert!(output.contains("\"source.raw\" -> \"model.a\" [label=\"source\", style=dashed];"));
+    }
 }
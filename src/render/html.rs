@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
 use std::io::Write;
 
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use serde::Serialize;
 
 use crate::graph::types::*;
+use crate::render::status::{node_status, status_label};
+use crate::render::RenderOptions;
 
 #[derive(Serialize)]
 struct HtmlJsonNode {
@@ -18,6 +21,12 @@ struct HtmlJsonNode {
     tags: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     columns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    column_types: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    run_status: Option<&'static str>,
 }
 
 #[derive(Serialize)]
@@ -33,7 +42,7 @@ struct HtmlJsonGraph {
     edges: Vec<HtmlJsonEdge>,
 }
 
-fn build_html_json(graph: &LineageGraph) -> String {
+fn build_html_json(graph: &LineageGraph, options: &RenderOptions) -> String {
     let nodes: Vec<HtmlJsonNode> = graph
         .node_indices()
         .map(|idx| {
@@ -46,6 +55,11 @@ fn build_html_json(graph: &LineageGraph) -> String {
                 materialization: node.materialization.clone(),
                 tags: node.tags.clone(),
                 columns: node.columns.clone(),
+                version: node.version.clone(),
+                column_types: node.column_types.clone(),
+                run_status: options
+                    .run_status
+                    .map(|map| status_label(node_status(map, &node.unique_id))),
             }
         })
         .collect();
@@ -78,9 +92,22 @@ pub fn render_html(graph: &LineageGraph) {
     render_html_to_writer(graph, &mut std::io::stdout().lock());
 }
 
+/// Render HTML with run status outlines to stdout
+pub fn render_html_with_options(graph: &LineageGraph, options: &RenderOptions) {
+    render_html_with_options_to_writer(graph, options, &mut std::io::stdout().lock());
+}
+
 pub fn render_html_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
-    let svg_content = crate::render::svg::render_svg_to_string(graph);
-    let json_data = build_html_json(graph);
+    render_html_with_options_to_writer(graph, &RenderOptions::default(), w);
+}
+
+pub fn render_html_with_options_to_writer<W: Write>(
+    graph: &LineageGraph,
+    options: &RenderOptions,
+    w: &mut W,
+) {
+    let svg_content = crate::render::svg::render_svg_to_string(graph, options);
+    let json_data = build_html_json(graph, options);
 
     write!(
         w,
@@ -110,6 +137,9 @@ body {{ background: #0d1117; color: #c9d1d9; font-family: -apple-system, BlinkMa
 .node:hover rect {{ stroke: #58a6ff; stroke-width: 2; }}
 .node.selected rect {{ stroke: #f0e68c; stroke-width: 2.5; }}
 .node.dimmed {{ opacity: 0.3; }}
+#tooltip {{ position: fixed; z-index: 20; display: none; max-width: 320px; background: #161b22; border: 1px solid #30363d; border-radius: 6px; padding: 8px 10px; font-size: 12px; pointer-events: none; }}
+#tooltip .name {{ color: #58a6ff; font-weight: bold; }}
+#tooltip .label {{ color: #8b949e; }}
 </style>
 </head>
 <body>
@@ -125,6 +155,7 @@ body {{ background: #0d1117; color: #c9d1d9; font-family: -apple-system, BlinkMa
 {svg_content}
     </div>
   </div>
+  <div id="tooltip"></div>
   <div id="detail-panel">
     <h2>Node Details</h2>
     <div id="detail-content"><div class="field">Click a node to inspect</div></div>
@@ -175,39 +206,88 @@ body {{ background: #0d1117; color: #c9d1d9; font-family: -apple-system, BlinkMa
     scale = 1; tx = 0; ty = 0; applyTransform();
   }};
 
-  // Node click
+  // Node selection; the selected node is mirrored into the URL fragment
+  // (`#node-<unique_id>`) so the page can be shared as a deep link.
+  function selectNode(g) {{
+    document.querySelectorAll('.node.selected').forEach(n => n.classList.remove('selected'));
+    g.classList.add('selected');
+    const id = g.getAttribute('data-id');
+    const node = nodeMap[id];
+    if (!node) return;
+    if (g.id && location.hash !== '#' + g.id) history.replaceState(null, '', '#' + g.id);
+    let html = `<div class="field"><span class="label">Name:</span> ${{node.label}}</div>`;
+    html += `<div class="field"><span class="label">Type:</span> ${{node.node_type}}</div>`;
+    html += `<div class="field"><span class="label">ID:</span> ${{node.unique_id}}</div>`;
+    if (node.version) html += `<div class="field"><span class="label">Version:</span> v${{node.version}}</div>`;
+    if (node.run_status) html += `<div class="field"><span class="label">Run status:</span> ${{node.run_status.replace('_', ' ')}}</div>`;
+    if (node.materialization) html += `<div class="field"><span class="label">Materialization:</span> ${{node.materialization}}</div>`;
+    if (node.description) html += `<div class="field"><span class="label">Description:</span> ${{node.description}}</div>`;
+    if (node.tags && node.tags.length) html += `<div class="field"><span class="label">Tags:</span> ${{node.tags.join(', ')}}</div>`;
+    if (node.columns && node.columns.length) {{
+      html += `<div class="field"><span class="label">Columns (${{node.columns.length}}):</span></div>`;
+      const types = node.column_types || {{}};
+      node.columns.forEach(c => html += `<div class="field">&nbsp;&nbsp;${{c}}${{types[c] ? ` <span class="label">${{types[c]}}</span>` : ''}}</div>`);
+    }}
+    // Find upstream/downstream
+    const upstream = data.edges.filter(e => e.target === id).map(e => nodeMap[e.source]).filter(Boolean);
+    const downstream = data.edges.filter(e => e.source === id).map(e => nodeMap[e.target]).filter(Boolean);
+    if (upstream.length) {{
+      html += `<div class="field"><span class="label">Upstream:</span></div>`;
+      upstream.forEach(n => html += `<div class="field">&nbsp;&nbsp;${{n.label}} (${{n.node_type}})</div>`);
+    }}
+    if (downstream.length) {{
+      html += `<div class="field"><span class="label">Downstream:</span></div>`;
+      downstream.forEach(n => html += `<div class="field">&nbsp;&nbsp;${{n.label}} (${{n.node_type}})</div>`);
+    }}
+    document.getElementById('detail-content').innerHTML = html;
+  }}
+
   document.querySelectorAll('.node').forEach(g => {{
-    g.addEventListener('click', () => {{
-      document.querySelectorAll('.node.selected').forEach(n => n.classList.remove('selected'));
-      g.classList.add('selected');
-      const id = g.getAttribute('data-id');
-      const node = nodeMap[id];
-      if (!node) return;
-      let html = `<div class="field"><span class="label">Name:</span> ${{node.label}}</div>`;
-      html += `<div class="field"><span class="label">Type:</span> ${{node.node_type}}</div>`;
-      html += `<div class="field"><span class="label">ID:</span> ${{node.unique_id}}</div>`;
-      if (node.materialization) html += `<div class="field"><span class="label">Materialization:</span> ${{node.materialization}}</div>`;
-      if (node.description) html += `<div class="field"><span class="label">Description:</span> ${{node.description}}</div>`;
-      if (node.tags && node.tags.length) html += `<div class="field"><span class="label">Tags:</span> ${{node.tags.join(', ')}}</div>`;
-      if (node.columns && node.columns.length) {{
-        html += `<div class="field"><span class="label">Columns (${{node.columns.length}}):</span></div>`;
-        node.columns.forEach(c => html += `<div class="field">&nbsp;&nbsp;${{c}}</div>`);
-      }}
-      // Find upstream/downstream
-      const upstream = data.edges.filter(e => e.target === id).map(e => nodeMap[e.source]).filter(Boolean);
-      const downstream = data.edges.filter(e => e.source === id).map(e => nodeMap[e.target]).filter(Boolean);
-      if (upstream.length) {{
-        html += `<div class="field"><span class="label">Upstream:</span></div>`;
-        upstream.forEach(n => html += `<div class="field">&nbsp;&nbsp;${{n.label}} (${{n.node_type}})</div>`);
-      }}
-      if (downstream.length) {{
-        html += `<div class="field"><span class="label">Downstream:</span></div>`;
-        downstream.forEach(n => html += `<div class="field">&nbsp;&nbsp;${{n.label}} (${{n.node_type}})</div>`);
-      }}
-      document.getElementById('detail-content').innerHTML = html;
+    g.addEventListener('click', () => selectNode(g));
+  }});
+
+  // Hover popover; it replaces the SVG's native <title> tooltips
+  const tooltip = document.getElementById('tooltip');
+  function esc(s) {{
+    const div = document.createElement('div');
+    div.textContent = s;
+    return div.innerHTML;
+  }}
+  document.querySelectorAll('.node').forEach(g => {{
+    const title = g.querySelector('title');
+    if (title) title.remove();
+    const node = nodeMap[g.getAttribute('data-id')];
+    if (!node) return;
+    g.addEventListener('mouseenter', () => {{
+      let html = `<div class="name">${{esc(node.label)}}${{node.version ? ` (v${{esc(node.version)}})` : ''}}</div>`;
+      html += `<div class="label">${{esc(node.unique_id)}}</div>`;
+      if (node.materialization) html += `<div><span class="label">materialized:</span> ${{esc(node.materialization)}}</div>`;
+      if (node.description) html += `<div>${{esc(node.description)}}</div>`;
+      tooltip.innerHTML = html;
+      tooltip.style.display = 'block';
+    }});
+    g.addEventListener('mousemove', e => {{
+      tooltip.style.left = (e.clientX + 12) + 'px';
+      tooltip.style.top = (e.clientY + 12) + 'px';
     }});
+    g.addEventListener('mouseleave', () => tooltip.style.display = 'none');
   }});
 
+  // Deep links: center and select the node named by the URL fragment
+  function focusFromHash() {{
+    const anchor = decodeURIComponent(location.hash.slice(1));
+    if (!anchor) return;
+    const g = document.getElementById(anchor);
+    if (!g || !g.classList.contains('node')) return;
+    const bb = g.getBBox();
+    tx = graphArea.clientWidth / 2 - (bb.x + bb.width / 2) * scale;
+    ty = graphArea.clientHeight / 2 - (bb.y + bb.height / 2) * scale;
+    applyTransform();
+    selectNode(g);
+  }}
+  window.addEventListener('hashchange', focusFromHash);
+  focusFromHash();
+
   // Search
   const searchInput = document.getElementById('search');
   searchInput.addEventListener('input', () => {{
@@ -238,11 +318,7 @@ mod tests {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -285,6 +361,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -298,11 +375,38 @@ mod tests {
     fn test_json_data_embedded() {
         let mut graph = LineageGraph::new();
         graph.add_node(make_node("model.a", "a", NodeType::Model));
-        let json = build_html_json(&graph);
+        let json = build_html_json(&graph, &RenderOptions::default());
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["nodes"].as_array().unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_run_status_in_json_and_svg() {
+        use crate::parser::artifacts::{RunStatus, RunStatusMap};
+
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let mut status = RunStatusMap::new();
+        status.insert("model.a".into(), RunStatus::Skipped { completed_at: None });
+        let options = RenderOptions {
+            run_status: Some(&status),
+            ..Default::default()
+        };
+
+        let json = build_html_json(&graph, &options);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["nodes"][0]["run_status"], "skipped");
+
+        let mut buf = Vec::new();
+        render_html_with_options_to_writer(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains(r#"data-status="skipped""#));
+        assert!(output.contains("Run status:"));
+
+        let json = build_html_json(&graph, &RenderOptions::default());
+        assert!(!json.contains("run_status"));
+    }
+
     #[test]
     fn test_node_with_full_metadata() {
         let mut graph = LineageGraph::new();
@@ -310,14 +414,14 @@ mod tests {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: NodeType::Model,
-            file_path: None,
             description: Some("All completed orders".into()),
             materialization: Some("table".into()),
             tags: vec!["nightly".into(), "finance".into()],
             columns: vec!["order_id".into(), "customer_id".into(), "amount".into()],
+            ..Default::default()
         });
 
-        let json = build_html_json(&graph);
+        let json = build_html_json(&graph, &RenderOptions::default());
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         let node = &parsed["nodes"][0];
         assert_eq!(node["unique_id"], "model.orders");
@@ -349,6 +453,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -356,6 +461,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -363,6 +469,7 @@ mod tests {
             test,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -370,10 +477,11 @@ mod tests {
             exp,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
-        let json = build_html_json(&graph);
+        let json = build_html_json(&graph, &RenderOptions::default());
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         let edges = parsed["edges"].as_array().unwrap();
         assert_eq!(edges.len(), 4);
@@ -398,6 +506,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -409,4 +518,36 @@ mod tests {
         assert!(output.contains("fit-btn"));
         assert!(output.contains("const data ="));
     }
+
+    #[test]
+    fn test_node_anchors_and_hash_handling() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node(
+            "source.raw.orders",
+            "raw.orders",
+            NodeType::Source,
+        ));
+        graph.add_node(make_node("model.stg orders", "stg orders", NodeType::Model));
+
+        let output = render_to_string(&graph);
+        assert!(output.contains(r#"id="node-source.raw.orders""#));
+        assert!(output.contains(r#"id="node-model.stg_orders""#));
+        assert!(output.contains("location.hash"));
+        assert!(output.contains("hashchange"));
+    }
+
+    #[test]
+    fn test_hover_popover_from_embedded_data() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.orders", "orders", NodeType::Model);
+        node.description = Some("One row per order".into());
+        graph.add_node(node);
+
+        let output = render_to_string(&graph);
+        assert!(output.contains(r#"<div id="tooltip"></div>"#));
+        assert!(output.contains("mouseenter"));
+        assert!(output.contains("title.remove()"));
+        // The SVG keeps its native tooltip for viewers without the script
+        assert!(output.contains("<title>orders\nmodel.orders\nOne row per order</title>"));
+    }
 }
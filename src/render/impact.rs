@@ -2,7 +2,9 @@ use std::io::Write;
 
 use colored::Colorize;
 
-use crate::graph::impact::{ImpactReport, ImpactSeverity};
+use crate::graph::impact::{ImpactDirection, ImpactReport, ImpactSeverity};
+use crate::graph::types::NodeType;
+use crate::render::markdown::{close_details, escape_cell, node_cell, open_details, type_groups};
 
 /// Render impact report as colored text to stdout
 pub fn render_impact_text(report: &ImpactReport) {
@@ -19,6 +21,11 @@ fn severity_color(severity: ImpactSeverity) -> colored::Color {
 }
 
 pub fn render_impact_text_to_writer<W: Write>(report: &ImpactReport, w: &mut W) {
+    if report.direction == ImpactDirection::Upstream {
+        render_root_cause_text_to_writer(report, w);
+        return;
+    }
+
     writeln!(w).unwrap();
     writeln!(
         w,
@@ -28,41 +35,127 @@ pub fn render_impact_text_to_writer<W: Write>(report: &ImpactReport, w: &mut W)
     .unwrap();
     writeln!(w, "{}", "=".repeat(50)).unwrap();
 
-    let severity_str = report
-        .overall_severity
-        .label()
-        .to_uppercase()
-        .color(severity_color(report.overall_severity))
-        .bold();
-    writeln!(w, "Overall Severity: {}", severity_str).unwrap();
-    writeln!(w).unwrap();
+    if let Some(severity) = report.overall_severity {
+        let severity_str = severity
+            .label()
+            .to_uppercase()
+            .color(severity_color(severity))
+            .bold();
+        writeln!(w, "Overall Severity: {}", severity_str).unwrap();
+        writeln!(w).unwrap();
+    }
 
     writeln!(w, "{}", "Summary:".bold()).unwrap();
     writeln!(w, "  Affected models:    {}", report.affected_models).unwrap();
     writeln!(w, "  Affected tests:     {}", report.affected_tests).unwrap();
     writeln!(w, "  Affected exposures: {}", report.affected_exposures).unwrap();
+    writeln!(
+        w,
+        "  Direct dependents:  {}",
+        report.impacted_nodes.iter().filter(|n| n.direct).count()
+    )
+    .unwrap();
     writeln!(
         w,
         "  Longest path:       {} hops",
         report.longest_path_length
     )
     .unwrap();
+    writeln!(w, "  Rebuild cost:       {}", report.rebuild_cost).unwrap();
     writeln!(w).unwrap();
 
-    if !report.longest_path.is_empty() {
+    if report.longest_path.len() > 1 {
         writeln!(w, "{}", "Longest Path:".bold()).unwrap();
         writeln!(w, "  {}", report.longest_path.join(" -> ")).unwrap();
         writeln!(w).unwrap();
     }
 
-    if !report.impacted_nodes.is_empty() {
+    if report.impacted_nodes.is_empty() {
+        if report.source_node_type == NodeType::Exposure.label() {
+            writeln!(
+                w,
+                "{} is an exposure: nothing is downstream of it",
+                report.source_model
+            )
+            .unwrap();
+        } else {
+            writeln!(w, "{} has no downstream dependents", report.source_model).unwrap();
+        }
+    } else {
         writeln!(w, "{}", "Impacted Nodes:".bold()).unwrap();
         for node in &report.impacted_nodes {
             let sev = node.severity.label().color(severity_color(node.severity));
+            let coupling = if node.direct {
+                "direct".bold()
+            } else {
+                "transitive".normal()
+            };
+            writeln!(
+                w,
+                "  [{:<8}] {} ({}, {} hops, {})",
+                sev, node.label, node.node_type, node.distance, coupling
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(w).unwrap();
+}
+
+/// Upstream report: what the model depends on, nearest first. Severity is about
+/// downstream blast radius, so it is left out here.
+fn render_root_cause_text_to_writer<W: Write>(report: &ImpactReport, w: &mut W) {
+    writeln!(w).unwrap();
+    writeln!(
+        w,
+        "{}",
+        format!("Root-Cause Analysis: {}", report.source_model).bold()
+    )
+    .unwrap();
+    writeln!(w, "{}", "=".repeat(50)).unwrap();
+    writeln!(w).unwrap();
+
+    writeln!(w, "{}", "Summary:".bold()).unwrap();
+    writeln!(w, "  Upstream models:    {}", report.affected_models).unwrap();
+    writeln!(w, "  Upstream sources:   {}", report.affected_sources).unwrap();
+    writeln!(w, "  Upstream seeds:     {}", report.affected_seeds).unwrap();
+    writeln!(
+        w,
+        "  Longest chain:      {} hops",
+        report.longest_path_length
+    )
+    .unwrap();
+    writeln!(w).unwrap();
+
+    if report.longest_path.len() > 1 {
+        writeln!(w, "{}", "Longest Chain:".bold()).unwrap();
+        writeln!(w, "  {}", report.longest_path.join(" -> ")).unwrap();
+        writeln!(w).unwrap();
+    }
+
+    if report.impacted_nodes.is_empty() {
+        if report.source_node_type == NodeType::Source.label() {
             writeln!(
                 w,
-                "  [{:<8}] {} ({}, {} hops)",
-                sev, node.label, node.node_type, node.distance
+                "{} is a source: nothing is upstream of it",
+                report.source_model
+            )
+            .unwrap();
+        } else {
+            writeln!(w, "{} has no upstream dependencies", report.source_model).unwrap();
+        }
+    } else {
+        writeln!(
+            w,
+            "{}",
+            format!("{} depends on:", report.source_model).bold()
+        )
+        .unwrap();
+        for node in &report.impacted_nodes {
+            writeln!(
+                w,
+                "  {} ({}, {} hops)",
+                node.label, node.node_type, node.distance
             )
             .unwrap();
         }
@@ -81,18 +174,139 @@ pub fn render_impact_json_to_writer<W: Write>(report: &ImpactReport, w: &mut W)
     writeln!(w).unwrap();
 }
 
+/// Render impact report as GitHub-flavored Markdown to stdout
+pub fn render_impact_markdown(report: &ImpactReport, link_prefix: Option<&str>) {
+    render_impact_markdown_to_writer(report, link_prefix, &mut std::io::stdout().lock());
+}
+
+/// Markdown for a PR comment: a table of counts, then the impacted nodes in a
+/// collapsed `<details>` section per node type. Node names link to their
+/// files when `link_prefix` is given.
+pub fn render_impact_markdown_to_writer<W: Write>(
+    report: &ImpactReport,
+    link_prefix: Option<&str>,
+    w: &mut W,
+) {
+    let upstream = report.direction == ImpactDirection::Upstream;
+    let source = escape_cell(&report.source_model);
+    if upstream {
+        writeln!(w, "### Root-cause analysis: `{}`\n", source).unwrap();
+    } else {
+        writeln!(w, "### Impact analysis: `{}`\n", source).unwrap();
+        if let Some(severity) = report.overall_severity {
+            writeln!(w, "**Overall severity:** {}\n", severity.label()).unwrap();
+        }
+    }
+
+    let direct = report.impacted_nodes.iter().filter(|n| n.direct).count();
+    let rows: Vec<(&str, String)> = if upstream {
+        vec![
+            ("Upstream models", report.affected_models.to_string()),
+            ("Upstream sources", report.affected_sources.to_string()),
+            ("Upstream seeds", report.affected_seeds.to_string()),
+            ("Direct dependencies", direct.to_string()),
+            (
+                "Longest chain",
+                format!("{} hops", report.longest_path_length),
+            ),
+        ]
+    } else {
+        vec![
+            ("Affected models", report.affected_models.to_string()),
+            ("Affected tests", report.affected_tests.to_string()),
+            ("Affected exposures", report.affected_exposures.to_string()),
+            ("Direct dependents", direct.to_string()),
+            (
+                "Longest path",
+                format!("{} hops", report.longest_path_length),
+            ),
+            ("Rebuild cost", report.rebuild_cost.to_string()),
+        ]
+    };
+    writeln!(w, "| | Count |\n| --- | ---: |").unwrap();
+    for (name, value) in rows {
+        writeln!(w, "| {} | {} |", name, value).unwrap();
+    }
+    writeln!(w).unwrap();
+
+    if report.longest_path.len() > 1 {
+        let path: Vec<String> = report
+            .longest_path
+            .iter()
+            .map(|label| format!("`{}`", label))
+            .collect();
+        let title = if upstream {
+            "Longest chain"
+        } else {
+            "Longest path"
+        };
+        writeln!(w, "**{}:** {}\n", title, path.join(" → ")).unwrap();
+    }
+
+    if report.impacted_nodes.is_empty() {
+        let direction = if upstream { "upstream" } else { "downstream" };
+        writeln!(w, "_Nothing {} of `{}`._", direction, source).unwrap();
+        return;
+    }
+    let groups = type_groups(|label| {
+        report
+            .impacted_nodes
+            .iter()
+            .any(|node| node.node_type == label)
+    });
+    for (label, heading) in groups {
+        let nodes: Vec<_> = report
+            .impacted_nodes
+            .iter()
+            .filter(|node| node.node_type == label)
+            .collect();
+        open_details(w, &heading, nodes.len());
+        if upstream {
+            writeln!(w, "| Node | Hops |\n| --- | ---: |").unwrap();
+        } else {
+            writeln!(
+                w,
+                "| Node | Severity | Hops | Coupling |\n| --- | --- | ---: | --- |"
+            )
+            .unwrap();
+        }
+        for node in nodes {
+            let name = node_cell(&node.label, node.file_path.as_deref(), link_prefix);
+            if upstream {
+                writeln!(w, "| {} | {} |", name, node.distance).unwrap();
+            } else {
+                writeln!(
+                    w,
+                    "| {} | {} | {} | {} |",
+                    name,
+                    node.severity.label(),
+                    node.distance,
+                    if node.direct { "direct" } else { "transitive" }
+                )
+                .unwrap();
+            }
+        }
+        close_details(w);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::impact::{ImpactReport, ImpactSeverity, ImpactedNode};
+    use crate::graph::impact::{ImpactDirection, ImpactReport, ImpactSeverity, ImpactedNode};
 
     fn make_report() -> ImpactReport {
         ImpactReport {
             source_model: "stg_orders".to_string(),
-            overall_severity: ImpactSeverity::Critical,
+            source_node_type: "model".to_string(),
+            direction: ImpactDirection::Downstream,
+            overall_severity: Some(ImpactSeverity::Critical),
             affected_models: 1,
             affected_tests: 1,
             affected_exposures: 1,
+            affected_sources: 0,
+            affected_seeds: 0,
+            rebuild_cost: 5,
             longest_path_length: 3,
             longest_path: vec![
                 "stg_orders".to_string(),
@@ -106,6 +320,8 @@ mod tests {
                     node_type: "exposure".to_string(),
                     severity: ImpactSeverity::Critical,
                     distance: 2,
+                    direct: false,
+                    file_path: None,
                 },
                 ImpactedNode {
                     unique_id: "model.orders".to_string(),
@@ -113,6 +329,8 @@ mod tests {
                     node_type: "model".to_string(),
                     severity: ImpactSeverity::High,
                     distance: 1,
+                    direct: true,
+                    file_path: None,
                 },
                 ImpactedNode {
                     unique_id: "test.orders_positive".to_string(),
@@ -120,6 +338,8 @@ mod tests {
                     node_type: "test".to_string(),
                     severity: ImpactSeverity::Low,
                     distance: 2,
+                    direct: false,
+                    file_path: None,
                 },
             ],
         }
@@ -139,6 +359,10 @@ mod tests {
         assert!(output.contains("Longest Path:"));
         assert!(output.contains("stg_orders -> orders -> dashboard"));
         assert!(output.contains("Impacted Nodes:"));
+        assert!(output.contains("Direct dependents:  1"));
+        assert!(output.contains("Rebuild cost:       5"));
+        assert!(output.contains("orders (model, 1 hops, direct)"));
+        assert!(output.contains("dashboard (exposure, 2 hops, transitive)"));
     }
 
     #[test]
@@ -152,17 +376,25 @@ mod tests {
         assert_eq!(parsed["source_model"], "stg_orders");
         assert_eq!(parsed["overall_severity"], "critical");
         assert_eq!(parsed["affected_models"], 1);
+        assert_eq!(parsed["rebuild_cost"], 5);
         assert_eq!(parsed["impacted_nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(parsed["impacted_nodes"][0]["direct"], false);
+        assert_eq!(parsed["impacted_nodes"][1]["direct"], true);
     }
 
     #[test]
     fn test_render_impact_text_empty() {
         let report = ImpactReport {
             source_model: "isolated".to_string(),
-            overall_severity: ImpactSeverity::Low,
+            source_node_type: "model".to_string(),
+            direction: ImpactDirection::Downstream,
+            overall_severity: Some(ImpactSeverity::Low),
             affected_models: 0,
             affected_tests: 0,
             affected_exposures: 0,
+            affected_sources: 0,
+            affected_seeds: 0,
+            rebuild_cost: 0,
             longest_path_length: 0,
             longest_path: vec![],
             impacted_nodes: vec![],
@@ -172,6 +404,36 @@ mod tests {
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("Impact Analysis: isolated"));
         assert!(output.contains("Affected models:    0"));
+        assert!(output.contains("isolated has no downstream dependents"));
+        assert!(!output.contains("Longest Path:"));
+    }
+
+    #[test]
+    fn test_render_impact_text_leaf_exposure() {
+        let report = ImpactReport {
+            source_model: "dashboard".to_string(),
+            source_node_type: "exposure".to_string(),
+            longest_path: vec!["dashboard".to_string()],
+            impacted_nodes: vec![],
+            ..make_report()
+        };
+        let mut buf = Vec::new();
+        render_impact_text_to_writer(&report, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("dashboard is an exposure: nothing is downstream of it"));
+        assert!(!output.contains("Impacted Nodes:"));
+
+        let report = ImpactReport {
+            source_model: "raw.orders".to_string(),
+            source_node_type: "source".to_string(),
+            direction: ImpactDirection::Upstream,
+            impacted_nodes: vec![],
+            ..make_report()
+        };
+        let mut buf = Vec::new();
+        render_impact_text_to_writer(&report, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("raw.orders is a source: nothing is upstream of it"));
     }
 
     #[test]
@@ -192,10 +454,15 @@ mod tests {
     fn test_render_impact_text_medium_severity() {
         let report = ImpactReport {
             source_model: "stg_payments".to_string(),
-            overall_severity: ImpactSeverity::Medium,
+            source_node_type: "model".to_string(),
+            direction: ImpactDirection::Downstream,
+            overall_severity: Some(ImpactSeverity::Medium),
             affected_models: 2,
             affected_tests: 0,
             affected_exposures: 0,
+            affected_sources: 0,
+            affected_seeds: 0,
+            rebuild_cost: 0,
             longest_path_length: 2,
             longest_path: vec!["stg_payments".to_string(), "payments".to_string()],
             impacted_nodes: vec![ImpactedNode {
@@ -204,6 +471,8 @@ mod tests {
                 node_type: "model".to_string(),
                 severity: ImpactSeverity::Medium,
                 distance: 1,
+                direct: true,
+                file_path: None,
             }],
         };
         let mut buf = Vec::new();
@@ -217,4 +486,95 @@ mod tests {
         assert!(output.contains("Impacted Nodes:"));
         assert!(output.contains("payments"));
     }
+
+    #[test]
+    fn test_render_root_cause_text() {
+        let report = ImpactReport {
+            source_model: "orders".to_string(),
+            source_node_type: "model".to_string(),
+            direction: ImpactDirection::Upstream,
+            overall_severity: None,
+            affected_models: 1,
+            affected_tests: 0,
+            affected_exposures: 0,
+            affected_sources: 1,
+            affected_seeds: 0,
+            rebuild_cost: 0,
+            longest_path_length: 2,
+            longest_path: vec![
+                "raw.orders".to_string(),
+                "stg_orders".to_string(),
+                "orders".to_string(),
+            ],
+            impacted_nodes: vec![ImpactedNode {
+                unique_id: "model.stg_orders".to_string(),
+                label: "stg_orders".to_string(),
+                node_type: "model".to_string(),
+                severity: ImpactSeverity::Medium,
+                distance: 1,
+                direct: true,
+                file_path: None,
+            }],
+        };
+        let mut buf = Vec::new();
+        render_impact_text_to_writer(&report, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Root-Cause Analysis: orders"));
+        assert!(output.contains("Upstream sources:   1"));
+        assert!(output.contains("raw.orders -> stg_orders -> orders"));
+        assert!(output.contains("orders depends on:"));
+        assert!(output.contains("stg_orders (model, 1 hops)"));
+        assert!(!output.contains("Impact Analysis"));
+        assert!(!output.contains("Overall Severity"));
+
+        let mut buf = Vec::new();
+        render_impact_json_to_writer(&report, &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["direction"], "upstream");
+        assert!(parsed.get("overall_severity").is_none());
+
+        let mut buf = Vec::new();
+        render_impact_json_to_writer(&report, &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["direction"], "upstream");
+    }
+
+    #[test]
+    fn test_render_impact_markdown() {
+        let mut report = make_report();
+        report.impacted_nodes[1].label = "orders|v2".to_string();
+        report.impacted_nodes[1].file_path = Some("models/orders.sql".into());
+        let mut buf = Vec::new();
+        render_impact_markdown_to_writer(&report, Some("https://x/blob/main"), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.starts_with(
+            "### Impact analysis: `stg_orders`\n\n\
+             **Overall severity:** critical\n\n\
+             | | Count |\n| --- | ---: |\n\
+             | Affected models | 1 |\n"
+        ));
+        assert!(output.contains("| Rebuild cost | 5 |\n"));
+        assert!(output.contains("**Longest path:** `stg_orders` → `orders` → `dashboard`"));
+        // Groups follow node type order, not the report's order
+        let models = output.find("<summary>Models (1)</summary>").unwrap();
+        let tests = output.find("<summary>Tests (1)</summary>").unwrap();
+        let exposures = output.find("<summary>Exposures (1)</summary>").unwrap();
+        assert!(models < tests && tests < exposures);
+        assert!(output.contains(
+            "| [`orders\\|v2`](https://x/blob/main/models/orders.sql) | high | 1 | direct |"
+        ));
+        assert!(output.contains("| `dashboard` | critical | 2 | transitive |"));
+        assert!(!output.contains("Sources"));
+
+        report.direction = ImpactDirection::Upstream;
+        report.overall_severity = None;
+        report.impacted_nodes.clear();
+        let mut buf = Vec::new();
+        render_impact_markdown_to_writer(&report, None, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.starts_with("### Root-cause analysis: `stg_orders`\n\n| | Count |"));
+        assert!(!output.contains("severity"));
+        assert!(output.ends_with("_Nothing upstream of `stg_orders`._\n"));
+    }
 }
@@ -1,12 +1,37 @@
+use std::collections::{BTreeMap, HashSet};
 use std::io::Write;
+use std::path::Path;
 
+use anyhow::Result;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::graph::stats::upstream_source_count;
 use crate::graph::types::*;
+use crate::parser::column_lineage::InheritedDescription;
+use crate::render::ascii::TreeDirection;
+use crate::render::RenderOptions;
+
+/// Layout of `-o json` (`--json-shape`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonShape {
+    /// Flat `nodes` and `edges` lists
+    #[default]
+    Flat,
+    /// Nested `{ node, children }` objects walking out from the focus model
+    Tree,
+}
+
+/// Version of the flat `-o json` layout, written as `format_version`. Bump it
+/// when a field is renamed or removed, so old `check --baseline` files are
+/// rejected instead of reporting every node as changed.
+pub const JSON_FORMAT_VERSION: u32 = 1;
 
 #[derive(Serialize)]
 struct JsonGraph {
+    format_version: u32,
     nodes: Vec<JsonNode>,
     edges: Vec<JsonEdge>,
 }
@@ -22,10 +47,80 @@ struct JsonNode {
     description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     materialization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot: Option<JsonSnapshot>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     tags: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     columns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    column_types: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    column_descriptions: BTreeMap<String, JsonColumnDescription>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    test_kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    /// Linked by an edge to a node of another project
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    cross_project: bool,
+    /// Distinct sources upstream of the node in the exported graph
+    upstream_sources: usize,
+}
+
+/// A snapshot's strategy settings
+#[derive(Serialize)]
+struct JsonSnapshot {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unique_key: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    check_cols: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonColumnDescription {
+    description: String,
+    /// Documented upstream column the description was inherited from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inherited_from: Option<JsonColumnRef>,
+}
+
+#[derive(Serialize)]
+struct JsonColumnRef {
+    node: String,
+    column: String,
+}
+
+/// One node of `--json-shape tree`
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsonTreeEntry {
+    Node {
+        node: Box<JsonNode>,
+        children: Vec<JsonTreeEntry>,
+    },
+    /// A node already expanded elsewhere in the tree
+    Ref {
+        unique_id: String,
+        #[serde(rename = "ref")]
+        is_ref: bool,
+    },
+}
+
+/// Root of `--json-shape tree` walking both ways
+#[derive(Serialize)]
+struct JsonTreeBothWays {
+    node: Box<JsonNode>,
+    upstream: Vec<JsonTreeEntry>,
+    downstream: Vec<JsonTreeEntry>,
 }
 
 #[derive(Serialize)]
@@ -33,6 +128,8 @@ struct JsonEdge {
     source: String,
     target: String,
     edge_type: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    cross_project: bool,
 }
 
 /// Render the lineage graph as JSON to stdout
@@ -40,22 +137,93 @@ pub fn render_json(graph: &LineageGraph) {
     render_json_to_writer(graph, &mut std::io::stdout().lock());
 }
 
+/// Render JSON, adding column descriptions inherited through column lineage
+pub fn render_json_with_options(graph: &LineageGraph, options: &RenderOptions) {
+    write_json(graph, options, &mut std::io::stdout().lock());
+}
+
 fn render_json_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
+    write_json(graph, &RenderOptions::default(), w);
+}
+
+/// Write the graph as flat JSON to `path`
+pub fn write_json_file(graph: &LineageGraph, path: &Path) -> Result<()> {
+    let mut buf = Vec::new();
+    render_json_to_writer(graph, &mut buf);
+    std::fs::write(path, buf).map_err(|e| crate::error::DbtLineageError::FileWriteError {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(())
+}
+
+/// Documented descriptions of a node's columns plus the ones it inherits
+fn node_column_descriptions(
+    node: &NodeData,
+    inherited: &[InheritedDescription],
+) -> BTreeMap<String, JsonColumnDescription> {
+    let mut descriptions: BTreeMap<String, JsonColumnDescription> = node
+        .column_descriptions
+        .iter()
+        .map(|(column, description)| {
+            (
+                column.clone(),
+                JsonColumnDescription {
+                    description: description.clone(),
+                    inherited_from: None,
+                },
+            )
+        })
+        .collect();
+    for d in inherited.iter().filter(|d| d.node == node.unique_id) {
+        descriptions
+            .entry(d.column.clone())
+            .or_insert_with(|| JsonColumnDescription {
+                description: d.description.clone(),
+                inherited_from: Some(JsonColumnRef {
+                    node: d.source_node.clone(),
+                    column: d.source_column.clone(),
+                }),
+            });
+    }
+    descriptions
+}
+
+fn json_node(graph: &LineageGraph, idx: NodeIndex, inherited: &[InheritedDescription]) -> JsonNode {
+    let node = &graph[idx];
+    JsonNode {
+        unique_id: node.unique_id.clone(),
+        label: node.label.clone(),
+        node_type: node.node_type.label().to_string(),
+        file_path: node.file_path.as_ref().map(|p| p.to_string_lossy().into()),
+        description: node.description.clone(),
+        materialization: node.materialization.clone(),
+        schema: node.schema.clone(),
+        snapshot: node.snapshot.clone().map(|s| JsonSnapshot {
+            strategy: s.strategy,
+            unique_key: s.unique_key,
+            updated_at: s.updated_at,
+            check_cols: s.check_cols,
+        }),
+        tags: node.tags.clone(),
+        columns: node.columns.clone(),
+        version: node.version.clone(),
+        column_types: node.column_types.clone(),
+        column_descriptions: node_column_descriptions(node, inherited),
+        test_kind: node.test_kind.map(|k| k.label()),
+        project: node.project.clone(),
+        cross_project: graph
+            .neighbors_undirected(idx)
+            .any(|other| node.in_other_project(&graph[other])),
+        upstream_sources: upstream_source_count(graph, idx),
+    }
+}
+
+fn write_json<W: Write>(graph: &LineageGraph, options: &RenderOptions, w: &mut W) {
+    let inherited = options.inherited_descriptions.unwrap_or_default();
     let nodes: Vec<JsonNode> = graph
         .node_indices()
-        .map(|idx| {
-            let node = &graph[idx];
-            JsonNode {
-                unique_id: node.unique_id.clone(),
-                label: node.label.clone(),
-                node_type: node.node_type.label().to_string(),
-                file_path: node.file_path.as_ref().map(|p| p.to_string_lossy().into()),
-                description: node.description.clone(),
-                materialization: node.materialization.clone(),
-                tags: node.tags.clone(),
-                columns: node.columns.clone(),
-            }
-        })
+        .map(|idx| json_node(graph, idx, inherited))
         .collect();
 
     let edges: Vec<JsonEdge> = graph
@@ -67,16 +235,107 @@ fn render_json_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
                 source: source.unique_id.clone(),
                 target: target.unique_id.clone(),
                 edge_type: edge_type_label(edge.weight().edge_type),
+                cross_project: source.in_other_project(target),
             }
         })
         .collect();
 
-    let json_graph = JsonGraph { nodes, edges };
+    let json_graph = JsonGraph {
+        format_version: JSON_FORMAT_VERSION,
+        nodes,
+        edges,
+    };
     serde_json::to_writer_pretty(&mut *w, &json_graph).unwrap();
     writeln!(w).unwrap();
 }
 
-fn edge_type_label(edge_type: EdgeType) -> String {
+/// Render the neighborhood of `root` as nested JSON to stdout
+#[cfg(not(tarpaulin_include))]
+pub fn render_json_tree_with_options(
+    graph: &LineageGraph,
+    root: NodeIndex,
+    directions: &[TreeDirection],
+    options: &RenderOptions,
+) {
+    write_json_tree(
+        graph,
+        root,
+        directions,
+        options,
+        &mut std::io::stdout().lock(),
+    );
+}
+
+/// Write the neighborhood of `root` as `{ node, children }` objects. With one
+/// direction the root holds `children`; with both it holds `upstream` and
+/// `downstream` lists instead. As in `-o tree`, a node reached again through
+/// another branch (a diamond) is written as `{ unique_id, ref: true }` and not
+/// expanded, so shared ancestors appear in full only once per direction.
+pub fn write_json_tree<W: Write>(
+    graph: &LineageGraph,
+    root: NodeIndex,
+    directions: &[TreeDirection],
+    options: &RenderOptions,
+    w: &mut W,
+) {
+    let inherited = options.inherited_descriptions.unwrap_or_default();
+    let walk = |direction: TreeDirection| {
+        let mut seen = HashSet::from([root]);
+        tree_children(graph, root, direction, inherited, &mut seen)
+    };
+    let node = Box::new(json_node(graph, root, inherited));
+    match directions {
+        [direction] => {
+            let tree = JsonTreeEntry::Node {
+                node,
+                children: walk(*direction),
+            };
+            serde_json::to_writer_pretty(&mut *w, &tree).unwrap();
+        }
+        _ => {
+            let tree = JsonTreeBothWays {
+                node,
+                upstream: walk(TreeDirection::Upstream),
+                downstream: walk(TreeDirection::Downstream),
+            };
+            serde_json::to_writer_pretty(&mut *w, &tree).unwrap();
+        }
+    }
+    writeln!(w).unwrap();
+}
+
+fn tree_children(
+    graph: &LineageGraph,
+    parent: NodeIndex,
+    direction: TreeDirection,
+    inherited: &[InheritedDescription],
+    seen: &mut HashSet<NodeIndex>,
+) -> Vec<JsonTreeEntry> {
+    let mut children: Vec<NodeIndex> = graph
+        .neighbors_directed(parent, direction.edge_direction())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    children.sort_by_key(|&idx| graph[idx].display_name());
+
+    children
+        .into_iter()
+        .map(|child| {
+            if !seen.insert(child) {
+                return JsonTreeEntry::Ref {
+                    unique_id: graph[child].unique_id.clone(),
+                    is_ref: true,
+                };
+            }
+            JsonTreeEntry::Node {
+                node: Box::new(json_node(graph, child, inherited)),
+                children: tree_children(graph, child, direction, inherited, seen),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn edge_type_label(edge_type: EdgeType) -> String {
     match edge_type {
         EdgeType::Ref => "ref",
         EdgeType::Source => "source",
@@ -96,11 +355,7 @@ mod tests {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -115,6 +370,7 @@ mod tests {
         let graph = LineageGraph::new();
         let output = render_to_string(&graph);
         let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["format_version"], JSON_FORMAT_VERSION);
         assert_eq!(parsed["nodes"].as_array().unwrap().len(), 0);
         assert_eq!(parsed["edges"].as_array().unwrap().len(), 0);
     }
@@ -143,9 +399,7 @@ mod tests {
             node_type: NodeType::Model,
             file_path: Some(PathBuf::from("models/orders.sql")),
             description: Some("Orders mart model".into()),
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let output = render_to_string(&graph);
         let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -168,6 +422,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -178,6 +433,10 @@ mod tests {
         assert_eq!(edges[0]["source"], "source.raw.orders");
         assert_eq!(edges[0]["target"], "model.stg_orders");
         assert_eq!(edges[0]["edge_type"], "source");
+
+        let nodes = parsed["nodes"].as_array().unwrap();
+        assert_eq!(nodes[0]["upstream_sources"], 0);
+        assert_eq!(nodes[1]["upstream_sources"], 1);
     }
 
     #[test]
@@ -221,6 +480,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         let output = render_to_string(&graph);
@@ -235,11 +495,10 @@ mod tests {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
             materialization: Some("table".into()),
             tags: vec!["daily".into(), "core".into()],
             columns: vec!["order_id".into(), "customer_id".into()],
+            ..Default::default()
         });
         let output = render_to_string(&graph);
         let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -250,4 +509,190 @@ mod tests {
         assert_eq!(node["columns"][0], "order_id");
         assert_eq!(node["columns"][1], "customer_id");
     }
+
+    #[test]
+    fn test_snapshot_config() {
+        let mut graph = LineageGraph::new();
+        let mut snapshot = make_node("snapshot.snap_orders", "snap_orders", NodeType::Snapshot);
+        snapshot.snapshot = Some(SnapshotConfig {
+            strategy: Some("check".into()),
+            unique_key: vec!["order_id".into()],
+            updated_at: None,
+            check_cols: vec!["status".into()],
+        });
+        graph.add_node(snapshot);
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        let parsed: serde_json::Value = serde_json::from_str(&render_to_string(&graph)).unwrap();
+        assert_eq!(
+            parsed["nodes"][0]["snapshot"],
+            serde_json::json!({
+                "strategy": "check",
+                "unique_key": ["order_id"],
+                "check_cols": ["status"]
+            })
+        );
+        assert!(parsed["nodes"][1].get("snapshot").is_none());
+    }
+
+    #[test]
+    fn test_versioned_node() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(NodeData {
+            unique_id: "model.dim_customers.v2".into(),
+            label: "dim_customers".into(),
+            node_type: NodeType::Model,
+            version: Some("2".into()),
+            ..Default::default()
+        });
+        graph.add_node(NodeData {
+            unique_id: "model.orders".into(),
+            label: "orders".into(),
+            node_type: NodeType::Model,
+            ..Default::default()
+        });
+        let output = render_to_string(&graph);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["nodes"][0]["label"], "dim_customers");
+        assert_eq!(parsed["nodes"][0]["version"], "2");
+        assert!(parsed["nodes"][1].get("version").is_none());
+    }
+
+    #[test]
+    fn test_column_descriptions_with_inherited_provenance() {
+        let mut graph = LineageGraph::new();
+        let mut stg = make_node("model.stg_orders", "stg_orders", NodeType::Model);
+        stg.column_descriptions = BTreeMap::from([("order_id".into(), "Order key".into())]);
+        graph.add_node(stg);
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+
+        let inherited = vec![InheritedDescription {
+            node: "model.orders".into(),
+            column: "id".into(),
+            description: "Order key".into(),
+            source_node: "model.stg_orders".into(),
+            source_column: "order_id".into(),
+        }];
+        let options = RenderOptions {
+            inherited_descriptions: Some(&inherited),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        write_json(&graph, &options, &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+
+        let own = &parsed["nodes"][0]["column_descriptions"]["order_id"];
+        assert_eq!(own["description"], "Order key");
+        assert!(own.get("inherited_from").is_none());
+        let id = &parsed["nodes"][1]["column_descriptions"]["id"];
+        assert_eq!(id["description"], "Order key");
+        assert_eq!(id["inherited_from"]["node"], "model.stg_orders");
+        assert_eq!(id["inherited_from"]["column"], "order_id");
+    }
+
+    #[test]
+    fn test_column_types_from_catalog() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(NodeData {
+            unique_id: "model.orders".into(),
+            label: "orders".into(),
+            node_type: NodeType::Model,
+            columns: vec!["order_id".into()],
+            column_types: BTreeMap::from([("order_id".into(), "INTEGER".into())]),
+            ..Default::default()
+        });
+        let output = render_to_string(&graph);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["nodes"][0]["column_types"]["order_id"], "INTEGER");
+    }
+
+    #[test]
+    fn test_cross_project_marks() {
+        let mut graph = LineageGraph::new();
+        let project_node = |id: &str, label: &str, project: &str| NodeData {
+            project: Some(project.into()),
+            ..make_node(id, label, NodeType::Model)
+        };
+        let customers = graph.add_node(project_node("model.core.customers", "customers", "core"));
+        let stg = graph.add_node(project_node("model.core.stg", "stg", "core"));
+        let orders = graph.add_node(project_node("model.finance.orders", "orders", "finance"));
+        for (a, b) in [(stg, customers), (customers, orders)] {
+            graph.add_edge(
+                a,
+                b,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&render_to_string(&graph)).unwrap();
+        let nodes = parsed["nodes"].as_array().unwrap();
+        assert_eq!(nodes[0]["project"], "core");
+        assert_eq!(nodes[0]["cross_project"], true);
+        assert!(nodes[1].get("cross_project").is_none());
+        assert_eq!(nodes[2]["cross_project"], true);
+        let edges = parsed["edges"].as_array().unwrap();
+        assert!(edges[0].get("cross_project").is_none());
+        assert_eq!(edges[1]["cross_project"], true);
+
+        // Graphs from SQL or a single manifest carry neither field
+        let single = render_to_string(&{
+            let mut g = LineageGraph::new();
+            g.add_node(make_node("model.orders", "orders", NodeType::Model));
+            g
+        });
+        assert!(!single.contains("project"));
+    }
+
+    #[test]
+    fn test_json_tree_marks_revisited_nodes() {
+        // raw.orders feeds orders through both stg_a and stg_b
+        let mut graph = LineageGraph::new();
+        let raw = graph.add_node(make_node("source.raw.orders", "orders", NodeType::Source));
+        let stg_a = graph.add_node(make_node("model.stg_a", "stg_a", NodeType::Model));
+        let stg_b = graph.add_node(make_node("model.stg_b", "stg_b", NodeType::Model));
+        let orders = graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        for (from, to) in [(raw, stg_a), (raw, stg_b), (stg_a, orders), (stg_b, orders)] {
+            graph.add_edge(
+                from,
+                to,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        let render = |directions: &[TreeDirection]| {
+            let mut buf = Vec::new();
+            write_json_tree(
+                &graph,
+                orders,
+                directions,
+                &RenderOptions::default(),
+                &mut buf,
+            );
+            serde_json::from_slice::<serde_json::Value>(&buf).unwrap()
+        };
+
+        let tree = render(&[TreeDirection::Upstream]);
+        assert_eq!(tree["node"]["unique_id"], "model.orders");
+        let children = tree["children"].as_array().unwrap();
+        assert_eq!(children[0]["node"]["label"], "stg_a");
+        assert_eq!(children[1]["node"]["label"], "stg_b");
+        let first = &children[0]["children"][0];
+        assert_eq!(first["node"]["unique_id"], "source.raw.orders");
+        assert_eq!(first["children"].as_array().unwrap().len(), 0);
+        // The second path to the source only points back at it
+        assert_eq!(
+            children[1]["children"][0],
+            serde_json::json!({"unique_id": "source.raw.orders", "ref": true})
+        );
+
+        let both = render(&[TreeDirection::Upstream, TreeDirection::Downstream]);
+        assert_eq!(both["node"]["unique_id"], "model.orders");
+        assert!(both.get("children").is_none());
+        assert_eq!(both["upstream"].as_array().unwrap().len(), 2);
+        assert_eq!(both["downstream"].as_array().unwrap().len(), 0);
+    }
 }
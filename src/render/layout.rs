@@ -3,7 +3,39 @@ use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use std::collections::HashMap;
 
-use crate::graph::types::LineageGraph;
+use crate::graph::types::{LineageGraph, NodeData};
+
+/// Tag prefix used to pin a node to a minimum layer, e.g. `layer:marts` or `layer:2`
+pub const LAYER_TAG_PREFIX: &str = "layer:";
+
+/// Named layer bands understood by `layer:<name>` tags
+const NAMED_LAYERS: &[(&str, usize)] = &[
+    ("raw", 0),
+    ("source", 0),
+    ("staging", 1),
+    ("intermediate", 2),
+    ("marts", 3),
+    ("mart", 3),
+];
+
+/// Minimum layer requested by a node's `layer:` tag, if any.
+/// Accepts a named band (raw, staging, intermediate, marts) or a number.
+/// With several layer tags the highest wins.
+pub fn layer_hint(node: &NodeData) -> Option<usize> {
+    node.tags
+        .iter()
+        .filter_map(|tag| tag.strip_prefix(LAYER_TAG_PREFIX))
+        .filter_map(|value| {
+            let value = value.trim().to_lowercase();
+            value.parse::<usize>().ok().or_else(|| {
+                NAMED_LAYERS
+                    .iter()
+                    .find(|(name, _)| *name == value)
+                    .map(|(_, layer)| *layer)
+            })
+        })
+        .max()
+}
 
 /// Layout result: each node gets a (layer, position_within_layer) coordinate
 #[derive(Debug, Clone)]
@@ -20,6 +52,18 @@ pub struct LayoutResult {
 
 /// Perform simplified Sugiyama layout
 pub fn sugiyama_layout(graph: &LineageGraph) -> LayoutResult {
+    sugiyama_layout_wrapped(graph, None)
+}
+
+/// Sugiyama layout that wraps any layer holding more than `max_layer_width`
+/// nodes into several adjacent columns of near-equal height, so one source
+/// feeding hundreds of models doesn't produce a single endless column.
+/// Positions and layers describe the wrapped columns; edges still point from
+/// earlier to later columns.
+pub fn sugiyama_layout_wrapped(
+    graph: &LineageGraph,
+    max_layer_width: Option<usize>,
+) -> LayoutResult {
     if graph.node_count() == 0 {
         return LayoutResult {
             positions: HashMap::new(),
@@ -34,6 +78,10 @@ pub fn sugiyama_layout(graph: &LineageGraph) -> LayoutResult {
 
     // Step 2: Order nodes within layers to minimize crossings (barycenter method)
     let ordered_layers = reduce_crossings(graph, &layers);
+    let ordered_layers = match max_layer_width {
+        Some(max) if max > 0 => wrap_layers(ordered_layers, max),
+        _ => ordered_layers,
+    };
 
     // Step 3: Build position map
     let mut positions = HashMap::new();
@@ -54,9 +102,42 @@ pub fn sugiyama_layout(graph: &LineageGraph) -> LayoutResult {
     }
 }
 
-/// Assign layers using longest path from roots (nodes with no incoming edges)
+/// Split every layer longer than `max` into the fewest columns of at most
+/// `max` nodes, balanced so the last column isn't a short remainder. The
+/// crossing-reduced order is kept, read column by column.
+fn wrap_layers(layers: Vec<Vec<NodeIndex>>, max: usize) -> Vec<Vec<NodeIndex>> {
+    layers
+        .into_iter()
+        .flat_map(|layer| {
+            let columns = layer.len().div_ceil(max).max(1);
+            // The first `len % columns` columns take one node more
+            let (height, extra) = (layer.len() / columns, layer.len() % columns);
+            let mut rest = layer.as_slice();
+            (0..columns)
+                .map(|i| {
+                    let (column, tail) = rest.split_at(height + usize::from(i < extra));
+                    rest = tail;
+                    column.to_vec()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Assign layers using longest path from roots (nodes with no incoming edges).
+/// A node's `layer:` tag raises its layer to at least the hinted rank; since
+/// successors are placed after their predecessors this stays acyclic.
+/// The named bands are fixed columns: empty layers up to the highest band a
+/// tag asks for are kept, so `layer:marts` lands in the same column whether or
+/// not the graph has staging models. Empty layers beyond them are dropped.
 fn assign_layers(graph: &LineageGraph) -> Vec<Vec<NodeIndex>> {
     let mut layer_of: HashMap<NodeIndex, usize> = HashMap::new();
+    let last_band = NAMED_LAYERS
+        .iter()
+        .map(|(_, layer)| *layer)
+        .max()
+        .unwrap_or(0);
+    let mut highest_hint = 0;
 
     // Use topological order for longest-path layer assignment
     if let Ok(topo) = petgraph::algo::toposort(graph, None) {
@@ -71,6 +152,15 @@ fn assign_layers(graph: &LineageGraph) -> Vec<Vec<NodeIndex>> {
             } else {
                 predecessors.iter().max().unwrap() + 1
             };
+            // Empty layers past the named bands are dropped below, so a hint
+            // beyond them and the node count can't place a node any further
+            // right; clamping it keeps `layer:<huge>` from overflowing or
+            // allocating millions of layers
+            let hint = layer_hint(&graph[*node])
+                .unwrap_or(0)
+                .min(graph.node_count().max(last_band));
+            highest_hint = highest_hint.max(hint);
+            let layer = layer.max(hint);
             layer_of.insert(*node, layer);
         }
     } else {
@@ -87,8 +177,14 @@ fn assign_layers(graph: &LineageGraph) -> Vec<Vec<NodeIndex>> {
         layers[*layer].push(*node);
     }
 
-    // Remove empty layers
-    layers.retain(|l| !l.is_empty());
+    // Remove empty layers, except the named bands a `layer:` tag relies on
+    let kept_bands = highest_hint.min(last_band);
+    let mut index = 0;
+    layers.retain(|l| {
+        let keep = !l.is_empty() || index < kept_bands;
+        index += 1;
+        keep
+    });
 
     layers
 }
@@ -174,12 +270,53 @@ mod tests {
             unique_id: unique_id.into(),
             label: unique_id.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_wide_layer_wraps_into_columns() {
+        // One source feeding seven models: the model layer is 7 nodes tall
+        let mut g = LineageGraph::new();
+        let src = g.add_node(make_node("source.raw.orders", NodeType::Source));
+        let models: Vec<NodeIndex> = (0..7)
+            .map(|i| g.add_node(make_node(&format!("model.m{}", i), NodeType::Model)))
+            .collect();
+        for &model in &models {
+            g.add_edge(
+                src,
+                model,
+                EdgeData {
+                    edge_type: EdgeType::Source,
+                    snippet: None,
+                },
+            );
+        }
+
+        let plain = sugiyama_layout(&g);
+        assert_eq!(plain.num_layers, 2);
+        assert_eq!(plain.max_layer_width, 7);
+
+        // 7 nodes at most 3 high: three balanced columns
+        let wrapped = sugiyama_layout_wrapped(&g, Some(3));
+        assert_eq!(wrapped.num_layers, 4);
+        assert_eq!(wrapped.max_layer_width, 3);
+        let heights: Vec<usize> = wrapped.layers.iter().map(Vec::len).collect();
+        assert_eq!(heights, vec![1, 3, 2, 2]);
+        for &model in &models {
+            let (layer, pos) = wrapped.positions[&model];
+            assert!(layer >= 1);
+            assert_eq!(wrapped.layers[layer][pos], model);
         }
+
+        // 7 nodes at most 4 high: two columns of 4 and 3
+        let wrapped = sugiyama_layout_wrapped(&g, Some(4));
+        let heights: Vec<usize> = wrapped.layers.iter().map(Vec::len).collect();
+        assert_eq!(heights, vec![1, 4, 3]);
+
+        // Narrow enough layers are untouched
+        let wrapped = sugiyama_layout_wrapped(&g, Some(7));
+        assert_eq!(wrapped.num_layers, 2);
     }
 
     #[test]
@@ -195,6 +332,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         // c has no edges — it's a disconnected node
@@ -215,37 +353,26 @@ mod tests {
             unique_id: "a".into(),
             label: "a".into(),
             node_type: NodeType::Source,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let b = g.add_node(NodeData {
             unique_id: "b".into(),
             label: "b".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let c = g.add_node(NodeData {
             unique_id: "c".into(),
             label: "c".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         g.add_edge(
             a,
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -253,6 +380,7 @@ mod tests {
             c,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -266,6 +394,120 @@ mod tests {
         assert!(lb < lc);
     }
 
+    #[test]
+    fn test_layer_hint_parsing() {
+        let mut node = make_node("m", NodeType::Model);
+        assert_eq!(layer_hint(&node), None);
+        node.tags = vec!["finance".into(), "layer:marts".into()];
+        assert_eq!(layer_hint(&node), Some(3));
+        node.tags = vec!["layer:Staging".into()];
+        assert_eq!(layer_hint(&node), Some(1));
+        node.tags = vec!["layer:5".into(), "layer:intermediate".into()];
+        assert_eq!(layer_hint(&node), Some(5));
+        node.tags = vec!["layer:unknown".into()];
+        assert_eq!(layer_hint(&node), None);
+    }
+
+    #[test]
+    fn test_layer_hint_pins_shortcut_node() {
+        // src -> stg -> int -> mart, plus src -> int_short (a shortcut that
+        // would otherwise land in the staging band)
+        let mut g = LineageGraph::new();
+        let src = g.add_node(make_node("src", NodeType::Source));
+        let stg = g.add_node(make_node("stg", NodeType::Model));
+        let int = g.add_node(make_node("int", NodeType::Model));
+        let mart = g.add_node(make_node("mart", NodeType::Model));
+        let mut short = make_node("int_short", NodeType::Model);
+        short.tags = vec!["layer:intermediate".into()];
+        let short = g.add_node(short);
+        let edge = || EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        };
+        g.add_edge(src, stg, edge());
+        g.add_edge(stg, int, edge());
+        g.add_edge(int, mart, edge());
+        g.add_edge(src, short, edge());
+        g.add_edge(short, mart, edge());
+
+        let layout = sugiyama_layout(&g);
+        assert_eq!(layout.positions[&short].0, layout.positions[&int].0);
+        assert!(layout.positions[&short].0 < layout.positions[&mart].0);
+    }
+
+    #[test]
+    fn test_layer_hint_pushes_successors() {
+        let mut g = LineageGraph::new();
+        let a = g.add_node(make_node("a", NodeType::Source));
+        let mut pinned = make_node("b", NodeType::Model);
+        pinned.tags = vec!["layer:3".into()];
+        let b = g.add_node(pinned);
+        let c = g.add_node(make_node("c", NodeType::Model));
+        let d = g.add_node(make_node("d", NodeType::Model));
+        let edge = || EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        };
+        g.add_edge(a, b, edge());
+        g.add_edge(b, c, edge());
+        g.add_edge(a, d, edge());
+
+        let layout = sugiyama_layout(&g);
+        // The empty band before b is kept so that `layer:3` is column 3
+        assert_eq!(layout.positions[&b].0, 3);
+        assert!(layout.positions[&a].0 < layout.positions[&b].0);
+        assert!(layout.positions[&b].0 < layout.positions[&c].0);
+        assert!(layout.positions[&d].0 < layout.positions[&b].0);
+    }
+
+    #[test]
+    fn test_huge_layer_hint_is_clamped() {
+        let mut g = LineageGraph::new();
+        let a = g.add_node(make_node("a", NodeType::Source));
+        let mut pinned = make_node("b", NodeType::Model);
+        pinned.tags = vec![format!("layer:{}", usize::MAX)];
+        let b = g.add_node(pinned);
+        let c = g.add_node(make_node("c", NodeType::Model));
+        let edge = || EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        };
+        g.add_edge(a, b, edge());
+        g.add_edge(b, c, edge());
+
+        // Clamped to the node count, 3: a, two empty bands, b, then c
+        let layout = sugiyama_layout(&g);
+        assert_eq!(layout.num_layers, 5);
+        assert_eq!(layout.positions[&b].0, 3);
+        assert_eq!(layout.positions[&c].0, 4);
+    }
+
+    #[test]
+    fn test_named_band_is_fixed_when_earlier_bands_are_missing() {
+        // raw -> orders (marts), with and without a staging model in between
+        let edge = || EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        };
+        let mut mart = make_node("orders", NodeType::Model);
+        mart.tags = vec!["layer:marts".into()];
+
+        let mut g = LineageGraph::new();
+        let raw = g.add_node(make_node("raw", NodeType::Source));
+        let orders = g.add_node(mart.clone());
+        g.add_edge(raw, orders, edge());
+        let without_staging = sugiyama_layout(&g);
+
+        let stg = g.add_node(make_node("stg_orders", NodeType::Model));
+        g.add_edge(raw, stg, edge());
+        g.add_edge(stg, orders, edge());
+        let with_staging = sugiyama_layout(&g);
+
+        assert_eq!(without_staging.positions[&orders].0, 3);
+        assert_eq!(with_staging.positions[&orders].0, 3);
+        assert_eq!(without_staging.num_layers, 4);
+    }
+
     #[test]
     fn test_cyclic_graph_fallback() {
         // Covers lines 78-79: cyclic graph fallback in assign_layers
@@ -278,6 +520,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         g.add_edge(
@@ -285,6 +528,7 @@ mod tests {
             a,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use petgraph::stable_graph::NodeIndex;
+
+use crate::graph::types::LineageGraph;
+
+/// Print the given nodes one per line to stdout
+pub fn render_list(graph: &LineageGraph, nodes: &[NodeIndex], labels: bool) {
+    render_list_to_writer(graph, nodes, labels, &mut std::io::stdout().lock());
+}
+
+/// Write one unique_id per line, or one label per line when `labels` is set, in
+/// the order given. Labels are deduplicated, since versions of a model share one.
+pub fn render_list_to_writer<W: Write>(
+    graph: &LineageGraph,
+    nodes: &[NodeIndex],
+    labels: bool,
+    w: &mut W,
+) {
+    if labels {
+        let mut seen = HashSet::new();
+        for &idx in nodes {
+            let name = graph[idx].label.as_str();
+            if seen.insert(name) {
+                writeln!(w, "{}", name).unwrap();
+            }
+        }
+    } else {
+        for &idx in nodes {
+            writeln!(w, "{}", graph[idx].unique_id).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::*;
+
+    fn make_node(unique_id: &str, label: &str) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type: NodeType::Model,
+            ..Default::default()
+        }
+    }
+
+    fn render(labels: bool) -> String {
+        let mut graph = LineageGraph::new();
+        let nodes = vec![
+            graph.add_node(make_node("model.dim_customers.v1", "dim_customers")),
+            graph.add_node(make_node("model.dim_customers.v2", "dim_customers")),
+            graph.add_node(make_node("model.orders", "orders")),
+        ];
+        let mut buf = Vec::new();
+        render_list_to_writer(&graph, &nodes, labels, &mut buf);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_unique_ids_one_per_line() {
+        assert_eq!(
+            render(false),
+            "model.dim_customers.v1\nmodel.dim_customers.v2\nmodel.orders\n"
+        );
+    }
+
+    #[test]
+    fn test_labels_deduplicated() {
+        assert_eq!(render(true), "dim_customers\norders\n");
+    }
+
+    #[test]
+    fn test_labels_keep_given_order() {
+        let mut graph = LineageGraph::new();
+        let orders = graph.add_node(make_node("model.orders", "orders"));
+        let v1 = graph.add_node(make_node("model.dim_customers.v1", "dim_customers"));
+        let v2 = graph.add_node(make_node("model.dim_customers.v2", "dim_customers"));
+        let mut buf = Vec::new();
+        render_list_to_writer(&graph, &[orders, v2, v1], true, &mut buf);
+        assert_eq!(String::from_utf8(buf).unwrap(), "orders\ndim_customers\n");
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+
+use petgraph::Direction;
+use serde::Serialize;
+
+use crate::graph::types::*;
+
+/// Minimal `manifest.json`: just enough for `--manifest` and other tools that
+/// read nodes, sources, exposures and their `depends_on`
+#[derive(Serialize)]
+struct ManifestOut {
+    metadata: ManifestMetadata,
+    nodes: BTreeMap<String, NodeOut>,
+    sources: BTreeMap<String, SourceOut>,
+    exposures: BTreeMap<String, ExposureOut>,
+}
+
+#[derive(Serialize)]
+struct ManifestMetadata {
+    project_name: String,
+}
+
+#[derive(Serialize)]
+struct NodeOut {
+    unique_id: String,
+    name: String,
+    resource_type: &'static str,
+    package_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_file_path: Option<String>,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    meta: BTreeMap<String, serde_json::Value>,
+    config: ConfigOut,
+    depends_on: DependsOnOut,
+    columns: BTreeMap<String, ColumnOut>,
+    /// Marks a generic test; the name and arguments of the generic test are not
+    /// known, so it is left empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    test_metadata: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Serialize)]
+struct SourceOut {
+    unique_id: String,
+    name: String,
+    source_name: String,
+    resource_type: &'static str,
+    package_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_file_path: Option<String>,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    meta: BTreeMap<String, serde_json::Value>,
+    columns: BTreeMap<String, ColumnOut>,
+}
+
+#[derive(Serialize)]
+struct ExposureOut {
+    unique_id: String,
+    name: String,
+    resource_type: &'static str,
+    package_name: String,
+    description: String,
+    depends_on: DependsOnOut,
+}
+
+#[derive(Serialize)]
+struct ConfigOut {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    materialized: Option<String>,
+    /// Custom schema (`+schema` / `config(schema=...)`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    meta: BTreeMap<String, serde_json::Value>,
+    /// Snapshot settings, only set on snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unique_key: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    check_cols: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct DependsOnOut {
+    macros: Vec<String>,
+    nodes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ColumnOut {
+    name: String,
+    description: String,
+}
+
+/// Expand a graph unique_id back to dbt's form by inserting the project name
+/// after the resource type: `model.orders` -> `model.shop.orders`,
+/// `source.raw.orders` -> `source.shop.raw.orders`
+pub fn expand_unique_id(unique_id: &str, project_name: &str) -> String {
+    match unique_id.split_once('.') {
+        Some((resource, rest)) => format!("{}.{}.{}", resource, project_name, rest),
+        None => unique_id.to_string(),
+    }
+}
+
+/// Write the graph as a minimal manifest to stdout
+pub fn render_manifest(graph: &LineageGraph, project_name: &str) {
+    render_manifest_to_writer(graph, project_name, &mut std::io::stdout().lock());
+}
+
+/// Write the graph as a minimal manifest to `path`
+pub fn write_manifest(graph: &LineageGraph, project_name: &str, path: &Path) -> Result<()> {
+    let mut buf = Vec::new();
+    render_manifest_to_writer(graph, project_name, &mut buf);
+    std::fs::write(path, buf).map_err(|e| crate::error::DbtLineageError::FileWriteError {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(())
+}
+
+/// Write the graph as a minimal manifest.
+///
+/// Models, seeds, snapshots and tests go under `nodes`, sources and exposures
+/// under their own keys, each with its upstream nodes in `depends_on.nodes`.
+/// Phantom nodes have no manifest counterpart and are left out, along with the
+/// dependencies on them.
+pub fn render_manifest_to_writer<W: Write>(graph: &LineageGraph, project_name: &str, w: &mut W) {
+    let mut manifest = ManifestOut {
+        metadata: ManifestMetadata {
+            project_name: project_name.to_string(),
+        },
+        nodes: BTreeMap::new(),
+        sources: BTreeMap::new(),
+        exposures: BTreeMap::new(),
+    };
+
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        let unique_id = expand_unique_id(&node.unique_id, project_name);
+        let path = node
+            .file_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().replace('\\', "/"));
+        let description = node.description.clone().unwrap_or_default();
+        let mut depends_on: Vec<String> = graph
+            .neighbors_directed(idx, Direction::Incoming)
+            .filter(|&dep| graph[dep].node_type != NodeType::Phantom)
+            .map(|dep| expand_unique_id(&graph[dep].unique_id, project_name))
+            .collect();
+        depends_on.sort();
+        depends_on.dedup();
+        let depends_on = DependsOnOut {
+            macros: vec![],
+            nodes: depends_on,
+        };
+
+        match node.node_type {
+            NodeType::Phantom => {}
+            NodeType::Source => {
+                let (source_name, name) = source_parts(node);
+                manifest.sources.insert(
+                    unique_id.clone(),
+                    SourceOut {
+                        unique_id,
+                        name,
+                        source_name,
+                        resource_type: "source",
+                        package_name: project_name.to_string(),
+                        path: path.clone(),
+                        original_file_path: path,
+                        description,
+                        schema: node.schema.clone(),
+                        meta: node.meta.clone(),
+                        columns: manifest_columns(node),
+                    },
+                );
+            }
+            NodeType::Exposure => {
+                manifest.exposures.insert(
+                    unique_id.clone(),
+                    ExposureOut {
+                        unique_id,
+                        name: node.label.clone(),
+                        resource_type: "exposure",
+                        package_name: project_name.to_string(),
+                        description,
+                        depends_on,
+                    },
+                );
+            }
+            NodeType::Model | NodeType::Seed | NodeType::Snapshot | NodeType::Test => {
+                let snapshot = node.snapshot.clone().unwrap_or_default();
+                manifest.nodes.insert(
+                    unique_id.clone(),
+                    NodeOut {
+                        unique_id,
+                        name: node.label.clone(),
+                        resource_type: node.node_type.label(),
+                        package_name: project_name.to_string(),
+                        path: path.clone(),
+                        original_file_path: path,
+                        description,
+                        version: node.version.clone(),
+                        tags: node.tags.clone(),
+                        meta: node.meta.clone(),
+                        config: ConfigOut {
+                            materialized: node.materialization.clone(),
+                            schema: node.schema.clone(),
+                            tags: node.tags.clone(),
+                            meta: node.meta.clone(),
+                            strategy: snapshot.strategy,
+                            unique_key: snapshot.unique_key,
+                            updated_at: snapshot.updated_at,
+                            check_cols: snapshot.check_cols,
+                        },
+                        depends_on,
+                        columns: manifest_columns(node),
+                        test_metadata: (node.test_kind == Some(TestKind::Generic))
+                            .then(BTreeMap::new),
+                    },
+                );
+            }
+        }
+    }
+
+    serde_json::to_writer_pretty(&mut *w, &manifest).unwrap();
+    writeln!(w).unwrap();
+}
+
+/// `(source_name, table_name)` of a source node, from `source.<source>.<table>`
+fn source_parts(node: &NodeData) -> (String, String) {
+    let rest = node
+        .unique_id
+        .strip_prefix("source.")
+        .unwrap_or(&node.label);
+    match rest.split_once('.') {
+        Some((source, table)) => (source.to_string(), table.to_string()),
+        None => (String::new(), rest.to_string()),
+    }
+}
+
+/// Every known column, with its documented description (empty when undocumented)
+fn manifest_columns(node: &NodeData) -> BTreeMap<String, ColumnOut> {
+    node.columns
+        .iter()
+        .chain(node.column_descriptions.keys())
+        .map(|name| {
+            let column = ColumnOut {
+                name: name.clone(),
+                description: node
+                    .column_descriptions
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            (name.clone(), column)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::manifest::{build_graph_from_parsed_manifest, Manifest};
+    use petgraph::stable_graph::NodeIndex;
+    use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
+        NodeData {
+            unique_id: unique_id.into(),
+            label: label.into(),
+            node_type,
+            ..Default::default()
+        }
+    }
+
+    fn edge(g: &mut LineageGraph, a: NodeIndex, b: NodeIndex, edge_type: EdgeType) {
+        g.add_edge(
+            a,
+            b,
+            EdgeData {
+                edge_type,
+                snippet: None,
+            },
+        );
+    }
+
+    /// raw.orders -> stg_orders -> orders (v2) -> dashboard; orders -> test;
+    /// stg_orders also reads an unresolved ref
+    fn make_graph() -> LineageGraph {
+        let mut g = LineageGraph::new();
+        let mut raw_node = make_node("source.raw.orders", "raw.orders", NodeType::Source);
+        raw_node.schema = Some("raw_data".into());
+        raw_node.meta = BTreeMap::from([("owner".to_string(), serde_json::json!("data-eng"))]);
+        let raw = g.add_node(raw_node);
+        let mut stg = make_node("model.stg_orders", "stg_orders", NodeType::Model);
+        stg.file_path = Some(PathBuf::from("models/staging/stg_orders.sql"));
+        stg.materialization = Some("view".into());
+        stg.schema = Some("staging".into());
+        stg.meta = BTreeMap::from([("sla_hours".to_string(), serde_json::json!(6))]);
+        stg.tags = vec!["staging".into()];
+        stg.description = Some("Cleaned orders".into());
+        stg.columns = vec!["order_id".into(), "amount".into()];
+        stg.column_descriptions =
+            BTreeMap::from([("order_id".to_string(), "Primary key".to_string())]);
+        let stg = g.add_node(stg);
+        let mut orders = make_node("model.orders.v2", "orders", NodeType::Model);
+        orders.version = Some("2".into());
+        let orders = g.add_node(orders);
+        let mut test = make_node("test.orders_positive", "orders_positive", NodeType::Test);
+        test.test_kind = Some(TestKind::Generic);
+        let test = g.add_node(test);
+        let mut snap = make_node(
+            "snapshot.orders_snapshot",
+            "orders_snapshot",
+            NodeType::Snapshot,
+        );
+        snap.snapshot = Some(SnapshotConfig {
+            strategy: Some("timestamp".into()),
+            unique_key: vec!["order_id".into()],
+            updated_at: Some("updated_at".into()),
+            check_cols: vec![],
+        });
+        let snap = g.add_node(snap);
+        let dashboard = g.add_node(make_node(
+            "exposure.dashboard",
+            "dashboard",
+            NodeType::Exposure,
+        ));
+        let phantom = g.add_node(make_node("model.missing", "missing", NodeType::Phantom));
+        edge(&mut g, raw, stg, EdgeType::Source);
+        edge(&mut g, stg, orders, EdgeType::Ref);
+        edge(&mut g, orders, test, EdgeType::Ref);
+        edge(&mut g, orders, dashboard, EdgeType::Exposure);
+        edge(&mut g, phantom, stg, EdgeType::Ref);
+        edge(&mut g, stg, snap, EdgeType::Ref);
+        g
+    }
+
+    fn render(graph: &LineageGraph) -> String {
+        let mut buf = Vec::new();
+        render_manifest_to_writer(graph, "shop", &mut buf);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_expand_unique_id() {
+        assert_eq!(
+            expand_unique_id("model.orders", "shop"),
+            "model.shop.orders"
+        );
+        assert_eq!(
+            expand_unique_id("model.orders.v2", "shop"),
+            "model.shop.orders.v2"
+        );
+        assert_eq!(
+            expand_unique_id("source.raw.orders", "shop"),
+            "source.shop.raw.orders"
+        );
+    }
+
+    #[test]
+    fn test_manifest_shape() {
+        let parsed: serde_json::Value = serde_json::from_str(&render(&make_graph())).unwrap();
+        assert_eq!(parsed["metadata"]["project_name"], "shop");
+
+        let stg = &parsed["nodes"]["model.shop.stg_orders"];
+        assert_eq!(stg["resource_type"], "model");
+        assert_eq!(stg["original_file_path"], "models/staging/stg_orders.sql");
+        assert_eq!(stg["config"]["materialized"], "view");
+        assert_eq!(stg["config"]["schema"], "staging");
+        assert_eq!(stg["config"]["meta"]["sla_hours"], 6);
+        // The phantom dependency is dropped
+        assert_eq!(
+            stg["depends_on"]["nodes"],
+            serde_json::json!(["source.shop.raw.orders"])
+        );
+        assert_eq!(stg["columns"]["order_id"]["description"], "Primary key");
+        assert_eq!(stg["columns"]["amount"]["description"], "");
+
+        let source = &parsed["sources"]["source.shop.raw.orders"];
+        assert_eq!(source["source_name"], "raw");
+        assert_eq!(source["name"], "orders");
+        assert_eq!(source["schema"], "raw_data");
+
+        assert_eq!(parsed["nodes"]["model.shop.orders.v2"]["version"], "2");
+        assert_eq!(
+            parsed["exposures"]["exposure.shop.dashboard"]["depends_on"]["nodes"],
+            serde_json::json!(["model.shop.orders.v2"])
+        );
+        assert!(parsed["nodes"].get("model.shop.missing").is_none());
+        assert_eq!(
+            parsed["nodes"]["test.shop.orders_positive"]["test_metadata"],
+            serde_json::json!({})
+        );
+        assert!(stg.get("test_metadata").is_none());
+        assert!(stg["config"].get("strategy").is_none());
+
+        let snapshot = &parsed["nodes"]["snapshot.shop.orders_snapshot"]["config"];
+        assert_eq!(snapshot["strategy"], "timestamp");
+        assert_eq!(snapshot["unique_key"], serde_json::json!(["order_id"]));
+        assert_eq!(snapshot["updated_at"], "updated_at");
+        assert!(snapshot.get("check_cols").is_none());
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let graph = make_graph();
+        let manifest: Manifest = serde_json::from_str(&render(&graph)).unwrap();
+        let round_tripped = build_graph_from_parsed_manifest(&manifest).unwrap();
+
+        type NodeKey = (
+            String,
+            String,
+            &'static str,
+            Option<PathBuf>,
+            Option<String>,
+            Option<String>,
+            Vec<String>,
+            Option<String>,
+            BTreeMap<String, String>,
+            Option<&'static str>,
+            // Schema, plus `meta` and snapshot settings as text, since
+            // `serde_json::Value` isn't `Ord`
+            (Option<String>, String, String),
+        );
+        let nodes = |g: &LineageGraph| -> BTreeSet<NodeKey> {
+            g.node_indices()
+                .map(|i| &g[i])
+                .filter(|n| n.node_type != NodeType::Phantom)
+                .map(|n| {
+                    (
+                        n.unique_id.clone(),
+                        n.label.clone(),
+                        n.node_type.label(),
+                        n.file_path.clone(),
+                        n.description.clone(),
+                        n.materialization.clone(),
+                        n.tags.clone(),
+                        n.version.clone(),
+                        n.column_descriptions.clone(),
+                        n.test_kind.map(|k| k.label()),
+                        (
+                            n.schema.clone(),
+                            serde_json::to_string(&n.meta).unwrap(),
+                            format!("{:?}", n.snapshot),
+                        ),
+                    )
+                })
+                .collect()
+        };
+        let edges = |g: &LineageGraph| -> BTreeSet<(String, String, String)> {
+            g.edge_references()
+                .filter(|e| g[e.source()].node_type != NodeType::Phantom)
+                .map(|e| {
+                    (
+                        g[e.source()].unique_id.clone(),
+                        g[e.target()].unique_id.clone(),
+                        format!("{:?}", e.weight().edge_type),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(nodes(&round_tripped), nodes(&graph));
+        assert_eq!(edges(&round_tripped), edges(&graph));
+    }
+}
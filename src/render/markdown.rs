@@ -0,0 +1,104 @@
+use std::io::Write;
+use std::path::Path;
+
+use crate::graph::filter::ALL_NODE_TYPES;
+
+/// Make text safe for a GitHub-flavored Markdown table cell: pipes are
+/// escaped and line breaks become spaces, so neither can end the row early
+pub fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .split(['\r', '\n'])
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A node's name as a table cell, linked to its file when `link_prefix` is
+/// set, e.g. `https://github.com/org/repo/blob/main/` +
+/// `models/orders.sql`. A `/` is added between the two when missing.
+pub fn node_cell(label: &str, file_path: Option<&Path>, link_prefix: Option<&str>) -> String {
+    let name = format!("`{}`", escape_cell(label));
+    match (file_path, link_prefix) {
+        (Some(path), Some(prefix)) => {
+            let path = path.to_string_lossy().replace('\\', "/");
+            let separator = if prefix.ends_with('/') { "" } else { "/" };
+            format!(
+                "[{}]({}{}{})",
+                name,
+                prefix,
+                separator,
+                path.replace(' ', "%20")
+            )
+        }
+        _ => name,
+    }
+}
+
+/// Node type labels (`model`, `source`, ...) in display order, keeping only
+/// those `present` holds, each with its plural heading (`Models`)
+pub fn type_groups(present: impl Fn(&str) -> bool) -> Vec<(&'static str, String)> {
+    ALL_NODE_TYPES
+        .iter()
+        .map(|nt| nt.label())
+        .filter(|label| present(label))
+        .map(|label| (label, format!("{}s", capitalize(label))))
+        .collect()
+}
+
+/// Start a collapsed section titled `heading (count)`
+pub fn open_details<W: Write>(w: &mut W, heading: &str, count: usize) {
+    writeln!(w, "<details>\n<summary>{} ({})</summary>\n", heading, count).unwrap();
+}
+
+pub fn close_details<W: Write>(w: &mut W) {
+    writeln!(w, "\n</details>\n").unwrap();
+}
+
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell("a | b"), "a \\| b");
+        assert_eq!(escape_cell("one\ntwo\r\nthree"), "one two three");
+    }
+
+    #[test]
+    fn test_node_cell() {
+        let path = Path::new("models/marts/orders.sql");
+        assert_eq!(node_cell("orders", Some(path), None), "`orders`");
+        assert_eq!(
+            node_cell("orders", None, Some("https://x/blob/main")),
+            "`orders`"
+        );
+        assert_eq!(
+            node_cell("orders", Some(path), Some("https://x/blob/main")),
+            "[`orders`](https://x/blob/main/models/marts/orders.sql)"
+        );
+        assert_eq!(
+            node_cell("a|b", Some(Path::new("models/a b.sql")), Some("dbt/")),
+            "[`a\\|b`](dbt/models/a%20b.sql)"
+        );
+    }
+
+    #[test]
+    fn test_type_groups() {
+        let groups = type_groups(|label| label == "exposure" || label == "model");
+        assert_eq!(
+            groups,
+            vec![
+                ("model", "Models".to_string()),
+                ("exposure", "Exposures".to_string())
+            ]
+        );
+    }
+}
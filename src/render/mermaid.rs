@@ -2,14 +2,36 @@ use std::io::Write;
 
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
+use crate::graph::diff::{DiffHighlight, DiffStatus};
+use crate::graph::groups::GroupConfig;
 use crate::graph::types::*;
+use crate::render::diff::{diff_color, DIFF_LEGEND};
+use crate::render::RenderOptions;
 
 /// Render the lineage graph as a Mermaid flowchart to stdout
 pub fn render_mermaid(graph: &LineageGraph) {
     render_mermaid_to_writer(graph, &mut std::io::stdout().lock());
 }
 
+/// Render a Mermaid flowchart with nodes wrapped in one `subgraph` per group
+pub fn render_mermaid_grouped(graph: &LineageGraph, groups: &GroupConfig) {
+    let options = RenderOptions {
+        groups: Some(groups),
+        ..Default::default()
+    };
+    write_mermaid(graph, &options, &mut std::io::stdout().lock());
+}
+
+/// Render a Mermaid flowchart with optional group subgraphs and label details
+pub fn render_mermaid_with_options(graph: &LineageGraph, options: &RenderOptions) {
+    write_mermaid(graph, options, &mut std::io::stdout().lock());
+}
+
 fn render_mermaid_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
+    write_mermaid(graph, &RenderOptions::default(), w);
+}
+
+fn write_mermaid<W: Write>(graph: &LineageGraph, options: &RenderOptions, w: &mut W) {
     writeln!(w, "flowchart LR").unwrap();
 
     if graph.node_count() == 0 {
@@ -17,20 +39,21 @@ fn render_mermaid_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     }
 
     // Render nodes with type-specific shapes
-    for idx in graph.node_indices() {
-        let node = &graph[idx];
-        let id = mermaid_id(&node.unique_id);
-        let label = &node.label;
-        let shape = match node.node_type {
-            NodeType::Model => format!("{}[\"{}\"]\n", id, label),
-            NodeType::Source => format!("{}([\"{}\"]) \n", id, label),
-            NodeType::Seed => format!("{}[/\"{}\"\\]\n", id, label),
-            NodeType::Snapshot => format!("{}{{{{\"{}\"}}}} \n", id, label),
-            NodeType::Test => format!("{}{{\"{}\"}} \n", id, label),
-            NodeType::Exposure => format!("{}>\"{}\"]\n", id, label),
-            NodeType::Phantom => format!("{}(\"{}\")\n", id, label),
-        };
-        write!(w, "    {}", shape).unwrap();
+    match options.groups {
+        Some(groups) => {
+            for (i, (name, members)) in groups.clusters(graph).into_iter().enumerate() {
+                writeln!(w, "    subgraph group_{}[\"{}\"]", i, name).unwrap();
+                for idx in members {
+                    write!(w, "    {}", mermaid_node_shape(&graph[idx], options)).unwrap();
+                }
+                writeln!(w, "    end").unwrap();
+            }
+        }
+        None => {
+            for idx in graph.node_indices() {
+                write!(w, "    {}", mermaid_node_shape(&graph[idx], options)).unwrap();
+            }
+        }
     }
 
     writeln!(w).unwrap();
@@ -52,36 +75,137 @@ fn render_mermaid_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
 
     writeln!(w).unwrap();
 
-    // Style classes for node types
-    writeln!(w, "    classDef model fill:#4A90D9,stroke:#333,color:#fff").unwrap();
-    writeln!(w, "    classDef source fill:#27AE60,stroke:#333,color:#fff").unwrap();
-    writeln!(w, "    classDef seed fill:#F39C12,stroke:#333,color:#fff").unwrap();
-    writeln!(
-        w,
-        "    classDef snapshot fill:#8E44AD,stroke:#333,color:#fff"
-    )
-    .unwrap();
-    writeln!(w, "    classDef test fill:#1ABC9C,stroke:#333,color:#fff").unwrap();
-    writeln!(
-        w,
-        "    classDef exposure fill:#E74C3C,stroke:#333,color:#fff"
-    )
-    .unwrap();
-    writeln!(
-        w,
-        "    classDef phantom fill:#BDC3C7,stroke:#333,color:#000"
-    )
-    .unwrap();
-
-    // Apply classes
+    match options.diff {
+        Some(diff) => write_mermaid_diff_styles(graph, diff, w),
+        None => write_mermaid_type_styles(graph, options, w),
+    }
+}
+
+/// Style classes and link colors by node and edge type
+fn write_mermaid_type_styles<W: Write>(graph: &LineageGraph, options: &RenderOptions, w: &mut W) {
+    let palette = options.palette();
+    for node_type in [
+        NodeType::Model,
+        NodeType::Source,
+        NodeType::Seed,
+        NodeType::Snapshot,
+        NodeType::Test,
+        NodeType::Exposure,
+        NodeType::Phantom,
+    ] {
+        let color = if palette.has_light_fill(node_type) {
+            "#000"
+        } else {
+            "#fff"
+        };
+        writeln!(
+            w,
+            "    classDef {} fill:{},stroke:#333,color:{}",
+            node_type.label(),
+            palette.node_fill(node_type),
+            color
+        )
+        .unwrap();
+    }
+
+    // Color edges by type; links are addressed by their position in the output
+    let link_styles = group_links(graph, |_, _, edge| {
+        palette
+            .edge_color(edge.edge_type)
+            .map(|color| format!("stroke:{}", color))
+    });
+    write_link_styles(link_styles, w);
+
+    for idx in graph.node_indices() {
+        let node = &graph[idx];
+        let id = mermaid_id(&node.unique_id);
+        writeln!(w, "    class {} {}", id, node.node_type.label()).unwrap();
+    }
+}
+
+/// Style classes and link colors by change status; removed nodes and edges
+/// are dashed ghosts
+fn write_mermaid_diff_styles<W: Write>(graph: &LineageGraph, diff: &DiffHighlight, w: &mut W) {
+    for status in DIFF_LEGEND {
+        let (color, dashes) = match status {
+            DiffStatus::Removed => ("#fff", ",stroke-dasharray:5 5"),
+            _ => ("#000", ""),
+        };
+        writeln!(
+            w,
+            "    classDef {} fill:{},stroke:#333,color:{}{}",
+            status.label(),
+            diff_color(status),
+            color,
+            dashes
+        )
+        .unwrap();
+    }
+
+    let link_styles = group_links(graph, |source, target, _| {
+        match diff.edge_status(&source.unique_id, &target.unique_id) {
+            status @ DiffStatus::Added => {
+                Some(format!("stroke:{},stroke-width:2px", diff_color(status)))
+            }
+            status @ DiffStatus::Removed => Some(format!(
+                "stroke:{},stroke-width:2px,stroke-dasharray:5 5",
+                diff_color(status)
+            )),
+            _ => None,
+        }
+    });
+    write_link_styles(link_styles, w);
+
     for idx in graph.node_indices() {
         let node = &graph[idx];
         let id = mermaid_id(&node.unique_id);
-        let class = node.node_type.label();
+        let class = diff.node_status(&node.unique_id).label();
         writeln!(w, "    class {} {}", id, class).unwrap();
     }
 }
 
+/// Link positions grouped by their style, in order of first use
+fn group_links(
+    graph: &LineageGraph,
+    style: impl Fn(&NodeData, &NodeData, &EdgeData) -> Option<String>,
+) -> Vec<(String, Vec<usize>)> {
+    let mut link_styles: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, edge) in graph.edge_references().enumerate() {
+        let (source, target) = (&graph[edge.source()], &graph[edge.target()]);
+        if let Some(style) = style(source, target, edge.weight()) {
+            match link_styles.iter_mut().find(|(s, _)| *s == style) {
+                Some((_, links)) => links.push(i),
+                None => link_styles.push((style, vec![i])),
+            }
+        }
+    }
+    link_styles
+}
+
+fn write_link_styles<W: Write>(link_styles: Vec<(String, Vec<usize>)>, w: &mut W) {
+    for (style, links) in link_styles {
+        let links: Vec<String> = links.iter().map(|i| i.to_string()).collect();
+        writeln!(w, "    linkStyle {} {}", links.join(","), style).unwrap();
+    }
+}
+
+/// Node declaration with a type-specific shape, newline-terminated
+fn mermaid_node_shape(node: &NodeData, options: &RenderOptions) -> String {
+    let id = mermaid_id(&node.unique_id);
+    let label = options
+        .label_detail
+        .apply(node, options.shorten(node.versioned_label()));
+    match node.node_type {
+        NodeType::Model => format!("{}[\"{}\"]\n", id, label),
+        NodeType::Source => format!("{}([\"{}\"]) \n", id, label),
+        NodeType::Seed => format!("{}[/\"{}\"\\]\n", id, label),
+        NodeType::Snapshot => format!("{}{{{{\"{}\"}}}} \n", id, label),
+        NodeType::Test => format!("{}{{\"{}\"}} \n", id, label),
+        NodeType::Exposure => format!("{}>\"{}\"]\n", id, label),
+        NodeType::Phantom => format!("{}(\"{}\")\n", id, label),
+    }
+}
+
 /// Convert a unique_id to a valid Mermaid node ID (replace dots with underscores)
 fn mermaid_id(unique_id: &str) -> String {
     unique_id.replace('.', "_")
@@ -90,17 +214,15 @@ fn mermaid_id(unique_id: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::palette::Palette;
+    use crate::render::LabelDetail;
 
     fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
         NodeData {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -128,6 +250,16 @@ mod tests {
         assert!(output.contains("class model_orders model"));
     }
 
+    #[test]
+    fn test_versioned_model_label() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.dim_customers.v2", "dim_customers", NodeType::Model);
+        node.version = Some("2".into());
+        graph.add_node(node);
+        let output = render_to_string(&graph);
+        assert!(output.contains("[\"dim_customers (v2)\"]"));
+    }
+
     #[test]
     fn test_source_node_shape() {
         let mut graph = LineageGraph::new();
@@ -154,6 +286,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -171,6 +304,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -188,6 +322,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
@@ -211,6 +346,7 @@ mod tests {
             t,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
 
@@ -259,4 +395,164 @@ mod tests {
         // Phantom: ("")
         assert!(output.contains("model_unknown(\"unknown\")"));
     }
+
+    #[test]
+    fn test_grouped_clusters() {
+        let groups =
+            GroupConfig::parse("groups:\n  - name: Finance\n    models: ['fct_*']\n").unwrap();
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node(
+            "model.fct_revenue",
+            "fct_revenue",
+            NodeType::Model,
+        ));
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            groups: Some(&groups),
+            ..Default::default()
+        };
+        write_mermaid(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("subgraph group_0[\"Finance\"]"));
+        assert!(output.contains("subgraph group_1[\"(other)\"]"));
+        assert_eq!(output.matches("    end\n").count(), 2);
+        let finance = output.find("group_0").unwrap();
+        let revenue = output.find("model_fct_revenue[").unwrap();
+        let other = output.find("group_1").unwrap();
+        assert!(finance < revenue && revenue < other);
+    }
+
+    #[test]
+    fn test_ungrouped_has_no_clusters() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.orders", "orders", NodeType::Model));
+        assert!(!render_to_string(&graph).contains("subgraph"));
+    }
+
+    #[test]
+    fn test_label_detail() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.orders", "orders", NodeType::Model);
+        node.columns = vec!["id".into(), "amount".into()];
+        graph.add_node(node);
+        graph.add_node(make_node("model.customers", "customers", NodeType::Model));
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            label_detail: LabelDetail::Columns,
+            ..Default::default()
+        };
+        write_mermaid(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("model_orders[\"orders (2 cols)\"]"));
+        // Nodes without known columns keep their plain label
+        assert!(output.contains("model_customers[\"customers\"]"));
+    }
+
+    #[test]
+    fn test_max_label_len() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.fct_sessions", "fct_sessions", NodeType::Model);
+        node.materialization = Some("table".into());
+        graph.add_node(node);
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            label_detail: LabelDetail::Materialization,
+            max_label_len: Some(6),
+            ..Default::default()
+        };
+        write_mermaid(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        // The detail is kept after the shortened name
+        assert!(output.contains("model_fct_sessions[\"fct_s… (table)\"]"));
+    }
+
+    #[test]
+    fn test_diff_classes() {
+        use crate::graph::diff::{DiffHighlight, DiffStatus};
+
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        let c = graph.add_node(make_node("model.c", "c", NodeType::Model));
+        for target in [b, c] {
+            graph.add_edge(
+                a,
+                target,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        let mut highlight = DiffHighlight::default();
+        highlight.nodes.insert("model.b".into(), DiffStatus::Added);
+        highlight
+            .nodes
+            .insert("model.c".into(), DiffStatus::Removed);
+        highlight
+            .edges
+            .insert(("model.a".into(), "model.b".into()), DiffStatus::Added);
+        highlight
+            .edges
+            .insert(("model.a".into(), "model.c".into()), DiffStatus::Removed);
+        let options = RenderOptions {
+            diff: Some(&highlight),
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        write_mermaid(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("classDef added fill:#2ECC71,stroke:#333,color:#000"));
+        assert!(output
+            .contains("classDef removed fill:#E74C3C,stroke:#333,color:#fff,stroke-dasharray:5 5"));
+        assert!(output.contains("class model_a unchanged"));
+        assert!(output.contains("class model_b added"));
+        assert!(output.contains("class model_c removed"));
+        assert!(output.contains("linkStyle 0 stroke:#2ECC71,stroke-width:2px"));
+        assert!(output.contains("linkStyle 1 stroke:#E74C3C,stroke-width:2px,stroke-dasharray:5 5"));
+        assert!(!output.contains("classDef model"));
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        let c = graph.add_node(make_node("model.c", "c", NodeType::Model));
+        let raw = graph.add_node(make_node("source.raw", "raw", NodeType::Source));
+        for (from, to, edge_type) in [
+            (raw, a, EdgeType::Source),
+            (a, b, EdgeType::Ref),
+            (b, c, EdgeType::Ref),
+        ] {
+            graph.add_edge(
+                from,
+                to,
+                EdgeData {
+                    edge_type,
+                    snippet: None,
+                },
+            );
+        }
+        let palette =
+            Palette::parse("nodes:\n  seed: \"#FFD700\"\nedges:\n  ref: \"#888888\"\n").unwrap();
+
+        let mut buf = Vec::new();
+        let options = RenderOptions {
+            palette: Some(&palette),
+            ..Default::default()
+        };
+        write_mermaid(&graph, &options, &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("classDef seed fill:#FFD700,stroke:#333,color:#000"));
+        assert!(output.contains("classDef model fill:#4A90D9,stroke:#333,color:#fff"));
+        assert!(output.contains("linkStyle 1,2 stroke:#888888"));
+        // Without edge colors there are no link styles
+        assert!(!render_to_string(&graph).contains("linkStyle"));
+    }
 }
@@ -1,9 +1,190 @@
 pub mod ascii;
+pub mod check;
+pub mod csv;
+pub mod cytoscape;
 pub mod diff;
+pub mod docs;
 pub mod dot;
 pub mod html;
 pub mod impact;
 pub mod json;
 pub mod layout;
+pub mod list;
+pub mod manifest;
+pub mod markdown;
 pub mod mermaid;
+pub mod palette;
+pub mod stats;
+pub mod status;
 pub mod svg;
+
+use serde::Deserialize;
+
+use crate::graph::diff::DiffHighlight;
+use crate::graph::groups::GroupConfig;
+use crate::graph::types::NodeData;
+use crate::parser::artifacts::RunStatusMap;
+use crate::parser::column_lineage::InheritedDescription;
+use palette::{Palette, DEFAULT_PALETTE};
+
+/// Extra detail appended to node labels in diagrams and the TUI (`--label-detail`).
+/// Display only: selectors and search still match the plain name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelDetail {
+    /// Just the name
+    #[default]
+    None,
+    /// Number of known columns, e.g. `(12 cols)`
+    Columns,
+    /// Materialization, e.g. `(incremental)`
+    Materialization,
+}
+
+impl LabelDetail {
+    /// Detail for `node`, or `None` when it isn't known (no parsed columns or
+    /// no materialization)
+    pub fn suffix(self, node: &NodeData) -> Option<String> {
+        match self {
+            LabelDetail::None => None,
+            LabelDetail::Columns => match node.columns.len() {
+                0 => None,
+                1 => Some("(1 col)".to_string()),
+                n => Some(format!("({} cols)", n)),
+            },
+            LabelDetail::Materialization => {
+                node.materialization.as_ref().map(|m| format!("({})", m))
+            }
+        }
+    }
+
+    /// `name` followed by the node's detail, if any
+    pub fn apply(self, node: &NodeData, name: String) -> String {
+        match self.suffix(node) {
+            Some(suffix) => format!("{} {}", name, suffix),
+            None => name,
+        }
+    }
+}
+
+/// How SVG and HTML edges are drawn between layers (`--edge-style`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeStyle {
+    /// Straight segments
+    Straight,
+    /// Horizontal and vertical segments, turning in the gaps between layers
+    Ortho,
+    /// Bezier curves
+    #[default]
+    Curved,
+}
+
+/// Optional decorations for the static renderers
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RenderOptions<'a> {
+    /// Cluster nodes by `.dbt-lineage-groups.yml` (DOT, Mermaid)
+    pub groups: Option<&'a GroupConfig>,
+    /// Outline nodes by their last run status and add a status legend (DOT, SVG, HTML)
+    pub run_status: Option<&'a RunStatusMap>,
+    /// Column descriptions inherited through column lineage (JSON)
+    pub inherited_descriptions: Option<&'a [InheritedDescription]>,
+    /// Detail appended to node labels (ASCII, DOT, Mermaid, SVG, HTML)
+    pub label_detail: LabelDetail,
+    /// Node and edge colors (DOT, Mermaid, SVG, HTML); built-in colors when unset
+    pub palette: Option<&'a Palette>,
+    /// Edge routing (SVG, HTML)
+    pub edge_style: EdgeStyle,
+    /// Longest node name, in characters, before it is cut with `…` (ASCII, DOT,
+    /// Mermaid, SVG); tooltips keep the full name
+    pub max_label_len: Option<usize>,
+    /// Wrap layers taller than this many nodes into several columns (ASCII,
+    /// SVG, HTML)
+    pub max_layer_width: Option<usize>,
+    /// Color nodes and edges by change status instead of type, with a changes
+    /// legend (DOT, Mermaid, SVG)
+    pub diff: Option<&'a DiffHighlight>,
+    /// Flat node and edge lists, or a tree nested from the focus model (JSON)
+    pub json_shape: json::JsonShape,
+}
+
+impl RenderOptions<'_> {
+    /// The configured palette, or the built-in one
+    pub fn palette(&self) -> &Palette {
+        self.palette.unwrap_or(&DEFAULT_PALETTE)
+    }
+
+    /// `name` cut to `max_label_len`, if set
+    pub fn shorten(&self, name: String) -> String {
+        shorten_label(name, self.max_label_len)
+    }
+}
+
+/// Cut `name` to at most `max_chars` characters, ending in `…` when anything was
+/// dropped. Counts chars, not bytes, so multibyte names are never split.
+pub fn shorten_label(name: String, max_chars: Option<usize>) -> String {
+    match max_chars {
+        Some(max) if name.chars().count() > max => {
+            let mut kept: String = name.chars().take(max.saturating_sub(1)).collect();
+            kept.push('…');
+            kept
+        }
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::NodeType;
+
+    fn make_node(columns: &[&str], materialization: Option<&str>) -> NodeData {
+        NodeData {
+            unique_id: "model.orders".into(),
+            label: "orders".into(),
+            node_type: NodeType::Model,
+            materialization: materialization.map(String::from),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_shorten_label() {
+        let name = "stg_stripe__payments".to_string();
+        assert_eq!(shorten_label(name.clone(), None), name);
+        assert_eq!(shorten_label(name.clone(), Some(20)), name);
+        assert_eq!(shorten_label(name.clone(), Some(8)), "stg_str…");
+        assert_eq!(shorten_label(name, Some(1)), "…");
+        // Multibyte characters are counted as one
+        assert_eq!(shorten_label("kündigungen".into(), Some(4)), "kün…");
+        assert_eq!(shorten_label("日本語のモデル".into(), Some(3)), "日本…");
+    }
+
+    #[test]
+    fn test_label_detail_suffix() {
+        let node = make_node(&["id", "amount", "status"], Some("incremental"));
+        assert_eq!(LabelDetail::None.suffix(&node), None);
+        assert_eq!(
+            LabelDetail::Columns.suffix(&node).as_deref(),
+            Some("(3 cols)")
+        );
+        assert_eq!(
+            LabelDetail::Materialization.apply(&node, "orders".into()),
+            "orders (incremental)"
+        );
+
+        let single = make_node(&["id"], None);
+        assert_eq!(
+            LabelDetail::Columns.suffix(&single).as_deref(),
+            Some("(1 col)")
+        );
+        // Unknown details are left out rather than shown as zero or empty
+        let bare = make_node(&[], None);
+        assert_eq!(LabelDetail::Columns.suffix(&bare), None);
+        assert_eq!(
+            LabelDetail::Materialization.apply(&bare, "orders".into()),
+            "orders"
+        );
+    }
+}
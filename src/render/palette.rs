@@ -0,0 +1,253 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde::{Deserialize, Deserializer};
+
+use crate::error::DbtLineageError;
+use crate::graph::types::{EdgeType, NodeType};
+
+/// Edge stroke used when the palette doesn't set one (SVG, HTML)
+pub const DEFAULT_EDGE_COLOR: &str = "#555";
+
+/// Node and edge colors shared by the DOT, SVG, Mermaid and HTML renderers
+/// (`--palette`). Types the palette leaves out keep the built-in color.
+///
+/// ```yaml
+/// nodes:
+///   model: "#0B3D91"
+///   source: "#00A36C"
+/// edges:
+///   ref: "#888888"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Palette {
+    pub nodes: NodeColors,
+    pub edges: EdgeColors,
+}
+
+/// Fill color per node type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NodeColors {
+    #[serde(deserialize_with = "color")]
+    pub model: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub source: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub seed: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub snapshot: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub test: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub exposure: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub phantom: Option<String>,
+}
+
+/// Stroke color per edge type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EdgeColors {
+    #[serde(rename = "ref", deserialize_with = "color")]
+    pub ref_: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub source: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub test: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub exposure: Option<String>,
+}
+
+/// The built-in colors, used when no palette is configured
+pub static DEFAULT_PALETTE: Palette = Palette {
+    nodes: NodeColors {
+        model: None,
+        source: None,
+        seed: None,
+        snapshot: None,
+        test: None,
+        exposure: None,
+        phantom: None,
+    },
+    edges: EdgeColors {
+        ref_: None,
+        source: None,
+        test: None,
+        exposure: None,
+    },
+};
+
+impl Palette {
+    /// Parse a palette from YAML
+    pub fn parse(content: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(content)
+    }
+
+    /// Load a palette file; unreadable files, unknown keys and invalid colors
+    /// are errors
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| DbtLineageError::FileReadError {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+        let palette = Self::parse(&content).map_err(|e| DbtLineageError::YamlParseError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Ok(palette)
+    }
+
+    /// Fill color for a node type
+    pub fn node_fill(&self, node_type: NodeType) -> &str {
+        let custom = match node_type {
+            NodeType::Model => &self.nodes.model,
+            NodeType::Source => &self.nodes.source,
+            NodeType::Seed => &self.nodes.seed,
+            NodeType::Snapshot => &self.nodes.snapshot,
+            NodeType::Test => &self.nodes.test,
+            NodeType::Exposure => &self.nodes.exposure,
+            NodeType::Phantom => &self.nodes.phantom,
+        };
+        custom
+            .as_deref()
+            .unwrap_or_else(|| default_node_fill(node_type))
+    }
+
+    /// Whether a node type's fill is light enough to need dark text
+    pub fn has_light_fill(&self, node_type: NodeType) -> bool {
+        is_light(self.node_fill(node_type))
+    }
+
+    /// Configured stroke color for an edge type; `None` keeps each renderer's
+    /// own default
+    pub fn edge_color(&self, edge_type: EdgeType) -> Option<&str> {
+        match edge_type {
+            EdgeType::Ref => &self.edges.ref_,
+            EdgeType::Source => &self.edges.source,
+            EdgeType::Test => &self.edges.test,
+            EdgeType::Exposure => &self.edges.exposure,
+        }
+        .as_deref()
+    }
+}
+
+fn default_node_fill(node_type: NodeType) -> &'static str {
+    match node_type {
+        NodeType::Model => "#4A90D9",
+        NodeType::Source => "#27AE60",
+        NodeType::Seed => "#F39C12",
+        NodeType::Snapshot => "#8E44AD",
+        NodeType::Test => "#1ABC9C",
+        NodeType::Exposure => "#E74C3C",
+        NodeType::Phantom => "#BDC3C7",
+    }
+}
+
+/// `#RGB` or `#RRGGBB` expanded to its red, green and blue components
+fn parse_hex(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        3 => {
+            let mut rgb = [0; 3];
+            for (i, c) in hex.chars().enumerate() {
+                rgb[i] = channel(&c.to_string().repeat(2))?;
+            }
+            Some(rgb)
+        }
+        6 => Some([
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ]),
+        _ => None,
+    }
+}
+
+/// Perceived brightness above which white text becomes hard to read
+fn is_light(color: &str) -> bool {
+    parse_hex(color).is_some_and(|[r, g, b]| {
+        0.299 * f64::from(r) + 0.587 * f64::from(g) + 0.114 * f64::from(b) > 186.0
+    })
+}
+
+/// A hex color. An empty value usually means an unquoted `#...` that YAML
+/// read as a comment, so it is rejected with a hint.
+fn color<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(color) if parse_hex(&color).is_some() => Ok(Some(color)),
+        Some(color) => Err(serde::de::Error::custom(format!(
+            "invalid color '{}' (expected #RGB or #RRGGBB)",
+            color
+        ))),
+        None => Err(serde::de::Error::custom(
+            "missing color (quote hex colors, e.g. \"#4A90D9\", so YAML doesn't read them as comments)",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_palette() {
+        let palette = Palette::default();
+        assert_eq!(palette, DEFAULT_PALETTE);
+        assert_eq!(palette.node_fill(NodeType::Model), "#4A90D9");
+        assert_eq!(palette.node_fill(NodeType::Phantom), "#BDC3C7");
+        assert!(palette.has_light_fill(NodeType::Phantom));
+        assert!(!palette.has_light_fill(NodeType::Seed));
+        assert_eq!(palette.edge_color(EdgeType::Ref), None);
+    }
+
+    #[test]
+    fn test_parse_overrides_some_types() {
+        let palette = Palette::parse(
+            "nodes:\n  model: \"#FFF\"\n  source: '#0b3d91'\nedges:\n  ref: \"#888888\"\n",
+        )
+        .unwrap();
+        assert_eq!(palette.node_fill(NodeType::Model), "#FFF");
+        assert!(palette.has_light_fill(NodeType::Model));
+        assert_eq!(palette.node_fill(NodeType::Source), "#0b3d91");
+        assert_eq!(palette.node_fill(NodeType::Seed), "#F39C12");
+        assert_eq!(palette.edge_color(EdgeType::Ref), Some("#888888"));
+        assert_eq!(palette.edge_color(EdgeType::Test), None);
+    }
+
+    #[test]
+    fn test_invalid_colors_rejected() {
+        let err = Palette::parse("nodes:\n  model: blue\n").unwrap_err();
+        assert!(err.to_string().contains("invalid color 'blue'"));
+        assert!(Palette::parse("nodes:\n  model: \"#12345\"\n").is_err());
+        assert!(Palette::parse("edges:\n  ref: \"#GGGGGG\"\n").is_err());
+
+        // Unquoted, YAML reads the color as a comment
+        let err = Palette::parse("nodes:\n  model: #4A90D9\n").unwrap_err();
+        assert!(err.to_string().contains("quote hex colors"));
+
+        let err = Palette::parse("nodes:\n  modle: \"#4A90D9\"\n").unwrap_err();
+        assert!(err.to_string().contains("unknown field `modle`"));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("palette.yml");
+        std::fs::write(&path, "nodes:\n  seed: \"#123456\"\n").unwrap();
+        assert_eq!(
+            Palette::load(&path).unwrap().node_fill(NodeType::Seed),
+            "#123456"
+        );
+        assert!(Palette::load(&dir.path().join("missing.yml")).is_err());
+    }
+}
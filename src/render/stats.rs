@@ -0,0 +1,242 @@
+use std::io::Write;
+
+use colored::Colorize;
+
+use crate::graph::stats::{GraphStats, PhantomKind};
+use crate::graph::suggest::did_you_mean;
+
+/// Render graph statistics as text to stdout
+pub fn render_stats_text(stats: &GraphStats) {
+    render_stats_text_to_writer(stats, &mut std::io::stdout().lock());
+}
+
+pub fn render_stats_text_to_writer<W: Write>(stats: &GraphStats, w: &mut W) {
+    writeln!(w, "{}", "Lineage Stats".bold()).unwrap();
+    writeln!(w, "{}", "=".repeat(50)).unwrap();
+    writeln!(w, "  Nodes:            {}", stats.total_nodes).unwrap();
+    for (node_type, count) in &stats.nodes_by_type {
+        writeln!(w, "    {:<16}{}", node_type, count).unwrap();
+    }
+    writeln!(w, "  Edges:            {}", stats.total_edges).unwrap();
+    writeln!(w, "  Phantom refs:     {}", stats.phantom_refs).unwrap();
+    if stats.phantom_refs > 0 {
+        writeln!(w, "    {:<16}{}", "unresolved ref", stats.unresolved_refs).unwrap();
+        writeln!(
+            w,
+            "    {:<16}{}",
+            "undeclared src", stats.undeclared_sources
+        )
+        .unwrap();
+    }
+    writeln!(w, "  Untested models:  {}", stats.untested_models).unwrap();
+    writeln!(w, "  Max depth:        {}", stats.max_depth).unwrap();
+    writeln!(w, "  Max sources:      {}", stats.max_upstream_sources).unwrap();
+
+    if !stats.source_integrations.is_empty() {
+        writeln!(w).unwrap();
+        writeln!(w, "{}", "Source integrations:".bold()).unwrap();
+        for model in &stats.source_integrations {
+            writeln!(
+                w,
+                "  {} derives from {} sources",
+                model.label.cyan(),
+                model.upstream_sources
+            )
+            .unwrap();
+        }
+    }
+
+    if !stats.phantoms.is_empty() {
+        writeln!(w).unwrap();
+        writeln!(w, "{}", "Phantoms:".bold()).unwrap();
+        for phantom in &stats.phantoms {
+            let kind = match phantom.kind {
+                PhantomKind::Ref => "ref",
+                PhantomKind::Source => "source",
+            };
+            let count = phantom.referenced_by.len();
+            writeln!(
+                w,
+                "  {} ({}) referenced by {} file{}: {}",
+                phantom.label.yellow(),
+                kind,
+                count,
+                if count == 1 { "" } else { "s" },
+                phantom.referenced_by.join(", ")
+            )
+            .unwrap();
+            if let Some(hint) = did_you_mean(&phantom.suggestions) {
+                writeln!(w, "    {}", hint).unwrap();
+            }
+        }
+    }
+}
+
+/// Render graph statistics as JSON to stdout
+pub fn render_stats_json(stats: &GraphStats) {
+    render_stats_json_to_writer(stats, &mut std::io::stdout().lock());
+}
+
+pub fn render_stats_json_to_writer<W: Write>(stats: &GraphStats, w: &mut W) {
+    serde_json::to_writer_pretty(&mut *w, stats).unwrap();
+    writeln!(w).unwrap();
+}
+
+/// Render graph statistics in the Prometheus text exposition format to stdout
+pub fn render_stats_prometheus(stats: &GraphStats) {
+    render_stats_prometheus_to_writer(stats, &mut std::io::stdout().lock());
+}
+
+pub fn render_stats_prometheus_to_writer<W: Write>(stats: &GraphStats, w: &mut W) {
+    writeln!(
+        w,
+        "# HELP dbt_lineage_nodes_total Number of nodes in the lineage graph by type."
+    )
+    .unwrap();
+    writeln!(w, "# TYPE dbt_lineage_nodes_total gauge").unwrap();
+    for (node_type, count) in &stats.nodes_by_type {
+        writeln!(
+            w,
+            "dbt_lineage_nodes_total{{type=\"{}\"}} {}",
+            node_type, count
+        )
+        .unwrap();
+    }
+
+    let gauges = [
+        (
+            "dbt_lineage_edges_total",
+            "Number of edges in the lineage graph.",
+            stats.total_edges,
+        ),
+        (
+            "dbt_lineage_phantom_refs_total",
+            "Number of unresolved ref() or source() targets.",
+            stats.phantom_refs,
+        ),
+        (
+            "dbt_lineage_unresolved_refs_total",
+            "Number of ref() targets that match no model, seed or snapshot.",
+            stats.unresolved_refs,
+        ),
+        (
+            "dbt_lineage_undeclared_sources_total",
+            "Number of source() targets not declared in any YAML file.",
+            stats.undeclared_sources,
+        ),
+        (
+            "dbt_lineage_untested_models_total",
+            "Number of models without a downstream test.",
+            stats.untested_models,
+        ),
+        (
+            "dbt_lineage_max_depth",
+            "Length of the longest dependency chain in edges.",
+            stats.max_depth,
+        ),
+        (
+            "dbt_lineage_max_upstream_sources",
+            "Most distinct sources any model ultimately derives from.",
+            stats.max_upstream_sources,
+        ),
+    ];
+    for (name, help, value) in gauges {
+        writeln!(w, "# HELP {} {}", name, help).unwrap();
+        writeln!(w, "# TYPE {} gauge", name).unwrap();
+        writeln!(w, "{} {}", name, value).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::stats::{PhantomNode, SourceIntegration};
+    use std::collections::BTreeMap;
+
+    fn make_stats() -> GraphStats {
+        let mut nodes_by_type = BTreeMap::new();
+        nodes_by_type.insert("model".to_string(), 123);
+        nodes_by_type.insert("source".to_string(), 4);
+        GraphStats {
+            total_nodes: 127,
+            total_edges: 200,
+            nodes_by_type,
+            phantom_refs: 2,
+            unresolved_refs: 1,
+            undeclared_sources: 1,
+            phantoms: vec![
+                PhantomNode {
+                    unique_id: "model.stg_ordres".into(),
+                    label: "stg_ordres".into(),
+                    kind: PhantomKind::Ref,
+                    referenced_by: vec!["models/a.sql".into(), "models/b.sql".into()],
+                    suggestions: vec!["stg_orders".into()],
+                },
+                PhantomNode {
+                    unique_id: "source.crm.accounts".into(),
+                    label: "crm.accounts".into(),
+                    kind: PhantomKind::Source,
+                    referenced_by: vec!["models/c.sql".into()],
+                    suggestions: vec![],
+                },
+            ],
+            untested_models: 17,
+            max_depth: 6,
+            max_upstream_sources: 3,
+            source_integrations: vec![SourceIntegration {
+                unique_id: "model.orders".into(),
+                label: "orders".into(),
+                upstream_sources: 3,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_stats_text() {
+        let mut buf = Vec::new();
+        render_stats_text_to_writer(&make_stats(), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("Nodes:            127"));
+        assert!(output.contains("Untested models:  17"));
+        assert!(output.contains("unresolved ref  1"));
+        assert!(output.contains("undeclared src  1"));
+        assert!(
+            output.contains("stg_ordres (ref) referenced by 2 files: models/a.sql, models/b.sql")
+        );
+        assert!(output.contains("crm.accounts (source) referenced by 1 file: models/c.sql"));
+        assert!(output.contains("models/b.sql\n    did you mean: stg_orders?\n"));
+        assert!(output.contains("Max sources:      3"));
+        assert!(output.contains("Source integrations:\n  orders derives from 3 sources\n"));
+    }
+
+    #[test]
+    fn test_render_stats_json() {
+        let mut buf = Vec::new();
+        render_stats_json_to_writer(&make_stats(), &mut buf);
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["nodes_by_type"]["model"], 123);
+        assert_eq!(parsed["max_depth"], 6);
+        assert_eq!(parsed["phantoms"][0]["kind"], "ref");
+        assert_eq!(parsed["phantoms"][1]["referenced_by"][0], "models/c.sql");
+    }
+
+    #[test]
+    fn test_render_stats_prometheus() {
+        let mut buf = Vec::new();
+        render_stats_prometheus_to_writer(&make_stats(), &mut buf);
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("dbt_lineage_nodes_total{type=\"model\"} 123"));
+        assert!(output.contains("dbt_lineage_nodes_total{type=\"source\"} 4"));
+        assert!(output.contains("dbt_lineage_phantom_refs_total 2"));
+        assert!(output.contains("dbt_lineage_unresolved_refs_total 1"));
+        assert!(output.contains("dbt_lineage_undeclared_sources_total 1"));
+        assert!(output.contains("dbt_lineage_untested_models_total 17"));
+        assert!(output.contains("dbt_lineage_max_depth 6"));
+        assert!(output.contains("dbt_lineage_max_upstream_sources 3"));
+        assert!(output.contains("# TYPE dbt_lineage_max_depth gauge"));
+        // Every sample line is `name{labels} value` or `name value`
+        for line in output.lines().filter(|l| !l.starts_with('#')) {
+            assert_eq!(line.split(' ').count(), 2, "bad sample line: {}", line);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::parser::artifacts::{RunStatus, RunStatusMap};
+
+const SUCCESS: &str = "#2ECC71";
+const ERROR: &str = "#E74C3C";
+const OUTDATED: &str = "#F1C40F";
+const NOT_RUN: &str = "#7F8C8D";
+
+/// Run status outline colors used by the static renderers, in legend order
+pub const STATUS_LEGEND: [(&str, &str); 4] = [
+    ("success", SUCCESS),
+    ("error", ERROR),
+    ("outdated", OUTDATED),
+    ("skipped / never run", NOT_RUN),
+];
+
+static NEVER_RUN: RunStatus = RunStatus::NeverRun;
+
+/// Status of a node, treating nodes missing from the map as never run
+pub fn node_status<'a>(map: &'a RunStatusMap, unique_id: &str) -> &'a RunStatus {
+    map.get(unique_id).unwrap_or(&NEVER_RUN)
+}
+
+/// Short machine-friendly name for a status
+pub fn status_label(status: &RunStatus) -> &'static str {
+    match status {
+        RunStatus::NeverRun => "never_run",
+        RunStatus::Success { .. } => "success",
+        RunStatus::Error { .. } => "error",
+        RunStatus::Skipped { .. } => "skipped",
+        RunStatus::Outdated { .. } => "outdated",
+    }
+}
+
+/// Outline color for a status (mirrors the TUI status colors)
+pub fn status_color(status: &RunStatus) -> &'static str {
+    match status {
+        RunStatus::Success { .. } => SUCCESS,
+        RunStatus::Error { .. } => ERROR,
+        RunStatus::Outdated { .. } => OUTDATED,
+        RunStatus::NeverRun | RunStatus::Skipped { .. } => NOT_RUN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_status_defaults_to_never_run() {
+        let mut map = RunStatusMap::new();
+        map.insert(
+            "model.a".into(),
+            RunStatus::Error {
+                completed_at: None,
+                message: "boom".into(),
+            },
+        );
+        assert_eq!(status_label(node_status(&map, "model.a")), "error");
+        assert_eq!(status_label(node_status(&map, "model.b")), "never_run");
+    }
+
+    #[test]
+    fn test_status_colors_match_legend() {
+        let error = RunStatus::Error {
+            completed_at: None,
+            message: String::new(),
+        };
+        assert_eq!(status_color(&error), STATUS_LEGEND[1].1);
+        assert_eq!(status_color(&RunStatus::NeverRun), STATUS_LEGEND[3].1);
+        assert_eq!(
+            status_color(&RunStatus::Skipped { completed_at: None }),
+            STATUS_LEGEND[3].1
+        );
+    }
+}
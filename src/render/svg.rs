@@ -2,31 +2,32 @@ use std::io::Write;
 
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 
+use crate::graph::diff::DiffStatus;
 use crate::graph::types::*;
-use crate::render::layout::{sugiyama_layout, LayoutResult};
+use crate::render::diff::{diff_color, diff_font_color, DIFF_LEGEND};
+use crate::render::layout::{sugiyama_layout_wrapped, LayoutResult};
+use crate::render::palette::{Palette, DEFAULT_EDGE_COLOR};
+use crate::render::status::{node_status, status_color, status_label, STATUS_LEGEND};
+use crate::render::{EdgeStyle, RenderOptions};
 
 const NODE_WIDTH: f64 = 160.0;
 const NODE_HEIGHT: f64 = 40.0;
 const LAYER_SPACING: f64 = 220.0;
 const NODE_SPACING: f64 = 60.0;
 const PADDING: f64 = 40.0;
+/// Height of one legend row at the bottom of the drawing
+const LEGEND_ROW_HEIGHT: f64 = 20.0;
+/// Characters of a 12px label that fit on one line inside a node box
+const MAX_LABEL_CHARS: usize = 21;
+/// Label lines drawn inside a node box; anything longer ends in an ellipsis
+const MAX_LABEL_LINES: usize = 2;
 
-fn node_fill(node_type: NodeType) -> &'static str {
-    match node_type {
-        NodeType::Model => "#4A90D9",
-        NodeType::Source => "#27AE60",
-        NodeType::Seed => "#F39C12",
-        NodeType::Snapshot => "#8E44AD",
-        NodeType::Test => "#1ABC9C",
-        NodeType::Exposure => "#E74C3C",
-        NodeType::Phantom => "#BDC3C7",
-    }
-}
-
-fn node_font_color(node_type: NodeType) -> &'static str {
-    match node_type {
-        NodeType::Phantom => "#000000",
-        _ => "#ffffff",
+/// Label color for a node type: dark on light fills, else white
+fn node_font_color(palette: &Palette, node_type: NodeType) -> &'static str {
+    if palette.has_light_fill(node_type) {
+        "#000000"
+    } else {
+        "#ffffff"
     }
 }
 
@@ -38,15 +39,100 @@ fn xml_escape(s: &str) -> String {
         .replace('\'', "&apos;")
 }
 
-fn edge_style(edge_type: EdgeType) -> &'static str {
-    match edge_type {
-        EdgeType::Ref => "stroke:#555;stroke-width:1.5",
-        EdgeType::Source => "stroke:#555;stroke-width:1.5;stroke-dasharray:5,3",
-        EdgeType::Test => "stroke:#555;stroke-width:1;stroke-dasharray:2,2",
-        EdgeType::Exposure => "stroke:#555;stroke-width:2.5",
+/// Stable, URL-safe element id for a node, used as a `#node-...` deep-link anchor.
+/// Characters outside `[A-Za-z0-9_.-]` are replaced with `_`.
+pub fn node_anchor_id(unique_id: &str) -> String {
+    let sanitized: String = unique_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("node-{}", sanitized)
+}
+
+/// Split a label into lines that fit the node box, breaking after `_`, `.`, `-`
+/// or a space where possible. The last line is cut with `…` if the label does
+/// not fit in `MAX_LABEL_LINES` lines.
+fn wrap_label(label: &str) -> Vec<String> {
+    wrap_label_to(label, MAX_LABEL_LINES)
+}
+
+/// [`wrap_label`] into at most `max_lines` lines
+fn wrap_label_to(label: &str, max_lines: usize) -> Vec<String> {
+    let mut rest: Vec<char> = label.chars().collect();
+    let mut lines = Vec::new();
+    while rest.len() > MAX_LABEL_CHARS {
+        if lines.len() + 1 == max_lines {
+            let mut line: String = rest[..MAX_LABEL_CHARS - 1].iter().collect();
+            line.push('…');
+            lines.push(line);
+            return lines;
+        }
+        let split = (1..=MAX_LABEL_CHARS)
+            .rev()
+            .find(|&i| matches!(rest[i - 1], '_' | '.' | '-' | ' '))
+            .unwrap_or(MAX_LABEL_CHARS);
+        lines.push(
+            rest.drain(..split)
+                .collect::<String>()
+                .trim_end()
+                .to_string(),
+        );
+    }
+    lines.push(rest.into_iter().collect());
+    lines
+}
+
+/// Hover text for a node: full name, unique_id, materialization and description
+fn node_tooltip(node: &NodeData) -> String {
+    let mut lines = vec![node.display_name(), node.unique_id.clone()];
+    if let Some(materialization) = &node.materialization {
+        lines.push(format!("materialized: {}", materialization));
     }
+    if let Some(description) = node.description.as_deref().map(str::trim) {
+        if !description.is_empty() {
+            lines.push(description.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+fn edge_style(palette: &Palette, edge_type: EdgeType) -> String {
+    let stroke = match edge_type {
+        EdgeType::Ref => "stroke-width:1.5",
+        EdgeType::Source => "stroke-width:1.5;stroke-dasharray:5,3",
+        EdgeType::Test => "stroke-width:1;stroke-dasharray:2,2",
+        EdgeType::Exposure => "stroke-width:2.5",
+    };
+    format!("stroke:{};{}", edge_color(palette, edge_type), stroke)
+}
+
+fn edge_color(palette: &Palette, edge_type: EdgeType) -> &str {
+    palette.edge_color(edge_type).unwrap_or(DEFAULT_EDGE_COLOR)
 }
 
+/// Arrowhead marker matching an edge color; the default color keeps the plain
+/// `arrowhead` id
+fn marker_id(color: &str) -> String {
+    if color == DEFAULT_EDGE_COLOR {
+        "arrowhead".to_string()
+    } else {
+        format!("arrowhead-{}", color.trim_start_matches('#'))
+    }
+}
+
+const EDGE_TYPES: [EdgeType; 4] = [
+    EdgeType::Ref,
+    EdgeType::Source,
+    EdgeType::Test,
+    EdgeType::Exposure,
+];
+
 fn node_center(layer: usize, pos: usize) -> (f64, f64) {
     let x = PADDING + layer as f64 * LAYER_SPACING + NODE_WIDTH / 2.0;
     let y = PADDING + pos as f64 * (NODE_HEIGHT + NODE_SPACING) + NODE_HEIGHT / 2.0;
@@ -58,15 +144,28 @@ pub fn render_svg(graph: &LineageGraph) {
     render_svg_to_writer(graph, &mut std::io::stdout().lock());
 }
 
+/// Render SVG with run status outlines to stdout
+pub fn render_svg_with_options(graph: &LineageGraph, options: &RenderOptions) {
+    render_svg_with_options_to_writer(graph, options, &mut std::io::stdout().lock());
+}
+
 /// Render SVG to a string (used by HTML renderer)
-pub fn render_svg_to_string(graph: &LineageGraph) -> String {
+pub fn render_svg_to_string(graph: &LineageGraph, options: &RenderOptions) -> String {
     let mut buf = Vec::new();
-    render_svg_to_writer(graph, &mut buf);
+    render_svg_with_options_to_writer(graph, options, &mut buf);
     String::from_utf8(buf).unwrap()
 }
 
 pub fn render_svg_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
-    let layout = sugiyama_layout(graph);
+    render_svg_with_options_to_writer(graph, &RenderOptions::default(), w);
+}
+
+pub fn render_svg_with_options_to_writer<W: Write>(
+    graph: &LineageGraph,
+    options: &RenderOptions,
+    w: &mut W,
+) {
+    let layout = sugiyama_layout_wrapped(graph, options.max_layer_width);
 
     let total_width = if layout.num_layers == 0 {
         200.0
@@ -78,6 +177,12 @@ pub fn render_svg_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     } else {
         PADDING * 2.0 + layout.max_layer_width as f64 * (NODE_HEIGHT + NODE_SPACING)
     };
+    // The status legend gets its own row below the node type legend
+    let total_height = if options.run_status.is_some() {
+        total_height + LEGEND_ROW_HEIGHT
+    } else {
+        total_height
+    };
 
     writeln!(
         w,
@@ -86,19 +191,40 @@ pub fn render_svg_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     )
     .unwrap();
 
-    // Defs for arrowhead marker
+    // Defs for arrowhead markers, one per edge color
+    let palette = options.palette();
+    let mut colors = vec![DEFAULT_EDGE_COLOR];
+    let mut edge_colors: Vec<&str> = EDGE_TYPES
+        .iter()
+        .map(|&edge_type| edge_color(palette, edge_type))
+        .collect();
+    if options.diff.is_some() {
+        edge_colors.extend([
+            diff_color(DiffStatus::Added),
+            diff_color(DiffStatus::Removed),
+        ]);
+    }
+    for color in edge_colors {
+        if !colors.contains(&color) {
+            colors.push(color);
+        }
+    }
     writeln!(w, "  <defs>").unwrap();
-    writeln!(
-        w,
-        r#"    <marker id="arrowhead" markerWidth="10" markerHeight="7" refX="10" refY="3.5" orient="auto">"#
-    )
-    .unwrap();
-    writeln!(
-        w,
-        r##"      <polygon points="0 0, 10 3.5, 0 7" fill="#555" />"##
-    )
-    .unwrap();
-    writeln!(w, "    </marker>").unwrap();
+    for color in colors {
+        writeln!(
+            w,
+            r#"    <marker id="{}" markerWidth="10" markerHeight="7" refX="10" refY="3.5" orient="auto">"#,
+            marker_id(color)
+        )
+        .unwrap();
+        writeln!(
+            w,
+            r#"      <polygon points="0 0, 10 3.5, 0 7" fill="{}" />"#,
+            color
+        )
+        .unwrap();
+        writeln!(w, "    </marker>").unwrap();
+    }
     writeln!(w, "  </defs>").unwrap();
 
     // Background
@@ -109,43 +235,130 @@ pub fn render_svg_to_writer<W: Write>(graph: &LineageGraph, w: &mut W) {
     .unwrap();
 
     // Render edges first (behind nodes)
-    render_svg_edges(w, graph, &layout);
+    render_svg_edges(w, graph, &layout, options);
 
     // Render nodes
-    render_svg_nodes(w, graph, &layout);
+    render_svg_nodes(w, graph, &layout, options);
 
     // Legend
-    render_svg_legend(w, total_height);
+    match (options.diff, options.run_status) {
+        (Some(_), _) => render_svg_diff_legend(w, total_height),
+        (None, Some(_)) => {
+            render_svg_legend(w, total_height - LEGEND_ROW_HEIGHT, palette);
+            render_svg_status_legend(w, total_height);
+        }
+        (None, None) => render_svg_legend(w, total_height, palette),
+    }
 
     writeln!(w, "</svg>").unwrap();
 }
 
-fn render_svg_edges<W: Write>(w: &mut W, graph: &LineageGraph, layout: &LayoutResult) {
+/// Y of the `k`-th horizontal gap between node rows (0 is above the first row)
+fn gap_row_y(k: usize) -> f64 {
+    PADDING - NODE_SPACING / 2.0 + k as f64 * (NODE_HEIGHT + NODE_SPACING)
+}
+
+/// Points an edge passes through: the right side of the source, then, for
+/// every layer it skips, across that layer's column along the gap between
+/// node rows closest to a straight line, and the left side of the target
+fn edge_waypoints(
+    (sl, sp): (usize, usize),
+    (tl, tp): (usize, usize),
+    max_layer_width: usize,
+) -> Vec<(f64, f64)> {
+    let (sx, sy) = node_center(sl, sp);
+    let (tx, ty) = node_center(tl, tp);
+    let start = (sx + NODE_WIDTH / 2.0, sy);
+    let end = (tx - NODE_WIDTH / 2.0, ty);
+
+    let mut points = vec![start];
+    for layer in (sl + 1)..tl {
+        let left = node_center(layer, 0).0 - NODE_WIDTH / 2.0;
+        let progress = (layer - sl) as f64 / (tl - sl) as f64;
+        let ideal_y = sy + (ty - sy) * progress;
+        let k = ((ideal_y - gap_row_y(0)) / (NODE_HEIGHT + NODE_SPACING)).round();
+        let y = gap_row_y((k.max(0.0) as usize).min(max_layer_width));
+        points.push((left, y));
+        points.push((left + NODE_WIDTH, y));
+    }
+    points.push(end);
+    points
+}
+
+/// SVG path data through `points` in the given style. Segments that stay at
+/// one height are straight in every style.
+fn edge_path_data(points: &[(f64, f64)], style: EdgeStyle) -> String {
+    let Some(&(x, y)) = points.first() else {
+        return String::new();
+    };
+    let mut d = format!("M{},{}", x, y);
+    for pair in points.windows(2) {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        if y1 == y2 || style == EdgeStyle::Straight {
+            d.push_str(&format!(" L{},{}", x2, y2));
+            continue;
+        }
+        match style {
+            EdgeStyle::Ortho => {
+                let mid = (x1 + x2) / 2.0;
+                d.push_str(&format!(" L{},{} L{},{} L{},{}", mid, y1, mid, y2, x2, y2));
+            }
+            _ => {
+                let cx1 = x1 + (x2 - x1) * 0.4;
+                let cx2 = x1 + (x2 - x1) * 0.6;
+                d.push_str(&format!(" C{},{} {},{} {},{}", cx1, y1, cx2, y2, x2, y2));
+            }
+        }
+    }
+    d
+}
+
+fn render_svg_edges<W: Write>(
+    w: &mut W,
+    graph: &LineageGraph,
+    layout: &LayoutResult,
+    options: &RenderOptions,
+) {
+    let palette = options.palette();
     for edge in graph.edge_references() {
         let source_pos = layout.positions.get(&edge.source());
         let target_pos = layout.positions.get(&edge.target());
 
-        if let (Some(&(sl, sp)), Some(&(tl, tp))) = (source_pos, target_pos) {
-            let (sx, sy) = node_center(sl, sp);
-            let (tx, ty) = node_center(tl, tp);
-
-            // Start from right edge of source, end at left edge of target
-            let x1 = sx + NODE_WIDTH / 2.0;
-            let y1 = sy;
-            let x2 = tx - NODE_WIDTH / 2.0;
-            let y2 = ty;
-
-            let cx1 = x1 + (x2 - x1) * 0.4;
-            let cx2 = x1 + (x2 - x1) * 0.6;
+        if let (Some(&source), Some(&target)) = (source_pos, target_pos) {
+            let points = edge_waypoints(source, target, layout.max_layer_width);
+            let d = edge_path_data(&points, options.edge_style);
 
             let source_node = &graph[edge.source()];
             let target_node = &graph[edge.target()];
-            let style = edge_style(edge.weight().edge_type);
+            let edge_type = edge.weight().edge_type;
+            let change = options
+                .diff
+                .map(|diff| diff.edge_status(&source_node.unique_id, &target_node.unique_id));
+            let (style, marker) = match change {
+                // Changed edges take the diff color; removed ones are dashed ghosts
+                Some(status @ DiffStatus::Added) => (
+                    format!("stroke:{};stroke-width:2.5", diff_color(status)),
+                    marker_id(diff_color(status)),
+                ),
+                Some(status @ DiffStatus::Removed) => (
+                    format!(
+                        "stroke:{};stroke-width:2.5;stroke-dasharray:6,4",
+                        diff_color(status)
+                    ),
+                    marker_id(diff_color(status)),
+                ),
+                _ => (
+                    edge_style(palette, edge_type),
+                    marker_id(edge_color(palette, edge_type)),
+                ),
+            };
 
             writeln!(
                 w,
-                r#"  <path d="M{},{} C{},{} {},{} {},{}" fill="none" style="{}" marker-end="url(#arrowhead)" data-source="{}" data-target="{}" />"#,
-                x1, y1, cx1, y1, cx2, y2, x2, y2, style,
+                r#"  <path d="{}" fill="none" style="{}" marker-end="url(#{})" data-source="{}" data-target="{}" />"#,
+                d,
+                style,
+                marker,
                 xml_escape(&source_node.unique_id),
                 xml_escape(&target_node.unique_id)
             )
@@ -154,7 +367,13 @@ fn render_svg_edges<W: Write>(w: &mut W, graph: &LineageGraph, layout: &LayoutRe
     }
 }
 
-fn render_svg_nodes<W: Write>(w: &mut W, graph: &LineageGraph, layout: &LayoutResult) {
+fn render_svg_nodes<W: Write>(
+    w: &mut W,
+    graph: &LineageGraph,
+    layout: &LayoutResult,
+    options: &RenderOptions,
+) {
+    let run_status = options.run_status;
     for idx in graph.node_indices() {
         let Some(&(layer, pos)) = layout.positions.get(&idx) else {
             continue;
@@ -164,46 +383,103 @@ fn render_svg_nodes<W: Write>(w: &mut W, graph: &LineageGraph, layout: &LayoutRe
         let x = cx - NODE_WIDTH / 2.0;
         let y = cy - NODE_HEIGHT / 2.0;
 
-        let fill = node_fill(node.node_type);
-        let font_color = node_font_color(node.node_type);
-        let label = xml_escape(&node.display_name());
+        let change = options.diff.map(|diff| diff.node_status(&node.unique_id));
+        let (fill, font_color) = match change {
+            Some(status) => (diff_color(status), diff_font_color(status)),
+            None => (
+                options.palette().node_fill(node.node_type),
+                node_font_color(options.palette(), node.node_type),
+            ),
+        };
+        // A label detail gets the last line to itself
+        let name = options.shorten(node.display_name());
+        let lines = match options.label_detail.suffix(node) {
+            Some(suffix) => {
+                let mut lines = wrap_label_to(&name, MAX_LABEL_LINES - 1);
+                lines.push(suffix);
+                lines
+            }
+            None => wrap_label(&name),
+        };
+
+        let status = run_status.map(|map| node_status(map, &node.unique_id));
+        let status_attr = status
+            .map(|s| format!(r#" data-status="{}""#, status_label(s)))
+            .unwrap_or_default();
+        let stroke = status
+            .map(|s| format!(r#" stroke="{}" stroke-width="3""#, status_color(s)))
+            .unwrap_or_default();
+        let change_attr = change
+            .map(|c| format!(r#" data-change="{}""#, c.label()))
+            .unwrap_or_default();
+        // Removed nodes are drawn as faded, dashed ghosts
+        let ghost = if change == Some(DiffStatus::Removed) {
+            r##" fill-opacity="0.45" stroke="#E74C3C" stroke-width="2" stroke-dasharray="6,4""##
+        } else {
+            ""
+        };
 
         writeln!(
             w,
-            r#"  <g data-id="{}" class="node">"#,
-            xml_escape(&node.unique_id)
+            r#"  <g id="{}" data-id="{}" class="node"{}{}>"#,
+            node_anchor_id(&node.unique_id),
+            xml_escape(&node.unique_id),
+            status_attr,
+            change_attr
         )
         .unwrap();
+        writeln!(w, "    <title>{}</title>", xml_escape(&node_tooltip(node))).unwrap();
         writeln!(
             w,
-            r#"    <rect x="{}" y="{}" width="{}" height="{}" rx="8" fill="{}" />"#,
-            x, y, NODE_WIDTH, NODE_HEIGHT, fill
+            r#"    <rect x="{}" y="{}" width="{}" height="{}" rx="8" fill="{}"{}{} />"#,
+            x, y, NODE_WIDTH, NODE_HEIGHT, fill, stroke, ghost
         )
         .unwrap();
+        let text = match lines.as_slice() {
+            [line] => xml_escape(line),
+            _ => {
+                // Center the block of lines vertically on the node
+                let first_dy = -0.6 * (lines.len() - 1) as f64;
+                lines
+                    .iter()
+                    .enumerate()
+                    .map(|(i, line)| {
+                        let dy = if i == 0 { first_dy } else { 1.2 };
+                        format!(
+                            r#"<tspan x="{}" dy="{}em">{}</tspan>"#,
+                            cx,
+                            dy,
+                            xml_escape(line)
+                        )
+                    })
+                    .collect()
+            }
+        };
         writeln!(
             w,
             r#"    <text x="{}" y="{}" text-anchor="middle" dominant-baseline="central" fill="{}" font-family="Helvetica,Arial,sans-serif" font-size="12">{}</text>"#,
-            cx, cy, font_color, label
+            cx, cy, font_color, text
         )
         .unwrap();
         writeln!(w, "  </g>").unwrap();
     }
 }
 
-fn render_svg_legend<W: Write>(w: &mut W, total_height: f64) {
+fn render_svg_legend<W: Write>(w: &mut W, total_height: f64, palette: &Palette) {
     let legend_y = total_height - 30.0;
-    let types: &[(&str, &str)] = &[
-        ("model", "#4A90D9"),
-        ("source", "#27AE60"),
-        ("seed", "#F39C12"),
-        ("snapshot", "#8E44AD"),
-        ("test", "#1ABC9C"),
-        ("exposure", "#E74C3C"),
-        ("phantom", "#BDC3C7"),
+    let types = [
+        NodeType::Model,
+        NodeType::Source,
+        NodeType::Seed,
+        NodeType::Snapshot,
+        NodeType::Test,
+        NodeType::Exposure,
+        NodeType::Phantom,
     ];
 
     let mut x = PADDING;
-    for (label, color) in types {
+    for node_type in types {
+        let (label, color) = (node_type.label(), palette.node_fill(node_type));
         writeln!(
             w,
             r#"  <rect x="{}" y="{}" width="12" height="12" rx="2" fill="{}" />"#,
@@ -222,20 +498,67 @@ fn render_svg_legend<W: Write>(w: &mut W, total_height: f64) {
     }
 }
 
+/// Row of outlined swatches explaining the run status colors
+fn render_svg_status_legend<W: Write>(w: &mut W, total_height: f64) {
+    let legend_y = total_height - 30.0;
+    let mut x = PADDING;
+    for (label, color) in STATUS_LEGEND {
+        writeln!(
+            w,
+            r##"  <rect x="{}" y="{}" width="12" height="12" rx="2" fill="none" stroke="{}" stroke-width="3" />"##,
+            x, legend_y, color
+        )
+        .unwrap();
+        writeln!(
+            w,
+            r##"  <text x="{}" y="{}" fill="#ccc" font-family="Helvetica,Arial,sans-serif" font-size="10">{}</text>"##,
+            x + 16.0,
+            legend_y + 10.0,
+            label
+        )
+        .unwrap();
+        x += if label.len() > 10 { 140.0 } else { 80.0 };
+    }
+}
+
+/// Row of swatches explaining the change status fills of a drawn diff
+fn render_svg_diff_legend<W: Write>(w: &mut W, total_height: f64) {
+    let legend_y = total_height - 30.0;
+    let mut x = PADDING;
+    for status in DIFF_LEGEND {
+        writeln!(
+            w,
+            r#"  <rect x="{}" y="{}" width="12" height="12" rx="2" fill="{}" />"#,
+            x,
+            legend_y,
+            diff_color(status)
+        )
+        .unwrap();
+        writeln!(
+            w,
+            r##"  <text x="{}" y="{}" fill="#ccc" font-family="Helvetica,Arial,sans-serif" font-size="10">{}</text>"##,
+            x + 16.0,
+            legend_y + 10.0,
+            status.label()
+        )
+        .unwrap();
+        x += 80.0;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::artifacts::RunStatusMap;
+    use crate::render::palette::DEFAULT_PALETTE;
+    use crate::render::LabelDetail;
 
     fn make_node(unique_id: &str, label: &str, node_type: NodeType) -> NodeData {
         NodeData {
             unique_id: unique_id.into(),
             label: label.into(),
             node_type,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         }
     }
 
@@ -259,6 +582,7 @@ mod tests {
         graph.add_node(make_node("model.orders", "orders", NodeType::Model));
         let output = render_to_string(&graph);
         assert!(output.contains("data-id=\"model.orders\""));
+        assert!(output.contains("id=\"node-model.orders\""));
         assert!(output.contains(">orders</text>"));
         assert!(output.contains("#4A90D9"));
     }
@@ -277,6 +601,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -287,6 +612,95 @@ mod tests {
         assert!(output.contains("data-target=\"model.stg_orders\""));
     }
 
+    #[test]
+    fn test_edge_path_styles() {
+        // Row 0 of layer 0 to row 1 of layer 1
+        let points = edge_waypoints((0, 0), (1, 1), 2);
+        assert_eq!(points, vec![(200.0, 60.0), (260.0, 160.0)]);
+        assert_eq!(
+            edge_path_data(&points, EdgeStyle::Curved),
+            "M200,60 C224,60 236,160 260,160"
+        );
+        assert_eq!(
+            edge_path_data(&points, EdgeStyle::Straight),
+            "M200,60 L260,160"
+        );
+        assert_eq!(
+            edge_path_data(&points, EdgeStyle::Ortho),
+            "M200,60 L230,60 L230,160 L260,160"
+        );
+    }
+
+    #[test]
+    fn test_long_edges_cross_layers_between_rows() {
+        // Layer 0 row 0 to layer 3 row 2, passing the columns of layers 1 and 2
+        let points = edge_waypoints((0, 0), (3, 2), 3);
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0], (200.0, 60.0));
+        assert_eq!(points[5], (700.0, 260.0));
+        for pair in points[1..5].chunks(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            // Straight across a node column, at the height of a gap between rows
+            assert_eq!(y1, y2);
+            assert_eq!(x2 - x1, NODE_WIDTH);
+            assert!((0..=3).any(|k| gap_row_y(k) == y1));
+            for row in 0..3 {
+                let (_, center) = node_center(1, row);
+                assert!((y1 - center).abs() > NODE_HEIGHT / 2.0);
+            }
+        }
+        // Descending towards the target
+        assert!(points[1].1 <= points[3].1);
+
+        let d = edge_path_data(&points, EdgeStyle::Ortho);
+        assert!(d.starts_with("M200,60 L230,60 L230,"));
+        assert!(d.ends_with(" L700,260"));
+    }
+
+    #[test]
+    fn test_edge_style_option() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        for id in ["model.b", "model.c"] {
+            let child = graph.add_node(make_node(id, &id[6..], NodeType::Model));
+            graph.add_edge(
+                a,
+                child,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        // Which child lands in which row is up to the layout, so compare both paths
+        let paths = |options: &RenderOptions| -> Vec<String> {
+            let svg = render_svg_to_string(&graph, options);
+            let mut paths: Vec<String> = svg
+                .lines()
+                .filter(|l| l.contains("<path "))
+                .map(|l| l.split('"').nth(1).unwrap().to_string())
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        // The edge to the first row stays level, the other one bends down
+        assert_eq!(
+            paths(&RenderOptions::default()),
+            vec!["M200,60 C224,60 236,160 260,160", "M200,60 L260,60"]
+        );
+        for (edge_style, expected) in [
+            (EdgeStyle::Straight, "M200,60 L260,160"),
+            (EdgeStyle::Ortho, "M200,60 L230,60 L230,160 L260,160"),
+        ] {
+            let options = RenderOptions {
+                edge_style,
+                ..Default::default()
+            };
+            assert_eq!(paths(&options), vec![expected, "M200,60 L260,60"]);
+        }
+    }
+
     #[test]
     fn test_all_node_colors() {
         let types = [
@@ -299,7 +713,7 @@ mod tests {
             NodeType::Phantom,
         ];
         for nt in types {
-            let fill = node_fill(nt);
+            let fill = DEFAULT_PALETTE.node_fill(nt);
             assert!(fill.starts_with('#'));
         }
     }
@@ -324,10 +738,86 @@ mod tests {
     fn test_render_svg_to_string() {
         let mut graph = LineageGraph::new();
         graph.add_node(make_node("model.a", "a", NodeType::Model));
-        let s = super::render_svg_to_string(&graph);
+        let s = super::render_svg_to_string(&graph, &RenderOptions::default());
         assert!(s.contains("<svg"));
     }
 
+    #[test]
+    fn test_diff_colors_and_legend() {
+        use crate::graph::diff::DiffHighlight;
+
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let b = graph.add_node(make_node("model.b", "b", NodeType::Model));
+        let c = graph.add_node(make_node("model.c", "c", NodeType::Model));
+        for target in [b, c] {
+            graph.add_edge(
+                a,
+                target,
+                EdgeData {
+                    edge_type: EdgeType::Ref,
+                    snippet: None,
+                },
+            );
+        }
+        let mut highlight = DiffHighlight::default();
+        highlight.nodes.insert("model.b".into(), DiffStatus::Added);
+        highlight
+            .nodes
+            .insert("model.c".into(), DiffStatus::Removed);
+        highlight
+            .edges
+            .insert(("model.a".into(), "model.b".into()), DiffStatus::Added);
+        highlight
+            .edges
+            .insert(("model.a".into(), "model.c".into()), DiffStatus::Removed);
+        let options = RenderOptions {
+            diff: Some(&highlight),
+            ..Default::default()
+        };
+
+        let s = super::render_svg_to_string(&graph, &options);
+        assert!(s.contains(r#"data-change="added""#));
+        assert!(s.contains(r#"data-change="unchanged""#));
+        assert!(s.contains(r##"fill="#2ECC71""##));
+        assert!(s.contains(r##"fill-opacity="0.45" stroke="#E74C3C""##));
+        assert!(s.contains("stroke:#E74C3C;stroke-width:2.5;stroke-dasharray:6,4"));
+        assert!(s.contains(r#"<marker id="arrowhead-2ECC71""#));
+        assert!(s.contains(">modified</text>"));
+        // The change legend replaces the node type legend
+        assert!(!s.contains(">model</text>"));
+    }
+
+    #[test]
+    fn test_run_status_outlines_and_legend() {
+        use crate::parser::artifacts::RunStatus;
+
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node("model.a", "a", NodeType::Model));
+        graph.add_node(make_node("model.b", "b", NodeType::Model));
+        let mut status = RunStatusMap::new();
+        status.insert(
+            "model.a".into(),
+            RunStatus::Error {
+                completed_at: None,
+                message: "boom".into(),
+            },
+        );
+        let options = RenderOptions {
+            run_status: Some(&status),
+            ..Default::default()
+        };
+
+        let plain = super::render_svg_to_string(&graph, &RenderOptions::default());
+        let s = super::render_svg_to_string(&graph, &options);
+        assert!(s.contains(r#"data-status="error""#));
+        assert!(s.contains(r#"data-status="never_run""#));
+        assert!(s.contains(r##"stroke="#E74C3C" stroke-width="3""##));
+        assert!(s.contains(">skipped / never run</text>"));
+        assert!(!plain.contains("data-status"));
+        assert!(!plain.contains("never run</text>"));
+    }
+
     #[test]
     fn test_multi_node_all_edge_types() {
         let mut graph = LineageGraph::new();
@@ -353,6 +843,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -360,6 +851,7 @@ mod tests {
             model,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -367,6 +859,7 @@ mod tests {
             test,
             EdgeData {
                 edge_type: EdgeType::Test,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -374,6 +867,7 @@ mod tests {
             exp,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
 
@@ -395,28 +889,169 @@ mod tests {
 
     #[test]
     fn test_node_font_color_all_types() {
-        assert_eq!(node_font_color(NodeType::Phantom), "#000000");
-        assert_eq!(node_font_color(NodeType::Model), "#ffffff");
-        assert_eq!(node_font_color(NodeType::Source), "#ffffff");
-        assert_eq!(node_font_color(NodeType::Seed), "#ffffff");
-        assert_eq!(node_font_color(NodeType::Snapshot), "#ffffff");
-        assert_eq!(node_font_color(NodeType::Test), "#ffffff");
-        assert_eq!(node_font_color(NodeType::Exposure), "#ffffff");
+        assert_eq!(
+            node_font_color(&DEFAULT_PALETTE, NodeType::Phantom),
+            "#000000"
+        );
+        assert_eq!(
+            node_font_color(&DEFAULT_PALETTE, NodeType::Model),
+            "#ffffff"
+        );
+        assert_eq!(
+            node_font_color(&DEFAULT_PALETTE, NodeType::Source),
+            "#ffffff"
+        );
+        assert_eq!(node_font_color(&DEFAULT_PALETTE, NodeType::Seed), "#ffffff");
+        assert_eq!(
+            node_font_color(&DEFAULT_PALETTE, NodeType::Snapshot),
+            "#ffffff"
+        );
+        assert_eq!(node_font_color(&DEFAULT_PALETTE, NodeType::Test), "#ffffff");
+        assert_eq!(
+            node_font_color(&DEFAULT_PALETTE, NodeType::Exposure),
+            "#ffffff"
+        );
     }
 
     #[test]
     fn test_edge_style_all_types() {
-        let ref_style = edge_style(EdgeType::Ref);
+        let ref_style = edge_style(&DEFAULT_PALETTE, EdgeType::Ref);
         assert!(ref_style.contains("stroke-width:1.5"));
         assert!(!ref_style.contains("dasharray"));
 
-        let source_style = edge_style(EdgeType::Source);
+        let source_style = edge_style(&DEFAULT_PALETTE, EdgeType::Source);
         assert!(source_style.contains("dasharray:5,3"));
 
-        let test_style = edge_style(EdgeType::Test);
+        let test_style = edge_style(&DEFAULT_PALETTE, EdgeType::Test);
         assert!(test_style.contains("dasharray:2,2"));
 
-        let exp_style = edge_style(EdgeType::Exposure);
+        let exp_style = edge_style(&DEFAULT_PALETTE, EdgeType::Exposure);
         assert!(exp_style.contains("stroke-width:2.5"));
     }
+
+    #[test]
+    fn test_node_anchor_id_sanitizes() {
+        assert_eq!(node_anchor_id("model.orders"), "node-model.orders");
+        assert_eq!(
+            node_anchor_id("source.raw.my table/<x>"),
+            "node-source.raw.my_table__x_"
+        );
+        assert_eq!(node_anchor_id("model.a-b_c"), "node-model.a-b_c");
+    }
+
+    #[test]
+    fn test_wrap_label() {
+        assert_eq!(wrap_label("orders"), vec!["orders"]);
+        assert_eq!(
+            wrap_label("stg_stripe__payment_intents"),
+            vec!["stg_stripe__payment_", "intents"]
+        );
+        assert_eq!(
+            wrap_label("abcdefghijklmnopqrstuvwxyz"),
+            vec!["abcdefghijklmnopqrstu", "vwxyz"]
+        );
+        let lines = wrap_label("fct_customer_lifetime_value_by_acquisition_channel_daily");
+        assert_eq!(lines.len(), MAX_LABEL_LINES);
+        assert!(lines[1].ends_with('…'));
+        assert!(lines.iter().all(|l| l.chars().count() <= MAX_LABEL_CHARS));
+    }
+
+    #[test]
+    fn test_long_label_wraps_into_tspans() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node(
+            "model.stg_stripe__payment_intents",
+            "stg_stripe__payment_intents",
+            NodeType::Model,
+        ));
+        let output = render_to_string(&graph);
+        assert!(output.contains(r#"dy="-0.6em">stg_stripe__payment_</tspan>"#));
+        assert!(output.contains(r#"dy="1.2em">intents</tspan>"#));
+    }
+
+    #[test]
+    fn test_node_tooltip_title() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node("model.orders", "orders", NodeType::Model);
+        node.materialization = Some("table".into());
+        node.description = Some("One row per <order>".into());
+        graph.add_node(node);
+        graph.add_node(make_node("source.raw.users", "raw.users", NodeType::Source));
+
+        let output = render_to_string(&graph);
+        assert!(output.contains(
+            "<title>orders\nmodel.orders\nmaterialized: table\nOne row per &lt;order&gt;</title>"
+        ));
+        assert!(output.contains("<title>src:raw.users\nsource.raw.users</title>"));
+    }
+
+    #[test]
+    fn test_label_detail_gets_its_own_line() {
+        let mut graph = LineageGraph::new();
+        let mut node = make_node(
+            "model.stg_stripe__payment_intents",
+            "stg_stripe__payment_intents",
+            NodeType::Model,
+        );
+        node.materialization = Some("view".into());
+        graph.add_node(node);
+
+        let options = RenderOptions {
+            label_detail: LabelDetail::Materialization,
+            ..Default::default()
+        };
+        let output = render_svg_to_string(&graph, &options);
+        assert!(output.contains(r#"dy="-0.6em">stg_stripe__payment_…</tspan>"#));
+        assert!(output.contains(r#"dy="1.2em">(view)</tspan>"#));
+    }
+
+    #[test]
+    fn test_max_label_len_keeps_full_name_in_tooltip() {
+        let mut graph = LineageGraph::new();
+        graph.add_node(make_node(
+            "model.stg_stripe__payment_intents",
+            "stg_stripe__payment_intents",
+            NodeType::Model,
+        ));
+
+        let options = RenderOptions {
+            max_label_len: Some(12),
+            ..Default::default()
+        };
+        let output = render_svg_to_string(&graph, &options);
+        assert!(output.contains(">stg_stripe_…</text>"));
+        assert!(output.contains("<title>stg_stripe__payment_intents\n"));
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut graph = LineageGraph::new();
+        let a = graph.add_node(make_node("model.a", "a", NodeType::Model));
+        let raw = graph.add_node(make_node("source.raw", "raw", NodeType::Source));
+        graph.add_edge(
+            raw,
+            a,
+            EdgeData {
+                edge_type: EdgeType::Source,
+                snippet: None,
+            },
+        );
+        let palette =
+            Palette::parse("nodes:\n  model: \"#FFFFFF\"\nedges:\n  source: \"#00A36C\"\n")
+                .unwrap();
+        let options = RenderOptions {
+            palette: Some(&palette),
+            ..Default::default()
+        };
+        let output = render_svg_to_string(&graph, &options);
+
+        assert!(output.contains(r##"rx="8" fill="#FFFFFF""##));
+        assert!(output.contains(r##"fill="#000000" font-family"##));
+        // The legend follows the palette
+        assert!(output.contains(r##"rx="2" fill="#FFFFFF""##));
+        assert!(output.contains(r##"rx="2" fill="#27AE60""##));
+        // Colored edges get an arrowhead of the same color
+        assert!(output.contains(r##"<marker id="arrowhead-00A36C""##));
+        assert!(output.contains("style=\"stroke:#00A36C;stroke-width:1.5;stroke-dasharray:5,3\" marker-end=\"url(#arrowhead-00A36C)\""));
+    }
 }
@@ -1,21 +1,40 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use indexmap::IndexMap;
-use petgraph::stable_graph::NodeIndex;
-use petgraph::visit::EdgeRef;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use petgraph::Direction;
 use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
 
+use crate::graph::diff::edge_type_str;
+use crate::graph::filter::ALL_NODE_TYPES;
+use crate::graph::groups::GroupConfig;
 use crate::graph::impact::ImpactReport;
-use crate::graph::types::{LineageGraph, NodeType};
+use crate::graph::paths::reachable;
+use crate::graph::sla::{evaluate_sla, SlaCheck};
+use crate::graph::sort::{sort_nodes, NodeSort};
+use crate::graph::stats::{phantom_nodes, PhantomNode};
+use crate::graph::types::{EdgeType, LineageGraph, NodeType};
 use crate::parser::artifacts::{self, RunStatus, RunStatusMap};
 use crate::parser::column_lineage::ColumnLineage;
-use crate::render::layout::{sugiyama_layout, LayoutResult};
+use crate::render::layout::{sugiyama_layout, sugiyama_layout_wrapped, LayoutResult};
+use crate::render::LabelDetail;
 
-use super::runner::{spawn_dbt_run, DbtRunMessage, DbtRunRequest};
+use super::runner::{spawn_dbt_run, CancelToken, DbtRunMessage, DbtRunRequest};
+
+/// Zoom bounds for the graph view
+pub const MIN_ZOOM: f64 = 0.3;
+pub const MAX_ZOOM: f64 = 3.0;
+/// Zoom decrement when searching for the largest zoom that fits the graph
+const FIT_ZOOM_STEP: f64 = 0.05;
+/// Columns and rows of the graph's bounding box that panning always leaves in view
+const MIN_VISIBLE_COLS: i32 = 12;
+const MIN_VISIBLE_ROWS: i32 = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
@@ -24,8 +43,76 @@ pub enum AppMode {
     RunMenu,
     ContextMenu,
     RunConfirm,
+    /// The command a run menu entry would execute, shown without running it
+    CommandPreview,
     RunOutput,
     Filter,
+    SqlView,
+    /// Node list has keyboard focus; typing jumps to matching labels
+    NodeList,
+    /// List of phantom (unresolved) nodes, to jump to each one
+    PhantomList,
+}
+
+/// How long the node list type-ahead prefix survives without a keystroke
+pub const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// How long filters must stay unchanged before the graph is laid out again
+pub const RELAYOUT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Most earlier selections kept for going back
+pub const MAX_HISTORY: usize = 100;
+
+/// Rebuilds the graph the TUI was started with (same project, flags and filters)
+/// from the given source
+pub type GraphLoader = Box<dyn Fn(GraphSource) -> anyhow::Result<LineageGraph>>;
+
+/// Puts text on the system clipboard for `y`/`Y`
+pub type ClipboardWriter = Box<dyn FnMut(&str) -> anyhow::Result<()>>;
+
+/// Where the graph's nodes and edges come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphSource {
+    /// dbt's manifest.json
+    Manifest,
+    /// The project's SQL files, parsed directly
+    Sql,
+}
+
+impl GraphSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphSource::Manifest => "manifest",
+            GraphSource::Sql => "SQL",
+        }
+    }
+
+    /// The source `M` switches to
+    pub fn other(self) -> Self {
+        match self {
+            GraphSource::Manifest => GraphSource::Sql,
+            GraphSource::Sql => GraphSource::Manifest,
+        }
+    }
+}
+
+/// Graph totals shown under the help bar
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphSummary {
+    pub nodes: usize,
+    pub edges: usize,
+    /// Node count per type (models first), types without nodes left out
+    pub by_type: Vec<(NodeType, usize)>,
+    /// Nodes hidden by the type and status filters
+    pub filtered_out: usize,
+}
+
+/// Read-only contents of a node's source file, shown by the `v` pane
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlView {
+    /// Pane title (the file path, or the node label when there is no file)
+    pub title: String,
+    pub lines: Vec<String>,
 }
 
 /// Filter by run status
@@ -42,6 +129,7 @@ pub enum DbtRunState {
     Running {
         receiver: mpsc::Receiver<DbtRunMessage>,
         output_lines: Vec<String>,
+        cancel: CancelToken,
     },
     Finished {
         output_lines: Vec<String>,
@@ -83,6 +171,12 @@ pub struct App {
     pub search_query: String,
     pub search_results: Vec<NodeIndex>,
     pub search_cursor: usize,
+    /// Selection when the search was opened, recorded in the history when it closes
+    pub search_origin: Option<NodeIndex>,
+    /// Earlier selections, most recent last (`Backspace` / `<`)
+    pub back_history: Vec<NodeIndex>,
+    /// Selections left by going back, most recent last (`>`)
+    pub forward_history: Vec<NodeIndex>,
     /// Ordered list of all node indices for Tab cycling
     pub node_order: Vec<NodeIndex>,
     pub node_cycle_index: usize,
@@ -93,6 +187,10 @@ pub struct App {
     pub node_groups: Vec<NodeGroup>,
     pub collapsed_groups: HashSet<String>,
     pub node_list_entries: Vec<NodeListEntry>,
+    /// Prefix typed while the node list has focus
+    pub type_ahead: String,
+    /// When `type_ahead` was last extended
+    pub type_ahead_at: Option<Instant>,
 
     // Mouse interaction state
     pub drag_state: Option<DragState>,
@@ -110,15 +208,59 @@ pub struct App {
     pub run_state: DbtRunState,
     pub run_output_scroll: usize,
     pub pending_run: Option<DbtRunRequest>,
+    /// Kill a dbt run that is still going after this long
+    pub run_timeout: Option<Duration>,
+    /// run_results.json the status overlay is read from (`--run-results`)
+    pub run_results_path: PathBuf,
+
+    // SQL preview pane
+    pub sql_view: Option<SqlView>,
+    pub sql_view_scroll: usize,
+
+    // Phantom list (`u`)
+    pub phantom_list: Vec<PhantomNode>,
+    pub phantom_cursor: usize,
 
     // Filtering state
     pub filter_node_types: HashSet<NodeType>,
     pub filter_status: Option<FilterStatus>,
+    /// When a filter change is due to be applied to the layout
+    pub relayout_due: Option<Instant>,
+    /// Grouping rules of the node list, kept to regroup after a re-layout
+    pub group_config: Option<GroupConfig>,
+    /// Order of the nodes inside each node list group
+    pub node_sort: Option<NodeSort>,
+    /// Rebuilds the graph for `R`; reloading is unavailable without one
+    pub graph_loader: Option<GraphLoader>,
+    /// Clear the terminal before the next frame (a reload may have printed warnings)
+    pub full_redraw: bool,
+    /// Copies node names for `y`/`Y`; copying is unavailable without one
+    pub clipboard: Option<ClipboardWriter>,
 
     // Path highlighting state
     pub highlighted_path: HashSet<NodeIndex>,
     /// The node for which the path was computed (so we can clear on re-select)
     pub path_highlight_source: Option<NodeIndex>,
+    /// Whether the highlighted path is just the main line through
+    /// `path_highlight_source` (`m`) rather than all its ancestors and
+    /// descendants (`p`)
+    pub path_main_line: bool,
+    /// First endpoint picked for a two-node path trace
+    pub path_anchor: Option<NodeIndex>,
+    /// One-line feedback shown in the help bar (cleared on the next action that sets it)
+    pub status_message: Option<String>,
+    /// Incoming edge of the selected node being explained (`e`)
+    pub selected_edge: Option<EdgeIndex>,
+    /// Where the graph came from; manifest.json carries no SQL snippets
+    pub graph_source: GraphSource,
+    /// Edges the other source didn't have, set when switching sources with `M`
+    pub source_only_edges: HashSet<EdgeIndex>,
+    /// Detail appended to node labels in the graph view
+    pub label_detail: LabelDetail,
+    /// Longest name shown in a node box before it is cut with `…`
+    pub max_label_len: Option<usize>,
+    /// Layers taller than this are wrapped into several columns
+    pub max_layer_width: Option<usize>,
 
     // Impact analysis (computed when path is highlighted)
     pub impact_report: Option<ImpactReport>,
@@ -126,6 +268,10 @@ pub struct App {
     // Column-level lineage
     pub column_lineage: ColumnLineage,
     pub show_column_lineage: bool,
+    /// Column picked in the column sub-panel: (owning node, index into its columns)
+    pub selected_column: Option<(NodeIndex, usize)>,
+    /// Nodes touched by the selected column's lineage chain
+    pub column_trace_nodes: HashSet<NodeIndex>,
 }
 
 impl App {
@@ -142,7 +288,8 @@ impl App {
 
         let selected = node_order.first().copied();
 
-        let node_groups = build_node_groups(&node_order, &graph, &project_dir);
+        let node_groups = build_node_groups(&node_order, &graph, &project_dir, None);
+        let run_results_path = artifacts::default_run_results_path(&project_dir);
         let collapsed_groups = HashSet::new();
         let node_list_entries = build_node_list_entries(&node_groups, &collapsed_groups);
 
@@ -157,17 +304,7 @@ impl App {
         }
 
         // Initialize filter_node_types with all node types shown by default
-        let filter_node_types: HashSet<NodeType> = [
-            NodeType::Model,
-            NodeType::Source,
-            NodeType::Exposure,
-            NodeType::Test,
-            NodeType::Seed,
-            NodeType::Snapshot,
-            NodeType::Phantom,
-        ]
-        .into_iter()
-        .collect();
+        let filter_node_types: HashSet<NodeType> = ALL_NODE_TYPES.into_iter().collect();
 
         App {
             graph,
@@ -181,6 +318,9 @@ impl App {
             search_query: String::new(),
             search_results: Vec::new(),
             search_cursor: 0,
+            search_origin: None,
+            back_history: Vec::new(),
+            forward_history: Vec::new(),
             node_order,
             node_cycle_index: 0,
             show_node_list: false,
@@ -188,6 +328,8 @@ impl App {
             node_groups,
             collapsed_groups,
             node_list_entries,
+            type_ahead: String::new(),
+            type_ahead_at: None,
             drag_state: None,
             last_node_list_area: None,
             context_menu_pos: None,
@@ -201,22 +343,281 @@ impl App {
             run_state: DbtRunState::Idle,
             run_output_scroll: 0,
             pending_run: None,
+            run_timeout: None,
+            run_results_path,
+            sql_view: None,
+            sql_view_scroll: 0,
+            phantom_list: Vec::new(),
+            phantom_cursor: 0,
             filter_node_types,
             filter_status: None,
+            relayout_due: None,
+            group_config: None,
+            node_sort: None,
+            graph_loader: None,
+            full_redraw: false,
+            clipboard: None,
             highlighted_path: HashSet::new(),
             path_highlight_source: None,
+            path_main_line: false,
+            path_anchor: None,
+            status_message: None,
+            selected_edge: None,
+            graph_source: GraphSource::Sql,
+            source_only_edges: HashSet::new(),
+            label_detail: LabelDetail::None,
+            max_label_len: None,
+            max_layer_width: None,
             impact_report: None,
             column_lineage: ColumnLineage::default(),
             show_column_lineage: false,
+            selected_column: None,
+            column_trace_nodes: HashSet::new(),
+        }
+    }
+
+    /// Regroup the node list using `.dbt-lineage-groups.yml` rules; nodes that
+    /// match no rule keep their directory group
+    pub fn with_groups(mut self, config: &GroupConfig) -> Self {
+        self.group_config = Some(config.clone());
+        self.collapsed_groups.clear();
+        self.rebuild_node_groups();
+        self
+    }
+
+    /// Order the nodes inside each node list group by `sort`; the groups keep
+    /// their order
+    /// Wrap layers taller than `max` nodes into several columns and lay out again
+    pub fn with_max_layer_width(mut self, max: Option<usize>) -> Self {
+        self.max_layer_width = max;
+        self.relayout();
+        self
+    }
+
+    pub fn with_sort(mut self, sort: NodeSort) -> Self {
+        self.node_sort = Some(sort);
+        self.rebuild_node_groups();
+        self
+    }
+
+    /// Rebuild the node list groups from `node_order`, the group config and the sort
+    fn rebuild_node_groups(&mut self) {
+        self.node_groups = build_node_groups(
+            &self.node_order,
+            &self.graph,
+            &self.project_dir,
+            self.group_config.as_ref(),
+        );
+        if let Some(sort) = self.node_sort {
+            for group in &mut self.node_groups {
+                sort_nodes(&self.graph, &mut group.nodes, sort, Some(&self.run_status));
+            }
+        }
+        self.node_list_entries = build_node_list_entries(&self.node_groups, &self.collapsed_groups);
+        self.sync_node_list_state();
+    }
+
+    /// Note that the filters changed; the layout follows once they have been
+    /// left alone for [`RELAYOUT_DEBOUNCE`]
+    pub fn filters_changed(&mut self, now: Instant) {
+        self.relayout_due = Some(now + RELAYOUT_DEBOUNCE);
+    }
+
+    /// Re-layout if a filter change is due by `now`. Returns whether it did.
+    pub fn apply_pending_relayout(&mut self, now: Instant) -> bool {
+        match self.relayout_due {
+            Some(due) if due <= now => {
+                self.relayout();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Lay out only the nodes passing the current filters, then rebuild the Tab
+    /// order and node list from that layout. The selection is kept while its node
+    /// is visible, otherwise the first visible node is selected.
+    pub fn relayout(&mut self) {
+        self.relayout_due = None;
+
+        // Removing nodes from a StableGraph keeps the remaining indices valid
+        let mut visible = self.graph.clone();
+        let hidden: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&idx| !self.node_passes_filter(idx))
+            .collect();
+        for idx in hidden {
+            visible.remove_node(idx);
+        }
+        self.layout = sugiyama_layout_wrapped(&visible, self.max_layer_width);
+        self.node_order = self.layout.layers.iter().flatten().copied().collect();
+
+        let selection_visible = self
+            .selected_node
+            .is_some_and(|sel| self.layout.positions.contains_key(&sel));
+        if !selection_visible {
+            self.selected_node = self.node_order.first().copied();
+            self.selected_edge = None;
+        }
+        self.node_cycle_index = 0;
+        self.sync_cycle_index();
+
+        self.rebuild_node_groups();
+        if self.node_list_entries.is_empty() {
+            self.node_list_state.select(None);
+        }
+        self.center_on_selected();
+    }
+
+    /// Rebuild the graph with `graph_loader` and swap it in. On failure the old
+    /// graph stays and the error is shown in the help bar.
+    pub fn reload(&mut self) {
+        let Some(loader) = &self.graph_loader else {
+            self.status_message = Some("reload is not available".to_string());
+            return;
+        };
+        let result = loader(self.graph_source);
+        self.full_redraw = true;
+        match result {
+            Ok(graph) => {
+                self.replace_graph(graph);
+                self.status_message = Some(format!("reloaded: {} nodes", self.graph.node_count()));
+            }
+            Err(e) => self.status_message = Some(format!("reload failed: {:#}", e)),
+        }
+    }
+
+    /// Rebuild the graph from the other source (manifest or SQL) and mark
+    /// the edges only the new source knows about. On failure the old graph stays.
+    pub fn switch_graph_source(&mut self) {
+        let Some(loader) = &self.graph_loader else {
+            self.status_message = Some("switching source is not available".to_string());
+            return;
+        };
+        let target = self.graph_source.other();
+        let result = loader(target);
+        self.full_redraw = true;
+        let graph = match result {
+            Ok(graph) => graph,
+            Err(e) => {
+                self.status_message = Some(format!("switch to {} failed: {:#}", target.label(), e));
+                return;
+            }
+        };
+
+        let old_edges = edge_keys(&self.graph);
+        let new_edges = edge_keys(&graph);
+        let only_new: HashSet<EdgeIndex> = graph
+            .edge_references()
+            .filter(|e| {
+                let key = (
+                    graph[e.source()].unique_id.as_str(),
+                    graph[e.target()].unique_id.as_str(),
+                );
+                !old_edges.contains(&key)
+            })
+            .map(|e| e.id())
+            .collect();
+        let only_old = old_edges.difference(&new_edges).count();
+        let (new_label, old_label) = (target.label(), self.graph_source.label());
+        let message = format!(
+            "showing {} graph: {} edges only in {}, {} only in {}",
+            new_label,
+            only_new.len(),
+            new_label,
+            only_old,
+            old_label
+        );
+
+        self.replace_graph(graph);
+        self.graph_source = target;
+        self.source_only_edges = only_new;
+        self.status_message = Some(message);
+    }
+
+    /// Copy the selected node's unique_id to the clipboard (`y`)
+    pub fn copy_selected_id(&mut self) {
+        if let Some(idx) = self.selected_node {
+            let text = self.graph[idx].unique_id.clone();
+            self.copy_to_clipboard(&text);
+        }
+    }
+
+    /// Copy the selected node's label, e.g. `orders`, to the clipboard (`Y`)
+    pub fn copy_selected_label(&mut self) {
+        if let Some(idx) = self.selected_node {
+            let text = self.graph[idx].label.clone();
+            self.copy_to_clipboard(&text);
+        }
+    }
+
+    /// Copy the command of the pending run, as shown in the command preview
+    pub fn copy_pending_command(&mut self) {
+        if let Some(request) = &self.pending_run {
+            let text = request.display_command();
+            self.copy_to_clipboard(&text);
         }
     }
 
+    /// Copy `text`, reporting the outcome in the help bar. A missing clipboard
+    /// (e.g. over SSH without a display) is reported, not fatal.
+    fn copy_to_clipboard(&mut self, text: &str) {
+        let Some(clipboard) = &mut self.clipboard else {
+            self.status_message = Some("clipboard is not available".to_string());
+            return;
+        };
+        self.status_message = Some(match clipboard(text) {
+            Ok(()) => format!("copied {}", text),
+            Err(e) => format!("copy failed: {:#}", e),
+        });
+    }
+
+    /// Show `graph` instead of the current one, keeping the filters and, when it
+    /// still exists, the selected node (matched by unique_id). Run status is
+    /// reloaded and the graph laid out again.
+    pub fn replace_graph(&mut self, graph: LineageGraph) {
+        let selected_id = self
+            .selected_node
+            .map(|idx| self.graph[idx].unique_id.clone());
+        self.graph = graph;
+        self.selected_node = selected_id.and_then(|id| {
+            self.graph
+                .node_indices()
+                .find(|&idx| self.graph[idx].unique_id == id)
+        });
+
+        // Everything else holding node or edge indices refers to the old graph
+        self.selected_edge = None;
+        self.source_only_edges.clear();
+        self.search_results.clear();
+        self.search_cursor = 0;
+        self.search_origin = None;
+        self.back_history.clear();
+        self.forward_history.clear();
+        self.highlighted_path.clear();
+        self.path_highlight_source = None;
+        self.path_anchor = None;
+        self.impact_report = None;
+        self.clear_column_selection();
+        self.column_lineage = if self.show_column_lineage {
+            crate::parser::column_lineage::resolve_column_lineage(&self.graph)
+        } else {
+            ColumnLineage::default()
+        };
+
+        self.reload_run_status();
+        self.relayout();
+    }
+
     pub fn cycle_next_node(&mut self) {
         if self.node_order.is_empty() {
             return;
         }
+        let previous = self.selected_node;
         self.node_cycle_index = (self.node_cycle_index + 1) % self.node_order.len();
         self.selected_node = Some(self.node_order[self.node_cycle_index]);
+        self.record_jump(previous);
         self.sync_node_list_state();
         self.center_on_selected();
     }
@@ -225,12 +626,14 @@ impl App {
         if self.node_order.is_empty() {
             return;
         }
+        let previous = self.selected_node;
         if self.node_cycle_index == 0 {
             self.node_cycle_index = self.node_order.len() - 1;
         } else {
             self.node_cycle_index -= 1;
         }
         self.selected_node = Some(self.node_order[self.node_cycle_index]);
+        self.record_jump(previous);
         self.sync_node_list_state();
         self.center_on_selected();
     }
@@ -262,6 +665,7 @@ impl App {
 
         if let Some((node, _, _)) = best {
             self.selected_node = Some(node);
+            self.record_jump(Some(current));
             self.sync_cycle_index();
             self.sync_node_list_state();
             self.center_on_selected();
@@ -297,6 +701,7 @@ impl App {
 
         if let Some((node, _, _)) = best {
             self.selected_node = Some(node);
+            self.record_jump(Some(current));
             self.sync_cycle_index();
             self.sync_node_list_state();
             self.center_on_selected();
@@ -327,6 +732,7 @@ impl App {
         let new_idx = if idx == 0 { layer.len() - 1 } else { idx - 1 };
 
         self.selected_node = Some(layer[new_idx]);
+        self.record_jump(Some(current));
         self.sync_cycle_index();
         self.sync_node_list_state();
         self.center_on_selected();
@@ -355,6 +761,7 @@ impl App {
         let new_idx = (idx + 1) % layer.len();
 
         self.selected_node = Some(layer[new_idx]);
+        self.record_jump(Some(current));
         self.sync_cycle_index();
         self.sync_node_list_state();
         self.center_on_selected();
@@ -449,9 +856,85 @@ impl App {
         }
     }
 
+    /// Give the node list keyboard focus, opening it if hidden
+    pub fn focus_node_list(&mut self) {
+        self.show_node_list = true;
+        self.type_ahead.clear();
+        self.type_ahead_at = None;
+        self.mode = AppMode::NodeList;
+    }
+
+    /// Extend the type-ahead prefix with `c` and select the next visible node
+    /// whose label starts with it (case-insensitive), wrapping around the list.
+    ///
+    /// The prefix starts over after `TYPE_AHEAD_TIMEOUT` idle. A one-letter
+    /// prefix searches from the row after the current one, so repeating a letter
+    /// cycles through its matches; a longer prefix keeps the current row if it
+    /// still matches.
+    pub fn type_ahead(&mut self, c: char, now: Instant) {
+        if self
+            .type_ahead_at
+            .is_some_and(|at| now.duration_since(at) > TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead.clear();
+        }
+        self.type_ahead.push(c);
+        self.type_ahead_at = Some(now);
+
+        let len = self.node_list_entries.len();
+        if len == 0 {
+            return;
+        }
+        let prefix = self.type_ahead.to_lowercase();
+        let current = self.node_list_state.selected().unwrap_or(0);
+        let start = if self.type_ahead.chars().count() == 1 {
+            current + 1
+        } else {
+            current
+        };
+        let hit = (0..len)
+            .map(|offset| (start + offset) % len)
+            .find_map(|row| match self.node_list_entries[row] {
+                NodeListEntry::Node(idx)
+                    if self.graph[idx].label.to_lowercase().starts_with(&prefix) =>
+                {
+                    Some((row, idx))
+                }
+                _ => None,
+            });
+        if let Some((row, idx)) = hit {
+            let previous = self.selected_node.replace(idx);
+            self.record_jump(previous);
+            self.node_list_state.select(Some(row));
+            self.sync_cycle_index();
+            self.center_on_selected();
+        }
+    }
+
+    /// Move the focused node list to the previous or next node row
+    pub fn step_node_list(&mut self, forward: bool) {
+        let current = self.node_list_state.selected().unwrap_or(0);
+        let rows: Box<dyn Iterator<Item = usize>> = if forward {
+            Box::new(current + 1..self.node_list_entries.len())
+        } else {
+            Box::new((0..current).rev())
+        };
+        for row in rows {
+            if let NodeListEntry::Node(idx) = self.node_list_entries[row] {
+                let previous = self.selected_node.replace(idx);
+                self.record_jump(previous);
+                self.node_list_state.select(Some(row));
+                self.sync_cycle_index();
+                self.center_on_selected();
+                return;
+            }
+        }
+    }
+
     /// Select a node without centering the viewport (used for mouse clicks on the graph)
     pub fn select_node_no_center(&mut self, idx: NodeIndex) {
-        self.selected_node = Some(idx);
+        let previous = self.selected_node.replace(idx);
+        self.record_jump(previous);
         self.sync_cycle_index();
         self.sync_node_list_state();
     }
@@ -493,15 +976,23 @@ impl App {
         }
     }
 
+    /// Select the nodes whose label or unique_id contains the query, ignoring
+    /// case. A query starting with `=` (`=orders`) must match one of them
+    /// exactly instead.
     pub fn update_search(&mut self) {
         let query = self.search_query.to_lowercase();
+        let anchored = query.strip_prefix('=');
         self.search_results = self
             .graph
             .node_indices()
             .filter(|&idx| {
                 let node = &self.graph[idx];
-                node.label.to_lowercase().contains(&query)
-                    || node.unique_id.to_lowercase().contains(&query)
+                let label = node.label.to_lowercase();
+                let unique_id = node.unique_id.to_lowercase();
+                match anchored {
+                    Some(name) => label == name || unique_id == name,
+                    None => label.contains(&query) || unique_id.contains(&query),
+                }
             })
             .collect();
         self.search_cursor = 0;
@@ -510,6 +1001,20 @@ impl App {
         }
     }
 
+    /// Start a search from the current selection
+    pub fn open_search(&mut self) {
+        self.mode = AppMode::Search;
+        self.search_query.clear();
+        self.search_origin = self.selected_node;
+    }
+
+    /// Leave search mode, recording the jump to the found node in the history
+    pub fn close_search(&mut self) {
+        self.mode = AppMode::Normal;
+        let origin = self.search_origin.take();
+        self.record_jump(origin);
+    }
+
     pub fn next_search_result(&mut self) {
         if self.search_results.is_empty() {
             return;
@@ -518,12 +1023,132 @@ impl App {
         self.selected_node = Some(self.search_results[self.search_cursor]);
     }
 
+    /// Remember `previous` as the place to go back to after the selection moved
+    /// away from it. A new jump drops the forward history, as in a browser.
+    fn record_jump(&mut self, previous: Option<NodeIndex>) {
+        let Some(previous) = previous else {
+            return;
+        };
+        if self.selected_node == Some(previous) {
+            return;
+        }
+        if self.back_history.last() != Some(&previous) {
+            self.back_history.push(previous);
+            if self.back_history.len() > MAX_HISTORY {
+                self.back_history.remove(0);
+            }
+        }
+        self.forward_history.clear();
+    }
+
+    /// Return to the previous selection and center on it. Entries hidden by
+    /// the current filters are skipped.
+    pub fn history_back(&mut self) {
+        if let Some(node) = self.pop_visible(false) {
+            if let Some(current) = self.selected_node {
+                self.forward_history.push(current);
+            }
+            self.restore_selection(node);
+        }
+    }
+
+    /// Redo a selection undone by [`App::history_back`]
+    pub fn history_forward(&mut self) {
+        if let Some(node) = self.pop_visible(true) {
+            if let Some(current) = self.selected_node {
+                self.back_history.push(current);
+            }
+            self.restore_selection(node);
+        }
+    }
+
+    /// Pop history entries until one is in the current layout and differs from
+    /// the selection
+    fn pop_visible(&mut self, forward: bool) -> Option<NodeIndex> {
+        let stack = if forward {
+            &mut self.forward_history
+        } else {
+            &mut self.back_history
+        };
+        while let Some(node) = stack.pop() {
+            if self.selected_node != Some(node) && self.layout.positions.contains_key(&node) {
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    fn restore_selection(&mut self, node: NodeIndex) {
+        self.selected_node = Some(node);
+        self.selected_edge = None;
+        self.sync_cycle_index();
+        self.sync_node_list_state();
+        self.center_on_selected();
+    }
+
     pub fn reset_view(&mut self) {
         self.viewport_x = 0;
         self.viewport_y = 0;
         self.zoom = 1.0;
     }
 
+    /// Pull the viewport back so at least a corner of the graph's bounding box
+    /// (`MIN_VISIBLE_COLS` x `MIN_VISIBLE_ROWS`, or the whole box if smaller)
+    /// stays inside the graph area at the current zoom. Panning past that
+    /// point is free; only losing the graph entirely is prevented.
+    pub fn clamp_viewport(&mut self) {
+        use super::graph_widget::layout_world_bounds;
+
+        let Some(area) = self.last_graph_area else {
+            return;
+        };
+        let Some((x0, y0, x1, y1)) = layout_world_bounds(self.layout.positions.values(), self.zoom)
+        else {
+            return;
+        };
+        let clamp = |v: i32, lo: i32, hi: i32, span: i32, keep: i32| {
+            let keep = keep.min(hi - lo).min(span);
+            v.clamp(lo + keep - span, hi - keep)
+        };
+        self.viewport_x = clamp(self.viewport_x, x0, x1, area.width as i32, MIN_VISIBLE_COLS);
+        self.viewport_y = clamp(
+            self.viewport_y,
+            y0,
+            y1,
+            area.height as i32,
+            MIN_VISIBLE_ROWS,
+        );
+    }
+
+    /// Zoom and pan so the whole graph fits in the graph area and is centered.
+    /// Graphs too large to fit even at `MIN_ZOOM` are centered at `MIN_ZOOM`.
+    pub fn zoom_to_fit(&mut self) {
+        use super::graph_widget::layout_world_bounds;
+
+        let Some(area) = self.last_graph_area else {
+            return;
+        };
+        let fits = |zoom: f64| {
+            layout_world_bounds(self.layout.positions.values(), zoom).is_some_and(
+                |(x0, y0, x1, y1)| x1 - x0 <= area.width as i32 && y1 - y0 <= area.height as i32,
+            )
+        };
+
+        // Gaps shrink in whole cells, so step down from the largest zoom
+        let mut zoom = MAX_ZOOM;
+        while zoom > MIN_ZOOM && !fits(zoom) {
+            zoom = (zoom - FIT_ZOOM_STEP).max(MIN_ZOOM);
+        }
+        let Some((x0, y0, x1, y1)) = layout_world_bounds(self.layout.positions.values(), zoom)
+        else {
+            return;
+        };
+
+        self.zoom = zoom;
+        self.viewport_x = (x0 + x1) / 2 - area.width as i32 / 2;
+        self.viewport_y = (y0 + y1) / 2 - area.height as i32 / 2;
+    }
+
     /// Get upstream neighbors of a node
     pub fn upstream_of(&self, idx: NodeIndex) -> Vec<NodeIndex> {
         self.graph
@@ -545,6 +1170,7 @@ impl App {
         if let DbtRunState::Running {
             ref receiver,
             ref mut output_lines,
+            ..
         } = self.run_state
         {
             // Non-blocking drain of all available messages
@@ -572,6 +1198,27 @@ impl App {
                         };
                         return;
                     }
+                    Ok(DbtRunMessage::Cancelled) => {
+                        output_lines.push("Cancelled: dbt process killed".to_string());
+                        let lines = std::mem::take(output_lines);
+                        self.run_state = DbtRunState::Finished {
+                            output_lines: lines,
+                            success: false,
+                        };
+                        return;
+                    }
+                    Ok(DbtRunMessage::TimedOut(after)) => {
+                        output_lines.push(format!(
+                            "ERROR: dbt process killed after timing out ({}s)",
+                            after.as_secs()
+                        ));
+                        let lines = std::mem::take(output_lines);
+                        self.run_state = DbtRunState::Finished {
+                            output_lines: lines,
+                            success: false,
+                        };
+                        return;
+                    }
                     Err(mpsc::TryRecvError::Empty) => break,
                     Err(mpsc::TryRecvError::Disconnected) => {
                         let lines = std::mem::take(output_lines);
@@ -589,19 +1236,36 @@ impl App {
     /// Start executing a dbt run from the pending request
     pub fn start_dbt_run(&mut self) {
         if let Some(request) = self.pending_run.take() {
-            let receiver = spawn_dbt_run(request);
+            let (receiver, cancel) = spawn_dbt_run(request, self.run_timeout);
             self.run_state = DbtRunState::Running {
                 receiver,
                 output_lines: Vec::new(),
+                cancel,
             };
             self.run_output_scroll = 0;
             self.mode = AppMode::RunOutput;
         }
     }
 
-    /// Reload run status from target/run_results.json, merging into existing state
+    /// Ask the runner to kill the in-progress dbt process; the run finishes
+    /// once the runner reports `Cancelled`
+    pub fn cancel_dbt_run(&mut self) {
+        if let DbtRunState::Running {
+            ref mut output_lines,
+            ref cancel,
+            ..
+        } = self.run_state
+        {
+            if !cancel.is_cancelled() {
+                cancel.cancel();
+                output_lines.push("Cancelling...".to_string());
+            }
+        }
+    }
+
+    /// Reload run status from `run_results_path`, merging into existing state
     pub fn reload_run_status(&mut self) {
-        if let Ok(Some(results)) = artifacts::load_run_results(&self.project_dir) {
+        if let Ok(Some(results)) = artifacts::load_run_results_from(&self.run_results_path) {
             artifacts::merge_run_status_map(
                 &mut self.run_status,
                 &results,
@@ -618,6 +1282,12 @@ impl App {
             .unwrap_or(&RunStatus::NeverRun)
     }
 
+    /// The node's `meta.sla_hours` measured against its last run, if it has both
+    pub fn node_sla(&self, idx: NodeIndex) -> Option<SlaCheck> {
+        let node = &self.graph[idx];
+        evaluate_sla(node, self.node_run_status(&node.unique_id), Utc::now())
+    }
+
     /// Check if a node passes the current filters
     pub fn node_passes_filter(&self, idx: NodeIndex) -> bool {
         let node = &self.graph[idx];
@@ -661,19 +1331,35 @@ impl App {
         }
     }
 
+    /// Totals of the whole graph and how many nodes the filters hide
+    pub fn graph_summary(&self) -> GraphSummary {
+        let mut by_type: Vec<(NodeType, usize)> =
+            ALL_NODE_TYPES.into_iter().map(|nt| (nt, 0)).collect();
+        let mut filtered_out = 0;
+        for idx in self.graph.node_indices() {
+            if let Some(entry) = by_type
+                .iter_mut()
+                .find(|(nt, _)| *nt == self.graph[idx].node_type)
+            {
+                entry.1 += 1;
+            }
+            if !self.node_passes_filter(idx) {
+                filtered_out += 1;
+            }
+        }
+        by_type.retain(|&(_, count)| count > 0);
+
+        GraphSummary {
+            nodes: self.graph.node_count(),
+            edges: self.graph.edge_count(),
+            by_type,
+            filtered_out,
+        }
+    }
+
     /// Build a description of active filters for the help bar
     pub fn filter_description(&self) -> Option<String> {
-        let all_types: HashSet<NodeType> = [
-            NodeType::Model,
-            NodeType::Source,
-            NodeType::Exposure,
-            NodeType::Test,
-            NodeType::Seed,
-            NodeType::Snapshot,
-            NodeType::Phantom,
-        ]
-        .into_iter()
-        .collect();
+        let all_types: HashSet<NodeType> = ALL_NODE_TYPES.into_iter().collect();
 
         let mut parts = Vec::new();
 
@@ -706,26 +1392,154 @@ impl App {
     /// Toggle path highlighting for the currently selected node.
     /// If already highlighting this node, clear it. Otherwise compute paths.
     pub fn toggle_path_highlight(&mut self) {
+        self.highlight_path(false);
+    }
+
+    /// Toggle highlighting of only the main line through the selected node:
+    /// the shortest path from its nearest source to its nearest exposure
+    pub fn toggle_main_line_highlight(&mut self) {
+        self.highlight_path(true);
+    }
+
+    /// Highlight the full path or the main line through the selected node, or
+    /// clear the highlight when it is already showing that
+    fn highlight_path(&mut self, main_line: bool) {
         let Some(selected) = self.selected_node else {
             return;
         };
 
-        // If already highlighting this node, clear
-        if self.path_highlight_source == Some(selected) {
+        // If already highlighting this node the same way, clear
+        if self.path_highlight_source == Some(selected) && self.path_main_line == main_line {
             self.highlighted_path.clear();
             self.path_highlight_source = None;
             self.impact_report = None;
             return;
         }
 
-        // Compute the full path through the selected node
-        self.highlighted_path = compute_path_through(&self.graph, selected);
+        if main_line {
+            let line = crate::graph::paths::main_line(&self.graph, selected);
+            self.status_message = Some(format!(
+                "main line: {} \u{2192} {}, {} hops",
+                self.graph[line[0]].label,
+                self.graph[line[line.len() - 1]].label,
+                line.len() - 1
+            ));
+            self.highlighted_path = line.into_iter().collect();
+        } else {
+            self.highlighted_path = compute_path_through(&self.graph, selected);
+        }
         self.path_highlight_source = Some(selected);
+        self.path_main_line = main_line;
 
         // Also compute impact report for downstream analysis
         self.impact_report = Some(crate::graph::impact::compute_impact(&self.graph, selected));
     }
 
+    /// Pick the selected node as a path endpoint. The first call sets the
+    /// anchor; the second highlights the shortest path between the anchor and
+    /// the selected node (in whichever direction one exists).
+    pub fn mark_path_endpoint(&mut self) {
+        let Some(selected) = self.selected_node else {
+            return;
+        };
+
+        let Some(anchor) = self.path_anchor.take() else {
+            self.path_anchor = Some(selected);
+            self.highlighted_path.clear();
+            self.path_highlight_source = None;
+            self.impact_report = None;
+            self.status_message = Some(format!(
+                "path from {} \u{2192} select target, P again",
+                self.graph[selected].label
+            ));
+            return;
+        };
+
+        if anchor == selected {
+            self.status_message = None;
+            return;
+        }
+
+        let path = crate::graph::paths::shortest_path(&self.graph, anchor, selected)
+            .or_else(|| crate::graph::paths::shortest_path(&self.graph, selected, anchor));
+        match path {
+            Some(path) => {
+                self.status_message = Some(format!("path: {} hops", path.len() - 1));
+                self.highlighted_path = path.into_iter().collect();
+            }
+            None => {
+                self.highlighted_path.clear();
+                self.status_message = Some(format!(
+                    "no path between {} and {}",
+                    self.graph[anchor].label, self.graph[selected].label
+                ));
+            }
+        }
+        self.path_highlight_source = None;
+        self.impact_report = None;
+    }
+
+    /// Step through the selected node's incoming edges, highlighting each one and
+    /// explaining in the help bar which line of SQL created it
+    pub fn explain_next_edge(&mut self) {
+        let Some(selected) = self.selected_node else {
+            return;
+        };
+        let mut incoming: Vec<_> = self
+            .graph
+            .edges_directed(selected, Direction::Incoming)
+            .filter(|e| self.node_passes_filter(e.source()))
+            .map(|e| (self.graph[e.source()].label.as_str(), e.id()))
+            .collect();
+        incoming.sort();
+        let incoming: Vec<EdgeIndex> = incoming.into_iter().map(|(_, id)| id).collect();
+
+        if incoming.is_empty() {
+            self.selected_edge = None;
+            self.status_message = Some(format!(
+                "{} has no upstream edges",
+                self.graph[selected].label
+            ));
+            return;
+        }
+        let next = self
+            .selected_edge
+            .and_then(|edge| incoming.iter().position(|&e| e == edge))
+            .map_or(0, |pos| (pos + 1) % incoming.len());
+        self.selected_edge = Some(incoming[next]);
+        self.status_message = Some(self.edge_explanation(incoming[next]));
+    }
+
+    /// The explained edge, while its target is still the selected node
+    pub fn active_edge(&self) -> Option<EdgeIndex> {
+        let edge = self.selected_edge?;
+        let (_, target) = self.graph.edge_endpoints(edge)?;
+        (Some(target) == self.selected_node).then_some(edge)
+    }
+
+    /// One-line reason an edge exists: the SQL line with the ref()/source() call,
+    /// or where the dependency was declared when there is no SQL for it
+    pub fn edge_explanation(&self, edge: EdgeIndex) -> String {
+        let Some((source, target)) = self.graph.edge_endpoints(edge) else {
+            return String::new();
+        };
+        let data = &self.graph[edge];
+        let origin = match (&data.snippet, data.edge_type) {
+            (Some(snippet), _) => format!("line {}: {}", snippet.line, snippet.text),
+            (None, _) if self.graph_source == GraphSource::Manifest => "from manifest".to_string(),
+            (None, EdgeType::Exposure) => "declared in exposure depends_on".to_string(),
+            (None, EdgeType::Test) => "declared in schema YAML".to_string(),
+            (None, _) => "found in compiled SQL".to_string(),
+        };
+        format!(
+            "{} \u{2192} {} ({}) {}",
+            self.graph[source].label,
+            self.graph[target].label,
+            edge_type_str(data.edge_type),
+            origin
+        )
+    }
+
     /// Toggle column-level lineage display. Resolves lazily on first toggle.
     pub fn toggle_column_lineage(&mut self) {
         self.show_column_lineage = !self.show_column_lineage;
@@ -735,24 +1549,241 @@ impl App {
             self.column_lineage =
                 crate::parser::column_lineage::resolve_column_lineage(&self.graph);
         }
+
+        if !self.show_column_lineage {
+            self.clear_column_selection();
+        }
     }
 
-    /// Whether a dbt run is currently in progress
-    pub fn is_run_in_progress(&self) -> bool {
-        matches!(self.run_state, DbtRunState::Running { .. })
+    /// Columns of a node that can be picked in the column sub-panel.
+    /// Falls back to lineage target columns when the node has no parsed columns.
+    pub fn selectable_columns(&self, idx: NodeIndex) -> Vec<String> {
+        let node = &self.graph[idx];
+        if !node.columns.is_empty() {
+            return node.columns.clone();
+        }
+        let mut columns: Vec<String> = Vec::new();
+        for edge in self.column_lineage.edges_for_target(&node.unique_id) {
+            if !columns.contains(&edge.target_column) {
+                columns.push(edge.target_column.clone());
+            }
+        }
+        columns
     }
 
-    /// Whether we have any run output to show
-    pub fn has_run_output(&self) -> bool {
-        !matches!(self.run_state, DbtRunState::Idle)
+    /// Name of the picked column, if it belongs to the currently selected node
+    pub fn selected_column_name(&self) -> Option<String> {
+        let (node, col_idx) = self.selected_column?;
+        if self.selected_node != Some(node) {
+            return None;
+        }
+        self.selectable_columns(node).get(col_idx).cloned()
     }
-}
 
-/// Derive a group key for a node based on its file path
-fn group_key_for_node(node: &crate::graph::types::NodeData, project_dir: &Path) -> String {
-    if let Some(path) = &node.file_path {
-        // Normalize absolute paths by stripping the project dir prefix
-        let rel = if path.is_absolute() {
+    /// Move the column cursor forward (or backward) and recompute the trace
+    fn step_column(&mut self, forward: bool) {
+        if !self.show_column_lineage {
+            return;
+        }
+        let Some(selected) = self.selected_node else {
+            return;
+        };
+        let count = self.selectable_columns(selected).len();
+        if count == 0 {
+            return;
+        }
+        let next = match self.selected_column {
+            Some((node, i)) if node == selected => {
+                if forward {
+                    (i + 1) % count
+                } else {
+                    (i + count - 1) % count
+                }
+            }
+            _ if forward => 0,
+            _ => count - 1,
+        };
+        self.selected_column = Some((selected, next));
+        self.recompute_column_trace();
+    }
+
+    pub fn select_next_column(&mut self) {
+        self.step_column(true);
+    }
+
+    pub fn select_prev_column(&mut self) {
+        self.step_column(false);
+    }
+
+    pub fn clear_column_selection(&mut self) {
+        self.selected_column = None;
+        self.column_trace_nodes.clear();
+    }
+
+    /// Recompute the set of graph nodes on the picked column's lineage chain
+    fn recompute_column_trace(&mut self) {
+        self.column_trace_nodes.clear();
+        let (Some(selected), Some(column)) = (self.selected_node, self.selected_column_name())
+        else {
+            return;
+        };
+        let unique_id = self.graph[selected].unique_id.clone();
+        let mut ids: HashSet<String> = HashSet::new();
+        for edge in self.column_lineage.trace_column(&unique_id, &column) {
+            ids.insert(edge.source_node.clone());
+            ids.insert(edge.target_node.clone());
+        }
+        if ids.is_empty() {
+            return;
+        }
+        ids.insert(unique_id);
+        self.column_trace_nodes = self
+            .graph
+            .node_indices()
+            .filter(|&idx| ids.contains(&self.graph[idx].unique_id))
+            .collect();
+    }
+
+    /// The node set the graph should emphasise: path highlighting wins, otherwise
+    /// the picked column's trace while its node stays selected.
+    pub fn active_highlight(&self) -> Option<&HashSet<NodeIndex>> {
+        if !self.highlighted_path.is_empty() {
+            return Some(&self.highlighted_path);
+        }
+        match self.selected_column {
+            Some((node, _))
+                if self.selected_node == Some(node) && !self.column_trace_nodes.is_empty() =>
+            {
+                Some(&self.column_trace_nodes)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a dbt run is currently in progress
+    pub fn is_run_in_progress(&self) -> bool {
+        matches!(self.run_state, DbtRunState::Running { .. })
+    }
+
+    /// Whether we have any run output to show
+    pub fn has_run_output(&self) -> bool {
+        !matches!(self.run_state, DbtRunState::Idle)
+    }
+
+    /// Load the selected node's file into the SQL pane and switch to it.
+    /// Nodes without a file (or with an unreadable one) get a placeholder.
+    pub fn open_sql_view(&mut self) {
+        let Some(idx) = self.selected_node else {
+            return;
+        };
+        let node = &self.graph[idx];
+        let view = match &node.file_path {
+            Some(rel) => {
+                let path = self.project_dir.join(rel);
+                let lines = match std::fs::read_to_string(&path) {
+                    Ok(content) => content.lines().map(str::to_string).collect(),
+                    Err(e) => vec![format!("(could not read {}: {})", path.display(), e)],
+                };
+                SqlView {
+                    title: rel.display().to_string(),
+                    lines,
+                }
+            }
+            None => SqlView {
+                title: node.label.clone(),
+                lines: vec![format!(
+                    "(no source file for {} '{}')",
+                    node.node_type.label(),
+                    node.label
+                )],
+            },
+        };
+        self.sql_view = Some(view);
+        self.sql_view_scroll = 0;
+        self.mode = AppMode::SqlView;
+    }
+
+    /// Number of lines in the open SQL pane
+    pub fn sql_view_len(&self) -> usize {
+        self.sql_view.as_ref().map_or(0, |v| v.lines.len())
+    }
+
+    /// Whether the graph has phantom nodes for the `u` list
+    pub fn has_phantoms(&self) -> bool {
+        self.graph
+            .node_weights()
+            .any(|node| node.node_type == NodeType::Phantom)
+    }
+
+    /// List the phantom nodes with the files that reference them and switch to
+    /// the list. Without phantoms, says so in the help bar instead.
+    pub fn open_phantom_list(&mut self) {
+        self.phantom_list = phantom_nodes(&self.graph);
+        self.phantom_cursor = 0;
+        if self.phantom_list.is_empty() {
+            self.status_message = Some("no phantom nodes".into());
+            return;
+        }
+        self.mode = AppMode::PhantomList;
+    }
+
+    /// Move the phantom list cursor one entry, wrapping around
+    pub fn step_phantom_list(&mut self, forward: bool) {
+        let len = self.phantom_list.len();
+        if len == 0 {
+            return;
+        }
+        self.phantom_cursor = if forward {
+            (self.phantom_cursor + 1) % len
+        } else {
+            (self.phantom_cursor + len - 1) % len
+        };
+    }
+
+    /// Select and center the phantom under the cursor, back in the graph view.
+    /// A phantom hidden by the filters is reported instead.
+    pub fn jump_to_phantom(&mut self) {
+        let Some(phantom) = self.phantom_list.get(self.phantom_cursor) else {
+            return;
+        };
+        let Some(idx) = self
+            .graph
+            .node_indices()
+            .find(|&idx| self.graph[idx].unique_id == phantom.unique_id)
+        else {
+            return;
+        };
+        self.mode = AppMode::Normal;
+        if !self.node_passes_filter(idx) {
+            self.status_message = Some(format!("{} is hidden by the filters", phantom.label));
+            return;
+        }
+        let previous = self.selected_node.replace(idx);
+        self.record_jump(previous);
+        self.sync_cycle_index();
+        self.sync_node_list_state();
+        self.center_on_selected();
+    }
+}
+
+/// Derive a group key for a node based on its file path
+/// Every edge as (source unique_id, target unique_id), comparable across graphs
+fn edge_keys(graph: &LineageGraph) -> HashSet<(&str, &str)> {
+    graph
+        .edge_references()
+        .map(|e| {
+            (
+                graph[e.source()].unique_id.as_str(),
+                graph[e.target()].unique_id.as_str(),
+            )
+        })
+        .collect()
+}
+
+fn group_key_for_node(node: &crate::graph::types::NodeData, project_dir: &Path) -> String {
+    if let Some(path) = &node.file_path {
+        // Normalize absolute paths by stripping the project dir prefix
+        let rel = if path.is_absolute() {
             path.strip_prefix(project_dir).unwrap_or(path.as_path())
         } else {
             path.as_path()
@@ -770,18 +1801,22 @@ fn group_key_for_node(node: &crate::graph::types::NodeData, project_dir: &Path)
     }
 }
 
-/// Build directory-based node groups from the node order
+/// Build node groups from the node order: configured groups first, else by directory
 fn build_node_groups(
     node_order: &[NodeIndex],
     graph: &LineageGraph,
     project_dir: &Path,
+    config: Option<&GroupConfig>,
 ) -> Vec<NodeGroup> {
     // Use IndexMap to preserve insertion order (first-seen group = first group)
     let mut groups: IndexMap<String, Vec<NodeIndex>> = IndexMap::new();
 
     for &idx in node_order {
         let node = &graph[idx];
-        let key = group_key_for_node(node, project_dir);
+        let key = config
+            .and_then(|c| c.group_for(node))
+            .map(str::to_string)
+            .unwrap_or_else(|| group_key_for_node(node, project_dir));
         groups.entry(key).or_default().push(idx);
     }
 
@@ -798,36 +1833,12 @@ fn build_node_groups(
         .collect()
 }
 
-/// Compute all nodes on paths through a given node.
-/// BFS backward to find all ancestors, BFS forward to find all descendants,
-/// then union them together with the node itself.
+/// Compute all nodes on paths through a given node: its ancestors, its
+/// descendants and the node itself.
 pub fn compute_path_through(graph: &LineageGraph, node: NodeIndex) -> HashSet<NodeIndex> {
-    let mut result = HashSet::new();
+    let mut result = reachable(graph, node, Direction::Incoming);
+    result.extend(reachable(graph, node, Direction::Outgoing));
     result.insert(node);
-
-    // BFS backward (upstream / ancestors)
-    let mut queue = VecDeque::new();
-    queue.push_back(node);
-    while let Some(current) = queue.pop_front() {
-        for edge in graph.edges_directed(current, Direction::Incoming) {
-            let src = edge.source();
-            if result.insert(src) {
-                queue.push_back(src);
-            }
-        }
-    }
-
-    // BFS forward (downstream / descendants)
-    queue.push_back(node);
-    while let Some(current) = queue.pop_front() {
-        for edge in graph.edges_directed(current, Direction::Outgoing) {
-            let tgt = edge.target();
-            if result.insert(tgt) {
-                queue.push_back(tgt);
-            }
-        }
-    }
-
     result
 }
 
@@ -862,46 +1873,34 @@ mod tests {
             label: "raw.orders".into(),
             node_type: NodeType::Source,
             file_path: Some(PathBuf::from("models/schema.yml")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let stg = graph.add_node(NodeData {
             unique_id: "model.stg_orders".into(),
             label: "stg_orders".into(),
             node_type: NodeType::Model,
             file_path: Some(PathBuf::from("models/staging/stg_orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let mart = graph.add_node(NodeData {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: NodeType::Model,
             file_path: Some(PathBuf::from("models/marts/orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let exp = graph.add_node(NodeData {
             unique_id: "exposure.dashboard".into(),
             label: "dashboard".into(),
             node_type: NodeType::Exposure,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         graph.add_edge(
             src,
             stg,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -909,6 +1908,7 @@ mod tests {
             mart,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -916,6 +1916,7 @@ mod tests {
             exp,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
         graph
@@ -935,6 +1936,61 @@ mod tests {
         assert_eq!(app.zoom, 1.0);
     }
 
+    #[test]
+    fn test_selection_history() {
+        let mut app = test_app();
+        let first = app.selected_node.unwrap();
+        app.cycle_next_node();
+        let second = app.selected_node.unwrap();
+        app.cycle_next_node();
+        let third = app.selected_node.unwrap();
+        assert_eq!(app.back_history, vec![first, second]);
+
+        app.history_back();
+        assert_eq!(app.selected_node, Some(second));
+        app.history_back();
+        assert_eq!(app.selected_node, Some(first));
+        // Nothing earlier: stays put
+        app.history_back();
+        assert_eq!(app.selected_node, Some(first));
+
+        app.history_forward();
+        assert_eq!(app.selected_node, Some(second));
+
+        // A new jump drops what was ahead
+        app.select_node_no_center(third);
+        assert!(app.forward_history.is_empty());
+        app.history_forward();
+        assert_eq!(app.selected_node, Some(third));
+        app.history_back();
+        assert_eq!(app.selected_node, Some(second));
+    }
+
+    #[test]
+    fn test_selection_history_is_bounded() {
+        let mut app = test_app();
+        for _ in 0..MAX_HISTORY + 10 {
+            app.cycle_next_node();
+        }
+        assert_eq!(app.back_history.len(), MAX_HISTORY);
+    }
+
+    #[test]
+    fn test_search_records_one_jump() {
+        let mut app = test_app();
+        let start = app.selected_node;
+        app.open_search();
+        for c in "stg".chars() {
+            app.search_query.push(c);
+            app.update_search();
+        }
+        app.close_search();
+        assert_ne!(app.selected_node, start);
+        assert_eq!(app.back_history, vec![start.unwrap()]);
+        app.history_back();
+        assert_eq!(app.selected_node, start);
+    }
+
     #[test]
     fn test_cycle_next_node() {
         let mut app = test_app();
@@ -1049,6 +2105,23 @@ mod tests {
         assert!(app.selected_node.is_some());
     }
 
+    #[test]
+    fn test_search_anchored() {
+        let mut app = test_app();
+        app.search_query = "=ORDERS".into();
+        app.update_search();
+        assert_eq!(app.search_results.len(), 1);
+        assert_eq!(app.graph[app.search_results[0]].label, "orders");
+
+        app.search_query = "=model.stg_orders".into();
+        app.update_search();
+        assert_eq!(app.search_results.len(), 1);
+
+        app.search_query = "=order".into();
+        app.update_search();
+        assert!(app.search_results.is_empty());
+    }
+
     #[test]
     fn test_search_no_match() {
         let mut app = test_app();
@@ -1089,6 +2162,90 @@ mod tests {
         assert_eq!(app.zoom, 1.0);
     }
 
+    #[test]
+    fn test_zoom_to_fit_centers_small_graph_at_max_zoom() {
+        use crate::tui::graph_widget::layout_world_bounds;
+
+        let mut app = test_app();
+        app.last_graph_area = Some(ratatui::layout::Rect::new(0, 0, 400, 200));
+        app.zoom_to_fit();
+        assert_eq!(app.zoom, MAX_ZOOM);
+
+        let (x0, y0, x1, y1) =
+            layout_world_bounds(app.layout.positions.values(), app.zoom).unwrap();
+        // Equal margins on both sides, up to integer rounding
+        let left = x0 - app.viewport_x;
+        let right = app.viewport_x + 400 - x1;
+        assert!((left - right).abs() <= 1, "{} vs {}", left, right);
+        let top = y0 - app.viewport_y;
+        let bottom = app.viewport_y + 200 - y1;
+        assert!((top - bottom).abs() <= 1, "{} vs {}", top, bottom);
+    }
+
+    #[test]
+    fn test_zoom_to_fit_shrinks_to_fit_area() {
+        use crate::tui::graph_widget::layout_world_bounds;
+
+        let mut app = test_app();
+        let (x0, y0, x1, y1) = layout_world_bounds(app.layout.positions.values(), 1.0).unwrap();
+        let area = ratatui::layout::Rect::new(0, 0, (x1 - x0) as u16, (y1 - y0) as u16);
+        app.last_graph_area = Some(area);
+        app.zoom_to_fit();
+
+        assert!(app.zoom >= 1.0 && app.zoom < MAX_ZOOM);
+        let (x0, y0, x1, y1) =
+            layout_world_bounds(app.layout.positions.values(), app.zoom).unwrap();
+        assert!(x0 >= app.viewport_x && x1 <= app.viewport_x + area.width as i32);
+        assert!(y0 >= app.viewport_y && y1 <= app.viewport_y + area.height as i32);
+    }
+
+    #[test]
+    fn test_clamp_viewport_keeps_part_of_graph_visible() {
+        use crate::tui::graph_widget::layout_world_bounds;
+
+        let mut app = test_app();
+        let (x0, y0, x1, y1) =
+            layout_world_bounds(app.layout.positions.values(), app.zoom).unwrap();
+
+        // Without a graph area there is nothing to clamp against
+        app.viewport_x = 10_000;
+        app.clamp_viewport();
+        assert_eq!(app.viewport_x, 10_000);
+
+        app.last_graph_area = Some(ratatui::layout::Rect::new(0, 0, 80, 24));
+        app.clamp_viewport();
+        assert_eq!(app.viewport_x, x1 - MIN_VISIBLE_COLS);
+
+        app.viewport_x = -10_000;
+        app.viewport_y = -10_000;
+        app.clamp_viewport();
+        assert_eq!(app.viewport_x, x0 + MIN_VISIBLE_COLS - 80);
+        assert_eq!(app.viewport_y, y0 + MIN_VISIBLE_ROWS.min(y1 - y0) - 24);
+
+        // Overscroll short of losing the graph is left alone
+        app.viewport_x = x0 - 40;
+        app.viewport_y = y0 - 10;
+        app.clamp_viewport();
+        assert_eq!((app.viewport_x, app.viewport_y), (x0 - 40, y0 - 10));
+    }
+
+    #[test]
+    fn test_zoom_to_fit_huge_graph_uses_min_zoom() {
+        let mut app = test_app();
+        app.last_graph_area = Some(ratatui::layout::Rect::new(0, 0, 10, 3));
+        app.zoom_to_fit();
+        assert_eq!(app.zoom, MIN_ZOOM);
+    }
+
+    #[test]
+    fn test_zoom_to_fit_without_graph_area_is_noop() {
+        let mut app = test_app();
+        app.last_graph_area = None;
+        app.zoom = 2.0;
+        app.zoom_to_fit();
+        assert_eq!(app.zoom, 2.0);
+    }
+
     #[test]
     fn test_center_on_selected() {
         let mut app = test_app();
@@ -1144,6 +2301,62 @@ mod tests {
         app.toggle_group_collapse();
     }
 
+    fn selected_label(app: &App) -> &str {
+        &app.graph[app.selected_node.unwrap()].label
+    }
+
+    #[test]
+    fn test_type_ahead_jumps_to_prefix_match() {
+        let mut app = test_app();
+        app.focus_node_list();
+        assert_eq!(app.mode, AppMode::NodeList);
+        assert!(app.show_node_list);
+
+        let t0 = Instant::now();
+        app.type_ahead('S', t0);
+        assert_eq!(selected_label(&app), "stg_orders");
+        let row = app.node_list_state.selected().unwrap();
+        assert!(
+            matches!(app.node_list_entries[row], NodeListEntry::Node(idx) if app.graph[idx].label == "stg_orders")
+        );
+
+        // Extending the prefix keeps a row that still matches
+        app.type_ahead('t', t0 + Duration::from_millis(100));
+        assert_eq!(app.type_ahead, "St");
+        assert_eq!(selected_label(&app), "stg_orders");
+
+        // No match leaves the selection alone
+        app.type_ahead('x', t0 + Duration::from_millis(200));
+        assert_eq!(selected_label(&app), "stg_orders");
+    }
+
+    #[test]
+    fn test_type_ahead_resets_after_idle() {
+        let mut app = test_app();
+        app.focus_node_list();
+        let t0 = Instant::now();
+        app.type_ahead('s', t0);
+        app.type_ahead('d', t0 + TYPE_AHEAD_TIMEOUT + Duration::from_millis(1));
+        assert_eq!(app.type_ahead, "d");
+        assert_eq!(selected_label(&app), "dashboard");
+    }
+
+    #[test]
+    fn test_step_node_list_skips_group_headers() {
+        let mut app = test_app();
+        app.focus_node_list();
+        let first = app.node_list_state.selected().unwrap();
+        app.step_node_list(true);
+        let next = app.node_list_state.selected().unwrap();
+        assert!(next > first);
+        assert!(matches!(
+            app.node_list_entries[next],
+            NodeListEntry::Node(_)
+        ));
+        app.step_node_list(false);
+        assert_eq!(app.node_list_state.selected(), Some(first));
+    }
+
     #[test]
     fn test_sync_node_list_state_auto_expand() {
         let mut app = test_app();
@@ -1194,6 +2407,7 @@ mod tests {
         app.run_state = DbtRunState::Running {
             receiver: rx,
             output_lines: vec![],
+            cancel: CancelToken::default(),
         };
         assert!(app.is_run_in_progress());
     }
@@ -1216,6 +2430,7 @@ mod tests {
         app.run_state = DbtRunState::Running {
             receiver: rx,
             output_lines: vec![],
+            cancel: CancelToken::default(),
         };
         tx.send(super::super::runner::DbtRunMessage::OutputLine(
             "line1".into(),
@@ -1237,6 +2452,7 @@ mod tests {
         app.run_state = DbtRunState::Running {
             receiver: rx,
             output_lines: vec![],
+            cancel: CancelToken::default(),
         };
         tx.send(super::super::runner::DbtRunMessage::SpawnError(
             "failed".into(),
@@ -1255,6 +2471,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_drain_run_messages_timed_out() {
+        let mut app = test_app();
+        let (tx, rx) = mpsc::channel();
+        app.run_state = DbtRunState::Running {
+            receiver: rx,
+            output_lines: vec![],
+            cancel: CancelToken::default(),
+        };
+        tx.send(DbtRunMessage::TimedOut(Duration::from_secs(600)))
+            .unwrap();
+        app.drain_run_messages();
+        match &app.run_state {
+            DbtRunState::Finished {
+                success,
+                output_lines,
+            } => {
+                assert!(!success);
+                assert!(output_lines[0].contains("timing out (600s)"));
+            }
+            _ => panic!("Expected Finished"),
+        }
+    }
+
+    #[test]
+    fn test_cancel_dbt_run_idle_is_noop() {
+        let mut app = test_app();
+        app.cancel_dbt_run();
+        assert!(matches!(app.run_state, DbtRunState::Idle));
+    }
+
     #[test]
     fn test_drain_run_messages_disconnected() {
         let mut app = test_app();
@@ -1262,6 +2509,7 @@ mod tests {
         app.run_state = DbtRunState::Running {
             receiver: rx,
             output_lines: vec![],
+            cancel: CancelToken::default(),
         };
         drop(tx); // Disconnect
         app.drain_run_messages();
@@ -1305,31 +2553,19 @@ mod tests {
             unique_id: "source.raw.orders".into(),
             label: "raw.orders".into(),
             node_type: NodeType::Source,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let a = graph.add_node(NodeData {
             unique_id: "model.stg_a".into(),
             label: "stg_a".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let b = graph.add_node(NodeData {
             unique_id: "model.stg_b".into(),
             label: "stg_b".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         // src → a, src → b — a and b end up in the same layer
         graph.add_edge(
@@ -1337,6 +2573,7 @@ mod tests {
             a,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -1344,6 +2581,7 @@ mod tests {
             b,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph
@@ -1410,11 +2648,7 @@ mod tests {
             unique_id: "exposure.x".into(),
             label: "x".into(),
             node_type: NodeType::Exposure,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         assert_eq!(
             group_key_for_node(&node_exp, std::path::Path::new("/tmp")),
@@ -1425,11 +2659,7 @@ mod tests {
             unique_id: "model.x".into(),
             label: "x".into(),
             node_type: NodeType::Phantom,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         assert_eq!(
             group_key_for_node(&node_phantom, std::path::Path::new("/tmp")),
@@ -1442,10 +2672,7 @@ mod tests {
             label: "x".into(),
             node_type: NodeType::Model,
             file_path: Some(PathBuf::from("models/staging/x.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         assert_eq!(
             group_key_for_node(&node_model, std::path::Path::new("/tmp")),
@@ -1465,6 +2692,319 @@ mod tests {
         assert!(entries.iter().any(|e| matches!(e, NodeListEntry::Node(_))));
     }
 
+    fn find_node(app: &App, unique_id: &str) -> NodeIndex {
+        app.graph
+            .node_indices()
+            .find(|&i| app.graph[i].unique_id == unique_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_relayout_drops_filtered_nodes() {
+        let mut app = test_app();
+        let source = find_node(&app, "source.raw.orders");
+        let stg = find_node(&app, "model.stg_orders");
+        assert_eq!(app.selected_node, Some(source));
+
+        app.toggle_filter_node_type(NodeType::Source);
+        app.relayout();
+
+        assert_eq!(app.layout.positions.len(), 3);
+        assert!(!app.layout.positions.contains_key(&source));
+        assert_eq!(app.layout.positions[&stg], (0, 0));
+        assert_eq!(app.node_order.len(), 3);
+        // The hidden selection moves to the first visible node
+        assert_eq!(app.selected_node, Some(stg));
+        assert_eq!(
+            app.node_list_entries.len(),
+            app.node_groups.len() + app.node_order.len()
+        );
+
+        app.toggle_filter_node_type(NodeType::Source);
+        app.relayout();
+        assert_eq!(app.layout.positions.len(), 4);
+        assert_eq!(app.layout.positions[&stg], (1, 0));
+    }
+
+    #[test]
+    fn test_max_layer_width_wraps_and_survives_relayout() {
+        let app = App::new(make_fan_graph(), PathBuf::from("/tmp"), HashMap::new())
+            .with_max_layer_width(Some(1));
+        let a = find_node(&app, "model.stg_a");
+        let b = find_node(&app, "model.stg_b");
+        // stg_a and stg_b share a layer, now split into two columns of one
+        assert_eq!(app.layout.num_layers, 3);
+        assert_eq!(app.layout.max_layer_width, 1);
+        assert_ne!(app.layout.positions[&a].0, app.layout.positions[&b].0);
+        assert_eq!(app.node_order.len(), 3);
+
+        let mut app = app;
+        app.relayout();
+        assert_eq!(app.layout.num_layers, 3);
+    }
+
+    #[test]
+    fn test_relayout_keeps_visible_selection() {
+        let mut app = test_app();
+        let orders = find_node(&app, "model.orders");
+        app.selected_node = Some(orders);
+        app.toggle_filter_node_type(NodeType::Exposure);
+        app.relayout();
+        assert_eq!(app.selected_node, Some(orders));
+        assert_eq!(app.node_order[app.node_cycle_index], orders);
+    }
+
+    #[test]
+    fn test_relayout_is_debounced() {
+        let mut app = test_app();
+        let t0 = Instant::now();
+        assert!(!app.apply_pending_relayout(t0));
+
+        app.toggle_filter_node_type(NodeType::Source);
+        app.filters_changed(t0);
+        assert!(!app.apply_pending_relayout(t0 + Duration::from_millis(10)));
+        assert_eq!(app.layout.positions.len(), 4);
+
+        // A further change pushes the deadline back
+        app.filters_changed(t0 + Duration::from_millis(100));
+        assert!(!app.apply_pending_relayout(t0 + RELAYOUT_DEBOUNCE));
+        assert!(app.apply_pending_relayout(t0 + Duration::from_millis(100) + RELAYOUT_DEBOUNCE));
+        assert_eq!(app.layout.positions.len(), 3);
+        assert!(app.relayout_due.is_none());
+    }
+
+    #[test]
+    fn test_reload_keeps_selection_and_filters() {
+        let mut app = test_app();
+        app.reload();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("reload is not available")
+        );
+
+        let orders = find_node(&app, "model.orders");
+        app.selected_node = Some(orders);
+        app.toggle_filter_node_type(NodeType::Exposure);
+        app.relayout();
+        app.toggle_path_highlight();
+        assert!(!app.highlighted_path.is_empty());
+
+        // The rebuilt graph has the same nodes under different indices
+        app.graph_loader = Some(Box::new(|_| {
+            Ok(crate::graph::sort::sorted_graph(
+                &make_test_graph(),
+                NodeSort::Name,
+                None,
+            ))
+        }));
+        app.reload();
+
+        let selected = app.selected_node.unwrap();
+        assert_eq!(app.graph[selected].unique_id, "model.orders");
+        assert_eq!(app.layout.positions.len(), 3);
+        assert!(app.highlighted_path.is_empty());
+        assert!(app.impact_report.is_none());
+        assert!(app.full_redraw);
+        assert_eq!(app.status_message.as_deref(), Some("reloaded: 4 nodes"));
+    }
+
+    #[test]
+    fn test_reload_failure_keeps_old_graph() {
+        let mut app = test_app();
+        let selected = app.selected_node;
+        app.graph_loader = Some(Box::new(|_| {
+            anyhow::bail!("manifest.json is not valid JSON")
+        }));
+        app.reload();
+
+        assert_eq!(app.graph.node_count(), 4);
+        assert_eq!(app.selected_node, selected);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("reload failed: manifest.json is not valid JSON")
+        );
+    }
+
+    #[test]
+    fn test_copy_selected_node() {
+        let mut app = test_app();
+        app.selected_node = Some(find_node(&app, "model.orders"));
+        app.copy_selected_id();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("clipboard is not available")
+        );
+
+        let copied = std::rc::Rc::new(std::cell::RefCell::new(Vec::<String>::new()));
+        let sink = copied.clone();
+        app.clipboard = Some(Box::new(move |text| {
+            sink.borrow_mut().push(text.to_string());
+            Ok(())
+        }));
+        app.copy_selected_id();
+        assert_eq!(app.status_message.as_deref(), Some("copied model.orders"));
+        app.copy_selected_label();
+        assert_eq!(*copied.borrow(), ["model.orders", "orders"]);
+
+        // A headless terminal reports the error instead of crashing
+        app.clipboard = Some(Box::new(|_| anyhow::bail!("no display")));
+        app.copy_selected_id();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("copy failed: no display")
+        );
+    }
+
+    #[test]
+    fn test_switch_graph_source_marks_edges_only_one_source_has() {
+        let mut app = test_app();
+        app.switch_graph_source();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("switching source is not available")
+        );
+
+        // The manifest knows about a dependency the SQL parser missed
+        app.graph_loader = Some(Box::new(|source| {
+            let mut graph = make_test_graph();
+            if source == GraphSource::Manifest {
+                let src = graph.node_indices().next().unwrap();
+                let mart = graph.node_indices().nth(2).unwrap();
+                graph.add_edge(
+                    src,
+                    mart,
+                    EdgeData {
+                        edge_type: EdgeType::Source,
+                        snippet: None,
+                    },
+                );
+            }
+            Ok(graph)
+        }));
+        let orders = find_node(&app, "model.orders");
+        app.selected_node = Some(orders);
+
+        app.switch_graph_source();
+        assert_eq!(app.graph_source, GraphSource::Manifest);
+        assert_eq!(app.graph.edge_count(), 4);
+        assert_eq!(app.source_only_edges.len(), 1);
+        let edge = *app.source_only_edges.iter().next().unwrap();
+        let (source, target) = app.graph.edge_endpoints(edge).unwrap();
+        assert_eq!(app.graph[source].unique_id, "source.raw.orders");
+        assert_eq!(app.graph[target].unique_id, "model.orders");
+        assert_eq!(
+            app.graph[app.selected_node.unwrap()].unique_id,
+            "model.orders"
+        );
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("showing manifest graph: 1 edges only in manifest, 0 only in SQL")
+        );
+
+        app.switch_graph_source();
+        assert_eq!(app.graph_source, GraphSource::Sql);
+        assert!(app.source_only_edges.is_empty());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("showing SQL graph: 0 edges only in SQL, 1 only in manifest")
+        );
+
+        // A reload rebuilds from the source being shown and drops the marks
+        app.switch_graph_source();
+        app.reload();
+        assert_eq!(app.graph_source, GraphSource::Manifest);
+        assert_eq!(app.graph.edge_count(), 4);
+        assert!(app.source_only_edges.is_empty());
+    }
+
+    #[test]
+    fn test_switch_graph_source_failure_keeps_old_graph() {
+        let mut app = test_app();
+        app.graph_loader = Some(Box::new(|source| match source {
+            GraphSource::Manifest => anyhow::bail!("target/manifest.json not found"),
+            GraphSource::Sql => Ok(make_test_graph()),
+        }));
+        app.switch_graph_source();
+
+        assert_eq!(app.graph_source, GraphSource::Sql);
+        assert_eq!(app.graph.node_count(), 4);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("switch to manifest failed: target/manifest.json not found")
+        );
+    }
+
+    #[test]
+    fn test_graph_summary_counts_filtered_nodes() {
+        let mut app = test_app();
+        let summary = app.graph_summary();
+        assert_eq!(summary.nodes, 4);
+        assert_eq!(summary.edges, 3);
+        assert_eq!(
+            summary.by_type,
+            vec![
+                (NodeType::Model, 2),
+                (NodeType::Source, 1),
+                (NodeType::Exposure, 1)
+            ]
+        );
+        assert_eq!(summary.filtered_out, 0);
+
+        app.toggle_filter_node_type(NodeType::Model);
+        assert_eq!(app.graph_summary().filtered_out, 2);
+        app.filter_status = Some(FilterStatus::Errored);
+        assert_eq!(app.graph_summary().filtered_out, 4);
+    }
+
+    #[test]
+    fn test_with_sort_orders_nodes_within_groups() {
+        let before: Vec<String> = test_app()
+            .node_groups
+            .iter()
+            .map(|g| g.key.clone())
+            .collect();
+        let app = test_app().with_sort(NodeSort::Name);
+        let after: Vec<String> = app.node_groups.iter().map(|g| g.key.clone()).collect();
+        assert_eq!(before, after);
+        for group in &app.node_groups {
+            let labels: Vec<&str> = group
+                .nodes
+                .iter()
+                .map(|&i| app.graph[i].label.as_str())
+                .collect();
+            let mut sorted = labels.clone();
+            sorted.sort_unstable();
+            assert_eq!(labels, sorted);
+        }
+        assert_eq!(
+            app.node_list_entries.len(),
+            app.node_groups.len() + app.graph.node_count()
+        );
+    }
+
+    #[test]
+    fn test_with_groups_overrides_directory_groups() {
+        let config =
+            GroupConfig::parse("groups:\n  - name: Orders\n    models: ['stg_orders', 'orders']\n")
+                .unwrap();
+        let app = test_app().with_groups(&config);
+
+        let orders = app
+            .node_groups
+            .iter()
+            .find(|g| g.key == "Orders")
+            .expect("configured group");
+        assert_eq!(orders.nodes.len(), 2);
+        // Unmatched nodes keep their directory group
+        assert!(app.node_groups.iter().any(|g| g.key == "models"));
+        assert!(app.node_groups.iter().any(|g| g.key == "(exposures)"));
+        assert!(!app.node_groups.iter().any(|g| g.key == "models/staging"));
+        assert_eq!(
+            app.node_list_entries.len(),
+            app.node_groups.len() + app.graph.node_count()
+        );
+    }
+
     // ─── Filter tests ───
 
     #[test]
@@ -1640,11 +3180,7 @@ mod tests {
             unique_id: "model.isolated".into(),
             label: "isolated".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let path = compute_path_through(&graph, n);
         assert_eq!(path.len(), 1);
@@ -1672,37 +3208,26 @@ mod tests {
             unique_id: "model.a".into(),
             label: "a".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let b = graph.add_node(NodeData {
             unique_id: "model.b".into(),
             label: "b".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let c = graph.add_node(NodeData {
             unique_id: "model.c".into(),
             label: "c".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         graph.add_edge(
             a,
             c,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -1710,6 +3235,7 @@ mod tests {
             c,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
 
@@ -1766,6 +3292,48 @@ mod tests {
         assert_eq!(app.path_highlight_source, app.selected_node);
     }
 
+    #[test]
+    fn test_toggle_main_line_highlight() {
+        let mut graph = make_test_graph();
+        let orders = graph
+            .node_indices()
+            .find(|&i| graph[i].label == "orders")
+            .unwrap();
+        let side = graph.add_node(NodeData {
+            unique_id: "model.orders_audit".into(),
+            label: "orders_audit".into(),
+            node_type: NodeType::Model,
+            ..Default::default()
+        });
+        graph.add_edge(
+            orders,
+            side,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+        let mut app = App::new(graph, PathBuf::from("/tmp"), HashMap::new());
+        app.selected_node = Some(orders);
+
+        app.toggle_path_highlight();
+        assert!(app.highlighted_path.contains(&side));
+
+        // `m` switches to the main line, which leaves the side branch dark
+        app.toggle_main_line_highlight();
+        assert!(app.path_main_line);
+        assert!(!app.highlighted_path.contains(&side));
+        assert!(app.highlighted_path.contains(&orders));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("main line: raw.orders \u{2192} dashboard, 3 hops")
+        );
+
+        app.toggle_main_line_highlight();
+        assert!(app.highlighted_path.is_empty());
+        assert!(app.path_highlight_source.is_none());
+    }
+
     #[test]
     fn test_toggle_path_highlight_no_selection() {
         let mut app = test_app();
@@ -1806,6 +3374,222 @@ mod tests {
         assert!(!app.show_column_lineage);
     }
 
+    fn node_by_label(app: &App, label: &str) -> NodeIndex {
+        app.graph
+            .node_indices()
+            .find(|&i| app.graph[i].label == label)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_mark_path_endpoint_highlights_path() {
+        let mut app = test_app();
+        let src = node_by_label(&app, "raw.orders");
+        let mart = node_by_label(&app, "orders");
+
+        app.selected_node = Some(src);
+        app.mark_path_endpoint();
+        assert_eq!(app.path_anchor, Some(src));
+        assert!(app.highlighted_path.is_empty());
+        assert!(app.status_message.is_some());
+
+        app.selected_node = Some(mart);
+        app.mark_path_endpoint();
+        assert!(app.path_anchor.is_none());
+        // raw.orders -> stg_orders -> orders
+        assert_eq!(app.highlighted_path.len(), 3);
+        assert!(!app
+            .highlighted_path
+            .contains(&node_by_label(&app, "dashboard")));
+        assert_eq!(app.status_message.as_deref(), Some("path: 2 hops"));
+    }
+
+    #[test]
+    fn test_mark_path_endpoint_reverse_order() {
+        let mut app = test_app();
+        app.selected_node = Some(node_by_label(&app, "dashboard"));
+        app.mark_path_endpoint();
+        app.selected_node = Some(node_by_label(&app, "stg_orders"));
+        app.mark_path_endpoint();
+        assert_eq!(app.highlighted_path.len(), 3);
+    }
+
+    #[test]
+    fn test_mark_path_endpoint_no_path() {
+        let mut graph = make_test_graph();
+        graph.add_node(NodeData {
+            unique_id: "model.island".into(),
+            label: "island".into(),
+            node_type: NodeType::Model,
+            ..Default::default()
+        });
+        let mut app = App::new(graph, PathBuf::from("/tmp"), HashMap::new());
+        app.selected_node = Some(node_by_label(&app, "orders"));
+        app.mark_path_endpoint();
+        app.selected_node = Some(node_by_label(&app, "island"));
+        app.mark_path_endpoint();
+        assert!(app.highlighted_path.is_empty());
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .starts_with("no path between"));
+    }
+
+    #[test]
+    fn test_mark_path_endpoint_same_node_cancels() {
+        let mut app = test_app();
+        app.mark_path_endpoint();
+        assert!(app.path_anchor.is_some());
+        app.mark_path_endpoint();
+        assert!(app.path_anchor.is_none());
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn test_explain_next_edge_cycles_incoming_edges() {
+        let mut graph = make_test_graph();
+        let stg = graph
+            .node_indices()
+            .find(|&i| graph[i].label == "stg_orders")
+            .unwrap();
+        let seed = graph.add_node(NodeData {
+            unique_id: "seed.countries".into(),
+            label: "countries".into(),
+            node_type: NodeType::Seed,
+            ..Default::default()
+        });
+        graph.add_edge(
+            seed,
+            stg,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: Some(EdgeSnippet {
+                    line: 3,
+                    text: "join {{ ref('countries') }} using (country_id)".into(),
+                }),
+            },
+        );
+        let mut app = App::new(graph, PathBuf::from("/tmp"), HashMap::new());
+        app.selected_node = Some(stg);
+
+        app.explain_next_edge();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("countries \u{2192} stg_orders (ref) line 3: join {{ ref('countries') }} using (country_id)")
+        );
+        let first = app.active_edge();
+        assert!(first.is_some());
+
+        app.explain_next_edge();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("raw.orders \u{2192} stg_orders (source) found in compiled SQL")
+        );
+        assert_ne!(app.active_edge(), first);
+
+        app.explain_next_edge();
+        assert_eq!(app.active_edge(), first);
+
+        // Moving the selection away drops the highlight
+        app.selected_node = Some(node_by_label(&app, "orders"));
+        assert!(app.active_edge().is_none());
+    }
+
+    #[test]
+    fn test_explain_edge_from_manifest_and_without_upstream() {
+        let mut app = test_app();
+        app.graph_source = GraphSource::Manifest;
+        app.selected_node = Some(node_by_label(&app, "dashboard"));
+        app.explain_next_edge();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("orders \u{2192} dashboard (exposure) from manifest")
+        );
+
+        app.selected_node = Some(node_by_label(&app, "raw.orders"));
+        app.explain_next_edge();
+        assert!(app.selected_edge.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("raw.orders has no upstream edges")
+        );
+    }
+
+    fn app_with_column_lineage() -> App {
+        use crate::parser::column_lineage::{ColumnConfidence, ColumnEdge};
+        let edge = |src: &str, src_col: &str, tgt: &str, tgt_col: &str| ColumnEdge {
+            source_node: src.into(),
+            source_column: src_col.into(),
+            target_node: tgt.into(),
+            target_column: tgt_col.into(),
+            confidence: ColumnConfidence::Direct,
+        };
+        let mut app = test_app();
+        app.column_lineage = ColumnLineage {
+            edges: vec![
+                edge("source.raw.orders", "id", "model.stg_orders", "order_id"),
+                edge("model.stg_orders", "order_id", "model.orders", "order_id"),
+                edge("model.stg_orders", "amount", "model.orders", "amount"),
+            ],
+        };
+        app.show_column_lineage = true;
+        let mart = app
+            .graph
+            .node_indices()
+            .find(|&i| app.graph[i].label == "orders")
+            .unwrap();
+        app.selected_node = Some(mart);
+        app
+    }
+
+    #[test]
+    fn test_selectable_columns_fallback_to_lineage() {
+        let app = app_with_column_lineage();
+        let mart = app.selected_node.unwrap();
+        assert_eq!(app.selectable_columns(mart), vec!["order_id", "amount"]);
+    }
+
+    #[test]
+    fn test_select_column_traces_chain() {
+        let mut app = app_with_column_lineage();
+        app.select_next_column();
+        assert_eq!(app.selected_column_name().as_deref(), Some("order_id"));
+        // source -> stg -> orders
+        assert_eq!(app.column_trace_nodes.len(), 3);
+        assert!(app.active_highlight().is_some());
+
+        app.select_next_column();
+        assert_eq!(app.selected_column_name().as_deref(), Some("amount"));
+        assert_eq!(app.column_trace_nodes.len(), 2);
+
+        // Wraps around backwards
+        app.select_prev_column();
+        app.select_prev_column();
+        assert_eq!(app.selected_column_name().as_deref(), Some("amount"));
+    }
+
+    #[test]
+    fn test_column_selection_ignored_for_other_node() {
+        let mut app = app_with_column_lineage();
+        app.select_next_column();
+        app.cycle_next_node();
+        assert!(app.selected_column_name().is_none());
+        assert!(app.active_highlight().is_none());
+    }
+
+    #[test]
+    fn test_column_selection_cleared_when_lineage_hidden() {
+        let mut app = app_with_column_lineage();
+        app.select_next_column();
+        app.toggle_column_lineage();
+        assert!(app.selected_column.is_none());
+        assert!(app.column_trace_nodes.is_empty());
+        // No-op while hidden
+        app.select_next_column();
+        assert!(app.selected_column.is_none());
+    }
+
     #[test]
     fn test_new_app_fields_initialized() {
         let app = test_app();
@@ -1825,10 +3609,7 @@ mod tests {
             label: "orders".into(),
             node_type: NodeType::Model,
             file_path: Some(file_path),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         let key = group_key_for_node(&node, &project_dir);
         assert_eq!(key, "models");
@@ -1841,11 +3622,7 @@ mod tests {
             unique_id: "exposure.dash".into(),
             label: "dash".into(),
             node_type: NodeType::Exposure,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         };
         assert_eq!(group_key_for_node(&node, &project_dir), "(exposures)");
     }
@@ -1859,12 +3636,9 @@ mod tests {
             label: "a".into(),
             node_type: NodeType::Model,
             file_path: Some(std::path::PathBuf::from("a.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
-        let groups = build_node_groups(&[idx], &graph, std::path::Path::new("/project"));
+        let groups = build_node_groups(&[idx], &graph, std::path::Path::new("/project"), None);
         // File "a.sql" has no parent dir, so group key is ""
         assert_eq!(groups[0].label, "(root)");
     }
@@ -1898,6 +3672,32 @@ mod tests {
         // Main goal: exercise the code path without panicking
     }
 
+    #[test]
+    fn test_reload_run_status_reads_configured_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("prod_run_results.json");
+        std::fs::write(
+            &path,
+            r#"{"results": [{"unique_id": "model.stg_orders", "status": "error", "message": "boom", "timing": []}]}"#,
+        )
+        .unwrap();
+
+        let mut app = App::new(make_test_graph(), tmp.path().to_path_buf(), HashMap::new());
+        assert_eq!(
+            app.run_results_path,
+            tmp.path().join("target/run_results.json")
+        );
+        app.reload_run_status();
+        assert!(app.run_status.is_empty());
+
+        app.run_results_path = path;
+        app.reload_run_status();
+        assert!(matches!(
+            app.node_run_status("model.stg_orders"),
+            RunStatus::Error { .. }
+        ));
+    }
+
     #[test]
     fn test_navigate_left_picks_closest_node() {
         // Covers lines 289-290: "update best" branch in navigate_left
@@ -1907,37 +3707,26 @@ mod tests {
             unique_id: "source.a".into(),
             label: "a".into(),
             node_type: NodeType::Source,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let s2 = graph.add_node(NodeData {
             unique_id: "source.b".into(),
             label: "b".into(),
             node_type: NodeType::Source,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let m = graph.add_node(NodeData {
             unique_id: "model.c".into(),
             label: "c".into(),
             node_type: NodeType::Model,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         graph.add_edge(
             s1,
             m,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -1945,6 +3734,7 @@ mod tests {
             m,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
 
@@ -1957,4 +3747,128 @@ mod tests {
             "Should select a source node"
         );
     }
+
+    #[test]
+    fn test_open_sql_view_reads_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("models/marts");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("orders.sql"),
+            "select *\nfrom {{ ref('stg_orders') }}\n",
+        )
+        .unwrap();
+
+        let mut app = App::new(make_test_graph(), tmp.path().to_path_buf(), HashMap::new());
+        app.selected_node = app
+            .graph
+            .node_indices()
+            .find(|&i| app.graph[i].label == "orders");
+        app.sql_view_scroll = 5;
+        app.open_sql_view();
+
+        assert_eq!(app.mode, AppMode::SqlView);
+        assert_eq!(app.sql_view_scroll, 0);
+        let view = app.sql_view.as_ref().unwrap();
+        assert_eq!(view.title, "models/marts/orders.sql");
+        assert_eq!(view.lines, vec!["select *", "from {{ ref('stg_orders') }}"]);
+        assert_eq!(app.sql_view_len(), 2);
+    }
+
+    #[test]
+    fn test_open_sql_view_placeholder_without_file() {
+        let mut app = test_app();
+        app.selected_node = app
+            .graph
+            .node_indices()
+            .find(|&i| app.graph[i].label == "dashboard");
+        app.open_sql_view();
+        assert_eq!(app.mode, AppMode::SqlView);
+        let view = app.sql_view.as_ref().unwrap();
+        assert_eq!(view.title, "dashboard");
+        assert!(view.lines[0].contains("no source file"));
+
+        // Missing file on disk: shows the read error instead of failing
+        app.selected_node = app
+            .graph
+            .node_indices()
+            .find(|&i| app.graph[i].label == "orders");
+        app.open_sql_view();
+        assert!(app.sql_view.as_ref().unwrap().lines[0].starts_with("(could not read"));
+    }
+
+    #[test]
+    fn test_open_sql_view_without_selection() {
+        let mut app = test_app();
+        app.selected_node = None;
+        app.open_sql_view();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.sql_view.is_none());
+        assert_eq!(app.sql_view_len(), 0);
+    }
+
+    #[test]
+    fn test_phantom_list_jumps_to_phantom() {
+        let mut graph = make_test_graph();
+        let orders = graph
+            .node_indices()
+            .find(|&i| graph[i].label == "orders")
+            .unwrap();
+        let ghost = graph.add_node(NodeData {
+            unique_id: "model.stg_ordres".into(),
+            label: "stg_ordres".into(),
+            node_type: NodeType::Phantom,
+            ..Default::default()
+        });
+        graph.add_edge(
+            ghost,
+            orders,
+            EdgeData {
+                edge_type: EdgeType::Ref,
+                snippet: None,
+            },
+        );
+        let mut app = App::new(graph, PathBuf::from("/tmp"), HashMap::new());
+        assert!(app.has_phantoms());
+
+        app.open_phantom_list();
+        assert_eq!(app.mode, AppMode::PhantomList);
+        assert_eq!(app.phantom_list.len(), 1);
+        assert_eq!(
+            app.phantom_list[0].referenced_by,
+            vec!["models/marts/orders.sql"]
+        );
+        app.step_phantom_list(true);
+        app.step_phantom_list(false);
+        assert_eq!(app.phantom_cursor, 0);
+
+        let before = app.selected_node;
+        app.jump_to_phantom();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.selected_node, Some(ghost));
+        assert_eq!(app.back_history.last().copied(), before);
+
+        // Hidden by the type filter: stays put and says why
+        app.open_phantom_list();
+        app.filter_node_types.remove(&NodeType::Phantom);
+        app.selected_node = Some(orders);
+        app.jump_to_phantom();
+        assert_eq!(app.selected_node, Some(orders));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("stg_ordres is hidden by the filters")
+        );
+    }
+
+    #[test]
+    fn test_phantom_list_without_phantoms() {
+        let mut app = test_app();
+        assert!(!app.has_phantoms());
+        app.open_phantom_list();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.status_message.as_deref(), Some("no phantom nodes"));
+        app.step_phantom_list(true);
+        app.jump_to_phantom();
+        assert_eq!(app.mode, AppMode::Normal);
+    }
 }
@@ -1,13 +1,16 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::Rect;
 
-use super::app::{App, AppMode, DbtRunState, DragState, FilterStatus, NodeListEntry};
+use super::app::{
+    App, AppMode, DbtRunState, DragState, FilterStatus, NodeListEntry, MAX_ZOOM, MIN_ZOOM,
+};
 use super::graph_widget::hit_test_node;
 use super::runner::{detect_use_uv, DbtCommand, DbtRunRequest, SelectionScope};
 
 const PAN_AMOUNT: i32 = 3;
 const ZOOM_STEP: f64 = 0.1;
 const MENU_ITEM_COUNT: u16 = 5;
+const SQL_VIEW_PAGE: usize = 20;
 
 /// Check if a mouse position is on a menu item row. Returns the item index (0-4).
 /// `items_y_offset` is the offset from the popup top to the first item row
@@ -54,6 +57,19 @@ fn make_run_request_for_item(app: &App, item: usize) -> Option<DbtRunRequest> {
     })
 }
 
+/// Command and scope a run menu key previews when typed in upper case
+/// (`R`, `U`, `D`, `A`, `T`)
+fn preview_choice(code: KeyCode) -> Option<(DbtCommand, SelectionScope)> {
+    match code {
+        KeyCode::Char('R') => Some((DbtCommand::Run, SelectionScope::Single)),
+        KeyCode::Char('U') => Some((DbtCommand::Run, SelectionScope::WithUpstream)),
+        KeyCode::Char('D') => Some((DbtCommand::Run, SelectionScope::WithDownstream)),
+        KeyCode::Char('A') => Some((DbtCommand::Run, SelectionScope::FullLineage)),
+        KeyCode::Char('T') => Some((DbtCommand::Test, SelectionScope::Single)),
+        _ => None,
+    }
+}
+
 /// Clear all menu overlay state.
 fn clear_menu_state(app: &mut App) {
     app.context_menu_pos = None;
@@ -91,8 +107,12 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
         AppMode::RunMenu => handle_run_menu_mode(app, key),
         AppMode::ContextMenu => handle_context_menu_mode(app, key),
         AppMode::RunConfirm => handle_run_confirm_mode(app, key),
+        AppMode::CommandPreview => handle_command_preview_mode(app, key),
         AppMode::RunOutput => handle_run_output_mode(app, key),
         AppMode::Filter => handle_filter_mode(app, key),
+        AppMode::SqlView => handle_sql_view_mode(app, key),
+        AppMode::NodeList => handle_node_list_mode(app, key),
+        AppMode::PhantomList => handle_phantom_list_mode(app, key),
     }
 }
 
@@ -105,6 +125,7 @@ fn handle_shift_pan(app: &mut App, code: KeyCode) -> Option<bool> {
         KeyCode::Char('L') => app.viewport_x += PAN_AMOUNT,
         _ => return None,
     }
+    app.clamp_viewport();
     Some(false)
 }
 
@@ -116,16 +137,23 @@ fn handle_normal_key(app: &mut App, code: KeyCode) -> bool {
         KeyCode::Char('l') | KeyCode::Right => app.navigate_right(),
         KeyCode::Char('k') | KeyCode::Up => app.navigate_up(),
         KeyCode::Char('j') | KeyCode::Down => app.navigate_down(),
-        KeyCode::Char('+') | KeyCode::Char('=') => app.zoom = (app.zoom + ZOOM_STEP).min(3.0),
-        KeyCode::Char('-') => app.zoom = (app.zoom - ZOOM_STEP).max(0.3),
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            app.zoom = (app.zoom + ZOOM_STEP).min(MAX_ZOOM);
+            app.clamp_viewport();
+        }
+        KeyCode::Char('-') => {
+            app.zoom = (app.zoom - ZOOM_STEP).max(MIN_ZOOM);
+            app.clamp_viewport();
+        }
+        KeyCode::Char('z') => app.zoom_to_fit(),
         KeyCode::Tab => app.cycle_next_node(),
         KeyCode::BackTab => app.cycle_prev_node(),
-        KeyCode::Char('/') => {
-            app.mode = AppMode::Search;
-            app.search_query.clear();
-        }
+        KeyCode::Char('/') => app.open_search(),
         KeyCode::Char('r') => app.reset_view(),
+        KeyCode::Char('R') => app.reload(),
+        KeyCode::Char('M') => app.switch_graph_source(),
         KeyCode::Char('n') => app.show_node_list = !app.show_node_list,
+        KeyCode::Char('N') => app.focus_node_list(),
         KeyCode::Char('c') if app.show_node_list => app.toggle_group_collapse(),
         KeyCode::Char('x') if app.selected_node.is_some() && !app.is_run_in_progress() => {
             app.menu_hover_index = None;
@@ -133,8 +161,19 @@ fn handle_normal_key(app: &mut App, code: KeyCode) -> bool {
         }
         KeyCode::Char('o') if app.has_run_output() => app.mode = AppMode::RunOutput,
         KeyCode::Char('f') => app.mode = AppMode::Filter,
+        KeyCode::Char('v') => app.open_sql_view(),
+        KeyCode::Char('u') => app.open_phantom_list(),
+        KeyCode::Char('e') => app.explain_next_edge(),
+        KeyCode::Char('y') => app.copy_selected_id(),
+        KeyCode::Char('Y') => app.copy_selected_label(),
         KeyCode::Char('p') => app.toggle_path_highlight(),
+        KeyCode::Char('m') => app.toggle_main_line_highlight(),
+        KeyCode::Char('P') => app.mark_path_endpoint(),
         KeyCode::Char('C') => app.toggle_column_lineage(),
+        KeyCode::Char(']') if app.show_column_lineage => app.select_next_column(),
+        KeyCode::Char('[') if app.show_column_lineage => app.select_prev_column(),
+        KeyCode::Backspace | KeyCode::Char('<') => app.history_back(),
+        KeyCode::Char('>') => app.history_forward(),
         _ => {}
     }
     false
@@ -152,20 +191,34 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> bool {
     handle_normal_key(app, key.code)
 }
 
-fn handle_search_mode(app: &mut App, key: KeyEvent) -> bool {
-    // Ctrl+C exits search
+fn handle_node_list_mode(app: &mut App, key: KeyEvent) -> bool {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
         app.mode = AppMode::Normal;
         return false;
     }
 
     match key.code {
-        KeyCode::Esc => {
-            app.mode = AppMode::Normal;
-        }
-        KeyCode::Enter => {
-            app.mode = AppMode::Normal;
+        KeyCode::Esc | KeyCode::Enter => app.mode = AppMode::Normal,
+        KeyCode::Up => app.step_node_list(false),
+        KeyCode::Down => app.step_node_list(true),
+        KeyCode::Char(c) if c.is_alphanumeric() || c == '_' => {
+            app.type_ahead(c, std::time::Instant::now())
         }
+        _ => {}
+    }
+
+    false
+}
+
+fn handle_search_mode(app: &mut App, key: KeyEvent) -> bool {
+    // Ctrl+C exits search
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.close_search();
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter => app.close_search(),
         KeyCode::Backspace => {
             app.search_query.pop();
             app.update_search();
@@ -209,6 +262,12 @@ fn handle_run_menu_mode(app: &mut App, key: KeyEvent) -> bool {
         use_uv,
     };
 
+    if let Some((command, scope)) = preview_choice(key.code) {
+        app.pending_run = Some(make_request(command, scope));
+        app.mode = AppMode::CommandPreview;
+        return false;
+    }
+
     match key.code {
         KeyCode::Char('r') => {
             app.pending_run = Some(make_request(DbtCommand::Run, SelectionScope::Single));
@@ -270,6 +329,13 @@ fn handle_context_menu_mode(app: &mut App, key: KeyEvent) -> bool {
         use_uv,
     };
 
+    if let Some((command, scope)) = preview_choice(key.code) {
+        app.pending_run = Some(make_request(command, scope));
+        app.context_menu_pos = None;
+        app.mode = AppMode::CommandPreview;
+        return false;
+    }
+
     match key.code {
         KeyCode::Char('r') => {
             app.pending_run = Some(make_request(DbtCommand::Run, SelectionScope::Single));
@@ -330,6 +396,27 @@ fn handle_run_confirm_mode(app: &mut App, key: KeyEvent) -> bool {
     false
 }
 
+/// The command preview never runs anything: `y` copies the command and any
+/// other close key drops it
+fn handle_command_preview_mode(app: &mut App, key: KeyEvent) -> bool {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.pending_run = None;
+        app.mode = AppMode::Normal;
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Char('y') => app.copy_pending_command(),
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+            app.pending_run = None;
+            app.mode = AppMode::Normal;
+        }
+        _ => {}
+    }
+
+    false
+}
+
 /// Check if a mouse position is within a Rect area
 fn is_within(area: Rect, column: u16, row: u16) -> bool {
     column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
@@ -412,7 +499,7 @@ fn handle_node_list_click(app: &mut App, column: u16, row: u16) -> bool {
         match app.node_list_entries[row_in_list] {
             NodeListEntry::GroupHeader(gi) => app.toggle_group_collapse_by_index(gi),
             NodeListEntry::Node(idx) => {
-                app.selected_node = Some(idx);
+                app.select_node_no_center(idx);
                 app.node_list_state.select(Some(row_in_list));
                 app.center_on_selected();
             }
@@ -450,9 +537,7 @@ fn handle_graph_right_click(app: &mut App, column: u16, row: u16) {
         return;
     }
     if let Some(node_idx) = hit_test_node(app, column, row) {
-        app.selected_node = Some(node_idx);
-        app.sync_cycle_index();
-        app.sync_node_list_state();
+        app.select_node_no_center(node_idx);
         app.context_menu_pos = Some((column, row));
         app.menu_hover_index = None;
         app.mode = AppMode::ContextMenu;
@@ -468,10 +553,11 @@ fn handle_graph_scroll(app: &mut App, column: u16, row: u16, zoom_in: bool) {
         return;
     }
     if zoom_in {
-        app.zoom = (app.zoom + ZOOM_STEP).min(3.0);
+        app.zoom = (app.zoom + ZOOM_STEP).min(MAX_ZOOM);
     } else {
-        app.zoom = (app.zoom - ZOOM_STEP).max(0.3);
+        app.zoom = (app.zoom - ZOOM_STEP).max(MIN_ZOOM);
     }
+    app.clamp_viewport();
 }
 
 /// Handle a mouse event. Returns true if the app should quit (never does).
@@ -491,15 +577,16 @@ fn handle_mouse_normal(app: &mut App, mouse: MouseEvent) {
         MouseEventKind::Down(MouseButton::Right) => {
             handle_graph_right_click(app, mouse.column, mouse.row);
         }
-        MouseEventKind::Down(MouseButton::Left) => {
-            if !handle_node_list_click(app, mouse.column, mouse.row) {
-                handle_graph_left_click(app, mouse.column, mouse.row);
-            }
+        MouseEventKind::Down(MouseButton::Left)
+            if !handle_node_list_click(app, mouse.column, mouse.row) =>
+        {
+            handle_graph_left_click(app, mouse.column, mouse.row);
         }
         MouseEventKind::Drag(MouseButton::Left) => {
             if let Some(ref drag) = app.drag_state {
                 app.viewport_x = drag.viewport_x0 - (mouse.column as i32 - drag.start_x as i32);
                 app.viewport_y = drag.viewport_y0 - (mouse.row as i32 - drag.start_y as i32);
+                app.clamp_viewport();
             }
         }
         MouseEventKind::Up(MouseButton::Left) => {
@@ -537,6 +624,7 @@ fn handle_run_output_mode(app: &mut App, key: KeyEvent) -> bool {
             };
             app.run_output_scroll = total_lines.saturating_sub(1);
         }
+        KeyCode::Char('x') => app.cancel_dbt_run(),
         KeyCode::Esc | KeyCode::Char('q') => {
             app.mode = AppMode::Normal;
         }
@@ -546,6 +634,55 @@ fn handle_run_output_mode(app: &mut App, key: KeyEvent) -> bool {
     false
 }
 
+fn handle_sql_view_mode(app: &mut App, key: KeyEvent) -> bool {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.mode = AppMode::Normal;
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.sql_view_scroll = app.sql_view_scroll.saturating_add(1);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.sql_view_scroll = app.sql_view_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown | KeyCode::Char(' ') => {
+            app.sql_view_scroll = app.sql_view_scroll.saturating_add(SQL_VIEW_PAGE);
+        }
+        KeyCode::PageUp => {
+            app.sql_view_scroll = app.sql_view_scroll.saturating_sub(SQL_VIEW_PAGE);
+        }
+        KeyCode::Char('g') => app.sql_view_scroll = 0,
+        KeyCode::Char('G') => app.sql_view_scroll = app.sql_view_len().saturating_sub(1),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => {
+            app.mode = AppMode::Normal;
+        }
+        _ => {}
+    }
+
+    false
+}
+
+fn handle_phantom_list_mode(app: &mut App, key: KeyEvent) -> bool {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.mode = AppMode::Normal;
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => app.step_phantom_list(true),
+        KeyCode::Char('k') | KeyCode::Up => app.step_phantom_list(false),
+        KeyCode::Enter => app.jump_to_phantom(),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('u') => {
+            app.mode = AppMode::Normal;
+        }
+        _ => {}
+    }
+
+    false
+}
+
 fn handle_filter_mode(app: &mut App, key: KeyEvent) -> bool {
     use crate::graph::types::NodeType;
 
@@ -574,10 +711,12 @@ fn handle_filter_mode(app: &mut App, key: KeyEvent) -> bool {
         }
         KeyCode::Esc => {
             app.mode = AppMode::Normal;
+            return false;
         }
-        _ => {}
+        _ => return false,
     }
 
+    app.filters_changed(std::time::Instant::now());
     false
 }
 
@@ -586,6 +725,7 @@ mod tests {
     use super::*;
     use crate::graph::types::*;
     use crate::parser::artifacts::RunStatusMap;
+    use crate::tui::app::GraphSource;
     use std::collections::HashMap;
     use std::path::PathBuf;
 
@@ -596,46 +736,34 @@ mod tests {
             label: "raw.orders".into(),
             node_type: NodeType::Source,
             file_path: Some(PathBuf::from("models/schema.yml")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let stg = graph.add_node(NodeData {
             unique_id: "model.stg_orders".into(),
             label: "stg_orders".into(),
             node_type: NodeType::Model,
             file_path: Some(PathBuf::from("models/staging/stg_orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let mart = graph.add_node(NodeData {
             unique_id: "model.orders".into(),
             label: "orders".into(),
             node_type: NodeType::Model,
             file_path: Some(PathBuf::from("models/marts/orders.sql")),
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         let exp = graph.add_node(NodeData {
             unique_id: "exposure.dashboard".into(),
             label: "dashboard".into(),
             node_type: NodeType::Exposure,
-            file_path: None,
-            description: None,
-            materialization: None,
-            tags: vec![],
-            columns: vec![],
+            ..Default::default()
         });
         graph.add_edge(
             src,
             stg,
             EdgeData {
                 edge_type: EdgeType::Source,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -643,6 +771,7 @@ mod tests {
             mart,
             EdgeData {
                 edge_type: EdgeType::Ref,
+                snippet: None,
             },
         );
         graph.add_edge(
@@ -650,6 +779,7 @@ mod tests {
             exp,
             EdgeData {
                 edge_type: EdgeType::Exposure,
+                snippet: None,
             },
         );
         graph
@@ -733,6 +863,14 @@ mod tests {
         assert!(app.zoom > z);
     }
 
+    #[test]
+    fn test_normal_z_zooms_to_fit() {
+        let mut app = test_app();
+        app.last_graph_area = Some(Rect::new(0, 0, 400, 200));
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('z'))));
+        assert_eq!(app.zoom, MAX_ZOOM);
+    }
+
     #[test]
     fn test_normal_tab_cycle() {
         let mut app = test_app();
@@ -751,6 +889,29 @@ mod tests {
         assert!(app.search_query.is_empty());
     }
 
+    #[test]
+    fn test_normal_history_keys() {
+        let mut app = test_app();
+        let first = app.selected_node;
+        assert!(!handle_key_event(&mut app, key(KeyCode::Tab)));
+        let second = app.selected_node;
+        assert!(!handle_key_event(&mut app, key(KeyCode::Backspace)));
+        assert_eq!(app.selected_node, first);
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('>'))));
+        assert_eq!(app.selected_node, second);
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('<'))));
+        assert_eq!(app.selected_node, first);
+
+        // With column lineage on, the brackets pick columns and leave the
+        // history keys alone
+        app.show_column_lineage = true;
+        app.graph[first.unwrap()].columns = vec!["a".into()];
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char(']'))));
+        assert_eq!(app.selected_node, first);
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('>'))));
+        assert_eq!(app.selected_node, second);
+    }
+
     #[test]
     fn test_normal_r_reset() {
         let mut app = test_app();
@@ -823,6 +984,20 @@ mod tests {
         assert_eq!(app.mode, AppMode::Normal);
     }
 
+    #[test]
+    fn test_normal_brackets_need_column_lineage() {
+        let mut app = test_app();
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char(']'))));
+        assert!(app.selected_column.is_none());
+
+        app.show_column_lineage = true;
+        app.graph[app.selected_node.unwrap()].columns = vec!["a".into(), "b".into()];
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char(']'))));
+        assert_eq!(app.selected_column_name().as_deref(), Some("a"));
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('['))));
+        assert_eq!(app.selected_column_name().as_deref(), Some("b"));
+    }
+
     // ─── Search mode tests ───
 
     #[test]
@@ -1041,6 +1216,59 @@ mod tests {
         assert_eq!(app.pending_run.as_ref().unwrap().command, DbtCommand::Test);
     }
 
+    #[test]
+    fn test_menu_shift_key_previews_command() {
+        let mut app = test_app();
+        app.mode = AppMode::RunMenu;
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('U'))));
+        assert_eq!(app.mode, AppMode::CommandPreview);
+        assert_eq!(
+            app.pending_run.as_ref().unwrap().scope,
+            SelectionScope::WithUpstream
+        );
+        assert!(!app.is_run_in_progress());
+
+        let mut app = test_app();
+        app.mode = AppMode::ContextMenu;
+        app.context_menu_pos = Some((10, 10));
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('T'))));
+        assert_eq!(app.mode, AppMode::CommandPreview);
+        assert_eq!(app.pending_run.as_ref().unwrap().command, DbtCommand::Test);
+        assert!(app.context_menu_pos.is_none());
+    }
+
+    #[test]
+    fn test_command_preview_copies_and_closes() {
+        let mut app = test_app();
+        app.mode = AppMode::CommandPreview;
+        app.pending_run = Some(DbtRunRequest {
+            command: DbtCommand::Run,
+            scope: SelectionScope::WithDownstream,
+            model_name: "orders".into(),
+            project_dir: PathBuf::from("/tmp"),
+            use_uv: false,
+        });
+        let copied = std::rc::Rc::new(std::cell::RefCell::new(Vec::<String>::new()));
+        let sink = copied.clone();
+        app.clipboard = Some(Box::new(move |text| {
+            sink.borrow_mut().push(text.to_string());
+            Ok(())
+        }));
+
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('y'))));
+        assert_eq!(
+            *copied.borrow(),
+            ["dbt run --select orders+ --project-dir /tmp"]
+        );
+        assert_eq!(app.mode, AppMode::CommandPreview);
+
+        // Enter closes the preview rather than running the command
+        assert!(!handle_key_event(&mut app, key(KeyCode::Enter)));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.pending_run.is_none());
+        assert!(!app.is_run_in_progress());
+    }
+
     // ─── RunConfirm mode tests ───
 
     #[test]
@@ -1233,6 +1461,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pan_and_drag_stop_before_graph_leaves_view() {
+        let mut app = test_app();
+        app.last_graph_area = Some(Rect::new(0, 0, 80, 24));
+
+        for _ in 0..500 {
+            handle_key_event(&mut app, key_shift(KeyCode::Char('L')));
+        }
+        let max_x = app.viewport_x;
+        handle_key_event(&mut app, key_shift(KeyCode::Char('L')));
+        assert_eq!(app.viewport_x, max_x);
+
+        app.drag_state = Some(crate::tui::app::DragState {
+            start_x: 70,
+            start_y: 20,
+            viewport_x0: 0,
+            viewport_y0: 0,
+        });
+        let drag = MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Left),
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_event(&mut app, drag);
+        // Within bounds the drag moves the viewport as before
+        assert_eq!(app.viewport_x, 70);
+        app.drag_state.as_mut().unwrap().viewport_x0 = 10_000;
+        app.drag_state.as_mut().unwrap().viewport_y0 = 10_000;
+        handle_mouse_event(&mut app, drag);
+        assert_eq!(app.viewport_x, max_x);
+        assert!(app.viewport_y < 10_000);
+    }
+
     #[test]
     fn test_mouse_not_in_normal_mode() {
         let mut app = test_app();
@@ -1551,11 +1813,44 @@ mod tests {
         app.run_state = DbtRunState::Running {
             receiver: rx,
             output_lines: vec!["a".into(), "b".into(), "c".into()],
+            cancel: Default::default(),
         };
         assert!(!handle_key_event(&mut app, key(KeyCode::Char('G'))));
         assert_eq!(app.run_output_scroll, 2);
     }
 
+    #[test]
+    fn test_run_output_cancel_key() {
+        let mut app = test_app();
+        app.mode = AppMode::RunOutput;
+        let (tx, rx) = std::sync::mpsc::channel::<super::super::runner::DbtRunMessage>();
+        let cancel = super::super::runner::CancelToken::default();
+        app.run_state = DbtRunState::Running {
+            receiver: rx,
+            output_lines: vec![],
+            cancel: cancel.clone(),
+        };
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('x'))));
+        assert!(cancel.is_cancelled());
+        assert_eq!(app.mode, AppMode::RunOutput);
+
+        // The runner acknowledges by sending Cancelled
+        tx.send(super::super::runner::DbtRunMessage::Cancelled)
+            .unwrap();
+        app.drain_run_messages();
+        match &app.run_state {
+            DbtRunState::Finished {
+                success,
+                output_lines,
+            } => {
+                assert!(!success);
+                assert_eq!(output_lines[0], "Cancelling...");
+                assert!(output_lines[1].starts_with("Cancelled"));
+            }
+            _ => panic!("Expected Finished"),
+        }
+    }
+
     #[test]
     fn test_run_output_jump_bottom_idle() {
         let mut app = test_app();
@@ -1729,6 +2024,16 @@ mod tests {
         assert!(app.filter_status.is_none());
     }
 
+    #[test]
+    fn test_filter_change_schedules_relayout() {
+        let mut app = test_app();
+        app.mode = AppMode::Filter;
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('x'))));
+        assert!(app.relayout_due.is_none());
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('s'))));
+        assert!(app.relayout_due.is_some());
+    }
+
     // ─── Path highlighting tests ───
 
     #[test]
@@ -1742,6 +2047,16 @@ mod tests {
         assert!(app.highlighted_path.is_empty());
     }
 
+    #[test]
+    fn test_normal_m_toggles_main_line() {
+        let mut app = test_app();
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('m'))));
+        assert!(app.path_main_line);
+        assert!(!app.highlighted_path.is_empty());
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('m'))));
+        assert!(app.highlighted_path.is_empty());
+    }
+
     #[test]
     fn test_path_highlight_no_selection() {
         let mut app = test_app();
@@ -1762,6 +2077,16 @@ mod tests {
         assert!(!app.show_column_lineage);
     }
 
+    #[test]
+    fn test_shift_m_switches_graph_source() {
+        let mut app = test_app();
+        app.graph_loader = Some(Box::new(|_| Ok(make_test_graph())));
+        assert!(!handle_key_event(&mut app, key_shift(KeyCode::Char('M'))));
+        assert_eq!(app.graph_source, GraphSource::Manifest);
+        assert!(!handle_key_event(&mut app, key_shift(KeyCode::Char('M'))));
+        assert_eq!(app.graph_source, GraphSource::Sql);
+    }
+
     // ─── Impact report via path highlight tests ───
 
     #[test]
@@ -1774,4 +2099,74 @@ mod tests {
         assert!(!handle_key_event(&mut app, key(KeyCode::Char('p'))));
         assert!(app.impact_report.is_none());
     }
+
+    #[test]
+    fn test_v_opens_sql_view_and_scrolls() {
+        let mut app = test_app();
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('v'))));
+        assert_eq!(app.mode, AppMode::SqlView);
+
+        app.sql_view = Some(crate::tui::app::SqlView {
+            title: "x.sql".into(),
+            lines: (0..50).map(|i| i.to_string()).collect(),
+        });
+        handle_key_event(&mut app, key(KeyCode::Char('j')));
+        handle_key_event(&mut app, key(KeyCode::Down));
+        assert_eq!(app.sql_view_scroll, 2);
+        handle_key_event(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.sql_view_scroll, 1);
+        handle_key_event(&mut app, key(KeyCode::PageDown));
+        assert_eq!(app.sql_view_scroll, 1 + SQL_VIEW_PAGE);
+        handle_key_event(&mut app, key(KeyCode::PageUp));
+        assert_eq!(app.sql_view_scroll, 1);
+        handle_key_event(&mut app, key(KeyCode::Char('G')));
+        assert_eq!(app.sql_view_scroll, 49);
+        handle_key_event(&mut app, key(KeyCode::Char('g')));
+        assert_eq!(app.sql_view_scroll, 0);
+
+        handle_key_event(&mut app, key(KeyCode::Char('v')));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_sql_view_esc_and_ctrl_c_close() {
+        let mut app = test_app();
+        app.mode = AppMode::SqlView;
+        assert!(!handle_key_event(&mut app, key(KeyCode::Esc)));
+        assert_eq!(app.mode, AppMode::Normal);
+
+        app.mode = AppMode::SqlView;
+        assert!(!handle_key_event(&mut app, key_ctrl('c')));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_u_opens_phantom_list() {
+        let mut app = test_app();
+        let orders = app
+            .graph
+            .node_indices()
+            .find(|&i| app.graph[i].label == "orders")
+            .unwrap();
+        let mut ghost = app.graph[orders].clone();
+        ghost.unique_id = "model.ghost".into();
+        ghost.label = "ghost".into();
+        ghost.node_type = NodeType::Phantom;
+        let ghost = app.graph.add_node(ghost);
+
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('u'))));
+        assert_eq!(app.mode, AppMode::PhantomList);
+        handle_key_event(&mut app, key(KeyCode::Char('j')));
+        handle_key_event(&mut app, key(KeyCode::Up));
+        handle_key_event(&mut app, key(KeyCode::Enter));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.selected_node, Some(ghost));
+
+        handle_key_event(&mut app, key(KeyCode::Char('u')));
+        assert!(!handle_key_event(&mut app, key(KeyCode::Char('q'))));
+        assert_eq!(app.mode, AppMode::Normal);
+        handle_key_event(&mut app, key(KeyCode::Char('u')));
+        assert!(!handle_key_event(&mut app, key_ctrl('c')));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
 }
@@ -7,9 +7,10 @@ use ratatui::widgets::Widget;
 
 use crate::graph::types::*;
 use crate::parser::artifacts::RunStatus;
+use crate::render::shorten_label;
 
 use super::app::App;
-use super::run_status::{status_color, status_symbol};
+use super::run_status::{status_color, status_symbol, SLA_BREACH_COLOR};
 
 /// Node box dimensions in terminal cells
 const NODE_BOX_WIDTH: u16 = 24;
@@ -26,23 +27,9 @@ impl<'a> GraphWidget<'a> {
         Self { app }
     }
 
-    /// Compute effective layer gap after zoom
-    fn eff_layer_gap(&self) -> u16 {
-        (LAYER_GAP as f64 * self.app.zoom).max(4.0) as u16
-    }
-
-    /// Compute effective node gap after zoom
-    fn eff_node_gap(&self) -> u16 {
-        (NODE_GAP as f64 * self.app.zoom).max(1.0) as u16
-    }
-
     /// Convert layout (layer, pos) to world-space (wx, wy) in terminal cells
     fn world_pos(&self, layer: usize, pos: usize) -> (i32, i32) {
-        let eff_lg = self.eff_layer_gap();
-        let eff_ng = self.eff_node_gap();
-        let wx = layer as i32 * (NODE_BOX_WIDTH as i32 + eff_lg as i32);
-        let wy = pos as i32 * (NODE_BOX_HEIGHT as i32 + eff_ng as i32);
-        (wx, wy)
+        node_world_origin(layer, pos, self.app.zoom)
     }
 
     /// Convert world-space to screen-space, returning None if outside render area
@@ -115,7 +102,11 @@ impl<'a> GraphWidget<'a> {
     }
 
     fn draw_edges(&self, buf: &mut Buffer, area: Rect) {
-        let has_highlight = !self.app.highlighted_path.is_empty();
+        let highlight = self.app.active_highlight();
+        let has_highlight = highlight.is_some();
+
+        let active_edge = self.app.active_edge();
+        let mut active = None;
 
         for edge in self.app.graph.edge_references() {
             let source = edge.source();
@@ -133,12 +124,14 @@ impl<'a> GraphWidget<'a> {
                 continue;
             };
 
-            let edge_highlighted = has_highlight
-                && self.app.highlighted_path.contains(&source)
-                && self.app.highlighted_path.contains(&target);
+            let edge_highlighted =
+                highlight.is_some_and(|h| h.contains(&source) && h.contains(&target));
 
             let color = if has_highlight && !edge_highlighted {
                 Color::DarkGray
+            } else if self.app.source_only_edges.contains(&edge.id()) {
+                // Only the current source knows about this edge (after `M`)
+                Color::Magenta
             } else {
                 match edge.weight().edge_type {
                     EdgeType::Ref => {
@@ -173,63 +166,86 @@ impl<'a> GraphWidget<'a> {
             };
             let style = Style::default().fg(color);
 
-            let (src_wx, src_wy) = self.world_pos(sl, sp);
-            let (tgt_wx, tgt_wy) = self.world_pos(tl, tp);
+            // The edge being explained is drawn last, on top of the others
+            if Some(edge.id()) == active_edge {
+                active = Some(((sl, sp), (tl, tp)));
+                continue;
+            }
+            self.draw_edge(buf, area, (sl, sp), (tl, tp), style);
+        }
 
-            // Source right edge midpoint, target left edge midpoint
-            let src_right = src_wx + NODE_BOX_WIDTH as i32;
-            let src_mid_y = src_wy + NODE_BOX_HEIGHT as i32 / 2;
-            let tgt_left = tgt_wx;
-            let tgt_mid_y = tgt_wy + NODE_BOX_HEIGHT as i32 / 2;
+        if let Some((from, to)) = active {
+            let style = Style::default().fg(Color::Yellow);
+            self.draw_edge(buf, area, from, to, style);
+        }
+    }
 
-            // Midpoint column for the vertical segment
-            let mid_x = (src_right + tgt_left) / 2;
+    /// Draw one edge from the node at layout position `from` to the one at `to`
+    fn draw_edge(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        (sl, sp): (usize, usize),
+        (tl, tp): (usize, usize),
+        style: Style,
+    ) {
+        let (src_wx, src_wy) = self.world_pos(sl, sp);
+        let (tgt_wx, tgt_wy) = self.world_pos(tl, tp);
+
+        // Source right edge midpoint, target left edge midpoint
+        let src_right = src_wx + NODE_BOX_WIDTH as i32;
+        let src_mid_y = src_wy + NODE_BOX_HEIGHT as i32 / 2;
+        let tgt_left = tgt_wx;
+        let tgt_mid_y = tgt_wy + NODE_BOX_HEIGHT as i32 / 2;
+
+        // Midpoint column for the vertical segment
+        let mid_x = (src_right + tgt_left) / 2;
+
+        if src_mid_y == tgt_mid_y {
+            // Same row: straight horizontal line
+            self.draw_hline(buf, src_right, tgt_left - 1, src_mid_y, area, "─", style);
+            // Arrowhead
+            self.set_cell(buf, tgt_left - 1, tgt_mid_y, area, "▸", style);
+        } else {
+            // Orthogonal 3-segment routing
+            // Segment 1: horizontal from source right to midpoint
+            if mid_x > src_right {
+                self.draw_hline(buf, src_right, mid_x - 1, src_mid_y, area, "─", style);
+            }
 
-            if src_mid_y == tgt_mid_y {
-                // Same row: straight horizontal line
-                self.draw_hline(buf, src_right, tgt_left - 1, src_mid_y, area, "─", style);
-                // Arrowhead
-                self.set_cell(buf, tgt_left - 1, tgt_mid_y, area, "▸", style);
+            // Segment 2: vertical from source row to target row at midpoint
+            let (vy_start, vy_end) = if src_mid_y < tgt_mid_y {
+                (src_mid_y + 1, tgt_mid_y - 1)
             } else {
-                // Orthogonal 3-segment routing
-                // Segment 1: horizontal from source right to midpoint
-                if mid_x > src_right {
-                    self.draw_hline(buf, src_right, mid_x - 1, src_mid_y, area, "─", style);
-                }
-
-                // Segment 2: vertical from source row to target row at midpoint
-                let (vy_start, vy_end) = if src_mid_y < tgt_mid_y {
-                    (src_mid_y + 1, tgt_mid_y - 1)
-                } else {
-                    (tgt_mid_y + 1, src_mid_y - 1)
-                };
-                if vy_start <= vy_end {
-                    self.draw_vline(buf, mid_x, vy_start, vy_end, area, "│", style);
-                }
+                (tgt_mid_y + 1, src_mid_y - 1)
+            };
+            if vy_start <= vy_end {
+                self.draw_vline(buf, mid_x, vy_start, vy_end, area, "│", style);
+            }
 
-                // Segment 3: horizontal from midpoint to target left
-                if tgt_left - 1 > mid_x {
-                    self.draw_hline(buf, mid_x + 1, tgt_left - 2, tgt_mid_y, area, "─", style);
-                }
-                // Arrowhead
-                self.set_cell(buf, tgt_left - 1, tgt_mid_y, area, "▸", style);
-
-                // Corner characters
-                if src_mid_y < tgt_mid_y {
-                    // Source above target: ┐ at top-right, └ at bottom-left
-                    self.set_cell(buf, mid_x, src_mid_y, area, "┐", style);
-                    self.set_cell(buf, mid_x, tgt_mid_y, area, "└", style);
-                } else {
-                    // Source below target: ┘ at bottom-right, ┌ at top-left
-                    self.set_cell(buf, mid_x, src_mid_y, area, "┘", style);
-                    self.set_cell(buf, mid_x, tgt_mid_y, area, "┌", style);
-                }
+            // Segment 3: horizontal from midpoint to target left
+            if tgt_left - 1 > mid_x {
+                self.draw_hline(buf, mid_x + 1, tgt_left - 2, tgt_mid_y, area, "─", style);
+            }
+            // Arrowhead
+            self.set_cell(buf, tgt_left - 1, tgt_mid_y, area, "▸", style);
+
+            // Corner characters
+            if src_mid_y < tgt_mid_y {
+                // Source above target: ┐ at top-right, └ at bottom-left
+                self.set_cell(buf, mid_x, src_mid_y, area, "┐", style);
+                self.set_cell(buf, mid_x, tgt_mid_y, area, "└", style);
+            } else {
+                // Source below target: ┘ at bottom-right, ┌ at top-left
+                self.set_cell(buf, mid_x, src_mid_y, area, "┘", style);
+                self.set_cell(buf, mid_x, tgt_mid_y, area, "┌", style);
             }
         }
     }
 
     fn draw_nodes(&self, buf: &mut Buffer, area: Rect) {
-        let has_highlight = !self.app.highlighted_path.is_empty();
+        let highlight = self.app.active_highlight();
+        let has_highlight = highlight.is_some();
 
         for idx in self.app.graph.node_indices() {
             // Skip nodes that don't pass the filter
@@ -245,7 +261,7 @@ impl<'a> GraphWidget<'a> {
             let node = &self.app.graph[idx];
             let is_selected = self.app.selected_node == Some(idx);
             let run_status = self.app.node_run_status(&node.unique_id);
-            let is_on_path = !has_highlight || self.app.highlighted_path.contains(&idx);
+            let is_on_path = highlight.is_none_or(|h| h.contains(&idx));
 
             let node_fg = if has_highlight && !is_on_path {
                 Color::DarkGray
@@ -273,6 +289,16 @@ impl<'a> GraphWidget<'a> {
             } else {
                 (Style::default().fg(node_fg), Style::default().fg(node_fg))
             };
+            // A breached SLA shows as a bold warning border unless the node is
+            // selected or dimmed by a highlight
+            let sla_breached = self.app.node_sla(idx).is_some_and(|sla| sla.breached());
+            let border_style = if sla_breached && !is_selected && (!has_highlight || is_on_path) {
+                Style::default()
+                    .fg(SLA_BREACH_COLOR)
+                    .add_modifier(ratatui::style::Modifier::BOLD)
+            } else {
+                border_style
+            };
 
             let w = NODE_BOX_WIDTH as i32;
             let h = NODE_BOX_HEIGHT as i32;
@@ -303,10 +329,18 @@ impl<'a> GraphWidget<'a> {
 
             // Label on the content row (row 1)
             let sym = status_symbol(run_status);
-            let display = node.display_name();
+            let display = shorten_label(node.display_name(), self.app.max_label_len);
             let label = format!("{} {}", sym, display);
             let max_chars = (NODE_BOX_WIDTH - 2) as usize; // space inside borders
-            let truncated = truncate_label(&label, max_chars);
+
+            // Shorten the name rather than the label detail
+            let truncated = match self.app.label_detail.suffix(node) {
+                Some(suffix) => {
+                    let name_chars = (max_chars - 1).saturating_sub(suffix.chars().count() + 1);
+                    format!("{} {}", shorten_label(label, Some(name_chars)), suffix)
+                }
+                None => shorten_label(label, Some(max_chars)),
+            };
 
             // Pad with spaces to fill the box width
             let padded = format!(" {:<width$}", truncated, width = max_chars - 1);
@@ -349,48 +383,68 @@ fn node_color(node_type: NodeType) -> Color {
     }
 }
 
-fn truncate_label(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max_len - 1])
-    }
-}
-
-/// Hit-test a screen coordinate against all node boxes.
-/// Returns the NodeIndex of the first node whose bounding box contains the point.
+/// Hit-test a screen coordinate against the node boxes as drawn: the same
+/// zoomed positions, only inside the graph area, and only nodes the filters
+/// show. Boxes are a fixed width, since labels are cut to fit. Where boxes
+/// overlap, the one drawn last (on top) wins.
 pub fn hit_test_node(app: &App, screen_x: u16, screen_y: u16) -> Option<NodeIndex> {
     let area = app.last_graph_area?;
-
-    let eff_lg = (LAYER_GAP as f64 * app.zoom).max(4.0) as u16;
-    let eff_ng = (NODE_GAP as f64 * app.zoom).max(1.0) as u16;
+    if !area.contains(Position::new(screen_x, screen_y)) {
+        return None;
+    }
 
     // Convert screen coords to world coords
     let wx = (screen_x as i32 - area.x as i32) + app.viewport_x;
     let wy = (screen_y as i32 - area.y as i32) + app.viewport_y;
 
-    for (&node_idx, &(layer, pos)) in &app.layout.positions {
-        let node_wx = layer as i32 * (NODE_BOX_WIDTH as i32 + eff_lg as i32);
-        let node_wy = pos as i32 * (NODE_BOX_HEIGHT as i32 + eff_ng as i32);
-
-        if wx >= node_wx
+    let drawn: Vec<NodeIndex> = app.graph.node_indices().collect();
+    drawn.into_iter().rev().find(|&idx| {
+        let Some(&(layer, pos)) = app.layout.positions.get(&idx) else {
+            return false;
+        };
+        if !app.node_passes_filter(idx) {
+            return false;
+        }
+        let (node_wx, node_wy) = node_world_origin(layer, pos, app.zoom);
+        wx >= node_wx
             && wx < node_wx + NODE_BOX_WIDTH as i32
             && wy >= node_wy
             && wy < node_wy + NODE_BOX_HEIGHT as i32
-        {
-            return Some(node_idx);
-        }
-    }
-    None
+    })
 }
 
-/// Compute world-space center of a node given its layout position.
-/// Used by App::center_on_selected.
-pub fn node_world_center(layer: usize, pos: usize, zoom: f64) -> (i32, i32) {
+/// World-space top-left corner of a node's box at the given zoom. Zoom widens
+/// the gaps between boxes, not the boxes themselves.
+fn node_world_origin(layer: usize, pos: usize, zoom: f64) -> (i32, i32) {
     let eff_lg = (LAYER_GAP as f64 * zoom).max(4.0) as u16;
     let eff_ng = (NODE_GAP as f64 * zoom).max(1.0) as u16;
     let wx = layer as i32 * (NODE_BOX_WIDTH as i32 + eff_lg as i32);
     let wy = pos as i32 * (NODE_BOX_HEIGHT as i32 + eff_ng as i32);
+    (wx, wy)
+}
+
+/// World-space bounding box `(min_x, min_y, max_x, max_y)` (exclusive max) of
+/// all node boxes at the given zoom, or `None` for an empty layout
+pub fn layout_world_bounds<'a>(
+    positions: impl IntoIterator<Item = &'a (usize, usize)>,
+    zoom: f64,
+) -> Option<(i32, i32, i32, i32)> {
+    positions.into_iter().fold(None, |bounds, &(layer, pos)| {
+        let (cx, cy) = node_world_center(layer, pos, zoom);
+        let x0 = cx - NODE_BOX_WIDTH as i32 / 2;
+        let y0 = cy - NODE_BOX_HEIGHT as i32 / 2;
+        let (x1, y1) = (x0 + NODE_BOX_WIDTH as i32, y0 + NODE_BOX_HEIGHT as i32);
+        Some(match bounds {
+            None => (x0, y0, x1, y1),
+            Some((a, b, c, d)) => (a.min(x0), b.min(y0), c.max(x1), d.max(y1)),
+        })
+    })
+}
+
+/// Compute world-space center of a node given its layout position.
+/// Used by App::center_on_selected.
+pub fn node_world_center(layer: usize, pos: usize, zoom: f64) -> (i32, i32) {
+    let (wx, wy) = node_world_origin(layer, pos, zoom);
     let cx = wx + NODE_BOX_WIDTH as i32 / 2;
     let cy = wy + NODE_BOX_HEIGHT as i32 / 2;
     (cx, cy)
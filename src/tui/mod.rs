@@ -6,7 +6,7 @@ pub mod runner;
 pub mod ui;
 
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
@@ -17,10 +17,13 @@ use crossterm::{
 use ratatui::prelude::*;
 use std::io;
 
+use crate::graph::groups::GroupConfig;
+use crate::graph::sort::NodeSort;
 use crate::graph::types::LineageGraph;
 use crate::parser::artifacts;
+use crate::render::LabelDetail;
 
-use app::App;
+use app::{App, ClipboardWriter, GraphLoader, GraphSource};
 use event::{handle_key_event, handle_mouse_event};
 use ui::draw_ui;
 
@@ -63,9 +66,10 @@ fn process_event(app: &mut App, event: Event) -> bool {
 #[cfg(not(tarpaulin_include))]
 fn load_run_status(
     project_dir: &std::path::Path,
+    run_results_path: &std::path::Path,
     graph: &LineageGraph,
 ) -> Result<std::collections::HashMap<String, crate::parser::artifacts::RunStatus>> {
-    match artifacts::load_run_results(project_dir)? {
+    match artifacts::load_run_results_from(run_results_path)? {
         Some(results) => Ok(artifacts::build_run_status_map(
             &results,
             graph,
@@ -75,6 +79,22 @@ fn load_run_status(
     }
 }
 
+/// The system clipboard, opened on first use so a terminal without one (e.g.
+/// over SSH) only fails when copying. The handle is kept open, since on X11
+/// the copied text is gone once its owner closes.
+#[cfg(not(tarpaulin_include))]
+fn system_clipboard() -> ClipboardWriter {
+    let mut clipboard: Option<arboard::Clipboard> = None;
+    Box::new(move |text| {
+        let handle = match &mut clipboard {
+            Some(handle) => handle,
+            None => clipboard.insert(arboard::Clipboard::new()?),
+        };
+        handle.set_text(text)?;
+        Ok(())
+    })
+}
+
 /// Run the main event loop, returning when the user quits
 #[cfg(not(tarpaulin_include))]
 fn run_event_loop(
@@ -82,8 +102,12 @@ fn run_event_loop(
     app: &mut App,
 ) -> Result<()> {
     loop {
+        if std::mem::take(&mut app.full_redraw) {
+            terminal.clear()?;
+        }
         terminal.draw(|f| draw_ui(f, app))?;
         app.drain_run_messages();
+        app.apply_pending_relayout(Instant::now());
         if poll(Duration::from_millis(50))? && process_event(app, read()?) {
             break;
         }
@@ -93,11 +117,42 @@ fn run_event_loop(
 
 /// Launch the interactive TUI
 #[cfg(not(tarpaulin_include))]
-pub fn run_tui(graph: LineageGraph, project_dir: PathBuf) -> Result<()> {
-    let run_status = load_run_status(&project_dir, &graph)?;
+#[allow(clippy::too_many_arguments)]
+pub fn run_tui(
+    graph: LineageGraph,
+    project_dir: PathBuf,
+    groups: Option<&GroupConfig>,
+    run_timeout: Option<Duration>,
+    graph_source: GraphSource,
+    sort: Option<NodeSort>,
+    label_detail: LabelDetail,
+    max_label_len: Option<usize>,
+    max_layer_width: Option<usize>,
+    graph_loader: Option<GraphLoader>,
+    run_results: Option<PathBuf>,
+) -> Result<()> {
+    let run_results_path =
+        run_results.unwrap_or_else(|| artifacts::default_run_results_path(&project_dir));
+    let run_status = load_run_status(&project_dir, &run_results_path, &graph)?;
 
     let mut terminal = setup_terminal()?;
     let mut app = App::new(graph, project_dir, run_status);
+    app.run_timeout = run_timeout;
+    app.run_results_path = run_results_path;
+    app.graph_source = graph_source;
+    app.graph_loader = graph_loader;
+    app.clipboard = Some(system_clipboard());
+    app.label_detail = label_detail;
+    app.max_label_len = max_label_len;
+    if max_layer_width.is_some() {
+        app = app.with_max_layer_width(max_layer_width);
+    }
+    if let Some(groups) = groups {
+        app = app.with_groups(groups);
+    }
+    if let Some(sort) = sort {
+        app = app.with_sort(sort);
+    }
 
     run_event_loop(&mut terminal, &mut app)?;
 
@@ -30,6 +30,9 @@ pub fn status_label(status: &RunStatus) -> String {
     }
 }
 
+/// Border color of a node whose last success is older than its `meta.sla_hours`
+pub const SLA_BREACH_COLOR: Color = Color::LightRed;
+
 /// Get the ratatui color for a run status
 pub fn status_color(status: &RunStatus) -> Color {
     match status {
@@ -1,8 +1,13 @@
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use std::sync::mpsc;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the runner checks the child for exit, cancellation and timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Which dbt command to run
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -121,19 +126,92 @@ impl DbtRunRequest {
         args
     }
 
-    /// Human-readable command string for display
+    /// Command string for display, quoted so it can be pasted into a shell
     pub fn display_command(&self) -> String {
-        let args = self.args();
+        let args: Vec<String> = self.args().iter().map(|arg| shell_quote(arg)).collect();
         format!("{} {}", self.program(), args.join(" "))
     }
 }
 
+/// Single-quote `arg` for a POSIX shell unless it only has characters the
+/// shell leaves alone
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_+-./=:@,%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
 /// Messages sent from the background dbt process
 #[derive(Debug)]
 pub enum DbtRunMessage {
     OutputLine(String),
-    Completed { success: bool },
+    Completed {
+        success: bool,
+    },
     SpawnError(String),
+    /// The child was killed at the user's request
+    Cancelled,
+    /// The child was killed after running longer than the timeout
+    TimedOut(Duration),
+}
+
+/// Shared flag the TUI sets to ask the runner thread to kill the child
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// How a supervised child process ended
+#[derive(Debug, PartialEq, Eq)]
+enum ChildOutcome {
+    Exited { success: bool },
+    Cancelled,
+    TimedOut,
+}
+
+/// Wait for `child` to exit, killing it if `cancel` is set or `timeout` elapses
+fn supervise_child(
+    child: &mut Child,
+    cancel: &CancelToken,
+    timeout: Option<Duration>,
+) -> ChildOutcome {
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                return ChildOutcome::Exited {
+                    success: status.success(),
+                }
+            }
+            Ok(None) => {}
+            Err(_) => return ChildOutcome::Exited { success: false },
+        }
+        let outcome = if cancel.is_cancelled() {
+            ChildOutcome::Cancelled
+        } else if timeout.is_some_and(|t| started.elapsed() >= t) {
+            ChildOutcome::TimedOut
+        } else {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        };
+        let _ = child.kill();
+        let _ = child.wait();
+        return outcome;
+    }
 }
 
 /// Pipe lines from a reader to a channel, stopping when the channel closes or the reader ends.
@@ -154,10 +232,16 @@ fn pipe_lines_to_channel<R: std::io::Read + Send + 'static>(
 }
 
 /// Spawn a dbt run in a background thread.
-/// Returns a receiver for progress messages.
+/// Returns a receiver for progress messages and a token that kills the run when cancelled.
+/// With a `timeout`, a run still going after that long is killed and reported as `TimedOut`.
 #[cfg(not(tarpaulin_include))]
-pub fn spawn_dbt_run(request: DbtRunRequest) -> mpsc::Receiver<DbtRunMessage> {
+pub fn spawn_dbt_run(
+    request: DbtRunRequest,
+    timeout: Option<Duration>,
+) -> (mpsc::Receiver<DbtRunMessage>, CancelToken) {
     let (tx, rx) = mpsc::channel();
+    let cancel = CancelToken::default();
+    let thread_cancel = cancel.clone();
 
     thread::spawn(move || {
         let program = request.program();
@@ -183,14 +267,21 @@ pub fn spawn_dbt_run(request: DbtRunRequest) -> mpsc::Receiver<DbtRunMessage> {
         let stdout_handle = pipe_lines_to_channel(child.stdout.take(), tx.clone());
         let stderr_handle = pipe_lines_to_channel(child.stderr.take(), tx.clone());
 
-        let _ = stdout_handle.join();
-        let _ = stderr_handle.join();
-
-        let success = child.wait().map(|s| s.success()).unwrap_or(false);
-        let _ = tx.send(DbtRunMessage::Completed { success });
+        let message = match supervise_child(&mut child, &thread_cancel, timeout) {
+            ChildOutcome::Exited { success } => {
+                let _ = stdout_handle.join();
+                let _ = stderr_handle.join();
+                DbtRunMessage::Completed { success }
+            }
+            // Don't join the readers: a grandchild (e.g. dbt under `uv run`) may
+            // still hold the pipes open after the direct child is killed
+            ChildOutcome::Cancelled => DbtRunMessage::Cancelled,
+            ChildOutcome::TimedOut => DbtRunMessage::TimedOut(timeout.unwrap_or_default()),
+        };
+        let _ = tx.send(message);
     });
 
-    rx
+    (rx, cancel)
 }
 
 #[cfg(test)]
@@ -294,6 +385,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display_command_quotes_paths() {
+        let req = DbtRunRequest {
+            command: DbtCommand::Run,
+            scope: SelectionScope::Single,
+            model_name: "orders".to_string(),
+            project_dir: PathBuf::from("/tmp/my project/it's"),
+            use_uv: false,
+        };
+        assert_eq!(
+            req.display_command(),
+            "dbt run --select orders --project-dir '/tmp/my project/it'\\''s'"
+        );
+    }
+
     #[test]
     fn test_detect_use_uv_with_lock_file() {
         use std::fs;
@@ -336,6 +442,54 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    #[cfg(unix)]
+    fn spawn_sleep(seconds: &str) -> Child {
+        Command::new("sleep").arg(seconds).spawn().unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_supervise_child_exits() {
+        let mut child = Command::new("true").spawn().unwrap();
+        let outcome = supervise_child(&mut child, &CancelToken::default(), None);
+        assert_eq!(outcome, ChildOutcome::Exited { success: true });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_supervise_child_cancelled() {
+        let mut child = spawn_sleep("30");
+        let cancel = CancelToken::default();
+        cancel.cancel();
+        let started = Instant::now();
+        assert_eq!(
+            supervise_child(&mut child, &cancel, None),
+            ChildOutcome::Cancelled
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_supervise_child_timed_out() {
+        let mut child = spawn_sleep("30");
+        let outcome = supervise_child(
+            &mut child,
+            &CancelToken::default(),
+            Some(Duration::from_millis(100)),
+        );
+        assert_eq!(outcome, ChildOutcome::TimedOut);
+    }
+
+    #[test]
+    fn test_cancel_token_shared_between_clones() {
+        let token = CancelToken::default();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
     #[test]
     fn test_dbt_command_as_str() {
         assert_eq!(DbtCommand::Run.as_str(), "run");
@@ -1,12 +1,14 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
+use crate::graph::sla::SlaCheck;
+use crate::graph::stats::{upstream_source_count, PhantomKind};
 use crate::graph::types::*;
 use crate::parser::artifacts::RunStatus;
 
-use super::app::{App, AppMode, DbtRunState, NodeListEntry};
+use super::app::{App, AppMode, DbtRunState, GraphSummary, NodeListEntry};
 use super::graph_widget::GraphWidget;
-use super::run_status::{status_color, status_label, status_symbol};
+use super::run_status::{status_color, status_label, status_symbol, SLA_BREACH_COLOR};
 
 pub fn draw_ui(f: &mut Frame, app: &mut App) {
     // Main layout depends on whether node list panel is visible
@@ -48,7 +50,10 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         AppMode::RunMenu => draw_run_menu(f, app),
         AppMode::ContextMenu => draw_context_menu(f, app),
         AppMode::RunConfirm => draw_run_confirm(f, app),
+        AppMode::CommandPreview => draw_command_preview(f, app),
         AppMode::RunOutput => draw_run_output(f, app),
+        AppMode::SqlView => draw_sql_view(f, app),
+        AppMode::PhantomList => draw_phantom_list(f, app),
         _ => {}
     }
 }
@@ -97,8 +102,18 @@ fn draw_node_list(f: &mut Frame, app: &mut App, area: Rect) {
         })
         .collect();
 
+    let border_style = if app.mode == AppMode::NodeList {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    };
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(" Nodes "))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(" Nodes "),
+        )
         .highlight_style(Style::default().fg(Color::Black).bg(Color::White));
 
     f.render_stateful_widget(list, area, &mut app.node_list_state);
@@ -119,8 +134,9 @@ fn draw_detail_panel(f: &mut Frame, app: &App, area: Rect) {
     let node = &app.graph[selected];
     let run_status = app.node_run_status(&node.unique_id);
 
-    let mut lines = detail_basic_lines(node, run_status);
+    let mut lines = detail_basic_lines(node, run_status, app.node_sla(selected));
     lines.extend(detail_column_lineage_lines(app, node));
+    lines.extend(detail_column_trace_lines(app, selected));
     lines.extend(detail_neighbors_lines(app, selected));
     lines.extend(detail_impact_lines(app, selected));
 
@@ -128,12 +144,73 @@ fn draw_detail_panel(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(paragraph, inner);
 }
 
-/// Build lines for basic node info: name, type, ID, file, status, timestamps, errors, description, columns
-fn detail_basic_lines<'a>(node: &'a NodeData, run_status: &'a RunStatus) -> Vec<Line<'a>> {
+/// SLA line for a node with `meta.sla_hours` and a recorded run
+fn detail_sla_lines(sla: Option<SlaCheck>) -> Vec<Line<'static>> {
+    let Some(sla) = sla else {
+        return Vec::new();
+    };
+    let (text, color) = match (sla.breached(), sla.age_hours) {
+        (false, Some(age)) => (
+            format!("{}h, met (last success {}h ago)", sla.sla_hours, age),
+            Color::Green,
+        ),
+        (true, Some(age)) => (
+            format!("{}h, breached (last success {}h ago)", sla.sla_hours, age),
+            SLA_BREACH_COLOR,
+        ),
+        (_, None) => (
+            format!("{}h, breached (last run did not succeed)", sla.sla_hours),
+            SLA_BREACH_COLOR,
+        ),
+    };
+    vec![Line::from(vec![
+        Span::styled("SLA: ", Style::default().bold()),
+        Span::styled(text, Style::default().fg(color)),
+    ])]
+}
+
+/// Strategy and unique key lines for a snapshot, e.g. `Strategy: timestamp
+/// (updated_at: updated_at)`
+fn detail_snapshot_lines(node: &NodeData) -> Vec<Line<'static>> {
+    let Some(snapshot) = &node.snapshot else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    let compared = match (&snapshot.updated_at, snapshot.check_cols.is_empty()) {
+        (Some(column), _) => Some(format!("updated_at: {}", column)),
+        (None, false) => Some(format!("check_cols: {}", snapshot.check_cols.join(", "))),
+        (None, true) => None,
+    };
+    let strategy = match (&snapshot.strategy, compared) {
+        (Some(strategy), Some(compared)) => Some(format!("{} ({})", strategy, compared)),
+        (Some(strategy), None) => Some(strategy.clone()),
+        (None, compared) => compared,
+    };
+    if let Some(strategy) = strategy {
+        lines.push(Line::from(vec![
+            Span::styled("Strategy: ", Style::default().bold()),
+            Span::raw(strategy),
+        ]));
+    }
+    if !snapshot.unique_key.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Unique key: ", Style::default().bold()),
+            Span::raw(snapshot.unique_key.join(", ")),
+        ]));
+    }
+    lines
+}
+
+/// Build lines for basic node info: name, type, ID, file, status, timestamps, errors, SLA, description, columns
+fn detail_basic_lines<'a>(
+    node: &'a NodeData,
+    run_status: &'a RunStatus,
+    sla: Option<SlaCheck>,
+) -> Vec<Line<'a>> {
     let mut lines = vec![
         Line::from(vec![
             Span::styled("Name: ", Style::default().bold()),
-            Span::raw(&node.label),
+            Span::raw(node.versioned_label()),
         ]),
         Line::from(vec![
             Span::styled("Type: ", Style::default().bold()),
@@ -154,6 +231,7 @@ fn detail_basic_lines<'a>(node: &'a NodeData, run_status: &'a RunStatus) -> Vec<
             Span::raw(path.display().to_string()),
         ]));
     }
+    lines.extend(detail_snapshot_lines(node));
 
     lines.push(Line::from(vec![
         Span::styled("Status: ", Style::default().bold()),
@@ -202,6 +280,7 @@ fn detail_basic_lines<'a>(node: &'a NodeData, run_status: &'a RunStatus) -> Vec<
         }
         _ => {}
     }
+    lines.extend(detail_sla_lines(sla));
 
     if let Some(desc) = &node.description {
         lines.push(Line::from(""));
@@ -219,7 +298,13 @@ fn detail_basic_lines<'a>(node: &'a NodeData, run_status: &'a RunStatus) -> Vec<
             Style::default().bold(),
         )]));
         for col in &node.columns {
-            lines.push(Line::from(format!("  {}", col)));
+            match node.column_types.get(col) {
+                Some(data_type) => lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", col)),
+                    Span::styled(data_type.as_str(), Style::default().fg(Color::DarkGray)),
+                ])),
+                None => lines.push(Line::from(format!("  {}", col))),
+            }
         }
     }
 
@@ -268,10 +353,82 @@ fn detail_column_lineage_lines<'a>(app: &'a App, node: &'a NodeData) -> Vec<Line
     lines
 }
 
+/// Build the column picker sub-panel and the picked column's lineage chain
+fn detail_column_trace_lines(
+    app: &App,
+    selected: petgraph::stable_graph::NodeIndex,
+) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+    if !app.show_column_lineage {
+        return lines;
+    }
+
+    let columns = app.selectable_columns(selected);
+    if columns.is_empty() {
+        return lines;
+    }
+    let picked = app.selected_column_name();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Trace column:", Style::default().bold()),
+        Span::styled(" [/] to pick", Style::default().fg(Color::DarkGray)),
+    ]));
+    for col in &columns {
+        if picked.as_deref() == Some(col.as_str()) {
+            lines.push(Line::from(Span::styled(
+                format!("\u{25b8} {}", col),
+                Style::default().fg(Color::Black).bg(Color::White),
+            )));
+        } else {
+            lines.push(Line::from(format!("  {}", col)));
+        }
+    }
+
+    let Some(column) = picked else {
+        return lines;
+    };
+    let unique_id = &app.graph[selected].unique_id;
+    let chain = app.column_lineage.trace_column(unique_id, &column);
+    if chain.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  (no column lineage)",
+            Style::default().fg(Color::DarkGray),
+        )));
+        return lines;
+    }
+
+    lines.push(Line::from(vec![Span::styled(
+        format!("Chain ({}):", chain.len()),
+        Style::default().bold(),
+    )]));
+    for edge in chain {
+        let source = if edge.source_column.is_empty() {
+            edge.source_node.clone()
+        } else {
+            format!("{}.{}", edge.source_node, edge.source_column)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {} ", source)),
+            Span::styled("\u{2192} ", Style::default().fg(Color::DarkGray)),
+            Span::raw(format!("{}.{}", edge.target_node, edge.target_column)),
+        ]));
+    }
+    lines
+}
+
 /// Build upstream and downstream neighbor lines
 fn detail_neighbors_lines(app: &App, selected: petgraph::stable_graph::NodeIndex) -> Vec<Line<'_>> {
     let mut lines = Vec::new();
 
+    if app.graph[selected].node_type != NodeType::Source {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Ultimate sources: ", Style::default().bold()),
+            Span::raw(upstream_source_count(&app.graph, selected).to_string()),
+        ]));
+    }
+
     let upstream = app.upstream_of(selected);
     if !upstream.is_empty() {
         lines.push(Line::from(""));
@@ -318,8 +475,12 @@ fn detail_impact_lines(app: &App, selected: petgraph::stable_graph::NodeIndex) -
     if app.path_highlight_source != Some(selected) {
         return lines;
     }
+    // The TUI only computes downstream reports, which always carry a severity
+    let Some(severity) = report.overall_severity else {
+        return lines;
+    };
 
-    let severity_color = match report.overall_severity {
+    let severity_color = match severity {
         crate::graph::impact::ImpactSeverity::Critical => Color::Red,
         crate::graph::impact::ImpactSeverity::High => Color::LightRed,
         crate::graph::impact::ImpactSeverity::Medium => Color::Yellow,
@@ -332,10 +493,7 @@ fn detail_impact_lines(app: &App, selected: petgraph::stable_graph::NodeIndex) -
     )]));
     lines.push(Line::from(vec![
         Span::styled("  Severity: ", Style::default().bold()),
-        Span::styled(
-            report.overall_severity.label(),
-            Style::default().fg(severity_color),
-        ),
+        Span::styled(severity.label(), Style::default().fg(severity_color)),
     ]));
     lines.push(Line::from(format!(
         "  Affected: {} models, {} tests, {} exposures",
@@ -360,10 +518,25 @@ fn draw_help_bar(f: &mut Frame, app: &App, area: Rect) {
             )
         }
         AppMode::RunMenu | AppMode::ContextMenu => {
-            " r: run | u: +upstream | d: downstream+ | a: +all+ | t: test | Esc: cancel".to_string()
+            " r: run | u: +upstream | d: downstream+ | a: +all+ | t: test | R/U/D/A/T: show command | Esc: cancel".to_string()
         }
         AppMode::RunConfirm => " y/Enter: execute | n/Esc: cancel".to_string(),
+        AppMode::CommandPreview => {
+            let mut help = String::from(" y: copy command | Enter/Esc/q: close");
+            if let Some(msg) = &app.status_message {
+                help.push_str(&format!(" | [{}]", msg));
+            }
+            help
+        }
         AppMode::RunOutput => " j/k: scroll | G: bottom | Esc/q: close".to_string(),
+        AppMode::NodeList => format!(
+            " Jump to: {}_ | type a name to jump | \u{2191}\u{2193}: move | Enter/Esc: back to graph",
+            app.type_ahead
+        ),
+        AppMode::SqlView => {
+            " j/k: scroll | Space/PgUp/PgDn: page | g/G: top/bottom | v/Esc/q: close".to_string()
+        }
+        AppMode::PhantomList => " j/k: move | Enter: jump to node | u/Esc/q: close".to_string(),
         AppMode::Filter => {
             let mut help = String::from(
                 " FILTER: m: models | s: sources | e: exposures | t: tests | d: seeds | 1: errored | 2: success | 3: never-run | 0: clear status | Esc: done",
@@ -378,29 +551,75 @@ fn draw_help_bar(f: &mut Frame, app: &App, area: Rect) {
     let style = match app.mode {
         AppMode::Normal => Style::default().bg(Color::DarkGray).fg(Color::White),
         AppMode::Search => Style::default().bg(Color::Blue).fg(Color::White),
+        AppMode::NodeList => Style::default().bg(Color::Green).fg(Color::Black),
         AppMode::RunMenu | AppMode::ContextMenu => {
             Style::default().bg(Color::Magenta).fg(Color::White)
         }
-        AppMode::RunConfirm => Style::default().bg(Color::Yellow).fg(Color::Black),
-        AppMode::RunOutput => Style::default().bg(Color::Cyan).fg(Color::Black),
+        AppMode::RunConfirm | AppMode::CommandPreview => {
+            Style::default().bg(Color::Yellow).fg(Color::Black)
+        }
+        AppMode::RunOutput | AppMode::SqlView | AppMode::PhantomList => {
+            Style::default().bg(Color::Cyan).fg(Color::Black)
+        }
         AppMode::Filter => Style::default().bg(Color::LightYellow).fg(Color::Black),
     };
 
-    let help = Paragraph::new(text).style(style);
+    let summary = format_graph_summary(&app.graph_summary(), area.width as usize);
+    let help = Paragraph::new(vec![Line::from(text), Line::from(summary)]).style(style);
     f.render_widget(help, area);
 }
 
+/// Graph totals on one line of at most `width` characters. The per-type counts
+/// and then the edge count are dropped when they don't fit; what is left is cut
+/// with an ellipsis.
+fn format_graph_summary(summary: &GraphSummary, width: usize) -> String {
+    let plural = |count: usize, noun: &str| {
+        format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+    };
+    let nodes = plural(summary.nodes, "node");
+    let edges = plural(summary.edges, "edge");
+    let filtered = format!("{} filtered out", summary.filtered_out);
+    let by_type = summary
+        .by_type
+        .iter()
+        .map(|&(nt, count)| plural(count, nt.label()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let candidates = [
+        format!(" {} ({}) | {} | {}", nodes, by_type, edges, filtered),
+        format!(" {} | {} | {}", nodes, edges, filtered),
+        format!(" {} | {}", nodes, filtered),
+    ];
+    let shortest = match candidates.iter().find(|c| c.chars().count() <= width) {
+        Some(fits) => return fits.clone(),
+        None => &candidates[2],
+    };
+    if width == 0 {
+        return String::new();
+    }
+    let mut cut: String = shortest.chars().take(width - 1).collect();
+    cut.push('\u{2026}');
+    cut
+}
+
 /// Build the help text for Normal mode with conditional segments
 fn build_normal_help_text(app: &App) -> String {
     let mut help = String::from(
-        " hjkl/\u{2190}\u{2193}\u{2191}\u{2192}: navigate | HJKL: pan | +/-: zoom | Tab: cycle | /: search | n: nodes | f: filter | p: path | r: reset | x: run",
+        " hjkl/\u{2190}\u{2193}\u{2191}\u{2192}: navigate | HJKL: pan | +/-: zoom | z: fit | Tab: cycle | /: search | n: nodes | f: filter | p: path | m: main line | P: path A\u{2192}B | r: reset | x: run",
     );
     if app.show_node_list {
-        help.push_str(" | c: collapse");
+        help.push_str(" | c: collapse | N: jump in list");
+    }
+    if app.selected_node.is_some() {
+        help.push_str(" | v: view SQL | e: explain edge | y/Y: copy id/name");
     }
     if app.has_run_output() {
         help.push_str(" | o: output");
     }
+    if app.has_phantoms() {
+        help.push_str(" | u: phantoms");
+    }
     if app.is_run_in_progress() {
         help.push_str(" | [running...]");
     }
@@ -411,9 +630,21 @@ fn build_normal_help_text(app: &App) -> String {
         help.push_str(" | [path]");
     }
     if app.show_column_lineage {
-        help.push_str(" | [columns]");
+        help.push_str(" | [columns] | [/]: trace column");
+    }
+    if !app.back_history.is_empty() {
+        help.push_str(" | Bksp: back");
+    }
+    if !app.forward_history.is_empty() {
+        help.push_str(" | >: forward");
     }
     help.push_str(" | C: columns | q: quit");
+    if app.graph_loader.is_some() {
+        help.push_str(&format!(" | M: [{}]", app.graph_source.label()));
+    }
+    if let Some(msg) = &app.status_message {
+        help.push_str(&format!(" | [{}]", msg));
+    }
     help
 }
 
@@ -442,6 +673,10 @@ fn draw_run_menu(f: &mut Frame, app: &mut App) {
         menu_item_line("  a", "  dbt run +all+", hover == Some(3)),
         menu_item_line("  t", "  dbt test", hover == Some(4)),
         Line::from(""),
+        Line::from(Span::styled(
+            "  Shift+key: show the command only",
+            Style::default().fg(Color::DarkGray),
+        )),
         Line::from(Span::styled(
             "  Esc to cancel",
             Style::default().fg(Color::DarkGray),
@@ -493,6 +728,10 @@ fn draw_context_menu(f: &mut Frame, app: &mut App) {
         menu_item_line(" a", "  dbt run +all+", hover == Some(3)),
         menu_item_line(" t", "  dbt test", hover == Some(4)),
         Line::from(""),
+        Line::from(Span::styled(
+            " Shift+key: show command",
+            Style::default().fg(Color::DarkGray),
+        )),
         Line::from(Span::styled(
             " Esc to close",
             Style::default().fg(Color::DarkGray),
@@ -554,6 +793,47 @@ fn draw_run_confirm(f: &mut Frame, app: &mut App) {
     f.render_widget(paragraph, popup);
 }
 
+/// The command a menu entry would run, wrapped to fit, for reading or copying
+fn draw_command_preview(f: &mut Frame, app: &App) {
+    let Some(request) = &app.pending_run else {
+        return;
+    };
+    let command = format!("$ {}", request.display_command());
+    let area = f.area();
+    let width =
+        (command.chars().count() as u16 + 6).clamp(40, area.width.saturating_sub(4).max(40));
+    let inner_width = width.saturating_sub(4).max(1) as usize;
+    let command_lines: Vec<String> = command
+        .chars()
+        .collect::<Vec<_>>()
+        .chunks(inner_width)
+        .map(|chunk| chunk.iter().collect())
+        .collect();
+    let popup = centered_rect(width, command_lines.len() as u16 + 5, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Command (not run) ")
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let mut text = vec![Line::from("")];
+    text.extend(command_lines.into_iter().map(|line| {
+        Line::from(Span::styled(
+            format!("  {}", line),
+            Style::default().bold().fg(Color::Cyan),
+        ))
+    }));
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "  y to copy, Esc to close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let paragraph = Paragraph::new(text).block(block);
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
 fn draw_run_output(f: &mut Frame, app: &App) {
     let area = f.area();
     // Full-screen overlay with 2-cell margin
@@ -582,7 +862,7 @@ fn draw_run_output(f: &mut Frame, app: &App) {
     };
 
     let title = if is_running {
-        " dbt (running...) "
+        " dbt (running... x to cancel) "
     } else if success {
         " dbt (success) "
     } else {
@@ -613,6 +893,162 @@ fn draw_run_output(f: &mut Frame, app: &App) {
     f.render_widget(paragraph, popup);
 }
 
+fn draw_sql_view(f: &mut Frame, app: &App) {
+    let Some(view) = &app.sql_view else {
+        return;
+    };
+    let area = f.area();
+    // Same full-screen overlay as the run output pane
+    let popup = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", view.title))
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(popup);
+    let visible_height = inner.height as usize;
+
+    // Clamp scroll
+    let max_scroll = view.lines.len().saturating_sub(visible_height);
+    let scroll = app.sql_view_scroll.min(max_scroll);
+    let number_width = view.lines.len().to_string().len();
+
+    let text_lines: Vec<Line> = view
+        .lines
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_height)
+        .map(|(i, l)| {
+            let mut spans = vec![Span::styled(
+                format!("{:>width$} \u{2502} ", i + 1, width = number_width),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(sql_line_spans(l));
+            Line::from(spans)
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(text_lines).block(block);
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
+/// Phantom nodes, one line each with the files that reference them and, for a
+/// likely typo, the closest existing names
+fn draw_phantom_list(f: &mut Frame, app: &App) {
+    let area = f.area();
+    // Same full-screen overlay as the SQL pane
+    let popup = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Phantom nodes ({}) ", app.phantom_list.len()))
+        .border_style(Style::default().fg(node_color(NodeType::Phantom)));
+
+    let items: Vec<ListItem> = app
+        .phantom_list
+        .iter()
+        .map(|phantom| {
+            let kind = match phantom.kind {
+                PhantomKind::Ref => "ref",
+                PhantomKind::Source => "source",
+            };
+            let referenced_by = if phantom.referenced_by.is_empty() {
+                "no referencing file".to_string()
+            } else {
+                phantom.referenced_by.join(", ")
+            };
+            let mut spans = vec![
+                Span::styled(phantom.label.as_str(), Style::default().bold()),
+                Span::styled(
+                    format!(" ({}) ", kind),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(format!("\u{2190} {}", referenced_by)),
+            ];
+            if !phantom.suggestions.is_empty() {
+                spans.push(Span::styled(
+                    format!("  did you mean {}?", phantom.suggestions.join(", ")),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("\u{25b6} ");
+    let mut state = ListState::default().with_selected(Some(app.phantom_cursor));
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// Split a line of dbt SQL into spans, highlighting Jinja blocks and `--` comments.
+fn sql_line_spans(line: &str) -> Vec<Span<'_>> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+    loop {
+        let jinja = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| rest.find(open))
+            .min();
+        let comment = rest.find("--");
+        match (jinja, comment) {
+            (_, Some(c)) if jinja.is_none_or(|j| c < j) => {
+                if c > 0 {
+                    spans.push(Span::raw(&rest[..c]));
+                }
+                spans.push(Span::styled(
+                    &rest[c..],
+                    Style::default().fg(Color::DarkGray),
+                ));
+                return spans;
+            }
+            (Some(j), _) => {
+                if j > 0 {
+                    spans.push(Span::raw(&rest[..j]));
+                }
+                let close = match &rest[j..j + 2] {
+                    "{{" => "}}",
+                    "{%" => "%}",
+                    _ => "#}",
+                };
+                let end = rest[j + 2..]
+                    .find(close)
+                    .map_or(rest.len(), |e| j + 2 + e + 2);
+                let color = if close == "#}" {
+                    Color::DarkGray
+                } else {
+                    Color::Yellow
+                };
+                spans.push(Span::styled(&rest[j..end], Style::default().fg(color)));
+                rest = &rest[end..];
+                if rest.is_empty() {
+                    return spans;
+                }
+            }
+            _ => {
+                spans.push(Span::raw(rest));
+                return spans;
+            }
+        }
+    }
+}
+
 /// Build a single menu item line with optional hover highlight.
 fn menu_item_line<'a>(key: &'a str, desc: &'a str, hovered: bool) -> Line<'a> {
     let line = Line::from(vec![
@@ -664,6 +1100,79 @@ mod tests {
         assert_eq!(node_color(NodeType::Phantom), Color::DarkGray);
     }
 
+    #[test]
+    fn test_format_graph_summary_drops_detail_to_fit() {
+        let summary = GraphSummary {
+            nodes: 12,
+            edges: 1,
+            by_type: vec![
+                (NodeType::Model, 9),
+                (NodeType::Source, 1),
+                (NodeType::Test, 2),
+            ],
+            filtered_out: 3,
+        };
+        let full = " 12 nodes (9 models, 1 source, 2 tests) | 1 edge | 3 filtered out";
+        assert_eq!(format_graph_summary(&summary, 200), full);
+        assert_eq!(
+            format_graph_summary(&summary, full.len()),
+            full,
+            "exactly as wide as the line"
+        );
+        assert_eq!(
+            format_graph_summary(&summary, 40),
+            " 12 nodes | 1 edge | 3 filtered out"
+        );
+        assert_eq!(
+            format_graph_summary(&summary, 30),
+            " 12 nodes | 3 filtered out"
+        );
+        assert_eq!(format_graph_summary(&summary, 10), " 12 nodes\u{2026}");
+        assert_eq!(format_graph_summary(&summary, 0), "");
+    }
+
+    #[test]
+    fn test_detail_snapshot_lines() {
+        let text = |snapshot: Option<SnapshotConfig>| {
+            let node = NodeData {
+                unique_id: "snapshot.snap_orders".into(),
+                label: "snap_orders".into(),
+                node_type: NodeType::Snapshot,
+                snapshot,
+                ..Default::default()
+            };
+            detail_snapshot_lines(&node)
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert!(text(None).is_empty());
+        assert_eq!(
+            text(Some(SnapshotConfig {
+                strategy: Some("timestamp".into()),
+                unique_key: vec!["order_id".into()],
+                updated_at: Some("updated_at".into()),
+                check_cols: vec![],
+            })),
+            vec![
+                "Strategy: timestamp (updated_at: updated_at)",
+                "Unique key: order_id"
+            ]
+        );
+        assert_eq!(
+            text(Some(SnapshotConfig {
+                strategy: Some("check".into()),
+                unique_key: vec!["id".into(), "region".into()],
+                updated_at: None,
+                check_cols: vec!["status".into(), "amount".into()],
+            })),
+            vec![
+                "Strategy: check (check_cols: status, amount)",
+                "Unique key: id, region"
+            ]
+        );
+    }
+
     #[test]
     fn test_centered_rect() {
         let area = Rect::new(0, 0, 100, 50);
@@ -697,4 +1206,27 @@ mod tests {
         // Hovered lines have a background style
         assert_eq!(line.style.bg, Some(Color::DarkGray));
     }
+
+    #[test]
+    fn test_sql_line_spans_highlights_jinja_and_comments() {
+        let spans = sql_line_spans("select a from {{ ref('x') }} -- note");
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            texts,
+            vec!["select a from ", "{{ ref('x') }}", " ", "-- note"]
+        );
+        assert_eq!(spans[1].style.fg, Some(Color::Yellow));
+        assert_eq!(spans[3].style.fg, Some(Color::DarkGray));
+
+        let spans = sql_line_spans("{% if x %}{# c #}");
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["{% if x %}", "{# c #}"]);
+
+        // Unterminated block runs to the end of the line
+        let spans = sql_line_spans("a {{ b");
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["a ", "{{ b"]);
+
+        assert_eq!(sql_line_spans("plain").len(), 1);
+    }
 }
@@ -133,6 +133,45 @@ mod artifacts {
     }
 }
 
+mod file_paths {
+    use super::*;
+    use dbt_lineage::graph::types::LineageGraph;
+    use std::collections::HashMap;
+
+    fn file_paths(graph: &LineageGraph) -> HashMap<String, Option<PathBuf>> {
+        graph
+            .node_indices()
+            .map(|i| (graph[i].unique_id.clone(), graph[i].file_path.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sql_and_manifest_file_paths_match() {
+        let dir = fixture_dir();
+        let project = dbt_lineage::parser::project::DbtProject::load(&dir).unwrap();
+        let files =
+            dbt_lineage::parser::discovery::discover_files(&project.resolve_paths(&dir)).unwrap();
+        let from_sql = dbt_lineage::graph::builder::build_graph(&dir, &files).unwrap();
+        let from_manifest = dbt_lineage::parser::manifest::build_graph_from_manifest(
+            &dir.join("target").join("manifest.json"),
+        )
+        .unwrap();
+
+        let sql_paths = file_paths(&from_sql);
+        let manifest_paths = file_paths(&from_manifest);
+        for id in [
+            "model.stg_orders",
+            "model.orders",
+            "seed.countries",
+            "source.raw.orders",
+        ] {
+            let sql_path = sql_paths[id].as_ref().unwrap();
+            assert!(sql_path.is_relative(), "{} should be project-relative", id);
+            assert_eq!(Some(sql_path), manifest_paths[id].as_ref(), "{}", id);
+        }
+    }
+}
+
 mod cli {
     use std::process::Command;
 
@@ -192,6 +231,258 @@ mod cli {
         );
     }
 
+    #[test]
+    fn test_docs_subcommand() {
+        let fixture = super::fixture_dir();
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("docs");
+        let output = Command::new(binary_path())
+            .args([
+                "docs",
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "--output-dir",
+                out.to_str().unwrap(),
+                "-s",
+                "path:models/staging orders",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Wrote 4 pages"), "{}", stderr);
+
+        let mut pages: Vec<String> = std::fs::read_dir(&out)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        pages.sort();
+        assert_eq!(
+            pages,
+            [
+                "orders.md",
+                "stg_customers.md",
+                "stg_orders.md",
+                "stg_payments.md"
+            ]
+        );
+        let orders = std::fs::read_to_string(out.join("orders.md")).unwrap();
+        assert!(
+            orders.starts_with("# orders\n\n`model.orders`\n"),
+            "{}",
+            orders
+        );
+        assert!(orders.contains("## Upstream\n\n"));
+        assert!(orders.contains("- [stg_orders](stg_orders.md) (model)"));
+        // customers has no page of its own in this subset, so it isn't linked
+        assert!(orders.contains("- customers (model)"), "{}", orders);
+    }
+
+    #[test]
+    fn test_list_subcommand() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args([
+                "list",
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "--type",
+                "model,source",
+                "-s",
+                "path:models/staging",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "model.stg_customers",
+                "model.stg_orders",
+                "model.stg_payments",
+                // Sources are declared in models/staging/schema.yml
+                "source.raw.customers",
+                "source.raw.orders",
+                "source.raw.payments",
+            ]
+        );
+
+        let output = Command::new(binary_path())
+            .args([
+                "list",
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "--type",
+                "model",
+                "--labels",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.lines().any(|l| l == "customers"));
+        assert!(!stdout.contains("model."));
+    }
+
+    #[test]
+    fn test_svg_with_status() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "-o",
+                "svg",
+                "--with-status",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // orders failed in the fixture's run_results.json; sources were never run
+        assert!(stdout.contains(r#"data-id="model.orders" class="node" data-status="error""#));
+        assert!(
+            stdout.contains(r#"data-id="source.raw.orders" class="node" data-status="never_run""#)
+        );
+        assert!(stdout.contains(">skipped / never run</text>"));
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let fixture = super::fixture_dir();
+        let dir = tempfile::tempdir().unwrap();
+        let map_path = dir.path().join("names.json");
+        let run = || {
+            Command::new(binary_path())
+                .args(["--project-dir", fixture.to_str().unwrap(), "-o", "json"])
+                .args(["--anonymize", "--anonymize-map", map_path.to_str().unwrap()])
+                .output()
+                .expect("Failed to run binary")
+        };
+        let output = run();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains("orders"));
+        assert!(!stdout.contains(".sql"));
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let plain = Command::new(binary_path())
+            .args(["--project-dir", fixture.to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("Failed to run binary");
+        let plain: serde_json::Value = serde_json::from_slice(&plain.stdout).unwrap();
+        assert_eq!(
+            json["nodes"].as_array().unwrap().len(),
+            plain["nodes"].as_array().unwrap().len()
+        );
+        assert_eq!(
+            json["edges"].as_array().unwrap().len(),
+            plain["edges"].as_array().unwrap().len()
+        );
+
+        // The map leads back to the real unique_ids, and names don't change between runs
+        let map: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&map_path).unwrap()).unwrap();
+        let plain_ids: Vec<&serde_json::Value> = plain["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| &n["unique_id"])
+            .collect();
+        for node in json["nodes"].as_array().unwrap() {
+            let label = node["label"].as_str().unwrap();
+            assert!(plain_ids.contains(&&map[label]), "{} not mapped", label);
+        }
+        let labels = |json: &serde_json::Value| {
+            let mut labels: Vec<String> = json["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["label"].as_str().unwrap().to_string())
+                .collect();
+            labels.sort();
+            labels
+        };
+        let again: serde_json::Value = serde_json::from_slice(&run().stdout).unwrap();
+        assert_eq!(labels(&again), labels(&json));
+
+        let stderr = run_failing(&["--anonymize-map", "names.json"]);
+        assert!(stderr.contains("--anonymize"));
+    }
+
+    #[test]
+    fn test_with_status_from_other_run_results() {
+        let fixture = super::fixture_dir();
+        let dir = tempfile::tempdir().unwrap();
+        let prod = dir.path().join("prod_run_results.json");
+        std::fs::write(
+            &prod,
+            r#"{"results": [{"unique_id": "model.simple_project.orders", "status": "success", "timing": [{"name": "execute", "completed_at": "2999-01-01T00:00:00Z"}]}]}"#,
+        )
+        .unwrap();
+        let run = |path: &std::path::Path| {
+            Command::new(binary_path())
+                .args(["--project-dir", fixture.to_str().unwrap(), "-o", "svg"])
+                .args(["--with-status", "--run-results", path.to_str().unwrap()])
+                .output()
+                .expect("Failed to run binary")
+        };
+
+        let output = run(&prod);
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(r#"data-id="model.orders" class="node" data-status="success""#));
+
+        // A missing file falls back to never run, naming the path it looked for
+        let missing = dir.path().join("missing.json");
+        let output = run(&missing);
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(r#"data-id="model.orders" class="node" data-status="never_run""#));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("missing.json not found"));
+    }
+
+    #[test]
+    fn test_list_sort_status_from_other_run_results() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(models.join("customers.sql"), "select 1").unwrap();
+        std::fs::write(models.join("orders.sql"), "select 1").unwrap();
+        std::fs::create_dir_all(dir.path().join("prod")).unwrap();
+        std::fs::write(
+            dir.path().join("prod/run_results.json"),
+            r#"{"results": [{"unique_id": "model.empty.orders", "status": "error"}]}"#,
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+        let list = |extra: &[&str]| {
+            let output = Command::new(binary_path())
+                .args(["list", "-p", project, "--labels", "--sort", "status"])
+                .args(extra)
+                .output()
+                .expect("Failed to run binary");
+            assert!(output.status.success());
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+
+        // Nothing under target/: both never run, so by name
+        assert_eq!(list(&[]), "customers\norders\n");
+        let prod = dir.path().join("prod/run_results.json");
+        assert_eq!(
+            list(&["--run-results", prod.to_str().unwrap()]),
+            "orders\ncustomers\n"
+        );
+
+        std::fs::write(
+            dir.path().join(".dbt-lineage.yml"),
+            "run-results: prod/run_results.json\n",
+        )
+        .unwrap();
+        assert_eq!(list(&[]), "orders\ncustomers\n");
+    }
+
     #[test]
     fn test_dot_output() {
         let fixture = super::fixture_dir();
@@ -292,4 +583,1378 @@ mod cli {
         assert!(output.status.success());
         assert!(stdout.contains("assert_orders_positive_amount"));
     }
+
+    #[test]
+    fn test_tests_filter_by_kind() {
+        let fixture = super::fixture_dir();
+        let run = |kind: &str| -> serde_json::Value {
+            let output = Command::new(binary_path())
+                .args(["-p", fixture.to_str().unwrap(), "-o", "json"])
+                .args(["--include-tests", "--tests", kind])
+                .output()
+                .expect("Failed to run binary");
+            assert!(output.status.success());
+            serde_json::from_slice(&output.stdout).unwrap()
+        };
+
+        let graph = run("singular");
+        let test = node_json(&graph, "test.assert_orders_positive_amount");
+        assert_eq!(test["test_kind"], "singular");
+
+        let graph = run("generic");
+        assert!(!graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|n| n["node_type"] == "test"));
+    }
+
+    #[test]
+    fn test_sort_orders_list_and_json_export() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args(["list", "-p", fixture.to_str().unwrap()])
+            .args(["--type", "model", "--sort", "downstream"])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "model.stg_orders",
+                "model.stg_payments",
+                "model.orders",
+                "model.stg_customers",
+                "model.customers",
+            ]
+        );
+
+        let output = Command::new(binary_path())
+            .args([
+                "-p",
+                fixture.to_str().unwrap(),
+                "-o",
+                "json",
+                "--sort",
+                "name",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let labels: Vec<&str> = graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["label"].as_str().unwrap())
+            .collect();
+        let mut sorted = labels.clone();
+        sorted.sort_unstable();
+        assert_eq!(labels, sorted);
+    }
+
+    fn node_json<'a>(graph: &'a serde_json::Value, unique_id: &str) -> &'a serde_json::Value {
+        graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["unique_id"] == unique_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_catalog_detected_next_to_manifest() {
+        let fixture = super::fixture_dir();
+        let manifest = fixture.join("target").join("manifest.json");
+        let output = Command::new(binary_path())
+            .args(["--manifest", manifest.to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("Failed to run binary");
+
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let stg_orders = node_json(&graph, "model.stg_orders");
+        assert_eq!(stg_orders["columns"][0], "order_id");
+        assert_eq!(stg_orders["column_types"]["order_date"], "DATE");
+        let raw_orders = node_json(&graph, "source.raw.orders");
+        assert_eq!(raw_orders["column_types"]["user_id"], "INTEGER");
+    }
+
+    #[test]
+    fn test_multiple_manifests_merge_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let core = dir.path().join("core.json");
+        let finance = dir.path().join("finance.json");
+        std::fs::write(
+            &core,
+            r#"{"nodes": {"model.core.customers": {
+                "unique_id": "model.core.customers", "name": "customers", "resource_type": "model"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &finance,
+            r#"{"nodes": {"model.finance.revenue": {
+                "unique_id": "model.finance.revenue", "name": "revenue", "resource_type": "model",
+                "depends_on": {"nodes": ["model.core.customers"]}}}}"#,
+        )
+        .unwrap();
+
+        let output = Command::new(binary_path())
+            .args(["--manifest", core.to_str().unwrap()])
+            .args(["--manifest", finance.to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let customers = node_json(&graph, "model.core.customers");
+        assert_eq!(customers["project"], "core");
+        assert_eq!(customers["cross_project"], true);
+        assert_eq!(
+            node_json(&graph, "model.finance.revenue")["project"],
+            "finance"
+        );
+        let edge = &graph["edges"][0];
+        assert_eq!(edge["source"], "model.core.customers");
+        assert_eq!(edge["target"], "model.finance.revenue");
+        assert_eq!(edge["cross_project"], true);
+    }
+
+    #[test]
+    fn test_json_inherits_column_descriptions_downstream() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args(["--project-dir", fixture.to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("Failed to run binary");
+
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let stg_orders = node_json(&graph, "model.stg_orders");
+        let own = &stg_orders["column_descriptions"]["order_id"];
+        assert_eq!(own["description"], "Primary key of the order");
+        assert!(own.get("inherited_from").is_none());
+
+        let orders = node_json(&graph, "model.orders");
+        let inherited = &orders["column_descriptions"]["order_id"];
+        assert_eq!(inherited["description"], "Primary key of the order");
+        assert_eq!(inherited["inherited_from"]["node"], "model.stg_orders");
+        assert_eq!(inherited["inherited_from"]["column"], "order_id");
+    }
+
+    #[test]
+    fn test_csv_output_writes_nodes_and_edges() {
+        let fixture = super::fixture_dir();
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path().join("lineage");
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "-o",
+                "csv",
+                "--output-file",
+                prefix.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+
+        let nodes = std::fs::read_to_string(dir.path().join("lineage_nodes.csv")).unwrap();
+        let mut lines = nodes.lines();
+        assert_eq!(
+            lines.next(),
+            Some("unique_id,label,node_type,materialization,tags,file_path,description")
+        );
+        assert!(nodes.contains("\nmodel.orders,orders,model,"), "{}", nodes);
+
+        let edges = std::fs::read_to_string(dir.path().join("lineage_edges.csv")).unwrap();
+        assert!(edges.starts_with("source_id,target_id,edge_type\n"));
+        assert!(
+            edges.contains("model.stg_orders,model.orders,ref"),
+            "{}",
+            edges
+        );
+    }
+
+    #[test]
+    fn test_csv_output_requires_output_file() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args(["--project-dir", fixture.to_str().unwrap(), "-o", "csv"])
+            .output()
+            .expect("Failed to run binary");
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--output-file"), "{}", stderr);
+
+        // Checked before the project is parsed, so a project without models
+        // still reports the missing flag
+        let dir = empty_project();
+        let project = dir.path().to_str().unwrap();
+        let stderr = run_failing(&["-p", project, "-o", "csv"]);
+        assert!(stderr.contains("needs --output-file"), "{}", stderr);
+        let stderr = run_failing(&["path", "a", "b", "-p", project, "-o", "csv"]);
+        assert!(stderr.contains("needs --output-file"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_select_from_file_and_stdin() {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let list = dir.path().join("changed.txt");
+        std::fs::write(&list, "# changed models\norders\nstg_orders\n").unwrap();
+        let arg = format!("@{}", list.display());
+
+        let output = Command::new(binary_path())
+            .args(["list", "--project-dir", project, "--labels", "-s", &arg])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "orders\nstg_orders\n"
+        );
+
+        let mut child = Command::new(binary_path())
+            .args(["--project-dir", project, "-o", "json", "-s", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to run binary");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"customers\n")
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let nodes = json["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0]["label"], "customers");
+
+        let missing = format!("@{}", dir.path().join("missing.txt").display());
+        let output = Command::new(binary_path())
+            .args(["--project-dir", project, "-s", &missing])
+            .output()
+            .expect("Failed to run binary");
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    #[test]
+    fn test_tree_output() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args([
+                "orders",
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "-u",
+                "2",
+                "-o",
+                "tree",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.starts_with("orders (upstream)\n"), "{}", stdout);
+        assert!(stdout.contains("├── stg_orders\n│   └── src:raw.orders"));
+        assert!(!stdout.contains("(downstream)"));
+
+        let stderr = run_failing(&["--project-dir", fixture.to_str().unwrap(), "-o", "tree"]);
+        assert!(stderr.contains("-o tree needs a model"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_json_tree_output() {
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let output = Command::new(binary_path())
+            .args([
+                "orders",
+                "--project-dir",
+                project,
+                "-u",
+                "2",
+                "-o",
+                "json",
+                "--json-shape",
+                "tree",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let tree: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(tree["node"]["label"], "orders");
+        let stg_orders = tree["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|child| child["node"]["label"] == "stg_orders")
+            .expect("stg_orders upstream of orders");
+        assert_eq!(stg_orders["children"][0]["node"]["node_type"], "source");
+
+        let stderr = run_failing(&[
+            "--project-dir",
+            project,
+            "-o",
+            "json",
+            "--json-shape",
+            "tree",
+        ]);
+        assert!(
+            stderr.contains("--json-shape tree needs a model"),
+            "{}",
+            stderr
+        );
+        let stderr = run_failing(&["orders", "--project-dir", project, "--json-shape", "tree"]);
+        assert!(stderr.contains("only applies to -o json"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_materialized_flag_keeps_only_matching_nodes() {
+        let fixture = super::fixture_dir();
+        let manifest = fixture.join("target").join("manifest.json");
+        let output = Command::new(binary_path())
+            .args([
+                "--manifest",
+                manifest.to_str().unwrap(),
+                "--materialized",
+                "table",
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let nodes = graph["nodes"].as_array().unwrap();
+        assert!(!nodes.is_empty());
+        assert!(nodes.iter().all(|n| n["materialization"] == "table"));
+    }
+
+    #[test]
+    fn test_collapse_group_replaces_directory_with_meta_node() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "--collapse-group",
+                "models/staging",
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let meta = node_json(&graph, "group.models/staging");
+        assert!(meta["label"]
+            .as_str()
+            .unwrap()
+            .starts_with("models/staging ("));
+        let nodes = graph["nodes"].as_array().unwrap();
+        assert!(!nodes.iter().any(|n| n["unique_id"] == "model.stg_orders"));
+        let edges = graph["edges"].as_array().unwrap();
+        assert!(edges
+            .iter()
+            .any(|e| e["source"] == "group.models/staging" && e["target"] == "model.orders"));
+    }
+
+    #[test]
+    fn test_trim_orphans_drops_unconnected_nodes() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "-s",
+                "path:models/marts/customers.sql stg_orders",
+                "--trim-orphans",
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        // customers has no edge within the selection; stg_orders was selected by name
+        let ids: Vec<&str> = graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["unique_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["model.stg_orders"]);
+    }
+
+    fn impact_json(args: &[&str]) -> serde_json::Value {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .arg("impact")
+            .args(args)
+            .args(["--project-dir", fixture.to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_impact_markdown_links_files() {
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let output = Command::new(binary_path())
+            .args(["impact", "raw.orders", "-p", project, "-o", "markdown"])
+            .args(["--link-prefix", "https://github.com/o/r/blob/main"])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.starts_with("### Impact analysis: `raw.orders`\n"));
+        assert!(stdout.contains("<summary>Models (3)</summary>"));
+        assert!(stdout.contains(
+            "| [`stg_orders`](https://github.com/o/r/blob/main/models/staging/stg_orders.sql) \
+             | medium | 1 | direct |"
+        ));
+        // Exposures have no file of their own
+        assert!(stdout.contains("| `weekly_report` | critical |"));
+
+        let stderr = run_failing(&["impact", "orders", "-p", project, "--link-prefix", "x"]);
+        assert!(stderr.contains("--link-prefix only applies to -o markdown"));
+    }
+
+    #[test]
+    fn test_impact_from_source_and_exposure() {
+        // `orders` is the model, not the `raw.orders` source
+        let report = impact_json(&["orders"]);
+        assert_eq!(report["source_node_type"], "model");
+
+        let report = impact_json(&["raw.orders"]);
+        assert_eq!(report["source_node_type"], "source");
+        assert_eq!(report["affected_exposures"], 1);
+        assert!(report["impacted_nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|n| n["unique_id"] == "exposure.weekly_report"));
+
+        let report = impact_json(&["weekly_report"]);
+        assert_eq!(report["source_node_type"], "exposure");
+        assert!(report["impacted_nodes"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dump_manifest_round_trips() {
+        let fixture = super::fixture_dir();
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("manifest.json");
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "--dump-manifest",
+            ])
+            .arg(&manifest)
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+
+        let dumped: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&manifest).unwrap()).unwrap();
+        assert!(dumped["nodes"]["model.simple_project.orders"].is_object());
+        assert!(dumped["sources"]["source.simple_project.raw.orders"].is_object());
+
+        let graph_json = |extra: &[&str]| -> (Vec<String>, Vec<String>) {
+            let output = Command::new(binary_path())
+                .args(["--project-dir", fixture.to_str().unwrap()])
+                .args(["--include-tests", "--include-seeds", "--include-snapshots"])
+                .args(["--include-exposures", "-o", "json"])
+                .args(extra)
+                .output()
+                .expect("Failed to run binary");
+            assert!(output.status.success());
+            let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+            let mut nodes: Vec<String> = graph["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| format!("{} {}", n["unique_id"], n["label"]))
+                .collect();
+            let mut edges: Vec<String> = graph["edges"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| format!("{} {} {}", e["source"], e["target"], e["edge_type"]))
+                .collect();
+            nodes.sort();
+            edges.sort();
+            (nodes, edges)
+        };
+        assert_eq!(
+            graph_json(&["--manifest", manifest.to_str().unwrap()]),
+            graph_json(&[])
+        );
+    }
+
+    #[test]
+    fn test_catalog_flag_with_sql_parsing() {
+        let fixture = super::fixture_dir();
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                fixture.to_str().unwrap(),
+                "--catalog",
+                fixture.to_str().unwrap(),
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let stg_orders = node_json(&graph, "model.stg_orders");
+        assert_eq!(stg_orders["column_types"]["status"], "TEXT");
+        // Models missing from the catalog keep their SQL-parsed columns
+        let customers = node_json(&graph, "model.stg_customers");
+        assert!(customers.get("column_types").is_none());
+        assert_eq!(customers["columns"][0], "customer_id");
+    }
+
+    fn empty_project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("dbt_project.yml"),
+            "name: empty\nversion: '1.0'\nmodel-paths: [\"models\"]\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    fn run_failing(args: &[&str]) -> String {
+        let output = Command::new(binary_path())
+            .args(args)
+            .output()
+            .expect("Failed to run binary");
+        assert!(!output.status.success());
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    }
+
+    #[test]
+    fn test_empty_project_reports_no_models() {
+        let dir = empty_project();
+        let project = dir.path().to_str().unwrap();
+
+        let stderr = run_failing(&["--project-dir", project]);
+        assert!(stderr.contains("no models found under"), "{}", stderr);
+        assert!(stderr.contains("models"));
+        assert!(stderr.contains("check --project-dir / model-paths"));
+
+        let stderr = run_failing(&["impact", "orders", "--project-dir", project]);
+        assert!(stderr.contains("no models found under"), "{}", stderr);
+
+        let stderr = run_failing(&["stats", "--project-dir", project]);
+        assert!(stderr.contains("no models found under"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_empty_project_refuses_tui() {
+        let dir = empty_project();
+        let stderr = run_failing(&["-i", "--project-dir", dir.path().to_str().unwrap()]);
+        assert!(stderr.contains("no models found under"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_empty_manifest_reports_no_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("manifest.json");
+        std::fs::write(&manifest, r#"{"nodes": {}, "sources": {}}"#).unwrap();
+
+        let stderr = run_failing(&["--manifest", manifest.to_str().unwrap()]);
+        assert!(stderr.contains("no nodes found in manifest"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_named_selector_from_selectors_yml() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(models.join("marts")).unwrap();
+        std::fs::write(models.join("stg_orders.sql"), "select 1 as id").unwrap();
+        std::fs::write(
+            models.join("marts").join("orders.sql"),
+            "{{ config(tags=['nightly']) }}\nselect * from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        std::fs::write(
+            models.join("marts").join("revenue.sql"),
+            "select * from {{ ref('orders') }}",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("selectors.yml"),
+            "selectors:\n  - name: nightly_marts\n    definition:\n      intersection:\n        \
+             - method: path\n          value: models/marts\n        - tag: nightly\n",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                project,
+                "--selector",
+                "nightly_marts",
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let labels: Vec<&str> = json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, ["orders"]);
+
+        let stderr = run_failing(&["--project-dir", project, "--selector", "weekly"]);
+        assert!(
+            stderr.contains("no selector named 'weekly' in selectors.yml (defined: nightly_marts)"),
+            "{}",
+            stderr
+        );
+    }
+
+    #[test]
+    fn test_check_layer_order_reports_backward_edge() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(
+            models.join("fct_orders.sql"),
+            "{{ config(tags=['marts']) }}\nselect 1",
+        )
+        .unwrap();
+        std::fs::write(
+            models.join("int_orders.sql"),
+            "{{ config(tags=['intermediate']) }}\nselect * from {{ ref('fct_orders') }}",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        let output = Command::new(binary_path())
+            .args([
+                "check",
+                "--layer-order",
+                "staging,intermediate,marts",
+                "--project-dir",
+                project,
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert_eq!(output.status.code(), Some(1));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("FAIL: 1 edge(s)"), "{}", stdout);
+        assert!(stdout.contains("model.fct_orders"), "{}", stdout);
+
+        let output = Command::new(binary_path())
+            .args([
+                "check",
+                "--layer-order",
+                "staging,marts,intermediate",
+                "--project-dir",
+                project,
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+    }
+
+    #[test]
+    fn test_impact_rebuild_weights_from_config() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(models.join("stg_orders.sql"), "select 1").unwrap();
+        std::fs::write(
+            models.join("orders.sql"),
+            "{{ config(materialized='table') }}\nselect * from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+        let cost = |args: &[&str]| {
+            let output = Command::new(binary_path())
+                .args(args)
+                .output()
+                .expect("Failed to run binary");
+            assert!(output.status.success());
+            let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+            report["rebuild_cost"].as_u64().unwrap()
+        };
+        let impact = [
+            "impact",
+            "stg_orders",
+            "-o",
+            "json",
+            "--project-dir",
+            project,
+        ];
+
+        assert_eq!(cost(&impact), 5);
+        std::fs::write(
+            dir.path().join(".dbt-lineage.yml"),
+            "rebuild-weights:\n  table: 12\n",
+        )
+        .unwrap();
+        assert_eq!(cost(&impact), 12);
+        let mut no_config = vec!["--no-config"];
+        no_config.extend(impact);
+        assert_eq!(cost(&no_config), 5);
+    }
+
+    #[test]
+    fn test_check_require_descriptions() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(models.join("marts")).unwrap();
+        std::fs::create_dir_all(models.join("staging")).unwrap();
+        std::fs::write(models.join("marts/orders.sql"), "select 1").unwrap();
+        std::fs::write(models.join("staging/stg_orders.sql"), "select 1").unwrap();
+        std::fs::write(
+            models.join("marts/schema.yml"),
+            "version: 2\nmodels:\n  - name: orders\n    description: One row per order\n",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        let output = Command::new(binary_path())
+            .args(["check", "--require-descriptions", "--project-dir", project])
+            .output()
+            .expect("Failed to run binary");
+        assert_eq!(output.status.code(), Some(1));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("50.0% (1/2 documented)"), "{}", stdout);
+        assert!(stdout.contains("stg_orders"), "{}", stdout);
+
+        let output = Command::new(binary_path())
+            .args([
+                "check",
+                "--require-descriptions",
+                "--select",
+                "path:models/marts",
+                "--project-dir",
+                project,
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("100.0% (1/1 documented)"), "{}", stdout);
+    }
+
+    #[test]
+    fn test_check_sla() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(
+            models.join("orders.sql"),
+            "{{ config(meta={'sla_hours': 6}) }}\nselect 1",
+        )
+        .unwrap();
+        std::fs::write(
+            models.join("payments.sql"),
+            "{{ config(meta={'sla_hours': 6}) }}\nselect 1",
+        )
+        .unwrap();
+        std::fs::write(models.join("customers.sql"), "select 1").unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        let stderr = run_failing(&["check", "--sla", "--project-dir", project]);
+        assert!(stderr.contains("--sla needs run results"), "{}", stderr);
+
+        // orders last succeeded long ago, payments "in the future", customers has no SLA
+        let result = |id: &str, at: &str| {
+            format!(
+                r#"{{"unique_id": "model.empty.{}", "status": "success", "timing": [{{"name": "execute", "completed_at": "{}"}}]}}"#,
+                id, at
+            )
+        };
+        std::fs::create_dir_all(dir.path().join("target")).unwrap();
+        std::fs::write(
+            dir.path().join("target/run_results.json"),
+            format!(
+                r#"{{"results": [{}, {}, {}]}}"#,
+                result("orders", "2020-01-01T00:00:00Z"),
+                result("payments", "2999-01-01T00:00:00Z"),
+                result("customers", "2020-01-01T00:00:00Z"),
+            ),
+        )
+        .unwrap();
+
+        let output = Command::new(binary_path())
+            .args(["check", "--sla", "--project-dir", project, "-o", "json"])
+            .output()
+            .expect("Failed to run binary");
+        assert_eq!(output.status.code(), Some(1));
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["checked"], 2);
+        assert_eq!(json["breaches"].as_array().unwrap().len(), 1);
+        assert_eq!(json["breaches"][0]["unique_id"], "model.orders");
+    }
+
+    #[test]
+    fn test_check_baseline() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(models.join("stg_orders.sql"), "select 1").unwrap();
+        std::fs::write(
+            models.join("orders.sql"),
+            "select * from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+        let baseline = dir.path().join("lineage.json");
+        let baseline = baseline.to_str().unwrap();
+        let check = |extra: &[&str]| {
+            Command::new(binary_path())
+                .args(["check", "--project-dir", project, "--baseline", baseline])
+                .args(extra)
+                .output()
+                .expect("Failed to run binary")
+        };
+
+        let stderr = run_failing(&["check", "--project-dir", project, "--baseline", baseline]);
+        assert!(stderr.contains("lineage.json"), "{}", stderr);
+
+        assert!(check(&["--write-baseline"]).status.success());
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(baseline).unwrap()).unwrap();
+        assert_eq!(written["format_version"], 1);
+        assert!(check(&[]).status.success());
+
+        // A new model downstream of orders is drift
+        std::fs::write(
+            models.join("order_stats.sql"),
+            "select * from {{ ref('orders') }}",
+        )
+        .unwrap();
+        let output = check(&["-o", "json"]);
+        assert_eq!(output.status.code(), Some(1));
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["summary"]["nodes_added"], 1);
+        assert_eq!(json["summary"]["edges_added"], 1);
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 1);
+        assert_eq!(json["nodes"][0]["unique_id"], "model.order_stats");
+
+        // Accepting the change makes the check pass again
+        assert!(check(&["--write-baseline"]).status.success());
+        assert!(check(&[]).status.success());
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let code = |args: &[&str]| {
+            Command::new(binary_path())
+                .args(args)
+                .output()
+                .expect("Failed to run binary")
+                .status
+                .code()
+        };
+
+        // 0: ran with nothing to report
+        assert_eq!(code(&["-p", project, "-o", "json"]), Some(0));
+        assert_eq!(
+            code(&["impact", "weekly_report", "-p", project, "--fail-on", "low"]),
+            Some(0)
+        );
+        // 1: findings the caller asked about
+        assert_eq!(
+            code(&["impact", "customers", "-p", project, "--fail-on", "high"]),
+            Some(1)
+        );
+        assert_eq!(
+            code(&["path", "orders", "stg_orders", "-p", project]),
+            Some(1)
+        );
+        // 2: usage errors, from clap and from our own argument checks
+        assert_eq!(code(&["--no-such-flag"]), Some(2));
+        assert_eq!(code(&["-p", project, "--edge-types", "bogus"]), Some(2));
+        assert_eq!(code(&["impact", "nope", "-p", project]), Some(2));
+        assert_eq!(
+            code(&["-p", project, "--manifest", "/no/such/manifest.json"]),
+            Some(2)
+        );
+        // 3: the project itself could not be processed
+        let dir = empty_project();
+        assert_eq!(code(&["-p", dir.path().to_str().unwrap()]), Some(3));
+    }
+
+    #[test]
+    fn test_max_label_len() {
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let output = Command::new(binary_path())
+            .args(["-p", project, "-o", "mermaid", "--max-label-len", "6"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("[\"stg_o…\"]"), "{}", stdout);
+
+        let stderr = run_failing(&["-p", project, "--max-label-len", "0"]);
+        assert!(stderr.contains("--max-label-len must be at least 1"));
+    }
+
+    #[test]
+    fn test_max_layer_width() {
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let svg_width = |extra: &[&str]| {
+            let output = Command::new(binary_path())
+                .args(["-p", project, "-o", "svg"])
+                .args(extra)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let start = stdout.find("width=\"").unwrap() + "width=\"".len();
+            let end = start + stdout[start..].find('"').unwrap();
+            stdout[start..end].parse::<f64>().unwrap()
+        };
+        // Wrapping every layer to one node spreads the graph sideways
+        assert!(svg_width(&["--max-layer-width", "1"]) > svg_width(&[]));
+
+        let stderr = run_failing(&["-p", project, "--max-layer-width", "0"]);
+        assert!(stderr.contains("--max-layer-width must be at least 1"));
+    }
+
+    #[test]
+    fn test_palette() {
+        let fixture = super::fixture_dir();
+        let project = fixture.to_str().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let palette = dir.path().join("palette.yml");
+        std::fs::write(&palette, "nodes:\n  model: \"#0B3D91\"\n").unwrap();
+
+        let output = Command::new(binary_path())
+            .args(["-p", project, "-o", "dot", "--palette"])
+            .arg(&palette)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("fillcolor=\"#0B3D91\""));
+        assert!(!stdout.contains("#4A90D9"));
+
+        // A bad color is a usage error reported before anything is rendered
+        std::fs::write(&palette, "nodes:\n  model: navy\n").unwrap();
+        let output = Command::new(binary_path())
+            .args(["-p", project, "-o", "dot", "--palette"])
+            .arg(&palette)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("invalid color 'navy'"));
+    }
+
+    #[test]
+    fn test_diff_exit_code() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(models.join("stg_orders.sql"), "select 1").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+
+        let project = dir.path().to_str().unwrap();
+        let diff = |extra: &[&str]| {
+            let mut args = vec!["diff", "--base", "HEAD", "-p", project];
+            args.extend_from_slice(extra);
+            Command::new(binary_path())
+                .args(&args)
+                .output()
+                .unwrap()
+                .status
+                .code()
+        };
+        assert_eq!(diff(&["--exit-code"]), Some(0));
+
+        std::fs::write(
+            models.join("orders.sql"),
+            "select * from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        assert_eq!(diff(&["--exit-code"]), Some(1));
+        // Without the flag changes are only reported
+        assert_eq!(diff(&[]), Some(0));
+
+        let output = Command::new(binary_path())
+            .args([
+                "diff",
+                "--base",
+                "HEAD",
+                "-p",
+                project,
+                "-o",
+                "json",
+                "--group-by",
+                "folder",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["group_by"], "folder");
+        assert_eq!(json["groups"][0]["group"], "models");
+        assert_eq!(json["groups"][0]["added"], 1);
+
+        let output = Command::new(binary_path())
+            .args([
+                "diff",
+                "--base",
+                "HEAD",
+                "-p",
+                project,
+                "-o",
+                "json",
+                "--with-impact",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["nodes"][0]["label"], "orders");
+        assert_eq!(json["nodes"][0]["impact"]["downstream"], 0);
+        assert_eq!(diff(&["--with-impact", "--fast"]), Some(2));
+
+        // Diagrams draw the new model with its unchanged parent as context
+        let output = Command::new(binary_path())
+            .args(["diff", "--base", "HEAD", "-p", project, "-o", "dot"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("\"model.orders\" [label=\"orders\", fillcolor=\"#2ECC71\""));
+        assert!(
+            stdout.contains("\"model.stg_orders\" [label=\"stg_orders\", fillcolor=\"#ECF0F1\"")
+        );
+        assert!(stdout.contains("label=\"Changes\";"));
+        assert_eq!(diff(&["-o", "svg", "--fast"]), Some(2));
+    }
+
+    #[test]
+    fn test_diff_columns() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(models.join("stg_orders.sql"), "select 1 as id").unwrap();
+        std::fs::write(
+            models.join("orders.sql"),
+            "select id, customer_id from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        std::fs::write(
+            models.join("customers.sql"),
+            "select customer_id from {{ ref('orders') }}",
+        )
+        .unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "init"]);
+
+        // Drop a column that customers still selects
+        std::fs::write(
+            models.join("orders.sql"),
+            "select id from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+
+        let project = dir.path().to_str().unwrap();
+        let output = Command::new(binary_path())
+            .args([
+                "diff",
+                "--base",
+                "HEAD",
+                "-p",
+                project,
+                "-o",
+                "json",
+                "--columns",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let columns = json["columns"].as_array().unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0]["node"], "model.orders");
+        assert_eq!(columns[0]["column"], "customer_id");
+        assert_eq!(columns[0]["status"], "removed");
+        assert_eq!(columns[0]["selected_by"][0]["node"], "model.customers");
+
+        let output = Command::new(binary_path())
+            .args(["diff", "--base", "HEAD", "-p", project, "--columns"])
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Column Changes:"));
+        assert!(stdout.contains("still selected by model.customers.customer_id"));
+
+        // Without the flag there is no column section
+        let output = Command::new(binary_path())
+            .args(["diff", "--base", "HEAD", "-p", project, "-o", "json"])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert!(json.get("columns").is_none());
+        let stderr = run_failing(&[
+            "diff",
+            "--base",
+            "HEAD",
+            "-p",
+            project,
+            "--columns",
+            "--fast",
+        ]);
+        assert!(stderr.contains("--fast"));
+    }
+
+    #[test]
+    fn test_model_glob() {
+        let dir = empty_project();
+        let nested = dir.path().join("transform/core/marts");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.path().join("transform/core/stg_orders.sql"), "select 1").unwrap();
+        std::fs::write(
+            nested.join("orders.sql"),
+            "select * from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        // Nothing under the configured model-paths
+        let stderr = run_failing(&["-p", project]);
+        assert!(stderr.contains("no models found under"), "{}", stderr);
+
+        let output = Command::new(binary_path())
+            .args([
+                "-p",
+                project,
+                "-o",
+                "json",
+                "--model-glob",
+                "transform/**/*.sql",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut ids: Vec<&str> = json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["unique_id"].as_str().unwrap())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["model.orders", "model.stg_orders"]);
+        assert_eq!(json["edges"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_seed_file_types() {
+        let dir = empty_project();
+        std::fs::create_dir_all(dir.path().join("models")).unwrap();
+        std::fs::create_dir_all(dir.path().join("seeds")).unwrap();
+        std::fs::write(dir.path().join("seeds/rates.json"), "[]").unwrap();
+        std::fs::write(
+            dir.path().join("models/orders.sql"),
+            "select * from {{ ref('rates') }}",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+        let seed_ids = |extra: &[&str]| -> Vec<String> {
+            let output = Command::new(binary_path())
+                .args(["-p", project, "--include", "seed", "-o", "json"])
+                .args(extra)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+            json["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|n| n["node_type"] == "seed")
+                .map(|n| n["unique_id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        assert!(seed_ids(&[]).is_empty());
+        assert_eq!(
+            seed_ids(&["--seed-file-types", "csv,json"]),
+            vec!["seed.rates"]
+        );
+    }
+
+    #[test]
+    fn test_changed_since_requires_git() {
+        let dir = empty_project();
+        std::fs::create_dir_all(dir.path().join("models")).unwrap();
+        std::fs::write(dir.path().join("models/orders.sql"), "select 1").unwrap();
+        let stderr = run_failing(&[
+            "--project-dir",
+            dir.path().to_str().unwrap(),
+            "--changed-since",
+            "2024-01-01",
+        ]);
+        assert!(stderr.contains("needs a git repository"), "{}", stderr);
+    }
+
+    #[test]
+    fn test_changed_since_keeps_recently_committed_models() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        let git = |args: &[&str], date: Option<&str>| {
+            let mut cmd = Command::new("git");
+            cmd.args(args).current_dir(dir.path());
+            if let Some(date) = date {
+                cmd.env("GIT_AUTHOR_DATE", date)
+                    .env("GIT_COMMITTER_DATE", date);
+            }
+            assert!(cmd.output().unwrap().status.success(), "git {:?}", args);
+        };
+        git(&["init"], None);
+        git(&["config", "user.email", "test@test.com"], None);
+        git(&["config", "user.name", "Test"], None);
+        std::fs::write(models.join("stg_orders.sql"), "select 1").unwrap();
+        std::fs::write(
+            models.join("orders.sql"),
+            "select * from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        git(&["add", "."], None);
+        git(&["commit", "-m", "old"], Some("2001-01-01T00:00:00"));
+        std::fs::write(
+            models.join("orders.sql"),
+            "select id from {{ ref('stg_orders') }}",
+        )
+        .unwrap();
+        git(&["commit", "-am", "new"], None);
+        let project = dir.path().to_str().unwrap();
+
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                project,
+                "--changed-since",
+                "2010-01-01",
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let ids: Vec<&str> = graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["unique_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["model.orders"]);
+
+        let output = Command::new(binary_path())
+            .args([
+                "--project-dir",
+                project,
+                "--changed-since",
+                "2010-01-01",
+                "--changed-neighbors",
+                "1",
+                "-o",
+                "json",
+            ])
+            .output()
+            .expect("Failed to run binary");
+        let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(graph["nodes"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_groups_file_clusters_dot_and_mermaid() {
+        let dir = empty_project();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(models.join("staging")).unwrap();
+        std::fs::write(models.join("staging/stg_stripe__charges.sql"), "select 1").unwrap();
+        std::fs::write(
+            models.join("staging/stg_orders.sql"),
+            "select * from {{ ref('stg_stripe__charges') }}",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join(".dbt-lineage-groups.yml"),
+            "groups:\n  - name: Finance\n    models: ['stg_stripe__*']\n",
+        )
+        .unwrap();
+        let project = dir.path().to_str().unwrap();
+
+        let output = Command::new(binary_path())
+            .args(["--project-dir", project, "-o", "dot"])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("label=\"Finance\""), "{}", stdout);
+        assert!(stdout.contains("label=\"models/staging\""), "{}", stdout);
+
+        let output = Command::new(binary_path())
+            .args(["--project-dir", project, "-o", "mermaid"])
+            .output()
+            .expect("Failed to run binary");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("[\"Finance\"]"), "{}", stdout);
+    }
 }
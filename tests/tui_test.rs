@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use dbt_lineage::graph::types::*;
 use dbt_lineage::parser::artifacts::RunStatusMap;
+use dbt_lineage::render::LabelDetail;
 use dbt_lineage::tui::app::{App, AppMode, DbtRunState, DragState, NodeListEntry};
 use dbt_lineage::tui::graph_widget::{hit_test_node, GraphWidget};
 use dbt_lineage::tui::ui::draw_ui;
@@ -21,10 +22,7 @@ fn build_two_node_graph() -> LineageGraph {
         label: "stg_orders".into(),
         node_type: NodeType::Model,
         file_path: Some(PathBuf::from("models/staging/stg_orders.sql")),
-        description: None,
-        materialization: None,
-        tags: vec![],
-        columns: vec![],
+        ..Default::default()
     });
     let b = graph.add_node(NodeData {
         unique_id: "model.proj.orders".into(),
@@ -32,15 +30,14 @@ fn build_two_node_graph() -> LineageGraph {
         node_type: NodeType::Model,
         file_path: Some(PathBuf::from("models/marts/orders.sql")),
         description: Some("Final orders model".into()),
-        materialization: None,
-        tags: vec![],
-        columns: vec![],
+        ..Default::default()
     });
     graph.add_edge(
         a,
         b,
         EdgeData {
             edge_type: EdgeType::Ref,
+            snippet: None,
         },
     );
     graph
@@ -55,19 +52,14 @@ fn build_four_node_graph() -> LineageGraph {
         node_type: NodeType::Source,
         file_path: Some(PathBuf::from("models/schema.yml")),
         description: Some("Raw orders source".into()),
-        materialization: None,
-        tags: vec![],
-        columns: vec![],
+        ..Default::default()
     });
     let stg = graph.add_node(NodeData {
         unique_id: "model.stg_orders".into(),
         label: "stg_orders".into(),
         node_type: NodeType::Model,
         file_path: Some(PathBuf::from("models/staging/stg_orders.sql")),
-        description: None,
-        materialization: None,
-        tags: vec![],
-        columns: vec![],
+        ..Default::default()
     });
     let mart = graph.add_node(NodeData {
         unique_id: "model.orders".into(),
@@ -75,25 +67,21 @@ fn build_four_node_graph() -> LineageGraph {
         node_type: NodeType::Model,
         file_path: Some(PathBuf::from("models/marts/orders.sql")),
         description: Some("Final orders mart".into()),
-        materialization: None,
-        tags: vec![],
-        columns: vec![],
+        ..Default::default()
     });
     let exp = graph.add_node(NodeData {
         unique_id: "exposure.dashboard".into(),
         label: "dashboard".into(),
         node_type: NodeType::Exposure,
-        file_path: None,
         description: Some("Analytics dashboard".into()),
-        materialization: None,
-        tags: vec![],
-        columns: vec![],
+        ..Default::default()
     });
     graph.add_edge(
         src,
         stg,
         EdgeData {
             edge_type: EdgeType::Source,
+            snippet: None,
         },
     );
     graph.add_edge(
@@ -101,6 +89,7 @@ fn build_four_node_graph() -> LineageGraph {
         mart,
         EdgeData {
             edge_type: EdgeType::Ref,
+            snippet: None,
         },
     );
     graph.add_edge(
@@ -108,6 +97,7 @@ fn build_four_node_graph() -> LineageGraph {
         exp,
         EdgeData {
             edge_type: EdgeType::Exposure,
+            snippet: None,
         },
     );
     graph
@@ -191,6 +181,34 @@ fn test_graph_widget_renders_node_boxes() {
     assertion.to_contain_text("orders").unwrap();
 }
 
+#[test]
+fn test_graph_widget_label_detail() {
+    let mut graph = build_two_node_graph();
+    let stg = graph
+        .node_indices()
+        .find(|&i| graph[i].label == "stg_orders")
+        .unwrap();
+    graph[stg].materialization = Some("view".into());
+    let mut app = make_app(graph);
+    app.label_detail = LabelDetail::Materialization;
+    let frame = render_graph_to_frame(&mut app, 80, 24);
+
+    let mut assertion = expect_frame(&frame);
+    assertion.to_contain_text("stg_orders (view)").unwrap();
+}
+
+#[test]
+fn test_graph_widget_max_label_len() {
+    let graph = build_two_node_graph();
+    let mut app = make_app(graph);
+    app.max_label_len = Some(5);
+    let frame = render_graph_to_frame(&mut app, 80, 24);
+
+    let mut assertion = expect_frame(&frame);
+    assertion.to_contain_text("stg_…").unwrap();
+    assertion.not_to_contain_text("stg_orders").unwrap();
+}
+
 #[test]
 fn test_graph_widget_renders_edges() {
     let graph = build_two_node_graph();
@@ -245,6 +263,10 @@ fn test_full_ui_normal_mode() {
     assertion.to_contain_text("Details").unwrap();
     // Help bar should be visible
     assertion.to_contain_text("hjkl").unwrap();
+    // Graph totals under it
+    assertion
+        .to_contain_text("2 nodes (2 models) | 1 edge | 0 filtered out")
+        .unwrap();
 }
 
 #[test]
@@ -285,6 +307,19 @@ fn test_full_ui_with_node_list() {
     assertion.to_contain_text("Nodes").unwrap();
 }
 
+#[test]
+fn test_full_ui_node_list_type_ahead() {
+    let graph = build_four_node_graph();
+    let mut app = make_app(graph);
+    app.focus_node_list();
+    app.type_ahead('o', std::time::Instant::now());
+
+    let frame = render_full_ui(&mut app, 160, 30);
+    let mut assertion = expect_frame(&frame);
+    assertion.to_contain_text("Jump to: o_").unwrap();
+    assertion.to_contain_text("Nodes").unwrap();
+}
+
 #[test]
 fn test_full_ui_search_mode() {
     let graph = build_two_node_graph();
@@ -330,6 +365,28 @@ fn test_full_ui_run_confirm() {
     assertion.to_contain_text("Cancel").unwrap();
 }
 
+#[test]
+fn test_full_ui_command_preview() {
+    let graph = build_two_node_graph();
+    let mut app = make_app(graph);
+    app.mode = AppMode::CommandPreview;
+    app.pending_run = Some(dbt_lineage::tui::runner::DbtRunRequest {
+        command: dbt_lineage::tui::runner::DbtCommand::Run,
+        scope: dbt_lineage::tui::runner::SelectionScope::FullLineage,
+        model_name: "orders".into(),
+        project_dir: PathBuf::from("/tmp/jaffle shop"),
+        use_uv: false,
+    });
+
+    let frame = render_full_ui(&mut app, 120, 30);
+    let mut assertion = expect_frame(&frame);
+    assertion.to_contain_text("Command (not run)").unwrap();
+    assertion
+        .to_contain_text("$ dbt run --select +orders+ --project-dir '/tmp/jaffle shop'")
+        .unwrap();
+    assertion.to_contain_text("y: copy command").unwrap();
+}
+
 #[test]
 fn test_full_ui_run_output_running() {
     let graph = build_two_node_graph();
@@ -338,12 +395,14 @@ fn test_full_ui_run_output_running() {
     app.run_state = DbtRunState::Running {
         receiver: rx,
         output_lines: vec!["Running dbt...".into()],
+        cancel: Default::default(),
     };
     app.mode = AppMode::RunOutput;
 
     let frame = render_full_ui(&mut app, 120, 30);
     let mut assertion = expect_frame(&frame);
     assertion.to_contain_text("running").unwrap();
+    assertion.to_contain_text("x to cancel").unwrap();
 }
 
 #[test]
@@ -376,6 +435,62 @@ fn test_full_ui_run_output_failure() {
     assertion.to_contain_text("failed").unwrap();
 }
 
+#[test]
+fn test_full_ui_sql_view() {
+    let graph = build_two_node_graph();
+    let mut app = make_app(graph);
+    app.sql_view = Some(dbt_lineage::tui::app::SqlView {
+        title: "models/orders.sql".into(),
+        lines: vec!["select *".into(), "from {{ ref('stg_orders') }}".into()],
+    });
+    app.mode = AppMode::SqlView;
+
+    let frame = render_full_ui(&mut app, 120, 30);
+    let mut assertion = expect_frame(&frame);
+    assertion.to_contain_text("models/orders.sql").unwrap();
+    let mut assertion = expect_frame(&frame);
+    assertion
+        .to_contain_text("2 │ from {{ ref('stg_orders') }}")
+        .unwrap();
+}
+
+#[test]
+fn test_full_ui_phantom_list() {
+    let mut graph = build_two_node_graph();
+    let orders = graph
+        .node_indices()
+        .find(|&i| graph[i].label == "orders")
+        .unwrap();
+    let ghost = graph.add_node(NodeData {
+        unique_id: "model.proj.stg_ordres".into(),
+        label: "stg_ordres".into(),
+        node_type: NodeType::Phantom,
+        ..Default::default()
+    });
+    graph.add_edge(
+        ghost,
+        orders,
+        EdgeData {
+            edge_type: EdgeType::Ref,
+            snippet: None,
+        },
+    );
+    let mut app = make_app(graph);
+    app.open_phantom_list();
+
+    let frame = render_full_ui(&mut app, 140, 30);
+    let mut assertion = expect_frame(&frame);
+    assertion.to_contain_text("Phantom nodes (1)").unwrap();
+    let mut assertion = expect_frame(&frame);
+    assertion
+        .to_contain_text("stg_ordres (ref) ← models/marts/orders.sql")
+        .unwrap();
+    let mut assertion = expect_frame(&frame);
+    assertion
+        .to_contain_text("did you mean stg_orders?")
+        .unwrap();
+}
+
 #[test]
 fn test_full_ui_context_menu() {
     let graph = build_two_node_graph();
@@ -421,6 +536,7 @@ fn test_full_ui_detail_shows_upstream_downstream() {
     let mut assertion = expect_frame(&frame);
     assertion.to_contain_text("Upstream:").unwrap();
     assertion.to_contain_text("Downstream:").unwrap();
+    assertion.to_contain_text("Ultimate sources: 1").unwrap();
 }
 
 // ───────────────────────────────────────────────────────────
@@ -452,6 +568,64 @@ fn test_hit_test_node_misses_empty_space() {
     assert!(result.is_none(), "Expected no hit at (79,23)");
 }
 
+#[test]
+fn test_click_selects_node_at_zoom() {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+    use dbt_lineage::tui::event::handle_mouse_event;
+    use dbt_lineage::tui::graph_widget::node_world_center;
+
+    let graph = build_four_node_graph();
+    let mut app = make_app(graph);
+    app.zoom = 1.5;
+    app.viewport_x = -3;
+    app.viewport_y = -2;
+    render_full_ui(&mut app, 260, 40);
+    let area = app.last_graph_area.unwrap();
+
+    let click = |app: &mut App, column: u16, row: u16| {
+        handle_mouse_event(
+            app,
+            MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: crossterm::event::KeyModifiers::NONE,
+            },
+        );
+        app.drag_state = None;
+    };
+    let screen_center = |app: &App, idx| {
+        let (layer, pos) = app.layout.positions[&idx];
+        let (cx, cy) = node_world_center(layer, pos, app.zoom);
+        (
+            (cx - app.viewport_x + area.x as i32) as u16,
+            (cy - app.viewport_y + area.y as i32) as u16,
+        )
+    };
+
+    let nodes: Vec<_> = app.graph.node_indices().collect();
+    for &idx in &nodes {
+        let (column, row) = screen_center(&app, idx);
+        click(&mut app, column, row);
+        assert_eq!(
+            app.selected_node,
+            Some(idx),
+            "click at the center of {} missed",
+            app.graph[idx].label
+        );
+    }
+
+    // A node hidden by the filters can't be clicked where it used to be drawn
+    let source = nodes
+        .iter()
+        .copied()
+        .find(|&idx| app.graph[idx].node_type == NodeType::Source)
+        .unwrap();
+    let (column, row) = screen_center(&app, source);
+    app.toggle_filter_node_type(NodeType::Source);
+    assert_eq!(hit_test_node(&app, column, row), None);
+}
+
 #[test]
 fn test_select_node_no_center() {
     let graph = build_two_node_graph();
@@ -592,3 +766,42 @@ fn test_context_menu_dismissed_by_click() {
     assert_eq!(app.mode, AppMode::Normal);
     assert!(app.context_menu_pos.is_none());
 }
+
+#[test]
+fn test_full_ui_sla_breach_in_detail_panel() {
+    use dbt_lineage::parser::artifacts::RunStatus;
+
+    let mut graph = build_two_node_graph();
+    let orders = graph
+        .node_indices()
+        .find(|&i| graph[i].label == "orders")
+        .unwrap();
+    graph[orders]
+        .meta
+        .insert("sla_hours".into(), serde_json::json!(6));
+    let mut app = make_app(graph);
+    app.run_status.insert(
+        "model.proj.orders".into(),
+        RunStatus::Success {
+            completed_at: chrono::Utc::now() - chrono::Duration::hours(9),
+        },
+    );
+    app.selected_node = Some(orders);
+    assert!(app.node_sla(orders).unwrap().breached());
+
+    let frame = render_full_ui(&mut app, 160, 40);
+    expect_frame(&frame)
+        .to_contain_text("SLA: 6h, breached (last success 9h ago)")
+        .unwrap();
+
+    // Nodes without an SLA get no SLA line
+    let stg = app
+        .graph
+        .node_indices()
+        .find(|&i| app.graph[i].label == "stg_orders")
+        .unwrap();
+    assert!(app.node_sla(stg).is_none());
+    app.selected_node = Some(stg);
+    let frame = render_full_ui(&mut app, 160, 40);
+    expect_frame(&frame).not_to_contain_text("SLA:").unwrap();
+}